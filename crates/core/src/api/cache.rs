@@ -0,0 +1,45 @@
+use http::HeaderValue;
+
+use crate::val::{Duration, Value};
+
+/// The `Cache-Control` policy which is applied to an API response.
+#[derive(Clone, Debug)]
+pub struct CachePolicy {
+	/// How long the response can be cached for.
+	pub max_age: Duration,
+	/// Whether the response can only be cached by the client.
+	pub private: bool,
+}
+
+impl CachePolicy {
+	/// Returns the value of the `Cache-Control` header for this policy.
+	pub fn header(&self) -> HeaderValue {
+		let visibility = if self.private {
+			"private"
+		} else {
+			"public"
+		};
+		let value = format!("{visibility}, max-age={}", self.max_age.as_secs());
+		// The value only ever contains visible ASCII characters
+		HeaderValue::from_str(&value).unwrap_or_else(|_| HeaderValue::from_static("no-cache"))
+	}
+}
+
+/// Computes a strong `ETag` for the body of a response.
+pub fn etag(body: &Value) -> HeaderValue {
+	let hash = blake3::hash(body.to_string().as_bytes());
+	let value = format!("\"{}\"", &hash.to_hex()[..32]);
+	// The value only ever contains quotes and hexadecimal characters
+	HeaderValue::from_str(&value).unwrap_or_else(|_| HeaderValue::from_static("\"\""))
+}
+
+/// Checks whether an `If-None-Match` header matches an `ETag`.
+pub fn matches(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+	let Ok(value) = if_none_match.to_str() else {
+		return false;
+	};
+	let Ok(etag) = etag.to_str() else {
+		return false;
+	};
+	value.split(',').map(str::trim).any(|x| x == "*" || x.trim_start_matches("W/") == etag)
+}
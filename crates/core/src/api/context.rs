@@ -1,13 +1,26 @@
 use http::HeaderMap;
 
-use crate::expr::Bytesize;
+use super::cache::CachePolicy;
+use super::cors::CorsPolicy;
+use super::limit::RateLimit;
+use crate::expr::{Bytesize, Kind};
+use crate::iam::Role;
 use crate::val::Duration;
 
 #[derive(Default, Debug)]
 pub struct InvocationContext {
 	pub request_body_max: Option<Bytesize>,
 	pub request_body_raw: bool,
+	pub request_body_kind: Option<Kind>,
+	pub request_body_validate: bool,
 	pub response_body_raw: bool,
 	pub response_headers: Option<HeaderMap>,
+	pub response_cache: Option<CachePolicy>,
+	pub response_etag: bool,
+	pub response_compress: Option<bool>,
+	pub required_access: Option<Vec<String>>,
+	pub required_roles: Option<Vec<Role>>,
+	pub cors: Option<CorsPolicy>,
+	pub rate_limit: Option<RateLimit>,
 	pub timeout: Option<Duration>,
 }
@@ -0,0 +1,210 @@
+use anyhow::Result;
+use http::header::{
+	ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+	ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE,
+	ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN, VARY,
+};
+use http::{HeaderMap, HeaderValue};
+
+use crate::err::Error;
+use crate::val::{Duration, Object, Value};
+
+/// The CORS policy which is applied to an API endpoint.
+#[derive(Clone, Debug, Default)]
+pub struct CorsPolicy {
+	/// The origins which are allowed, or `None` to allow any origin.
+	pub origins: Option<Vec<String>>,
+	/// The methods which are allowed, or `None` to allow the requested method.
+	pub methods: Option<Vec<String>>,
+	/// The headers which are allowed, or `None` to allow the requested headers.
+	pub headers: Option<Vec<String>>,
+	/// The response headers which are exposed to the client.
+	pub expose: Vec<String>,
+	/// Whether credentials are allowed to be sent with the request, which
+	/// is only permitted for an explicit list of origins.
+	pub credentials: bool,
+	/// How long the result of a preflight request can be cached for.
+	pub max_age: Option<Duration>,
+}
+
+impl CorsPolicy {
+	/// The policy which is applied when no policy has been configured.
+	pub fn permissive() -> Self {
+		Self {
+			max_age: Some(Duration::from_secs(86400)),
+			..Default::default()
+		}
+	}
+
+	/// Returns the value of the allowed origin for a request, if the origin
+	/// of the request is allowed.
+	fn allowed_origin(&self, request: &HeaderMap) -> Option<HeaderValue> {
+		let origin = request.get(ORIGIN)?;
+		match &self.origins {
+			// Credentials are never allowed for any origin
+			None if self.credentials => None,
+			None => Some(HeaderValue::from_static("*")),
+			Some(origins) => {
+				let value = origin.to_str().ok()?;
+				origins
+					.iter()
+					.any(|x| (x == "*" && !self.credentials) || x == value)
+					.then(|| origin.clone())
+			}
+		}
+	}
+
+	/// Applies the policy to the headers of a response.
+	pub fn apply(&self, request: &HeaderMap, response: &mut HeaderMap) {
+		if self.origins.is_some() || self.credentials {
+			response.append(VARY, HeaderValue::from_static("Origin"));
+		}
+		let Some(origin) = self.allowed_origin(request) else {
+			return;
+		};
+		response.insert(ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+		if self.credentials {
+			response.insert(ACCESS_CONTROL_ALLOW_CREDENTIALS, HeaderValue::from_static("true"));
+		}
+		let expose = if self.expose.is_empty() {
+			None
+		} else {
+			HeaderValue::from_str(&self.expose.join(", ")).ok()
+		};
+		if let Some(v) = expose {
+			response.insert(ACCESS_CONTROL_EXPOSE_HEADERS, v);
+		}
+	}
+
+	/// Computes the headers of a response to a preflight request.
+	pub fn preflight(&self, request: &HeaderMap) -> HeaderMap {
+		let mut response = HeaderMap::new();
+		self.apply(request, &mut response);
+		if !response.contains_key(ACCESS_CONTROL_ALLOW_ORIGIN) {
+			return response;
+		}
+		let methods = match &self.methods {
+			Some(v) => HeaderValue::from_str(&v.join(", ")).ok(),
+			None => request.get(ACCESS_CONTROL_REQUEST_METHOD).cloned(),
+		};
+		if let Some(v) = methods {
+			response.insert(ACCESS_CONTROL_ALLOW_METHODS, v);
+		}
+		let headers = match &self.headers {
+			Some(v) => HeaderValue::from_str(&v.join(", ")).ok(),
+			None => request.get(ACCESS_CONTROL_REQUEST_HEADERS).cloned(),
+		};
+		if let Some(v) = headers {
+			response.insert(ACCESS_CONTROL_ALLOW_HEADERS, v);
+		}
+		if let Some(v) = self.max_age {
+			response.insert(ACCESS_CONTROL_MAX_AGE, HeaderValue::from(v.as_secs()));
+		}
+		response
+	}
+}
+
+impl TryFrom<Object> for CorsPolicy {
+	type Error = anyhow::Error;
+	fn try_from(obj: Object) -> Result<Self> {
+		let mut policy = CorsPolicy::default();
+		for (key, value) in obj.into_iter() {
+			match key.as_str() {
+				"origins" => {
+					policy.origins = match value {
+						Value::String(v) if v == "*" => None,
+						v => Some(strings("origins", v)?),
+					}
+				}
+				"methods" => policy.methods = Some(strings("methods", value)?),
+				"headers" => policy.headers = Some(strings("headers", value)?),
+				"expose" => policy.expose = strings("expose", value)?,
+				"credentials" => policy.credentials = value.coerce_to::<bool>()?,
+				"max_age" => policy.max_age = Some(value.coerce_to::<Duration>()?),
+				key => {
+					return Err(invalid(format!("Unexpected CORS option `{key}`")));
+				}
+			}
+		}
+		// Credentials require an explicit list of origins
+		if policy.credentials {
+			let explicit = policy.origins.as_ref().is_some_and(|v| !v.iter().any(|x| x == "*"));
+			if !explicit {
+				return Err(invalid(
+					"The CORS option `credentials` requires an explicit list of `origins`"
+						.to_string(),
+				));
+			}
+		}
+		Ok(policy)
+	}
+}
+
+/// Converts a string, or an array of strings, into a list of strings.
+fn strings(option: &str, value: Value) -> Result<Vec<String>> {
+	match value {
+		Value::String(v) => Ok(vec![v]),
+		Value::Array(v) => {
+			v.into_iter().map(|x| x.coerce_to::<String>().map_err(anyhow::Error::from)).collect()
+		}
+		_ => Err(invalid(format!(
+			"Expected the CORS option `{option}` to be a string or an array of strings"
+		))),
+	}
+}
+
+fn invalid(message: String) -> anyhow::Error {
+	Error::InvalidArguments {
+		name: "api::cors".to_string(),
+		message,
+	}
+	.into()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::syn;
+
+	fn policy(value: &str) -> Result<CorsPolicy> {
+		match syn::value(value).unwrap() {
+			Value::Object(v) => CorsPolicy::try_from(v),
+			_ => unreachable!(),
+		}
+	}
+
+	fn origin(policy: &CorsPolicy, origin: &'static str) -> Option<HeaderValue> {
+		let mut request = HeaderMap::new();
+		request.insert(ORIGIN, HeaderValue::from_static(origin));
+		let mut response = HeaderMap::new();
+		policy.apply(&request, &mut response);
+		response.get(ACCESS_CONTROL_ALLOW_ORIGIN).cloned()
+	}
+
+	#[test]
+	fn credentials_require_explicit_origins() {
+		policy("{ credentials: true }").unwrap_err();
+		policy("{ credentials: true, origins: '*' }").unwrap_err();
+		policy("{ credentials: true, origins: ['https://a.com', '*'] }").unwrap_err();
+		let res = policy("{ credentials: true, origins: ['https://a.com'] }").unwrap();
+		assert_eq!(origin(&res, "https://a.com").unwrap(), "https://a.com");
+		assert_eq!(origin(&res, "https://b.com"), None);
+	}
+
+	#[test]
+	fn wildcard_is_never_reflected_with_credentials() {
+		let res = CorsPolicy {
+			origins: Some(vec!["*".to_string()]),
+			credentials: true,
+			..Default::default()
+		};
+		assert_eq!(origin(&res, "https://a.com"), None);
+		let res = CorsPolicy {
+			credentials: true,
+			..Default::default()
+		};
+		assert_eq!(origin(&res, "https://a.com"), None);
+		let res = policy("{ origins: '*' }").unwrap();
+		assert_eq!(origin(&res, "https://a.com").unwrap(), "*");
+	}
+}
@@ -14,6 +14,9 @@ pub enum ApiError {
 	#[error("Failed to decode the request body")]
 	BodyDecodeFailure,
 
+	#[error("Invalid request body: {0}")]
+	RequestBodyMismatch(String),
+
 	#[error("Failed to encode the response body")]
 	BodyEncodeFailure,
 
@@ -26,6 +29,12 @@ pub enum ApiError {
 	#[error("Missing Accept or Content-Type header")]
 	MissingFormat,
 
+	#[error("Authentication is required to access this endpoint")]
+	Unauthorized,
+
+	#[error("Not allowed to access this endpoint")]
+	Forbidden,
+
 	#[error("An unreachable error occured: {0}")]
 	Unreachable(String),
 }
@@ -36,10 +45,13 @@ impl ApiError {
 			Self::InvalidRequestBody => StatusCode::BAD_REQUEST,
 			Self::RequestBodyTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
 			Self::BodyDecodeFailure => StatusCode::BAD_REQUEST,
+			Self::RequestBodyMismatch(_) => StatusCode::BAD_REQUEST,
 			Self::BodyEncodeFailure => StatusCode::INTERNAL_SERVER_ERROR,
 			Self::InvalidApiResponse(_) => StatusCode::INTERNAL_SERVER_ERROR,
 			Self::InvalidFormat => StatusCode::BAD_REQUEST,
 			Self::MissingFormat => StatusCode::BAD_REQUEST,
+			Self::Unauthorized => StatusCode::UNAUTHORIZED,
+			Self::Forbidden => StatusCode::FORBIDDEN,
 			Self::Unreachable(_) => StatusCode::INTERNAL_SERVER_ERROR,
 		}
 	}
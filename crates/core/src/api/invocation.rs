@@ -1,19 +1,25 @@
 use std::collections::BTreeMap;
 
-use anyhow::Result;
-use http::HeaderMap;
+use anyhow::{Result, bail, ensure};
+use http::header::{CACHE_CONTROL, ETAG, IF_NONE_MATCH, RETRY_AFTER};
+use http::{HeaderMap, HeaderValue, StatusCode};
 use reblessive::TreeStack;
 use reblessive::tree::Stk;
 
 use super::body::ApiBody;
 use super::context::InvocationContext;
-use super::convert;
+use super::cors::CorsPolicy;
+use super::err::ApiError;
 use super::middleware::invoke;
-use super::response::{ApiResponse, ResponseInstruction};
+use super::response::{ApiResponse, ApiStream, ResponseInstruction};
+use super::{cache, convert};
+use crate::buc::BucketController;
+use crate::buc::store::ObjectKey;
 use crate::catalog::providers::DatabaseProvider;
-use crate::catalog::{ApiDefinition, ApiMethod};
+use crate::catalog::{ApiActionDefinition, ApiDefinition, ApiMethod};
 use crate::ctx::{Context, MutableContext};
 use crate::dbs::Options;
+use crate::err::Error;
 use crate::expr::FlowResultExt as _;
 use crate::val::{Object, Value};
 
@@ -68,26 +74,37 @@ impl ApiInvocation {
 			return Ok(None);
 		}
 
-		let mut inv_ctx = InvocationContext::default();
+		let inv_ctx = Self::middleware(ctx, opt, api, method_action).await?;
 
-		// first run the middleware which is globally configured for the database.
-		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
-		let global = ctx.tx().get_db_config(ns, db, "api").await?;
-		if let Some(config) = global.as_ref().map(|v| v.try_as_api()).transpose()? {
-			for m in config.middleware.iter() {
-				invoke::invoke(&mut inv_ctx, &m.name, m.args.clone())?;
-			}
-		}
-
-		// run the middleware for the api definition.
-		for m in api.config.middleware.iter() {
-			invoke::invoke(&mut inv_ctx, &m.name, m.args.clone())?;
-		}
+		// Check the authentication requirements
+		Self::authorize(ctx, opt, &inv_ctx)?;
 
-		// run the middleware for the http method.
-		if let Some(method_action) = method_action {
-			for m in method_action.config.middleware.iter() {
-				invoke::invoke(&mut inv_ctx, &m.name, m.args.clone())?;
+		// Check the rate limit for this client
+		if let Some(limit) = &inv_ctx.rate_limit {
+			let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+			let client = if opt.auth.is_anon() {
+				match ctx.value("session") {
+					Some(Value::Object(v)) => {
+						v.get("ip").map(|x| x.to_string()).unwrap_or_default()
+					}
+					_ => String::new(),
+				}
+			} else {
+				format!("{}:{}", opt.auth.level(), opt.auth.id())
+			};
+			let key = format!("{ns}:{db}:{}:{}:{client}", api.path, self.method);
+			if let Some(retry) = limit.check(key) {
+				let mut res = ApiResponse {
+					raw: None,
+					status: StatusCode::TOO_MANY_REQUESTS,
+					body: None,
+					headers: HeaderMap::new(),
+					compress: None,
+					stream: None,
+				};
+				res.headers.insert(RETRY_AFTER, HeaderValue::from(retry.as_secs().max(1)));
+				Self::apply_cors(&inv_ctx, &self.headers, &mut res.headers);
+				return Ok(Some((res, ResponseInstruction::Raw)));
 			}
 		}
 
@@ -102,6 +119,23 @@ impl ApiInvocation {
 
 		let body = body.process(&inv_ctx, &self).await?;
 
+		// Validate the request body against the expected type
+		let kind = match &inv_ctx.request_body_kind {
+			Some(kind) => Some(kind),
+			None if inv_ctx.request_body_validate => method_action.and_then(|x| x.request.as_ref()),
+			None => None,
+		};
+		let body = match kind {
+			Some(kind) => body
+				.coerce_to_kind(kind)
+				.map_err(|e| Error::ApiError(ApiError::RequestBodyMismatch(e.to_string())))?,
+			None => body,
+		};
+
+		// Keep the request details which are needed for the response
+		let method = self.method;
+		let headers = self.headers.clone();
+
 		// Edit the options
 		let opt = opt.new_with_perms(false);
 
@@ -131,12 +165,143 @@ impl ApiInvocation {
 		let res = stk.run(|stk| action.compute(stk, &ctx, &opt, None)).await.catch_return()?;
 
 		let mut res = ApiResponse::from_action_result(res)?;
-		if let Some(headers) = inv_ctx.response_headers {
+		if let Some(headers) = inv_ctx.response_headers.clone() {
 			let mut headers = headers;
 			headers.extend(res.headers);
 			res.headers = headers;
 		}
 
+		// Open a file which is streamed from a bucket
+		let native = matches!(res_instruction, ResponseInstruction::Native);
+		let file = res.stream.take_if(|x| !native && matches!(x, ApiStream::File(_)));
+		if let Some(ApiStream::File(file)) = file {
+			let mut controller = BucketController::new(stk, &ctx, &opt, None, &file.bucket).await?;
+			match controller.stream(&ObjectKey::new(file.key)).await? {
+				Some(stream) => res.stream = Some(ApiStream::Bytes(stream)),
+				None => res.status = StatusCode::NOT_FOUND,
+			}
+		}
+
+		// Apply the caching headers
+		if let Some(policy) = &inv_ctx.response_cache {
+			res.headers.entry(CACHE_CONTROL).or_insert_with(|| policy.header());
+		}
+		let etag = match &res.body {
+			Some(body) if inv_ctx.response_etag && method == ApiMethod::Get => {
+				(res.status == StatusCode::OK).then(|| cache::etag(body))
+			}
+			_ => None,
+		};
+		if let Some(etag) = etag {
+			if headers.get(IF_NONE_MATCH).is_some_and(|x| cache::matches(x, &etag)) {
+				res.status = StatusCode::NOT_MODIFIED;
+				res.body = None;
+			}
+			res.headers.insert(ETAG, etag);
+		}
+
+		res.compress = inv_ctx.response_compress;
+		Self::apply_cors(&inv_ctx, &headers, &mut res.headers);
+
 		Ok(Some((res, res_instruction)))
 	}
+
+	/// Responds to a CORS preflight request for the API.
+	pub async fn preflight(
+		self,
+		ctx: &Context,
+		opt: &Options,
+		api: &ApiDefinition,
+	) -> Result<Option<ApiResponse>> {
+		let method_action = api.actions.iter().find(|x| x.methods.contains(&self.method));
+
+		if method_action.is_none() && api.fallback.is_none() {
+			return Ok(None);
+		}
+
+		let inv_ctx = Self::middleware(ctx, opt, api, method_action).await?;
+		let policy = inv_ctx.cors.unwrap_or_else(CorsPolicy::permissive);
+
+		Ok(Some(ApiResponse {
+			raw: None,
+			status: StatusCode::NO_CONTENT,
+			body: None,
+			headers: policy.preflight(&self.headers),
+			compress: None,
+			stream: None,
+		}))
+	}
+
+	/// Runs the middleware which applies to an action, in order of the
+	/// database, the API, and the action itself.
+	async fn middleware(
+		ctx: &Context,
+		opt: &Options,
+		api: &ApiDefinition,
+		method_action: Option<&ApiActionDefinition>,
+	) -> Result<InvocationContext> {
+		let mut inv_ctx = InvocationContext::default();
+
+		// first run the middleware which is globally configured for the database.
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let global = ctx.tx().get_db_config(ns, db, "api").await?;
+		if let Some(config) = global.as_ref().map(|v| v.try_as_api()).transpose()? {
+			for m in config.middleware.iter() {
+				invoke::invoke(&mut inv_ctx, &m.name, m.args.clone())?;
+			}
+		}
+
+		// run the middleware for the api definition.
+		for m in api.config.middleware.iter() {
+			invoke::invoke(&mut inv_ctx, &m.name, m.args.clone())?;
+		}
+
+		// run the middleware for the http method.
+		if let Some(method_action) = method_action {
+			for m in method_action.config.middleware.iter() {
+				invoke::invoke(&mut inv_ctx, &m.name, m.args.clone())?;
+			}
+		}
+
+		Ok(inv_ctx)
+	}
+
+	/// Checks the access method and roles required by the middleware.
+	fn authorize(ctx: &Context, opt: &Options, inv_ctx: &InvocationContext) -> Result<()> {
+		if inv_ctx.required_access.is_none() && inv_ctx.required_roles.is_none() {
+			return Ok(());
+		}
+
+		if opt.auth.is_anon() {
+			bail!(Error::ApiError(ApiError::Unauthorized));
+		}
+
+		if let Some(access) = &inv_ctx.required_access {
+			let current = match ctx.value("access") {
+				Some(Value::String(v)) => Some(v.as_str()),
+				_ => None,
+			};
+			ensure!(
+				current.is_some_and(|x| access.iter().any(|a| a == x)),
+				Error::ApiError(ApiError::Forbidden)
+			);
+		}
+
+		if let Some(roles) = &inv_ctx.required_roles {
+			ensure!(
+				roles.iter().any(|x| opt.auth.has_role(*x)),
+				Error::ApiError(ApiError::Forbidden)
+			);
+		}
+
+		Ok(())
+	}
+
+	/// Applies the CORS policy to the headers of a response.
+	fn apply_cors(inv_ctx: &InvocationContext, request: &HeaderMap, response: &mut HeaderMap) {
+		match &inv_ctx.cors {
+			Some(policy) => policy.apply(request, response),
+			None => CorsPolicy::permissive().apply(request, response),
+		}
+	}
 }
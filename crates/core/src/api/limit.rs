@@ -0,0 +1,107 @@
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use dashmap::DashMap;
+use trice::Instant;
+
+/// The number of tracked clients above which expired windows are pruned.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// The rate limit windows of all clients, across all API endpoints.
+static WINDOWS: LazyLock<DashMap<String, Window>> = LazyLock::new(DashMap::new);
+
+/// The number of tracked clients at which expired windows are next pruned.
+static PRUNE_AT: AtomicUsize = AtomicUsize::new(PRUNE_THRESHOLD);
+
+/// A fixed window of requests made by a single client.
+#[derive(Debug)]
+struct Window {
+	/// When this window started.
+	started: Instant,
+	/// The length of this window, as configured on the endpoint.
+	window: Duration,
+	/// The number of requests made in this window.
+	count: u64,
+}
+
+impl Window {
+	/// Checks whether this window has ended.
+	fn expired(&self) -> bool {
+		self.started.elapsed() >= self.window
+	}
+}
+
+/// The rate limit which is applied to an API endpoint.
+#[derive(Clone, Debug)]
+pub struct RateLimit {
+	/// The number of requests which are allowed in each window.
+	pub limit: u64,
+	/// The length of each window.
+	pub window: Duration,
+}
+
+impl RateLimit {
+	/// Records a request for the specified key. Returns the amount of time
+	/// to wait before retrying when the request exceeds the limit.
+	pub fn check(&self, key: String) -> Option<Duration> {
+		prune();
+		let mut entry = WINDOWS.entry(key).or_insert_with(|| Window {
+			started: Instant::now(),
+			window: self.window,
+			count: 0,
+		});
+		entry.window = self.window;
+		if entry.expired() {
+			entry.started = Instant::now();
+			entry.count = 0;
+		}
+		if entry.count >= self.limit {
+			return Some(self.window.saturating_sub(entry.started.elapsed()));
+		}
+		entry.count += 1;
+		None
+	}
+}
+
+/// Removes the expired windows of all endpoints once the number of tracked
+/// clients reaches the pruning threshold. The threshold is then raised to
+/// twice the number of remaining windows, so that the cost of pruning is
+/// amortised across requests.
+fn prune() {
+	let at = PRUNE_AT.load(Ordering::Relaxed);
+	if WINDOWS.len() < at {
+		return;
+	}
+	// Ensure that only one request prunes the windows
+	if PRUNE_AT.compare_exchange(at, usize::MAX, Ordering::AcqRel, Ordering::Relaxed).is_err() {
+		return;
+	}
+	WINDOWS.retain(|_, w| !w.expired());
+	PRUNE_AT.store((WINDOWS.len() * 2).max(PRUNE_THRESHOLD), Ordering::Release);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn windows_expire_independently() {
+		let short = RateLimit {
+			limit: 1,
+			window: Duration::ZERO,
+		};
+		let long = RateLimit {
+			limit: 1,
+			window: Duration::from_secs(3600),
+		};
+		assert!(short.check("windows_expire_independently:a".to_string()).is_none());
+		assert!(long.check("windows_expire_independently:b".to_string()).is_none());
+		// Each window is pruned according to its own length
+		PRUNE_AT.store(0, Ordering::Release);
+		prune();
+		assert!(!WINDOWS.contains_key("windows_expire_independently:a"));
+		assert!(WINDOWS.contains_key("windows_expire_independently:b"));
+		assert!(long.check("windows_expire_independently:b".to_string()).is_some());
+	}
+}
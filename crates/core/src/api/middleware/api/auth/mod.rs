@@ -0,0 +1,16 @@
+use anyhow::Result;
+
+use crate::api::context::InvocationContext;
+use crate::fnc::args::Rest;
+use crate::iam::Role;
+
+pub fn access(context: &mut InvocationContext, Rest(access): Rest<String>) -> Result<()> {
+	context.required_access = Some(access);
+	Ok(())
+}
+
+pub fn roles(context: &mut InvocationContext, Rest(roles): Rest<String>) -> Result<()> {
+	let roles = roles.iter().map(|x| x.parse()).collect::<Result<Vec<Role>, _>>()?;
+	context.required_roles = Some(roles);
+	Ok(())
+}
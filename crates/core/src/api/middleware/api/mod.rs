@@ -1,12 +1,40 @@
+pub mod auth;
 pub mod req;
 pub mod res;
 
 use anyhow::Result;
 
 use crate::api::context::InvocationContext;
-use crate::val::Duration;
+use crate::api::cors::CorsPolicy;
+use crate::api::limit::RateLimit;
+use crate::err::Error;
+use crate::fnc::args::Optional;
+use crate::val::{Duration, Object};
 
 pub fn timeout(context: &mut InvocationContext, (timeout,): (Duration,)) -> Result<()> {
 	context.timeout = Some(timeout);
 	Ok(())
 }
+
+pub fn cors(
+	context: &mut InvocationContext,
+	(Optional(policy),): (Optional<Object>,),
+) -> Result<()> {
+	context.cors = Some(match policy {
+		Some(policy) => policy.try_into()?,
+		None => CorsPolicy::permissive(),
+	});
+	Ok(())
+}
+
+pub fn rate_limit(context: &mut InvocationContext, (limit, window): (i64, Duration)) -> Result<()> {
+	let limit = u64::try_from(limit).map_err(|_| Error::InvalidArguments {
+		name: "api::rate_limit".to_string(),
+		message: "Argument 1 was the wrong type, expected a positive integer".to_string(),
+	})?;
+	context.rate_limit = Some(RateLimit {
+		limit,
+		window: window.0,
+	});
+	Ok(())
+}
@@ -3,6 +3,7 @@ use anyhow::Result;
 use crate::api::context::InvocationContext;
 use crate::err::Error;
 use crate::fnc::args::Optional;
+use crate::syn;
 
 pub fn max_body(context: &mut InvocationContext, (max_size,): (String,)) -> Result<()> {
 	let bytesize = max_size.parse().map_err(|_| Error::InvalidArguments {
@@ -20,3 +21,12 @@ pub fn raw_body(
 	context.request_body_raw = raw.unwrap_or(true);
 	Ok(())
 }
+
+pub fn validate(
+	context: &mut InvocationContext,
+	(Optional(kind),): (Optional<String>,),
+) -> Result<()> {
+	context.request_body_kind = kind.map(|x| syn::kind(&x).map(Into::into)).transpose()?;
+	context.request_body_validate = true;
+	Ok(())
+}
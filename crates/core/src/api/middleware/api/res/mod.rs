@@ -1,9 +1,10 @@
 use anyhow::Result;
 use http::{HeaderMap, HeaderName, HeaderValue};
 
+use crate::api::cache::CachePolicy;
 use crate::api::context::InvocationContext;
 use crate::fnc::args::Optional;
-use crate::val::{Object, Value};
+use crate::val::{Duration, Object, Value};
 
 pub fn raw_body(
 	context: &mut InvocationContext,
@@ -64,3 +65,27 @@ pub fn headers(context: &mut InvocationContext, (headers,): (Object,)) -> Result
 
 	Ok(())
 }
+
+pub fn cache(
+	context: &mut InvocationContext,
+	(max_age, Optional(private)): (Duration, Optional<bool>),
+) -> Result<()> {
+	context.response_cache = Some(CachePolicy {
+		max_age,
+		private: private.unwrap_or(false),
+	});
+	Ok(())
+}
+
+pub fn etag(context: &mut InvocationContext, (Optional(etag),): (Optional<bool>,)) -> Result<()> {
+	context.response_etag = etag.unwrap_or(true);
+	Ok(())
+}
+
+pub fn compress(
+	context: &mut InvocationContext,
+	(Optional(compress),): (Optional<bool>,),
+) -> Result<()> {
+	context.response_compress = Some(compress.unwrap_or(true));
+	Ok(())
+}
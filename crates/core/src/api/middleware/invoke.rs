@@ -31,13 +31,22 @@ pub fn invoke(context: &mut InvocationContext, name: &str, args: Vec<Value>) ->
 		args,
 		context,
 		//
+		"api::auth::access" => api::auth::access,
+		"api::auth::roles" => api::auth::roles,
+		//
 		"api::req::max_body" => api::req::max_body,
 		"api::req::raw_body" => api::req::raw_body,
+		"api::req::validate" => api::req::validate,
 		//
+		"api::res::cache" => api::res::cache,
+		"api::res::compress" => api::res::compress,
+		"api::res::etag" => api::res::etag,
 		"api::res::raw_body" => api::res::raw_body,
 		"api::res::headers" => api::res::headers,
 		"api::res::header" => api::res::header,
 		//
+		"api::cors" => api::cors,
+		"api::rate_limit" => api::rate_limit,
 		"api::timeout" => api::timeout,
 	)
 }
@@ -1,8 +1,12 @@
 pub mod body;
+pub mod cache;
 pub mod context;
 pub mod convert;
+pub mod cors;
 pub mod err;
 pub mod invocation;
+pub mod limit;
 pub mod middleware;
+pub mod openapi;
 pub mod path;
 pub mod response;
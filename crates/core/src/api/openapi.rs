@@ -0,0 +1,398 @@
+//! Generation of OpenAPI documents for `DEFINE API` endpoints.
+//!
+//! The document describes every API defined on a database. Path parameters
+//! are typed from the kinds of their segments, request and response bodies are
+//! typed from the `REQUEST` and `RESPONSE` clauses of each action, and every
+//! operation documents whether it requires authentication.
+
+use super::path::Segment;
+use crate::catalog::{ApiActionDefinition, ApiDefinition, ApiMethod, Permission};
+use crate::expr::{Kind, KindLiteral};
+use crate::val::{Array, Object, Value};
+
+/// The version of the OpenAPI specification which is generated.
+pub const OPENAPI_VERSION: &str = "3.1.0";
+
+/// The content types which an API body can be encoded with.
+const CONTENT_TYPES: [&str; 3] = ["application/json", "application/cbor", "application/surrealdb"];
+
+/// All methods which an API can respond to.
+const METHODS: [ApiMethod; 6] = [
+	ApiMethod::Delete,
+	ApiMethod::Get,
+	ApiMethod::Patch,
+	ApiMethod::Post,
+	ApiMethod::Put,
+	ApiMethod::Trace,
+];
+
+/// Generates an OpenAPI document for the APIs defined on a database.
+pub fn document(ns: &str, db: &str, apis: &[ApiDefinition]) -> Value {
+	let mut paths = Object::default();
+	for api in apis {
+		let item = path_item(api);
+		if !item.is_empty() {
+			paths.insert(path_template(api), Value::from(item));
+		}
+	}
+
+	Value::from(map! {
+		"openapi".to_string() => Value::from(OPENAPI_VERSION),
+		"info".to_string() => Value::from(map! {
+			"title".to_string() => Value::from(format!("{ns}/{db}")),
+			"version".to_string() => Value::from(env!("CARGO_PKG_VERSION")),
+		}),
+		"servers".to_string() => Value::from(vec![Value::from(map! {
+			"url".to_string() => Value::from(format!("/api/{ns}/{db}")),
+		})]),
+		"paths".to_string() => Value::from(paths),
+		"components".to_string() => Value::from(map! {
+			"securitySchemes".to_string() => Value::from(map! {
+				"bearer".to_string() => Value::from(map! {
+					"type".to_string() => Value::from("http"),
+					"scheme".to_string() => Value::from("bearer"),
+					"bearerFormat".to_string() => Value::from("JWT"),
+				}),
+				"basic".to_string() => Value::from(map! {
+					"type".to_string() => Value::from("http"),
+					"scheme".to_string() => Value::from("basic"),
+				}),
+			}),
+		}),
+	})
+}
+
+/// Converts the path of an API into an OpenAPI path template.
+fn path_template(api: &ApiDefinition) -> String {
+	let mut out = String::new();
+	for segment in api.path.iter() {
+		out.push('/');
+		match segment {
+			Segment::Fixed(x) => out.push_str(x),
+			Segment::Dynamic(x, _) | Segment::Rest(x) => {
+				out.push('{');
+				out.push_str(x);
+				out.push('}');
+			}
+		}
+	}
+	if out.is_empty() {
+		out.push('/');
+	}
+	out
+}
+
+/// Describes the parameters of the path of an API.
+fn parameters(api: &ApiDefinition) -> Value {
+	api.path
+		.iter()
+		.filter_map(|segment| match segment {
+			Segment::Fixed(_) => None,
+			Segment::Dynamic(name, kind) => Some(Value::from(map! {
+				"name".to_string() => Value::from(name.as_str()),
+				"in".to_string() => Value::from("path"),
+				"required".to_string() => Value::Bool(true),
+				"schema".to_string() => kind.as_ref().map(schema).unwrap_or_else(|| string(None)),
+			})),
+			Segment::Rest(name) => Some(Value::from(map! {
+				"name".to_string() => Value::from(name.as_str()),
+				"in".to_string() => Value::from("path"),
+				"required".to_string() => Value::Bool(true),
+				"description".to_string() => Value::from("Matches all remaining path segments"),
+				"schema".to_string() => string(None),
+			})),
+		})
+		.collect()
+}
+
+/// Describes all operations of an API, keyed by their method.
+fn path_item(api: &ApiDefinition) -> Object {
+	let mut item = Object::default();
+	for method in METHODS {
+		let action = api.actions.iter().find(|x| x.methods.contains(&method));
+		if action.is_none() && api.fallback.is_none() {
+			continue;
+		}
+		item.insert(method.to_string(), operation(api, action));
+	}
+	if !item.is_empty() {
+		let params = parameters(api);
+		if !matches!(&params, Value::Array(x) if x.is_empty()) {
+			item.insert("parameters".to_string(), params);
+		}
+	}
+	item
+}
+
+/// Describes a single operation of an API. When no action is passed, the
+/// operation is handled by the fallback of the API.
+fn operation(api: &ApiDefinition, action: Option<&ApiActionDefinition>) -> Value {
+	let mut op = Object::default();
+	if let Some(comment) = &api.comment {
+		op.insert("description".to_string(), Value::from(comment.as_str()));
+	}
+	let (request, response) = match action {
+		Some(action) => (action.request.as_ref(), action.response.as_ref()),
+		None => (None, None),
+	};
+	if let Some(kind) = request {
+		op.insert(
+			"requestBody".to_string(),
+			Value::from(map! {
+				"required".to_string() => Value::Bool(!kind.can_be_none()),
+				"content".to_string() => content(kind),
+			}),
+		);
+	}
+	let mut success = map! {
+		"description".to_string() => Value::from("Successful response"),
+	};
+	if let Some(kind) = response {
+		success.insert("content".to_string(), content(kind));
+	}
+	let mut responses = map! {
+		"200".to_string() => Value::from(success),
+	};
+	// The permissions of both the API and the action must be satisfied
+	let permissions = [Some(&api.config.permissions), action.map(|x| &x.config.permissions)];
+	let permissions = permissions.into_iter().flatten();
+	if permissions.clone().any(|x| matches!(x, Permission::None)) {
+		op.insert("x-surrealdb-permissions".to_string(), Value::Bool(false));
+		responses.insert(
+			"403".to_string(),
+			Value::from(map! {
+				"description".to_string() => Value::from("Access to this endpoint is not permitted"),
+			}),
+		);
+	} else if permissions.clone().any(|x| matches!(x, Permission::Specific(_))) {
+		op.insert(
+			"security".to_string(),
+			Value::from(vec![
+				Value::from(map! { "bearer".to_string() => Value::from(Array::new()) }),
+				Value::from(map! { "basic".to_string() => Value::from(Array::new()) }),
+			]),
+		);
+		responses.insert(
+			"403".to_string(),
+			Value::from(map! {
+				"description".to_string() => Value::from("The authenticated user is not permitted to access this endpoint"),
+			}),
+		);
+	} else {
+		op.insert("security".to_string(), Value::from(Array::new()));
+	}
+	op.insert("responses".to_string(), Value::from(responses));
+	Value::from(op)
+}
+
+/// Describes a body with the supported content types.
+fn content(kind: &Kind) -> Value {
+	let schema = schema(kind);
+	Value::from(
+		CONTENT_TYPES
+			.iter()
+			.map(|x| {
+				(
+					(*x).to_string(),
+					Value::from(map! {
+						"schema".to_string() => schema.clone(),
+					}),
+				)
+			})
+			.collect::<Object>(),
+	)
+}
+
+/// Creates a schema for a string with an optional format.
+fn string(format: Option<&str>) -> Value {
+	Value::from(map! {
+		"type".to_string() => Value::from("string"),
+		"format".to_string(), if let Some(format) = format => Value::from(format),
+	})
+}
+
+/// Creates a schema for a single JSON type.
+fn typed(ty: &str) -> Value {
+	Value::from(map! {
+		"type".to_string() => Value::from(ty),
+	})
+}
+
+/// Converts a kind into a JSON schema.
+pub fn schema(kind: &Kind) -> Value {
+	match kind {
+		Kind::Any | Kind::Function(_, _) => Value::from(Object::default()),
+		Kind::None | Kind::Null => typed("null"),
+		Kind::Bool => typed("boolean"),
+		Kind::Bytes => string(Some("byte")),
+		Kind::Datetime => string(Some("date-time")),
+		Kind::Decimal | Kind::Number => typed("number"),
+		Kind::Duration => string(Some("duration")),
+		Kind::Float => Value::from(map! {
+			"type".to_string() => Value::from("number"),
+			"format".to_string() => Value::from("double"),
+		}),
+		Kind::Int => Value::from(map! {
+			"type".to_string() => Value::from("integer"),
+			"format".to_string() => Value::from("int64"),
+		}),
+		Kind::Object => typed("object"),
+		Kind::String | Kind::Range => string(None),
+		Kind::Uuid => string(Some("uuid")),
+		Kind::Regex => string(Some("regex")),
+		Kind::File(_) => string(None),
+		Kind::Record(tables) => Value::from(map! {
+			"type".to_string() => Value::from("string"),
+			"x-surrealdb-tables".to_string(), if !tables.is_empty() => tables.iter().map(|x| Value::from(x.as_str())).collect(),
+		}),
+		Kind::Geometry(types) => Value::from(map! {
+			"type".to_string() => Value::from("object"),
+			"description".to_string() => Value::from("GeoJSON geometry"),
+			"x-surrealdb-geometry".to_string(), if !types.is_empty() => types.iter().map(|x| Value::from(x.to_string())).collect(),
+		}),
+		Kind::Either(kinds) => Value::from(map! {
+			"anyOf".to_string() => kinds.iter().map(schema).collect(),
+		}),
+		Kind::Array(kind, max) => array(kind, *max, false),
+		Kind::Set(kind, max) => array(kind, *max, true),
+		Kind::Literal(literal) => literal_schema(literal),
+	}
+}
+
+/// Creates a schema for an array of items of the given kind.
+fn array(kind: &Kind, max: Option<u64>, unique: bool) -> Value {
+	Value::from(map! {
+		"type".to_string() => Value::from("array"),
+		"items".to_string() => schema(kind),
+		"maxItems".to_string(), if let Some(max) = max => Value::from(max),
+		"uniqueItems".to_string(), if unique => Value::Bool(true),
+	})
+}
+
+/// Converts a literal kind into a JSON schema.
+fn literal_schema(literal: &KindLiteral) -> Value {
+	match literal {
+		KindLiteral::String(x) => constant(Value::from(x.as_str())),
+		KindLiteral::Integer(x) => constant(Value::from(*x)),
+		KindLiteral::Float(x) => constant(Value::from(*x)),
+		KindLiteral::Decimal(x) => constant(Value::from(*x)),
+		KindLiteral::Duration(x) => constant(Value::from(x.to_string())),
+		KindLiteral::Bool(x) => constant(Value::Bool(*x)),
+		KindLiteral::Array(kinds) => Value::from(map! {
+			"type".to_string() => Value::from("array"),
+			"prefixItems".to_string() => kinds.iter().map(schema).collect(),
+			"minItems".to_string() => Value::from(kinds.len()),
+			"maxItems".to_string() => Value::from(kinds.len()),
+		}),
+		KindLiteral::Object(fields) => Value::from(map! {
+			"type".to_string() => Value::from("object"),
+			"properties".to_string() => Value::from(
+				fields.iter().map(|(k, v)| (k.clone(), schema(v))).collect::<Object>(),
+			),
+			"required".to_string() => fields
+				.iter()
+				.filter(|(_, v)| !v.can_be_none())
+				.map(|(k, _)| Value::from(k.as_str()))
+				.collect(),
+			"additionalProperties".to_string() => Value::Bool(false),
+		}),
+	}
+}
+
+/// Creates a schema which only matches a single value.
+fn constant(value: Value) -> Value {
+	Value::from(map! {
+		"const".to_string() => value,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::catalog::ApiConfigDefinition;
+	use crate::expr::{Expr, Literal};
+
+	fn action(methods: Vec<ApiMethod>, permissions: Permission) -> ApiActionDefinition {
+		ApiActionDefinition {
+			methods,
+			action: Expr::Literal(Literal::None),
+			config: ApiConfigDefinition {
+				middleware: Vec::new(),
+				permissions,
+			},
+			request: Some(Kind::Object),
+			response: Some(Kind::Array(Box::new(Kind::Int), Some(10))),
+		}
+	}
+
+	#[test]
+	fn generates_paths_and_operations() {
+		let api = ApiDefinition {
+			path: "/users/:id<int>/*rest".parse().unwrap(),
+			actions: vec![
+				action(vec![ApiMethod::Get], Permission::Full),
+				action(
+					vec![ApiMethod::Post],
+					Permission::Specific(Expr::Literal(Literal::Bool(true))),
+				),
+				action(vec![ApiMethod::Delete], Permission::None),
+			],
+			..Default::default()
+		};
+		let doc = document("ns", "db", &[api]);
+		let Value::Object(doc) = doc else {
+			panic!("expected an object");
+		};
+		assert_eq!(doc.get("openapi"), Some(&Value::from(OPENAPI_VERSION)));
+		let Some(Value::Object(paths)) = doc.get("paths") else {
+			panic!("expected paths");
+		};
+		let Some(Value::Object(item)) = paths.get("/users/{id}/{rest}") else {
+			panic!("expected a templated path");
+		};
+		// Only the defined methods are documented
+		assert!(item.get("get").is_some());
+		assert!(item.get("post").is_some());
+		assert!(item.get("delete").is_some());
+		assert!(item.get("put").is_none());
+		// Path parameters are typed from the segment kinds
+		let Some(Value::Array(params)) = item.get("parameters") else {
+			panic!("expected parameters");
+		};
+		assert_eq!(params.len(), 2);
+		let Value::Object(id) = &params[0] else {
+			panic!("expected a parameter");
+		};
+		assert_eq!(id.get("schema"), Some(&schema(&Kind::Int)));
+		// Authentication requirements are documented per operation
+		let security = |method: &str| match item.get(method) {
+			Some(Value::Object(op)) => op.get("security").cloned(),
+			_ => None,
+		};
+		assert_eq!(security("get"), Some(Value::from(Array::new())));
+		assert!(matches!(security("post"), Some(Value::Array(x)) if x.len() == 2));
+		assert_eq!(security("delete"), None);
+	}
+
+	#[test]
+	fn converts_kinds_to_schemas() {
+		assert_eq!(
+			schema(&Kind::option(Kind::String)),
+			Value::from(map! {
+				"anyOf".to_string() => Value::from(vec![typed("null"), string(None)]),
+			})
+		);
+		assert_eq!(
+			schema(&Kind::Set(Box::new(Kind::Bool), None)),
+			Value::from(map! {
+				"type".to_string() => Value::from("array"),
+				"items".to_string() => typed("boolean"),
+				"uniqueItems".to_string() => Value::Bool(true),
+			})
+		);
+		assert_eq!(
+			schema(&Kind::Literal(KindLiteral::String("a".to_string()))),
+			constant(Value::from("a"))
+		);
+	}
+}
@@ -1,13 +1,26 @@
+use std::pin::Pin;
+use std::{fmt, mem};
+
 use anyhow::Result;
+use futures::{Stream, StreamExt as _, TryStreamExt as _};
 use http::header::{ACCEPT, CONTENT_TYPE};
 use http::{HeaderMap, StatusCode};
 
 use super::convert;
 use super::err::ApiError;
 use super::invocation::ApiInvocation;
+use crate::buc::store::ByteStream;
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::err::Error;
+use crate::key::record;
+use crate::kvs::{KVValue as _, Transaction};
 use crate::rpc::format::Format;
-use crate::val::{Object, Value};
+use crate::val::record::Record;
+use crate::val::{Array, File, Object, RecordId, Table, Value};
+
+/// A stream of values, read in batches.
+pub type ValueStream = Pin<Box<dyn Stream<Item = Result<Value>> + Send>>;
 
 #[derive(Debug)]
 pub struct ApiResponse {
@@ -15,6 +28,117 @@ pub struct ApiResponse {
 	pub status: StatusCode,
 	pub body: Option<Value>,
 	pub headers: HeaderMap,
+	/// Whether the response body should be compressed, when the client
+	/// supports it. `None` leaves the decision to the server.
+	pub compress: Option<bool>,
+	/// A body which is sent to the client as a stream, instead of `body`.
+	pub stream: Option<ApiStream>,
+}
+
+/// A response body which is sent to the client as a stream.
+pub enum ApiStream {
+	/// Each value of an array, or each record of a table, is sent as a
+	/// separate line of JSON.
+	Ndjson(Value),
+	/// The values which are sent as separate lines of JSON, read in batches.
+	Rows(ValueStream),
+	/// A file from a bucket, which has not been opened yet.
+	File(File),
+	/// The contents of a file from a bucket, read in chunks.
+	Bytes(ByteStream),
+	/// Server-sent events, fed by the notifications of a live query on a
+	/// table, which runs while the client is connected.
+	Events(Table),
+}
+
+impl ApiStream {
+	/// Creates a stream from the `stream` and `body` properties of an API
+	/// response.
+	fn from_body(kind: &str, body: Option<Value>) -> Result<Self, Error> {
+		let body = body.unwrap_or_default();
+		match kind {
+			"ndjson" => match body {
+				Value::Table(_) => Ok(Self::Ndjson(body)),
+				body => Ok(Self::Ndjson(Value::Array(body.coerce_to::<Array>()?))),
+			},
+			"file" => Ok(Self::File(body.coerce_to()?)),
+			"events" => match body {
+				Value::Table(tb) => Ok(Self::Events(tb)),
+				body => Ok(Self::Events(Table::new(body.coerce_to()?))),
+			},
+			kind => Err(ApiError::InvalidApiResponse(format!(
+				"Expected a stream of `ndjson`, `file` or `events`, found `{kind}`"
+			))
+			.into()),
+		}
+	}
+
+	/// Converts the stream back into the `body` of an API response, if it
+	/// has not been opened yet.
+	fn into_value(self) -> Option<Value> {
+		match self {
+			Self::Ndjson(v) => Some(v),
+			Self::File(v) => Some(v.into()),
+			Self::Rows(_) | Self::Bytes(_) => None,
+			Self::Events(v) => Some(Value::Table(v)),
+		}
+	}
+
+	/// Reads the records of a table in batches, so that the whole table is
+	/// never held in memory. The records are read from the transaction, which
+	/// is cancelled once the last record has been read.
+	pub(crate) fn rows(
+		txn: Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &str,
+	) -> Result<Self> {
+		let beg = record::prefix(ns, db, tb)?;
+		let end = record::suffix(ns, db, tb)?;
+		let batches =
+			futures::stream::try_unfold((txn, Some(beg..end)), |(txn, next)| async move {
+				let Some(rng) = next else {
+					txn.cancel().await?;
+					return Ok::<_, anyhow::Error>(None);
+				};
+				let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+				let mut rows = Vec::with_capacity(batch.result.len());
+				for (k, v) in batch.result {
+					let k = record::RecordKey::decode_key(&k)?;
+					let mut v = Record::kv_decode_value(v)?;
+					// Inject the id field into the document
+					let rid = RecordId {
+						table: k.tb.into_owned(),
+						key: k.id,
+					};
+					v.data.to_mut().def(&rid);
+					rows.push(Ok(mem::take(v.data.to_mut())));
+				}
+				Ok(Some((futures::stream::iter(rows), (txn, batch.next))))
+			});
+		Ok(Self::Rows(batches.try_flatten().boxed()))
+	}
+
+	/// The name of this stream type.
+	pub fn kind(&self) -> &'static str {
+		match self {
+			Self::Ndjson(_) | Self::Rows(_) => "ndjson",
+			Self::File(_) | Self::Bytes(_) => "file",
+			Self::Events(_) => "events",
+		}
+	}
+}
+
+impl fmt::Debug for ApiStream {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Ndjson(v) => f.debug_tuple("Ndjson").field(v).finish(),
+			Self::Rows(_) => f.debug_tuple("Rows").finish_non_exhaustive(),
+			Self::File(v) => f.debug_tuple("File").field(v).finish(),
+			Self::Bytes(_) => f.debug_tuple("Bytes").finish_non_exhaustive(),
+			Self::Events(v) => f.debug_tuple("Events").field(v).finish(),
+		}
+	}
 }
 
 impl ApiResponse {
@@ -51,6 +175,13 @@ impl ApiResponse {
 
 			let body = opts.remove("body");
 
+			let (body, stream) = match opts.remove("stream") {
+				Some(kind) => {
+					(None, Some(ApiStream::from_body(&kind.coerce_to::<String>()?, body)?))
+				}
+				None => (body, None),
+			};
+
 			if !opts.is_empty() {
 				Err(ApiError::InvalidApiResponse("Contains invalid properties".into()).into())
 			} else {
@@ -59,6 +190,8 @@ impl ApiResponse {
 					status,
 					body,
 					headers,
+					compress: None,
+					stream,
 				})
 			}
 		} else {
@@ -72,6 +205,8 @@ impl ApiResponse {
 			"status".to_owned() => Value::from(self.status.as_u16() as i64),
 			"headers".to_owned() => Value::Object(convert::headermap_to_object(self.headers)?),
 			"body".to_owned(), if let Some(body) = self.body => body,
+			"stream".to_owned(), if let Some(stream) = &self.stream => Value::from(stream.kind()),
+			"body".to_owned(), if let Some(body) = self.stream.and_then(ApiStream::into_value) => body,
 		})))
 	}
 }
@@ -4,7 +4,7 @@ use std::sync::Arc;
 use anyhow::{Result, bail, ensure};
 use reblessive::tree::Stk;
 
-use super::store::{ListOptions, ObjectKey, ObjectMeta, ObjectStore};
+use super::store::{ByteStream, ListOptions, ObjectKey, ObjectMeta, ObjectStore};
 use crate::catalog::providers::BucketProvider;
 use crate::catalog::{BucketDefinition, Permission};
 use crate::ctx::{Context, MutableContext};
@@ -125,6 +125,16 @@ impl<'a> BucketController<'a> {
 		Ok(Some(bytes.to_vec().into()))
 	}
 
+	pub(crate) async fn stream(&mut self, key: &ObjectKey) -> Result<Option<ByteStream>> {
+		self.check_permission(BucketOperation::Get, Some(key), None).await?;
+
+		self.store
+			.stream(key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e.to_string()))
+			.map_err(anyhow::Error::new)
+	}
+
 	pub(crate) async fn delete(&mut self, key: &ObjectKey) -> Result<()> {
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Delete, Some(key), None).await?;
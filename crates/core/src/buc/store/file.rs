@@ -5,14 +5,17 @@ use std::pin::Pin;
 use bytes::Bytes;
 use path_clean::PathClean;
 use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use url::Url;
 
-use super::{ListOptions, ObjectKey, ObjectMeta, ObjectStore};
+use super::{ByteStream, ListOptions, ObjectKey, ObjectMeta, ObjectStore};
 use crate::cnf::BUCKET_FOLDER_ALLOWLIST;
 use crate::err::Error;
 use crate::val::Datetime;
 
+/// The size of the chunks in which files are streamed
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
 /// Options for configuring the FileStore
 #[derive(Clone, Debug)]
 pub struct FileStoreOptions {
@@ -299,6 +302,37 @@ impl ObjectStore for FileStore {
 		})
 	}
 
+	fn stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ByteStream>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let os_path = self.to_os_path(key).await?;
+
+			// Check if the file exists
+			if !Self::path_exists(&os_path).await? {
+				return Ok(None);
+			}
+
+			let file =
+				File::open(&os_path).await.map_err(|e| format!("Failed to open file: {}", e))?;
+
+			// Read the file in chunks, as the stream is consumed
+			let stream = futures::stream::try_unfold(file, |mut file| async move {
+				let mut buf = vec![0; STREAM_CHUNK_SIZE];
+				let n =
+					file.read(&mut buf).await.map_err(|e| format!("Failed to read file: {}", e))?;
+				if n == 0 {
+					return Ok(None);
+				}
+				buf.truncate(n);
+				Ok(Some((Bytes::from(buf), file)))
+			});
+
+			Ok(Some(Box::pin(stream) as ByteStream))
+		})
+	}
+
 	fn head<'a>(
 		&'a self,
 		key: &'a ObjectKey,
@@ -5,7 +5,7 @@ use bytes::Bytes;
 use dashmap::DashMap;
 use url::Url;
 
-use super::{ListOptions, ObjectKey, ObjectMeta, ObjectStore};
+use super::{ByteStream, ListOptions, ObjectKey, ObjectMeta, ObjectStore};
 use crate::val::Datetime;
 
 #[derive(Clone, Debug)]
@@ -80,6 +80,17 @@ impl ObjectStore for MemoryStore {
 		})
 	}
 
+	fn stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ByteStream>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			// The data is already held in memory, so it is sent as a single chunk
+			let data = self.store.get(key).map(|v| v.bytes.clone());
+			Ok(data.map(|v| Box::pin(futures::stream::once(async move { Ok(v) })) as ByteStream))
+		})
+	}
+
 	fn head<'a>(
 		&'a self,
 		key: &'a ObjectKey,
@@ -3,6 +3,7 @@ use std::pin::Pin;
 use std::sync::Arc;
 
 use bytes::Bytes;
+use futures::Stream;
 
 use crate::err::Error;
 use crate::val::{Datetime, File, Object, Value};
@@ -14,6 +15,9 @@ pub(crate) mod prefixed;
 pub(crate) mod util;
 pub(crate) use util::ObjectKey;
 
+/// A stream of the contents of an object, read in chunks.
+pub type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, String>> + Send>>;
+
 pub(crate) struct ObjectMeta {
 	pub size: u64,
 	pub updated: Datetime,
@@ -80,6 +84,11 @@ pub(crate) trait ObjectStore: Send + Sync + 'static {
 		key: &'a ObjectKey,
 	) -> Pin<Box<dyn Future<Output = Result<Option<Bytes>, String>> + Send + 'a>>;
 
+	fn stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ByteStream>, String>> + Send + 'a>>;
+
 	fn head<'a>(
 		&'a self,
 		key: &'a ObjectKey,
@@ -149,6 +158,13 @@ impl ObjectStore for Arc<dyn ObjectStore> {
 		(**self).get(key)
 	}
 
+	fn stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ByteStream>, String>> + Send + 'a>> {
+		(**self).stream(key)
+	}
+
 	fn head<'a>(
 		&'a self,
 		key: &'a ObjectKey,
@@ -3,7 +3,7 @@ use std::pin::Pin;
 
 use bytes::Bytes;
 
-use super::{ListOptions, ObjectKey, ObjectMeta, ObjectStore};
+use super::{ByteStream, ListOptions, ObjectKey, ObjectMeta, ObjectStore};
 
 #[derive(Clone, Debug)]
 pub struct PrefixedStore<T: ObjectStore> {
@@ -50,6 +50,15 @@ impl<T: ObjectStore> ObjectStore for PrefixedStore<T> {
 		Box::pin(async move { self.store.get(&full_key).await })
 	}
 
+	fn stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ByteStream>, String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.stream(&full_key).await })
+	}
+
 	fn head<'a>(
 		&'a self,
 		key: &'a ObjectKey,
@@ -90,7 +90,8 @@ mod test {
         cache_events_ts: Uuid::default(),
         cache_tables_ts: Uuid::default(),
         cache_indexes_ts: Uuid::default(),
-    }, 147)]
+        constraints: Vec::new(),
+    }, 148)]
 	#[case::subscription(SubscriptionDefinition {
         id: Uuid::default(),
         node: Uuid::default(),
@@ -103,7 +104,7 @@ mod test {
         fetch: Some(Fetchs(vec![Fetch(Expr::Literal(Literal::String("fetch".to_string())))])),
         auth: Some(Auth::default()),
         session: Some(Value::default()),
-    }, 97)]
+    }, 101)]
 	#[case::access(AccessDefinition {
         name: "access".to_string(),
         access_type: AccessType::Bearer(BearerAccess {
@@ -152,6 +153,8 @@ mod test {
                 methods: vec![ApiMethod::Get],
                 action: Expr::Literal(Literal::String("action".to_string())),
                 config: ApiConfigDefinition::default(),
+                request: None,
+                response: None,
             },
         ],
         fallback: None,
@@ -165,7 +168,7 @@ mod test {
             permissions: Permission::Full,
         },
         comment: None,
-    }, 44)]
+    }, 46)]
 	#[case::bucket(BucketDefinition {
         id: Some(BucketId(123)),
         readonly: false,
@@ -200,7 +203,8 @@ mod test {
         update_permission: Permission::Full,
         comment: Some("comment".to_string()),
         reference: None,
-    }, 39)]
+        mask: None,
+    }, 40)]
 	#[case::function(FunctionDefinition {
         name: "function".to_string(),
         args: vec![],
@@ -210,7 +214,8 @@ mod test {
         comment: Some("comment".to_string()),
         permissions: Permission::Full,
         returns: Some(Kind::Any),
-    }, 34)]
+        wasm: None,
+    }, 35)]
 	#[case::index(IndexDefinition {
         index_id: IndexId(123),
         name: "test".to_string(),
@@ -226,6 +231,11 @@ mod test {
         comment: Some("comment".to_string()),
         permissions: Permission::Full,
     }, 29)]
+	#[case::module(ModuleDefinition {
+        name: "module".to_string(),
+        source: "export default 1;".to_string(),
+        comment: Some("comment".to_string()),
+    }, 35)]
 	#[case::param(ParamDefinition {
         name: "param".to_string(),
         value: Value::Bool(true),
@@ -248,7 +258,11 @@ mod test {
         token_duration: Some(Duration::from_secs(123)),
         session_duration: Some(Duration::from_secs(123)),
         comment: Some("comment".to_string()),
-    }, 38)]
+        totp: None,
+        policy: None,
+        history: Vec::new(),
+        changed: None,
+    }, 42)]
 	#[case::record(Record::new(Data::from(Value::Bool(true))), 5)]
 	fn test_serialize_deserialize<T>(#[case] original: T, #[case] expected_encoded_size: usize)
 	where
@@ -9,8 +9,8 @@ use uuid::Uuid;
 
 use crate::catalog;
 use crate::catalog::{
-	DatabaseDefinition, DatabaseId, IndexId, NamespaceDefinition, NamespaceId, TableDefinition,
-	UserDefinition,
+	DatabaseDefinition, DatabaseId, IndexId, NamespaceDefinition, NamespaceId, RoleDefinition,
+	TableDefinition, UserDefinition,
 };
 use crate::dbs::node::Node;
 use crate::err::Error;
@@ -152,6 +152,13 @@ pub trait DatabaseProvider: NamespaceProvider {
 		db: DatabaseId,
 	) -> Result<Arc<[catalog::ParamDefinition]>>;
 
+	/// Retrieve all module definitions for a specific database.
+	async fn all_db_modules(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Arc<[catalog::ModuleDefinition]>>;
+
 	/// Retrieve all model definitions for a specific database.
 	async fn all_db_models(
 		&self,
@@ -206,6 +213,22 @@ pub trait DatabaseProvider: NamespaceProvider {
 		fc: &catalog::FunctionDefinition,
 	) -> Result<()>;
 
+	/// Retrieve a specific module definition from a database.
+	async fn get_db_module(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		md: &str,
+	) -> Result<Arc<catalog::ModuleDefinition>>;
+
+	/// Put a module definition into a database.
+	async fn put_db_module(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		md: &catalog::ModuleDefinition,
+	) -> Result<()>;
+
 	/// Retrieve a specific function definition from a database.
 	async fn get_db_param(
 		&self,
@@ -551,6 +574,39 @@ pub trait UserProvider {
 	async fn put_db_user(&self, ns: NamespaceId, db: DatabaseId, us: &UserDefinition)
 	-> Result<()>;
 
+	/// Retrieve all root role definitions.
+	async fn all_root_roles(&self) -> Result<Arc<[RoleDefinition]>>;
+
+	/// Retrieve all namespace role definitions for a specific namespace.
+	async fn all_ns_roles(&self, ns: NamespaceId) -> Result<Arc<[RoleDefinition]>>;
+
+	/// Retrieve all database role definitions for a specific database.
+	async fn all_db_roles(&self, ns: NamespaceId, db: DatabaseId) -> Result<Arc<[RoleDefinition]>>;
+
+	/// Retrieve a specific root role definition.
+	async fn get_root_role(&self, rl: &str) -> Result<Option<Arc<RoleDefinition>>>;
+
+	/// Put a role definition into a root.
+	async fn put_root_role(&self, rl: &RoleDefinition) -> Result<()>;
+
+	/// Retrieve a specific namespace role definition.
+	async fn get_ns_role(&self, ns: NamespaceId, rl: &str) -> Result<Option<Arc<RoleDefinition>>>;
+
+	/// Put a role definition into a namespace.
+	async fn put_ns_role(&self, ns: NamespaceId, rl: &RoleDefinition) -> Result<()>;
+
+	/// Retrieve a specific role definition from a database.
+	async fn get_db_role(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		rl: &str,
+	) -> Result<Option<Arc<RoleDefinition>>>;
+
+	/// Put a role definition into a database.
+	async fn put_db_role(&self, ns: NamespaceId, db: DatabaseId, rl: &RoleDefinition)
+	-> Result<()>;
+
 	/// Retrieve a specific user definition from a root returning an error if it does not exist.
 	async fn expect_root_user(&self, us: &str) -> Result<Arc<UserDefinition>> {
 		match self.get_root_user(us).await? {
@@ -3,8 +3,9 @@ use std::time::Duration;
 
 use revision::revisioned;
 
-use crate::expr::Expr;
 use crate::expr::statements::info::InfoStructure;
+use crate::expr::user::PasswordPolicy;
+use crate::expr::{Expr, Param};
 use crate::kvs::impl_kv_value_revisioned;
 use crate::val::Value;
 
@@ -55,6 +56,9 @@ impl InfoStructure for AccessType {
 				"signup".to_string(), if let Some(v) = v.signup => v.structure(),
 				"signin".to_string(), if let Some(v) = v.signin => v.structure(),
 				"refresh".to_string(), if v.bearer.is_some() => true.into(),
+				"policy".to_string(), if let Some(v) = v.policy => v.structure(),
+				"identity".to_string(), if let Some(v) = v.identity => v.structure(),
+				"password".to_string(), if let Some(v) = v.password => v.to_string().into(),
 			}),
 			AccessType::Bearer(ac) => Value::from(map! {
 					"kind".to_string() => "BEARER".into(),
@@ -68,13 +72,22 @@ impl InfoStructure for AccessType {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Debug, Hash, Clone, Eq, PartialEq)]
 pub struct RecordAccess {
 	pub signup: Option<Expr>,
 	pub signin: Option<Expr>,
 	pub jwt: JwtAccess,
 	pub bearer: Option<BearerAccess>,
+	/// The password policy which is enforced for record users
+	#[revision(start = 2)]
+	pub policy: Option<PasswordPolicy>,
+	/// The identity against which failed signins are tracked
+	#[revision(start = 2)]
+	pub identity: Option<Expr>,
+	/// The signup variable which is checked against the password policy
+	#[revision(start = 2)]
+	pub password: Option<Param>,
 }
 
 #[revisioned(revision = 1)]
@@ -5,8 +5,8 @@ use revision::revisioned;
 
 use crate::api::path::Path;
 use crate::catalog::Permission;
-use crate::expr::Expr;
 use crate::expr::statements::info::InfoStructure;
+use crate::expr::{Expr, Kind};
 use crate::fmt::Fmt;
 use crate::kvs::impl_kv_value_revisioned;
 use crate::sql::{Literal, ToSql};
@@ -142,12 +142,18 @@ impl InfoStructure for ApiMethod {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct ApiActionDefinition {
 	pub methods: Vec<ApiMethod>,
 	pub action: Expr,
 	pub config: ApiConfigDefinition,
+	/// The expected type of the request body, used for documentation.
+	#[revision(start = 2)]
+	pub request: Option<Kind>,
+	/// The expected type of the response body, used for documentation.
+	#[revision(start = 2)]
+	pub response: Option<Kind>,
 }
 
 impl_kv_value_revisioned!(ApiActionDefinition);
@@ -158,6 +164,8 @@ impl ApiActionDefinition {
 			methods: self.methods.clone(),
 			action: self.action.clone().into(),
 			config: self.config.to_sql_config(),
+			request: self.request.clone().map(Into::into),
+			response: self.response.clone().map(Into::into),
 		}
 	}
 }
@@ -168,6 +176,8 @@ impl InfoStructure for ApiActionDefinition {
 			"methods" => Value::from(self.methods.into_iter().map(InfoStructure::structure).collect::<Vec<Value>>()),
 			"action" => Value::from(self.action.to_string()),
 			"config" => self.config.structure(),
+			"request", if let Some(v) = self.request => Value::from(v.to_string()),
+			"response", if let Some(v) = self.response => Value::from(v.to_string()),
 		))
 	}
 }
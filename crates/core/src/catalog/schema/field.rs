@@ -17,7 +17,7 @@ pub enum DefineDefault {
 	Set(Expr),
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub struct FieldDefinition {
 	// TODO: Needs to be it's own type.
@@ -41,6 +41,10 @@ pub struct FieldDefinition {
 
 	pub comment: Option<String>,
 	pub reference: Option<Reference>,
+	/// The value returned in place of the field when the `SELECT` permission
+	/// is not granted.
+	#[revision(start = 2)]
+	pub mask: Option<Expr>,
 }
 impl_kv_value_revisioned!(FieldDefinition);
 
@@ -65,6 +69,7 @@ impl FieldDefinition {
 					crate::sql::statements::define::DefineDefault::Always(x.clone().into())
 				}
 			},
+			mask: self.mask.clone().map(|x| x.into()),
 			permissions: crate::sql::Permissions {
 				select: self.select_permission.to_sql_definition(),
 				create: self.create_permission.to_sql_definition(),
@@ -93,6 +98,7 @@ impl InfoStructure for FieldDefinition {
 			"default_always".to_string(), if matches!(&self.default, DefineDefault::Always(_) | DefineDefault::Set(_)) => Value::Bool(matches!(self.default,DefineDefault::Always(_))), // Only reported if DEFAULT is also enabled for this field
 			"default".to_string(), if let DefineDefault::Always(v) | DefineDefault::Set(v) = self.default => v.structure(),
 			"reference".to_string(), if let Some(v) = self.reference => v.structure(),
+			"mask".to_string(), if let Some(v) = self.mask => v.structure(),
 			"readonly".to_string() => self.readonly.into(),
 			"permissions".to_string() => Value::from(map!{
 				"select".to_string() => self.select_permission.structure(),
@@ -4,11 +4,11 @@ use crate::catalog::Permission;
 use crate::expr::statements::info::InfoStructure;
 use crate::expr::{Block, Kind};
 use crate::kvs::impl_kv_value_revisioned;
-use crate::sql::statements::define::DefineKind;
+use crate::sql::statements::define::{DefineKind, FunctionWasm};
 use crate::sql::{DefineFunctionStatement, ToSql};
-use crate::val::Value;
+use crate::val::{Bytes, Value};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct FunctionDefinition {
 	pub name: String,
@@ -17,6 +17,21 @@ pub struct FunctionDefinition {
 	pub comment: Option<String>,
 	pub permissions: Permission,
 	pub returns: Option<Kind>,
+	/// The WebAssembly export which implements this function, if any
+	#[revision(start = 2)]
+	pub wasm: Option<WasmFunction>,
+}
+
+/// A function which is implemented by an export of a WebAssembly module
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct WasmFunction {
+	/// The compiled module, in either the binary or the text format
+	pub module: Bytes,
+	/// The name of the exported function
+	pub export: String,
+	/// The SHA-256 hash of the module, which identifies the compiled module
+	pub hash: [u8; 32],
 }
 
 impl_kv_value_revisioned!(FunctionDefinition);
@@ -39,6 +54,10 @@ impl FunctionDefinition {
 				.comment
 				.clone()
 				.map(|x| crate::sql::Expr::Literal(crate::sql::Literal::String(x))),
+			wasm: self.wasm.as_ref().map(|x| FunctionWasm {
+				module: crate::sql::Expr::Literal(crate::sql::Literal::Bytes(x.module.clone())),
+				export: x.export.clone(),
+			}),
 		}
 	}
 }
@@ -56,6 +75,9 @@ impl InfoStructure for FunctionDefinition {
 			"permissions".to_string() => self.permissions.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 			"returns".to_string(), if let Some(v) = self.returns => v.structure(),
+			"wasm".to_string(), if let Some(v) = self.wasm => Value::from(map! {
+				"export".to_string() => v.export.into(),
+			}),
 		})
 	}
 }
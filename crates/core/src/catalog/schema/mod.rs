@@ -10,7 +10,9 @@ mod field;
 mod function;
 mod index;
 mod ml;
+mod module;
 mod param;
+mod role;
 mod sequence;
 mod user;
 
@@ -26,7 +28,9 @@ pub use field::*;
 pub use function::*;
 pub use index::*;
 pub use ml::*;
+pub use module::*;
 pub use param::*;
+pub use role::*;
 pub use sequence::*;
 pub use user::*;
 
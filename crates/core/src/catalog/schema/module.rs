@@ -0,0 +1,59 @@
+use revision::revisioned;
+use sha2::{Digest, Sha256};
+
+use crate::expr::statements::info::InfoStructure;
+use crate::kvs::impl_kv_value_revisioned;
+use crate::sql::ToSql;
+use crate::sql::statements::define::{DefineKind, DefineModuleStatement};
+use crate::val::Value;
+
+/// A JavaScript module which can be imported by embedded functions
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ModuleDefinition {
+	pub name: String,
+	/// The source code of the module
+	pub source: String,
+	pub comment: Option<String>,
+}
+impl_kv_value_revisioned!(ModuleDefinition);
+
+impl ModuleDefinition {
+	/// The specifier used to import this module from a script
+	pub fn specifier(&self) -> String {
+		format!("mod::{}", self.name)
+	}
+
+	/// A checksum of the module source, used to key the compiled bytecode
+	pub fn checksum(&self) -> [u8; 32] {
+		Sha256::digest(self.source.as_bytes()).into()
+	}
+
+	pub fn to_sql_definition(&self) -> DefineModuleStatement {
+		DefineModuleStatement {
+			kind: DefineKind::Default,
+			name: self.name.clone(),
+			source: crate::sql::Expr::Literal(crate::sql::Literal::String(self.source.clone())),
+			comment: self
+				.comment
+				.clone()
+				.map(|x| crate::sql::Expr::Literal(crate::sql::Literal::String(x))),
+		}
+	}
+}
+
+impl ToSql for &ModuleDefinition {
+	fn to_sql(&self) -> String {
+		self.to_sql_definition().to_string()
+	}
+}
+
+impl InfoStructure for ModuleDefinition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name".to_string() => self.name.into(),
+			"source".to_string() => self.source.into(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
@@ -0,0 +1,30 @@
+use revision::revisioned;
+
+use crate::expr::statements::info::InfoStructure;
+use crate::iam::RoleGrant;
+use crate::kvs::impl_kv_value_revisioned;
+use crate::val::{Array, Value};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct RoleDefinition {
+	pub name: String,
+	/// The privileges granted to users with this role
+	pub grants: Vec<RoleGrant>,
+	/// The privileges revoked from users with this role
+	pub revokes: Vec<RoleGrant>,
+	pub comment: Option<String>,
+}
+
+impl_kv_value_revisioned!(RoleDefinition);
+
+impl InfoStructure for RoleDefinition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name".to_string() => Value::from(self.name),
+			"grants".to_string() => Array::from(self.grants.iter().map(|g| Value::from(g.to_string())).collect::<Vec<_>>()).into(),
+			"revokes".to_string() => Array::from(self.revokes.iter().map(|g| Value::from(g.to_string())).collect::<Vec<_>>()).into(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
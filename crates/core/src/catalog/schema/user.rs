@@ -4,8 +4,9 @@ use revision::{Revisioned, revisioned};
 use serde::{Deserialize, Serialize};
 
 use crate::expr::statements::info::InfoStructure;
+use crate::expr::user::PasswordPolicy;
 use crate::kvs::impl_kv_value_revisioned;
-use crate::val::{Array, Value};
+use crate::val::{Array, Datetime, Value};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -33,7 +34,7 @@ impl Revisioned for UserId {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct UserDefinition {
 	pub name: String,
@@ -45,6 +46,30 @@ pub struct UserDefinition {
 	/// Duration after which the session authenticated with user credentials or token expires
 	pub session_duration: Option<Duration>,
 	pub comment: Option<String>,
+	/// The TOTP second factor, if the user has enrolled in multi-factor authentication
+	#[revision(start = 2)]
+	pub totp: Option<UserTotp>,
+	/// The password policy which is enforced for the user
+	#[revision(start = 2)]
+	pub policy: Option<PasswordPolicy>,
+	/// The hashes of previous passwords, used to prevent password reuse
+	#[revision(start = 2)]
+	pub history: Vec<String>,
+	/// The time at which the password of the user was last changed
+	#[revision(start = 2)]
+	pub changed: Option<Datetime>,
+}
+
+/// The TOTP second factor which a user has enrolled
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct UserTotp {
+	/// The base32 encoded shared secret
+	pub secret: String,
+	/// The hashes of the recovery codes which have not yet been used
+	pub recovery: Vec<String>,
+	/// The time step of the last code which was accepted, as codes can not be reused
+	pub step: Option<i64>,
 }
 
 impl InfoStructure for UserDefinition {
@@ -58,6 +83,8 @@ impl InfoStructure for UserDefinition {
 				"session".to_string() => self.token_duration.map(Value::from).unwrap_or(Value::None),
 			}),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+			"totp".to_string(), if self.totp.is_some() => Value::Bool(true),
+			"policy".to_string(), if let Some(v) = self.policy => v.structure(),
 		})
 	}
 }
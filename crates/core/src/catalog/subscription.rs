@@ -41,6 +41,8 @@ impl SubscriptionDefinition {
 			what: self.what.clone().into(),
 			cond: self.cond.clone().map(|c| crate::sql::Cond(c.into())),
 			fetch: self.fetch.clone().map(|f| f.into()),
+			snapshot: false,
+			since: None,
 		}
 	}
 }
@@ -3,7 +3,7 @@ use uuid::Uuid;
 
 use crate::catalog::{DatabaseId, NamespaceId, Permissions, ViewDefinition};
 use crate::expr::statements::info::InfoStructure;
-use crate::expr::{ChangeFeed, Kind};
+use crate::expr::{ChangeFeed, Expr, Kind};
 use crate::kvs::impl_kv_value_revisioned;
 use crate::sql::ToSql;
 use crate::sql::statements::DefineTableStatement;
@@ -34,7 +34,7 @@ impl Revisioned for TableId {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct TableDefinition {
 	pub namespace_id: NamespaceId,
@@ -58,6 +58,9 @@ pub struct TableDefinition {
 	pub cache_tables_ts: Uuid,
 	/// The last time that a DEFINE INDEX was added to this table
 	pub cache_indexes_ts: Uuid,
+	/// The conditions which every record in this table must satisfy
+	#[revision(start = 2)]
+	pub constraints: Vec<TableConstraint>,
 }
 
 impl_kv_value_revisioned!(TableDefinition);
@@ -86,6 +89,7 @@ impl TableDefinition {
 			cache_events_ts: now,
 			cache_tables_ts: now,
 			cache_indexes_ts: now,
+			constraints: Vec::new(),
 		}
 	}
 
@@ -117,6 +121,7 @@ impl TableDefinition {
 				.clone()
 				.map(|v| crate::sql::Expr::Literal(crate::sql::Literal::String(v))),
 			table_type: self.table_type.clone().into(),
+			constraints: self.constraints.iter().cloned().map(Into::into).collect(),
 			..Default::default()
 		}
 	}
@@ -139,6 +144,26 @@ impl InfoStructure for TableDefinition {
 			"changefeed".to_string(), if let Some(v) = self.changefeed => v.structure(),
 			"permissions".to_string() => self.permissions.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+			"constraints".to_string(), if !self.constraints.is_empty() =>
+				self.constraints.into_iter().map(InfoStructure::structure).collect::<Vec<_>>().into(),
+		})
+	}
+}
+
+/// A named condition which every record in a table must satisfy
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TableConstraint {
+	pub name: String,
+	/// The condition, evaluated against the full record
+	pub check: Expr,
+}
+
+impl InfoStructure for TableConstraint {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name".to_string() => self.name.into(),
+			"check".to_string() => self.check.structure(),
 		})
 	}
 }
@@ -5,5 +5,5 @@ pub(crate) mod writer;
 
 pub use self::gc::*;
 pub use self::mutations::*;
-pub use self::reader::read;
+pub use self::reader::{read, read_batch};
 pub use self::writer::Writer;
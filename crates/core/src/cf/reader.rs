@@ -7,7 +7,7 @@ use crate::expr::statements::show::ShowSince;
 use crate::key::change;
 #[cfg(debug_assertions)]
 use crate::key::debug::Sprintable;
-use crate::kvs::{KVKey, KVValue, Transaction};
+use crate::kvs::{KVKey, KVValue, Key, Transaction, Val};
 use crate::vs::VersionStamp;
 
 // Reads the change feed for a specific database or a table,
@@ -27,13 +27,83 @@ pub async fn read(
 	limit: Option<u32>,
 ) -> Result<Vec<ChangeSet>> {
 	// Calculate the start of the changefeed range
-	let beg = match start {
-		ShowSince::Versionstamp(x) => change::prefix_ts(ns, db, VersionStamp::from_u64(x)),
+	let beg = change::prefix_ts(ns, db, versionstamp(tx, ns, db, start).await?).encode_key()?;
+	// Calculate the end of the changefeed range
+	let end = change::suffix(ns, db).encode_key()?;
+	// Limit the changefeed results with a default
+	let limit = limit.unwrap_or(100).min(1000);
+	// Fetch and decode the changefeed entries
+	decode(tx.scan(beg..end, limit, None).await?, tb)
+}
+
+/// Reads a batch of the change feed for a specific database or a table,
+/// scanning at most `limit` changefeed entries from the start versionstamp.
+///
+/// Unlike [`read`], the changesets which are returned are always complete,
+/// even when a transaction changed more tables than fit in the batch. The
+/// versionstamp from which the next batch should be read is returned, or
+/// `None` once the end of the changefeed has been reached. This is returned
+/// even if none of the scanned entries were for the specified table.
+pub async fn read_batch(
+	tx: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: Option<&str>,
+	start: ShowSince,
+	limit: u32,
+) -> Result<(Vec<ChangeSet>, Option<u64>)> {
+	// Calculate the start of the changefeed range
+	let beg = change::prefix_ts(ns, db, versionstamp(tx, ns, db, start).await?).encode_key()?;
+	// Calculate the end of the changefeed range
+	let end = change::suffix(ns, db).encode_key()?;
+	// Fetch the changefeed entries
+	let limit = limit.max(1);
+	let mut entries = tx.scan(beg..end, limit, None).await?;
+	// The changefeed has been read to the end
+	let (Some((first, _)), Some((last, _))) = (entries.first(), entries.last()) else {
+		return Ok((Vec::new(), None));
+	};
+	if entries.len() < limit as usize {
+		return Ok((decode(entries, tb)?, None));
+	}
+	// The last versionstamp in the batch may be incomplete
+	let first = change::Cf::decode_key(first)?.vs;
+	let last = change::Cf::decode_key(last)?.vs;
+	let Some(next) = last.next() else {
+		return Ok((decode(entries, tb)?, None));
+	};
+	if first == last {
+		// A single versionstamp fills the batch, so read all of it
+		let beg = change::prefix_ts(ns, db, last).encode_key()?;
+		let end = change::prefix_ts(ns, db, next).encode_key()?;
+		entries = tx.scan(beg..end, u32::MAX, None).await?;
+		return Ok((decode(entries, tb)?, Some(next.into_u64_lossy())));
+	}
+	// Otherwise the last versionstamp is read again by the next batch
+	let mut res = Vec::with_capacity(entries.len());
+	for (k, v) in entries {
+		if change::Cf::decode_key(&k)?.vs == last {
+			break;
+		}
+		res.push((k, v));
+	}
+	Ok((decode(res, tb)?, Some(last.into_u64_lossy())))
+}
+
+/// Resolves the versionstamp from which a changefeed should be read
+async fn versionstamp(
+	tx: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	start: ShowSince,
+) -> Result<VersionStamp> {
+	match start {
+		ShowSince::Versionstamp(x) => Ok(VersionStamp::from_u64(x)),
 		ShowSince::Timestamp(x) => {
 			let ts = x.0.timestamp() as u64;
 			let vs = tx.lock().await.get_versionstamp_from_timestamp(ts, ns, db).await?;
 			match vs {
-				Some(vs) => change::prefix_ts(ns, db, vs),
+				Some(vs) => Ok(vs),
 				None => {
 					bail!(Error::Internal(
 						"No versionstamp found associated to this timestamp. Hint: Try a later datetime or use SINCE 0 to show all changes for a changefeed".to_string(),
@@ -42,11 +112,10 @@ pub async fn read(
 			}
 		}
 	}
-	.encode_key()?;
-	// Calculate the end of the changefeed range
-	let end = change::suffix(ns, db).encode_key()?;
-	// Limit the changefeed results with a default
-	let limit = limit.unwrap_or(100).min(1000);
+}
+
+/// Decodes changefeed entries into changesets, grouped by versionstamp
+fn decode(entries: Vec<(Key, Val)>, tb: Option<&str>) -> Result<Vec<ChangeSet>> {
 	// Create an empty buffer for the versionstamp
 	let mut vs: Option<VersionStamp> = None;
 	// Create an empty buffer for the table mutations
@@ -55,7 +124,7 @@ pub async fn read(
 	let mut res = Vec::<ChangeSet>::new();
 
 	// iterate over _x and put decoded elements to r
-	for (k, v) in tx.scan(beg..end, limit, None).await? {
+	for (k, v) in entries {
 		#[cfg(debug_assertions)]
 		trace!("Reading change feed entry: {}", k.sprint());
 
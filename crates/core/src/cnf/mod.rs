@@ -43,6 +43,21 @@ pub static MAX_QUERY_PARSING_DEPTH: LazyLock<u32> =
 pub static IDIOM_RECURSION_LIMIT: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_IDIOM_RECURSION_LIMIT", usize, 256);
 
+/// The maximum number of edges or records which a graph function holds in
+/// memory (default: 1,000,000)
+pub static GRAPH_FUNCTION_LIMIT: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_GRAPH_FUNCTION_LIMIT", usize, 1_000_000);
+
+/// The maximum number of partially matched rows which a MATCH statement holds
+/// in memory (default: 1,000,000)
+pub static MATCH_ROW_LIMIT: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_MATCH_ROW_LIMIT", usize, 1_000_000);
+
+/// The maximum number of tokens which an embedding model is given for each
+/// input text, including the special tokens (default: 256)
+pub static EMBEDDING_MAX_TOKENS: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_EMBEDDING_MAX_TOKENS", usize, 256);
+
 /// The maximum size of a compiled regular expression (default: 10 MiB)
 pub static REGEX_SIZE_LIMIT: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_REGEX_SIZE_LIMIT", usize, 10 * 1024 * 1024);
@@ -100,6 +115,25 @@ pub static SCRIPTING_MAX_MEMORY_LIMIT: LazyLock<usize> =
 pub static SCRIPTING_MAX_TIME_LIMIT: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_SCRIPTING_MAX_TIME_LIMIT", usize, 5 * 1000);
 
+/// The maximum memory limit of a WebAssembly function instance (default: 16
+/// MiB)
+pub static WASM_MAX_MEMORY_LIMIT: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_WASM_MAX_MEMORY_LIMIT", usize, 16 << 20);
+
+/// The maximum amount of time that a WebAssembly function can run (default: 5
+/// seconds)
+pub static WASM_MAX_TIME_LIMIT: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_WASM_MAX_TIME_LIMIT", usize, 5 * 1000);
+
+/// The maximum amount of fuel which a WebAssembly function can consume, where
+/// roughly one unit of fuel is consumed per instruction (default: 1000000000)
+pub static WASM_MAX_FUEL: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_WASM_MAX_FUEL", u64, 1_000_000_000);
+
+/// The number of compiled WebAssembly modules which are cached (default: 100)
+pub static WASM_MODULE_CACHE_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_WASM_MODULE_CACHE_SIZE", usize, 100);
+
 /// The maximum number of HTTP redirects allowed within http functions (default:
 /// 10)
 pub static MAX_HTTP_REDIRECTS: LazyLock<usize> =
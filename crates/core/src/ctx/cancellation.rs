@@ -1,4 +1,4 @@
-#![cfg(feature = "scripting")]
+#![cfg(any(feature = "scripting", feature = "wasm-functions"))]
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
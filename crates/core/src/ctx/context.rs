@@ -540,7 +540,7 @@ impl MutableContext {
 	}
 
 	/// Get a 'static view into the cancellation status.
-	#[cfg(feature = "scripting")]
+	#[cfg(any(feature = "scripting", feature = "wasm-functions"))]
 	pub(crate) fn cancellation(&self) -> crate::ctx::cancellation::Cancellation {
 		crate::ctx::cancellation::Cancellation::new(
 			self.deadline,
@@ -598,6 +598,17 @@ impl MutableContext {
 		Ok(())
 	}
 
+	/// Check if WebAssembly functions are allowed
+	#[cfg(feature = "wasm-functions")]
+	pub(crate) fn check_allowed_wasm(&self) -> Result<()> {
+		if !self.capabilities.allows_wasm() {
+			warn!("Capabilities denied WebAssembly function attempt");
+			bail!(Error::WasmNotAllowed);
+		}
+		trace!("Capabilities allowed WebAssembly function");
+		Ok(())
+	}
+
 	/// Check if a function is allowed
 	pub(crate) fn check_allowed_function(&self, target: &str) -> Result<()> {
 		if !self.capabilities.allows_function_name(target) {
@@ -383,6 +383,7 @@ pub enum RouteTarget {
 	Ml,
 	GraphQL,
 	Api,
+	Snapshot,
 }
 
 // impl display
@@ -402,6 +403,7 @@ impl fmt::Display for RouteTarget {
 			RouteTarget::Ml => write!(f, "ml"),
 			RouteTarget::GraphQL => write!(f, "graphql"),
 			RouteTarget::Api => write!(f, "api"),
+			RouteTarget::Snapshot => write!(f, "snapshot"),
 		}
 	}
 }
@@ -440,6 +442,7 @@ impl std::str::FromStr for RouteTarget {
 			"ml" => Ok(RouteTarget::Ml),
 			"graphql" => Ok(RouteTarget::GraphQL),
 			"api" => Ok(RouteTarget::Api),
+			"snapshot" => Ok(RouteTarget::Snapshot),
 			_ => Err(ParseRouteTargetError),
 		}
 	}
@@ -585,6 +588,7 @@ impl<T: Hash + Eq + PartialEq + Ord> Hash for Targets<T> {
 #[derive(Debug, Clone, Hash)]
 pub struct Capabilities {
 	scripting: bool,
+	wasm: bool,
 	guest_access: bool,
 	live_query_notifications: bool,
 
@@ -606,8 +610,9 @@ impl fmt::Display for Capabilities {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(
 			f,
-			"scripting={}, guest_access={}, live_query_notifications={}, allow_funcs={}, deny_funcs={}, allow_net={}, deny_net={}, allow_rpc={}, deny_rpc={}, allow_http={}, deny_http={}, allow_experimental={}, deny_experimental={}, allow_arbitrary_query={}, deny_arbitrary_query={}",
+			"scripting={}, wasm={}, guest_access={}, live_query_notifications={}, allow_funcs={}, deny_funcs={}, allow_net={}, deny_net={}, allow_rpc={}, deny_rpc={}, allow_http={}, deny_http={}, allow_experimental={}, deny_experimental={}, allow_arbitrary_query={}, deny_arbitrary_query={}",
 			self.scripting,
+			self.wasm,
 			self.guest_access,
 			self.live_query_notifications,
 			self.allow_funcs,
@@ -630,6 +635,7 @@ impl Default for Capabilities {
 	fn default() -> Self {
 		Self {
 			scripting: false,
+			wasm: false,
 			guest_access: false,
 			live_query_notifications: true,
 
@@ -653,6 +659,7 @@ impl Capabilities {
 	pub fn all() -> Self {
 		Self {
 			scripting: true,
+			wasm: true,
 			guest_access: true,
 			live_query_notifications: true,
 
@@ -674,6 +681,7 @@ impl Capabilities {
 	pub fn none() -> Self {
 		Self {
 			scripting: false,
+			wasm: false,
 			guest_access: false,
 			live_query_notifications: false,
 
@@ -697,6 +705,11 @@ impl Capabilities {
 		self
 	}
 
+	pub fn with_wasm(mut self, wasm: bool) -> Self {
+		self.wasm = wasm;
+		self
+	}
+
 	pub fn with_guest_access(mut self, guest_access: bool) -> Self {
 		self.guest_access = guest_access;
 		self
@@ -801,6 +814,10 @@ impl Capabilities {
 		self.scripting
 	}
 
+	pub fn allows_wasm(&self) -> bool {
+		self.wasm
+	}
+
 	pub fn allows_guest_access(&self) -> bool {
 		self.guest_access
 	}
@@ -1142,6 +1159,19 @@ mod tests {
 			assert!(!caps.allows_scripting());
 		}
 
+		// When WebAssembly functions are allowed
+		{
+			let caps = Capabilities::default().with_wasm(true);
+			assert!(caps.allows_wasm());
+			assert!(!caps.allows_scripting());
+		}
+
+		// When WebAssembly functions are denied
+		{
+			let caps = Capabilities::default().with_wasm(false);
+			assert!(!caps.allows_wasm());
+		}
+
 		// When guest access is allowed
 		{
 			let caps = Capabilities::default().with_guest_access(true);
@@ -3,6 +3,7 @@ use std::fmt::{self, Debug, Display};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 
+use crate::kvs::impl_kv_value_revisioned;
 use crate::val::{Object, Uuid, Value};
 
 #[revisioned(revision = 2)]
@@ -27,7 +28,7 @@ impl Display for Action {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct Notification {
 	/// The id of the LIVE query to which this notification belongs
@@ -38,17 +39,26 @@ pub struct Notification {
 	pub record: Value,
 	/// The resulting notification content, usually the altered record content
 	pub result: Value,
+	/// The changefeed versionstamp from which the live query can be resumed
+	#[revision(start = 2)]
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub versionstamp: Option<u64>,
 }
 
+impl_kv_value_revisioned!(Notification);
+
 impl Display for Notification {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		let obj: Object = map! {
+		let mut obj: Object = map! {
 			"id".to_string() => self.id.to_string().into(),
 			"action".to_string() => self.action.to_string().into(),
 			"record".to_string() => self.record.clone(),
 			"result".to_string() => self.result.clone(),
 		}
 		.into();
+		if let Some(vs) = self.versionstamp {
+			obj.insert("versionstamp".to_string(), vs.into());
+		}
 		write!(f, "{}", obj)
 	}
 }
@@ -61,6 +71,13 @@ impl Notification {
 			action,
 			record,
 			result,
+			versionstamp: None,
 		}
 	}
+
+	/// Set the changefeed versionstamp of this notification
+	pub fn with_versionstamp(mut self, versionstamp: Option<u64>) -> Self {
+		self.versionstamp = versionstamp;
+		self
+	}
 }
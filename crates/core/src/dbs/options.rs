@@ -6,12 +6,13 @@ use anyhow::{Result, bail};
 use uuid::Uuid;
 
 use crate::catalog;
-use crate::catalog::SubscriptionDefinition;
+use crate::catalog::{DatabaseId, NamespaceId};
 use crate::cnf::MAX_COMPUTATION_DEPTH;
 use crate::dbs::Notification;
 use crate::err::Error;
 use crate::expr::Base;
-use crate::iam::{Action, Auth, ResourceKind};
+use crate::iam::{Action, Auth, Level, Resource, ResourceKind};
+use crate::kvs::Transaction;
 
 /// An Options is passed around when processing a set of query
 /// statements.
@@ -62,11 +63,25 @@ pub enum Force {
 /// Trait for a pluggable message broker used to forward live query events across nodes.
 /// Default implementation can be a no-op. Implementations should be cheap to clone behind Arc.
 pub trait MessageBroker: Send + Sync + Debug {
-	fn can_be_sent(&self, opt: &Options, subscription: &SubscriptionDefinition) -> Result<bool>;
-
 	/// Forward a live query event for the given subscription to its owning node.
 	/// The concrete implementation decides how to encode and route this request.
 	fn send(&self, notification: Notification) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+
+	/// Forward a notification for a live query which is owned by a different node.
+	/// This runs within the transaction which produced the notification, so that the
+	/// notification is only forwarded if the transaction is committed. The namespace
+	/// and database are those of the change which produced the notification. By
+	/// default, notifications for live queries on other nodes are dropped.
+	fn forward<'a>(
+		&'a self,
+		_txn: &'a Transaction,
+		_ns: NamespaceId,
+		_db: DatabaseId,
+		_node: Uuid,
+		_notification: Notification,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
+		Box::pin(async { Ok(()) })
+	}
 }
 
 impl Default for Options {
@@ -361,15 +376,29 @@ impl Options {
 
 	/// Check if the current auth is allowed to perform an action on a given resource
 	pub fn is_allowed(&self, action: Action, res: ResourceKind, base: &Base) -> Result<()> {
+		self.is_allowed_on(action, res, "", base)
+	}
+
+	/// Check if the current auth is allowed to perform an action on a
+	/// given named resource, so that any custom role privileges which
+	/// grant or revoke access to specific resources are taken into account
+	pub fn is_allowed_on(
+		&self,
+		action: Action,
+		res: ResourceKind,
+		name: &str,
+		base: &Base,
+	) -> Result<()> {
 		// Validate the target resource and base
-		let res = match base {
-			Base::Root => res.on_root(),
-			Base::Ns => res.on_ns(self.ns()?),
+		let level = match base {
+			Base::Root => Level::Root,
+			Base::Ns => Level::Namespace(self.ns()?.to_owned()),
 			Base::Db => {
 				let (ns, db) = self.ns_db()?;
-				res.on_db(ns, db)
+				Level::Database(ns.to_owned(), db.to_owned())
 			}
 		};
+		let res = Resource::new(name.to_owned(), res, level);
 
 		// If auth is disabled, allow all actions for anonymous users
 		if !self.auth_enabled && self.auth.is_anon() {
@@ -402,40 +431,75 @@ impl Options {
 		if !self.auth_enabled && self.auth.is_anon() {
 			return Ok(false);
 		}
-		// Check the action to determine if we need to check permissions
-		match action {
+		// Privileges revoked by custom roles always need checking
+		if self.auth.privileges().has_revokes() {
+			return Ok(true);
+		}
+		// Check the predefined roles of the actor
+		self.check_role_perms(action)
+	}
+
+	/// Checks whether the predefined roles of the current
+	/// actor require table permissions to be processed,
+	/// ignoring any privileges revoked by custom roles.
+	fn check_role_perms(&self, action: Action) -> Result<bool> {
+		// Check if the actor is allowed to perform the action
+		let allowed = match action.is_edit() {
 			// This is a request to edit a resource
-			Action::Edit => {
-				// Check if the actor is allowed to edit
-				let allowed = self.auth.has_editor_role();
-				// Today all users have at least View
-				// permissions, so if the target database
-				// belongs to the user's level, we don't
-				// need to check any table permissions.
-				let (ns, db) = self.ns_db()?;
-				let db_in_actor_level = self.auth.is_root()
-					|| self.auth.is_ns_check(ns)
-					|| self.auth.is_db_check(ns, db);
-				// If either of the above checks are false
-				// then we need to check table permissions
-				Ok(!allowed || !db_in_actor_level)
-			}
+			true => self.auth.has_editor_role(),
 			// This is a request to view a resource
-			Action::View => {
-				// Check if the actor is allowed to view
-				let allowed = self.auth.has_viewer_role();
-				// Today, Owner and Editor roles have
-				// Edit permissions, so if the target
-				// database belongs to the user's level
-				// we don't need to check table permissions.
-				let (ns, db) = self.ns_db()?;
-				let db_in_actor_level = self.auth.is_root()
-					|| self.auth.is_ns_check(ns)
-					|| self.auth.is_db_check(ns, db);
-				// If either of the above checks are false
-				// then we need to check table permissions
-				Ok(!allowed || !db_in_actor_level)
-			}
+			false => self.auth.has_viewer_role(),
+		};
+		// Today, Owner and Editor roles have Edit
+		// permissions, and all predefined roles have
+		// View permissions, so if the target database
+		// belongs to the user's level, we don't need
+		// to check any table permissions.
+		let (ns, db) = self.ns_db()?;
+		let db_in_actor_level =
+			self.auth.is_root() || self.auth.is_ns_check(ns) || self.auth.is_db_check(ns, db);
+		// If either of the above checks are false
+		// then we need to check table permissions
+		Ok(!allowed || !db_in_actor_level)
+	}
+
+	/// Checks whether the privileges granted to the custom
+	/// roles of the current actor determine if an action
+	/// is allowed on a specific database resource.
+	///
+	/// Returns `None` when neither the custom roles nor
+	/// the predefined roles of the actor determine access,
+	/// in which case the `PERMISSIONS` clause defined on
+	/// the resource should be used to determine access.
+	pub fn check_grants(
+		&self,
+		action: Action,
+		kind: ResourceKind,
+		name: &str,
+	) -> Result<Option<bool>> {
+		// Check if the actor has any custom roles
+		if self.auth.privileges().is_empty() {
+			return Ok(None);
+		}
+		// Build the resource for this database
+		let (ns, db) = self.ns_db()?;
+		let res =
+			Resource::new(name.to_owned(), kind, Level::Database(ns.to_owned(), db.to_owned()));
+		// Only check the privileges of the custom roles, as the
+		// predefined roles are already covered by check_perms
+		let privileges = self.auth.privileges();
+		let privilege = action.privilege();
+		if privileges.is_revoked(privilege, &res) {
+			return Ok(Some(false));
+		}
+		if privileges.is_granted(privilege, &res, self.auth.level()) {
+			return Ok(Some(true));
+		}
+		// Otherwise fall back to the predefined roles, which
+		// are only bypassed here because of a revoked privilege
+		match self.check_role_perms(action)? {
+			true => Ok(None),
+			false => Ok(Some(true)),
 		}
 	}
 }
@@ -1,19 +1,21 @@
+use std::sync::Arc;
+
 use anyhow::{Result, bail, ensure};
 use reblessive::tree::Stk;
 
 use super::IgnoreError;
-use crate::catalog::Permission;
-use crate::ctx::Context;
+use crate::catalog::{Permission, TableConstraint};
+use crate::ctx::{Context, MutableContext};
 use crate::dbs::{Options, Statement, Workable};
-use crate::doc::Document;
 use crate::doc::Permitted::*;
 use crate::doc::compute::DocKind;
+use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
 use crate::expr::FlowResultExt as _;
 use crate::expr::paths::{ID, IN, OUT};
-use crate::iam::Action;
+use crate::iam::{Action, ResourceKind};
 use crate::sql::ToSql;
-use crate::val::Value;
+use crate::val::{RecordId, Value};
 
 impl Document {
 	/// Checks whether this operation is allowed on
@@ -89,6 +91,49 @@ impl Document {
 		// Carry on
 		Ok(())
 	}
+	/// Checks that the document satisfies each of
+	/// the constraints defined on the table, once
+	/// the fields of the document have been fully
+	/// processed, so that constraints can compare
+	/// the final values of several fields.
+	pub(super) async fn check_table_constraints(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		_stm: &Statement<'_>,
+	) -> Result<()> {
+		// Check import
+		if opt.import {
+			return Ok(());
+		}
+		// Get the table for this document
+		let tb = self.tb(ctx, opt).await?;
+		// Check if the table has any constraints
+		if tb.constraints.is_empty() {
+			return Ok(());
+		}
+		// Get the record id
+		let rid = self.id()?;
+		// Don't run permissions
+		let opt = &opt.new_with_perms(false);
+		// Get the document before and after the change
+		let before = self.initial.doc.as_arc();
+		let after = self.current.doc.as_arc();
+		// Check each of the table constraints
+		check_constraints(
+			stk,
+			ctx,
+			opt,
+			&tb.name,
+			&tb.constraints,
+			&rid,
+			before,
+			after,
+			&self.current,
+		)
+		.await
+	}
 	/// Checks that a specifically selected record
 	/// actually exists in the underlying datastore.
 	/// If the user specifies a record directly
@@ -354,6 +399,15 @@ impl Document {
 			if opt.check_perms(Action::View)? {
 				// Get the table for this document
 				let table = self.tb(ctx, opt).await?;
+				// Check any custom role privileges
+				if let Some(allowed) =
+					opt.check_grants(Action::Select, ResourceKind::Table, &table.name)?
+				{
+					return match allowed {
+						true => Ok(()),
+						false => Err(IgnoreError::Ignore),
+					};
+				}
 				// Get the correct document to check
 				let doc = match stm.is_delete() {
 					true => &self.initial,
@@ -400,6 +454,15 @@ impl Document {
 			if opt.check_perms(stm.into())? {
 				// Get the table for this document
 				let table = self.tb(ctx, opt).await?;
+				// Check any custom role privileges
+				if let Some(allowed) =
+					opt.check_grants(stm.into(), ResourceKind::Table, &table.name)?
+				{
+					return match allowed {
+						true => Ok(()),
+						false => Err(IgnoreError::Ignore),
+					};
+				}
 				// Get the permissions for this table
 				let perms = stm.permissions(&table, self.is_new());
 				// Exit early if permissions are NONE
@@ -444,6 +507,15 @@ impl Document {
 				}
 				// Get the table
 				let table = self.tb(ctx, opt).await?;
+				// Check any custom role privileges
+				if let Some(allowed) =
+					opt.check_grants(stm.into(), ResourceKind::Table, &table.name)?
+				{
+					return match allowed {
+						true => Ok(()),
+						false => Err(IgnoreError::Ignore),
+					};
+				}
 				// Get the permission clause
 				let perms = stm.permissions(&table, self.is_new());
 				// Process the table permissions
@@ -480,3 +552,57 @@ impl Document {
 		Ok(())
 	}
 }
+
+/// Checks that a record satisfies each of the given constraints of its table,
+/// with the `$before` and `$after` parameters set to the record before and
+/// after the change.
+#[expect(clippy::too_many_arguments)]
+pub(crate) async fn check_constraints(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	tb: &str,
+	constraints: &[TableConstraint],
+	rid: &RecordId,
+	before: Arc<Value>,
+	after: Arc<Value>,
+	doc: &CursorDoc,
+) -> Result<()> {
+	// Configure the context
+	let mut ctx = MutableContext::new(ctx);
+	ctx.add_value("before", before);
+	ctx.add_value("after", after);
+	let ctx = ctx.freeze();
+	// Check each of the constraints
+	for constraint in constraints.iter() {
+		check_constraint(stk, &ctx, opt, tb, constraint, rid, doc).await?;
+	}
+	Ok(())
+}
+
+/// Checks that a record satisfies a constraint of its table, returning a
+/// [`Error::TableConstraint`] which names the constraint when it does not.
+async fn check_constraint(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	tb: &str,
+	constraint: &TableConstraint,
+	rid: &RecordId,
+	doc: &CursorDoc,
+) -> Result<()> {
+	// Process the constraint condition
+	let res =
+		stk.run(|stk| constraint.check.compute(stk, ctx, opt, Some(doc))).await.catch_return()?;
+	// Check the constraint result
+	ensure!(
+		res.is_truthy(),
+		Error::TableConstraint {
+			thing: rid.to_string(),
+			table: tb.to_owned(),
+			constraint: constraint.name.clone(),
+			check: constraint.check.to_string(),
+		}
+	);
+	Ok(())
+}
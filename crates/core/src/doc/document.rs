@@ -10,6 +10,7 @@ use crate::catalog::providers::{CatalogProvider, TableProvider};
 use crate::catalog::{self, DatabaseDefinition, Permission, TableDefinition};
 use crate::ctx::{Context, MutableContext};
 use crate::dbs::{Options, Workable};
+use crate::doc::compute_mask;
 use crate::expr::{Base, FlowResultExt as _};
 use crate::iam::{Action, ResourceKind};
 use crate::idx::planner::RecordStrategy;
@@ -378,7 +379,15 @@ impl Document {
 				// Process the field permissions
 				match &fd.select_permission {
 					Permission::Full => (),
-					Permission::None => reduced.to_mut().cut(k),
+					Permission::None => match &fd.mask {
+						// Replace the field with its mask
+						Some(mask) => {
+							let val = full.doc.as_ref().pick(k);
+							let val = compute_mask(stk, ctx, opt, mask, full, val).await?;
+							reduced.to_mut().put(k, val);
+						}
+						None => reduced.to_mut().cut(k),
+					},
 					Permission::Specific(e) => {
 						// Disable permissions
 						let opt = &opt.new_with_perms(false);
@@ -395,7 +404,15 @@ impl Document {
 							.catch_return()?
 							.is_truthy()
 						{
-							reduced.to_mut().cut(k);
+							match &fd.mask {
+								// Replace the field with its mask
+								Some(mask) => {
+									let val = full.doc.as_ref().pick(k);
+									let val = compute_mask(stk, &ctx, opt, mask, full, val).await?;
+									reduced.to_mut().put(k, val);
+								}
+								None => reduced.to_mut().cut(k),
+							}
 						}
 					}
 				}
@@ -472,7 +489,7 @@ impl Document {
 							Some(tb) => tb,
 							None => {
 								// Allowed to run?
-								opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+								opt.is_allowed(Action::Define, ResourceKind::Table, &Base::Db)?;
 								// We can create the table automatically
 								let (ns, db) = opt.ns_db()?;
 								txn.ensure_ns_db_tb(ns, db, &id.table, opt.strict).await?
@@ -491,7 +508,7 @@ impl Document {
 					Some(tb) => Ok(tb),
 					None => {
 						// Allowed to run?
-						opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+						opt.is_allowed(Action::Define, ResourceKind::Table, &Base::Db)?;
 						// We can create the table automatically
 						let (ns, db) = opt.ns_db()?;
 						txn.ensure_ns_db_tb(ns, db, &id.table, opt.strict).await
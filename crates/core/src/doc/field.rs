@@ -7,12 +7,12 @@ use crate::catalog::{self, FieldDefinition};
 use crate::ctx::{Context, MutableContext};
 use crate::dbs::capabilities::ExperimentalTarget;
 use crate::dbs::{Options, Statement};
-use crate::doc::Document;
+use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
 use crate::expr::data::Data;
 use crate::expr::idiom::{Idiom, IdiomTrie, IdiomTrieContains};
 use crate::expr::kind::Kind;
-use crate::expr::{FlowResultExt as _, Part};
+use crate::expr::{Expr, FlowResultExt as _, Part};
 use crate::iam::Action;
 use crate::val::value::CoerceError;
 use crate::val::value::every::ArrayBehaviour;
@@ -776,3 +776,24 @@ impl FieldEditContext<'_> {
 		Ok(())
 	}
 }
+
+/// Computes the `MASK` clause of a field, which is returned in place of the
+/// value of the field when the `SELECT` permission of the field is not
+/// granted. The value of the field is available to the clause as `$value`.
+pub(crate) async fn compute_mask(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	mask: &Expr,
+	doc: &CursorDoc,
+	val: Value,
+) -> Result<Value> {
+	// Disable permissions
+	let opt = &opt.new_with_perms(false);
+	// Configure the context
+	let mut ctx = MutableContext::new(ctx);
+	ctx.add_value("value", Arc::new(val));
+	let ctx = ctx.freeze();
+	// Process the MASK clause
+	stk.run(|stk| mask.compute(stk, &ctx, opt, Some(doc))).await.catch_return()
+}
@@ -6,16 +6,19 @@ use async_channel::Sender;
 use async_graphql::futures_util::future::try_join_all;
 use reblessive::TreeStack;
 use reblessive::tree::Stk;
+use uuid::Uuid;
 
 use super::IgnoreError;
-use crate::catalog::{Permission, SubscriptionDefinition};
+use crate::catalog::{DatabaseId, NamespaceId, Permission, SubscriptionDefinition};
 use crate::ctx::{Context, MutableContext};
 use crate::dbs::{Action, MessageBroker, Notification, Options, Statement};
 use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
 use crate::expr::FlowResultExt as _;
 use crate::expr::paths::{AC, RD, TK};
-use crate::kvs::Transaction;
+use crate::iam::ResourceKind;
+use crate::key::node::nq;
+use crate::kvs::{KVValue, Transaction};
 use crate::val::Value;
 
 impl Document {
@@ -205,9 +208,6 @@ impl Document {
 			Err(IgnoreError::Error(e)) => return Err(e),
 			Ok(_) => (),
 		}
-		if !sender.can_be_sent(&opt, &live_subscription)? {
-			return Ok(());
-		}
 		// Let's check what type of statement
 		// caused this LIVE query to run, and obtain
 		// the relevant result.
@@ -257,15 +257,24 @@ impl Document {
 			}
 		}
 
+		// Get the versionstamp from which this live query can be resumed
+		let versionstamp = self.lq_versionstamp(&ctx, &opt).await?;
+
 		let notification = Notification {
 			id: live_subscription.id.into(),
 			action,
 			record: Value::RecordId(rid.as_ref().clone()),
 			result,
+			versionstamp,
 		};
 
-		// Send the notification
-		sender.send(notification).await;
+		// Send the notification to the node which owns the live query
+		if live_subscription.node == opt.id()? {
+			sender.send(notification).await;
+		} else {
+			let (ns, db) = ctx.expect_ns_db_ids(&opt).await?;
+			sender.forward(&ctx.tx(), ns, db, live_subscription.node, notification).await?;
+		}
 
 		Ok(())
 	}
@@ -307,11 +316,14 @@ impl Document {
 		if opt.check_perms(crate::iam::Action::View)? {
 			// Get the table
 			let tb = self.tb(ctx, opt).await?;
+			// Check any custom role privileges
+			let granted =
+				opt.check_grants(crate::iam::Action::Select, ResourceKind::Table, &tb.name)?;
 			// Process the table permissions
-			match &tb.permissions.select {
-				Permission::None => return Err(IgnoreError::Ignore),
-				Permission::Full => return Ok(()),
-				Permission::Specific(e) => {
+			match (granted, &tb.permissions.select) {
+				(Some(false), _) | (None, Permission::None) => return Err(IgnoreError::Ignore),
+				(Some(true), _) | (None, Permission::Full) => return Ok(()),
+				(None, Permission::Specific(e)) => {
 					// Retrieve the document to check permissions against
 					let doc = &self.current;
 
@@ -333,6 +345,26 @@ impl Document {
 		Ok(())
 	}
 
+	/// Get the versionstamp for a LIVE query notification
+	///
+	/// When the changes to this table are recorded in a changefeed, every
+	/// notification is given the versionstamp of the changefeed entry which
+	/// is recorded by the current transaction. A live query which is resumed
+	/// from this versionstamp replays only the changes which were recorded
+	/// by later transactions, so no changes are sent twice or missed.
+	async fn lq_versionstamp(&self, ctx: &Context, opt: &Options) -> Result<Option<u64>> {
+		// Check if changefeeds are enabled
+		let tb = self.tb(ctx, opt).await?;
+		let db = self.db(ctx, opt).await?;
+		if db.changefeed.is_none() && tb.changefeed.is_none() {
+			return Ok(None);
+		}
+		// Get the versionstamp of this transaction
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let vs = ctx.tx().lock().await.changes_versionstamp(ns, db).await?;
+		Ok(vs.map(|vs| vs.into_u64_lossy()))
+	}
+
 	async fn lq_pluck(
 		&self,
 		stk: &mut Stk,
@@ -349,6 +381,10 @@ impl Document {
 	}
 }
 
+/// The default message broker, which delivers notifications for live queries
+/// owned by this node to the local notification channel, and which queues
+/// notifications for live queries owned by other nodes in the datastore, so
+/// that they can be delivered by the owning node.
 #[derive(Clone, Debug)]
 pub(crate) struct DefaultBroker(Sender<Notification>);
 
@@ -358,10 +394,6 @@ impl DefaultBroker {
 	}
 }
 impl MessageBroker for DefaultBroker {
-	fn can_be_sent(&self, opt: &Options, subscription: &SubscriptionDefinition) -> Result<bool> {
-		Ok(opt.id()? == subscription.node)
-	}
-
 	fn send(&self, notification: Notification) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
 		Box::pin(async move {
 			// If there is an error, we can just ignore it,
@@ -369,4 +401,23 @@ impl MessageBroker for DefaultBroker {
 			let _ = self.0.send(notification).await;
 		})
 	}
+
+	fn forward<'a>(
+		&'a self,
+		txn: &'a Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+		node: Uuid,
+		notification: Notification,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
+		Box::pin(async move {
+			// Queue the notification for the owning node, keyed by the
+			// versionstamp of this transaction, so that notifications
+			// are delivered in the order their transactions committed
+			let prefix = nq::versionstamped_key_prefix(node, notification.id.into())?;
+			let suffix = nq::versionstamped_key_suffix(Uuid::now_v7());
+			let value = notification.kv_encode_value()?;
+			txn.lock().await.set_changes_versionstamped(ns, db, prefix, suffix, value).await
+		})
+	}
 }
@@ -5,7 +5,9 @@
 //! - `initial`: value before the transaction
 //! - `id`: traditionally an integer but can be an object or collection such as an array
 
+pub(crate) use self::check::check_constraints;
 pub(crate) use self::document::*;
+pub(crate) use self::field::compute_mask;
 pub(crate) use self::lives::DefaultBroker;
 
 mod document; // The entry point for a document to be processed
@@ -6,9 +6,9 @@ use super::IgnoreError;
 use crate::catalog;
 use crate::ctx::{Context, MutableContext};
 use crate::dbs::{Options, Statement};
-use crate::doc::Document;
 use crate::doc::Permitted::*;
 use crate::doc::compute::DocKind;
+use crate::doc::{Document, compute_mask};
 use crate::expr::output::Output;
 use crate::expr::{FlowResultExt as _, Operation};
 use crate::iam::Action;
@@ -140,7 +140,16 @@ impl Document {
 						// Process the field permissions
 						match &fd.select_permission {
 							catalog::Permission::Full => (),
-							catalog::Permission::None => out.del(stk, ctx, opt, k).await?,
+							catalog::Permission::None => match &fd.mask {
+								// Replace the field with its mask
+								Some(mask) => {
+									let val = self.current.doc.as_ref().pick(k);
+									let val = compute_mask(stk, ctx, opt, mask, &self.current, val)
+										.await?;
+									out.put(k, val);
+								}
+								None => out.del(stk, ctx, opt, k).await?,
+							},
 							catalog::Permission::Specific(e) => {
 								// Disable permissions
 								let opt = &opt.new_with_perms(false);
@@ -157,7 +166,23 @@ impl Document {
 									.catch_return()?
 									.is_truthy()
 								{
-									out.cut(k);
+									match &fd.mask {
+										// Replace the field with its mask
+										Some(mask) => {
+											let val = self.current.doc.as_ref().pick(k);
+											let val = compute_mask(
+												stk,
+												&ctx,
+												opt,
+												mask,
+												&self.current,
+												val,
+											)
+											.await?;
+											out.put(k, val);
+										}
+										None => out.cut(k),
+									}
 								}
 							}
 						}
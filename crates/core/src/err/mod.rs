@@ -202,6 +202,12 @@ pub enum Error {
 		message: String,
 	},
 
+	/// There was an error with a WebAssembly function
+	#[error("Problem with WebAssembly function. {message}")]
+	InvalidWasm {
+		message: String,
+	},
+
 	/// There was an error with the provided machine learning model
 	#[error("Problem with machine learning computation. {message}")]
 	InvalidModel {
@@ -358,6 +364,12 @@ pub enum Error {
 		name: String,
 	},
 
+	/// The requested module does not exist
+	#[error("The module 'mod::{name}' does not exist")]
+	MdNotFound {
+		name: String,
+	},
+
 	/// The requested field does not exist
 	#[error("The field '{name}' does not exist")]
 	FdNotFound {
@@ -466,6 +478,27 @@ pub enum Error {
 		db: String,
 	},
 
+	/// The requested root role does not exist
+	#[error("The root role '{name}' does not exist")]
+	RoleRootNotFound {
+		name: String,
+	},
+
+	/// The requested namespace role does not exist
+	#[error("The role '{name}' does not exist in the namespace '{ns}'")]
+	RoleNsNotFound {
+		name: String,
+		ns: String,
+	},
+
+	/// The requested database role does not exist
+	#[error("The role '{name}' does not exist in the database '{db}'")]
+	RoleDbNotFound {
+		name: String,
+		ns: String,
+		db: String,
+	},
+
 	/// Unable to perform the realtime query
 	#[error("Unable to perform the realtime query")]
 	RealtimeDisabled,
@@ -563,6 +596,14 @@ pub enum Error {
 		value: String,
 	},
 
+	/// Can not resume a LIVE statement from a changefeed without the original records
+	#[error(
+		"Can not execute LIVE statement with SINCE, as the changefeed for table '{tb}' does not INCLUDE ORIGINAL"
+	)]
+	LiveSinceWithoutOriginal {
+		tb: String,
+	},
+
 	/// Can not execute KILL statement using the specified id
 	#[error("Can not execute KILL statement using id: {value}")]
 	KillStatement {
@@ -638,6 +679,17 @@ pub enum Error {
 		check: String,
 	},
 
+	/// The specified record did not conform to a table CONSTRAINT clause
+	#[error(
+		"Found record `{thing}` on table `{table}`, but it does not satisfy constraint `{constraint}`: {check}"
+	)]
+	TableConstraint {
+		thing: String,
+		table: String,
+		constraint: String,
+		check: String,
+	},
+
 	/// The specified value did not conform to the LET type check
 	#[error("Tried to set `${name}`, but couldn't coerce value: {error}")]
 	SetCoerce {
@@ -886,6 +938,10 @@ pub enum Error {
 	#[error("Scripting functions are not allowed")]
 	ScriptingNotAllowed,
 
+	/// WebAssembly functions are not allowed
+	#[error("WebAssembly functions are not allowed")]
+	WasmNotAllowed,
+
 	/// Function is not allowed
 	#[error("Function '{0}' is not allowed to be executed")]
 	FunctionNotAllowed(String),
@@ -911,6 +967,28 @@ pub enum Error {
 	#[error("Username or Password was not provided")]
 	MissingUserOrPass,
 
+	/// The user has enrolled in TOTP, and a code must be provided to sign in
+	#[error("A TOTP code is required to sign in as this user")]
+	TotpRequired,
+
+	/// The password does not satisfy the password policy
+	#[error("The password does not satisfy the password policy: {message}")]
+	PasswordPolicy {
+		message: String,
+	},
+
+	/// The password has been used recently, and can not be reused
+	#[error("The password has been used recently and can not be reused")]
+	PasswordReused,
+
+	/// The password has expired, and must be changed before signing in
+	#[error("The password has expired and must be changed")]
+	PasswordExpired,
+
+	/// The account has been locked after too many failed signin attempts
+	#[error("The account has been temporarily locked after too many failed signin attempts")]
+	AccountLocked,
+
 	#[error("No signin target to either SC or DB or NS or KV")]
 	NoSigninTarget,
 
@@ -999,6 +1077,12 @@ pub enum Error {
 		name: String,
 	},
 
+	/// The requested module already exists
+	#[error("The module 'mod::{name}' already exists")]
+	MdAlreadyExists {
+		name: String,
+	},
+
 	/// The requested index already exists
 	#[error("The index '{name}' already exists")]
 	IxAlreadyExists {
@@ -1074,6 +1158,33 @@ pub enum Error {
 		db: String,
 	},
 
+	/// The requested root role already exists
+	#[error("The root role '{name}' already exists")]
+	RoleRootAlreadyExists {
+		name: String,
+	},
+
+	/// The requested namespace role already exists
+	#[error("The role '{name}' already exists in the namespace '{ns}'")]
+	RoleNsAlreadyExists {
+		name: String,
+		ns: String,
+	},
+
+	/// The requested database role already exists
+	#[error("The role '{name}' already exists in the database '{db}'")]
+	RoleDbAlreadyExists {
+		name: String,
+		ns: String,
+		db: String,
+	},
+
+	/// A custom role can not use the name of a built-in role
+	#[error("The role '{name}' is a built-in role and can not be redefined")]
+	RoleBuiltin {
+		name: String,
+	},
+
 	/// A database index entry for the specified table is already building
 	#[error("Database index `{name}` is currently building")]
 	IndexAlreadyBuilding {
@@ -1289,6 +1400,25 @@ pub enum Error {
 		limit: u32,
 	},
 
+	/// A MATCH pattern has no node from which matching can start
+	#[error("Unable to match the pattern `{pattern}`, as none of its nodes specify a table")]
+	MatchNoStart {
+		pattern: String,
+	},
+
+	/// A MATCH statement held too many partially matched rows in memory
+	#[error("The MATCH statement exceeded the limit of {limit} matched rows")]
+	MatchRowLimitExceeded {
+		limit: usize,
+	},
+
+	/// A graph function held too many records or edges in memory
+	#[error("The graph function `{name}` exceeded the limit of {limit} records or edges")]
+	GraphFunctionLimitExceeded {
+		name: String,
+		limit: usize,
+	},
+
 	/// Found an unexpected value in a range
 	#[error("Tried to use a `@` repeat recurse symbol, while not recursing.")]
 	RepeatRecurseNotRecursing,
@@ -1420,6 +1550,25 @@ pub enum Error {
 	/// Cannot use the `{0}` keyword on the `id` field
 	#[error("Cannot use the `{0}` type on the `id` field, as that's not a valid record id key.")]
 	IdFieldUnsupportedKind(String),
+
+	/// A migration could not be loaded or run
+	#[error("Invalid migration '{version}': {message}")]
+	InvalidMigration {
+		version: String,
+		message: String,
+	},
+
+	/// A migration was modified after it was applied
+	#[error("The migration '{version}' has been modified since it was applied")]
+	MigrationModified {
+		version: String,
+	},
+
+	/// Migrations are being applied or reverted by another run
+	#[error(
+		"Migrations are already being run. If no other run is in progress, remove the `_migrations:lock` record"
+	)]
+	MigrationLocked,
 }
 
 impl Error {
@@ -1438,6 +1587,7 @@ impl Error {
 				| Error::FieldValue { .. }
 				| Error::FieldReadonly { .. }
 				| Error::FieldUndefined { .. }
+				| Error::TableConstraint { .. }
 		)
 	}
 }
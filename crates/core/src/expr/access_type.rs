@@ -6,7 +6,8 @@ use anyhow::Result;
 
 use crate::err::Error;
 use crate::expr::statements::DefineAccessStatement;
-use crate::expr::{Algorithm, Expr, Literal};
+use crate::expr::user::PasswordPolicy;
+use crate::expr::{Algorithm, Expr, Literal, Param};
 
 /// The type of access methods available
 
@@ -41,6 +42,15 @@ impl Display for AccessType {
 				if let Some(ref v) = ac.signin {
 					write!(f, " SIGNIN {v}")?
 				}
+				if let Some(ref v) = ac.policy {
+					write!(f, " {v}")?
+				}
+				if let Some(ref v) = ac.password {
+					write!(f, " PASSWORD {v}")?
+				}
+				if let Some(ref v) = ac.identity {
+					write!(f, " IDENTITY {v}")?
+				}
 				if ac.bearer.is_some() {
 					write!(f, " WITH REFRESH")?
 				}
@@ -211,6 +221,9 @@ pub struct RecordAccess {
 	pub signin: Option<Expr>,
 	pub jwt: JwtAccess,
 	pub bearer: Option<BearerAccess>,
+	pub policy: Option<PasswordPolicy>,
+	pub identity: Option<Expr>,
+	pub password: Option<Param>,
 }
 
 impl Default for RecordAccess {
@@ -222,6 +235,9 @@ impl Default for RecordAccess {
 				..Default::default()
 			},
 			bearer: None,
+			policy: None,
+			identity: None,
+			password: None,
 		}
 	}
 }
@@ -13,9 +13,9 @@ use crate::expr::operator::BindingPower;
 use crate::expr::statements::info::InfoStructure;
 use crate::expr::statements::{
 	AlterStatement, CreateStatement, DefineStatement, DeleteStatement, ForeachStatement,
-	IfelseStatement, InfoStatement, InsertStatement, OutputStatement, RebuildStatement,
-	RelateStatement, RemoveStatement, SelectStatement, SetStatement, UpdateStatement,
-	UpsertStatement,
+	IfelseStatement, InfoStatement, InsertStatement, MatchStatement, OutputStatement,
+	RebuildStatement, RelateStatement, RemoveStatement, SelectStatement, SetStatement,
+	UpdateStatement, UpsertStatement,
 };
 use crate::expr::{
 	BinaryOperator, Block, Constant, ControlFlow, FlowResult, FunctionCall, Idiom, Literal, Mock,
@@ -61,6 +61,7 @@ pub enum Expr {
 
 	IfElse(Box<IfelseStatement>),
 	Select(Box<SelectStatement>),
+	Match(Box<MatchStatement>),
 	Create(Box<CreateStatement>),
 	Update(Box<UpdateStatement>),
 	Upsert(Box<UpsertStatement>),
@@ -111,6 +112,7 @@ impl Expr {
 			Expr::Throw(expr) => expr.read_only(),
 			Expr::IfElse(s) => s.read_only(),
 			Expr::Select(s) => s.read_only(),
+			Expr::Match(s) => s.read_only(),
 			Expr::Let(s) => s.read_only(),
 			Expr::Foreach(s) => s.read_only(),
 			Expr::Closure(_) => true,
@@ -164,6 +166,7 @@ impl Expr {
 			| Expr::Throw(_)
 			| Expr::IfElse(_)
 			| Expr::Select(_)
+			| Expr::Match(_)
 			| Expr::Create(_)
 			| Expr::Update(_)
 			| Expr::Delete(_)
@@ -270,6 +273,12 @@ impl Expr {
 				})
 				.await
 			}
+			Expr::Match(match_statement) => {
+				Self::update_parent_doc(ctx, doc, async |ctx, doc| {
+					match_statement.compute(stk, ctx, &opt, doc).await.map_err(ControlFlow::Err)
+				})
+				.await
+			}
 			Expr::Create(create_statement) => {
 				Self::update_parent_doc(ctx, doc, async |ctx, doc| {
 					create_statement.compute(stk, ctx, &opt, doc).await.map_err(ControlFlow::Err)
@@ -650,9 +659,15 @@ impl VisitExpression for Expr {
 	{
 		visitor(self);
 		match self {
-			Expr::Literal(_) => {}
+			Expr::Literal(literal) => match literal {
+				Literal::Array(exprs) => exprs.iter().for_each(|expr| expr.visit(visitor)),
+				Literal::Object(entries) => entries.iter().for_each(|entry| entry.visit(visitor)),
+				Literal::RecordId(record_id) => record_id.key.visit(visitor),
+				Literal::Closure(closure) => closure.visit(visitor),
+				_ => {}
+			},
 			Expr::Param(_) => {}
-			Expr::Idiom(_) => {}
+			Expr::Idiom(idiom) => idiom.visit(visitor),
 			Expr::Table(_) => {}
 			Expr::Mock(_) => {}
 			Expr::Block(block) => {
@@ -670,8 +685,13 @@ impl VisitExpression for Expr {
 				..
 			} => expr.visit(visitor),
 			Expr::Binary {
+				left,
+				right,
 				..
-			} => {}
+			} => {
+				left.visit(visitor);
+				right.visit(visitor);
+			}
 			Expr::FunctionCall(function) => function.visit(visitor),
 			Expr::Closure(closure) => {
 				closure.visit(visitor);
@@ -682,10 +702,21 @@ impl VisitExpression for Expr {
 				output.visit(visitor);
 			}
 			Expr::Throw(expr) => expr.visit(visitor),
-			Expr::IfElse(_) => {}
+			Expr::IfElse(ifelse) => {
+				for (cond, then) in ifelse.exprs.iter() {
+					cond.visit(visitor);
+					then.visit(visitor);
+				}
+				if let Some(close) = &ifelse.close {
+					close.visit(visitor);
+				}
+			}
 			Expr::Select(select) => {
 				select.visit(visitor);
 			}
+			Expr::Match(r#match) => {
+				r#match.visit(visitor);
+			}
 			Expr::Create(create) => {
 				create.visit(visitor);
 			}
@@ -807,6 +838,7 @@ impl fmt::Display for Expr {
 			Expr::Throw(expr) => write!(f, "THROW {expr}"),
 			Expr::IfElse(s) => write!(f, "{s}"),
 			Expr::Select(s) => write!(f, "{s}"),
+			Expr::Match(s) => write!(f, "{s}"),
 			Expr::Create(s) => write!(f, "{s}"),
 			Expr::Update(s) => write!(f, "{s}"),
 			Expr::Delete(s) => write!(f, "{s}"),
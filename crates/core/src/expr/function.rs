@@ -10,10 +10,11 @@ use crate::ctx::{Context, MutableContext};
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::expr::expression::VisitExpression;
 use crate::expr::{Expr, Idiom, Model, Script, Value};
 use crate::fmt::Fmt;
 use crate::fnc;
-use crate::iam::Action;
+use crate::iam::{Action, ResourceKind};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Function {
@@ -109,16 +110,18 @@ impl Function {
 				let val = ctx.tx().get_db_function(ns, db, s).await?;
 				// Check permissions
 				if opt.check_perms(Action::View)? {
-					match &val.permissions {
-						Permission::Full => (),
-						Permission::None => {
+					// Check any custom role privileges
+					let granted = opt.check_grants(Action::Select, ResourceKind::Function, s)?;
+					match (granted, &val.permissions) {
+						(Some(true), _) | (None, Permission::Full) => (),
+						(Some(false), _) | (None, Permission::None) => {
 							return Err(ControlFlow::from(anyhow::Error::new(
 								Error::FunctionPermissions {
 									name: s.to_owned(),
 								},
 							)));
 						}
-						Permission::Specific(e) => {
+						(None, Permission::Specific(e)) => {
 							// Disable permissions
 							let opt = &opt.new_with_perms(false);
 							// Process the PERMISSION clause
@@ -159,23 +162,46 @@ impl Function {
 						},
 					})));
 				}
-				// Compute the function arguments
-				// Duplicate context
-				let mut ctx = MutableContext::new_isolated(ctx);
-				// Process the function arguments
-				for (val, (name, kind)) in args.into_iter().zip(&val.args) {
-					ctx.add_value(
-						name.clone(),
-						val.coerce_to_kind(kind)
-							.map_err(Error::from)
-							.map_err(anyhow::Error::new)?
-							.into(),
-					);
-				}
-				let ctx = ctx.freeze();
+				// Coerce the function arguments
+				let args = args
+					.into_iter()
+					.zip(&val.args)
+					.map(|(arg, (_, kind))| {
+						arg.coerce_to_kind(kind).map_err(Error::from).map_err(anyhow::Error::new)
+					})
+					.collect::<anyhow::Result<Vec<_>>>()?;
 				// Run the custom function
-				let result =
-					stk.run(|stk| val.block.compute(stk, &ctx, opt, doc)).await.catch_return()?;
+				let result = match &val.wasm {
+					#[cfg_attr(not(feature = "wasm-functions"), expect(unused_variables))]
+					Some(wasm) => {
+						#[cfg(feature = "wasm-functions")]
+						{
+							// Check if WebAssembly functions are allowed
+							ctx.check_allowed_wasm()?;
+							// Run the WebAssembly function
+							fnc::wasm::run(ctx, wasm, args).await?
+						}
+						#[cfg(not(feature = "wasm-functions"))]
+						{
+							return Err(ControlFlow::Err(anyhow::Error::new(Error::InvalidWasm {
+								message: String::from("WebAssembly functions are not enabled."),
+							})));
+						}
+					}
+					None => {
+						// Duplicate context
+						let mut ctx = MutableContext::new_isolated(ctx);
+						// Process the function arguments
+						for (arg, (name, _)) in args.into_iter().zip(&val.args) {
+							ctx.add_value(name.clone(), arg.into());
+						}
+						let ctx = ctx.freeze();
+						// Run the function block
+						stk.run(|stk| val.block.compute(stk, &ctx, opt, doc))
+							.await
+							.catch_return()?
+					}
+				};
 
 				if let Some(ref returns) = val.returns {
 					result
@@ -229,7 +255,7 @@ impl FunctionCall {
 	where
 		F: FnMut(&Expr),
 	{
-		self.arguments.iter().for_each(visitor);
+		self.arguments.iter().for_each(|expr| expr.visit(visitor));
 	}
 }
 
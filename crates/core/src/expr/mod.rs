@@ -79,7 +79,7 @@ pub use self::limit::Limit;
 pub use self::literal::Literal;
 pub use self::lookup::Lookup;
 pub use self::mock::Mock;
-pub use self::model::{Model, get_model_path};
+pub use self::model::{INFERENCE, InferenceMetrics, Model, get_model_path};
 pub use self::operation::Operation;
 pub use self::operator::{AssignOperator, BinaryOperator, PostfixOperator, PrefixOperator};
 pub use self::order::Order;
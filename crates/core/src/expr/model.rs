@@ -1,30 +1,84 @@
 #[cfg(feature = "ml")]
 use std::collections::HashMap;
 use std::fmt;
+#[cfg(feature = "ml")]
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
+#[cfg(feature = "ml")]
+use anyhow::{Result, bail};
+#[cfg(feature = "ml")]
+use parking_lot::Mutex;
 use reblessive::tree::Stk;
 #[cfg(feature = "ml")]
-use surrealml::errors::error::SurrealError;
+use surrealml::errors::error::{SurrealError, SurrealErrorStatus};
 #[cfg(feature = "ml")]
 use surrealml::execution::compute::ModelComputation;
 #[cfg(feature = "ml")]
 use surrealml::ndarray as mlNdarray;
 #[cfg(feature = "ml")]
 use surrealml::storage::surml_file::SurMlFile;
+#[cfg(feature = "ml")]
+use trice::Instant;
 
 #[cfg(feature = "ml")]
-use crate::catalog::Permission;
+use crate::catalog::{DatabaseId, MlModelDefinition, NamespaceId, Permission};
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
+#[cfg(feature = "ml")]
+use crate::expr::FlowResultExt as _;
 use crate::expr::{ControlFlow, FlowResult};
 #[cfg(feature = "ml")]
 use crate::iam::Action;
+#[cfg(feature = "ml")]
+use crate::kvs::cache::ds::{Entry, Lookup};
+#[cfg(feature = "ml")]
+use crate::val::Number;
 use crate::val::Value;
 
 #[cfg(feature = "ml")]
-const ARGUMENTS: &str = "The model expects 1 argument. The argument can be either a number, an object, or an array of numbers.";
+const ARGUMENTS: &str = "The model expects 1 argument. The argument can be either a number, an object, an array of numbers, or an array of objects or of arrays of numbers to compute in a batch.";
+
+/// Cumulative statistics for the machine learning model computations
+/// on this node, which can be exported as telemetry metrics.
+#[derive(Debug, Default)]
+pub struct InferenceMetrics {
+	/// The number of model computations
+	pub calls: AtomicU64,
+	/// The number of input rows across all model computations
+	pub rows: AtomicU64,
+	/// The total time spent computing models, in microseconds
+	pub micros: AtomicU64,
+	/// The number of computations which used a cached parsed model
+	pub cache_hits: AtomicU64,
+	/// The number of computations which had to parse the model file
+	pub cache_misses: AtomicU64,
+}
+
+impl InferenceMetrics {
+	const fn new() -> Self {
+		Self {
+			calls: AtomicU64::new(0),
+			rows: AtomicU64::new(0),
+			micros: AtomicU64::new(0),
+			cache_hits: AtomicU64::new(0),
+			cache_misses: AtomicU64::new(0),
+		}
+	}
+
+	/// Records a model computation over a number of input rows
+	pub fn record(&self, rows: usize, elapsed: Duration) {
+		self.calls.fetch_add(1, Ordering::Relaxed);
+		self.rows.fetch_add(rows as u64, Ordering::Relaxed);
+		self.micros.fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+	}
+}
+
+/// The inference statistics for all models computed on this node
+pub static INFERENCE: InferenceMetrics = InferenceMetrics::new();
 
 pub fn get_model_path(ns: &str, db: &str, name: &str, version: &str, hash: &str) -> String {
 	format!("ml/{ns}/{db}/{name}-{version}-{hash}.surml")
@@ -53,20 +107,72 @@ impl Model {
 		mut args: Vec<Value>,
 	) -> FlowResult<Value> {
 		use crate::catalog::providers::DatabaseProvider;
-		use crate::val::{CoerceError, Number};
 
 		// Get the full name of this model
 		let name = format!("ml::{}", self.name);
 		// Check this function is allowed
 		ctx.check_allowed_function(name.as_str())?;
+		// Get the model definition
+		let (ns, db, path, val) = self.definition(stk, ctx, opt, doc).await?;
+		// Check the minimum argument length
+		if args.len() != 1 {
+			return Err(ControlFlow::from(anyhow::Error::new(Error::InvalidArguments {
+				name: format!("ml::{}<{}>", self.name, self.version),
+				message: ARGUMENTS.into(),
+			})));
+		}
+		// Take the first and only specified argument
+		let Some(input) = Input::parse(args.pop().unwrap()) else {
+			return Err(ControlFlow::from(anyhow::Error::new(Error::InvalidArguments {
+				name: format!("ml::{}<{}>", self.name, self.version),
+				message: ARGUMENTS.into(),
+			})));
+		};
+		// Fetch the parsed model file
+		let file = self.load(ctx, ns, db, &path, &val.hash).await?;
+		// Run the compute in a blocking task
+		let rows = input.rows();
+		let started = Instant::now();
+		let outcome = tokio::task::spawn_blocking(move || {
+			let mut surml = file.take()?;
+			let res = input.compute(&ModelComputation {
+				surml_file: &mut surml,
+			});
+			file.put(surml);
+			res
+		})
+		.await
+		.unwrap()
+		.map_err(|err: SurrealError| {
+			anyhow::Error::new(Error::ModelComputation(err.message.to_string()))
+		})?;
+		// Record the inference latency
+		let elapsed = started.elapsed();
+		INFERENCE.record(rows, elapsed);
+		trace!("Computed {rows} rows with model {self} in {elapsed:?}");
+		// Ok all good
+		Ok(outcome)
+	}
+
+	/// Fetches the definition of this model, checking that the model can be
+	/// used by the current user, and returns it along with its file path
+	#[cfg(feature = "ml")]
+	pub(crate) async fn definition(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<(NamespaceId, DatabaseId, String, Arc<MlModelDefinition>)> {
+		use crate::catalog::providers::DatabaseProvider;
+
 		// Get the model definition
 		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
 		let Some(val) = ctx.tx().get_db_model(ns, db, &self.name, &self.version).await? else {
-			return Err(ControlFlow::from(anyhow::Error::new(Error::MlNotFound {
+			bail!(Error::MlNotFound {
 				name: format!("{}<{}>", self.name, self.version),
-			})));
+			});
 		};
-
 		// Calculate the model path
 		let path = {
 			let (ns, db) = opt.ns_db()?;
@@ -77,142 +183,72 @@ impl Model {
 			match &val.permissions {
 				Permission::Full => (),
 				Permission::None => {
-					return Err(ControlFlow::from(anyhow::Error::new(
-						Error::FunctionPermissions {
-							name: self.name.clone(),
-						},
-					)));
+					bail!(Error::FunctionPermissions {
+						name: self.name.clone(),
+					});
 				}
 				Permission::Specific(e) => {
 					// Disable permissions
 					let opt = &opt.new_with_perms(false);
 					// Process the PERMISSION clause
-					if !stk.run(|stk| e.compute(stk, ctx, opt, doc)).await?.is_truthy() {
-						return Err(ControlFlow::from(anyhow::Error::new(
-							Error::FunctionPermissions {
-								name: self.name.clone(),
-							},
-						)));
+					if !stk
+						.run(|stk| e.compute(stk, ctx, opt, doc))
+						.await
+						.catch_return()?
+						.is_truthy()
+					{
+						bail!(Error::FunctionPermissions {
+							name: self.name.clone(),
+						});
 					}
 				}
 			}
 		}
+		Ok((ns, db, path, val))
+	}
 
-		// Check the minimum argument length
-		if args.len() != 1 {
-			return Err(ControlFlow::from(anyhow::Error::new(Error::InvalidArguments {
-				name: format!("ml::{}<{}>", self.name, self.version),
-				message: ARGUMENTS.into(),
-			})));
+	/// Fetches and parses a model file, caching the parsed model in the
+	/// datastore cache so that it is only fetched once for each model hash
+	#[cfg(feature = "ml")]
+	async fn load(
+		&self,
+		ctx: &Context,
+		ns: NamespaceId,
+		db: DatabaseId,
+		path: &str,
+		hash: &str,
+	) -> Result<Arc<ParsedModel>> {
+		// Check if the model has already been parsed
+		let cache = ctx.get_cache();
+		let lookup = Lookup::Mlm(ns, db, &self.name, &self.version, hash);
+		if let Some(file) = cache
+			.as_ref()
+			.and_then(|c| c.get(&lookup))
+			.and_then(|v| v.try_into_type::<ParsedModel>().ok())
+		{
+			INFERENCE.cache_hits.fetch_add(1, Ordering::Relaxed);
+			return Ok(file);
 		}
-
-		// Take the first and only specified argument
-		let argument = args.pop().unwrap();
-		match argument {
-			// Perform bufferered compute
-			Value::Object(v) => {
-				// Compute the model function arguments
-				let mut args = v
-					.into_iter()
-					.map(|(k, v)| Ok((k, v.coerce_to::<f64>()? as f32)))
-					.collect::<std::result::Result<HashMap<String, f32>, CoerceError>>()
-					.map_err(|_| Error::InvalidArguments {
-						name: format!("ml::{}<{}>", self.name, self.version),
-						message: ARGUMENTS.into(),
-					})
-					.map_err(anyhow::Error::new)?;
-				// Get the model file as bytes
-				let bytes = crate::obs::get(&path).await?;
-				// Run the compute in a blocking task
-				let outcome: Vec<f32> = tokio::task::spawn_blocking(move || {
-					let mut file = SurMlFile::from_bytes(bytes).map_err(|err: SurrealError| {
-						anyhow::Error::new(Error::ModelComputation(err.message.to_string()))
-					})?;
-					let compute_unit = ModelComputation {
-						surml_file: &mut file,
-					};
-					compute_unit.buffered_compute(&mut args).map_err(|err: SurrealError| {
-						anyhow::Error::new(Error::ModelComputation(err.message.to_string()))
-					})
-				})
-				.await
-				.unwrap()
-				.map_err(ControlFlow::from)?;
-				// Convert the output to a value
-				Ok(outcome.into_iter().map(|x| Value::Number(Number::Float(x as f64))).collect())
-			}
-			// Perform raw compute
-			Value::Number(v) => {
-				// Compute the model function arguments
-				let args: f32 = Value::Number(v)
-					.coerce_to::<f64>()
-					.map_err(|_| Error::InvalidArguments {
-						name: format!("ml::{}<{}>", self.name, self.version),
-						message: ARGUMENTS.into(),
-					})
-					.map_err(anyhow::Error::new)? as f32;
-				// Get the model file as bytes
-				let bytes = crate::obs::get(&path).await.map_err(ControlFlow::from)?;
-				// Convert the argument to a tensor
-				let tensor = mlNdarray::arr1::<f32>(&[args]).into_dyn();
-				// Run the compute in a blocking task
-				let outcome: Vec<f32> = tokio::task::spawn_blocking(move || {
-					let mut file = SurMlFile::from_bytes(bytes).map_err(|err: SurrealError| {
-						anyhow::Error::new(Error::ModelComputation(err.message.to_string()))
-					})?;
-					let compute_unit = ModelComputation {
-						surml_file: &mut file,
-					};
-					compute_unit.raw_compute(tensor, None).map_err(|err: SurrealError| {
-						anyhow::Error::new(Error::ModelComputation(err.message.to_string()))
-					})
-				})
-				.await
-				.unwrap()
-				.map_err(ControlFlow::from)?;
-				// Convert the output to a value
-				Ok(outcome.into_iter().map(|x| Value::Number(Number::Float(x as f64))).collect())
-			}
-			// Perform raw compute
-			Value::Array(v) => {
-				// Compute the model function arguments
-				let args = v
-					.into_iter()
-					.map(|x| x.coerce_to::<f64>().map(|x| x as f32))
-					.collect::<std::result::Result<Vec<f32>, _>>()
-					.map_err(|_| Error::InvalidArguments {
-						name: format!("ml::{}<{}>", self.name, self.version),
-						message: ARGUMENTS.into(),
-					})
-					.map_err(anyhow::Error::new)?;
-				// Get the model file as bytes
-				let bytes = crate::obs::get(&path).await?;
-				// Convert the argument to a tensor
-				let tensor = mlNdarray::arr1::<f32>(&args).into_dyn();
-				// Run the compute in a blocking task
-				let outcome: Vec<f32> = tokio::task::spawn_blocking(move || {
-					let mut file = SurMlFile::from_bytes(bytes).map_err(|err: SurrealError| {
-						anyhow::Error::new(Error::ModelComputation(err.message.to_string()))
-					})?;
-					let compute_unit = ModelComputation {
-						surml_file: &mut file,
-					};
-					compute_unit.raw_compute(tensor, None).map_err(|err: SurrealError| {
-						anyhow::Error::new(Error::ModelComputation(err.message.to_string()))
-					})
-				})
-				.await
-				.unwrap()
-				.map_err(ControlFlow::from)?;
-				// Convert the output to a value
-				Ok(outcome.into_iter().map(|x| Value::Number(Number::Float(x as f64))).collect())
-			}
-			//
-			_ => Err(ControlFlow::from(anyhow::Error::new(Error::InvalidArguments {
-				name: format!("ml::{}<{}>", self.name, self.version),
-				message: ARGUMENTS.into(),
-			}))),
+		INFERENCE.cache_misses.fetch_add(1, Ordering::Relaxed);
+		// Get the model file as bytes
+		let bytes = crate::obs::get(path).await?;
+		// Parse the model file in a blocking task
+		let file = tokio::task::spawn_blocking(move || {
+			let file = SurMlFile::from_bytes(bytes.clone())?;
+			Ok(ParsedModel {
+				bytes,
+				idle: Mutex::new(vec![file]),
+			})
+		})
+		.await
+		.unwrap()
+		.map_err(|err: SurrealError| Error::ModelComputation(err.message.to_string()))?;
+		let file = Arc::new(file);
+		// Store the parsed model for subsequent computations
+		if let Some(cache) = cache {
+			cache.insert(lookup, Entry::Any(file.clone()));
 		}
+		Ok(file)
 	}
 
 	#[cfg(not(feature = "ml"))]
@@ -229,3 +265,164 @@ impl Model {
 		})))
 	}
 }
+
+/// A parsed model file. A computation needs exclusive access to a parsed
+/// model, so idle copies of the model are kept for reuse, allowing
+/// computations on the same model to run concurrently.
+#[cfg(feature = "ml")]
+struct ParsedModel {
+	/// The model file, from which further copies are parsed
+	bytes: Vec<u8>,
+	/// The parsed copies which are not in use by a computation
+	idle: Mutex<Vec<SurMlFile>>,
+}
+
+#[cfg(feature = "ml")]
+impl ParsedModel {
+	/// Takes an idle copy of the model, parsing a new copy if none is idle
+	fn take(&self) -> std::result::Result<SurMlFile, SurrealError> {
+		let file = self.idle.lock().pop();
+		match file {
+			Some(file) => Ok(file),
+			None => SurMlFile::from_bytes(self.bytes.clone()),
+		}
+	}
+
+	/// Returns a copy of the model once a computation has finished with it,
+	/// keeping at most one idle copy for each thread
+	fn put(&self, file: SurMlFile) {
+		let max = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+		let mut idle = self.idle.lock();
+		if idle.len() < max {
+			idle.push(file);
+		}
+	}
+}
+
+#[cfg(feature = "ml")]
+enum Input {
+	/// Named inputs, which the model normalises before computing
+	Buffered(HashMap<String, f32>),
+	/// A tensor of raw inputs
+	Raw(Vec<f32>),
+	/// A batch of named inputs
+	BufferedBatch(Vec<HashMap<String, f32>>),
+	/// A batch of raw input rows, all with the same number of columns
+	RawBatch(Vec<f32>, usize),
+}
+
+#[cfg(feature = "ml")]
+impl Input {
+	/// Converts a model argument into an input, returning `None` if the
+	/// argument is not of a supported shape
+	fn parse(v: Value) -> Option<Self> {
+		fn buffered(v: crate::val::Object) -> Option<HashMap<String, f32>> {
+			v.into_iter().map(|(k, v)| Some((k, v.coerce_to::<f64>().ok()? as f32))).collect()
+		}
+		fn raw(v: crate::val::Array) -> Option<Vec<f32>> {
+			v.into_iter().map(|v| v.coerce_to::<f64>().ok().map(|v| v as f32)).collect()
+		}
+		match v {
+			Value::Object(v) => Some(Self::Buffered(buffered(v)?)),
+			Value::Number(v) => Some(Self::Raw(vec![v.to_float() as f32])),
+			Value::Array(v) => match v.first() {
+				Some(Value::Object(_)) => v
+					.into_iter()
+					.map(|v| match v {
+						Value::Object(v) => buffered(v),
+						_ => None,
+					})
+					.collect::<Option<_>>()
+					.map(Self::BufferedBatch),
+				Some(Value::Array(first)) => {
+					let cols = first.len();
+					let mut data = Vec::with_capacity(cols * v.len());
+					for v in v {
+						match v {
+							Value::Array(v) if v.len() == cols => data.extend(raw(v)?),
+							_ => return None,
+						}
+					}
+					Some(Self::RawBatch(data, cols))
+				}
+				_ => Some(Self::Raw(raw(v)?)),
+			},
+			_ => None,
+		}
+	}
+
+	/// The number of input rows which will be computed
+	fn rows(&self) -> usize {
+		match self {
+			Self::Buffered(_) | Self::Raw(_) => 1,
+			Self::BufferedBatch(v) => v.len(),
+			Self::RawBatch(v, cols) => v.len().checked_div(*cols).unwrap_or_default(),
+		}
+	}
+
+	/// Runs the model over this input. A batch is computed as a single
+	/// two-dimensional tensor, and results in an array of outputs. The named
+	/// inputs of a batch are normalised and ordered by the keys of the model,
+	/// as they are for a single computation.
+	fn compute(self, unit: &ModelComputation) -> std::result::Result<Value, SurrealError> {
+		let output = |v: Vec<f32>| -> Value {
+			v.into_iter().map(|x| Value::Number(Number::Float(x as f64))).collect()
+		};
+		match self {
+			Self::Buffered(mut v) => Ok(output(unit.buffered_compute(&mut v)?)),
+			Self::Raw(v) => {
+				let tensor = mlNdarray::arr1::<f32>(&v).into_dyn();
+				Ok(output(unit.raw_compute(tensor, None)?))
+			}
+			Self::BufferedBatch(v) => {
+				let header = &unit.surml_file.header;
+				let keys = &header.keys.store;
+				let rows = v.len();
+				if rows == 0 || keys.is_empty() {
+					return Ok(Value::from(Vec::<Value>::new()));
+				}
+				let mut data = Vec::with_capacity(rows * keys.len());
+				for mut row in v {
+					// Normalise the inputs which have a normaliser
+					for (key, value) in row.iter_mut() {
+						if let Some(normaliser) =
+							header.normalisers.get_normaliser(key.to_string())?
+						{
+							*value = normaliser.normalise(*value);
+						}
+					}
+					// Order the inputs by the keys of the model
+					for key in keys.iter() {
+						let Some(value) = row.get(key) else {
+							return Err(SurrealError::new(
+								format!("key {key} not found in input values"),
+								SurrealErrorStatus::NotFound,
+							));
+						};
+						data.push(*value);
+					}
+				}
+				let tensor = mlNdarray::arr1::<f32>(&data).into_dyn();
+				let mut res = unit.raw_compute(tensor, Some((rows as i32, keys.len() as i32)))?;
+				// Reverse the normalisation of the outputs
+				if let Some(normaliser) = header.output.normaliser.as_ref() {
+					for value in res.iter_mut() {
+						*value = normaliser.inverse_normalise(*value);
+					}
+				}
+				let width = (res.len() / rows).max(1);
+				Ok(res.chunks(width).map(|v| output(v.to_vec())).collect())
+			}
+			Self::RawBatch(v, cols) => {
+				let rows = v.len().checked_div(cols).unwrap_or_default();
+				if rows == 0 {
+					return Ok(Value::from(Vec::<Value>::new()));
+				}
+				let tensor = mlNdarray::arr1::<f32>(&v).into_dyn();
+				let res = unit.raw_compute(tensor, Some((rows as i32, cols as i32)))?;
+				let width = (res.len() / rows).max(1);
+				Ok(res.chunks(width).map(|v| output(v.to_vec())).collect())
+			}
+		}
+	}
+}
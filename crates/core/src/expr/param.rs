@@ -13,7 +13,7 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::fmt::EscapeKwFreeIdent;
-use crate::iam::Action;
+use crate::iam::{Action, ResourceKind};
 use crate::val::Value;
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
@@ -117,14 +117,17 @@ impl Param {
 					};
 
 					if opt.check_perms(Action::View)? {
-						match &val.permissions {
-							Permission::Full => (),
-							Permission::None => {
+						// Check any custom role privileges
+						let granted =
+							opt.check_grants(Action::Select, ResourceKind::Parameter, v)?;
+						match (granted, &val.permissions) {
+							(Some(true), _) | (None, Permission::Full) => (),
+							(Some(false), _) | (None, Permission::None) => {
 								bail!(Error::ParamPermissions {
 									name: v.to_owned(),
 								})
 							}
-							Permission::Specific(e) => {
+							(None, Permission::Specific(e)) => {
 								// Disable permissions
 								let opt = &opt.new_with_perms(false);
 								// Process the PERMISSION clause
@@ -0,0 +1,93 @@
+use std::fmt::{self, Display};
+
+use anyhow::{Result, bail};
+
+use crate::catalog::providers::AuthorisationProvider;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::expr::{Base, Value};
+use crate::fmt::EscapeIdent;
+use crate::iam::{Action, ResourceKind};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct AlterAccessStatement {
+	pub name: String,
+	pub base: Base,
+	pub if_exists: bool,
+	pub unlock: bool,
+}
+
+impl AlterAccessStatement {
+	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Define, ResourceKind::Actor, &self.base)?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check the statement type
+		match self.base {
+			Base::Root => {
+				// Check that the access method exists
+				if txn.get_root_access(&self.name).await?.is_none() {
+					if self.if_exists {
+						return Ok(Value::None);
+					}
+					bail!(Error::AccessRootNotFound {
+						ac: self.name.clone(),
+					});
+				}
+			}
+			Base::Ns => {
+				let ns = ctx.expect_ns_id(opt).await?;
+				// Check that the access method exists
+				if txn.get_ns_access(ns, &self.name).await?.is_none() {
+					if self.if_exists {
+						return Ok(Value::None);
+					}
+					bail!(Error::AccessNsNotFound {
+						ac: self.name.clone(),
+						ns: opt.ns()?.to_string(),
+					});
+				}
+			}
+			Base::Db => {
+				let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+				// Check that the access method exists
+				if txn.get_db_access(ns, db, &self.name).await?.is_none() {
+					if self.if_exists {
+						return Ok(Value::None);
+					}
+					let (ns, db) = opt.ns_db()?;
+					bail!(Error::AccessDbNotFound {
+						ac: self.name.clone(),
+						ns: ns.to_string(),
+						db: db.to_string(),
+					});
+				}
+				// Clear the failed signin attempts of every record user, as
+				// only record access methods track failed signins
+				if self.unlock {
+					let beg = crate::key::database::access::lk::prefix(ns, db, &self.name)?;
+					let end = crate::key::database::access::lk::suffix(ns, db, &self.name)?;
+					txn.delr(beg..end).await?;
+				}
+			}
+		}
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for AlterAccessStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ALTER ACCESS")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", EscapeIdent(&self.name), self.base)?;
+		if self.unlock {
+			write!(f, " UNLOCK")?;
+		}
+		Ok(())
+	}
+}
@@ -9,13 +9,17 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::val::Value;
 
+mod access;
 mod field;
 mod sequence;
 mod table;
+mod user;
 
+pub use access::AlterAccessStatement;
 pub use field::{AlterDefault, AlterFieldStatement};
 pub use sequence::AlterSequenceStatement;
 pub use table::AlterTableStatement;
+pub use user::AlterUserStatement;
 
 use crate::expr::Expr;
 use crate::expr::expression::VisitExpression;
@@ -79,6 +83,8 @@ pub enum AlterStatement {
 	Table(AlterTableStatement),
 	Sequence(AlterSequenceStatement),
 	Field(AlterFieldStatement),
+	User(AlterUserStatement),
+	Access(AlterAccessStatement),
 }
 
 impl AlterStatement {
@@ -94,6 +100,8 @@ impl AlterStatement {
 			Self::Table(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Sequence(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Field(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::User(v) => v.compute(ctx, opt).await,
+			Self::Access(v) => v.compute(ctx, opt).await,
 		}
 	}
 }
@@ -119,6 +127,8 @@ impl Display for AlterStatement {
 			Self::Table(v) => Display::fmt(v, f),
 			Self::Sequence(v) => Display::fmt(v, f),
 			Self::Field(v) => Display::fmt(v, f),
+			Self::User(v) => Display::fmt(v, f),
+			Self::Access(v) => Display::fmt(v, f),
 		}
 	}
 }
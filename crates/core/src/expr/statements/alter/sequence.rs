@@ -30,7 +30,7 @@ impl AlterSequenceStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Sequence, &Base::Db)?;
+		opt.is_allowed(Action::Define, ResourceKind::Sequence, &Base::Db)?;
 		// Get the NS and DB
 		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
 		// Fetch the transaction
@@ -1,21 +1,26 @@
 use std::fmt::{self, Display, Write};
 use std::ops::Deref;
+use std::sync::Arc;
 
 use anyhow::Result;
 use reblessive::tree::Stk;
 
 use super::AlterKind;
 use crate::catalog::providers::TableProvider;
-use crate::catalog::{Permissions, TableType};
+use crate::catalog::{DatabaseId, NamespaceId, Permissions, TableConstraint, TableType};
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::Options;
-use crate::doc::CursorDoc;
+use crate::doc::{CursorDoc, check_constraints};
 use crate::err::Error;
 use crate::expr::statements::DefineTableStatement;
 use crate::expr::{Base, ChangeFeed, Kind};
 use crate::fmt::{EscapeIdent, is_pretty, pretty_indent};
 use crate::iam::{Action, ResourceKind};
-use crate::val::Value;
+use crate::key::record;
+use crate::kvs::KVValue;
+use crate::val::record::Record;
+use crate::val::{RecordId, Value};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub struct AlterTableStatement {
@@ -26,18 +31,21 @@ pub struct AlterTableStatement {
 	pub changefeed: AlterKind<ChangeFeed>,
 	pub comment: AlterKind<String>,
 	pub kind: Option<TableType>,
+	pub constraints: Vec<TableConstraint>,
+	pub drop_constraints: Vec<String>,
+	pub validate: bool,
 }
 
 impl AlterTableStatement {
 	pub(crate) async fn compute(
 		&self,
-		_stk: &mut Stk,
+		stk: &mut Stk,
 		ctx: &Context,
 		opt: &Options,
 		_doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+		opt.is_allowed_on(Action::Define, ResourceKind::Table, &self.name, &Base::Db)?;
 		// Get the NS and DB
 		let (ns_name, db_name) = opt.ns_db()?;
 		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
@@ -90,6 +98,23 @@ impl AlterTableStatement {
 			dt.table_type = kind.clone();
 		}
 
+		for name in self.drop_constraints.iter() {
+			dt.constraints.retain(|x| &x.name != name);
+		}
+
+		for constraint in self.constraints.iter() {
+			// Replace any existing constraint with the same name
+			match dt.constraints.iter_mut().find(|x| x.name == constraint.name) {
+				Some(x) => *x = constraint.clone(),
+				None => dt.constraints.push(constraint.clone()),
+			}
+		}
+
+		// Check the existing records against the new constraints
+		if self.validate {
+			self.validate_constraints(stk, ctx, opt, ns, db).await?;
+		}
+
 		// Add table relational fields
 		if matches!(self.kind, Some(TableType::Relation(_))) {
 			DefineTableStatement::add_in_out_fields(&txn, ns, db, &mut dt).await?;
@@ -103,11 +128,64 @@ impl AlterTableStatement {
 		// Set the table definition
 		txn.put_tb(ns_name, db_name, &dt).await?;
 
+		// Clear the cache
+		if let Some(cache) = ctx.get_cache() {
+			cache.clear_tb(ns, db, &self.name);
+		}
 		// Clear the cache
 		txn.clear_cache();
 		// Ok all good
 		Ok(Value::None)
 	}
+
+	/// Checks that every existing record in the table
+	/// satisfies the constraints added by this statement
+	async fn validate_constraints(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<()> {
+		// Don't run permissions
+		let opt = &opt.new_with_perms(false);
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Scan the records of the table in batches
+		let beg = record::prefix(ns, db, &self.name)?;
+		let end = record::suffix(ns, db, &self.name)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for (k, v) in batch.result {
+				let k = record::RecordKey::decode_key(&k)?;
+				let rid = RecordId {
+					table: k.tb.into_owned(),
+					key: k.id,
+				};
+				let mut val = Record::kv_decode_value(v)?;
+				val.data.to_mut().def(&rid);
+				let mut doc = CursorDoc::new(Some(Arc::new(rid.clone())), None, val);
+				// The record is unchanged, so it is both before and after
+				let value = doc.doc.as_arc();
+				check_constraints(
+					stk,
+					ctx,
+					opt,
+					&self.name,
+					&self.constraints,
+					&rid,
+					value.clone(),
+					value,
+					&doc,
+				)
+				.await?;
+			}
+		}
+		Ok(())
+	}
 }
 
 impl Display for AlterTableStatement {
@@ -168,6 +246,18 @@ impl Display for AlterTableStatement {
 			AlterKind::None => {}
 		}
 
+		for name in self.drop_constraints.iter() {
+			write!(f, " DROP CONSTRAINT {}", EscapeIdent(name))?;
+		}
+
+		for constraint in self.constraints.iter() {
+			write!(f, " CONSTRAINT {} ASSERT {}", EscapeIdent(&constraint.name), constraint.check)?;
+		}
+
+		if self.validate {
+			write!(f, " VALIDATE")?;
+		}
+
 		let _indent = if is_pretty() {
 			Some(pretty_indent())
 		} else {
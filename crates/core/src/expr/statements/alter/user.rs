@@ -0,0 +1,100 @@
+use std::fmt::{self, Display};
+
+use anyhow::{Result, bail};
+
+use crate::catalog::providers::UserProvider;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::expr::{Base, Value};
+use crate::fmt::EscapeIdent;
+use crate::iam::{Action, ResourceKind};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct AlterUserStatement {
+	pub name: String,
+	pub base: Base,
+	pub if_exists: bool,
+	pub unlock: bool,
+}
+
+impl AlterUserStatement {
+	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Define, ResourceKind::Actor, &self.base)?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check the statement type
+		match self.base {
+			Base::Root => {
+				// Check that the user exists
+				if txn.get_root_user(&self.name).await?.is_none() {
+					if self.if_exists {
+						return Ok(Value::None);
+					}
+					bail!(Error::UserRootNotFound {
+						name: self.name.clone(),
+					});
+				}
+				// Clear any failed signin attempts
+				if self.unlock {
+					let key = crate::key::root::lk::new(&self.name);
+					txn.del(&key).await?;
+				}
+			}
+			Base::Ns => {
+				let ns = ctx.expect_ns_id(opt).await?;
+				// Check that the user exists
+				if txn.get_ns_user(ns, &self.name).await?.is_none() {
+					if self.if_exists {
+						return Ok(Value::None);
+					}
+					bail!(Error::UserNsNotFound {
+						ns: opt.ns()?.to_string(),
+						name: self.name.clone(),
+					});
+				}
+				// Clear any failed signin attempts
+				if self.unlock {
+					let key = crate::key::namespace::lk::new(ns, &self.name);
+					txn.del(&key).await?;
+				}
+			}
+			Base::Db => {
+				let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+				// Check that the user exists
+				if txn.get_db_user(ns, db, &self.name).await?.is_none() {
+					if self.if_exists {
+						return Ok(Value::None);
+					}
+					bail!(Error::UserDbNotFound {
+						ns: opt.ns()?.to_string(),
+						db: opt.db()?.to_string(),
+						name: self.name.clone(),
+					});
+				}
+				// Clear any failed signin attempts
+				if self.unlock {
+					let key = crate::key::database::lk::new(ns, db, &self.name);
+					txn.del(&key).await?;
+				}
+			}
+		}
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for AlterUserStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ALTER USER")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", EscapeIdent(&self.name), self.base)?;
+		if self.unlock {
+			write!(f, " UNLOCK")?;
+		}
+		Ok(())
+	}
+}
@@ -143,6 +143,9 @@ impl DefineAccessStatement {
 					signin: record_access.signin.clone(),
 					jwt: convert_jwt_access(&record_access.jwt),
 					bearer: record_access.bearer.as_ref().map(convert_bearer_access),
+					policy: record_access.policy.clone(),
+					identity: record_access.identity.clone(),
+					password: record_access.password.clone(),
 				}),
 				catalog::AccessType::Jwt(jwt_access) => {
 					AccessType::Jwt(convert_jwt_access(jwt_access))
@@ -260,6 +263,9 @@ impl DefineAccessStatement {
 						signin: record_access.signin.clone(),
 						jwt: convert_jwt_access(stk, ctx, opt, doc, &record_access.jwt).await?,
 						bearer: map_opt!(x as &record_access.bearer => convert_bearer_access(stk, ctx, opt, doc, x).await?),
+						policy: record_access.policy.clone(),
+						identity: record_access.identity.clone(),
+						password: record_access.password.clone(),
 					})
 				}
 				AccessType::Jwt(jwt_access) => catalog::AccessType::Jwt(
@@ -283,7 +289,7 @@ impl DefineAccessStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+		opt.is_allowed(Action::Define, ResourceKind::Actor, &self.base)?;
 		// Compute the definition
 		let definition = self.to_definition(stk, ctx, opt, doc).await?;
 		// Check the statement type
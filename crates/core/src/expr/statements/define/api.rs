@@ -12,7 +12,7 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::err::Error;
 use crate::expr::expression::VisitExpression;
-use crate::expr::{Base, Expr, FlowResultExt as _, Value};
+use crate::expr::{Base, Expr, FlowResultExt as _, Kind, Value};
 use crate::fmt::{Fmt, pretty_indent};
 use crate::iam::{Action, ResourceKind};
 
@@ -46,7 +46,7 @@ impl DefineApiStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Api, &Base::Db)?;
+		opt.is_allowed(Action::Define, ResourceKind::Api, &Base::Db)?;
 		// Fetch the transaction
 		let txn = ctx.tx();
 		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
@@ -79,6 +79,8 @@ impl DefineApiStatement {
 				methods: action.methods.clone(),
 				action: action.action.clone(),
 				config: action.config.compute(stk, ctx, opt, doc).await?,
+				request: action.request.clone(),
+				response: action.response.clone(),
 			});
 		}
 
@@ -139,6 +141,8 @@ pub struct ApiAction {
 	pub methods: Vec<ApiMethod>,
 	pub action: Expr,
 	pub config: ApiConfig,
+	pub request: Option<Kind>,
+	pub response: Option<Kind>,
 }
 
 impl VisitExpression for ApiAction {
@@ -156,6 +160,12 @@ impl fmt::Display for ApiAction {
 		write!(f, "FOR {}", Fmt::comma_separated(self.methods.iter()))?;
 		let indent = pretty_indent();
 		write!(f, "{}", &self.config)?;
+		if let Some(ref v) = self.request {
+			write!(f, " REQUEST {v}")?;
+		}
+		if let Some(ref v) = self.response {
+			write!(f, " RESPONSE {v}")?;
+		}
 		write!(f, " THEN {}", self.action)?;
 		drop(indent);
 		Ok(())
@@ -40,7 +40,7 @@ impl DefineConfigStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Config(ConfigKind::GraphQL), &Base::Db)?;
+		opt.is_allowed(Action::Define, ResourceKind::Config(ConfigKind::GraphQL), &Base::Db)?;
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Get the config kind
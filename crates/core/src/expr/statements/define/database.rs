@@ -59,7 +59,7 @@ impl DefineDatabaseStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Database, &Base::Ns)?;
+		opt.is_allowed(Action::Define, ResourceKind::Database, &Base::Ns)?;
 
 		// Get the NS
 		let ns = opt.ns()?;
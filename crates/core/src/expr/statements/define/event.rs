@@ -56,7 +56,7 @@ impl DefineEventStatement {
 		let comment = map_opt!(x as &self.comment => compute_to!(stk, ctx, opt, _doc, x => String));
 
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Event, &Base::Db)?;
+		opt.is_allowed(Action::Define, ResourceKind::Event, &Base::Db)?;
 		// Get the NS and DB
 		let (ns_name, db_name) = opt.ns_db()?;
 		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
@@ -47,6 +47,9 @@ pub struct DefineFieldStatement {
 	pub assert: Option<Expr>,
 	pub computed: Option<Expr>,
 	pub default: DefineDefault,
+	/// The value returned in place of the field when the `SELECT` permission
+	/// is not granted.
+	pub mask: Option<Expr>,
 	pub permissions: Permissions,
 	pub comment: Option<Expr>,
 	pub reference: Option<Reference>,
@@ -60,6 +63,7 @@ impl VisitExpression for DefineFieldStatement {
 		self.value.iter().for_each(|expr| expr.visit(visitor));
 		self.assert.iter().for_each(|expr| expr.visit(visitor));
 		self.computed.iter().for_each(|expr| expr.visit(visitor));
+		self.mask.iter().for_each(|expr| expr.visit(visitor));
 		self.name.visit(visitor);
 		self.comment.iter().for_each(|expr| expr.visit(visitor));
 		self.reference.iter().for_each(|reference| reference.visit(visitor));
@@ -79,6 +83,7 @@ impl Default for DefineFieldStatement {
 			assert: None,
 			computed: None,
 			default: DefineDefault::None,
+			mask: None,
 			permissions: Permissions::default(),
 			comment: None,
 			reference: None,
@@ -116,6 +121,7 @@ impl DefineFieldStatement {
 				DefineDefault::Set(x) => catalog::DefineDefault::Set(x.clone()),
 				DefineDefault::Always(x) => catalog::DefineDefault::Always(x.clone()),
 			},
+			mask: self.mask.clone(),
 			select_permission: convert_permission(&self.permissions.select),
 			create_permission: convert_permission(&self.permissions.create),
 			update_permission: convert_permission(&self.permissions.update),
@@ -135,7 +141,7 @@ impl DefineFieldStatement {
 		let definition = self.to_definition(stk, ctx, opt, doc).await?;
 
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Field, &Base::Db)?;
+		opt.is_allowed(Action::Define, ResourceKind::Field, &Base::Db)?;
 
 		// Get the NS and DB
 		let (ns_name, db_name) = opt.ns_db()?;
@@ -474,6 +480,9 @@ impl DefineFieldStatement {
 			// Ensure no `COMPUTED` clause is specified
 			ensure!(self.computed.is_none(), Error::IdFieldKeywordConflict("COMPUTED".into()));
 
+			// Ensure no `MASK` clause is specified
+			ensure!(self.mask.is_none(), Error::IdFieldKeywordConflict("MASK".into()));
+
 			// Ensure no `DEFAULT` clause is specified
 			ensure!(
 				matches!(self.default, DefineDefault::None),
@@ -528,6 +537,9 @@ impl Display for DefineFieldStatement {
 		if let Some(ref v) = self.reference {
 			write!(f, " REFERENCE {v}")?
 		}
+		if let Some(ref v) = self.mask {
+			write!(f, " MASK {v}")?
+		}
 		if let Some(ref comment) = self.comment {
 			write!(f, " COMMENT {}", comment)?
 		}
@@ -2,17 +2,20 @@ use std::fmt::{self, Display, Write};
 
 use anyhow::{Result, bail};
 use reblessive::tree::Stk;
+use sha2::{Digest, Sha256};
 
 use super::DefineKind;
+use crate::buc::BucketController;
+use crate::buc::store::ObjectKey;
 use crate::catalog::providers::{CatalogProvider, DatabaseProvider};
-use crate::catalog::{FunctionDefinition, Permission};
+use crate::catalog::{FunctionDefinition, Permission, WasmFunction};
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::expression::VisitExpression;
-use crate::expr::{Base, Block, Expr, Kind};
-use crate::fmt::{EscapeKwFreeIdent, is_pretty, pretty_indent};
+use crate::expr::{Base, Block, Expr, FlowResultExt as _, Kind};
+use crate::fmt::{EscapeKwFreeIdent, QuoteStr, is_pretty, pretty_indent};
 use crate::iam::{Action, ResourceKind};
 use crate::val::Value;
 
@@ -25,6 +28,63 @@ pub struct DefineFunctionStatement {
 	pub comment: Option<Expr>,
 	pub permissions: Permission,
 	pub returns: Option<Kind>,
+	pub wasm: Option<FunctionWasm>,
+}
+
+/// A function which is implemented by an export of a WebAssembly module
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FunctionWasm {
+	/// The module, either as bytes or as a file stored in a bucket
+	pub module: Expr,
+	/// The name of the exported function
+	pub export: String,
+}
+
+impl FunctionWasm {
+	/// Loads the module, and ensures that it can be compiled
+	async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<WasmFunction> {
+		let module =
+			match stk.run(|stk| self.module.compute(stk, ctx, opt, doc)).await.catch_return()? {
+				Value::Bytes(v) => v,
+				Value::File(file) => {
+					let mut controller =
+						BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
+					match controller.get(&ObjectKey::new(file.key.clone())).await? {
+						Some(v) => v,
+						None => bail!(Error::InvalidWasm {
+							message: format!("The module file {file} does not exist."),
+						}),
+					}
+				}
+				v => bail!(Error::InvalidWasm {
+					message: format!(
+						"Expected the module to be bytes or a file, but found {}.",
+						v.kind_of()
+					),
+				}),
+			};
+		// Ensure that the module is valid before storing it
+		let wasm = WasmFunction {
+			hash: Sha256::digest(module.as_slice()).into(),
+			module,
+			export: self.export.clone(),
+		};
+		#[cfg(feature = "wasm-functions")]
+		crate::fnc::wasm::validate(&wasm).await?;
+		Ok(wasm)
+	}
+}
+
+impl Display for FunctionWasm {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "LANGUAGE WASM MODULE {} EXPORT {}", self.module, QuoteStr(&self.export))
+	}
 }
 
 impl VisitExpression for DefineFunctionStatement {
@@ -33,6 +93,7 @@ impl VisitExpression for DefineFunctionStatement {
 		F: FnMut(&Expr),
 	{
 		self.block.visit(visitor);
+		self.wasm.iter().for_each(|wasm| wasm.module.visit(visitor));
 		self.comment.iter().for_each(|comment| comment.visit(visitor));
 	}
 }
@@ -47,7 +108,7 @@ impl DefineFunctionStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Function, &Base::Db)?;
+		opt.is_allowed_on(Action::Define, ResourceKind::Function, &self.name, &Base::Db)?;
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Check if the definition exists
@@ -67,6 +128,11 @@ impl DefineFunctionStatement {
 				}
 			}
 		}
+		// Load the WebAssembly module
+		let wasm = match &self.wasm {
+			Some(v) => Some(v.compute(stk, ctx, opt, doc).await?),
+			None => None,
+		};
 		// Process the statement
 		{
 			let (ns, db) = opt.ns_db()?;
@@ -83,6 +149,7 @@ impl DefineFunctionStatement {
 				comment: map_opt!(x as &self.comment => compute_to!(stk, ctx, opt, doc, x => String)),
 				permissions: self.permissions.clone(),
 				returns: self.returns.clone(),
+				wasm,
 			},
 		)
 		.await?;
@@ -112,7 +179,10 @@ impl fmt::Display for DefineFunctionStatement {
 		if let Some(ref v) = self.returns {
 			write!(f, "-> {v} ")?;
 		}
-		Display::fmt(&self.block, f)?;
+		match self.wasm {
+			Some(ref v) => Display::fmt(v, f)?,
+			None => Display::fmt(&self.block, f)?,
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {}", v)?
 		}
@@ -75,14 +75,13 @@ impl DefineIndexStatement {
 		opt: &Options,
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
-		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Index, &Base::Db)?;
-		// Fetch the transaction
-		let txn = ctx.tx();
-
 		// Compute name and what
 		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "index name").await?;
 		let what = expr_to_ident(stk, ctx, opt, doc, &self.what, "index table").await?;
+		// Allowed to run?
+		opt.is_allowed_on(Action::Define, ResourceKind::Index, &name, &Base::Db)?;
+		// Fetch the transaction
+		let txn = ctx.tx();
 
 		let (ns, db) = opt.ns_db()?;
 		let tb = txn.ensure_ns_db_tb(ns, db, &what, opt.strict).await?;
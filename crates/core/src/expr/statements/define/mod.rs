@@ -9,8 +9,10 @@ mod field;
 mod function;
 mod index;
 mod model;
+mod module;
 mod namespace;
 mod param;
+mod role;
 mod sequence;
 mod table;
 mod user;
@@ -26,13 +28,15 @@ pub use config::DefineConfigStatement;
 pub use database::DefineDatabaseStatement;
 pub use event::DefineEventStatement;
 pub use field::{DefineDefault, DefineFieldStatement};
-pub use function::DefineFunctionStatement;
+pub use function::{DefineFunctionStatement, FunctionWasm};
 pub use index::DefineIndexStatement;
 pub(in crate::expr::statements) use index::run_indexing;
 pub use model::DefineModelStatement;
+pub use module::DefineModuleStatement;
 pub use namespace::DefineNamespaceStatement;
 pub use param::DefineParamStatement;
 use reblessive::tree::Stk;
+pub use role::DefineRoleStatement;
 pub use sequence::DefineSequenceStatement;
 pub use table::DefineTableStatement;
 pub use user::DefineUserStatement;
@@ -64,6 +68,8 @@ pub enum DefineStatement {
 	Field(DefineFieldStatement),
 	Index(DefineIndexStatement),
 	User(DefineUserStatement),
+	Role(DefineRoleStatement),
+	Module(DefineModuleStatement),
 	Model(DefineModelStatement),
 	Access(DefineAccessStatement),
 	Config(DefineConfigStatement),
@@ -92,6 +98,8 @@ impl DefineStatement {
 			Self::Index(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Analyzer(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::User(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Role(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Module(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Model(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Access(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Config(v) => v.compute(stk, ctx, opt, doc).await,
@@ -118,6 +126,8 @@ impl VisitExpression for DefineStatement {
 			DefineStatement::Field(field) => field.visit(visitor),
 			DefineStatement::Index(index) => index.visit(visitor),
 			DefineStatement::User(user) => user.visit(visitor),
+			DefineStatement::Role(role) => role.visit(visitor),
+			DefineStatement::Module(module) => module.visit(visitor),
 			DefineStatement::Model(model) => model.visit(visitor),
 			DefineStatement::Access(access) => access.visit(visitor),
 			DefineStatement::Config(_) => {}
@@ -135,6 +145,8 @@ impl Display for DefineStatement {
 			Self::Database(v) => Display::fmt(v, f),
 			Self::Function(v) => Display::fmt(v, f),
 			Self::User(v) => Display::fmt(v, f),
+			Self::Role(v) => Display::fmt(v, f),
+			Self::Module(v) => Display::fmt(v, f),
 			Self::Param(v) => Display::fmt(v, f),
 			Self::Table(v) => Display::fmt(v, f),
 			Self::Event(v) => Display::fmt(v, f),
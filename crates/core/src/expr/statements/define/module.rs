@@ -0,0 +1,144 @@
+use std::fmt::{self, Display};
+
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+
+use super::DefineKind;
+use crate::buc::BucketController;
+use crate::buc::store::ObjectKey;
+use crate::catalog::ModuleDefinition;
+use crate::catalog::providers::{CatalogProvider, DatabaseProvider};
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::expression::VisitExpression;
+use crate::expr::{Base, Expr, FlowResultExt as _};
+use crate::iam::{Action, ResourceKind};
+use crate::val::Value;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DefineModuleStatement {
+	pub kind: DefineKind,
+	pub name: String,
+	/// The module source, either as a string or as a file stored in a bucket
+	pub source: Expr,
+	pub comment: Option<Expr>,
+}
+
+impl VisitExpression for DefineModuleStatement {
+	fn visit<F>(&self, visitor: &mut F)
+	where
+		F: FnMut(&Expr),
+	{
+		self.source.visit(visitor);
+		self.comment.iter().for_each(|comment| comment.visit(visitor));
+	}
+}
+
+impl DefineModuleStatement {
+	/// Loads the source of the module
+	async fn source(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<String> {
+		match stk.run(|stk| self.source.compute(stk, ctx, opt, doc)).await.catch_return()? {
+			Value::String(v) => Ok(v),
+			Value::File(file) => {
+				let mut controller =
+					BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
+				match controller.get(&ObjectKey::new(file.key.clone())).await? {
+					Some(v) => String::from_utf8(v.into_inner()).map_err(|_| {
+						anyhow::Error::new(Error::InvalidScript {
+							message: format!("The module file {file} is not valid UTF-8."),
+						})
+					}),
+					None => bail!(Error::InvalidScript {
+						message: format!("The module file {file} does not exist."),
+					}),
+				}
+			}
+			v => bail!(Error::InvalidScript {
+				message: format!(
+					"Expected the module source to be a string or a file, but found {}.",
+					v.kind_of()
+				),
+			}),
+		}
+	}
+
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Define, ResourceKind::Function, &Base::Db)?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check if the definition exists
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		let existing = match txn.get_db_module(ns, db, &self.name).await {
+			Ok(md) => {
+				match self.kind {
+					DefineKind::Default => {
+						if !opt.import {
+							bail!(Error::MdAlreadyExists {
+								name: self.name.to_string(),
+							});
+						}
+					}
+					DefineKind::Overwrite => {}
+					DefineKind::IfNotExists => return Ok(Value::None),
+				}
+				Some(md)
+			}
+			Err(_) => None,
+		};
+		// Compute the definition
+		let definition = ModuleDefinition {
+			name: self.name.clone(),
+			source: self.source(stk, ctx, opt, doc).await?,
+			comment: map_opt!(x as &self.comment => compute_to!(stk, ctx, opt, doc, x => String)),
+		};
+		// Ensure that the module compiles before storing it
+		#[cfg(feature = "scripting")]
+		crate::fnc::script::validate_module(&definition)?;
+		// Process the statement
+		{
+			let (ns, db) = opt.ns_db()?;
+			txn.get_or_add_db(ns, db, opt.strict).await?
+		};
+		txn.put_db_module(ns, db, &definition).await?;
+		// Clear the compiled bytecode for the previous definition
+		if let (Some(existing), Some(cache)) = (existing, ctx.get_cache()) {
+			cache.clear_md(ns, db, &existing.name, existing.checksum());
+		}
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for DefineModuleStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE MODULE")?;
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => write!(f, " OVERWRITE")?,
+			DefineKind::IfNotExists => write!(f, " IF NOT EXISTS")?,
+		}
+		write!(f, " mod::{} AS {}", self.name, self.source)?;
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {}", v)?
+		}
+		Ok(())
+	}
+}
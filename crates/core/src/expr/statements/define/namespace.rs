@@ -55,7 +55,7 @@ impl DefineNamespaceStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Namespace, &Base::Root)?;
+		opt.is_allowed(Action::Define, ResourceKind::Namespace, &Base::Root)?;
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Process the name
@@ -44,7 +44,7 @@ impl DefineParamStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Parameter, &Base::Db)?;
+		opt.is_allowed_on(Action::Define, ResourceKind::Parameter, &self.name, &Base::Db)?;
 
 		let value = stk.run(|stk| self.value.compute(stk, ctx, opt, doc)).await.catch_return()?;
 
@@ -0,0 +1,212 @@
+use std::fmt::{self, Display};
+
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+
+use super::DefineKind;
+use crate::catalog::providers::{CatalogProvider, NamespaceProvider, UserProvider};
+use crate::catalog::{self, RoleDefinition};
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::expression::VisitExpression;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, Idiom, Literal};
+use crate::iam::{Action, ResourceKind, Role, RoleGrant};
+use crate::val::Value;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DefineRoleStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub base: Base,
+	pub grants: Vec<RoleGrant>,
+	pub revokes: Vec<RoleGrant>,
+	pub comment: Option<Expr>,
+}
+
+impl VisitExpression for DefineRoleStatement {
+	fn visit<F>(&self, visitor: &mut F)
+	where
+		F: FnMut(&Expr),
+	{
+		self.name.visit(visitor);
+		self.comment.iter().for_each(|expr| expr.visit(visitor));
+	}
+}
+
+impl Default for DefineRoleStatement {
+	fn default() -> Self {
+		Self {
+			kind: DefineKind::Default,
+			name: Expr::Literal(Literal::None),
+			base: Base::Root,
+			grants: vec![],
+			revokes: vec![],
+			comment: None,
+		}
+	}
+}
+
+impl DefineRoleStatement {
+	pub(crate) async fn to_definition(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<catalog::RoleDefinition> {
+		Ok(RoleDefinition {
+			name: expr_to_ident(stk, ctx, opt, doc, &self.name, "role name").await?,
+			grants: self.grants.clone(),
+			revokes: self.revokes.clone(),
+			comment: map_opt!(x as &self.comment => compute_to!(stk, ctx, opt, doc, x => String)),
+		})
+	}
+
+	pub fn from_definition(base: Base, def: &catalog::RoleDefinition) -> Self {
+		Self {
+			kind: DefineKind::Default,
+			base,
+			name: Expr::Idiom(Idiom::field(def.name.clone())),
+			grants: def.grants.clone(),
+			revokes: def.revokes.clone(),
+			comment: def.comment.as_ref().map(|x| Expr::Idiom(Idiom::field(x.clone()))),
+		}
+	}
+
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Define, ResourceKind::Actor, &self.base)?;
+		// Compute definition
+		let definition = self.to_definition(stk, ctx, opt, doc).await?;
+		// Custom roles can not shadow the built-in roles
+		if Role::is_builtin(&definition.name) {
+			bail!(Error::RoleBuiltin {
+				name: definition.name,
+			});
+		}
+		// Check the statement type
+		match self.base {
+			Base::Root => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check if the definition exists
+				if let Some(role) = txn.get_root_role(&definition.name).await? {
+					match self.kind {
+						DefineKind::Default => {
+							if !opt.import {
+								bail!(Error::RoleRootAlreadyExists {
+									name: role.name.to_string(),
+								});
+							}
+						}
+						DefineKind::Overwrite => {}
+						DefineKind::IfNotExists => return Ok(Value::None),
+					}
+				}
+				// Process the statement
+				txn.put_root_role(&definition).await?;
+				// Clear the cache
+				txn.clear_cache();
+				// Ok all good
+				Ok(Value::None)
+			}
+			Base::Ns => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				let ns = ctx.get_ns_id(opt).await?;
+				// Check if the definition exists
+				if let Some(role) = txn.get_ns_role(ns, &definition.name).await? {
+					match self.kind {
+						DefineKind::Default => {
+							if !opt.import {
+								bail!(Error::RoleNsAlreadyExists {
+									name: role.name.to_string(),
+									ns: opt.ns()?.into(),
+								});
+							}
+						}
+						DefineKind::Overwrite => {}
+						DefineKind::IfNotExists => return Ok(Value::None),
+					}
+				}
+
+				let ns = {
+					let ns = opt.ns()?;
+					txn.get_or_add_ns(ns, opt.strict).await?
+				};
+
+				// Process the statement
+				txn.put_ns_role(ns.namespace_id, &definition).await?;
+				// Clear the cache
+				txn.clear_cache();
+				// Ok all good
+				Ok(Value::None)
+			}
+			Base::Db => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check if the definition exists
+				let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+				if let Some(role) = txn.get_db_role(ns, db, &definition.name).await? {
+					match self.kind {
+						DefineKind::Default => {
+							if !opt.import {
+								bail!(Error::RoleDbAlreadyExists {
+									name: role.name.to_string(),
+									ns: opt.ns()?.to_string(),
+									db: opt.db()?.to_string(),
+								});
+							}
+						}
+						DefineKind::Overwrite => {}
+						DefineKind::IfNotExists => return Ok(Value::None),
+					}
+				}
+
+				let db = {
+					let (ns, db) = opt.ns_db()?;
+					txn.get_or_add_db(ns, db, opt.strict).await?
+				};
+
+				// Process the statement
+				txn.put_db_role(db.namespace_id, db.database_id, &definition).await?;
+				// Clear the cache
+				txn.clear_cache();
+				// Ok all good
+				Ok(Value::None)
+			}
+		}
+	}
+}
+
+impl Display for DefineRoleStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE ROLE")?;
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => write!(f, " OVERWRITE")?,
+			DefineKind::IfNotExists => write!(f, " IF NOT EXISTS")?,
+		}
+		write!(f, " {} ON {}", self.name, self.base)?;
+		for grant in self.grants.iter() {
+			write!(f, " GRANT {grant}")?;
+		}
+		for revoke in self.revokes.iter() {
+			write!(f, " REVOKE {revoke}")?;
+		}
+		if let Some(ref comment) = self.comment {
+			write!(f, " COMMENT {}", comment)?
+		}
+		Ok(())
+	}
+}
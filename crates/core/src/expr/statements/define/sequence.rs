@@ -58,7 +58,7 @@ impl DefineSequenceStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Sequence, &Base::Db)?;
+		opt.is_allowed(Action::Define, ResourceKind::Sequence, &Base::Db)?;
 		// Compute name
 		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "sequence name").await?;
 		// Compute timeout
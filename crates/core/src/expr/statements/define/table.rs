@@ -8,7 +8,8 @@ use uuid::Uuid;
 use super::DefineKind;
 use crate::catalog::providers::TableProvider;
 use crate::catalog::{
-	DatabaseId, FieldDefinition, NamespaceId, Permissions, TableDefinition, TableType,
+	DatabaseId, FieldDefinition, NamespaceId, Permissions, TableConstraint, TableDefinition,
+	TableType,
 };
 use crate::ctx::Context;
 use crate::dbs::{Force, Options};
@@ -37,6 +38,7 @@ pub struct DefineTableStatement {
 	pub changefeed: Option<ChangeFeed>,
 	pub comment: Option<Expr>,
 	pub table_type: TableType,
+	pub constraints: Vec<TableConstraint>,
 }
 
 impl VisitExpression for DefineTableStatement {
@@ -47,6 +49,7 @@ impl VisitExpression for DefineTableStatement {
 		self.name.visit(visitor);
 		self.view.iter().for_each(|view| view.visit(visitor));
 		self.comment.iter().for_each(|comment| comment.visit(visitor));
+		self.constraints.iter().for_each(|constraint| constraint.check.visit(visitor));
 	}
 }
 
@@ -63,6 +66,7 @@ impl Default for DefineTableStatement {
 			changefeed: None,
 			comment: None,
 			table_type: TableType::default(),
+			constraints: Vec::new(),
 		}
 	}
 }
@@ -75,12 +79,12 @@ impl DefineTableStatement {
 		opt: &Options,
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
-		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
-
 		// Process the name
 		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "table name").await?;
 
+		// Allowed to run?
+		opt.is_allowed_on(Action::Define, ResourceKind::Table, &name, &Base::Db)?;
+
 		// Get the NS and DB
 		let (ns_name, db_name) = opt.ns_db()?;
 		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
@@ -119,6 +123,7 @@ impl DefineTableStatement {
 			permissions: self.permissions.clone(),
 			comment: map_opt!(x as &self.comment => compute_to!(stk, ctx, opt, doc, x => String)),
 			changefeed: self.changefeed,
+			constraints: self.constraints.clone(),
 
 			cache_fields_ts: cache_ts,
 			cache_events_ts: cache_ts,
@@ -320,6 +325,9 @@ impl Display for DefineTableStatement {
 		if let Some(ref v) = self.changefeed {
 			write!(f, " {v}")?;
 		}
+		for constraint in self.constraints.iter() {
+			write!(f, " CONSTRAINT {} ASSERT {}", EscapeIdent(&constraint.name), constraint.check)?;
+		}
 		let _indent = if is_pretty() {
 			Some(pretty_indent())
 		} else {
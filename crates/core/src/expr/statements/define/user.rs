@@ -1,8 +1,9 @@
 use std::fmt::{self, Display};
+use std::str::FromStr;
 
 use anyhow::{Result, bail};
 use argon2::Argon2;
-use argon2::password_hash::{PasswordHasher, SaltString};
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
 use rand::Rng as _;
 use rand::distributions::Alphanumeric;
 use rand::rngs::OsRng;
@@ -17,11 +18,11 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::expression::VisitExpression;
 use crate::expr::parameterize::expr_to_ident;
-use crate::expr::user::UserDuration;
+use crate::expr::user::{PasswordPolicy, UserDuration, UserTotp};
 use crate::expr::{Base, Expr, Idiom, Literal};
 use crate::fmt::{Fmt, QuoteStr};
-use crate::iam::{Action, ResourceKind};
-use crate::val::{self, Duration, Value};
+use crate::iam::{Action, ResourceKind, Role, totp};
+use crate::val::{self, Array, Datetime, Duration, Value};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct DefineUserStatement {
@@ -29,9 +30,14 @@ pub struct DefineUserStatement {
 	pub name: Expr,
 	pub base: Base,
 	pub hash: String,
+	/// The plaintext password, when the password was specified with PASSWORD,
+	/// used to check the password against the policy of the user
+	pub pass: Option<String>,
 	pub code: String,
 	pub roles: Vec<String>,
 	pub duration: UserDuration,
+	pub totp: Option<UserTotp>,
+	pub policy: Option<PasswordPolicy>,
 	pub comment: Option<Expr>,
 }
 
@@ -52,9 +58,12 @@ impl Default for DefineUserStatement {
 			name: Expr::Literal(Literal::None),
 			base: Base::Root,
 			hash: String::new(),
+			pass: None,
 			code: String::new(),
 			roles: vec![],
 			duration: UserDuration::default(),
+			totp: None,
+			policy: None,
 			comment: None,
 		}
 	}
@@ -70,6 +79,7 @@ impl DefineUserStatement {
 				.hash_password(pass.as_ref(), &SaltString::generate(&mut OsRng))
 				.unwrap()
 				.to_string(),
+			pass: None,
 			code: rand::thread_rng()
 				.sample_iter(&Alphanumeric)
 				.take(128)
@@ -77,6 +87,8 @@ impl DefineUserStatement {
 				.collect::<String>(),
 			roles: vec![role],
 			duration: UserDuration::default(),
+			totp: None,
+			policy: None,
 			comment: None,
 		}
 	}
@@ -96,6 +108,94 @@ impl DefineUserStatement {
 			token_duration: map_opt!(x as &self.duration.token => compute_to!(stk, ctx, opt, doc, x => Duration).0),
 			session_duration: map_opt!(x as &self.duration.session => compute_to!(stk, ctx, opt, doc, x => Duration).0),
 			comment: map_opt!(x as &self.comment => compute_to!(stk, ctx, opt, doc, x => String)),
+			totp: match &self.totp {
+				Some(UserTotp::Secret {
+					secret,
+					recovery,
+				}) => Some(catalog::UserTotp {
+					secret: secret.clone(),
+					recovery: recovery.clone(),
+					step: None,
+				}),
+				// Secrets are generated when the statement is processed
+				Some(UserTotp::Generate) | None => None,
+			},
+			policy: self.policy.clone(),
+			// The password history is maintained when the statement is processed
+			history: vec![],
+			changed: None,
+		})
+	}
+
+	/// Applies the password policy of the user to a new definition, checking
+	/// the password against the policy, and maintaining the password history.
+	fn apply_policy(
+		&self,
+		definition: &mut catalog::UserDefinition,
+		existing: Option<&catalog::UserDefinition>,
+	) -> Result<()> {
+		// Carry over the password state if the password is unchanged
+		if let Some(existing) =
+			existing.filter(|v| self.pass.is_none() && v.hash == definition.hash)
+		{
+			definition.history = existing.history.clone();
+			definition.changed = existing.changed.clone();
+			return Ok(());
+		}
+		if let Some(policy) = &self.policy {
+			// Check the password complexity
+			if let Some(pass) = &self.pass {
+				policy.check(pass)?;
+			}
+			// Check that a previous password is not being reused
+			if let (Some(limit), Some(existing)) = (policy.history, existing) {
+				let limit = limit as usize;
+				let previous = std::iter::once(&existing.hash)
+					.chain(existing.history.iter())
+					.take(limit)
+					.collect::<Vec<_>>();
+				for hash in previous.iter() {
+					let reused = match &self.pass {
+						Some(pass) => PasswordHash::new(hash).is_ok_and(|hash| {
+							Argon2::default().verify_password(pass.as_bytes(), &hash).is_ok()
+						}),
+						None => **hash == definition.hash,
+					};
+					if reused {
+						bail!(Error::PasswordReused);
+					}
+				}
+				// Store the previous passwords in the history
+				definition.history =
+					previous.into_iter().take(limit.saturating_sub(1)).cloned().collect();
+			}
+		}
+		// Record when the password was changed
+		definition.changed = Some(Datetime::now());
+		Ok(())
+	}
+
+	/// The roles of the user which are not built-in roles, and so must have
+	/// been defined as custom roles at the level of the user.
+	fn custom_roles(&self) -> impl Iterator<Item = &String> {
+		self.roles.iter().filter(|name| Role::from_str(name).is_err())
+	}
+
+	/// Enrols the user with a newly generated TOTP secret and set of recovery
+	/// codes, returning the details which need to be provided to the user.
+	fn enrol_totp(definition: &mut catalog::UserDefinition) -> Value {
+		let secret = totp::secret();
+		let recovery = totp::recovery_codes();
+		let uri = totp::uri(&secret, &definition.name, None);
+		definition.totp = Some(catalog::UserTotp {
+			secret: secret.clone(),
+			recovery: recovery.iter().map(|x| totp::hash_recovery_code(x)).collect(),
+			step: None,
+		});
+		Value::from(map! {
+			"secret".to_string() => Value::from(secret),
+			"uri".to_string() => Value::from(uri),
+			"recovery".to_string() => Array::from(recovery.into_iter().map(Value::from).collect::<Vec<_>>()).into(),
 		})
 	}
 
@@ -105,6 +205,7 @@ impl DefineUserStatement {
 			base,
 			name: Expr::Idiom(Idiom::field(def.name.clone())),
 			hash: def.hash.clone(),
+			pass: None,
 			code: def.code.clone(),
 			roles: def.roles.clone(),
 			duration: UserDuration {
@@ -115,6 +216,11 @@ impl DefineUserStatement {
 					.session_duration
 					.map(|x| Expr::Literal(Literal::Duration(val::Duration(x)))),
 			},
+			totp: def.totp.as_ref().map(|x| UserTotp::Secret {
+				secret: x.secret.clone(),
+				recovery: x.recovery.clone(),
+			}),
+			policy: def.policy.clone(),
 			comment: def.comment.as_ref().map(|x| Expr::Idiom(Idiom::field(x.clone()))),
 		}
 	}
@@ -128,16 +234,22 @@ impl DefineUserStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+		opt.is_allowed(Action::Define, ResourceKind::Actor, &self.base)?;
 		// Compute definition
-		let definition = self.to_definition(stk, ctx, opt, doc).await?;
+		let mut definition = self.to_definition(stk, ctx, opt, doc).await?;
+		// Enrol the user in TOTP if requested
+		let output = match self.totp {
+			Some(UserTotp::Generate) => Self::enrol_totp(&mut definition),
+			_ => Value::None,
+		};
 		// Check the statement type
 		match self.base {
 			Base::Root => {
 				// Fetch the transaction
 				let txn = ctx.tx();
 				// Check if the definition exists
-				if let Some(user) = txn.get_root_user(&definition.name).await? {
+				let existing = txn.get_root_user(&definition.name).await?;
+				if let Some(user) = &existing {
 					match self.kind {
 						DefineKind::Default => {
 							if !opt.import {
@@ -150,19 +262,30 @@ impl DefineUserStatement {
 						DefineKind::IfNotExists => return Ok(Value::None),
 					}
 				}
+				// Check that the custom roles exist
+				for name in self.custom_roles() {
+					if txn.get_root_role(name).await?.is_none() {
+						bail!(Error::RoleRootNotFound {
+							name: name.to_owned(),
+						});
+					}
+				}
+				// Apply the password policy
+				self.apply_policy(&mut definition, existing.as_deref())?;
 				// Process the statement
 				txn.put_root_user(&definition).await?;
 				// Clear the cache
 				txn.clear_cache();
 				// Ok all good
-				Ok(Value::None)
+				Ok(output)
 			}
 			Base::Ns => {
 				// Fetch the transaction
 				let txn = ctx.tx();
 				let ns = ctx.get_ns_id(opt).await?;
 				// Check if the definition exists
-				if let Some(user) = txn.get_ns_user(ns, &definition.name).await? {
+				let existing = txn.get_ns_user(ns, &definition.name).await?;
+				if let Some(user) = &existing {
 					match self.kind {
 						DefineKind::Default => {
 							if !opt.import {
@@ -182,19 +305,31 @@ impl DefineUserStatement {
 					txn.get_or_add_ns(ns, opt.strict).await?
 				};
 
+				// Check that the custom roles exist
+				for name in self.custom_roles() {
+					if txn.get_ns_role(ns.namespace_id, name).await?.is_none() {
+						bail!(Error::RoleNsNotFound {
+							name: name.to_owned(),
+							ns: opt.ns()?.into(),
+						});
+					}
+				}
+				// Apply the password policy
+				self.apply_policy(&mut definition, existing.as_deref())?;
 				// Process the statement
 				txn.put_ns_user(ns.namespace_id, &definition).await?;
 				// Clear the cache
 				txn.clear_cache();
 				// Ok all good
-				Ok(Value::None)
+				Ok(output)
 			}
 			Base::Db => {
 				// Fetch the transaction
 				let txn = ctx.tx();
 				// Check if the definition exists
 				let (ns, db) = ctx.get_ns_db_ids(opt).await?;
-				if let Some(user) = txn.get_db_user(ns, db, &definition.name).await? {
+				let existing = txn.get_db_user(ns, db, &definition.name).await?;
+				if let Some(user) = &existing {
 					match self.kind {
 						DefineKind::Default => {
 							if !opt.import {
@@ -215,12 +350,24 @@ impl DefineUserStatement {
 					txn.get_or_add_db(ns, db, opt.strict).await?
 				};
 
+				// Check that the custom roles exist
+				for name in self.custom_roles() {
+					if txn.get_db_role(db.namespace_id, db.database_id, name).await?.is_none() {
+						bail!(Error::RoleDbNotFound {
+							name: name.to_owned(),
+							ns: opt.ns()?.to_string(),
+							db: opt.db()?.to_string(),
+						});
+					}
+				}
+				// Apply the password policy
+				self.apply_policy(&mut definition, existing.as_deref())?;
 				// Process the statement
 				txn.put_db_user(db.namespace_id, db.database_id, &definition).await?;
 				// Clear the cache
 				txn.clear_cache();
 				// Ok all good
-				Ok(Value::None)
+				Ok(output)
 			}
 		}
 	}
@@ -264,6 +411,12 @@ impl Display for DefineUserStatement {
 				None => "NONE".to_string(),
 			}
 		)?;
+		if let Some(ref v) = self.totp {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.policy {
+			write!(f, " {v}")?
+		}
 		if let Some(ref comment) = self.comment {
 			write!(f, " COMMENT {}", comment)?
 		}
@@ -81,6 +81,7 @@ impl KillStatement {
 					action: Action::Killed,
 					record: Value::None,
 					result: Value::None,
+					versionstamp: None,
 				})
 				.await;
 		}
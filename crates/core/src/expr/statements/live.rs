@@ -1,17 +1,27 @@
 use std::fmt;
+use std::sync::Arc;
 
-use anyhow::{Result, bail};
+use anyhow::{Result, bail, ensure};
 use reblessive::tree::Stk;
 use uuid::Uuid;
 
-use crate::catalog::providers::CatalogProvider;
-use crate::catalog::{NodeLiveQuery, SubscriptionDefinition};
+use crate::catalog::providers::{CatalogProvider, TableProvider};
+use crate::catalog::{NodeLiveQuery, Permission, SubscriptionDefinition, TableDefinition};
+use crate::cf::{ChangeSet, DatabaseMutation, TableMutation, TableMutations};
 use crate::ctx::Context;
-use crate::dbs::Options;
+use crate::dbs::{Action, Notification, Options};
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::expr::paths::ID;
+use crate::expr::statements::SelectStatement;
+use crate::expr::statements::show::ShowSince;
 use crate::expr::{Cond, Expr, Fetchs, Fields, FlowResultExt as _, Literal};
-use crate::val::Value;
+use crate::iam::ResourceKind;
+use crate::val::{RecordId, Value};
+
+/// The number of changefeed entries which are scanned at a time when
+/// replaying the changes since a versionstamp
+const REPLAY_BATCH_SIZE: u32 = 100;
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct LiveStatement {
@@ -21,6 +31,8 @@ pub struct LiveStatement {
 	pub what: Expr,
 	pub cond: Option<Cond>,
 	pub fetch: Option<Fetchs>,
+	pub snapshot: bool,
+	pub since: Option<ShowSince>,
 }
 
 impl LiveStatement {
@@ -32,6 +44,8 @@ impl LiveStatement {
 			what: Expr::Literal(Literal::Null),
 			cond: None,
 			fetch: None,
+			snapshot: false,
+			since: None,
 		}
 	}
 
@@ -43,6 +57,8 @@ impl LiveStatement {
 			fields: expr,
 			cond: None,
 			fetch: None,
+			snapshot: false,
+			since: None,
 		}
 	}
 
@@ -116,6 +132,8 @@ impl LiveStatement {
 				}
 				// Clear the cache
 				txn.clear_cache();
+				// Send the initial notifications
+				self.initial(stk, ctx, opt, &tb, live_query_id).await?;
 			}
 			v => {
 				bail!(Error::LiveStatement {
@@ -126,6 +144,188 @@ impl LiveStatement {
 		// Return the query id
 		Ok(crate::val::Uuid(live_query_id).into())
 	}
+
+	/// Sends the notifications which are emitted when the live query starts.
+	///
+	/// With `WITH SNAPSHOT`, the records which currently match the live query
+	/// are sent as `CREATE` notifications. With `SINCE`, the changes which were
+	/// recorded in the table changefeed after the specified versionstamp, or
+	/// from the specified timestamp, are replayed. These notifications are sent within the same
+	/// transaction which registers the live query, so no changes are missed.
+	async fn initial(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		tb: &str,
+		id: Uuid,
+	) -> Result<()> {
+		// Check if we can send notifications
+		let Some(sender) = opt.broker.as_ref() else {
+			return Ok(());
+		};
+		// Get the changefeed which is enabled for this table
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let txn = ctx.tx();
+		let dbv = ctx.get_db(opt).await?;
+		let tbv = txn.expect_tb(ns, db, tb).await?;
+		let changefeed = dbv.changefeed.or(tbv.changefeed);
+		// Send the records which currently match
+		if self.snapshot {
+			// The snapshot includes every change up to the latest versionstamp
+			let versionstamp = match changefeed {
+				Some(_) => txn.lock().await.current_versionstamp(ns, db).await?,
+				None => None,
+			};
+			let stm = SelectStatement {
+				what: vec![Expr::Table(tb.to_owned())],
+				cond: self.cond.clone(),
+				..Default::default()
+			};
+			let res = stm.compute(stk, ctx, opt, None).await?;
+			let Value::Array(docs) = res else {
+				return Ok(());
+			};
+			for doc in docs {
+				let Value::RecordId(rid) = doc.pick(&*ID) else {
+					continue;
+				};
+				let result = self.project(stk, ctx, opt, &rid, doc).await?;
+				let notification =
+					Notification::new(id.into(), Action::Create, Value::RecordId(rid), result)
+						.with_versionstamp(versionstamp.map(|vs| vs.into_u64_lossy()));
+				sender.send(notification).await;
+			}
+		}
+		// Replay the changes from the changefeed
+		if let Some(since) = &self.since {
+			// Without the original records, created records can not be told
+			// apart from updated records, and deleted records can not be
+			// checked against the WHERE clause and the table permissions
+			ensure!(
+				changefeed.is_some_and(|cf| cf.store_diff),
+				Error::LiveSinceWithoutOriginal {
+					tb: tb.to_owned(),
+				}
+			);
+			// Replay the changes after the specified versionstamp
+			let mut start = match since {
+				ShowSince::Versionstamp(vs) => ShowSince::Versionstamp(vs.saturating_add(1)),
+				ShowSince::Timestamp(_) => since.clone(),
+			};
+			loop {
+				let (changes, next) =
+					crate::cf::read_batch(&txn, ns, db, Some(tb), start, REPLAY_BATCH_SIZE).await?;
+				for ChangeSet(vs, DatabaseMutation(mutations)) in changes {
+					for TableMutations(_, mutations) in mutations {
+						for mutation in mutations {
+							// Get the action and the record for this change
+							let (action, rid, doc) = match mutation {
+								TableMutation::Set(rid, doc) => (Action::Create, rid, doc),
+								TableMutation::SetWithDiff(rid, doc, _) => {
+									(Action::Update, rid, doc)
+								}
+								TableMutation::DelWithOriginal(rid, doc) => {
+									(Action::Delete, rid, doc)
+								}
+								// Deletions which were recorded before the changefeed
+								// included the original records can not be checked
+								TableMutation::Del(_) | TableMutation::Def(_) => continue,
+							};
+							// Check the WHERE clause and the table permissions
+							if !self.matches(stk, ctx, opt, &tbv, &rid, &doc).await? {
+								continue;
+							}
+							let result = self.project(stk, ctx, opt, &rid, doc).await?;
+							let notification =
+								Notification::new(id.into(), action, Value::RecordId(rid), result)
+									.with_versionstamp(Some(vs.into_u64_lossy()));
+							sender.send(notification).await;
+						}
+					}
+				}
+				// Continue after the scanned changes, whether or not they
+				// included any changes to this table
+				match next {
+					Some(vs) => start = ShowSince::Versionstamp(vs),
+					None => break,
+				}
+				// Pause and yield execution
+				yield_now!();
+			}
+		}
+		Ok(())
+	}
+
+	/// Checks whether a replayed record matches the live query
+	async fn matches(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		tb: &TableDefinition,
+		rid: &RecordId,
+		doc: &Value,
+	) -> Result<bool> {
+		let doc = CursorDoc::new(Some(Arc::new(rid.clone())), None, doc.clone());
+		// Check the table permissions
+		if opt.check_perms(crate::iam::Action::View)? {
+			// Check any custom role privileges
+			let granted =
+				opt.check_grants(crate::iam::Action::Select, ResourceKind::Table, &tb.name)?;
+			match (granted, &tb.permissions.select) {
+				(Some(true), _) | (None, Permission::Full) => (),
+				(Some(false), _) | (None, Permission::None) => return Ok(false),
+				(None, Permission::Specific(e)) => {
+					let opt = &opt.new_with_perms(false);
+					if !stk
+						.run(|stk| e.compute(stk, ctx, opt, Some(&doc)))
+						.await
+						.catch_return()?
+						.is_truthy()
+					{
+						return Ok(false);
+					}
+				}
+			}
+		}
+		// Check the WHERE clause
+		if let Some(cond) = self.cond.as_ref() {
+			if !stk
+				.run(|stk| cond.0.compute(stk, ctx, opt, Some(&doc)))
+				.await
+				.catch_return()?
+				.is_truthy()
+			{
+				return Ok(false);
+			}
+		}
+		Ok(true)
+	}
+
+	/// Computes the fields of the live query for a record
+	async fn project(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		rid: &RecordId,
+		doc: Value,
+	) -> Result<Value> {
+		let doc = CursorDoc::new(Some(Arc::new(rid.clone())), None, doc);
+		let mut result = self.fields.compute(stk, ctx, opt, Some(&doc), false).await?;
+		// Process any potential `FETCH` clause on the live statement
+		if let Some(fetchs) = &self.fetch {
+			let mut idioms = Vec::with_capacity(fetchs.len());
+			for fetch in fetchs.iter() {
+				fetch.compute(stk, ctx, opt, &mut idioms).await?;
+			}
+			for i in &idioms {
+				stk.run(|stk| result.fetch(stk, ctx, opt, i)).await?;
+			}
+		}
+		Ok(result)
+	}
 }
 
 impl fmt::Display for LiveStatement {
@@ -137,6 +337,14 @@ impl fmt::Display for LiveStatement {
 		if let Some(ref v) = self.fetch {
 			write!(f, " {v}")?
 		}
+		if self.snapshot {
+			write!(f, " WITH SNAPSHOT")?
+		}
+		match self.since {
+			Some(ShowSince::Timestamp(ref v)) => write!(f, " SINCE {v}")?,
+			Some(ShowSince::Versionstamp(ref v)) => write!(f, " SINCE {v}")?,
+			None => {}
+		}
 		Ok(())
 	}
 }
@@ -0,0 +1,583 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{self, Display, Formatter, Write};
+
+use anyhow::{Result, bail, ensure};
+use reblessive::tree::Stk;
+
+use crate::catalog::providers::TableProvider;
+use crate::cnf::MATCH_ROW_LIMIT;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::expression::VisitExpression;
+use crate::expr::lookup::{LookupKind, LookupSubject};
+use crate::expr::order::Ordering;
+use crate::expr::statements::SelectStatement;
+use crate::expr::{
+	BinaryOperator, Cond, Dir, Expr, Fields, FlowResultExt as _, Idiom, Limit, Literal, Lookup,
+	Part, Start,
+};
+use crate::fmt::{EscapeIdent, Fmt};
+use crate::val::{RecordId, Value};
+
+/// A node in a MATCH pattern, `(alias:table|table)`
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct MatchNode {
+	pub alias: Option<String>,
+	pub what: Vec<String>,
+}
+
+/// An edge in a MATCH pattern, `-[alias:table|table]->`
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct MatchEdge {
+	pub alias: Option<String>,
+	pub what: Vec<String>,
+	pub dir: Dir,
+}
+
+/// A path of nodes joined by edges in a MATCH statement
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct MatchPattern {
+	pub start: MatchNode,
+	pub steps: Vec<(MatchEdge, MatchNode)>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct MatchStatement {
+	pub patterns: Vec<MatchPattern>,
+	pub cond: Option<Cond>,
+	/// The foo,bar part in MATCH ... RETURN foo,bar.
+	pub expr: Fields,
+	pub order: Option<Ordering>,
+	pub limit: Option<Limit>,
+	pub start: Option<Start>,
+}
+
+/// The bound records of a partially matched pattern, indexed by slot
+type Row = Vec<Option<RecordId>>;
+
+/// A node or edge of a pattern, with the slot which holds its record
+struct Binding<'a> {
+	slot: usize,
+	alias: Option<&'a str>,
+	what: &'a [String],
+}
+
+/// A pattern whose nodes and edges have been assigned slots. The edge at
+/// index `i` joins the nodes at index `i` and `i + 1`.
+struct Plan<'a> {
+	source: &'a MatchPattern,
+	nodes: Vec<Binding<'a>>,
+	edges: Vec<(Binding<'a>, Dir)>,
+}
+
+/// Assigns a slot to every node and edge, so that repeated aliases are joined
+#[derive(Default)]
+struct Slots {
+	names: Vec<Option<String>>,
+	index: HashMap<String, usize>,
+}
+
+impl Slots {
+	fn bind<'a>(&mut self, alias: &'a Option<String>, what: &'a [String]) -> Binding<'a> {
+		let slot = match alias {
+			Some(alias) => *self.index.entry(alias.clone()).or_insert_with(|| {
+				self.names.push(Some(alias.clone()));
+				self.names.len() - 1
+			}),
+			None => {
+				self.names.push(None);
+				self.names.len() - 1
+			}
+		};
+		Binding {
+			slot,
+			alias: alias.as_deref(),
+			what,
+		}
+	}
+
+	fn plan<'a>(&mut self, source: &'a MatchPattern) -> Plan<'a> {
+		let mut nodes = vec![self.bind(&source.start.alias, &source.start.what)];
+		let mut edges = Vec::new();
+		for (edge, node) in source.steps.iter() {
+			edges.push((self.bind(&edge.alias, &edge.what), edge.dir.clone()));
+			nodes.push(self.bind(&node.alias, &node.what));
+		}
+		Plan {
+			source,
+			nodes,
+			edges,
+		}
+	}
+}
+
+impl MatchStatement {
+	/// Check if computing this type can be done on a read only transaction.
+	pub(crate) fn read_only(&self) -> bool {
+		self.expr.read_only() && self.cond.as_ref().map(|x| x.0.read_only()).unwrap_or(true)
+	}
+
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Valid options?
+		opt.valid_for_db()?;
+		// Assign the pattern bindings to slots
+		let mut slots = Slots::default();
+		let plans = self.patterns.iter().map(|p| slots.plan(p)).collect::<Vec<_>>();
+		// Split the condition, so that parts of it can be pushed to the start nodes
+		let mut conds = Vec::new();
+		if let Some(cond) = &self.cond {
+			conjuncts(&cond.0, &mut conds);
+		}
+		// Filter the records bound to each alias by the conditions which concern it
+		let filters = slots
+			.names
+			.iter()
+			.enumerate()
+			.filter_map(|(slot, name)| {
+				let alias = name.as_deref()?;
+				let cond = conds.iter().filter_map(|c| pushdown(c, alias)).map(|(c, _)| c).reduce(
+					|left, right| Expr::Binary {
+						left: Box::new(left),
+						op: BinaryOperator::And,
+						right: Box::new(right),
+					},
+				)?;
+				Some((slot, Cond(cond)))
+			})
+			.collect::<HashMap<_, _>>();
+		// Match each pattern in turn, joining on the slots which are already bound
+		let mut rows: Vec<Row> = vec![vec![None; slots.names.len()]];
+		for plan in plans.iter() {
+			let Some(first) = rows.first() else {
+				break;
+			};
+			let mut next = Vec::new();
+			match plan.nodes.iter().position(|n| first[n.slot].is_some()) {
+				// Expand each row from a node which an earlier pattern bound
+				Some(pos) => {
+					for row in rows {
+						next.extend(self.expand(stk, ctx, opt, plan, &filters, pos, row).await?);
+						ensure!(next.len() <= *MATCH_ROW_LIMIT, exceeded());
+					}
+				}
+				// Otherwise choose the start nodes, and join them with each row
+				None => {
+					let (pos, ids) = self.plan_start(stk, ctx, opt, plan, &conds).await?;
+					for row in rows {
+						for id in ids.iter() {
+							let mut row = row.clone();
+							row[plan.nodes[pos].slot] = Some(id.clone());
+							next.extend(
+								self.expand(stk, ctx, opt, plan, &filters, pos, row).await?,
+							);
+							ensure!(next.len() <= *MATCH_ROW_LIMIT, exceeded());
+						}
+					}
+				}
+			}
+			rows = next;
+		}
+		// Output the named bindings of each matched row
+		let rows = rows
+			.into_iter()
+			.map(|row| {
+				let obj = slots
+					.names
+					.iter()
+					.zip(row)
+					.filter_map(|(name, rid)| Some((name.clone()?, Value::RecordId(rid?))))
+					.collect::<BTreeMap<_, _>>();
+				Value::from(obj)
+			})
+			.collect::<Vec<_>>();
+		// Filter, order and project the rows
+		let stm = SelectStatement {
+			expr: self.expr.clone(),
+			what: vec![Value::from(rows).into_literal()],
+			cond: self.cond.clone(),
+			order: self.order.clone(),
+			limit: self.limit.clone(),
+			start: self.start.clone(),
+			..SelectStatement::default()
+		};
+		stk.run(|stk| stm.compute(stk, ctx, opt, doc)).await
+	}
+
+	/// Chooses the node from which to start matching a pattern, preferring
+	/// nodes with conditions on indexed fields, and fetches its records
+	async fn plan_start(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		plan: &Plan<'_>,
+		conds: &[&Expr],
+	) -> Result<(usize, Vec<RecordId>)> {
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let txn = ctx.tx();
+		let mut best: Option<((bool, usize), usize, Vec<Expr>)> = None;
+		for (pos, node) in plan.nodes.iter().enumerate() {
+			if node.what.is_empty() {
+				continue;
+			}
+			// Rewrite the conditions which only concern this node
+			let pushed: Vec<(Expr, Idiom)> = match node.alias {
+				Some(alias) => conds.iter().filter_map(|c| pushdown(c, alias)).collect(),
+				None => Vec::new(),
+			};
+			// Check if any of the conditions can make use of an index
+			let mut indexed = false;
+			for tb in node.what.iter() {
+				let ixs = txn.all_tb_indexes(ns, db, tb).await?;
+				indexed |= pushed
+					.iter()
+					.any(|(_, idiom)| ixs.iter().any(|ix| ix.cols.first() == Some(idiom)));
+			}
+			let score = (indexed, pushed.len());
+			if best.as_ref().is_none_or(|(s, ..)| score > *s) {
+				best = Some((score, pos, pushed.into_iter().map(|(c, _)| c).collect()));
+			}
+		}
+		let Some((_, pos, pushed)) = best else {
+			bail!(Error::MatchNoStart {
+				pattern: plan.source.to_string(),
+			});
+		};
+		// Select the start records, letting the query planner pick an index
+		let cond = pushed.into_iter().reduce(|left, right| Expr::Binary {
+			left: Box::new(left),
+			op: BinaryOperator::And,
+			right: Box::new(right),
+		});
+		// Fetch one record more than the row limit, to detect when it is exceeded
+		let limit = i64::try_from(*MATCH_ROW_LIMIT).unwrap_or(i64::MAX).saturating_add(1);
+		let stm = SelectStatement {
+			expr: Fields::value_id(),
+			what: plan.nodes[pos].what.iter().cloned().map(Expr::Table).collect(),
+			cond: cond.map(Cond),
+			limit: Some(Limit(Expr::Literal(Literal::Integer(limit)))),
+			..SelectStatement::default()
+		};
+		let res = stk.run(|stk| stm.compute(stk, ctx, opt, None)).await?;
+		let ids = records(res);
+		ensure!(ids.len() <= *MATCH_ROW_LIMIT, exceeded());
+		Ok((pos, ids))
+	}
+
+	/// Expands a row outwards from the node at the given position, following
+	/// the edges on either side of it, and filtering each newly bound record
+	/// by the conditions which were pushed down to its alias
+	#[expect(clippy::too_many_arguments)]
+	async fn expand(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		plan: &Plan<'_>,
+		filters: &HashMap<usize, Cond>,
+		pos: usize,
+		row: Row,
+	) -> Result<Vec<Row>> {
+		let mut rows = vec![row];
+		// Follow the edges after the start node, then the edges before it
+		let steps = (pos..plan.edges.len())
+			.map(|i| (i, i + 1, plan.edges[i].1.clone()))
+			.chain((0..pos).rev().map(|i| (i + 1, i, reverse(&plan.edges[i].1))));
+		for (from, to, dir) in steps {
+			let (edge, node) = (&plan.edges[from.min(to)].0, &plan.nodes[to]);
+			// Every row has the same slots bound at each step
+			let Some(first) = rows.first() else {
+				break;
+			};
+			let fresh = [edge.slot, node.slot]
+				.into_iter()
+				.filter(|slot| first[*slot].is_none())
+				.collect::<Vec<_>>();
+			let mut next = Vec::new();
+			for row in rows {
+				// Check if the context is finished
+				if let Some(reason) = ctx.done(true)? {
+					bail!(Error::from(reason));
+				}
+				let Some(rid) = &row[plan.nodes[from].slot] else {
+					continue;
+				};
+				for (e, n) in step(stk, ctx, opt, rid, &dir, edge.what, node.what).await? {
+					let mut row = row.clone();
+					if bind(&mut row, edge.slot, e) && bind(&mut row, node.slot, n) {
+						next.push(row);
+					}
+				}
+				ensure!(next.len() <= *MATCH_ROW_LIMIT, exceeded());
+			}
+			for slot in fresh {
+				if let Some(cond) = filters.get(&slot) {
+					next = filter(stk, ctx, opt, next, slot, cond).await?;
+				}
+			}
+			rows = next;
+		}
+		Ok(rows)
+	}
+}
+
+fn exceeded() -> Error {
+	Error::MatchRowLimitExceeded {
+		limit: *MATCH_ROW_LIMIT,
+	}
+}
+
+/// Keeps the rows whose record in the given slot matches a condition,
+/// checking each distinct record once
+async fn filter(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	rows: Vec<Row>,
+	slot: usize,
+	cond: &Cond,
+) -> Result<Vec<Row>> {
+	if rows.is_empty() {
+		return Ok(rows);
+	}
+	let ids = rows
+		.iter()
+		.filter_map(|row| row[slot].clone())
+		.collect::<HashSet<_>>()
+		.into_iter()
+		.map(Value::RecordId)
+		.collect::<Vec<_>>();
+	let stm = SelectStatement {
+		expr: Fields::value_id(),
+		what: vec![Value::from(ids).into_literal()],
+		cond: Some(cond.clone()),
+		..SelectStatement::default()
+	};
+	let res = stk.run(|stk| stm.compute(stk, ctx, opt, None)).await?;
+	let ids = records(res).into_iter().collect::<HashSet<_>>();
+	Ok(rows
+		.into_iter()
+		.filter(|row| row[slot].as_ref().is_some_and(|id| ids.contains(id)))
+		.collect())
+}
+
+/// Binds a record to a slot, failing if the slot holds a different record
+fn bind(row: &mut Row, slot: usize, rid: RecordId) -> bool {
+	match &row[slot] {
+		Some(x) => *x == rid,
+		None => {
+			row[slot] = Some(rid);
+			true
+		}
+	}
+}
+
+fn reverse(dir: &Dir) -> Dir {
+	match dir {
+		Dir::In => Dir::Out,
+		Dir::Out => Dir::In,
+		Dir::Both => Dir::Both,
+	}
+}
+
+fn records(v: Value) -> Vec<RecordId> {
+	match v {
+		Value::Array(v) => v
+			.into_iter()
+			.filter_map(|v| match v {
+				Value::RecordId(v) => Some(v),
+				_ => None,
+			})
+			.collect(),
+		_ => Vec::new(),
+	}
+}
+
+/// Follows the edges from a record, returning each edge and the record at
+/// its other end. The lookups apply the table permissions of the edges and
+/// of the records which they point to.
+async fn step(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	from: &RecordId,
+	dir: &Dir,
+	edge: &[String],
+	node: &[String],
+) -> Result<Vec<(RecordId, RecordId)>> {
+	let dirs = match dir {
+		Dir::Both => vec![Dir::Out, Dir::In],
+		dir => vec![dir.clone()],
+	};
+	let mut res = Vec::new();
+	for dir in dirs {
+		for e in lookup(stk, ctx, opt, from, &dir, edge).await? {
+			for n in lookup(stk, ctx, opt, &e, &dir, node).await? {
+				res.push((e.clone(), n));
+			}
+		}
+	}
+	Ok(res)
+}
+
+async fn lookup(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	from: &RecordId,
+	dir: &Dir,
+	what: &[String],
+) -> Result<Vec<RecordId>> {
+	let path = [Part::Lookup(Lookup {
+		kind: LookupKind::Graph(dir.clone()),
+		what: what.iter().cloned().map(LookupSubject::Table).collect(),
+		..Default::default()
+	})];
+	let from = Value::RecordId(from.clone());
+	let res = stk.run(|stk| from.get(stk, ctx, opt, None, &path)).await.catch_return()?;
+	Ok(records(res))
+}
+
+/// Splits a condition into the expressions which are joined by `AND`
+fn conjuncts<'a>(expr: &'a Expr, res: &mut Vec<&'a Expr>) {
+	match expr {
+		Expr::Binary {
+			left,
+			op: BinaryOperator::And,
+			right,
+		} => {
+			conjuncts(left, res);
+			conjuncts(right, res);
+		}
+		x => res.push(x),
+	}
+}
+
+/// Rewrites a comparison between a field of the aliased node and a static
+/// value, so that it can be evaluated against the node's table directly.
+/// Returns the rewritten expression along with the field which it compares.
+fn pushdown(expr: &Expr, alias: &str) -> Option<(Expr, Idiom)> {
+	let Expr::Binary {
+		left,
+		op,
+		right,
+	} = expr
+	else {
+		return None;
+	};
+	let field = |x: &Expr| match x {
+		Expr::Idiom(i) if i.len() > 1 && i[0] == Part::Field(alias.to_owned()) => {
+			Some(Idiom(i[1..].to_vec()))
+		}
+		_ => None,
+	};
+	let value = |x: &Expr| match x {
+		Expr::Literal(x) => x.is_static(),
+		Expr::Param(x) => !matches!(x.as_str(), "this" | "parent"),
+		Expr::Constant(_) => true,
+		_ => false,
+	};
+	let (field, left, right) = match (field(left), field(right)) {
+		(Some(f), None) if value(right) => (f.clone(), Expr::Idiom(f), (**right).clone()),
+		(None, Some(f)) if value(left) => (f.clone(), (**left).clone(), Expr::Idiom(f)),
+		_ => return None,
+	};
+	let expr = Expr::Binary {
+		left: Box::new(left),
+		op: op.clone(),
+		right: Box::new(right),
+	};
+	Some((expr, field))
+}
+
+impl VisitExpression for MatchStatement {
+	fn visit<F>(&self, visitor: &mut F)
+	where
+		F: FnMut(&Expr),
+	{
+		self.cond.iter().for_each(|cond| cond.0.visit(visitor));
+		self.expr.visit(visitor);
+		self.order.iter().for_each(|order| order.visit(visitor));
+		self.limit.iter().for_each(|limit| limit.visit(visitor));
+		self.start.iter().for_each(|start| start.visit(visitor));
+	}
+}
+
+fn fmt_binding(f: &mut Formatter, alias: &Option<String>, what: &[String]) -> fmt::Result {
+	if let Some(alias) = alias {
+		EscapeIdent(alias).fmt(f)?;
+	}
+	for (i, tb) in what.iter().enumerate() {
+		f.write_char(if i == 0 {
+			':'
+		} else {
+			'|'
+		})?;
+		EscapeIdent(tb).fmt(f)?;
+	}
+	Ok(())
+}
+
+impl Display for MatchNode {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_char('(')?;
+		fmt_binding(f, &self.alias, &self.what)?;
+		f.write_char(')')
+	}
+}
+
+impl Display for MatchEdge {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if self.alias.is_none() && self.what.is_empty() {
+			return Display::fmt(&self.dir, f);
+		}
+		f.write_str(match self.dir {
+			Dir::In => "<-[",
+			_ => "-[",
+		})?;
+		fmt_binding(f, &self.alias, &self.what)?;
+		f.write_str(match self.dir {
+			Dir::Out => "]->",
+			_ => "]-",
+		})
+	}
+}
+
+impl Display for MatchPattern {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		self.start.fmt(f)?;
+		for (edge, node) in self.steps.iter() {
+			write!(f, "{edge}{node}")?;
+		}
+		Ok(())
+	}
+}
+
+impl Display for MatchStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "MATCH {}", Fmt::comma_separated(self.patterns.iter()))?;
+		if let Some(ref v) = self.cond {
+			write!(f, " {v}")?
+		}
+		write!(f, " RETURN {}", self.expr)?;
+		if let Some(ref v) = self.order {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.limit {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.start {
+			write!(f, " {v}")?
+		}
+		Ok(())
+	}
+}
@@ -11,6 +11,7 @@ pub(crate) mod info;
 pub(crate) mod insert;
 pub(crate) mod kill;
 pub(crate) mod live;
+pub(crate) mod r#match;
 pub(crate) mod option;
 pub(crate) mod output;
 pub(crate) mod rebuild;
@@ -30,8 +31,8 @@ pub use self::create::CreateStatement;
 pub use self::define::{
 	DefineAccessStatement, DefineAnalyzerStatement, DefineApiStatement, DefineDatabaseStatement,
 	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
-	DefineModelStatement, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
-	DefineTableStatement, DefineUserStatement,
+	DefineModelStatement, DefineModuleStatement, DefineNamespaceStatement, DefineParamStatement,
+	DefineRoleStatement, DefineStatement, DefineTableStatement, DefineUserStatement,
 };
 pub use self::delete::DeleteStatement;
 pub use self::foreach::ForeachStatement;
@@ -40,6 +41,7 @@ pub use self::info::InfoStatement;
 pub use self::insert::InsertStatement;
 pub use self::kill::KillStatement;
 pub use self::live::LiveStatement;
+pub use self::r#match::MatchStatement;
 pub use self::option::OptionStatement;
 pub use self::output::OutputStatement;
 pub use self::rebuild::RebuildStatement;
@@ -47,8 +49,8 @@ pub use self::relate::RelateStatement;
 pub use self::remove::{
 	RemoveAccessStatement, RemoveAnalyzerStatement, RemoveDatabaseStatement, RemoveEventStatement,
 	RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement, RemoveModelStatement,
-	RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
-	RemoveUserStatement,
+	RemoveModuleStatement, RemoveNamespaceStatement, RemoveParamStatement, RemoveRoleStatement,
+	RemoveStatement, RemoveTableStatement, RemoveUserStatement,
 };
 pub use self::select::SelectStatement;
 pub use self::set::SetStatement;
@@ -61,7 +61,7 @@ impl RebuildIndexStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Index, &Base::Db)?;
+		opt.is_allowed_on(Action::Define, ResourceKind::Index, &self.name, &Base::Db)?;
 		// Get the index definition
 		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
 		let res = ctx.tx().get_tb_index(ns, db, &self.what, &self.name).await?;
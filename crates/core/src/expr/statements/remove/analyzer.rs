@@ -47,7 +47,7 @@ impl RemoveAnalyzerStatement {
 	) -> Result<Value> {
 		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Analyzer, &Base::Db)?;
+		opt.is_allowed(Action::Remove, ResourceKind::Analyzer, &Base::Db)?;
 		// Compute the name
 		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "analyzer name").await?;
 		// Get the transaction
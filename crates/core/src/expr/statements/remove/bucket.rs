@@ -46,7 +46,7 @@ impl RemoveBucketStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Bucket, &Base::Db)?;
+		opt.is_allowed(Action::Remove, ResourceKind::Bucket, &Base::Db)?;
 		// Compute the name
 		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "bucket name").await?;
 		// Get the transaction
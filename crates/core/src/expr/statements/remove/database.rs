@@ -48,7 +48,7 @@ impl RemoveDatabaseStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Database, &Base::Ns)?;
+		opt.is_allowed(Action::Remove, ResourceKind::Database, &Base::Ns)?;
 		// Get the transaction
 		let txn = ctx.tx();
 
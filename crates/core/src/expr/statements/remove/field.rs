@@ -51,7 +51,7 @@ impl RemoveFieldStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Field, &Base::Db)?;
+		opt.is_allowed(Action::Remove, ResourceKind::Field, &Base::Db)?;
 		// Compute the table name
 		let table_name = expr_to_ident(stk, ctx, opt, doc, &self.table_name, "table name").await?;
 		// Compute the name
@@ -19,7 +19,7 @@ impl RemoveFunctionStatement {
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Function, &Base::Db)?;
+		opt.is_allowed_on(Action::Remove, ResourceKind::Function, &self.name, &Base::Db)?;
 		// Get the transaction
 		let txn = ctx.tx();
 		// Get the definition
@@ -50,10 +50,10 @@ impl RemoveIndexStatement {
 		opt: &Options,
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
-		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Index, &Base::Db)?;
 		// Compute the name
 		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "index name").await?;
+		// Allowed to run?
+		opt.is_allowed_on(Action::Remove, ResourceKind::Index, &name, &Base::Db)?;
 		// Compute the what
 		let what = expr_to_ident(stk, ctx, opt, doc, &self.what, "what").await?;
 		// Get the NS and DB
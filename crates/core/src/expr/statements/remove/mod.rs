@@ -8,8 +8,10 @@ mod field;
 mod function;
 mod index;
 mod model;
+mod module;
 mod namespace;
 mod param;
+mod role;
 mod sequence;
 mod table;
 mod user;
@@ -27,9 +29,11 @@ pub use field::RemoveFieldStatement;
 pub use function::RemoveFunctionStatement;
 pub use index::RemoveIndexStatement;
 pub use model::RemoveModelStatement;
+pub use module::RemoveModuleStatement;
 pub use namespace::RemoveNamespaceStatement;
 pub use param::RemoveParamStatement;
 use reblessive::tree::Stk;
+pub use role::RemoveRoleStatement;
 pub use sequence::RemoveSequenceStatement;
 pub use table::RemoveTableStatement;
 pub use user::RemoveUserStatement;
@@ -53,6 +57,8 @@ pub enum RemoveStatement {
 	Field(RemoveFieldStatement),
 	Index(RemoveIndexStatement),
 	User(RemoveUserStatement),
+	Role(RemoveRoleStatement),
+	Module(RemoveModuleStatement),
 	Model(RemoveModelStatement),
 	Api(RemoveApiStatement),
 	Bucket(RemoveBucketStatement),
@@ -80,6 +86,8 @@ impl RemoveStatement {
 			Self::Index(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Analyzer(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::User(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Role(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Module(v) => v.compute(ctx, opt).await,
 			Self::Model(v) => v.compute(ctx, opt).await,
 			Self::Api(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Bucket(v) => v.compute(stk, ctx, opt, doc).await,
@@ -103,11 +111,13 @@ impl VisitExpression for RemoveStatement {
 			RemoveStatement::Field(field) => field.visit(visitor),
 			RemoveStatement::Index(index) => index.visit(visitor),
 			RemoveStatement::User(user) => user.visit(visitor),
+			RemoveStatement::Role(role) => role.visit(visitor),
 			RemoveStatement::Api(api) => api.visit(visitor),
 			RemoveStatement::Bucket(bucket) => bucket.visit(visitor),
 			RemoveStatement::Sequence(sequence) => sequence.visit(visitor),
 			RemoveStatement::Model(_)
 			| RemoveStatement::Function(_)
+			| RemoveStatement::Module(_)
 			| RemoveStatement::Param(_) => {}
 		}
 	}
@@ -127,6 +137,8 @@ impl Display for RemoveStatement {
 			Self::Index(v) => Display::fmt(v, f),
 			Self::Analyzer(v) => Display::fmt(v, f),
 			Self::User(v) => Display::fmt(v, f),
+			Self::Role(v) => Display::fmt(v, f),
+			Self::Module(v) => Display::fmt(v, f),
 			Self::Model(v) => Display::fmt(v, f),
 			Self::Api(v) => Display::fmt(v, f),
 			Self::Bucket(v) => Display::fmt(v, f),
@@ -20,7 +20,7 @@ impl RemoveModelStatement {
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Model, &Base::Db)?;
+		opt.is_allowed(Action::Remove, ResourceKind::Model, &Base::Db)?;
 		// Get the transaction
 		let txn = ctx.tx();
 		// Get the defined model
@@ -42,6 +42,10 @@ impl RemoveModelStatement {
 		txn.del(&key).await?;
 		// Clear the cache
 		txn.clear_cache();
+		// Clear the parsed model from the datastore cache
+		if let Some(cache) = ctx.get_cache() {
+			cache.clear_ml(ns, db, &ml.name, &ml.version, &ml.hash);
+		}
 		// TODO Remove the model file from storage
 		// Ok all good
 		Ok(Value::None)
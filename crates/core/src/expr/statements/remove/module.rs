@@ -0,0 +1,60 @@
+use std::fmt::{self, Display};
+
+use anyhow::Result;
+
+use crate::catalog::providers::DatabaseProvider;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::expr::{Base, Value};
+use crate::iam::{Action, ResourceKind};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct RemoveModuleStatement {
+	pub name: String,
+	pub if_exists: bool,
+}
+
+impl RemoveModuleStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Remove, ResourceKind::Function, &Base::Db)?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the definition
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let md = match txn.get_db_module(ns, db, &self.name).await {
+			Ok(x) => x,
+			Err(e) => {
+				if self.if_exists && matches!(e.downcast_ref(), Some(Error::MdNotFound { .. })) {
+					return Ok(Value::None);
+				} else {
+					return Err(e);
+				}
+			}
+		};
+		// Delete the definition
+		let key = crate::key::database::md::new(ns, db, &md.name);
+		txn.del(&key).await?;
+		// Clear the compiled bytecode
+		if let Some(cache) = ctx.get_cache() {
+			cache.clear_md(ns, db, &md.name, md.checksum());
+		}
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for RemoveModuleStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "REMOVE MODULE")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " mod::{}", self.name)?;
+		Ok(())
+	}
+}
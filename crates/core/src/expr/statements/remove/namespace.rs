@@ -49,7 +49,7 @@ impl RemoveNamespaceStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Namespace, &Base::Root)?;
+		opt.is_allowed(Action::Remove, ResourceKind::Namespace, &Base::Root)?;
 		// Get the transaction
 		let txn = ctx.tx();
 		// Compute the name
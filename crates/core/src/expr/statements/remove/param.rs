@@ -20,7 +20,7 @@ impl RemoveParamStatement {
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Parameter, &Base::Db)?;
+		opt.is_allowed_on(Action::Remove, ResourceKind::Parameter, &self.name, &Base::Db)?;
 		// Get the transaction
 		let txn = ctx.tx();
 		// Get the definition
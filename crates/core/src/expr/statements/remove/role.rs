@@ -0,0 +1,150 @@
+use std::fmt::{self, Display, Formatter};
+
+use anyhow::Result;
+use reblessive::tree::Stk;
+
+use crate::catalog::providers::UserProvider;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::expression::VisitExpression;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, Literal, Value};
+use crate::iam::{Action, ResourceKind};
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct RemoveRoleStatement {
+	pub name: Expr,
+	pub base: Base,
+	pub if_exists: bool,
+}
+
+impl VisitExpression for RemoveRoleStatement {
+	fn visit<F>(&self, visitor: &mut F)
+	where
+		F: FnMut(&Expr),
+	{
+		self.name.visit(visitor);
+	}
+}
+impl Default for RemoveRoleStatement {
+	fn default() -> Self {
+		Self {
+			name: Expr::Literal(Literal::None),
+			base: Base::default(),
+			if_exists: false,
+		}
+	}
+}
+
+impl RemoveRoleStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Remove, ResourceKind::Actor, &self.base)?;
+		// Compute the name
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "role name").await?;
+		// Check the statement type
+		match self.base {
+			Base::Root => {
+				// Get the transaction
+				let txn = ctx.tx();
+				// Get the definition
+				let rl = match txn.get_root_role(&name).await? {
+					Some(x) => x,
+					None => {
+						if self.if_exists {
+							return Ok(Value::None);
+						}
+
+						return Err(Error::RoleRootNotFound {
+							name,
+						}
+						.into());
+					}
+				};
+
+				// Process the statement
+				let key = crate::key::root::rl::new(&rl.name);
+				txn.del(&key).await?;
+				// Clear the cache
+				txn.clear_cache();
+				// Ok all good
+				Ok(Value::None)
+			}
+			Base::Ns => {
+				// Get the transaction
+				let txn = ctx.tx();
+				// Get the definition
+				let ns = ctx.get_ns_id(opt).await?;
+				let rl = match txn.get_ns_role(ns, &name).await? {
+					Some(x) => x,
+					None => {
+						if self.if_exists {
+							return Ok(Value::None);
+						}
+
+						return Err(Error::RoleNsNotFound {
+							ns: opt.ns()?.to_string(),
+							name,
+						}
+						.into());
+					}
+				};
+				// Delete the definition
+				let key = crate::key::namespace::rl::new(ns, &rl.name);
+				txn.del(&key).await?;
+				// Clear the cache
+				txn.clear_cache();
+				// Ok all good
+				Ok(Value::None)
+			}
+			Base::Db => {
+				// Get the transaction
+				let txn = ctx.tx();
+				// Get the definition
+				let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+				let rl = match txn.get_db_role(ns, db, &name).await? {
+					Some(x) => x,
+					None => {
+						if self.if_exists {
+							return Ok(Value::None);
+						}
+
+						return Err(Error::RoleDbNotFound {
+							ns: opt.ns()?.to_string(),
+							db: opt.db()?.to_string(),
+							name,
+						}
+						.into());
+					}
+				};
+				// Delete the definition
+				let key = crate::key::database::rl::new(ns, db, &rl.name);
+				txn.del(&key).await?;
+				// Clear the cache
+				txn.clear_cache();
+				// Ok all good
+				Ok(Value::None)
+			}
+		}
+	}
+}
+
+impl Display for RemoveRoleStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "REMOVE ROLE")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", self.name, self.base)?;
+		Ok(())
+	}
+}
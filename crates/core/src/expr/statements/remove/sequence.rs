@@ -47,7 +47,7 @@ impl RemoveSequenceStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Sequence, &Base::Db)?;
+		opt.is_allowed(Action::Remove, ResourceKind::Sequence, &Base::Db)?;
 		// Compute the name
 		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "sequence name").await?;
 		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
@@ -49,10 +49,10 @@ impl RemoveTableStatement {
 		opt: &Options,
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
-		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
 		// Compute the name
 		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "table name").await?;
+		// Allowed to run?
+		opt.is_allowed_on(Action::Remove, ResourceKind::Table, &name, &Base::Db)?;
 		// Get the NS and DB
 		let (ns_name, db_name) = opt.ns_db()?;
 		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
@@ -136,6 +136,7 @@ impl RemoveTableStatement {
 						action: dbs::Action::Killed,
 						record: Value::None,
 						result: Value::None,
+						versionstamp: None,
 					})
 					.await;
 			}
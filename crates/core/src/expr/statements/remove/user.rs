@@ -48,7 +48,7 @@ impl RemoveUserStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+		opt.is_allowed(Action::Remove, ResourceKind::Actor, &self.base)?;
 		// Compute the name
 		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "user name").await?;
 		// Check the statement type
@@ -74,6 +74,9 @@ impl RemoveUserStatement {
 				// Process the statement
 				let key = crate::key::root::us::new(&us.name);
 				txn.del(&key).await?;
+				// Delete any failed signin attempts
+				let key = crate::key::root::lk::new(&us.name);
+				txn.del(&key).await?;
 				// Clear the cache
 				txn.clear_cache();
 				// Ok all good
@@ -101,6 +104,9 @@ impl RemoveUserStatement {
 				// Delete the definition
 				let key = crate::key::namespace::us::new(ns, &us.name);
 				txn.del(&key).await?;
+				// Delete any failed signin attempts
+				let key = crate::key::namespace::lk::new(ns, &us.name);
+				txn.del(&key).await?;
 				// Clear the cache
 				txn.clear_cache();
 				// Ok all good
@@ -129,6 +135,9 @@ impl RemoveUserStatement {
 				// Delete the definition
 				let key = crate::key::database::us::new(ns, db, &us.name);
 				txn.del(&key).await?;
+				// Delete any failed signin attempts
+				let key = crate::key::database::lk::new(ns, db, &us.name);
+				txn.del(&key).await?;
 				// Clear the cache
 				txn.clear_cache();
 				// Ok all good
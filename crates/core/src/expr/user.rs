@@ -1,5 +1,15 @@
+use std::fmt::{self, Display, Formatter};
+use std::time;
+
+use anyhow::{Result, ensure};
+use chrono::Utc;
+use revision::revisioned;
+
+use crate::err::Error;
+use crate::expr::statements::info::InfoStructure;
 use crate::expr::{Expr, Literal};
-use crate::val::Duration;
+use crate::fmt::{Fmt, QuoteStr};
+use crate::val::{Array, Datetime, Duration, Value};
 
 #[derive(Debug, Hash, Clone, Eq, PartialEq)]
 // Durations representing the expiration of different elements of user
@@ -24,3 +34,215 @@ impl Default for UserDuration {
 		}
 	}
 }
+
+/// The TOTP second factor of a user
+#[derive(Debug, Hash, Clone, Eq, PartialEq)]
+pub enum UserTotp {
+	/// Enrol the user with a newly generated secret and recovery codes
+	Generate,
+	/// Enrol the user with an existing secret and hashed recovery codes
+	Secret {
+		secret: String,
+		recovery: Vec<String>,
+	},
+}
+
+impl Display for UserTotp {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "TOTP")?;
+		if let UserTotp::Secret {
+			secret,
+			recovery,
+		} = self
+		{
+			write!(f, " SECRET {}", QuoteStr(secret))?;
+			if !recovery.is_empty() {
+				write!(
+					f,
+					" RECOVERY {}",
+					Fmt::comma_separated(recovery.iter().map(|x| QuoteStr(x)))
+				)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// A class of characters which a password can be required to contain
+#[revisioned(revision = 1)]
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq)]
+pub enum PasswordClass {
+	Uppercase,
+	Lowercase,
+	Numeric,
+	Punct,
+}
+
+impl PasswordClass {
+	/// Checks whether a character belongs to this class
+	fn matches(&self, c: char) -> bool {
+		match self {
+			Self::Uppercase => c.is_uppercase(),
+			Self::Lowercase => c.is_lowercase(),
+			Self::Numeric => c.is_numeric(),
+			Self::Punct => !c.is_alphanumeric() && !c.is_whitespace(),
+		}
+	}
+
+	/// A human readable description of this class
+	fn describe(&self) -> &'static str {
+		match self {
+			Self::Uppercase => "an uppercase character",
+			Self::Lowercase => "a lowercase character",
+			Self::Numeric => "a numeric character",
+			Self::Punct => "a punctuation character",
+		}
+	}
+}
+
+impl Display for PasswordClass {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Uppercase => f.write_str("UPPERCASE"),
+			Self::Lowercase => f.write_str("LOWERCASE"),
+			Self::Numeric => f.write_str("NUMERIC"),
+			Self::Punct => f.write_str("PUNCT"),
+		}
+	}
+}
+
+/// The number of failed signins after which an account is locked, and the
+/// duration for which it then remains locked
+#[revisioned(revision = 1)]
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq)]
+pub struct PasswordLockout {
+	pub attempts: u64,
+	pub duration: time::Duration,
+}
+
+/// The password policy of a user or record access method
+#[revisioned(revision = 1)]
+#[derive(Debug, Hash, Clone, Default, Eq, PartialEq)]
+pub struct PasswordPolicy {
+	// The minimum number of characters in a password
+	pub length: Option<u64>,
+	// The classes of characters which a password must contain
+	pub require: Vec<PasswordClass>,
+	// Duration after which a password expires, and must be changed
+	pub expiry: Option<time::Duration>,
+	// The number of previous passwords which can not be reused
+	pub history: Option<u64>,
+	// The lockout which is applied after repeated failed signins
+	pub lockout: Option<PasswordLockout>,
+}
+
+impl PasswordPolicy {
+	/// Checks that a password satisfies the complexity requirements of this
+	/// policy
+	pub(crate) fn check(&self, pass: &str) -> Result<()> {
+		if let Some(length) = self.length {
+			ensure!(
+				pass.chars().count() as u64 >= length,
+				Error::PasswordPolicy {
+					message: format!("The password must contain at least {length} characters"),
+				}
+			);
+		}
+		for class in self.require.iter() {
+			ensure!(
+				pass.chars().any(|c| class.matches(c)),
+				Error::PasswordPolicy {
+					message: format!("The password must contain {}", class.describe()),
+				}
+			);
+		}
+		Ok(())
+	}
+
+	/// Checks whether a password which was last changed at the specified
+	/// time has expired
+	pub(crate) fn expired(&self, changed: &Datetime) -> bool {
+		match self.expiry.and_then(|v| chrono::Duration::from_std(v).ok()) {
+			Some(expiry) => changed.0 + expiry < Utc::now(),
+			None => false,
+		}
+	}
+}
+
+impl Display for PasswordPolicy {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "POLICY")?;
+		if let Some(v) = self.length {
+			write!(f, " LENGTH {v}")?;
+		}
+		if !self.require.is_empty() {
+			write!(f, " REQUIRE {}", Fmt::comma_separated(self.require.iter()))?;
+		}
+		if let Some(v) = self.expiry {
+			write!(f, " EXPIRY {}", Duration(v))?;
+		}
+		if let Some(v) = self.history {
+			write!(f, " HISTORY {v}")?;
+		}
+		if let Some(ref v) = self.lockout {
+			write!(f, " LOCKOUT {} FOR {}", v.attempts, Duration(v.duration))?;
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for PasswordPolicy {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"length".to_string(), if let Some(v) = self.length => v.into(),
+			"require".to_string(), if !self.require.is_empty() => Array::from(
+				self.require.iter().map(|x| Value::from(x.to_string())).collect::<Vec<_>>()
+			).into(),
+			"expiry".to_string(), if let Some(v) = self.expiry => Duration(v).structure(),
+			"history".to_string(), if let Some(v) = self.history => v.into(),
+			"lockout".to_string(), if let Some(v) = self.lockout => Value::from(map! {
+				"attempts".to_string() => v.attempts.into(),
+				"duration".to_string() => Duration(v.duration).structure(),
+			}),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_password_policy_check() {
+		let policy = PasswordPolicy {
+			length: Some(8),
+			require: vec![PasswordClass::Uppercase, PasswordClass::Numeric, PasswordClass::Punct],
+			..Default::default()
+		};
+		assert!(policy.check("Hunter2!").is_ok());
+		assert!(policy.check("Hunt2!").is_err());
+		assert!(policy.check("hunter22!").is_err());
+		assert!(policy.check("Hunterrr!").is_err());
+		assert!(policy.check("Hunter222").is_err());
+		// Length is measured in characters rather than bytes
+		let policy = PasswordPolicy {
+			length: Some(4),
+			..Default::default()
+		};
+		assert!(policy.check("ñññ").is_err());
+		assert!(policy.check("ññññ").is_ok());
+	}
+
+	#[test]
+	fn test_password_policy_expired() {
+		let policy = PasswordPolicy {
+			expiry: Some(time::Duration::from_secs(3600)),
+			..Default::default()
+		};
+		assert!(!policy.expired(&Datetime::now()));
+		assert!(policy.expired(&Datetime(Utc::now() - chrono::Duration::hours(2))));
+		assert!(
+			!PasswordPolicy::default().expired(&Datetime(Utc::now() - chrono::Duration::days(365)))
+		);
+	}
+}
@@ -29,6 +29,32 @@ impl Arity {
 			upper: self.upper.and_then(|a| other.upper.map(|b| a + b)),
 		}
 	}
+
+	/// Returns whether the specified number of arguments is accepted.
+	pub fn accepts(&self, len: usize) -> bool {
+		len >= self.lower && self.upper.is_none_or(|upper| len <= upper)
+	}
+
+	/// Describes the number of arguments which are expected.
+	pub fn expected(&self) -> String {
+		if let Some(upper) = self.upper {
+			if upper == self.lower {
+				if upper == 0 {
+					"Expected no arguments".to_string()
+				} else if upper == 1 {
+					"Expected 1 argument".to_string()
+				} else {
+					format!("Expected {upper} arguments")
+				}
+			} else {
+				format!("Expected {} to {} arguments", self.lower, upper)
+			}
+		} else if self.lower == 0 {
+			"Expected zero or more arguments".to_string()
+		} else {
+			format!("Expected {} or more arguments", self.lower)
+		}
+	}
 }
 
 pub struct Args {
@@ -78,9 +104,23 @@ pub trait FromArg: Sized {
 }
 
 pub trait FromArgs: Sized {
+	/// Returns the number of arguments which are accepted, or `None` if the
+	/// arguments are checked by the function itself.
+	fn arity() -> Option<Arity>;
 	fn from_args(name: &str, args: Vec<Value>) -> Result<Self>;
 }
 
+/// Returns the number of arguments which a function accepts.
+pub fn arity_of<A: FromArgs, R>(_: fn(A) -> R) -> Option<Arity> {
+	A::arity()
+}
+
+/// Returns the number of arguments which a function accepts, for a function
+/// which is passed some context before its arguments.
+pub fn arity_of_with<C, A: FromArgs, R>(_: fn(C, A) -> R) -> Option<Arity> {
+	A::arity()
+}
+
 /// A wrapper type for optional arguments, as opposed to Option which might also
 /// indicate None being a proper value.
 #[repr(transparent)]
@@ -188,31 +228,17 @@ impl<T: CastTrait> FromArg for Cast<T> {
 }
 
 impl<T: FromArg> FromArgs for T {
+	fn arity() -> Option<Arity> {
+		Some(<T as FromArg>::arity())
+	}
+
 	fn from_args(name: &str, args: Vec<Value>) -> Result<Self> {
-		let arity = T::arity();
-
-		if args.len() < arity.lower || arity.upper.map(|x| args.len() > x).unwrap_or(false) {
-			let message = if let Some(upper) = arity.upper {
-				if upper == arity.lower {
-					if upper == 0 {
-						"Expected no arguments".to_string()
-					} else if upper == 1 {
-						"Expected 1 argument".to_string()
-					} else {
-						format!("Expected {upper} arguments")
-					}
-				} else {
-					format!("Expected {} to {} arguments", arity.lower, upper)
-				}
-			} else if arity.lower == 0 {
-				"Expected zero or more arguments".to_string()
-			} else {
-				format!("Expected {} or more arguments", arity.lower)
-			};
+		let arity = <T as FromArg>::arity();
 
+		if !arity.accepts(args.len()) {
 			bail!(Error::InvalidArguments {
 				name: name.to_owned(),
-				message,
+				message: arity.expected(),
 			});
 		}
 
@@ -230,6 +256,10 @@ pub struct Any(pub Vec<Value>);
 // Take ownership of the raw arguments collection, and assume responsibility of
 // validating the number of arguments and converting them as necessary.
 impl FromArgs for Any {
+	fn arity() -> Option<Arity> {
+		None
+	}
+
 	fn from_args(_name: &str, args: Vec<Value>) -> Result<Self> {
 		Ok(Any(args))
 	}
@@ -266,3 +296,4 @@ impl_tuple!(A);
 impl_tuple!(A, B);
 impl_tuple!(A, B, C);
 impl_tuple!(A, B, C, D);
+impl_tuple!(A, B, C, D, E);
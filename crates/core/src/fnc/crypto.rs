@@ -218,6 +218,37 @@ pub mod scrypt {
 	}
 }
 
+pub mod totp {
+
+	use anyhow::Result;
+
+	use crate::fnc::args::Optional;
+	use crate::iam::totp;
+	use crate::val::{Datetime, Value};
+
+	pub fn generate((secret, Optional(time)): (String, Optional<Datetime>)) -> Result<Value> {
+		let time = time.unwrap_or_else(Datetime::now);
+		Ok(totp::generate(&secret, time.timestamp())?.into())
+	}
+
+	pub fn secret(_: ()) -> Result<Value> {
+		Ok(totp::secret().into())
+	}
+
+	pub fn uri(
+		(secret, account, Optional(issuer)): (String, String, Optional<String>),
+	) -> Result<Value> {
+		Ok(totp::uri(&secret, &account, issuer.as_deref()).into())
+	}
+
+	pub fn verify(
+		(secret, code, Optional(time)): (String, String, Optional<Datetime>),
+	) -> Result<Value> {
+		let time = time.unwrap_or_else(Datetime::now);
+		Ok(totp::verify(&secret, &code, time.timestamp())?.into())
+	}
+}
+
 /// Code borrowed from [joaat-rs](https://github.com/Pocakking/joaat-rs).
 /// All credits to its author.
 mod joaat {
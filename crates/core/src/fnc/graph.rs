@@ -0,0 +1,787 @@
+//! Graph algorithms which run over the edges of a relation table.
+//!
+//! Path searches expand the graph lazily from the starting record, reading
+//! only the `key::graph` edge pointers of the records which they visit, and
+//! never follow a path further than `IDIOM_RECURSION_LIMIT` edges. The
+//! whole-graph algorithms scan the edge pointers of a single relation table.
+//! In both cases the number of records or edges which are held in memory is
+//! bounded by `GRAPH_FUNCTION_LIMIT`.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
+
+use anyhow::{Result, bail, ensure};
+use futures::StreamExt;
+use reblessive::tree::Stk;
+
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{DatabaseId, NamespaceId, Permission};
+use crate::cnf::{GRAPH_FUNCTION_LIMIT, IDIOM_RECURSION_LIMIT};
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::dir::Dir;
+use crate::expr::{Base, Idiom};
+use crate::fnc::args::Optional;
+use crate::iam::{Action, ResourceKind};
+use crate::idx::planner::ScanDirection;
+use crate::key::graph;
+use crate::val::{Closure, Object, RecordId, RecordIdKey, Value};
+
+/// Finds the cheapest path between two records, following the edges of a
+/// relation table. Edges cost `1` unless a `weight` field is specified, and
+/// a `heuristic` closure turns the search from Dijkstra into A*.
+pub async fn shortest_path(
+	(stk, ctx, opt, doc): (&mut Stk, &Context, Option<&Options>, Option<&CursorDoc>),
+	(from, to, edge, Optional(options)): (RecordId, RecordId, String, Optional<Object>),
+) -> Result<Value> {
+	let Some(opt) = opt else {
+		return Ok(Value::None);
+	};
+	let name = "graph::shortest_path";
+	let config = Config::parse(name, options, Dir::Out, &["direction", "heuristic", "weight"])?;
+	let walker = Walker::new(ctx, opt, name, &edge, config).await?;
+	let none = HashSet::new();
+	let path = walker.search(stk, doc, &from, &to, &none, &none).await?;
+	Ok(path.map(Path::into_value).unwrap_or_default())
+}
+
+/// Finds up to `k` loopless paths between two records in order of increasing
+/// cost, using Yen's algorithm.
+pub async fn k_shortest_paths(
+	(stk, ctx, opt, doc): (&mut Stk, &Context, Option<&Options>, Option<&CursorDoc>),
+	(from, to, edge, k, Optional(options)): (RecordId, RecordId, String, i64, Optional<Object>),
+) -> Result<Value> {
+	let Some(opt) = opt else {
+		return Ok(Value::None);
+	};
+	let name = "graph::k_shortest_paths";
+	ensure!(k > 0, invalid(name, "Expected the number of paths to be a positive integer"));
+	let config = Config::parse(name, options, Dir::Out, &["direction", "heuristic", "weight"])?;
+	let walker = Walker::new(ctx, opt, name, &edge, config).await?;
+	let none = HashSet::new();
+	let Some(first) = walker.search(stk, doc, &from, &to, &none, &none).await? else {
+		return Ok(Value::from(Vec::<Value>::new()));
+	};
+	let mut found = vec![first];
+	let mut candidates: Vec<Path> = Vec::new();
+	while found.len() < k as usize {
+		let last = found[found.len() - 1].clone();
+		// Deviate from the last path at each of its records in turn
+		for i in 0..last.edges.len() {
+			let root = &last.nodes[..=i];
+			// Remove the edges which previous paths take from the same root
+			let banned_edges = found
+				.iter()
+				.filter(|p| p.edges.len() > i && p.nodes[..=i] == *root)
+				.map(|p| p.edges[i].clone())
+				.collect::<HashSet<_>>();
+			// Remove the root records, so that paths do not loop
+			let banned_nodes = root[..i].iter().cloned().collect::<HashSet<_>>();
+			let spur = walker.search(stk, doc, &last.nodes[i], &to, &banned_nodes, &banned_edges);
+			if let Some(spur) = spur.await? {
+				let mut path = Path {
+					nodes: root[..i].to_vec(),
+					edges: last.edges[..i].to_vec(),
+					costs: last.costs[..i].to_vec(),
+				};
+				path.nodes.extend(spur.nodes);
+				path.edges.extend(spur.edges);
+				path.costs.extend(spur.costs);
+				if !found.contains(&path) && !candidates.contains(&path) {
+					ensure!(candidates.len() < *GRAPH_FUNCTION_LIMIT, walker.exceeded());
+					candidates.push(path);
+				}
+			}
+		}
+		// Keep the cheapest candidate, preferring fewer edges on a tie
+		let best = candidates
+			.iter()
+			.enumerate()
+			.min_by(|(_, a), (_, b)| {
+				a.cost().total_cmp(&b.cost()).then(a.edges.len().cmp(&b.edges.len()))
+			})
+			.map(|(i, _)| i);
+		match best {
+			Some(i) => found.push(candidates.swap_remove(i)),
+			None => break,
+		}
+	}
+	Ok(found.into_iter().map(Path::into_value).collect::<Vec<_>>().into())
+}
+
+/// Groups the records connected by a relation table into weakly connected
+/// components.
+pub async fn components(
+	(ctx, opt): (&Context, Option<&Options>),
+	(edge,): (String,),
+) -> Result<Value> {
+	let Some(opt) = opt else {
+		return Ok(Value::None);
+	};
+	let name = "graph::components";
+	let config = Config::parse(name, None, Dir::Both, &[])?;
+	let walker = Walker::new(ctx, opt, name, &edge, config).await?;
+	let network = walker.load().await?;
+	// Merge the records at both ends of each edge
+	let mut parent = (0..network.nodes.len()).collect::<Vec<_>>();
+	for &(a, b, _) in network.edges.iter() {
+		let (a, b) = (find(&mut parent, a), find(&mut parent, b));
+		if a != b {
+			parent[a.max(b)] = a.min(b);
+		}
+	}
+	let labels = (0..network.nodes.len()).map(|i| find(&mut parent, i)).collect::<Vec<_>>();
+	Ok(network.groups(&labels))
+}
+
+/// Computes the PageRank of each record connected by a relation table,
+/// following edges from their `in` record to their `out` record.
+pub async fn pagerank(
+	(ctx, opt): (&Context, Option<&Options>),
+	(edge, Optional(options)): (String, Optional<Object>),
+) -> Result<Value> {
+	let Some(opt) = opt else {
+		return Ok(Value::None);
+	};
+	let name = "graph::pagerank";
+	let config = Config::parse(name, options, Dir::Out, &["damping", "iterations", "weight"])?;
+	let walker = Walker::new(ctx, opt, name, &edge, config).await?;
+	let network = walker.load().await?;
+	let n = network.nodes.len();
+	let damping = walker.config.damping;
+	// Sum the weight of the edges leaving each record
+	let mut outgoing = vec![0.0; n];
+	for &(a, _, w) in network.edges.iter() {
+		outgoing[a] += w;
+	}
+	let mut rank = vec![1.0 / n as f64; n];
+	for i in 0..walker.config.iterations {
+		walker.check(i)?;
+		// Records without outgoing edges share their rank with every record
+		let dangling = (0..n).filter(|&v| outgoing[v] == 0.0).map(|v| rank[v]).sum::<f64>();
+		let base = (1.0 - damping + damping * dangling) / n as f64;
+		let mut next = vec![base; n];
+		for &(a, b, w) in network.edges.iter() {
+			if outgoing[a] > 0.0 {
+				next[b] += damping * rank[a] * w / outgoing[a];
+			}
+		}
+		let delta = rank.iter().zip(next.iter()).map(|(a, b)| (a - b).abs()).sum::<f64>();
+		rank = next;
+		if delta < 1e-12 {
+			break;
+		}
+	}
+	let mut res = network.nodes.into_iter().zip(rank).collect::<Vec<_>>();
+	res.sort_by(|(a, x), (b, y)| y.total_cmp(x).then_with(|| a.cmp(b)));
+	Ok(res
+		.into_iter()
+		.map(|(id, rank)| {
+			Value::from(map! {
+				"id".to_string() => Value::from(id),
+				"rank".to_string() => Value::from(rank),
+			})
+		})
+		.collect::<Vec<_>>()
+		.into())
+}
+
+/// Counts the edges of a relation table at each record, along with the
+/// degree normalised by the number of other records.
+pub async fn degree_centrality(
+	(ctx, opt): (&Context, Option<&Options>),
+	(edge, Optional(options)): (String, Optional<Object>),
+) -> Result<Value> {
+	let Some(opt) = opt else {
+		return Ok(Value::None);
+	};
+	let name = "graph::degree_centrality";
+	let config = Config::parse(name, options, Dir::Both, &["direction"])?;
+	let walker = Walker::new(ctx, opt, name, &edge, config).await?;
+	let network = walker.load().await?;
+	let n = network.nodes.len();
+	let mut degree = vec![0usize; n];
+	for &(a, b, _) in network.edges.iter() {
+		match walker.config.dir {
+			Dir::Out => degree[a] += 1,
+			Dir::In => degree[b] += 1,
+			Dir::Both => {
+				degree[a] += 1;
+				degree[b] += 1;
+			}
+		}
+	}
+	let mut res = network.nodes.into_iter().zip(degree).collect::<Vec<_>>();
+	res.sort_by(|(a, x), (b, y)| y.cmp(x).then_with(|| a.cmp(b)));
+	Ok(res
+		.into_iter()
+		.map(|(id, degree)| {
+			let centrality = match n {
+				0 | 1 => 0.0,
+				n => degree as f64 / (n - 1) as f64,
+			};
+			Value::from(map! {
+				"id".to_string() => Value::from(id),
+				"degree".to_string() => Value::from(degree),
+				"centrality".to_string() => Value::from(centrality),
+			})
+		})
+		.collect::<Vec<_>>()
+		.into())
+}
+
+/// Counts the triangles formed by the edges of a relation table, ignoring
+/// the direction of each edge.
+pub async fn triangles(
+	(ctx, opt): (&Context, Option<&Options>),
+	(edge,): (String,),
+) -> Result<Value> {
+	let Some(opt) = opt else {
+		return Ok(Value::None);
+	};
+	let name = "graph::triangles";
+	let config = Config::parse(name, None, Dir::Both, &[])?;
+	let walker = Walker::new(ctx, opt, name, &edge, config).await?;
+	let network = walker.load().await?;
+	let adjacent = network.adjacency();
+	// Visit each triangle once, from its lowest ranked record
+	let rank = |v: usize| (adjacent[v].len(), v);
+	let mut count = 0usize;
+	for (u, neighbours) in adjacent.iter().enumerate() {
+		walker.check(u)?;
+		for &v in neighbours.keys().filter(|&&v| rank(v) > rank(u)) {
+			count += adjacent[v]
+				.keys()
+				.filter(|&&w| rank(w) > rank(v) && neighbours.contains_key(&w))
+				.count();
+		}
+	}
+	Ok(count.into())
+}
+
+/// Detects communities of records connected by a relation table, by
+/// repeatedly giving each record the most common label among its neighbours.
+pub async fn label_propagation(
+	(ctx, opt): (&Context, Option<&Options>),
+	(edge, Optional(options)): (String, Optional<Object>),
+) -> Result<Value> {
+	let Some(opt) = opt else {
+		return Ok(Value::None);
+	};
+	let name = "graph::label_propagation";
+	let config = Config::parse(name, options, Dir::Both, &["iterations", "weight"])?;
+	let walker = Walker::new(ctx, opt, name, &edge, config).await?;
+	let network = walker.load().await?;
+	let adjacent = network.adjacency();
+	// Visit the records in a stable order, so that the result is deterministic
+	let mut order = (0..network.nodes.len()).collect::<Vec<_>>();
+	order.sort_by(|&a, &b| network.nodes[a].cmp(&network.nodes[b]));
+	let mut labels = (0..network.nodes.len()).collect::<Vec<_>>();
+	for i in 0..walker.config.iterations {
+		walker.check(i)?;
+		let mut changed = false;
+		for &v in order.iter() {
+			let mut tally = BTreeMap::new();
+			for (&u, &w) in adjacent[v].iter() {
+				*tally.entry(labels[u]).or_insert(0.0) += w;
+			}
+			// Pick the heaviest label, keeping the current label on a tie
+			let mut best = (labels[v], tally.get(&labels[v]).copied().unwrap_or(0.0));
+			for (&label, &w) in tally.iter() {
+				if w > best.1 {
+					best = (label, w);
+				}
+			}
+			if best.0 != labels[v] {
+				labels[v] = best.0;
+				changed = true;
+			}
+		}
+		if !changed {
+			break;
+		}
+	}
+	Ok(network.groups(&labels))
+}
+
+fn invalid(name: &str, message: impl Into<String>) -> Error {
+	Error::InvalidArguments {
+		name: name.to_owned(),
+		message: message.into(),
+	}
+}
+
+/// Finds the representative of a set, halving the path along the way
+fn find(parent: &mut [usize], mut v: usize) -> usize {
+	while parent[v] != v {
+		parent[v] = parent[parent[v]];
+		v = parent[v];
+	}
+	v
+}
+
+/// The options which can be passed to the graph functions
+struct Config {
+	/// The direction in which edges are followed
+	dir: Dir,
+	/// The edge field which holds the cost of an edge
+	weight: Option<Idiom>,
+	/// A closure estimating the remaining cost from a record to the target
+	heuristic: Option<Box<Closure>>,
+	/// The PageRank damping factor
+	damping: f64,
+	/// The maximum number of iterations of an iterative algorithm
+	iterations: usize,
+}
+
+impl Config {
+	fn parse(name: &str, options: Option<Object>, dir: Dir, allowed: &[&str]) -> Result<Self> {
+		let mut config = Config {
+			dir,
+			weight: None,
+			heuristic: None,
+			damping: 0.85,
+			iterations: 20,
+		};
+		for (key, value) in options.into_iter().flat_map(|o| o.0) {
+			ensure!(
+				allowed.contains(&key.as_str()),
+				invalid(name, format!("Unexpected option `{key}`"))
+			);
+			match (key.as_str(), value) {
+				("direction", Value::String(v)) => {
+					config.dir = match v.to_ascii_lowercase().as_str() {
+						"in" => Dir::In,
+						"out" => Dir::Out,
+						"both" => Dir::Both,
+						_ => bail!(invalid(
+							name,
+							"Expected the `direction` option to be 'in', 'out' or 'both'"
+						)),
+					}
+				}
+				("weight", Value::String(v)) => config.weight = Some(Idiom::field(v)),
+				("heuristic", Value::Closure(v)) => config.heuristic = Some(v),
+				("damping", Value::Number(v)) if (0.0..=1.0).contains(&v.to_float()) => {
+					config.damping = v.to_float()
+				}
+				("iterations", Value::Number(v)) if v.is_int() && v.to_int() >= 0 => {
+					config.iterations = v.to_int() as usize
+				}
+				(key, _) => bail!(invalid(name, format!("Invalid value for option `{key}`"))),
+			}
+		}
+		Ok(config)
+	}
+}
+
+/// A path between two records
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Path {
+	/// The records along the path, including both ends
+	nodes: Vec<RecordId>,
+	/// The edges between consecutive records
+	edges: Vec<RecordId>,
+	/// The cost of each edge
+	costs: Vec<f64>,
+}
+
+impl Path {
+	fn cost(&self) -> f64 {
+		self.costs.iter().sum()
+	}
+
+	fn into_value(self) -> Value {
+		Value::from(map! {
+			"cost".to_string() => Value::from(self.cost()),
+			"edges".to_string() => Value::from(self.edges.into_iter().map(Value::from).collect::<Vec<_>>()),
+			"path".to_string() => Value::from(self.nodes.into_iter().map(Value::from).collect::<Vec<_>>()),
+		})
+	}
+}
+
+/// A record waiting to be visited by a path search
+struct Entry {
+	/// The cost so far plus the estimated remaining cost
+	estimate: f64,
+	/// The index of the record
+	node: usize,
+}
+
+impl PartialEq for Entry {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Entry {
+	// Reversed, so that the binary heap pops the cheapest entry first
+	fn cmp(&self, other: &Self) -> Ordering {
+		other.estimate.total_cmp(&self.estimate).then_with(|| other.node.cmp(&self.node))
+	}
+}
+
+/// The best known route to a record during a path search
+struct Visit {
+	/// The record which was reached
+	node: RecordId,
+	/// The cheapest known cost of reaching the record
+	cost: f64,
+	/// The number of edges on the cheapest known route
+	hops: usize,
+	/// The previous record, the edge taken from it, and the cost of that edge
+	prev: Option<(usize, RecordId, f64)>,
+	/// Whether the cheapest route to this record is final
+	settled: bool,
+}
+
+/// The edges of a relation table, loaded into memory
+#[derive(Default)]
+struct Network {
+	/// The records at either end of the edges
+	nodes: Vec<RecordId>,
+	/// The `in` record, `out` record and weight of each edge
+	edges: Vec<(usize, usize, f64)>,
+}
+
+impl Network {
+	/// Returns the weighted neighbours of each record, ignoring direction
+	fn adjacency(&self) -> Vec<HashMap<usize, f64>> {
+		let mut adjacent = vec![HashMap::new(); self.nodes.len()];
+		for &(a, b, w) in self.edges.iter().filter(|(a, b, _)| a != b) {
+			*adjacent[a].entry(b).or_insert(0.0) += w;
+			*adjacent[b].entry(a).or_insert(0.0) += w;
+		}
+		adjacent
+	}
+
+	/// Returns the records grouped by label, largest group first
+	fn groups(self, labels: &[usize]) -> Value {
+		let mut groups = BTreeMap::<usize, Vec<RecordId>>::new();
+		for (node, &label) in self.nodes.into_iter().zip(labels) {
+			groups.entry(label).or_default().push(node);
+		}
+		let mut groups = groups.into_values().collect::<Vec<_>>();
+		groups.iter_mut().for_each(|g| g.sort());
+		groups.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+		groups
+			.into_iter()
+			.map(|g| Value::from(g.into_iter().map(Value::from).collect::<Vec<_>>()))
+			.collect::<Vec<_>>()
+			.into()
+	}
+}
+
+/// Reads the edges of a relation table on behalf of a graph function
+struct Walker<'a> {
+	ctx: &'a Context,
+	opt: &'a Options,
+	ns: NamespaceId,
+	db: DatabaseId,
+	name: &'static str,
+	edge: &'a str,
+	config: Config,
+}
+
+impl<'a> Walker<'a> {
+	async fn new(
+		ctx: &'a Context,
+		opt: &'a Options,
+		name: &'static str,
+		edge: &'a str,
+		config: Config,
+	) -> Result<Self> {
+		// Ensure we have a valid database context
+		opt.valid_for_db()?;
+		// Check if the user has permission to view records
+		opt.is_allowed(Action::View, ResourceKind::Record, &Base::Db)?;
+		// Get the namespace and database IDs
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		// Record users may only walk edges which they can fully select, as
+		// the functions read edge pointers without per-record permissions
+		if opt.check_perms(Action::View)? {
+			let allowed = match ctx.tx().get_tb(ns, db, edge).await? {
+				Some(tb) => matches!(tb.permissions.select, Permission::Full),
+				None => false,
+			};
+			ensure!(
+				allowed,
+				Error::TablePermissions {
+					table: edge.to_owned(),
+				}
+			);
+		}
+		Ok(Self {
+			ctx,
+			opt,
+			ns,
+			db,
+			name,
+			edge,
+			config,
+		})
+	}
+
+	/// Checks if the query has been cancelled or has timed out
+	fn check(&self, count: usize) -> Result<()> {
+		match self.ctx.done(count % 100 == 0)? {
+			Some(reason) => Err(Error::from(reason).into()),
+			None => Ok(()),
+		}
+	}
+
+	fn exceeded(&self) -> Error {
+		Error::GraphFunctionLimitExceeded {
+			name: self.name.to_owned(),
+			limit: *GRAPH_FUNCTION_LIMIT,
+		}
+	}
+
+	/// Returns the cost of an edge
+	async fn weight(&self, edge: &RecordId) -> Result<f64> {
+		let Some(field) = &self.config.weight else {
+			return Ok(1.0);
+		};
+		let txn = self.ctx.tx();
+		let record = txn.get_record(self.ns, self.db, &edge.table, &edge.key, self.opt.version);
+		match record.await?.data.as_ref().pick(field) {
+			Value::Number(v) if v.to_float().is_finite() && v.to_float() >= 0.0 => Ok(v.to_float()),
+			_ => bail!(invalid(
+				self.name,
+				format!("Expected edge `{edge}` to have a non-negative number in field `{field}`")
+			)),
+		}
+	}
+
+	/// Returns the estimated remaining cost from a record to the target
+	async fn estimate(
+		&self,
+		stk: &mut Stk,
+		doc: Option<&CursorDoc>,
+		node: &RecordId,
+		target: &RecordId,
+	) -> Result<f64> {
+		let Some(heuristic) = &self.config.heuristic else {
+			return Ok(0.0);
+		};
+		let args = vec![Value::from(node.clone()), Value::from(target.clone())];
+		match heuristic.compute(stk, self.ctx, self.opt, doc, args).await? {
+			Value::Number(v) if v.to_float().is_finite() && v.to_float() >= 0.0 => Ok(v.to_float()),
+			_ => {
+				bail!(invalid(self.name, "Expected the heuristic to return a non-negative number"))
+			}
+		}
+	}
+
+	/// Returns the edges leading away from a record, along with the record
+	/// at the other end of each edge
+	async fn neighbours(&self, node: &RecordId) -> Result<Vec<(RecordId, RecordId)>> {
+		let dirs = match self.config.dir {
+			Dir::Both => vec![Dir::Out, Dir::In],
+			ref dir => vec![dir.clone()],
+		};
+		let txn = self.ctx.tx();
+		let mut res = Vec::new();
+		for dir in dirs.iter() {
+			// Fetch the edges from the record, /ns/db/tb/id/dir/edge
+			let beg = graph::ftprefix(self.ns, self.db, &node.table, &node.key, dir, self.edge)?;
+			let end = graph::ftsuffix(self.ns, self.db, &node.table, &node.key, dir, self.edge)?;
+			let mut edges = Vec::new();
+			let mut stream = txn.stream_keys(beg..end, None, ScanDirection::Forward);
+			while let Some(key) = stream.next().await {
+				let key = key?;
+				let g = graph::Graph::decode_key(&key)?;
+				edges.push(RecordId {
+					table: g.ft.into_owned(),
+					key: g.fk.into_owned(),
+				});
+			}
+			drop(stream);
+			// Fetch the record at the other end of each edge
+			for edge in edges {
+				let beg = graph::egprefix(self.ns, self.db, &edge.table, &edge.key, dir)?;
+				let end = graph::egsuffix(self.ns, self.db, &edge.table, &edge.key, dir)?;
+				let mut stream = txn.stream_keys(beg..end, None, ScanDirection::Forward);
+				while let Some(key) = stream.next().await {
+					let key = key?;
+					let g = graph::Graph::decode_key(&key)?;
+					let next = RecordId {
+						table: g.ft.into_owned(),
+						key: g.fk.into_owned(),
+					};
+					res.push((edge.clone(), next));
+				}
+			}
+		}
+		Ok(res)
+	}
+
+	/// Runs a Dijkstra search, or an A* search when a heuristic is given,
+	/// avoiding the specified records and edges
+	async fn search(
+		&self,
+		stk: &mut Stk,
+		doc: Option<&CursorDoc>,
+		from: &RecordId,
+		to: &RecordId,
+		banned_nodes: &HashSet<RecordId>,
+		banned_edges: &HashSet<RecordId>,
+	) -> Result<Option<Path>> {
+		let mut index = HashMap::from([(from.clone(), 0)]);
+		let mut visits = vec![Visit {
+			node: from.clone(),
+			cost: 0.0,
+			hops: 0,
+			prev: None,
+			settled: false,
+		}];
+		let mut heap = BinaryHeap::from([Entry {
+			estimate: self.estimate(stk, doc, from, to).await?,
+			node: 0,
+		}]);
+		let mut count = 0;
+		while let Some(Entry {
+			node,
+			..
+		}) = heap.pop()
+		{
+			self.check(count)?;
+			count += 1;
+			if visits[node].settled {
+				continue;
+			}
+			visits[node].settled = true;
+			if visits[node].node == *to {
+				return Ok(Some(Self::trace(&visits, node)));
+			}
+			// Do not follow paths beyond the recursion limit
+			if visits[node].hops >= *IDIOM_RECURSION_LIMIT {
+				continue;
+			}
+			let (cost, hops) = (visits[node].cost, visits[node].hops + 1);
+			let current = visits[node].node.clone();
+			for (edge, next) in self.neighbours(&current).await? {
+				if banned_edges.contains(&edge) || banned_nodes.contains(&next) {
+					continue;
+				}
+				let weight = self.weight(&edge).await?;
+				let total = cost + weight;
+				let idx = match index.get(&next) {
+					Some(&idx) => idx,
+					None => {
+						ensure!(visits.len() < *GRAPH_FUNCTION_LIMIT, self.exceeded());
+						index.insert(next.clone(), visits.len());
+						visits.push(Visit {
+							node: next.clone(),
+							cost: f64::INFINITY,
+							hops,
+							prev: None,
+							settled: false,
+						});
+						visits.len() - 1
+					}
+				};
+				if visits[idx].settled || total >= visits[idx].cost {
+					continue;
+				}
+				visits[idx].cost = total;
+				visits[idx].hops = hops;
+				visits[idx].prev = Some((node, edge, weight));
+				heap.push(Entry {
+					estimate: total + self.estimate(stk, doc, &next, to).await?,
+					node: idx,
+				});
+			}
+		}
+		Ok(None)
+	}
+
+	/// Rebuilds the path which a search took to reach a record
+	fn trace(visits: &[Visit], mut node: usize) -> Path {
+		let mut path = Path {
+			nodes: vec![visits[node].node.clone()],
+			..Default::default()
+		};
+		while let Some((prev, edge, weight)) = &visits[node].prev {
+			path.nodes.push(visits[*prev].node.clone());
+			path.edges.push(edge.clone());
+			path.costs.push(*weight);
+			node = *prev;
+		}
+		path.nodes.reverse();
+		path.edges.reverse();
+		path.costs.reverse();
+		path
+	}
+
+	/// Loads every edge of the relation table
+	async fn load(&self) -> Result<Network> {
+		let txn = self.ctx.tx();
+		// Pair up the in and out pointers of each edge, /ns/db/edge
+		let mut pairs = Vec::new();
+		let mut current: Option<(RecordIdKey, Option<RecordId>, Option<RecordId>)> = None;
+		let beg = graph::tbprefix(self.ns, self.db, self.edge)?;
+		let end = graph::tbsuffix(self.ns, self.db, self.edge)?;
+		let mut stream = txn.stream_keys(beg..end, None, ScanDirection::Forward);
+		let mut count = 0;
+		loop {
+			self.check(count)?;
+			count += 1;
+			let key = match stream.next().await {
+				Some(key) => Some(key?),
+				None => None,
+			};
+			let g = key.as_deref().map(graph::Graph::decode_key).transpose()?;
+			// Keys are ordered by edge id, so an edge is complete once the id changes
+			let finished = |c: &mut (RecordIdKey, _, _)| g.as_ref().is_none_or(|g| g.id != c.0);
+			if let Some((id, Some(a), Some(b))) = current.take_if(finished) {
+				ensure!(pairs.len() < *GRAPH_FUNCTION_LIMIT, self.exceeded());
+				pairs.push((id, a, b));
+			}
+			let Some(g) = g else {
+				break;
+			};
+			let entry = current.get_or_insert_with(|| (g.id.clone(), None, None));
+			let node = RecordId {
+				table: g.ft.into_owned(),
+				key: g.fk.into_owned(),
+			};
+			match g.eg {
+				Dir::In if entry.1.is_none() => entry.1 = Some(node),
+				Dir::Out if entry.2.is_none() => entry.2 = Some(node),
+				_ => {}
+			}
+		}
+		drop(stream);
+		// Index the records, and fetch the weight of each edge
+		let mut network = Network::default();
+		let mut index = HashMap::new();
+		for (id, a, b) in pairs {
+			let weight = self
+				.weight(&RecordId {
+					table: self.edge.to_owned(),
+					key: id,
+				})
+				.await?;
+			let mut intern = |node: RecordId| {
+				*index.entry(node).or_insert_with_key(|node: &RecordId| {
+					network.nodes.push(node.clone());
+					network.nodes.len() - 1
+				})
+			};
+			let (a, b) = (intern(a), intern(b));
+			network.edges.push((a, b, weight));
+		}
+		Ok(network)
+	}
+}
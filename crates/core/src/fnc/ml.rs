@@ -0,0 +1,596 @@
+use anyhow::Result;
+use reblessive::tree::Stk;
+
+use super::CursorDoc;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::val::Value;
+
+/// Computes a normalised sentence embedding for a string, or for each string
+/// in an array, using an ONNX embedding model. The model is either the name
+/// and version of a model imported into the database, such as
+/// `'minilm<1.0.0>'`, whose input keys hold its WordPiece vocabulary, or a
+/// file pointing to a bucket directory containing the `model.onnx` model and
+/// its `vocab.txt` vocabulary. The model is loaded once, and is reloaded when
+/// it changes.
+#[cfg(feature = "ml")]
+pub async fn embed(
+	(stk, ctx, opt, doc): (&mut Stk, &Context, &Options, Option<&CursorDoc>),
+	(model, text): (Value, Value),
+) -> Result<Value> {
+	use anyhow::bail;
+	use trice::Instant;
+
+	use crate::dbs::capabilities::ExperimentalTarget;
+	use crate::err::Error;
+	use crate::expr::{INFERENCE, Model};
+
+	// Check the texts which are to be embedded
+	let (texts, batch) = match text {
+		Value::String(v) => (vec![v], false),
+		Value::Array(v) => {
+			let texts = v
+				.into_iter()
+				.map(|v| match v {
+					Value::String(v) => Some(v),
+					_ => None,
+				})
+				.collect::<Option<Vec<_>>>();
+			let Some(texts) = texts else {
+				bail!(Error::InvalidArguments {
+					name: "ml::embed".to_owned(),
+					message: "Expected a string or an array of strings to embed".to_owned(),
+				});
+			};
+			(texts, true)
+		}
+		_ => bail!(Error::InvalidArguments {
+			name: "ml::embed".to_owned(),
+			message: "Expected a string or an array of strings to embed".to_owned(),
+		}),
+	};
+	// Load the embedding model
+	let model = match model {
+		// The model has been imported into the database
+		Value::String(v) => {
+			let Some((name, version)) = v.strip_suffix('>').and_then(|v| v.split_once('<')) else {
+				bail!(Error::InvalidArguments {
+					name: "ml::embed".to_owned(),
+					message: format!(
+						"Expected the model to be specified as `name<version>` but found '{v}'"
+					),
+				});
+			};
+			let model = Model {
+				name: name.to_owned(),
+				version: version.to_owned(),
+			};
+			embedding::model(stk, ctx, opt, doc, &model).await?
+		}
+		// The model is stored in a bucket
+		Value::File(file) => {
+			if !ctx.get_capabilities().allows_experimental(&ExperimentalTarget::Files) {
+				bail!(Error::InvalidFunction {
+					name: "ml::embed".to_owned(),
+					message: format!(
+						"Experimental feature {} is not enabled",
+						ExperimentalTarget::Files
+					),
+				});
+			}
+			embedding::file(stk, ctx, opt, doc, &file).await?
+		}
+		_ => bail!(Error::InvalidArguments {
+			name: "ml::embed".to_owned(),
+			message: "Expected the name and version of a model, or a file in a bucket".to_owned(),
+		}),
+	};
+	// Run the model in a blocking task
+	let rows = texts.len();
+	let started = Instant::now();
+	let mut res = tokio::task::spawn_blocking(move || model.embed(&texts))
+		.await
+		.map_err(|e| Error::ModelComputation(e.to_string()))??;
+	// Record the inference latency
+	INFERENCE.record(rows, started.elapsed());
+	// Return a single embedding for a single text
+	let embedding = |v: Vec<f64>| v.into_iter().map(Value::from).collect::<Value>();
+	if batch {
+		Ok(res.into_iter().map(embedding).collect())
+	} else {
+		Ok(res.pop().map(embedding).unwrap_or_default())
+	}
+}
+
+#[cfg(not(feature = "ml"))]
+pub async fn embed(
+	_: (&mut Stk, &Context, &Options, Option<&CursorDoc>),
+	_: (Value, Value),
+) -> Result<Value> {
+	Err(anyhow::Error::new(crate::err::Error::InvalidModel {
+		message: String::from("Machine learning computation is not enabled."),
+	}))
+}
+
+#[cfg(feature = "ml")]
+mod embedding {
+	use std::sync::Arc;
+
+	use anyhow::Result;
+	use ort::session::Session;
+	use ort::value::Tensor;
+	use reblessive::tree::Stk;
+	use surrealml::errors::error::SurrealError;
+	use surrealml::storage::surml_file::SurMlFile;
+
+	use super::tokenizer::{self, WordPiece};
+	use crate::buc::BucketController;
+	use crate::buc::store::ObjectKey;
+	use crate::ctx::Context;
+	use crate::dbs::Options;
+	use crate::doc::CursorDoc;
+	use crate::err::Error;
+	use crate::expr::Model;
+	use crate::kvs::cache::ds::{Entry, Lookup};
+	use crate::val::File;
+
+	/// The file name of the ONNX model within a model directory
+	const MODEL: &str = "model.onnx";
+	/// The file name of the WordPiece vocabulary within a model directory
+	const VOCAB: &str = "vocab.txt";
+
+	fn invalid(message: impl Into<String>) -> anyhow::Error {
+		anyhow::Error::new(Error::InvalidModel {
+			message: message.into(),
+		})
+	}
+
+	fn computation(err: ort::Error) -> anyhow::Error {
+		anyhow::Error::new(Error::ModelComputation(err.to_string()))
+	}
+
+	/// Loads an embedding model which has been imported into the database,
+	/// using the datastore cache for each model hash
+	pub(super) async fn model(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		model: &Model,
+	) -> Result<Arc<Embedder>> {
+		// Get the model definition
+		let (ns, db, path, val) = model.definition(stk, ctx, opt, doc).await?;
+		// Check if the model has already been loaded
+		let cache = ctx.get_cache();
+		let lookup = Lookup::Mld(ns, db, &model.name, &model.version, &val.hash);
+		if let Some(model) = cache
+			.as_ref()
+			.and_then(|c| c.get(&lookup))
+			.and_then(|v| v.try_into_type::<Embedder>().ok())
+		{
+			return Ok(model);
+		}
+		// Get the model file as bytes
+		let bytes = crate::obs::get(&path).await?;
+		// Create the model session in a blocking task
+		let embedder = tokio::task::spawn_blocking(move || {
+			let file = SurMlFile::from_bytes(bytes)
+				.map_err(|err: SurrealError| Error::ModelComputation(err.message.to_string()))?;
+			// The vocabulary is stored as the input keys, in token id order
+			let tokenizer = WordPiece::new(file.header.keys.store.iter().map(String::as_str))
+				.map_err(|t| invalid(format!("The model keys do not contain the `{t}` token")))?;
+			Embedder::new(&file.model, tokenizer)
+		})
+		.await
+		.map_err(|e| Error::ModelComputation(e.to_string()))??;
+		let embedder = Arc::new(embedder);
+		// Store the model for subsequent computations
+		if let Some(cache) = cache {
+			cache.insert(lookup, Entry::Any(embedder.clone()));
+		}
+		Ok(embedder)
+	}
+
+	/// Loads an embedding model from a bucket, using the datastore cache
+	/// while the model file has not been modified
+	pub(super) async fn file(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		file: &File,
+	) -> Result<Arc<Embedder>> {
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let dir = ObjectKey::new(file.key.clone());
+		let model = dir.join(&ObjectKey::new(MODEL));
+		let vocab = dir.join(&ObjectKey::new(VOCAB));
+		let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
+		// Check when the model was last modified
+		let Some(meta) = controller.head(&model).await? else {
+			return Err(invalid(format!(
+				"The embedding model {file} does not contain a `{MODEL}` file"
+			)));
+		};
+		let cache = ctx.get_cache();
+		let lookup =
+			Lookup::Mle(ns, db, &file.bucket, &file.key, meta.updated.0.timestamp_micros());
+		if let Some(model) = cache
+			.as_ref()
+			.and_then(|c| c.get(&lookup))
+			.and_then(|v| v.try_into_type::<Embedder>().ok())
+		{
+			return Ok(model);
+		}
+		// Fetch the model and the vocabulary
+		let Some(model) = controller.get(&model).await? else {
+			return Err(invalid(format!(
+				"The embedding model {file} does not contain a `{MODEL}` file"
+			)));
+		};
+		let Some(vocab) = controller.get(&vocab).await? else {
+			return Err(invalid(format!(
+				"The embedding model {file} does not contain a `{VOCAB}` file"
+			)));
+		};
+		// Create the model session in a blocking task
+		let (model, vocab) = (model.into_inner(), vocab.into_inner());
+		let embedder = tokio::task::spawn_blocking(move || {
+			let vocab = std::str::from_utf8(&vocab)
+				.map_err(|_| invalid(format!("The `{VOCAB}` file is not valid UTF-8")))?;
+			let tokenizer = WordPiece::new(vocab.lines()).map_err(|t| {
+				invalid(format!("The `{VOCAB}` file does not contain the `{t}` token"))
+			})?;
+			Embedder::new(&model, tokenizer)
+		})
+		.await
+		.map_err(|e| Error::ModelComputation(e.to_string()))??;
+		let embedder = Arc::new(embedder);
+		// Store the model for subsequent computations
+		if let Some(cache) = cache {
+			cache.insert(lookup, Entry::Any(embedder.clone()));
+		}
+		Ok(embedder)
+	}
+
+	/// A sentence embedding model, with the tokenizer for its inputs
+	pub(super) struct Embedder {
+		session: Session,
+		tokenizer: WordPiece,
+		/// Whether the model expects a `token_type_ids` input
+		token_types: bool,
+	}
+
+	impl Embedder {
+		fn new(model: &[u8], tokenizer: WordPiece) -> Result<Self> {
+			let session = Session::builder()
+				.and_then(|b| b.commit_from_memory(model))
+				.map_err(computation)?;
+			let token_types = session.inputs.iter().any(|i| i.name == "token_type_ids");
+			Ok(Self {
+				session,
+				tokenizer,
+				token_types,
+			})
+		}
+
+		/// Embeds a batch of texts in a single computation, mean pooling the
+		/// token embeddings and normalising the result to unit length
+		pub(super) fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f64>>> {
+			if texts.is_empty() {
+				return Ok(Vec::new());
+			}
+			// Tokenize the texts, padding them to the same length
+			let max = *crate::cnf::EMBEDDING_MAX_TOKENS;
+			let tokens = texts.iter().map(|t| self.tokenizer.encode(t, max)).collect::<Vec<_>>();
+			let (ids, mask, cols) = self.tokenizer.pad(&tokens);
+			let rows = tokens.len();
+			// Run the model over the whole batch
+			let mut inputs = vec![
+				(
+					"input_ids",
+					Tensor::from_array(([rows, cols], ids)).map_err(computation)?.into_dyn(),
+				),
+				(
+					"attention_mask",
+					Tensor::from_array(([rows, cols], mask.clone()))
+						.map_err(computation)?
+						.into_dyn(),
+				),
+			];
+			if self.token_types {
+				let types = vec![0i64; rows * cols];
+				let types = Tensor::from_array(([rows, cols], types)).map_err(computation)?;
+				inputs.push(("token_type_ids", types.into_dyn()));
+			}
+			let outputs = self.session.run(inputs).map_err(computation)?;
+			let (shape, data) = outputs[0].try_extract_raw_tensor::<f32>().map_err(computation)?;
+			// Pool the token embeddings of each text
+			tokenizer::pool(shape, data, &mask, rows, cols).ok_or_else(|| {
+				invalid(format!(
+					"Expected the embedding model to output a tensor of shape [batch, tokens, dimensions] but found {shape:?}"
+				))
+			})
+		}
+	}
+}
+
+/// The model independent parts of the embedding computation, which
+/// tokenize the input texts and pool the output of the model
+#[cfg(any(feature = "ml", test))]
+mod tokenizer {
+	use std::collections::HashMap;
+
+	/// Words longer than this are treated as unknown tokens
+	const MAX_WORD_CHARS: usize = 100;
+
+	/// A BERT style WordPiece tokenizer, which lowercases the input and
+	/// splits it on whitespace and punctuation before matching subwords
+	pub(super) struct WordPiece {
+		vocab: HashMap<String, i64>,
+		unk: i64,
+		cls: i64,
+		sep: i64,
+		pad: i64,
+	}
+
+	impl WordPiece {
+		/// Creates a tokenizer from the tokens of a vocabulary, in token id
+		/// order, returning the first missing special token on failure
+		pub(super) fn new<'a>(
+			vocab: impl IntoIterator<Item = &'a str>,
+		) -> Result<Self, &'static str> {
+			let vocab = vocab
+				.into_iter()
+				.enumerate()
+				.map(|(i, t)| (t.trim_end().to_owned(), i as i64))
+				.collect::<HashMap<_, _>>();
+			let special = |t: &'static str| vocab.get(t).copied().ok_or(t);
+			Ok(Self {
+				unk: special("[UNK]")?,
+				cls: special("[CLS]")?,
+				sep: special("[SEP]")?,
+				pad: special("[PAD]")?,
+				vocab,
+			})
+		}
+
+		/// Converts a text into token ids, truncated to the maximum length
+		pub(super) fn encode(&self, text: &str, max: usize) -> Vec<i64> {
+			let mut res = vec![self.cls];
+			let limit = max.max(2) - 1;
+			for word in Self::words(text) {
+				res.extend(self.pieces(&word));
+				if res.len() >= limit {
+					res.truncate(limit);
+					break;
+				}
+			}
+			res.push(self.sep);
+			res
+		}
+
+		/// Pads a batch of token ids to the same length, returning the
+		/// flattened ids and attention mask, and the padded length
+		pub(super) fn pad(&self, tokens: &[Vec<i64>]) -> (Vec<i64>, Vec<i64>, usize) {
+			let cols = tokens.iter().map(Vec::len).max().unwrap_or_default();
+			let mut ids = Vec::with_capacity(tokens.len() * cols);
+			let mut mask = Vec::with_capacity(tokens.len() * cols);
+			for row in tokens.iter() {
+				ids.extend(row.iter().copied());
+				ids.extend(std::iter::repeat_n(self.pad, cols - row.len()));
+				mask.extend(std::iter::repeat_n(1i64, row.len()));
+				mask.extend(std::iter::repeat_n(0i64, cols - row.len()));
+			}
+			(ids, mask, cols)
+		}
+
+		/// Splits a text into lowercased words and punctuation characters
+		fn words(text: &str) -> Vec<String> {
+			let mut words = Vec::new();
+			let mut word = String::new();
+			for c in text.chars() {
+				if c.is_whitespace() || c.is_control() {
+					words.extend((!word.is_empty()).then(|| std::mem::take(&mut word)));
+				} else if c.is_ascii_punctuation() || !c.is_alphanumeric() {
+					words.extend((!word.is_empty()).then(|| std::mem::take(&mut word)));
+					words.push(c.to_string());
+				} else {
+					// Strip the accents from latin characters
+					match c {
+						'\u{00C0}'..='\u{024F}' => word.push_str(
+							&deunicode::deunicode_char(c).unwrap_or_default().to_lowercase(),
+						),
+						c => word.extend(c.to_lowercase()),
+					}
+				}
+			}
+			words.extend((!word.is_empty()).then_some(word));
+			words
+		}
+
+		/// Greedily matches the longest subwords of a word in the vocabulary
+		fn pieces(&self, word: &str) -> Vec<i64> {
+			let chars = word.chars().collect::<Vec<_>>();
+			if chars.len() > MAX_WORD_CHARS {
+				return vec![self.unk];
+			}
+			let mut res = Vec::new();
+			let mut start = 0;
+			while start < chars.len() {
+				let mut end = chars.len();
+				let mut found = None;
+				while start < end {
+					let mut piece = chars[start..end].iter().collect::<String>();
+					if start > 0 {
+						piece.insert_str(0, "##");
+					}
+					if let Some(id) = self.vocab.get(&piece) {
+						found = Some(*id);
+						break;
+					}
+					end -= 1;
+				}
+				match found {
+					Some(id) => {
+						res.push(id);
+						start = end;
+					}
+					None => return vec![self.unk],
+				}
+			}
+			res
+		}
+	}
+
+	/// Pools the output of an embedding model into one embedding for each
+	/// text, normalised to unit length. Token embeddings are averaged over
+	/// the attention mask, while already pooled embeddings are used as is.
+	/// Returns `None` if the output shape does not match the batch.
+	pub(super) fn pool(
+		shape: &[i64],
+		data: &[f32],
+		mask: &[i64],
+		rows: usize,
+		cols: usize,
+	) -> Option<Vec<Vec<f64>>> {
+		let res = match shape {
+			// The model outputs an embedding for each token
+			[n, len, dim] if *n as usize == rows && *len as usize == cols => {
+				let dim = *dim as usize;
+				(0..rows)
+					.map(|r| {
+						let mut sum = vec![0f64; dim];
+						let mut count = 0f64;
+						for c in 0..cols {
+							if mask[r * cols + c] == 0 {
+								continue;
+							}
+							let offset = (r * cols + c) * dim;
+							for (s, v) in sum.iter_mut().zip(&data[offset..offset + dim]) {
+								*s += *v as f64;
+							}
+							count += 1.0;
+						}
+						sum.iter().map(|s| s / count.max(1.0)).collect::<Vec<_>>()
+					})
+					.collect::<Vec<_>>()
+			}
+			// The model outputs a pooled embedding for each text
+			[n, dim] if *n as usize == rows && *dim > 0 => data
+				.chunks(*dim as usize)
+				.map(|v| v.iter().map(|x| *x as f64).collect::<Vec<_>>())
+				.collect(),
+			_ => return None,
+		};
+		// Normalise the embeddings to unit length
+		Some(
+			res.into_iter()
+				.map(|v| {
+					let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt().max(f64::EPSILON);
+					v.into_iter().map(|x| x / norm).collect()
+				})
+				.collect(),
+		)
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+
+		const VOCAB: &str =
+			"[PAD]\n[UNK]\n[CLS]\n[SEP]\nhello\nworld\n,\n!\nun\n##aff\n##able\ncafe\n";
+
+		fn tokenizer() -> WordPiece {
+			WordPiece::new(VOCAB.lines()).unwrap()
+		}
+
+		fn assert_close(a: &[f64], b: &[f64]) {
+			assert_eq!(a.len(), b.len(), "{a:?} != {b:?}");
+			for (a, b) in a.iter().zip(b) {
+				assert!((a - b).abs() < 1e-6, "{a} != {b}");
+			}
+		}
+
+		#[test]
+		fn missing_special_token() {
+			let res = WordPiece::new(["[PAD]", "[UNK]", "[CLS]"]);
+			assert_eq!(res.err(), Some("[SEP]"));
+		}
+
+		#[test]
+		fn words_split_on_whitespace_and_punctuation() {
+			assert_eq!(
+				WordPiece::words("Hello,  World!\tCafé"),
+				vec!["hello", ",", "world", "!", "cafe"]
+			);
+			assert!(WordPiece::words(" \n ").is_empty());
+		}
+
+		#[test]
+		fn pieces_match_longest_subwords() {
+			let tk = tokenizer();
+			assert_eq!(tk.pieces("unaffable"), vec![8, 9, 10]);
+			assert_eq!(tk.pieces("hello"), vec![4]);
+			// A word with an unknown subword is a single unknown token
+			assert_eq!(tk.pieces("unknown"), vec![1]);
+			assert_eq!(tk.pieces(&"a".repeat(MAX_WORD_CHARS + 1)), vec![1]);
+		}
+
+		#[test]
+		fn encode_adds_special_tokens() {
+			let tk = tokenizer();
+			assert_eq!(tk.encode("Hello, world!", 512), vec![2, 4, 6, 5, 7, 3]);
+			assert_eq!(tk.encode("", 512), vec![2, 3]);
+			assert_eq!(tk.encode("café unaffable", 512), vec![2, 11, 8, 9, 10, 3]);
+		}
+
+		#[test]
+		fn encode_truncates_to_max_tokens() {
+			let tk = tokenizer();
+			assert_eq!(tk.encode("hello world hello world", 4), vec![2, 4, 5, 3]);
+			assert_eq!(tk.encode("unaffable", 3), vec![2, 8, 3]);
+			// The special tokens are always kept
+			assert_eq!(tk.encode("hello", 0), vec![2, 3]);
+		}
+
+		#[test]
+		fn pad_batch() {
+			let tk = tokenizer();
+			let (ids, mask, cols) = tk.pad(&[vec![2, 4, 3], vec![2, 3]]);
+			assert_eq!(cols, 3);
+			assert_eq!(ids, vec![2, 4, 3, 2, 3, 0]);
+			assert_eq!(mask, vec![1, 1, 1, 1, 1, 0]);
+		}
+
+		#[test]
+		fn pool_token_embeddings() {
+			// Two texts of two tokens, with the second token of the
+			// second text being padding
+			let data = [3.0, 0.0, 1.0, 4.0, 0.0, 2.0, 9.0, 9.0];
+			let mask = [1, 1, 1, 0];
+			let res = pool(&[2, 2, 2], &data, &mask, 2, 2).unwrap();
+			assert_eq!(res.len(), 2);
+			// The mean of [3, 0] and [1, 4] is [2, 2]
+			let v = 1.0 / 2f64.sqrt();
+			assert_close(&res[0], &[v, v]);
+			// The padding token is not included
+			assert_close(&res[1], &[0.0, 1.0]);
+		}
+
+		#[test]
+		fn pool_sentence_embeddings() {
+			let data = [3.0, 4.0, 0.0, 0.0];
+			let res = pool(&[2, 2], &data, &[1, 1], 2, 1).unwrap();
+			assert_close(&res[0], &[0.6, 0.8]);
+			// A zero vector is not divided by zero
+			assert_close(&res[1], &[0.0, 0.0]);
+		}
+
+		#[test]
+		fn pool_unexpected_shape() {
+			assert!(pool(&[1, 2, 2], &[0.0; 4], &[1, 1], 2, 1).is_none());
+			assert!(pool(&[2, 3, 2], &[0.0; 12], &[1; 4], 2, 2).is_none());
+			assert!(pool(&[2], &[0.0; 2], &[1, 1], 2, 1).is_none());
+		}
+	}
+}
@@ -20,8 +20,10 @@ pub mod duration;
 pub mod encoding;
 pub mod file;
 pub mod geo;
+pub mod graph;
 pub mod http;
 pub mod math;
+pub mod ml;
 pub mod not;
 pub mod object;
 pub mod operate;
@@ -40,6 +42,7 @@ pub mod r#type;
 pub mod util;
 pub mod value;
 pub mod vector;
+pub mod wasm;
 
 /// Attempts to run any function
 pub async fn run(
@@ -76,6 +79,7 @@ pub async fn run(
 		|| name.eq("file::rename")
 		|| name.eq("file::rename_if_not_exists")
 		|| name.eq("file::list")
+		|| name.eq("ml::embed")
 		|| name.eq("record::exists")
 		|| name.eq("record::is_edge")
 		|| name.eq("type::field")
@@ -83,6 +87,7 @@ pub async fn run(
 		|| name.eq("value::diff")
 		|| name.eq("value::patch")
 		|| name.eq("sequence::nextval")
+		|| name.starts_with("graph::")
 		|| name.starts_with("http")
 		|| name.starts_with("search")
 		|| name.starts_with("crypto::argon2")
@@ -136,6 +141,506 @@ macro_rules! dispatch {
     };
 }
 
+/// The builtin functions which are run synchronously.
+///
+/// The list of functions is passed to the specified macro, following the
+/// leading arguments, so that it can be used both to run the functions and
+/// to determine the number of arguments which they accept.
+macro_rules! synchronous_functions {
+	($mac: ident!($($leading: tt)*), $ctx: ident, $doc: ident) => {
+		$mac!(
+			$($leading)*
+			//
+			"array::add" => array::add,
+			"array::append" => array::append,
+			"array::at" => array::at,
+			"array::boolean_and" => array::boolean_and,
+			"array::boolean_not" => array::boolean_not,
+			"array::boolean_or" => array::boolean_or,
+			"array::boolean_xor" => array::boolean_xor,
+			"array::clump" => array::clump,
+			"array::combine" => array::combine,
+			"array::complement" => array::complement,
+			"array::concat" => array::concat,
+			"array::difference" => array::difference,
+			"array::distinct" => array::distinct,
+			"array::fill" => array::fill,
+			"array::first" => array::first,
+			"array::flatten" => array::flatten,
+			"array::group" => array::group,
+			"array::insert" => array::insert,
+			"array::intersect" => array::intersect,
+			"array::is_empty" => array::is_empty,
+			"array::join" => array::join,
+			"array::last" => array::last,
+			"array::len" => array::len,
+			"array::logical_and" => array::logical_and,
+			"array::logical_or" => array::logical_or,
+			"array::logical_xor" => array::logical_xor,
+			"array::matches" => array::matches,
+			"array::max" => array::max,
+			"array::min" => array::min,
+			"array::pop" => array::pop,
+			"array::prepend" => array::prepend,
+			"array::push" => array::push,
+			"array::range" => array::range,
+			"array::remove" => array::remove,
+			"array::repeat" => array::repeat,
+			"array::reverse" => array::reverse,
+			"array::shuffle" => array::shuffle,
+			"array::slice" => array::slice,
+			"array::sort" => array::sort,
+			"array::sort_natural" => array::sort_natural,
+			"array::sort_lexical" => array::sort_lexical,
+			"array::sort_natural_lexical" => array::sort_natural_lexical,
+			"array::swap" => array::swap,
+			"array::transpose" => array::transpose,
+			"array::union" => array::union,
+			"array::sort::asc" => array::sort::asc,
+			"array::sort::desc" => array::sort::desc,
+			"array::windows" => array::windows,
+			//
+			"bytes::len" => bytes::len,
+			//
+			"count" => count::count,
+			//
+			"crypto::blake3" => crypto::blake3,
+			"crypto::joaat" => crypto::joaat,
+			"crypto::md5" => crypto::md5,
+			"crypto::sha1" => crypto::sha1,
+			"crypto::sha256" => crypto::sha256,
+			"crypto::sha512" => crypto::sha512,
+			"crypto::totp::generate" => crypto::totp::generate,
+			"crypto::totp::secret" => crypto::totp::secret,
+			"crypto::totp::uri" => crypto::totp::uri,
+			"crypto::totp::verify" => crypto::totp::verify,
+			//
+			"duration::days" => duration::days,
+			"duration::hours" => duration::hours,
+			"duration::micros" => duration::micros,
+			"duration::millis" => duration::millis,
+			"duration::mins" => duration::mins,
+			"duration::nanos" => duration::nanos,
+			"duration::secs" => duration::secs,
+			"duration::weeks" => duration::weeks,
+			"duration::years" => duration::years,
+			"duration::from_days" => duration::from::days,
+			"duration::from_hours" => duration::from::hours,
+			"duration::from_micros" => duration::from::micros,
+			"duration::from_millis" => duration::from::millis,
+			"duration::from_mins" => duration::from::mins,
+			"duration::from_nanos" => duration::from::nanos,
+			"duration::from_secs" => duration::from::secs,
+			"duration::from_weeks" => duration::from::weeks,
+			//
+			exp(Files) "file::bucket" => file::bucket,
+			exp(Files) "file::key" => file::key,
+			//
+			"encoding::base64::decode" => encoding::base64::decode,
+			"encoding::base64::encode" => encoding::base64::encode,
+			"encoding::cbor::decode" => encoding::cbor::decode,
+			"encoding::cbor::encode" => encoding::cbor::encode,
+			//
+			"geo::area" => geo::area,
+			"geo::bearing" => geo::bearing,
+			"geo::centroid" => geo::centroid,
+			"geo::distance" => geo::distance,
+			"geo::hash::decode" => geo::hash::decode,
+			"geo::hash::encode" => geo::hash::encode,
+			"geo::is_valid" => geo::is::valid,
+			//
+			"math::abs" => math::abs,
+			"math::acos" => math::acos,
+			"math::acot" => math::acot,
+			"math::asin" => math::asin,
+			"math::atan" => math::atan,
+			"math::bottom" => math::bottom,
+			"math::ceil" => math::ceil,
+			"math::clamp" => math::clamp,
+			"math::cos" => math::cos,
+			"math::cot" => math::cot,
+			"math::deg2rad" => math::deg2rad,
+			"math::fixed" => math::fixed,
+			"math::floor" => math::floor,
+			"math::interquartile" => math::interquartile,
+			"math::lerp" => math::lerp,
+			"math::lerpangle" => math::lerpangle,
+			"math::ln" => math::ln,
+			"math::log" => math::log,
+			"math::log10" => math::log10,
+			"math::log2" => math::log2,
+			"math::max" => math::max,
+			"math::mean" => math::mean,
+			"math::median" => math::median,
+			"math::midhinge" => math::midhinge,
+			"math::min" => math::min,
+			"math::mode" => math::mode,
+			"math::nearestrank" => math::nearestrank,
+			"math::percentile" => math::percentile,
+			"math::pow" => math::pow,
+			"math::product" => math::product,
+			"math::rad2deg" => math::rad2deg,
+			"math::round" => math::round,
+			"math::sign" => math::sign,
+			"math::sin" => math::sin,
+			"math::spread" => math::spread,
+			"math::sqrt" => math::sqrt,
+			"math::stddev" => math::stddev,
+			"math::sum" => math::sum,
+			"math::tan" => math::tan,
+			"math::top" => math::top,
+			"math::trimean" => math::trimean,
+			"math::variance" => math::variance,
+			//
+			"meta::id" => record::id,
+			"meta::tb" => record::tb,
+			//
+			"not" => not::not,
+			//
+			"object::entries" => object::entries,
+			"object::extend" => object::extend,
+			"object::remove" => object::remove,
+			"object::from_entries" => object::from_entries,
+			"object::is_empty" => object::is_empty,
+			"object::keys" => object::keys,
+			"object::len" => object::len,
+			"object::values" => object::values,
+			//
+			"parse::email::host" => parse::email::host,
+			"parse::email::user" => parse::email::user,
+			"parse::url::domain" => parse::url::domain,
+			"parse::url::fragment" => parse::url::fragment,
+			"parse::url::host" => parse::url::host,
+			"parse::url::path" => parse::url::path,
+			"parse::url::port" => parse::url::port,
+			"parse::url::query" => parse::url::query,
+			"parse::url::scheme" => parse::url::scheme,
+			//
+			"rand" => rand::rand,
+			"rand::bool" => rand::bool,
+			"rand::duration" => rand::duration,
+			"rand::enum" => rand::r#enum,
+			"rand::float" => rand::float,
+			"rand::guid" => rand::guid,
+			"rand::int" => rand::int,
+			"rand::string" => rand::string,
+			"rand::time" => rand::time,
+			"rand::ulid" => rand::ulid,
+			"rand::uuid::v4" => rand::uuid::v4,
+			"rand::uuid::v7" => rand::uuid::v7,
+			"rand::uuid" => rand::uuid,
+			//
+			"record::id" => record::id,
+			"record::table" => record::tb,
+			"record::tb" => record::tb,
+			//
+			"session::ac" => session::ac($ctx),
+			"session::db" => session::db($ctx),
+			"session::id" => session::id($ctx),
+			"session::ip" => session::ip($ctx),
+			"session::ns" => session::ns($ctx),
+			"session::origin" => session::origin($ctx),
+			"session::rd" => session::rd($ctx),
+			"session::token" => session::token($ctx),
+			//
+			"string::concat" => string::concat,
+			"string::contains" => string::contains,
+			"string::ends_with" => string::ends_with,
+			"string::join" => string::join,
+			"string::len" => string::len,
+			"string::lowercase" => string::lowercase,
+			"string::matches" => string::matches,
+			"string::repeat" => string::repeat,
+			"string::replace" => string::replace,
+			"string::reverse" => string::reverse,
+			"string::slice" => string::slice,
+			"string::slug" => string::slug,
+			"string::split" => string::split,
+			"string::starts_with" => string::starts_with,
+			"string::trim" => string::trim,
+			"string::uppercase" => string::uppercase,
+			"string::words" => string::words,
+			//
+			"string::distance::damerau_levenshtein" => string::distance::damerau_levenshtein,
+			"string::distance::hamming" => string::distance::hamming,
+			"string::distance::levenshtein" => string::distance::levenshtein,
+			"string::distance::normalized_damerau_levenshtein" => string::distance::normalized_damerau_levenshtein,
+			"string::distance::normalized_levenshtein" => string::distance::normalized_levenshtein,
+			"string::distance::osa" => string::distance::osa_distance,
+			//
+			"string::html::encode" => string::html::encode,
+			"string::html::sanitize" => string::html::sanitize,
+			"string::is_alphanum" => string::is::alphanum,
+			"string::is_alpha" => string::is::alpha,
+			"string::is_ascii" => string::is::ascii,
+			"string::is_datetime" => string::is::datetime,
+			"string::is_domain" => string::is::domain,
+			"string::is_email" => string::is::email,
+			"string::is_hexadecimal" => string::is::hexadecimal,
+			"string::is_ip" => string::is::ip,
+			"string::is_ipv4" => string::is::ipv4,
+			"string::is_ipv6" => string::is::ipv6,
+			"string::is_latitude" => string::is::latitude,
+			"string::is_longitude" => string::is::longitude,
+			"string::is_numeric" => string::is::numeric,
+			"string::is_semver" => string::is::semver,
+			"string::is_url" => string::is::url,
+			"string::is_ulid" => string::is::ulid,
+			"string::is_uuid" => string::is::uuid,
+			"string::is_record" => string::is::record,
+			//
+			"string::similarity::fuzzy" => string::similarity::fuzzy,
+			"string::similarity::jaro" => string::similarity::jaro,
+			"string::similarity::jaro_winkler" => string::similarity::jaro_winkler,
+			"string::similarity::smithwaterman" => string::similarity::smithwaterman,
+			"string::similarity::sorensen_dice" => string::similarity::sorensen_dice,
+			//
+			"string::semver::compare" => string::semver::compare,
+			"string::semver::major" => string::semver::major,
+			"string::semver::minor" => string::semver::minor,
+			"string::semver::patch" => string::semver::patch,
+			"string::semver::inc::major" => string::semver::inc::major,
+			"string::semver::inc::minor" => string::semver::inc::minor,
+			"string::semver::inc::patch" => string::semver::inc::patch,
+			"string::semver::set::major" => string::semver::set::major,
+			"string::semver::set::minor" => string::semver::set::minor,
+			"string::semver::set::patch" => string::semver::set::patch,
+			//
+			"time::ceil" => time::ceil,
+			"time::day" => time::day,
+			"time::floor" => time::floor,
+			"time::format" => time::format,
+			"time::group" => time::group,
+			"time::hour" => time::hour,
+			"time::max" => time::max,
+			"time::min" => time::min,
+			"time::minute" => time::minute,
+			"time::month" => time::month,
+			"time::nano" => time::nano,
+			"time::micros" => time::micros,
+			"time::millis" => time::millis,
+			"time::now" => time::now,
+			"time::round" => time::round,
+			"time::second" => time::second,
+			"time::timezone" => time::timezone,
+			"time::unix" => time::unix,
+			"time::wday" => time::wday,
+			"time::week" => time::week,
+			"time::yday" => time::yday,
+			"time::year" => time::year,
+			"time::from_nanos" => time::from::nanos,
+			"time::from_micros" => time::from::micros,
+			"time::from_millis" => time::from::millis,
+			"time::from_secs" => time::from::secs,
+			"time::from_ulid" => time::from::ulid,
+			"time::from_unix" => time::from::unix,
+			"time::from_uuid" => time::from::uuid,
+			"time::is_leap_year" => time::is::leap_year,
+			//
+			"type::array" => r#type::array,
+			"type::bool" => r#type::bool,
+			"type::bytes" => r#type::bytes,
+			"type::datetime" => r#type::datetime,
+			"type::decimal" => r#type::decimal,
+			"type::duration" => r#type::duration,
+			exp(Files) "type::file" => r#type::file,
+			"type::float" => r#type::float,
+			"type::geometry" => r#type::geometry,
+			"type::int" => r#type::int,
+			"type::number" => r#type::number,
+			"type::point" => r#type::point,
+			"type::range" => r#type::range,
+			"type::record" => r#type::record,
+			"type::string" => r#type::string,
+			"type::string_lossy" => r#type::string_lossy,
+			"type::table" => r#type::table,
+			"type::thing" => r#type::thing,
+			"type::uuid" => r#type::uuid,
+			"type::is_array" => r#type::is::array,
+			"type::is_bool" => r#type::is::bool,
+			"type::is_bytes" => r#type::is::bytes,
+			"type::is_collection" => r#type::is::collection,
+			"type::is_datetime" => r#type::is::datetime,
+			"type::is_decimal" => r#type::is::decimal,
+			"type::is_duration" => r#type::is::duration,
+			"type::is_float" => r#type::is::float,
+			"type::is_geometry" => r#type::is::geometry,
+			"type::is_int" => r#type::is::int,
+			"type::is_line" => r#type::is::line,
+			"type::is_none" => r#type::is::none,
+			"type::is_null" => r#type::is::null,
+			"type::is_multiline" => r#type::is::multiline,
+			"type::is_multipoint" => r#type::is::multipoint,
+			"type::is_multipolygon" => r#type::is::multipolygon,
+			"type::is_number" => r#type::is::number,
+			"type::is_object" => r#type::is::object,
+			"type::is_point" => r#type::is::point,
+			"type::is_polygon" => r#type::is::polygon,
+			"type::is_range" => r#type::is::range,
+			"type::is_record" => r#type::is::record,
+			"type::is_string" => r#type::is::string,
+			"type::is_uuid" => r#type::is::uuid,
+			//
+			"vector::add" => vector::add,
+			"vector::angle" => vector::angle,
+			"vector::cross" => vector::cross,
+			"vector::dot" => vector::dot,
+			"vector::divide" => vector::divide,
+			"vector::magnitude" => vector::magnitude,
+			"vector::multiply" => vector::multiply,
+			"vector::normalize" => vector::normalize,
+			"vector::project" => vector::project,
+			"vector::scale" => vector::scale,
+			"vector::subtract" => vector::subtract,
+			"vector::distance::chebyshev" => vector::distance::chebyshev,
+			"vector::distance::euclidean" => vector::distance::euclidean,
+			"vector::distance::hamming" => vector::distance::hamming,
+			"vector::distance::knn" => vector::distance::knn(($ctx, $doc)),
+			"vector::distance::mahalanobis" => vector::distance::mahalanobis,
+			"vector::distance::manhattan" => vector::distance::manhattan,
+			"vector::distance::minkowski" => vector::distance::minkowski,
+			"vector::similarity::cosine" => vector::similarity::cosine,
+			"vector::similarity::jaccard" => vector::similarity::jaccard,
+			"vector::similarity::pearson" => vector::similarity::pearson,
+			"vector::similarity::spearman" => vector::similarity::spearman,
+		)
+	};
+}
+
+/// The builtin functions which are run asynchronously.
+///
+/// The list of functions is passed to the specified macro, following the
+/// leading arguments, so that it can be used both to run the functions and
+/// to determine the number of arguments which they accept.
+macro_rules! asynchronous_functions {
+	($mac: ident!($($leading: tt)*), $stk: ident, $ctx: ident, $opt: ident, $doc: ident) => {
+		$mac!(
+			$($leading)*
+			//
+			exp(DefineApi) "api::invoke" => api::invoke(($stk, $ctx, $opt)).await,
+			//
+			"array::all" => array::all(($stk, $ctx, Some($opt), $doc)).await,
+			"array::any" => array::any(($stk, $ctx, Some($opt), $doc)).await,
+			"array::every" => array::all(($stk, $ctx, Some($opt), $doc)).await,
+			"array::filter" => array::filter(($stk, $ctx, Some($opt), $doc)).await,
+			"array::filter_index" => array::filter_index(($stk, $ctx, Some($opt), $doc)).await,
+			"array::find" => array::find(($stk, $ctx, Some($opt), $doc)).await,
+			"array::find_index" => array::find_index(($stk, $ctx, Some($opt), $doc)).await,
+			"array::fold" => array::fold(($stk, $ctx, Some($opt), $doc)).await,
+			"array::includes" => array::any(($stk, $ctx, Some($opt), $doc)).await,
+			"array::index_of" => array::find_index(($stk, $ctx, Some($opt), $doc)).await,
+			"array::map" => array::map(($stk, $ctx, Some($opt), $doc)).await,
+			"array::reduce" => array::reduce(($stk, $ctx, Some($opt), $doc)).await,
+			"array::some" => array::any(($stk, $ctx, Some($opt), $doc)).await,
+			//
+			"crypto::argon2::compare" => (cpu_intensive) crypto::argon2::cmp.await,
+			"crypto::argon2::generate" => (cpu_intensive) crypto::argon2::r#gen.await,
+			"crypto::bcrypt::compare" => (cpu_intensive) crypto::bcrypt::cmp.await,
+			"crypto::bcrypt::generate" => (cpu_intensive) crypto::bcrypt::r#gen.await,
+			"crypto::pbkdf2::compare" => (cpu_intensive) crypto::pbkdf2::cmp.await,
+			"crypto::pbkdf2::generate" => (cpu_intensive) crypto::pbkdf2::r#gen.await,
+			"crypto::scrypt::compare" => (cpu_intensive) crypto::scrypt::cmp.await,
+			"crypto::scrypt::generate" => (cpu_intensive) crypto::scrypt::r#gen.await,
+			//
+			exp(Files) "file::put" => file::put(($stk, $ctx, $opt, $doc)).await,
+			exp(Files) "file::put_if_not_exists" => file::put_if_not_exists(($stk, $ctx, $opt, $doc)).await,
+			exp(Files) "file::get" => file::get(($stk, $ctx, $opt, $doc)).await,
+			exp(Files) "file::head" => file::head(($stk, $ctx, $opt, $doc)).await,
+			exp(Files) "file::delete" => file::delete(($stk, $ctx, $opt, $doc)).await,
+			exp(Files) "file::copy" => file::copy(($stk, $ctx, $opt, $doc)).await,
+			exp(Files) "file::copy_if_not_exists" => file::copy_if_not_exists(($stk, $ctx, $opt, $doc)).await,
+			exp(Files) "file::rename" => file::rename(($stk, $ctx, $opt, $doc)).await,
+			exp(Files) "file::rename_if_not_exists" => file::rename_if_not_exists(($stk, $ctx, $opt, $doc)).await,
+			exp(Files) "file::exists" => file::exists(($stk, $ctx, $opt, $doc)).await,
+			exp(Files) "file::list" => file::list(($stk, $ctx, $opt, $doc)).await,
+			//
+			"graph::components" => graph::components(($ctx, Some($opt))).await,
+			"graph::degree_centrality" => graph::degree_centrality(($ctx, Some($opt))).await,
+			"graph::k_shortest_paths" => graph::k_shortest_paths(($stk, $ctx, Some($opt), $doc)).await,
+			"graph::label_propagation" => graph::label_propagation(($ctx, Some($opt))).await,
+			"graph::pagerank" => graph::pagerank(($ctx, Some($opt))).await,
+			"graph::shortest_path" => graph::shortest_path(($stk, $ctx, Some($opt), $doc)).await,
+			"graph::triangles" => graph::triangles(($ctx, Some($opt))).await,
+			//
+			"http::head" => http::head($ctx).await,
+			"http::get" => http::get($ctx).await,
+			"http::put" => http::put($ctx).await,
+			"http::post" =>  http::post($ctx).await,
+			"http::patch" => http::patch($ctx).await,
+			"http::delete" => http::delete($ctx).await,
+			//
+			"ml::embed" => ml::embed(($stk, $ctx, $opt, $doc)).await,
+			//
+			"record::exists" => record::exists(($stk, $ctx, Some($opt), $doc)).await,
+			"record::is_edge" => record::is::edge(($stk, $ctx, Some($opt), $doc)).await,
+			//
+			"search::analyze" => search::analyze(($stk, $ctx, Some($opt))).await,
+			"search::linear" => search::linear($ctx).await,
+			"search::rrf" => search::rrf($ctx).await,
+			"search::score" => search::score(($ctx, $doc)).await,
+			"search::highlight" => search::highlight(($ctx, $doc)).await,
+			"search::offsets" => search::offsets(($ctx, $doc)).await,
+			//
+			"sleep" => sleep::sleep($ctx).await,
+			//
+			"sequence::nextval" => sequence::nextval(($ctx, $opt)).await,
+			//
+			"type::field" => r#type::field(($stk, $ctx, Some($opt), $doc)).await,
+			"type::fields" => r#type::fields(($stk, $ctx, Some($opt), $doc)).await,
+			//
+			"value::diff" => value::diff.await,
+			"value::patch" => value::patch.await,
+			"schema::table::exists" => schema::table::exists(($ctx, Some($opt))).await,
+		)
+	};
+}
+
+/// Determines the number of arguments which a function accepts, given the
+/// name of the function, and a list of functions in the same format as
+/// [`dispatch!`].
+macro_rules! arity {
+	($name: ident,
+		$($(exp($exp_target: ident))? $function_name: literal =>
+			$(($wrapper: tt))* $($function_path: ident)::+ $(($ctx_arg: expr_2021))* $(.$await:tt)*,)+
+	) => {
+		match $name {
+			$($function_name => arity!(@function $($function_path)::+ $(($ctx_arg))*),)+
+			_ => None
+		}
+	};
+	(@function $($function_path: ident)::+) => {
+		args::arity_of($($function_path)::+)
+	};
+	(@function $($function_path: ident)::+ ($ctx_arg: expr)) => {
+		args::arity_of_with($($function_path)::+)
+	};
+}
+
+/// Returns the number of arguments which a builtin function accepts.
+///
+/// Returns `None` if the function does not exist, or if the function checks
+/// its own arguments.
+pub(crate) fn arity(name: &str) -> Option<args::Arity> {
+	synchronous_functions!(arity!(name,), ctx, doc)
+		.or_else(|| asynchronous_functions!(arity!(name,), stk, ctx, opt, doc))
+}
+
+/// Lists the names of functions in the same format as [`dispatch!`].
+macro_rules! names {
+	($($(exp($exp_target: ident))? $function_name: literal =>
+			$(($wrapper: tt))* $($function_path: ident)::+ $(($ctx_arg: expr_2021))* $(.$await:tt)*,)+
+	) => {
+		&[$($function_name,)+]
+	};
+}
+
+/// Returns the names of all of the builtin functions.
+pub(crate) fn names() -> impl Iterator<Item = &'static str> {
+	let synchronous: &[&str] = synchronous_functions!(names!(), ctx, doc);
+	let asynchronous: &[&str] = asynchronous_functions!(names!(), stk, ctx, opt, doc);
+	synchronous.iter().chain(asynchronous).copied()
+}
+
 /// Attempts to run any synchronous function.
 pub fn synchronous(
 	ctx: &Context,
@@ -143,361 +648,7 @@ pub fn synchronous(
 	name: &str,
 	args: Vec<Value>,
 ) -> Result<Value> {
-	dispatch!(
-		ctx,
-		name,
-		args,
-		"no such builtin function found",
-		//
-		"array::add" => array::add,
-		"array::append" => array::append,
-		"array::at" => array::at,
-		"array::boolean_and" => array::boolean_and,
-		"array::boolean_not" => array::boolean_not,
-		"array::boolean_or" => array::boolean_or,
-		"array::boolean_xor" => array::boolean_xor,
-		"array::clump" => array::clump,
-		"array::combine" => array::combine,
-		"array::complement" => array::complement,
-		"array::concat" => array::concat,
-		"array::difference" => array::difference,
-		"array::distinct" => array::distinct,
-		"array::fill" => array::fill,
-		"array::first" => array::first,
-		"array::flatten" => array::flatten,
-		"array::group" => array::group,
-		"array::insert" => array::insert,
-		"array::intersect" => array::intersect,
-		"array::is_empty" => array::is_empty,
-		"array::join" => array::join,
-		"array::last" => array::last,
-		"array::len" => array::len,
-		"array::logical_and" => array::logical_and,
-		"array::logical_or" => array::logical_or,
-		"array::logical_xor" => array::logical_xor,
-		"array::matches" => array::matches,
-		"array::max" => array::max,
-		"array::min" => array::min,
-		"array::pop" => array::pop,
-		"array::prepend" => array::prepend,
-		"array::push" => array::push,
-		"array::range" => array::range,
-		"array::remove" => array::remove,
-		"array::repeat" => array::repeat,
-		"array::reverse" => array::reverse,
-		"array::shuffle" => array::shuffle,
-		"array::slice" => array::slice,
-		"array::sort" => array::sort,
-		"array::sort_natural" => array::sort_natural,
-		"array::sort_lexical" => array::sort_lexical,
-		"array::sort_natural_lexical" => array::sort_natural_lexical,
-		"array::swap" => array::swap,
-		"array::transpose" => array::transpose,
-		"array::union" => array::union,
-		"array::sort::asc" => array::sort::asc,
-		"array::sort::desc" => array::sort::desc,
-		"array::windows" => array::windows,
-		//
-		"bytes::len" => bytes::len,
-		//
-		"count" => count::count,
-		//
-		"crypto::blake3" => crypto::blake3,
-		"crypto::joaat" => crypto::joaat,
-		"crypto::md5" => crypto::md5,
-		"crypto::sha1" => crypto::sha1,
-		"crypto::sha256" => crypto::sha256,
-		"crypto::sha512" => crypto::sha512,
-		//
-		"duration::days" => duration::days,
-		"duration::hours" => duration::hours,
-		"duration::micros" => duration::micros,
-		"duration::millis" => duration::millis,
-		"duration::mins" => duration::mins,
-		"duration::nanos" => duration::nanos,
-		"duration::secs" => duration::secs,
-		"duration::weeks" => duration::weeks,
-		"duration::years" => duration::years,
-		"duration::from_days" => duration::from::days,
-		"duration::from_hours" => duration::from::hours,
-		"duration::from_micros" => duration::from::micros,
-		"duration::from_millis" => duration::from::millis,
-		"duration::from_mins" => duration::from::mins,
-		"duration::from_nanos" => duration::from::nanos,
-		"duration::from_secs" => duration::from::secs,
-		"duration::from_weeks" => duration::from::weeks,
-		//
-		exp(Files) "file::bucket" => file::bucket,
-		exp(Files) "file::key" => file::key,
-		//
-		"encoding::base64::decode" => encoding::base64::decode,
-		"encoding::base64::encode" => encoding::base64::encode,
-		"encoding::cbor::decode" => encoding::cbor::decode,
-		"encoding::cbor::encode" => encoding::cbor::encode,
-		//
-		"geo::area" => geo::area,
-		"geo::bearing" => geo::bearing,
-		"geo::centroid" => geo::centroid,
-		"geo::distance" => geo::distance,
-		"geo::hash::decode" => geo::hash::decode,
-		"geo::hash::encode" => geo::hash::encode,
-		"geo::is_valid" => geo::is::valid,
-		//
-		"math::abs" => math::abs,
-		"math::acos" => math::acos,
-		"math::acot" => math::acot,
-		"math::asin" => math::asin,
-		"math::atan" => math::atan,
-		"math::bottom" => math::bottom,
-		"math::ceil" => math::ceil,
-		"math::clamp" => math::clamp,
-		"math::cos" => math::cos,
-		"math::cot" => math::cot,
-		"math::deg2rad" => math::deg2rad,
-		"math::fixed" => math::fixed,
-		"math::floor" => math::floor,
-		"math::interquartile" => math::interquartile,
-		"math::lerp" => math::lerp,
-		"math::lerpangle" => math::lerpangle,
-		"math::ln" => math::ln,
-		"math::log" => math::log,
-		"math::log10" => math::log10,
-		"math::log2" => math::log2,
-		"math::max" => math::max,
-		"math::mean" => math::mean,
-		"math::median" => math::median,
-		"math::midhinge" => math::midhinge,
-		"math::min" => math::min,
-		"math::mode" => math::mode,
-		"math::nearestrank" => math::nearestrank,
-		"math::percentile" => math::percentile,
-		"math::pow" => math::pow,
-		"math::product" => math::product,
-		"math::rad2deg" => math::rad2deg,
-		"math::round" => math::round,
-		"math::sign" => math::sign,
-		"math::sin" => math::sin,
-		"math::spread" => math::spread,
-		"math::sqrt" => math::sqrt,
-		"math::stddev" => math::stddev,
-		"math::sum" => math::sum,
-		"math::tan" => math::tan,
-		"math::top" => math::top,
-		"math::trimean" => math::trimean,
-		"math::variance" => math::variance,
-		//
-		"meta::id" => record::id,
-		"meta::tb" => record::tb,
-		//
-		"not" => not::not,
-		//
-		"object::entries" => object::entries,
-		"object::extend" => object::extend,
-		"object::remove" => object::remove,
-		"object::from_entries" => object::from_entries,
-		"object::is_empty" => object::is_empty,
-		"object::keys" => object::keys,
-		"object::len" => object::len,
-		"object::values" => object::values,
-		//
-		"parse::email::host" => parse::email::host,
-		"parse::email::user" => parse::email::user,
-		"parse::url::domain" => parse::url::domain,
-		"parse::url::fragment" => parse::url::fragment,
-		"parse::url::host" => parse::url::host,
-		"parse::url::path" => parse::url::path,
-		"parse::url::port" => parse::url::port,
-		"parse::url::query" => parse::url::query,
-		"parse::url::scheme" => parse::url::scheme,
-		//
-		"rand" => rand::rand,
-		"rand::bool" => rand::bool,
-		"rand::duration" => rand::duration,
-		"rand::enum" => rand::r#enum,
-		"rand::float" => rand::float,
-		"rand::guid" => rand::guid,
-		"rand::int" => rand::int,
-		"rand::string" => rand::string,
-		"rand::time" => rand::time,
-		"rand::ulid" => rand::ulid,
-		"rand::uuid::v4" => rand::uuid::v4,
-		"rand::uuid::v7" => rand::uuid::v7,
-		"rand::uuid" => rand::uuid,
-		//
-		"record::id" => record::id,
-		"record::table" => record::tb,
-		"record::tb" => record::tb,
-		//
-		"session::ac" => session::ac(ctx),
-		"session::db" => session::db(ctx),
-		"session::id" => session::id(ctx),
-		"session::ip" => session::ip(ctx),
-		"session::ns" => session::ns(ctx),
-		"session::origin" => session::origin(ctx),
-		"session::rd" => session::rd(ctx),
-		"session::token" => session::token(ctx),
-		//
-		"string::concat" => string::concat,
-		"string::contains" => string::contains,
-		"string::ends_with" => string::ends_with,
-		"string::join" => string::join,
-		"string::len" => string::len,
-		"string::lowercase" => string::lowercase,
-		"string::matches" => string::matches,
-		"string::repeat" => string::repeat,
-		"string::replace" => string::replace,
-		"string::reverse" => string::reverse,
-		"string::slice" => string::slice,
-		"string::slug" => string::slug,
-		"string::split" => string::split,
-		"string::starts_with" => string::starts_with,
-		"string::trim" => string::trim,
-		"string::uppercase" => string::uppercase,
-		"string::words" => string::words,
-		//
-		"string::distance::damerau_levenshtein" => string::distance::damerau_levenshtein,
-		"string::distance::hamming" => string::distance::hamming,
-		"string::distance::levenshtein" => string::distance::levenshtein,
-		"string::distance::normalized_damerau_levenshtein" => string::distance::normalized_damerau_levenshtein,
-		"string::distance::normalized_levenshtein" => string::distance::normalized_levenshtein,
-		"string::distance::osa" => string::distance::osa_distance,
-		//
-		"string::html::encode" => string::html::encode,
-		"string::html::sanitize" => string::html::sanitize,
-		"string::is_alphanum" => string::is::alphanum,
-		"string::is_alpha" => string::is::alpha,
-		"string::is_ascii" => string::is::ascii,
-		"string::is_datetime" => string::is::datetime,
-		"string::is_domain" => string::is::domain,
-		"string::is_email" => string::is::email,
-		"string::is_hexadecimal" => string::is::hexadecimal,
-		"string::is_ip" => string::is::ip,
-		"string::is_ipv4" => string::is::ipv4,
-		"string::is_ipv6" => string::is::ipv6,
-		"string::is_latitude" => string::is::latitude,
-		"string::is_longitude" => string::is::longitude,
-		"string::is_numeric" => string::is::numeric,
-		"string::is_semver" => string::is::semver,
-		"string::is_url" => string::is::url,
-		"string::is_ulid" => string::is::ulid,
-		"string::is_uuid" => string::is::uuid,
-		"string::is_record" => string::is::record,
-		//
-		"string::similarity::fuzzy" => string::similarity::fuzzy,
-		"string::similarity::jaro" => string::similarity::jaro,
-		"string::similarity::jaro_winkler" => string::similarity::jaro_winkler,
-		"string::similarity::smithwaterman" => string::similarity::smithwaterman,
-		"string::similarity::sorensen_dice" => string::similarity::sorensen_dice,
-		//
-		"string::semver::compare" => string::semver::compare,
-		"string::semver::major" => string::semver::major,
-		"string::semver::minor" => string::semver::minor,
-		"string::semver::patch" => string::semver::patch,
-		"string::semver::inc::major" => string::semver::inc::major,
-		"string::semver::inc::minor" => string::semver::inc::minor,
-		"string::semver::inc::patch" => string::semver::inc::patch,
-		"string::semver::set::major" => string::semver::set::major,
-		"string::semver::set::minor" => string::semver::set::minor,
-		"string::semver::set::patch" => string::semver::set::patch,
-		//
-		"time::ceil" => time::ceil,
-		"time::day" => time::day,
-		"time::floor" => time::floor,
-		"time::format" => time::format,
-		"time::group" => time::group,
-		"time::hour" => time::hour,
-		"time::max" => time::max,
-		"time::min" => time::min,
-		"time::minute" => time::minute,
-		"time::month" => time::month,
-		"time::nano" => time::nano,
-		"time::micros" => time::micros,
-		"time::millis" => time::millis,
-		"time::now" => time::now,
-		"time::round" => time::round,
-		"time::second" => time::second,
-		"time::timezone" => time::timezone,
-		"time::unix" => time::unix,
-		"time::wday" => time::wday,
-		"time::week" => time::week,
-		"time::yday" => time::yday,
-		"time::year" => time::year,
-		"time::from_nanos" => time::from::nanos,
-		"time::from_micros" => time::from::micros,
-		"time::from_millis" => time::from::millis,
-		"time::from_secs" => time::from::secs,
-		"time::from_ulid" => time::from::ulid,
-		"time::from_unix" => time::from::unix,
-		"time::from_uuid" => time::from::uuid,
-		"time::is_leap_year" => time::is::leap_year,
-		//
-		"type::array" => r#type::array,
-		"type::bool" => r#type::bool,
-		"type::bytes" => r#type::bytes,
-		"type::datetime" => r#type::datetime,
-		"type::decimal" => r#type::decimal,
-		"type::duration" => r#type::duration,
-		exp(Files) "type::file" => r#type::file,
-		"type::float" => r#type::float,
-		"type::geometry" => r#type::geometry,
-		"type::int" => r#type::int,
-		"type::number" => r#type::number,
-		"type::point" => r#type::point,
-		"type::range" => r#type::range,
-		"type::record" => r#type::record,
-		"type::string" => r#type::string,
-		"type::string_lossy" => r#type::string_lossy,
-		"type::table" => r#type::table,
-		"type::thing" => r#type::thing,
-		"type::uuid" => r#type::uuid,
-		"type::is_array" => r#type::is::array,
-		"type::is_bool" => r#type::is::bool,
-		"type::is_bytes" => r#type::is::bytes,
-		"type::is_collection" => r#type::is::collection,
-		"type::is_datetime" => r#type::is::datetime,
-		"type::is_decimal" => r#type::is::decimal,
-		"type::is_duration" => r#type::is::duration,
-		"type::is_float" => r#type::is::float,
-		"type::is_geometry" => r#type::is::geometry,
-		"type::is_int" => r#type::is::int,
-		"type::is_line" => r#type::is::line,
-		"type::is_none" => r#type::is::none,
-		"type::is_null" => r#type::is::null,
-		"type::is_multiline" => r#type::is::multiline,
-		"type::is_multipoint" => r#type::is::multipoint,
-		"type::is_multipolygon" => r#type::is::multipolygon,
-		"type::is_number" => r#type::is::number,
-		"type::is_object" => r#type::is::object,
-		"type::is_point" => r#type::is::point,
-		"type::is_polygon" => r#type::is::polygon,
-		"type::is_range" => r#type::is::range,
-		"type::is_record" => r#type::is::record,
-		"type::is_string" => r#type::is::string,
-		"type::is_uuid" => r#type::is::uuid,
-		//
-		"vector::add" => vector::add,
-		"vector::angle" => vector::angle,
-		"vector::cross" => vector::cross,
-		"vector::dot" => vector::dot,
-		"vector::divide" => vector::divide,
-		"vector::magnitude" => vector::magnitude,
-		"vector::multiply" => vector::multiply,
-		"vector::normalize" => vector::normalize,
-		"vector::project" => vector::project,
-		"vector::scale" => vector::scale,
-		"vector::subtract" => vector::subtract,
-		"vector::distance::chebyshev" => vector::distance::chebyshev,
-		"vector::distance::euclidean" => vector::distance::euclidean,
-		"vector::distance::hamming" => vector::distance::hamming,
-		"vector::distance::knn" => vector::distance::knn((ctx, doc)),
-		"vector::distance::mahalanobis" => vector::distance::mahalanobis,
-		"vector::distance::manhattan" => vector::distance::manhattan,
-		"vector::distance::minkowski" => vector::distance::minkowski,
-		"vector::similarity::cosine" => vector::similarity::cosine,
-		"vector::similarity::jaccard" => vector::similarity::jaccard,
-		"vector::similarity::pearson" => vector::similarity::pearson,
-		"vector::similarity::spearman" => vector::similarity::spearman,
-	)
+	synchronous_functions!(dispatch!(ctx, name, args, "no such builtin function found",), ctx, doc)
 }
 
 /// Attempts to run any asynchronous function.
@@ -525,76 +676,12 @@ pub async fn asynchronous(
 		|| std::future::ready(function())
 	}
 
-	dispatch!(
+	asynchronous_functions!(
+		dispatch!(ctx, name, args, "no such builtin function found",),
+		stk,
 		ctx,
-		name,
-		args,
-		"no such builtin function found",
-		//
-		exp(DefineApi) "api::invoke" => api::invoke((stk, ctx, opt)).await,
-		//
-		"array::all" => array::all((stk, ctx, Some(opt), doc)).await,
-		"array::any" => array::any((stk, ctx, Some(opt), doc)).await,
-		"array::every" => array::all((stk, ctx, Some(opt), doc)).await,
-		"array::filter" => array::filter((stk, ctx, Some(opt), doc)).await,
-		"array::filter_index" => array::filter_index((stk, ctx, Some(opt), doc)).await,
-		"array::find" => array::find((stk, ctx, Some(opt), doc)).await,
-		"array::find_index" => array::find_index((stk, ctx, Some(opt), doc)).await,
-		"array::fold" => array::fold((stk, ctx, Some(opt), doc)).await,
-		"array::includes" => array::any((stk, ctx, Some(opt), doc)).await,
-		"array::index_of" => array::find_index((stk, ctx, Some(opt), doc)).await,
-		"array::map" => array::map((stk, ctx, Some(opt), doc)).await,
-		"array::reduce" => array::reduce((stk, ctx, Some(opt), doc)).await,
-		"array::some" => array::any((stk, ctx, Some(opt), doc)).await,
-		//
-		"crypto::argon2::compare" => (cpu_intensive) crypto::argon2::cmp.await,
-		"crypto::argon2::generate" => (cpu_intensive) crypto::argon2::r#gen.await,
-		"crypto::bcrypt::compare" => (cpu_intensive) crypto::bcrypt::cmp.await,
-		"crypto::bcrypt::generate" => (cpu_intensive) crypto::bcrypt::r#gen.await,
-		"crypto::pbkdf2::compare" => (cpu_intensive) crypto::pbkdf2::cmp.await,
-		"crypto::pbkdf2::generate" => (cpu_intensive) crypto::pbkdf2::r#gen.await,
-		"crypto::scrypt::compare" => (cpu_intensive) crypto::scrypt::cmp.await,
-		"crypto::scrypt::generate" => (cpu_intensive) crypto::scrypt::r#gen.await,
-		//
-		exp(Files) "file::put" => file::put((stk, ctx, opt, doc)).await,
-		exp(Files) "file::put_if_not_exists" => file::put_if_not_exists((stk, ctx, opt, doc)).await,
-		exp(Files) "file::get" => file::get((stk, ctx, opt, doc)).await,
-		exp(Files) "file::head" => file::head((stk, ctx, opt, doc)).await,
-		exp(Files) "file::delete" => file::delete((stk, ctx, opt, doc)).await,
-		exp(Files) "file::copy" => file::copy((stk, ctx, opt, doc)).await,
-		exp(Files) "file::copy_if_not_exists" => file::copy_if_not_exists((stk, ctx, opt, doc)).await,
-		exp(Files) "file::rename" => file::rename((stk, ctx, opt, doc)).await,
-		exp(Files) "file::rename_if_not_exists" => file::rename_if_not_exists((stk, ctx, opt, doc)).await,
-		exp(Files) "file::exists" => file::exists((stk, ctx, opt, doc)).await,
-		exp(Files) "file::list" => file::list((stk, ctx, opt, doc)).await,
-		//
-		"http::head" => http::head(ctx).await,
-		"http::get" => http::get(ctx).await,
-		"http::put" => http::put(ctx).await,
-		"http::post" =>  http::post(ctx).await,
-		"http::patch" => http::patch(ctx).await,
-		"http::delete" => http::delete(ctx).await,
-		//
-		"record::exists" => record::exists((stk, ctx, Some(opt), doc)).await,
-		"record::is_edge" => record::is::edge((stk, ctx, Some(opt), doc)).await,
-		//
-		"search::analyze" => search::analyze((stk, ctx, Some(opt))).await,
-		"search::linear" => search::linear(ctx).await,
-		"search::rrf" => search::rrf(ctx).await,
-		"search::score" => search::score((ctx, doc)).await,
-		"search::highlight" => search::highlight((ctx, doc)).await,
-		"search::offsets" => search::offsets((ctx, doc)).await,
-		//
-		"sleep" => sleep::sleep(ctx).await,
-		//
-		"sequence::nextval" => sequence::nextval((ctx, opt)).await,
-		//
-		"type::field" => r#type::field((stk, ctx, Some(opt), doc)).await,
-		"type::fields" => r#type::fields((stk, ctx, Some(opt), doc)).await,
-		//
-		"value::diff" => value::diff.await,
-		"value::patch" => value::patch.await,
-		"schema::table::exists" => schema::table::exists((ctx, Some(opt))).await,
+		opt,
+		doc
 	)
 }
 
@@ -5,6 +5,7 @@ use anyhow::Result;
 use js::prelude::*;
 use js::{CatchResultExt, Ctx, Function, Module, Promise, async_with};
 
+use super::modules::library::Library;
 use super::modules::surrealdb::query::QueryContext;
 use super::modules::{loader, resolver};
 use super::{classes, fetch, globals, modules};
@@ -62,6 +63,9 @@ pub async fn run(
 	let instant_start = Instant::now();
 	let time_limit = Duration::from_millis(*crate::cnf::SCRIPTING_MAX_TIME_LIMIT as u64);
 
+	// Fetch the modules which can be imported by the script
+	let library = Library::new(context, &opt).await?;
+
 	// Create a JavaScript context
 	let run = js::AsyncRuntime::new().unwrap();
 	// Explicitly set max stack size to 256 KiB
@@ -75,7 +79,7 @@ pub async fn run(
 	// Create an execution context
 	let ctx = js::AsyncContext::full(&run).await.unwrap();
 	// Set the module resolver and loader
-	run.set_loader(resolver(), loader()).await;
+	run.set_loader((resolver(), library.clone()), (loader(), library)).await;
 	// Create the main function structure
 	let src = format!(
 		"export default async function() {{ try {{ {src} }} catch(e) {{ return (e instanceof Error) ? e : new Error(e); }} }}"
@@ -1,6 +1,7 @@
 #![cfg(feature = "scripting")]
 
 pub use main::run;
+pub(crate) use modules::library::validate_module;
 
 mod classes;
 mod error;
@@ -0,0 +1,104 @@
+//! Resolves and loads the modules which are defined in a database using
+//! `DEFINE MODULE`, so that they can be imported by embedded functions.
+//!
+//! Modules are imported using their full name, for example
+//! `import { slugify } from 'mod::utils::strings'`. Each module is compiled
+//! to bytecode the first time it is loaded, and the bytecode is stored in
+//! the datastore cache, keyed by a checksum of the module source. This means
+//! that a module is only compiled once per datastore, and that a changed
+//! definition is never served from stale bytecode.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use js::loader::{Loader, Resolver};
+use js::module::{Declared, WriteOptions};
+use js::{CatchResultExt, Ctx, Module};
+
+use crate::catalog::providers::DatabaseProvider;
+use crate::catalog::{DatabaseId, ModuleDefinition, NamespaceId};
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::kvs::cache::ds::{DatastoreCache, Entry, Lookup};
+
+/// The modules which can be imported from the current database
+#[derive(Clone, Default)]
+pub struct Library {
+	db: Option<(NamespaceId, DatabaseId)>,
+	modules: Arc<[ModuleDefinition]>,
+	cache: Option<Arc<DatastoreCache>>,
+}
+
+impl Library {
+	/// Fetches the modules which are defined in the current database
+	pub async fn new(ctx: &Context, opt: &Options) -> Result<Self> {
+		// Modules can only be used when a database is selected
+		let Ok((ns, db)) = opt.ns_db() else {
+			return Ok(Self::default());
+		};
+		let Some(db) = ctx.tx().get_db_by_name(ns, db).await? else {
+			return Ok(Self::default());
+		};
+		let modules = ctx.tx().all_db_modules(db.namespace_id, db.database_id).await?;
+		Ok(Self {
+			db: Some((db.namespace_id, db.database_id)),
+			modules,
+			cache: ctx.get_cache(),
+		})
+	}
+
+	/// Finds the module with the specified import specifier
+	fn find(&self, specifier: &str) -> Option<&ModuleDefinition> {
+		let name = specifier.strip_prefix("mod::")?;
+		self.modules.iter().find(|md| md.name == name)
+	}
+}
+
+impl Resolver for Library {
+	fn resolve<'js>(&mut self, _ctx: &Ctx<'js>, base: &str, name: &str) -> js::Result<String> {
+		match self.find(name) {
+			Some(md) => Ok(md.specifier()),
+			None => Err(js::Error::new_resolving(base, name)),
+		}
+	}
+}
+
+impl Loader for Library {
+	fn load<'js>(&mut self, ctx: &Ctx<'js>, name: &str) -> js::Result<Module<'js, Declared>> {
+		let Some(md) = self.find(name) else {
+			return Err(js::Error::new_loading(name));
+		};
+		let (Some(cache), Some((ns, db))) = (&self.cache, self.db) else {
+			return Module::declare(ctx.clone(), md.specifier(), md.source.as_str());
+		};
+		// Load the module from the compiled bytecode if possible
+		let lookup = Lookup::Mdc(ns, db, &md.name, md.checksum());
+		if let Some(bytecode) = cache.get(&lookup).and_then(|v| v.try_into_type::<Vec<u8>>().ok()) {
+			// SAFETY: The bytecode was written by the same version of the
+			// engine, from the module source which matches the checksum.
+			return unsafe { Module::load(ctx.clone(), &bytecode) };
+		}
+		// Otherwise compile the module, and store the bytecode
+		let module = Module::declare(ctx.clone(), md.specifier(), md.source.as_str())?;
+		let bytecode = module.write(WriteOptions::default())?;
+		cache.insert(lookup, Entry::Any(Arc::new(bytecode)));
+		Ok(module)
+	}
+}
+
+/// Ensures that the source of a module can be compiled
+pub fn validate_module(md: &ModuleDefinition) -> Result<()> {
+	let run = js::Runtime::new().map_err(|e| Error::InvalidScript {
+		message: e.to_string(),
+	})?;
+	let ctx = js::Context::full(&run).map_err(|e| Error::InvalidScript {
+		message: e.to_string(),
+	})?;
+	ctx.with(|ctx| {
+		Module::declare(ctx.clone(), md.specifier(), md.source.as_str())
+			.catch(&ctx)
+			.map(|_| ())
+			.map_err(|e| anyhow::Error::new(Error::from(e)))
+	})
+}
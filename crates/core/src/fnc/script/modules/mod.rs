@@ -1,3 +1,4 @@
+pub mod library;
 pub mod os;
 pub mod surrealdb;
 
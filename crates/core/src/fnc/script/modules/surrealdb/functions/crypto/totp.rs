@@ -0,0 +1,13 @@
+use super::super::run;
+use crate::fnc::script::modules::impl_module_def;
+
+pub struct Package;
+
+impl_module_def!(
+	Package,
+	"crypto::totp",
+	"generate" => run,
+	"secret" => run,
+	"uri" => run,
+	"verify" => run
+);
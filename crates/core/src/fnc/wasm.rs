@@ -0,0 +1,302 @@
+#![cfg(feature = "wasm-functions")]
+//! Executes user-defined functions which are implemented by an export of a
+//! WebAssembly module.
+//!
+//! Modules run in a sandbox, and can not import any host functionality. Each
+//! invocation runs in a new instance, with limits on the memory which the
+//! instance can allocate, the fuel which it can consume, and the time for
+//! which it can run.
+//!
+//! Arguments and results cross the boundary encoded as CBOR, using the same
+//! encoding as the RPC protocol. A module must export:
+//!
+//! - `memory`: the linear memory of the module.
+//! - `alloc(len: i32) -> i32`: allocates `len` bytes, returning a pointer.
+//! - the bound function, as `(ptr: i32, len: i32) -> i64`, which receives an array of the function
+//!   arguments, and returns the pointer to the result in the upper 32 bits, and the length of the
+//!   result in the lower 32 bits.
+
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use wasmtime::{
+	Config, Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder, Trap, UpdateDeadline,
+};
+
+use crate::catalog::WasmFunction;
+use crate::cnf::{
+	WASM_MAX_FUEL, WASM_MAX_MEMORY_LIMIT, WASM_MAX_TIME_LIMIT, WASM_MODULE_CACHE_SIZE,
+};
+use crate::ctx::Context;
+use crate::ctx::cancellation::Cancellation;
+use crate::err::Error;
+use crate::rpc::format::cbor;
+use crate::val::{Array, Value};
+
+/// The interval at which running functions check whether they should stop
+const EPOCH_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The engine which compiles and runs every module
+static ENGINE: LazyLock<Engine> = LazyLock::new(|| {
+	let mut config = Config::new();
+	config.consume_fuel(true);
+	config.epoch_interruption(true);
+	Engine::new(&config).expect("the WebAssembly engine configuration is valid")
+});
+
+/// Tracks the functions which are currently running
+static TICKER: Mutex<Ticker> = Mutex::new(Ticker {
+	running: 0,
+	ticking: false,
+});
+
+/// Advances the epoch of the engine while any function is running, so that
+/// running functions can be interrupted. No thread runs while idle.
+struct Ticker {
+	/// The number of functions which are currently running
+	running: usize,
+	/// Whether a thread is currently advancing the epoch
+	ticking: bool,
+}
+
+impl Ticker {
+	/// Registers a running function, starting the ticker thread if needed
+	fn start() -> TickerGuard {
+		let mut ticker = TICKER.lock();
+		ticker.running += 1;
+		if !ticker.ticking {
+			ticker.ticking = true;
+			std::thread::spawn(|| {
+				loop {
+					std::thread::sleep(EPOCH_INTERVAL);
+					let mut ticker = TICKER.lock();
+					// Stop once there are no more running functions
+					if ticker.running == 0 {
+						ticker.ticking = false;
+						return;
+					}
+					drop(ticker);
+					ENGINE.increment_epoch();
+				}
+			});
+		}
+		TickerGuard
+	}
+}
+
+/// Unregisters a running function when dropped
+struct TickerGuard;
+
+impl Drop for TickerGuard {
+	fn drop(&mut self) {
+		TICKER.lock().running -= 1;
+	}
+}
+
+/// Compiled modules, keyed by the hash of the module in the function definition
+static MODULES: LazyLock<quick_cache::sync::Cache<[u8; 32], Module>> =
+	LazyLock::new(|| quick_cache::sync::Cache::new(*WASM_MODULE_CACHE_SIZE));
+
+/// The state of a running function
+struct State {
+	limits: StoreLimits,
+	started: Instant,
+	cancellation: Cancellation,
+}
+
+fn invalid(message: impl Into<String>) -> anyhow::Error {
+	anyhow::Error::new(Error::InvalidWasm {
+		message: message.into(),
+	})
+}
+
+/// Converts an error raised by the runtime into a user facing error
+fn runtime_error(e: anyhow::Error) -> anyhow::Error {
+	match e.downcast_ref::<Trap>() {
+		Some(Trap::OutOfFuel) => invalid("The function exceeded the maximum amount of fuel."),
+		Some(Trap::Interrupt) => invalid("The function was interrupted."),
+		_ => invalid(format!("{e:#}")),
+	}
+}
+
+/// Compiles a module, or fetches the compiled module from the cache
+fn module(wasm: &WasmFunction) -> Result<Module> {
+	MODULES.get_or_insert_with(&wasm.hash, || {
+		Module::new(&ENGINE, wasm.module.as_slice()).map_err(runtime_error)
+	})
+}
+
+/// Compiles a module in a blocking task, unless it has already been compiled
+async fn compile(wasm: &WasmFunction) -> Result<Module> {
+	if let Some(module) = MODULES.get(&wasm.hash) {
+		return Ok(module);
+	}
+	let wasm = wasm.clone();
+	tokio::task::spawn_blocking(move || module(&wasm)).await.map_err(|e| invalid(e.to_string()))?
+}
+
+/// Ensures that a module can be compiled, and that it exports the function
+pub async fn validate(wasm: &WasmFunction) -> Result<()> {
+	let module = compile(wasm).await?;
+	if module.imports().len() > 0 {
+		return Err(invalid("The module must not import any functions or memories."));
+	}
+	for name in ["memory", "alloc", wasm.export.as_str()] {
+		if module.get_export(name).is_none() {
+			return Err(invalid(format!("The module does not export `{name}`.")));
+		}
+	}
+	Ok(())
+}
+
+/// Runs a WebAssembly function with the specified arguments
+///
+/// The function runs in a blocking task, so that it does not hold up the
+/// other tasks on the executor while it runs.
+pub async fn run(ctx: &Context, wasm: &WasmFunction, args: Vec<Value>) -> Result<Value> {
+	// Compile the module, or fetch it from the cache
+	let module = compile(wasm).await?;
+	// Run the function in a blocking task
+	let export = wasm.export.clone();
+	let cancellation = ctx.cancellation();
+	tokio::task::spawn_blocking(move || call(&module, &export, cancellation, args))
+		.await
+		.map_err(|e| invalid(e.to_string()))?
+}
+
+/// Calls an export of a compiled module with the specified arguments
+fn call(
+	module: &Module,
+	export: &str,
+	cancellation: Cancellation,
+	args: Vec<Value>,
+) -> Result<Value> {
+	// Setup the sandbox for this invocation
+	let time_limit = Duration::from_millis(*WASM_MAX_TIME_LIMIT as u64);
+	let state = State {
+		limits: StoreLimitsBuilder::new()
+			.memory_size(*WASM_MAX_MEMORY_LIMIT)
+			.instances(1)
+			.memories(1)
+			.tables(1)
+			.build(),
+		started: Instant::now(),
+		cancellation,
+	};
+	let mut store = Store::new(&ENGINE, state);
+	store.limiter(|state| &mut state.limits);
+	store.set_fuel(*WASM_MAX_FUEL)?;
+	store.set_epoch_deadline(1);
+	store.epoch_deadline_callback(move |store| {
+		let state = store.data();
+		if state.cancellation.is_done() || state.started.elapsed() > time_limit {
+			return Err(Trap::Interrupt.into());
+		}
+		Ok(UpdateDeadline::Continue(1))
+	});
+	// Advance the epoch while the function runs
+	let _ticker = Ticker::start();
+	// Modules can not import any host functionality
+	let instance = Instance::new(&mut store, module, &[]).map_err(runtime_error)?;
+	let memory = instance
+		.get_memory(&mut store, "memory")
+		.ok_or_else(|| invalid("The module does not export `memory`."))?;
+	let alloc = instance
+		.get_typed_func::<i32, i32>(&mut store, "alloc")
+		.map_err(|_| invalid("The module does not export `alloc` as (i32) -> i32."))?;
+	let func = instance.get_typed_func::<(i32, i32), i64>(&mut store, export).map_err(|_| {
+		invalid(format!("The module does not export `{export}` as (i32, i32) -> i64."))
+	})?;
+	// Copy the arguments into the instance
+	let input = cbor::encode(Value::Array(Array::from(args))).map_err(invalid)?;
+	let len = i32::try_from(input.len()).map_err(|_| invalid("The arguments are too large."))?;
+	let ptr = alloc.call(&mut store, len).map_err(runtime_error)?;
+	memory.write(&mut store, ptr as u32 as usize, &input).map_err(|e| invalid(e.to_string()))?;
+	// Run the function
+	let packed = func.call(&mut store, (ptr, len)).map_err(runtime_error)? as u64;
+	// Copy the result out of the instance
+	let ptr = (packed >> 32) as usize;
+	let len = (packed & 0xffff_ffff) as usize;
+	let output = memory
+		.data(&store)
+		.get(ptr..ptr.saturating_add(len))
+		.ok_or_else(|| invalid("The function returned a result outside of its memory."))?;
+	cbor::decode(output).map_err(|e| invalid(format!("The function returned invalid CBOR: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+	use sha2::{Digest, Sha256};
+
+	use super::*;
+	use crate::ctx::MutableContext;
+	use crate::val::Bytes;
+
+	/// A module which returns its arguments, using a bump allocator
+	const ECHO: &str = r#"
+		(module
+			(memory (export "memory") 1)
+			(global $next (mut i32) (i32.const 1024))
+			(func (export "alloc") (param $len i32) (result i32)
+				(local $ptr i32)
+				(local.set $ptr (global.get $next))
+				(global.set $next (i32.add (global.get $next) (local.get $len)))
+				(local.get $ptr))
+			(func (export "echo") (param $ptr i32) (param $len i32) (result i64)
+				(i64.or
+					(i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+					(i64.extend_i32_u (local.get $len))))
+			(func (export "spin") (param i32) (param i32) (result i64)
+				(loop $l (br $l))
+				(i64.const 0)))
+	"#;
+
+	fn function(module: &str, export: &str) -> WasmFunction {
+		WasmFunction {
+			module: Bytes(module.as_bytes().to_vec()),
+			export: export.to_string(),
+			hash: Sha256::digest(module.as_bytes()).into(),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_validate() {
+		assert!(validate(&function(ECHO, "echo")).await.is_ok());
+		assert!(validate(&function(ECHO, "missing")).await.is_err());
+		assert!(validate(&function("not a module", "echo")).await.is_err());
+		let imports = r#"(module (import "env" "f" (func)) (memory (export "memory") 1))"#;
+		assert!(validate(&function(imports, "memory")).await.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_run() {
+		let ctx = MutableContext::background().freeze();
+		let args = vec![Value::from(1), Value::from("two")];
+		let res = run(&ctx, &function(ECHO, "echo"), args.clone()).await.unwrap();
+		assert_eq!(res, Value::Array(Array::from(args)));
+	}
+
+	#[tokio::test]
+	async fn test_run_out_of_fuel() {
+		let ctx = MutableContext::background().freeze();
+		let res = run(&ctx, &function(ECHO, "spin"), vec![]).await;
+		assert!(res.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_run_does_not_block_executor() {
+		let ctx = MutableContext::background().freeze();
+		let func = function(ECHO, "spin");
+		// Other tasks make progress while a function is running
+		let spin = run(&ctx, &func, vec![]);
+		tokio::pin!(spin);
+		tokio::select! {
+			biased;
+			_ = &mut spin => panic!("The function blocked the executor"),
+			_ = tokio::time::sleep(Duration::from_millis(1)) => {}
+		}
+		assert!(spin.await.is_err());
+	}
+}
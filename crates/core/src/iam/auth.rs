@@ -2,7 +2,7 @@ use anyhow::Result;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 
-use super::{Action, Actor, Level, Resource, Role, is_allowed};
+use super::{Action, Actor, Level, Privileges, Resource, Role, is_allowed};
 
 /// Specifies the current authentication for the datastore execution context.
 #[revisioned(revision = 1)]
@@ -123,4 +123,9 @@ impl Auth {
 	pub fn has_viewer_role(&self) -> bool {
 		self.actor.has_viewer_role()
 	}
+
+	/// Returns the privileges of the custom roles of the current actor
+	pub fn privileges(&self) -> &Privileges {
+		self.actor.privileges()
+	}
 }
@@ -1,13 +1,22 @@
 use crate::catalog::PermissionKind;
 use crate::dbs::Statement;
+use crate::iam::Privilege;
 
-// TODO(sgirones): For now keep it simple. In the future, we will allow for
-// custom roles and policies using a more exhaustive list of actions and
-// resources.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd)]
+/// The action which an actor is attempting to perform on a resource.
+///
+/// The `View` and `Edit` actions are coarse-grained, and are used where no
+/// finer privilege applies. The remaining actions map directly onto the
+/// privileges which can be granted to, or revoked from, a custom role.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd)]
 pub enum Action {
 	View,
 	Edit,
+	Select,
+	Create,
+	Update,
+	Delete,
+	Define,
+	Remove,
 }
 
 impl std::fmt::Display for Action {
@@ -15,6 +24,12 @@ impl std::fmt::Display for Action {
 		match self {
 			Action::View => write!(f, "View"),
 			Action::Edit => write!(f, "Edit"),
+			Action::Select => write!(f, "Select"),
+			Action::Create => write!(f, "Create"),
+			Action::Update => write!(f, "Update"),
+			Action::Delete => write!(f, "Delete"),
+			Action::Define => write!(f, "Define"),
+			Action::Remove => write!(f, "Remove"),
 		}
 	}
 }
@@ -23,22 +38,47 @@ impl Action {
 	pub fn id(&self) -> String {
 		self.to_string()
 	}
+
+	/// Checks if this action only reads a resource
+	pub fn is_view(&self) -> bool {
+		matches!(self, Action::View | Action::Select)
+	}
+
+	/// Checks if this action modifies a resource
+	pub fn is_edit(&self) -> bool {
+		!self.is_view()
+	}
+
+	/// Returns the custom role privilege which is required to
+	/// perform this action. Coarse-grained edits, such as
+	/// altering or rebuilding a resource, require the `DEFINE`
+	/// privilege on that resource.
+	pub fn privilege(&self) -> Privilege {
+		match self {
+			Action::View | Action::Select => Privilege::Select,
+			Action::Create => Privilege::Create,
+			Action::Update => Privilege::Update,
+			Action::Delete => Privilege::Delete,
+			Action::Edit | Action::Define => Privilege::Define,
+			Action::Remove => Privilege::Remove,
+		}
+	}
 }
 
 impl From<&Statement<'_>> for Action {
 	fn from(stmt: &Statement) -> Self {
 		match stmt {
-			Statement::Live(_) => Action::View,
+			Statement::Live(_) => Action::Select,
 			Statement::Select {
 				..
-			} => Action::View,
-			Statement::Show(_) => Action::View,
-			Statement::Create(_) => Action::Edit,
-			Statement::Upsert(_) => Action::Edit,
-			Statement::Update(_) => Action::Edit,
-			Statement::Relate(_) => Action::Edit,
-			Statement::Delete(_) => Action::Edit,
-			Statement::Insert(_) => Action::Edit,
+			} => Action::Select,
+			Statement::Show(_) => Action::Select,
+			Statement::Create(_) => Action::Create,
+			Statement::Upsert(_) => Action::Update,
+			Statement::Update(_) => Action::Update,
+			Statement::Relate(_) => Action::Create,
+			Statement::Delete(_) => Action::Delete,
+			Statement::Insert(_) => Action::Create,
 			Statement::Access(_) => Action::Edit,
 		}
 	}
@@ -47,10 +87,10 @@ impl From<&Statement<'_>> for Action {
 impl std::convert::From<PermissionKind> for Action {
 	fn from(kind: PermissionKind) -> Self {
 		match kind {
-			PermissionKind::Select => Action::View,
-			PermissionKind::Create => Action::Edit,
-			PermissionKind::Update => Action::Edit,
-			PermissionKind::Delete => Action::Edit,
+			PermissionKind::Select => Action::Select,
+			PermissionKind::Create => Action::Create,
+			PermissionKind::Update => Action::Update,
+			PermissionKind::Delete => Action::Delete,
 		}
 	}
 }
@@ -1,7 +1,9 @@
 mod action;
+mod privilege;
 mod resources;
 mod roles;
 
 pub use self::action::*;
+pub use self::privilege::*;
 pub use self::resources::*;
 pub use self::roles::*;
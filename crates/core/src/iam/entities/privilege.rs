@@ -0,0 +1,157 @@
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+
+use super::{Level, Resource, ResourceKind};
+use crate::fmt::{EscapeIdent, EscapeKwFreeIdent};
+
+/// A fine-grained privilege which can be granted to, or
+/// revoked from, a custom role.
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Privilege {
+	Select,
+	Create,
+	Update,
+	Delete,
+	Define,
+	Remove,
+}
+
+impl Privilege {
+	/// All of the privileges which can be granted to a role
+	pub const ALL: [Privilege; 6] = [
+		Privilege::Select,
+		Privilege::Create,
+		Privilege::Update,
+		Privilege::Delete,
+		Privilege::Define,
+		Privilege::Remove,
+	];
+}
+
+impl std::fmt::Display for Privilege {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Privilege::Select => write!(f, "SELECT"),
+			Privilege::Create => write!(f, "CREATE"),
+			Privilege::Update => write!(f, "UPDATE"),
+			Privilege::Delete => write!(f, "DELETE"),
+			Privilege::Define => write!(f, "DEFINE"),
+			Privilege::Remove => write!(f, "REMOVE"),
+		}
+	}
+}
+
+/// A set of privileges on a kind of resource, as specified by
+/// a `GRANT` or `REVOKE` clause on a `DEFINE ROLE` statement.
+///
+/// When no resource names are specified, the grant applies to
+/// every resource of the specified kind.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RoleGrant {
+	pub privileges: Vec<Privilege>,
+	pub kind: ResourceKind,
+	pub names: Vec<String>,
+}
+
+impl RoleGrant {
+	/// Checks if this grant covers the given privilege on a resource
+	pub(crate) fn matches(&self, privilege: Privilege, res: &Resource) -> bool {
+		// Check that the resource kinds match
+		if &self.kind != res.kind() {
+			return false;
+		}
+		// Check that the privilege has been specified
+		if !self.privileges.contains(&privilege) {
+			return false;
+		}
+		// Check that the resource name matches
+		self.names.is_empty() || self.names.iter().any(|n| n == res.id())
+	}
+
+	/// Returns whether this is a resource which can be specified in a grant
+	pub fn is_grantable(kind: &ResourceKind) -> bool {
+		matches!(
+			kind,
+			ResourceKind::Table
+				| ResourceKind::Function
+				| ResourceKind::Parameter
+				| ResourceKind::Index
+				| ResourceKind::Access
+		)
+	}
+}
+
+impl std::fmt::Display for RoleGrant {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if Privilege::ALL.iter().all(|p| self.privileges.contains(p)) {
+			write!(f, "ALL")?;
+		} else {
+			let privileges = self.privileges.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+			write!(f, "{}", privileges.join(", "))?;
+		}
+		let kind = match self.kind {
+			ResourceKind::Table => "TABLE",
+			ResourceKind::Function => "FUNCTION",
+			ResourceKind::Parameter => "PARAM",
+			ResourceKind::Index => "INDEX",
+			ResourceKind::Access => "ACCESS",
+			_ => "ANY",
+		};
+		write!(f, " ON {kind}")?;
+		if !self.names.is_empty() {
+			let names = self
+				.names
+				.iter()
+				.map(|n| match self.kind {
+					ResourceKind::Function => format!("fn::{n}"),
+					ResourceKind::Parameter => format!("${}", EscapeKwFreeIdent(n)),
+					_ => EscapeIdent(n).to_string(),
+				})
+				.collect::<Vec<_>>();
+			write!(f, " {}", names.join(", "))?;
+		}
+		Ok(())
+	}
+}
+
+/// The resolved privileges of the custom roles assigned to an actor.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Privileges {
+	/// The name of each custom role which was resolved
+	pub roles: Vec<String>,
+	/// The privileges granted by the custom roles
+	pub grants: Vec<RoleGrant>,
+	/// The privileges revoked by the custom roles
+	pub revokes: Vec<RoleGrant>,
+}
+
+impl Privileges {
+	/// Checks if any custom roles were resolved for the actor
+	pub fn is_empty(&self) -> bool {
+		self.roles.is_empty()
+	}
+
+	/// Checks if any privileges are revoked by the custom roles
+	pub fn has_revokes(&self) -> bool {
+		!self.revokes.is_empty()
+	}
+
+	/// Checks if the privilege has been revoked on the resource
+	pub(crate) fn is_revoked(&self, privilege: Privilege, res: &Resource) -> bool {
+		self.revokes.iter().any(|g| g.matches(privilege, res))
+	}
+
+	/// Checks if the privilege has been granted on the resource,
+	/// and has not subsequently been revoked by any custom role.
+	pub(crate) fn is_granted(&self, privilege: Privilege, res: &Resource, level: &Level) -> bool {
+		res.level().sublevel_of(level)
+			&& !self.is_revoked(privilege, res)
+			&& self.grants.iter().any(|g| g.matches(privilege, res))
+	}
+}
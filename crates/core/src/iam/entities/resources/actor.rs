@@ -6,17 +6,20 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 
 use super::{Level, Resource, ResourceKind};
-use crate::iam::Role;
+use crate::iam::{Privileges, Role};
 
 //
 // User
 //
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Actor {
 	res: Resource,
 	roles: Vec<Role>,
+	#[revision(start = 2)]
+	#[serde(default)]
+	privileges: Privileges,
 }
 
 impl Default for Actor {
@@ -24,6 +27,7 @@ impl Default for Actor {
 		Self {
 			res: ResourceKind::Actor.on_level(Level::No),
 			roles: Vec::new(),
+			privileges: Privileges::default(),
 		}
 	}
 }
@@ -40,7 +44,12 @@ impl std::fmt::Display for Actor {
 			self.res.level(),
 			self.res.kind(),
 			self.res.id(),
-			self.roles.iter().map(|r| format!("{}", r)).collect::<Vec<String>>().join(", ")
+			self.roles
+				.iter()
+				.map(|r| format!("{}", r))
+				.chain(self.privileges.roles.iter().cloned())
+				.collect::<Vec<String>>()
+				.join(", ")
 		)
 	}
 }
@@ -50,9 +59,16 @@ impl Actor {
 		Self {
 			res: Resource::new(id, super::ResourceKind::Actor, level),
 			roles,
+			privileges: Privileges::default(),
 		}
 	}
 
+	/// Attaches the resolved privileges of any custom roles to the actor.
+	pub(crate) fn with_privileges(mut self, privileges: Privileges) -> Self {
+		self.privileges = privileges;
+		self
+	}
+
 	pub fn from_role_names(id: String, roles: &[String], level: Level) -> Result<Self> {
 		let roles = roles.iter().map(|x| Role::from_str(x)).collect::<Result<Vec<_>, _>>()?;
 		Ok(Self::new(id, roles, level))
@@ -63,6 +79,11 @@ impl Actor {
 		self.roles.contains(&role)
 	}
 
+	/// Returns the resolved privileges of the custom roles of the actor.
+	pub(crate) fn privileges(&self) -> &Privileges {
+		&self.privileges
+	}
+
 	/// Checks if the actor has the Owner role.
 	pub(crate) fn has_owner_role(&self) -> bool {
 		self.roles.iter().any(|r| r.eq(&Role::Owner))
@@ -5,8 +5,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::iam::Error;
 
-// In the future, we will allow for custom roles. For now, provide predefined
-// roles.
+// The predefined roles. Custom roles, with fine-grained privileges, are
+// defined using the `DEFINE ROLE` statement and are resolved into a set of
+// `Privileges` when an actor authenticates.
 #[revisioned(revision = 1)]
 #[derive(Hash, Copy, Clone, Default, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -28,6 +29,13 @@ impl std::fmt::Display for Role {
 	}
 }
 
+impl Role {
+	/// Checks if the given name refers to one of the predefined roles
+	pub fn is_builtin(name: &str) -> bool {
+		Self::from_str(name).is_ok()
+	}
+}
+
 impl FromStr for Role {
 	type Err = Error;
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -0,0 +1,105 @@
+//! Tracking of failed signin attempts, used to temporarily lock accounts
+//! after repeated failures.
+//!
+//! Attempts are stored in the datastore, rather than in memory, so that a
+//! lockout is enforced across every node in a cluster.
+
+use std::fmt::Debug;
+
+use anyhow::{Result, ensure};
+use chrono::Utc;
+use revision::revisioned;
+
+use crate::err::Error;
+use crate::expr::user::PasswordLockout;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+use crate::kvs::{Datastore, KVKey, impl_kv_value_revisioned};
+
+/// The failed signin attempts recorded for an account
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Attempts {
+	/// The number of consecutive failed signins
+	pub failures: u64,
+	/// The unix timestamp until which the account is locked
+	pub locked: Option<i64>,
+}
+
+impl_kv_value_revisioned!(Attempts);
+
+impl Attempts {
+	/// Checks whether the account is currently locked
+	fn is_locked(&self) -> bool {
+		self.locked.is_some_and(|v| v > Utc::now().timestamp())
+	}
+}
+
+/// Ensures that the account has not been locked
+pub(crate) async fn check<K>(kvs: &Datastore, key: &K) -> Result<()>
+where
+	K: KVKey<ValueType = Attempts> + Debug,
+{
+	// Create a new readonly transaction
+	let tx = kvs.transaction(Read, Optimistic).await?;
+	// Fetch the recorded attempts
+	let attempts = tx.get(key, None).await?;
+	// Ensure that the transaction is cancelled
+	tx.cancel().await?;
+	// Check if the account is locked
+	ensure!(!attempts.is_some_and(|v| v.is_locked()), Error::AccountLocked);
+	Ok(())
+}
+
+/// Records a failed signin, locking the account once the number of
+/// consecutive failures reaches the limit of the lockout policy
+pub(crate) async fn failure<K>(kvs: &Datastore, key: &K, lockout: &PasswordLockout) -> Result<()>
+where
+	K: KVKey<ValueType = Attempts> + Debug,
+{
+	// Create a new writeable transaction
+	let tx = kvs.transaction(Write, Optimistic).await?;
+	// Fetch the recorded attempts
+	let mut attempts = match tx.get(key, None).await {
+		Ok(v) => v.unwrap_or_default(),
+		Err(e) => {
+			tx.cancel().await?;
+			return Err(e);
+		}
+	};
+	// Record the failed attempt
+	attempts.failures += 1;
+	if attempts.failures >= lockout.attempts {
+		let duration = i64::try_from(lockout.duration.as_secs()).unwrap_or(i64::MAX);
+		attempts.locked = Some(Utc::now().timestamp().saturating_add(duration));
+		attempts.failures = 0;
+		debug!("Locking account {key:?} after repeated failed signin attempts");
+	}
+	// Store the recorded attempts
+	if let Err(e) = tx.set(key, &attempts, None).await {
+		tx.cancel().await?;
+		return Err(e);
+	}
+	tx.commit().await
+}
+
+/// Clears the failed signins of an account after a successful signin
+pub(crate) async fn success<K>(kvs: &Datastore, key: &K) -> Result<()>
+where
+	K: KVKey<ValueType = Attempts> + Debug,
+{
+	// Avoid writing to the datastore if there is nothing to clear
+	let tx = kvs.transaction(Read, Optimistic).await?;
+	let exists = tx.exists(key, None).await;
+	tx.cancel().await?;
+	if !exists? {
+		return Ok(());
+	}
+	// Clear the recorded attempts
+	let tx = kvs.transaction(Write, Optimistic).await?;
+	if let Err(e) = tx.del(key).await {
+		tx.cancel().await?;
+		return Err(e);
+	}
+	tx.commit().await
+}
@@ -11,10 +11,13 @@ pub(crate) mod file;
 pub mod issue;
 #[cfg(feature = "jwks")]
 pub mod jwks;
+pub(crate) mod lockout;
+pub(crate) mod privileges;
 pub mod reset;
 pub mod signin;
 pub mod signup;
 pub mod token;
+pub mod totp;
 pub mod verify;
 
 pub use self::auth::*;
@@ -54,32 +57,36 @@ fn algorithm_to_jwt_algorithm(alg: catalog::Algorithm) -> jsonwebtoken::Algorith
 }
 
 pub fn is_allowed_check(actor: &Actor, action: &Action, resource: &Resource) -> bool {
-	match action {
-		Action::View => resource.level().sublevel_of(actor.level()),
-		Action::Edit => {
-			if actor.has_role(Role::Owner) {
-				resource.level().sublevel_of(actor.level())
-			} else if actor.has_role(Role::Editor) {
-				matches!(
-					resource.kind(),
-					ResourceKind::Namespace
-						| ResourceKind::Database
-						| ResourceKind::Record
-						| ResourceKind::Table
-						| ResourceKind::Document
-						| ResourceKind::Option
-						| ResourceKind::Function
-						| ResourceKind::Analyzer
-						| ResourceKind::Parameter
-						| ResourceKind::Event
-						| ResourceKind::Field
-						| ResourceKind::Index
-				) && resource.level().sublevel_of(actor.level())
-			} else {
-				false
-			}
-		}
+	// Privileges revoked by a custom role always take precedence
+	if actor.privileges().is_revoked(action.privilege(), resource) {
+		return false;
 	}
+	// Actors with only custom roles get no implicit view access
+	let can_view = actor.has_viewer_role() || actor.privileges().is_empty();
+	// Check the privileges of the predefined roles
+	let allowed = if (action.is_view() && can_view) || actor.has_role(Role::Owner) {
+		resource.level().sublevel_of(actor.level())
+	} else if actor.has_role(Role::Editor) {
+		matches!(
+			resource.kind(),
+			ResourceKind::Namespace
+				| ResourceKind::Database
+				| ResourceKind::Record
+				| ResourceKind::Table
+				| ResourceKind::Document
+				| ResourceKind::Option
+				| ResourceKind::Function
+				| ResourceKind::Analyzer
+				| ResourceKind::Parameter
+				| ResourceKind::Event
+				| ResourceKind::Field
+				| ResourceKind::Index
+		) && resource.level().sublevel_of(actor.level())
+	} else {
+		false
+	};
+	// Otherwise check the privileges of any custom roles
+	allowed || actor.privileges().is_granted(action.privilege(), resource, actor.level())
 }
 
 pub fn is_allowed(actor: &Actor, action: &Action, resource: &Resource) -> Result<(), Error> {
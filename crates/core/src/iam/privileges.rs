@@ -0,0 +1,63 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+
+use crate::catalog;
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, UserProvider};
+use crate::iam::{self, Actor, Level, Privileges, Role};
+use crate::kvs::Datastore;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+
+/// Creates the actor for a system user, resolving any custom roles which
+/// have been assigned to the user into a set of privileges.
+///
+/// Custom roles are looked up at the same level as the user. Any role which
+/// is neither a built-in role nor a role defined at that level is invalid.
+pub(crate) async fn user_actor(
+	kvs: &Datastore,
+	user: &catalog::UserDefinition,
+	level: Level,
+) -> Result<Actor> {
+	// Split the built-in roles from the custom roles
+	let mut roles = Vec::new();
+	let mut custom = Vec::new();
+	for name in user.roles.iter() {
+		match Role::from_str(name) {
+			Ok(role) => roles.push(role),
+			Err(_) => custom.push(name),
+		}
+	}
+	// Only open a transaction if there are custom roles
+	if custom.is_empty() {
+		return Ok(Actor::new(user.name.clone(), roles, level));
+	}
+	// Resolve the custom roles at the level of the user
+	let tx = kvs.transaction(Read, Optimistic).await?;
+	let mut privileges = Privileges::default();
+	for name in custom {
+		let def = match &level {
+			Level::Root => tx.get_root_role(name).await?,
+			Level::Namespace(ns) => match tx.get_ns_by_name(ns).await? {
+				Some(ns) => tx.get_ns_role(ns.namespace_id, name).await?,
+				None => None,
+			},
+			Level::Database(ns, db) => match tx.get_db_by_name(ns, db).await? {
+				Some(db) => tx.get_db_role(db.namespace_id, db.database_id, name).await?,
+				None => None,
+			},
+			_ => None,
+		};
+		let Some(def) = def else {
+			tx.cancel().await?;
+			return Err(iam::Error::InvalidRole(name.to_string()).into());
+		};
+		privileges.roles.push(def.name.clone());
+		privileges.grants.extend(def.grants.iter().cloned());
+		privileges.revokes.extend(def.revokes.iter().cloned());
+	}
+	// Ensure that the transaction is cancelled
+	tx.cancel().await?;
+	// Create the actor with the resolved privileges
+	Ok(Actor::new(user.name.clone(), roles, level).with_privileges(privileges))
+}
@@ -13,7 +13,9 @@ use super::access::{
 	authenticate_generic, authenticate_record, create_refresh_token_record,
 	revoke_refresh_token_record,
 };
-use super::verify::{verify_db_creds, verify_ns_creds, verify_root_creds};
+use super::verify::{
+	verify_db_creds, verify_ns_creds, verify_root_creds, verify_totp_challenge, verify_user_factor,
+};
 use super::{Actor, Level, Role};
 use crate::catalog;
 use crate::catalog::providers::{
@@ -24,14 +26,15 @@ use crate::cnf::{INSECURE_FORWARD_ACCESS_ERRORS, SERVER_NAME};
 use crate::dbs::capabilities::ExperimentalTarget;
 use crate::dbs::{Session, Variables};
 use crate::err::Error;
-use crate::expr::access_type;
 use crate::expr::statements::access;
+use crate::expr::{Expr, access_type};
 use crate::iam::issue::{config, expiration};
+use crate::iam::privileges::user_actor;
 use crate::iam::token::{Claims, HEADER};
-use crate::iam::{self, Auth, algorithm_to_jwt_algorithm};
-use crate::kvs::Datastore;
+use crate::iam::{self, Auth, algorithm_to_jwt_algorithm, lockout, totp};
 use crate::kvs::LockType::*;
 use crate::kvs::TransactionType::*;
+use crate::kvs::{Datastore, Transaction};
 use crate::val::{Datetime, Object, Value};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -45,6 +48,21 @@ pub async fn signin(kvs: &Datastore, session: &mut Session, vars: Object) -> Res
 	let ns = vars.get("NS").or_else(|| vars.get("ns"));
 	let db = vars.get("DB").or_else(|| vars.get("db"));
 	let ac = vars.get("AC").or_else(|| vars.get("ac"));
+	// Complete a two-step signin using a TOTP challenge
+	if let Some(challenge) = vars.get("challenge") {
+		return match vars.get("totp") {
+			Some(code) => {
+				// Process the provided values
+				let challenge = challenge.to_raw_string();
+				let code = code.to_raw_string();
+				// Attempt to complete the signin
+				super::signin::totp_challenge(kvs, session, challenge, code).await
+			}
+			None => Err(anyhow::Error::new(Error::TotpRequired)),
+		};
+	}
+	// Get the provided TOTP code, if any
+	let totp = vars.get("totp").map(|v| v.to_raw_string());
 	// Check if the parameters exist
 	match (ns, db, ac) {
 		// DB signin with access method
@@ -71,7 +89,7 @@ pub async fn signin(kvs: &Datastore, session: &mut Session, vars: Object) -> Res
 					let user = user.to_raw_string();
 					let pass = pass.to_raw_string();
 					// Attempt to signin to database
-					super::signin::db_user(kvs, session, ns, db, user, pass).await
+					super::signin::db_user(kvs, session, ns, db, user, pass, totp).await
 				}
 				_ => Err(anyhow::Error::new(Error::MissingUserOrPass)),
 			}
@@ -98,7 +116,7 @@ pub async fn signin(kvs: &Datastore, session: &mut Session, vars: Object) -> Res
 					let user = user.to_raw_string();
 					let pass = pass.to_raw_string();
 					// Attempt to signin to namespace
-					super::signin::ns_user(kvs, session, ns, user, pass).await
+					super::signin::ns_user(kvs, session, ns, user, pass, totp).await
 				}
 				_ => Err(anyhow::Error::new(Error::MissingUserOrPass)),
 			}
@@ -116,7 +134,7 @@ pub async fn signin(kvs: &Datastore, session: &mut Session, vars: Object) -> Res
 					let user = user.to_raw_string();
 					let pass = pass.to_raw_string();
 					// Attempt to signin to root
-					super::signin::root_user(kvs, session, user, pass).await
+					super::signin::root_user(kvs, session, user, pass, totp).await
 				}
 				_ => Err(anyhow::Error::new(Error::MissingUserOrPass)),
 			}
@@ -184,11 +202,47 @@ pub async fn db_access(
 							let mut sess = Session::editor().with_ns(&ns).with_db(&db);
 							sess.ip.clone_from(&session.ip);
 							sess.or.clone_from(&session.or);
+							// Failed signins are tracked against the declared identity
+							let limit = at.policy.as_ref().and_then(|p| p.lockout.as_ref());
+							let id = match (limit, &at.identity) {
+								(Some(_), Some(identity)) => {
+									Some(record_identity(kvs, &sess, identity, vars.clone()).await?)
+								}
+								_ => None,
+							};
+							let lk = id.as_deref().map(|id| {
+								crate::key::database::access::lk::new(
+									db_def.namespace_id,
+									db_def.database_id,
+									&ac,
+									id,
+								)
+							});
+							// Ensure that the identity has not been locked
+							if let Some(lk) = &lk {
+								lockout::check(kvs, lk).await?;
+							}
 							// Compute the value with the params
-							match kvs.evaluate(val, &sess, vars).await {
+							let res = kvs.evaluate(val, &sess, vars).await.map(Value::record);
+							// Record the outcome of the signin attempt
+							if let (Some(limit), Some(lk)) = (limit, &lk) {
+								// Transaction errors are not caused by the credentials
+								let transient = res.as_ref().err().is_some_and(|e| {
+									matches!(
+										e.downcast_ref(),
+										Some(Error::Tx(_) | Error::TxFailure | Error::TxRetryable)
+									)
+								});
+								match &res {
+									Ok(Some(_)) => lockout::success(kvs, lk).await?,
+									_ if transient => {}
+									_ => lockout::failure(kvs, lk, limit).await?,
+								}
+							}
+							match res {
 								// The signin value succeeded
 								Ok(val) => {
-									match val.record() {
+									match val {
 										// There is a record returned
 										Some(mut rid) => {
 											// Create the authentication key
@@ -335,14 +389,16 @@ pub async fn db_access(
 	}
 }
 
-fn auth_from_level_user(level: Level, user: &catalog::UserDefinition) -> Result<Auth> {
-	let roles = user
-		.roles
-		.iter()
-		.map(|x| Role::from_str(x))
-		.collect::<Result<Vec<_>, _>>()
-		.map_err(Error::from)?;
-	let actor = Actor::new(user.name.clone(), roles, level);
+async fn auth_from_level_user(
+	kvs: &Datastore,
+	level: Level,
+	user: &catalog::UserDefinition,
+) -> Result<Auth> {
+	let actor =
+		user_actor(kvs, user, level).await.map_err(|e| match e.downcast::<iam::Error>() {
+			Ok(e) => anyhow::Error::new(Error::from(e)),
+			Err(e) => e,
+		})?;
 	Ok(Auth::new(actor))
 }
 
@@ -353,52 +409,26 @@ pub async fn db_user(
 	db: String,
 	user: String,
 	pass: String,
+	totp: Option<String>,
 ) -> Result<SigninData> {
 	match verify_db_creds(kvs, &ns, &db, &user, &pass).await {
 		Ok(u) => {
-			// Create the authentication key
-			let key = EncodingKey::from_secret(u.code.as_ref());
-			// Create the authentication claim
-			let val = Claims {
-				iss: Some(SERVER_NAME.to_owned()),
-				iat: Some(Utc::now().timestamp()),
-				nbf: Some(Utc::now().timestamp()),
-				exp: expiration(u.token_duration)?,
-				jti: Some(Uuid::new_v4().to_string()),
-				ns: Some(ns.clone()),
-				db: Some(db.clone()),
-				id: Some(user),
-				..Claims::default()
-			};
+			let level = Level::Database(ns.clone(), db.clone());
+			// Check the second factor if the user has enrolled in TOTP
+			if let Some(challenge) = user_totp(kvs, &level, &u, totp.as_deref()).await? {
+				return Ok(challenge);
+			}
 			// Log the authenticated database info
 			trace!("Signing in to database `{ns}/{db}`");
 			// Create the authentication token
-			let enc = encode(&HEADER, &val, &key);
-
-			let au = auth_from_level_user(Level::Database(ns.clone(), db.clone()), &u)?;
-
-			// Set the authentication on the session
-			session.tk = Some(val.into_claims_object().into());
-			session.ns = Some(ns.clone());
-			session.db = Some(db.clone());
-			session.exp = expiration(u.session_duration)?;
-			session.au = Arc::new(au);
-			// Check the authentication token
-			match enc {
-				// The auth token was created successfully
-				Ok(tk) => Ok(SigninData {
-					token: tk,
-					refresh: None,
-				}),
-				_ => Err(anyhow::Error::new(Error::TokenMakingFailed)),
-			}
+			user_token(kvs, session, level, &u).await
 		}
 		// The password did not verify
 		Err(e) => {
 			debug!(
 				"Failed to verify signin credentials for user `{user}` in database `{ns}/{db}`: {e}"
 			);
-			Err(anyhow::Error::new(Error::InvalidAuth))
+			Err(user_signin_error(e))
 		}
 	}
 }
@@ -445,51 +475,26 @@ pub async fn ns_user(
 	ns: String,
 	user: String,
 	pass: String,
+	totp: Option<String>,
 ) -> Result<SigninData> {
 	match verify_ns_creds(kvs, &ns, &user, &pass).await {
 		Ok(u) => {
-			// Create the authentication key
-			let key = EncodingKey::from_secret(u.code.as_ref());
-			// Create the authentication claim
-			let val = Claims {
-				iss: Some(SERVER_NAME.to_owned()),
-				iat: Some(Utc::now().timestamp()),
-				nbf: Some(Utc::now().timestamp()),
-				exp: expiration(u.token_duration)?,
-
-				jti: Some(Uuid::new_v4().to_string()),
-				ns: Some(ns.clone()),
-				id: Some(user),
-				..Claims::default()
-			};
+			let level = Level::Namespace(ns.clone());
+			// Check the second factor if the user has enrolled in TOTP
+			if let Some(challenge) = user_totp(kvs, &level, &u, totp.as_deref()).await? {
+				return Ok(challenge);
+			}
 			// Log the authenticated namespace info
 			trace!("Signing in to namespace `{ns}`");
 			// Create the authentication token
-			let enc = encode(&HEADER, &val, &key);
-
-			let au = auth_from_level_user(Level::Namespace(ns.clone()), &u)?;
-
-			// Set the authentication on the session
-			session.tk = Some(val.into_claims_object().into());
-			session.ns = Some(ns.clone());
-			session.exp = expiration(u.session_duration)?;
-			session.au = Arc::new(au);
-			// Check the authentication token
-			match enc {
-				// The auth token was created successfully
-				Ok(tk) => Ok(SigninData {
-					token: tk,
-					refresh: None,
-				}),
-				_ => Err(anyhow::Error::new(Error::TokenMakingFailed)),
-			}
+			user_token(kvs, session, level, &u).await
 		}
 		// The password did not verify
 		Err(e) => {
 			debug!(
 				"Failed to verify signin credentials for user `{user}` in namespace `{ns}`: {e}"
 			);
-			Err(anyhow::Error::new(Error::InvalidAuth))
+			Err(user_signin_error(e))
 		}
 	}
 }
@@ -499,48 +504,275 @@ pub async fn root_user(
 	session: &mut Session,
 	user: String,
 	pass: String,
+	totp: Option<String>,
 ) -> Result<SigninData> {
 	match verify_root_creds(kvs, &user, &pass).await {
 		Ok(u) => {
-			// Create the authentication key
-			let key = EncodingKey::from_secret(u.code.as_ref());
-			// Create the authentication claim
+			// Check the second factor if the user has enrolled in TOTP
+			if let Some(challenge) = user_totp(kvs, &Level::Root, &u, totp.as_deref()).await? {
+				return Ok(challenge);
+			}
+			// Log the authenticated root info
+			trace!("Signing in as root");
+			// Create the authentication token
+			user_token(kvs, session, Level::Root, &u).await
+		}
+		// The password did not verify
+		Err(e) => {
+			debug!("Failed to verify signin credentials for user `{user}` in root: {e}");
+			Err(user_signin_error(e))
+		}
+	}
+}
+
+/// Converts an error which occurred while verifying the credentials of a
+/// system user into the error which is returned to the client. Lockouts and
+/// expired passwords are reported, as the client needs to act upon them.
+fn user_signin_error(e: anyhow::Error) -> anyhow::Error {
+	match e.downcast_ref() {
+		Some(Error::AccountLocked | Error::PasswordExpired) => e,
+		_ => anyhow::Error::new(Error::InvalidAuth),
+	}
+}
+
+/// Computes the identity against which failed signins to a record access
+/// method are tracked, by evaluating the `IDENTITY` clause of the access
+/// method with the variables provided when signing in. The identity is
+/// hashed so that the lockout key has a bounded length.
+async fn record_identity(
+	kvs: &Datastore,
+	sess: &Session,
+	identity: &Expr,
+	vars: Option<Variables>,
+) -> Result<String> {
+	let val = kvs.evaluate(identity, sess, vars).await.map_err(|e| {
+		debug!("Unexpected error found while executing an IDENTITY clause: {e}");
+		Error::InvalidAuth
+	})?;
+	let mut hash = Sha256::new();
+	hash.update(val.to_string().as_bytes());
+	Ok(format!("{:x}", hash.finalize()))
+}
+
+/// Completes a two-step signin for a system user which has enrolled in TOTP,
+/// exchanging a challenge token and a valid code for a session token.
+pub async fn totp_challenge(
+	kvs: &Datastore,
+	session: &mut Session,
+	challenge: String,
+	code: String,
+) -> Result<SigninData> {
+	// Verify the challenge, and fetch the user it was issued to
+	let (level, u) = verify_totp_challenge(kvs, &challenge).await?;
+	// Verify the provided code for the user
+	verify_user_totp(kvs, &level, &u, &code).await?;
+	// Log the authenticated user info
+	trace!("Signing in to `{level}` using a TOTP challenge");
+	// Create the authentication token
+	user_token(kvs, session, level, &u).await
+}
+
+/// Creates a session token for a system user whose credentials have been
+/// fully verified, and sets the authentication on the session.
+async fn user_token(
+	kvs: &Datastore,
+	session: &mut Session,
+	level: Level,
+	u: &catalog::UserDefinition,
+) -> Result<SigninData> {
+	// Create the authentication key
+	let key = EncodingKey::from_secret(u.code.as_ref());
+	// Create the authentication claim
+	let val = Claims {
+		iss: Some(SERVER_NAME.to_owned()),
+		iat: Some(Utc::now().timestamp()),
+		nbf: Some(Utc::now().timestamp()),
+		exp: expiration(u.token_duration)?,
+		jti: Some(Uuid::new_v4().to_string()),
+		ns: level.ns().map(ToOwned::to_owned),
+		db: level.db().map(ToOwned::to_owned),
+		id: Some(u.name.clone()),
+		..Claims::default()
+	};
+	// Create the authentication token
+	let enc = encode(&HEADER, &val, &key);
+	// Set the authentication on the session
+	session.tk = Some(val.into_claims_object().into());
+	if let Some(ns) = level.ns() {
+		session.ns = Some(ns.to_owned());
+	}
+	if let Some(db) = level.db() {
+		session.db = Some(db.to_owned());
+	}
+	session.exp = expiration(u.session_duration)?;
+	session.au = Arc::new(auth_from_level_user(kvs, level, u).await?);
+	// Check the authentication token
+	match enc {
+		// The auth token was created successfully
+		Ok(tk) => Ok(SigninData {
+			token: tk,
+			refresh: None,
+		}),
+		_ => Err(anyhow::Error::new(Error::TokenMakingFailed)),
+	}
+}
+
+/// Checks the second factor for a system user which has enrolled in TOTP.
+///
+/// When no code has been provided, a short-lived challenge token is returned
+/// in place of a session token. The challenge can then be exchanged for a
+/// session token by signing in with the challenge and a valid code.
+async fn user_totp(
+	kvs: &Datastore,
+	level: &Level,
+	u: &catalog::UserDefinition,
+	code: Option<&str>,
+) -> Result<Option<SigninData>> {
+	// Users which have not enrolled need no second factor
+	if u.totp.is_none() {
+		return Ok(None);
+	}
+	match code {
+		// A code was provided alongside the credentials
+		Some(code) => {
+			verify_user_totp(kvs, level, u, code).await?;
+			Ok(None)
+		}
+		// Issue a challenge which must be completed with a code
+		None => {
+			// Create the challenge key
+			let key = EncodingKey::from_secret(totp::challenge_key(&u.code).as_ref());
+			// Create the challenge claim
+			let now = Utc::now().timestamp();
 			let val = Claims {
 				iss: Some(SERVER_NAME.to_owned()),
-				iat: Some(Utc::now().timestamp()),
-				nbf: Some(Utc::now().timestamp()),
-				exp: expiration(u.token_duration)?,
+				iat: Some(now),
+				nbf: Some(now),
+				exp: Some(now + totp::CHALLENGE_DURATION),
 				jti: Some(Uuid::new_v4().to_string()),
-				id: Some(user),
+				ns: level.ns().map(ToOwned::to_owned),
+				db: level.db().map(ToOwned::to_owned),
+				id: Some(u.name.clone()),
 				..Claims::default()
 			};
-			// Log the authenticated root info
-			trace!("Signing in as root");
-			// Create the authentication token
-			let enc = encode(&HEADER, &val, &key);
-
-			let au = auth_from_level_user(Level::Root, &u)?;
-
-			// Set the authentication on the session
-			session.tk = Some(val.into_claims_object().into());
-			session.exp = expiration(u.session_duration)?;
-			session.au = Arc::new(au);
-			// Check the authentication token
-			match enc {
-				// The auth token was created successfully
-				Ok(tk) => Ok(SigninData {
+			// Create the challenge token
+			match encode(&HEADER, &val, &key) {
+				Ok(tk) => Ok(Some(SigninData {
 					token: tk,
 					refresh: None,
-				}),
+				})),
 				_ => Err(anyhow::Error::new(Error::TokenMakingFailed)),
 			}
 		}
-		// The password did not verify
-		Err(e) => {
-			debug!("Failed to verify signin credentials for user `{user}` in root: {e}");
+	}
+}
+
+/// Verifies a TOTP code or a recovery code for a system user. Codes are
+/// only accepted once, and recovery codes are removed from the user once
+/// they have been used.
+async fn verify_user_totp(
+	kvs: &Datastore,
+	level: &Level,
+	u: &catalog::UserDefinition,
+	code: &str,
+) -> Result<()> {
+	let Some(totp) = &u.totp else {
+		return Ok(());
+	};
+	verify_user_factor(kvs, level, u, async {
+		// Check the code against the current time
+		if let Some(step) = totp::verify_step(&totp.secret, code, Utc::now().timestamp())? {
+			// Ensure that this code, or a later code, has not been used before
+			return update_user_totp(kvs, level, &u.name, |totp| {
+				if totp.step.is_some_and(|v| v >= step) {
+					debug!("Rejected a reused TOTP code for user `{}` in `{level}`", u.name);
+					return false;
+				}
+				totp.step = Some(step);
+				true
+			})
+			.await;
+		}
+		// Otherwise check if this is a recovery code
+		let hash = totp::hash_recovery_code(code);
+		if !totp.recovery.contains(&hash) {
+			debug!("Failed to verify TOTP code for user `{}` in `{level}`", u.name);
+			bail!(Error::InvalidAuth);
+		}
+		// Consume the recovery code
+		update_user_totp(kvs, level, &u.name, |totp| {
+			let Some(pos) = totp.recovery.iter().position(|v| *v == hash) else {
+				return false;
+			};
+			totp.recovery.remove(pos);
+			true
+		})
+		.await
+	})
+	.await
+}
+
+/// Updates the stored TOTP second factor of a user, failing if the update
+/// is rejected, for instance when a code was used by a concurrent signin.
+async fn update_user_totp<F>(kvs: &Datastore, level: &Level, user: &str, update: F) -> Result<()>
+where
+	F: FnOnce(&mut catalog::UserTotp) -> bool,
+{
+	let tx = kvs.transaction(Write, Optimistic).await?;
+	match store_user_totp(&tx, level, user, update).await {
+		Ok(true) => tx.commit().await,
+		Ok(false) => {
+			tx.cancel().await?;
 			Err(anyhow::Error::new(Error::InvalidAuth))
 		}
+		Err(e) => {
+			tx.cancel().await?;
+			Err(e)
+		}
+	}
+}
+
+/// Stores an update to the TOTP second factor of a user, returning false if
+/// the user has not enrolled or if the update was rejected.
+async fn store_user_totp<F>(tx: &Transaction, level: &Level, user: &str, update: F) -> Result<bool>
+where
+	F: FnOnce(&mut catalog::UserTotp) -> bool,
+{
+	// Applies the update to the stored user definition
+	let updated = |u: &catalog::UserDefinition| {
+		let mut u = u.clone();
+		let totp = u.totp.as_mut()?;
+		update(totp).then_some(u)
+	};
+	match level {
+		Level::Root => {
+			let Some(u) = tx.get_root_user(user).await?.and_then(|u| updated(&u)) else {
+				return Ok(false);
+			};
+			tx.put_root_user(&u).await?;
+		}
+		Level::Namespace(ns) => {
+			let ns = tx.expect_ns_by_name(ns).await?;
+			let Some(u) = tx.get_ns_user(ns.namespace_id, user).await?.and_then(|u| updated(&u))
+			else {
+				return Ok(false);
+			};
+			tx.put_ns_user(ns.namespace_id, &u).await?;
+		}
+		Level::Database(ns, db) => {
+			let db = tx.expect_db_by_name(ns, db).await?;
+			let Some(u) = tx
+				.get_db_user(db.namespace_id, db.database_id, user)
+				.await?
+				.and_then(|u| updated(&u))
+			else {
+				return Ok(false);
+			};
+			tx.put_db_user(db.namespace_id, db.database_id, &u).await?;
+		}
+		_ => return Ok(false),
 	}
+	Ok(true)
 }
 
 pub async fn root_access(
@@ -1614,8 +1846,14 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 
 				let res = match level.level {
 					"ROOT" => {
-						root_user(&ds, &mut sess, "user".to_string(), case.password.to_string())
-							.await
+						root_user(
+							&ds,
+							&mut sess,
+							"user".to_string(),
+							case.password.to_string(),
+							None,
+						)
+						.await
 					}
 					"NS" => {
 						ns_user(
@@ -1624,6 +1862,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 							level.ns.unwrap().to_string(),
 							"user".to_string(),
 							case.password.to_string(),
+							None,
 						)
 						.await
 					}
@@ -1635,6 +1874,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 							level.db.unwrap().to_string(),
 							"user".to_string(),
 							case.password.to_string(),
+							None,
 						)
 						.await
 					}
@@ -1725,6 +1965,244 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 		}
 	}
 
+	#[tokio::test]
+	async fn test_signin_user_totp() {
+		let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner();
+		// The recovery code is stored as a hash of the code `abcdefghij`
+		ds.execute(
+			&format!(
+				"DEFINE USER user ON ROOT PASSWORD 'pass' ROLES OWNER TOTP SECRET '{secret}' RECOVERY '72399361da6a7754fec986dca5b7cbaf1c810a28ded4abaf56b2106d06cb78b0'"
+			),
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+
+		// Signin without a code returns a challenge
+		let mut sess = Session::default();
+		let challenge =
+			root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), None).await.unwrap();
+		assert!(sess.au.is_anon(), "The challenge should not authenticate the session");
+		assert!(
+			crate::iam::verify::token(&ds, &mut sess, &challenge.token).await.is_err(),
+			"The challenge should not be usable as a session token"
+		);
+
+		// The challenge cannot be completed with an invalid code
+		let res =
+			totp_challenge(&ds, &mut sess, challenge.token.clone(), "000000".to_string()).await;
+		assert!(res.is_err(), "Unexpected successful signin: {:?}", res);
+
+		// The challenge can be completed with a valid code
+		let code = totp::generate(secret, Utc::now().timestamp()).unwrap();
+		let res = totp_challenge(&ds, &mut sess, challenge.token, code.clone()).await;
+		assert!(res.is_ok(), "Failed to signin: {:?}", res);
+		assert!(sess.au.is_root());
+		assert_eq!(sess.au.id(), "user");
+
+		// A code can not be used more than once
+		let mut sess = Session::default();
+		let res =
+			root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), Some(code)).await;
+		assert!(res.is_err(), "Unexpected successful signin: {:?}", res);
+		assert!(sess.au.is_anon());
+
+		// Signin with a valid code issues a session token directly
+		let code = totp::generate(secret, Utc::now().timestamp() + totp::PERIOD).unwrap();
+		let mut sess = Session::default();
+		let res =
+			root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), Some(code)).await;
+		assert!(res.is_ok(), "Failed to signin: {:?}", res);
+		assert!(sess.au.is_root());
+
+		// Recovery codes can only be used once
+		let mut sess = Session::default();
+		let res = root_user(
+			&ds,
+			&mut sess,
+			"user".to_string(),
+			"pass".to_string(),
+			Some("ABCDEFGHIJ".to_string()),
+		)
+		.await;
+		assert!(res.is_ok(), "Failed to signin: {:?}", res);
+		assert!(sess.au.is_root());
+		let mut sess = Session::default();
+		let res = root_user(
+			&ds,
+			&mut sess,
+			"user".to_string(),
+			"pass".to_string(),
+			Some("abcdefghij".to_string()),
+		)
+		.await;
+		assert!(res.is_err(), "Unexpected successful signin: {:?}", res);
+		assert!(sess.au.is_anon());
+
+		// Repeated failed codes, including the reused recovery code, lock the account
+		for _ in 1..totp::LOCKOUT.attempts {
+			let mut sess = Session::default();
+			let challenge =
+				root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), None).await;
+			let res =
+				totp_challenge(&ds, &mut sess, challenge.unwrap().token, "000000".to_string())
+					.await;
+			match res.unwrap_err().downcast().unwrap() {
+				Error::InvalidAuth => {}
+				e => panic!("Unexpected error, expected InvalidAuth found {e}"),
+			}
+		}
+		let code = totp::generate(secret, Utc::now().timestamp() + totp::PERIOD).unwrap();
+		let mut sess = Session::default();
+		let res =
+			root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), Some(code)).await;
+		match res.unwrap_err().downcast().unwrap() {
+			Error::AccountLocked => {}
+			e => panic!("Unexpected error, expected AccountLocked found {e}"),
+		}
+		assert!(sess.au.is_anon());
+	}
+
+	#[tokio::test]
+	async fn test_signin_user_lockout() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner();
+		ds.execute(
+			"DEFINE USER user ON ROOT PASSWORD 'Password123' ROLES OWNER POLICY LENGTH 8 REQUIRE UPPERCASE, NUMERIC LOCKOUT 2 FOR 1h",
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+
+		// Failed signins are reported as invalid credentials
+		for _ in 0..2 {
+			let mut sess = Session::default();
+			let res =
+				root_user(&ds, &mut sess, "user".to_string(), "invalid".to_string(), None).await;
+			match res.unwrap_err().downcast().unwrap() {
+				Error::InvalidAuth => {}
+				e => panic!("Unexpected error, expected InvalidAuth found {e}"),
+			}
+		}
+
+		// The account is locked, even with the correct password
+		let mut sess = Session::default();
+		let res =
+			root_user(&ds, &mut sess, "user".to_string(), "Password123".to_string(), None).await;
+		match res.unwrap_err().downcast().unwrap() {
+			Error::AccountLocked => {}
+			e => panic!("Unexpected error, expected AccountLocked found {e}"),
+		}
+		assert!(sess.au.is_anon());
+
+		// The account can be unlocked by an administrator
+		let res = ds.execute("ALTER USER user ON ROOT UNLOCK", &Session::owner(), None).await;
+		res.unwrap().remove(0).result.unwrap();
+		let mut sess = Session::default();
+		let res =
+			root_user(&ds, &mut sess, "user".to_string(), "Password123".to_string(), None).await;
+		assert!(res.is_ok(), "Failed to signin: {:?}", res);
+		assert!(sess.au.is_root());
+
+		// New passwords must satisfy the policy
+		let res = ds
+			.execute(
+				"DEFINE USER OVERWRITE user ON ROOT PASSWORD 'password' ROLES OWNER POLICY LENGTH 8 REQUIRE UPPERCASE",
+				&Session::owner(),
+				None,
+			)
+			.await;
+		match res.unwrap().remove(0).result.unwrap_err().downcast().unwrap() {
+			Error::PasswordPolicy {
+				..
+			} => {}
+			e => panic!("Unexpected error, expected PasswordPolicy found {e}"),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_signin_record_lockout() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute(
+			r#"
+			DEFINE ACCESS user ON DATABASE TYPE RECORD
+				SIGNIN (
+					SELECT * FROM user WHERE name = $user AND crypto::argon2::compare(pass, $pass)
+				)
+				POLICY LOCKOUT 2 FOR 1h
+				IDENTITY $user
+			;
+
+			CREATE user:test CONTENT {
+				name: 'user',
+				pass: crypto::argon2::generate('pass')
+			}
+			"#,
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+
+		let signin = |pass: &str, junk: Option<&str>| {
+			let mut vars: HashMap<&str, Value> = HashMap::new();
+			vars.insert("user", "user".into());
+			vars.insert("pass", pass.into());
+			if let Some(junk) = junk {
+				vars.insert("junk", junk.into());
+			}
+			let ds = &ds;
+			async move {
+				let mut sess = Session {
+					ns: Some("test".to_string()),
+					db: Some("test".to_string()),
+					..Default::default()
+				};
+				db_access(
+					ds,
+					&mut sess,
+					"test".to_string(),
+					"test".to_string(),
+					"user".to_string(),
+					vars.into(),
+				)
+				.await
+			}
+		};
+
+		// Additional variables do not change the tracked identity
+		signin("invalid", Some("a")).await.unwrap_err();
+		signin("invalid", Some("b")).await.unwrap_err();
+
+		// The identity is locked, even with the correct password
+		let res = signin("pass", Some("c")).await;
+		match res.unwrap_err().downcast().unwrap() {
+			Error::AccountLocked => {}
+			e => panic!("Unexpected error, expected AccountLocked found {e}"),
+		}
+
+		// The identity can be unlocked by an administrator
+		let res = ds.execute("ALTER ACCESS user ON DATABASE UNLOCK", &sess, None).await;
+		res.unwrap().remove(0).result.unwrap();
+		let res = signin("pass", None).await;
+		assert!(res.is_ok(), "Failed to signin: {:?}", res);
+
+		// A lockout can not be defined without an identity
+		let res = ds
+			.execute(
+				"DEFINE ACCESS other ON DATABASE TYPE RECORD SIGNIN (SELECT * FROM user) POLICY LOCKOUT 2 FOR 1h",
+				&sess,
+				None,
+			)
+			.await;
+		assert!(res.is_err(), "Expected a lockout without an identity to be rejected");
+	}
+
 	#[tokio::test]
 	async fn test_signin_record_and_authenticate_clause() {
 		// Test with correct credentials
@@ -4224,6 +4702,8 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 				roles: vec!["nonexistent".to_owned()],
 				session_duration: None,
 				token_duration: None,
+				totp: None,
+				policy: None,
 				comment: None,
 			};
 
@@ -4233,8 +4713,32 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 				)))],
 			};
 
-			// Use pre-parsed definition, which bypasses the existent role check during parsing.
-			ds.process(ast, &sess, None).await.unwrap();
+			// The role is neither a built-in role nor a custom role defined with DEFINE ROLE.
+			let mut res = ds.process(ast, &sess, None).await.unwrap();
+			let e = res.remove(0).result.unwrap_err();
+			match e.downcast().expect("Unexpected error kind") {
+				crate::err::Error::RoleRootNotFound {
+					..
+				}
+				| crate::err::Error::RoleNsNotFound {
+					..
+				}
+				| crate::err::Error::RoleDbNotFound {
+					..
+				} => {}
+				e => panic!("Unexpected error, expected a role not found error, found {e}"),
+			}
+
+			// A custom role which is removed after being assigned is invalid at signin.
+			let define_query = format!(
+				"DEFINE ROLE temporary ON {0};
+				DEFINE USER user ON {0} PASSWORD 'pass' ROLES temporary;
+				REMOVE ROLE temporary ON {0};",
+				level.level,
+			);
+			for res in ds.execute(&define_query, &sess, None).await.unwrap() {
+				res.result.unwrap();
+			}
 
 			let mut sess = Session {
 				ns: level.ns.map(String::from),
@@ -4244,7 +4748,9 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 
 			// Sign in using the newly defined user.
 			let res = match level.level {
-				"ROOT" => root_user(&ds, &mut sess, "user".to_string(), "pass".to_string()).await,
+				"ROOT" => {
+					root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), None).await
+				}
 				"NS" => {
 					ns_user(
 						&ds,
@@ -4252,6 +4758,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 						level.ns.unwrap().to_string(),
 						"user".to_string(),
 						"pass".to_string(),
+						None,
 					)
 					.await
 				}
@@ -4263,6 +4770,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 						level.db.unwrap().to_string(),
 						"user".to_string(),
 						"pass".to_string(),
+						None,
 					)
 					.await
 				}
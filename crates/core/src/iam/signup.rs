@@ -106,6 +106,11 @@ pub async fn db_access(
 	let Some(val) = &at.signup else {
 		bail!(Error::AccessRecordNoSignup);
 	};
+	// Check the declared password variable against the password policy
+	if let (Some(policy), Some(password)) = (&at.policy, &at.password) {
+		let pass = vars.get(password.as_str()).map(|v| v.to_raw_string()).unwrap_or_default();
+		policy.check(&pass)?;
+	}
 	// Setup the query params
 	let vars = Some(Variables::from(vars));
 	// Setup the system session for finding the signup record
@@ -345,6 +350,77 @@ mod tests {
 		}
 	}
 
+	#[tokio::test]
+	async fn test_signup_record_password_policy() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute(
+			r#"
+			DEFINE ACCESS user ON DATABASE TYPE RECORD
+				SIGNUP (
+					CREATE user CONTENT {
+						name: $user,
+						pass: crypto::argon2::generate($secret)
+					}
+				)
+				POLICY LENGTH 12
+				PASSWORD $secret
+			;
+			"#,
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+
+		let signup = |secret: &str, passport: &str| {
+			let mut vars: HashMap<&str, Value> = HashMap::new();
+			vars.insert("user", "user".into());
+			vars.insert("secret", secret.into());
+			vars.insert("passport", passport.into());
+			let ds = &ds;
+			async move {
+				let mut sess = Session {
+					ns: Some("test".to_string()),
+					db: Some("test".to_string()),
+					..Default::default()
+				};
+				db_access(
+					ds,
+					&mut sess,
+					"test".to_string(),
+					"test".to_string(),
+					"user".to_string(),
+					vars.into(),
+				)
+				.await
+			}
+		};
+
+		// The declared variable is checked, whatever its name
+		let res = signup("short", "a very long passport number").await;
+		match res.unwrap_err().downcast().unwrap() {
+			Error::PasswordPolicy {
+				..
+			} => {}
+			e => panic!("Unexpected error, expected PasswordPolicy found {e}"),
+		}
+
+		// Other variables are not checked against the policy
+		let res = signup("a long enough secret", "short").await;
+		assert!(res.is_ok(), "Failed to signup: {:?}", res);
+
+		// A password policy can not be defined without a password variable
+		let res = ds
+			.execute(
+				"DEFINE ACCESS other ON DATABASE TYPE RECORD SIGNUP (CREATE user) POLICY LENGTH 12",
+				&sess,
+				None,
+			)
+			.await;
+		assert!(res.is_err(), "Expected a password policy without a variable to be rejected");
+	}
+
 	#[tokio::test]
 	async fn test_signup_record_with_refresh() {
 		use crate::iam::signin;
@@ -0,0 +1,286 @@
+//! Time-based one-time passwords, as specified in RFC 6238.
+//!
+//! Secrets are encoded using unpadded base32, which is the format expected
+//! by authenticator applications when provisioning a new secret.
+
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use rand::rngs::OsRng;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::cnf::SERVER_NAME;
+use crate::err::Error;
+use crate::expr::user::PasswordLockout;
+
+/// The number of seconds for which each code is valid
+pub const PERIOD: i64 = 30;
+/// The number of digits in each generated code
+pub const DIGITS: u32 = 6;
+/// The number of periods either side of the current time which are accepted
+pub const SKEW: i64 = 1;
+/// The number of bytes of entropy in a generated secret
+const SECRET_LENGTH: usize = 20;
+/// The number of recovery codes generated when enrolling a user
+pub const RECOVERY_CODES: usize = 10;
+/// The length of each generated recovery code
+const RECOVERY_LENGTH: usize = 10;
+/// The number of seconds for which a signin challenge is valid
+pub const CHALLENGE_DURATION: i64 = 300;
+/// The lockout which is applied to failed codes, for users whose password
+/// policy does not define a lockout
+pub const LOCKOUT: PasswordLockout = PasswordLockout {
+	attempts: 5,
+	duration: Duration::from_secs(900),
+};
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Encodes bytes as an unpadded RFC 4648 base32 string
+pub fn base32_encode(data: &[u8]) -> String {
+	let mut out = String::with_capacity(data.len().div_ceil(5) * 8);
+	let mut buffer: u64 = 0;
+	let mut bits = 0;
+	for byte in data {
+		buffer = (buffer << 8) | u64::from(*byte);
+		bits += 8;
+		while bits >= 5 {
+			bits -= 5;
+			out.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+		}
+	}
+	if bits > 0 {
+		out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+	}
+	out
+}
+
+/// Decodes an RFC 4648 base32 string, ignoring padding, whitespace and case
+pub fn base32_decode(data: &str) -> Option<Vec<u8>> {
+	let mut out = Vec::with_capacity(data.len() * 5 / 8);
+	let mut buffer: u64 = 0;
+	let mut bits = 0;
+	for c in data.chars() {
+		let val = match c.to_ascii_uppercase() {
+			c @ 'A'..='Z' => c as u8 - b'A',
+			c @ '2'..='7' => c as u8 - b'2' + 26,
+			'=' | ' ' | '-' => continue,
+			_ => return None,
+		};
+		buffer = (buffer << 5) | u64::from(val);
+		bits += 5;
+		if bits >= 8 {
+			bits -= 8;
+			out.push((buffer >> bits) as u8);
+		}
+	}
+	Some(out)
+}
+
+/// Computes an HMAC-based one-time password, as specified in RFC 4226
+pub fn hotp(secret: &[u8], counter: u64, digits: u32) -> String {
+	let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+	mac.update(&counter.to_be_bytes());
+	let hash = mac.finalize().into_bytes();
+	// Dynamically truncate the hash
+	let offset = (hash[19] & 0x0f) as usize;
+	let code = u32::from_be_bytes([
+		hash[offset] & 0x7f,
+		hash[offset + 1],
+		hash[offset + 2],
+		hash[offset + 3],
+	]);
+	let code = code % 10u32.pow(digits);
+	format!("{code:0width$}", width = digits as usize)
+}
+
+/// Generates a new random secret, encoded as base32
+pub fn secret() -> String {
+	let mut bytes = [0u8; SECRET_LENGTH];
+	OsRng.fill(&mut bytes);
+	base32_encode(&bytes)
+}
+
+/// Decodes a base32 secret, ensuring that it is not empty
+fn decode_secret(secret: &str) -> Result<Vec<u8>> {
+	match base32_decode(secret) {
+		Some(v) if !v.is_empty() => Ok(v),
+		_ => bail!(Error::InvalidArguments {
+			name: "crypto::totp".to_owned(),
+			message: "The TOTP secret must be a non-empty base32 string.".to_owned(),
+		}),
+	}
+}
+
+/// Generates the code for a base32 secret at the specified unix timestamp
+pub fn generate(secret: &str, timestamp: i64) -> Result<String> {
+	let secret = decode_secret(secret)?;
+	Ok(hotp(&secret, (timestamp / PERIOD) as u64, DIGITS))
+}
+
+/// Verifies a code for a base32 secret at the specified unix timestamp,
+/// allowing for a small amount of clock skew between the two parties.
+pub fn verify(secret: &str, code: &str, timestamp: i64) -> Result<bool> {
+	Ok(verify_step(secret, code, timestamp)?.is_some())
+}
+
+/// Verifies a code for a base32 secret at the specified unix timestamp,
+/// returning the time step for which the code was generated. The time step
+/// can be stored to ensure that a code is never accepted more than once.
+pub fn verify_step(secret: &str, code: &str, timestamp: i64) -> Result<Option<i64>> {
+	let secret = decode_secret(secret)?;
+	let code = code.trim();
+	let step = timestamp / PERIOD;
+	let mut valid = None;
+	for step in (step - SKEW)..=(step + SKEW) {
+		if step < 0 {
+			continue;
+		}
+		let expected = hotp(&secret, step as u64, DIGITS);
+		if bool::from(expected.as_bytes().ct_eq(code.as_bytes())) {
+			valid = Some(step);
+		}
+	}
+	Ok(valid)
+}
+
+/// Percent-encodes every character other than the unreserved characters
+fn encode_component(val: &str) -> String {
+	let mut out = String::with_capacity(val.len());
+	for byte in val.bytes() {
+		match byte {
+			b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+				out.push(byte as char)
+			}
+			_ => out.push_str(&format!("%{byte:02X}")),
+		}
+	}
+	out
+}
+
+/// Creates a provisioning URI which can be displayed as a QR code, and
+/// scanned by an authenticator application to enrol the secret.
+pub fn uri(secret: &str, account: &str, issuer: Option<&str>) -> String {
+	let issuer = encode_component(issuer.unwrap_or(SERVER_NAME));
+	let account = encode_component(account);
+	let secret = encode_component(secret);
+	format!(
+		"otpauth://totp/{issuer}:{account}?secret={secret}&issuer={issuer}&algorithm=SHA1&digits={DIGITS}&period={PERIOD}"
+	)
+}
+
+/// Generates a set of single-use recovery codes
+pub fn recovery_codes() -> Vec<String> {
+	(0..RECOVERY_CODES)
+		.map(|_| {
+			let code = secret();
+			code[..RECOVERY_LENGTH].to_ascii_lowercase()
+		})
+		.collect()
+}
+
+/// Hashes a recovery code for storage. Recovery codes are random and
+/// high-entropy, so a fast hash function is sufficient here.
+pub fn hash_recovery_code(code: &str) -> String {
+	let val = Sha256::digest(code.trim().to_ascii_lowercase().as_bytes());
+	format!("{val:x}")
+}
+
+/// Derives the key used to sign the challenge tokens for a user. This is
+/// distinct from the user's token key, so that a challenge token can never
+/// be used to authenticate a session.
+pub fn challenge_key(code: &str) -> String {
+	format!("{code}:totp")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// The shared secret used in the RFC 6238 test vectors
+	const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+	#[test]
+	fn test_base32() {
+		// Test vectors from RFC 4648, without padding
+		let cases = [
+			("", ""),
+			("f", "MY"),
+			("fo", "MZXQ"),
+			("foo", "MZXW6"),
+			("foob", "MZXW6YQ"),
+			("fooba", "MZXW6YTB"),
+			("foobar", "MZXW6YTBOI"),
+		];
+		for (raw, enc) in cases {
+			assert_eq!(base32_encode(raw.as_bytes()), enc);
+			assert_eq!(base32_decode(enc).unwrap(), raw.as_bytes());
+		}
+		assert_eq!(base32_decode("mzxw6ytboi======").unwrap(), b"foobar");
+		assert_eq!(base32_decode("MZXW6YTBO1"), None);
+	}
+
+	#[test]
+	fn test_rfc6238() {
+		// Test vectors from RFC 6238, using SHA1
+		let cases = [
+			(59, "94287082"),
+			(1111111109, "07081804"),
+			(1111111111, "14050471"),
+			(1234567890, "89005924"),
+			(2000000000, "69279037"),
+			(20000000000, "65353130"),
+		];
+		let secret = base32_encode(RFC_SECRET);
+		assert_eq!(secret, "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ");
+		for (time, code) in cases {
+			assert_eq!(hotp(RFC_SECRET, (time / PERIOD) as u64, 8), code);
+			assert_eq!(generate(&secret, time).unwrap(), code[2..]);
+			assert!(verify(&secret, &code[2..], time).unwrap());
+		}
+	}
+
+	#[test]
+	fn test_verify_skew() {
+		let secret = base32_encode(RFC_SECRET);
+		// The code for the current period is valid one period either side
+		assert!(verify(&secret, "081804", 1111111109 - PERIOD).unwrap());
+		assert!(verify(&secret, "081804", 1111111109 + PERIOD).unwrap());
+		assert!(!verify(&secret, "081804", 1111111109 + PERIOD * 2).unwrap());
+		assert!(!verify(&secret, "000000", 1111111109).unwrap());
+		// The time step for which the code was generated is returned
+		assert_eq!(verify_step(&secret, "081804", 1111111109 + PERIOD).unwrap(), Some(37037036));
+		assert_eq!(verify_step(&secret, "000000", 1111111109).unwrap(), None);
+		assert!(verify("not base32!", "000000", 0).is_err());
+	}
+
+	#[test]
+	fn test_secret() {
+		let secret = secret();
+		assert_eq!(secret.len(), 32);
+		assert_eq!(base32_decode(&secret).unwrap().len(), SECRET_LENGTH);
+	}
+
+	#[test]
+	fn test_uri() {
+		let uri = uri("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ", "tobie@surrealdb.com", None);
+		assert_eq!(
+			uri,
+			"otpauth://totp/SurrealDB:tobie%40surrealdb.com?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&issuer=SurrealDB&algorithm=SHA1&digits=6&period=30"
+		);
+	}
+
+	#[test]
+	fn test_recovery_codes() {
+		let codes = recovery_codes();
+		assert_eq!(codes.len(), RECOVERY_CODES);
+		for code in codes.iter() {
+			assert_eq!(code.len(), RECOVERY_LENGTH);
+			assert_eq!(hash_recovery_code(code), hash_recovery_code(&code.to_ascii_uppercase()));
+		}
+	}
+}
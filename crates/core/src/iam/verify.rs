@@ -1,7 +1,8 @@
+use std::fmt::Debug;
 use std::str::{self, FromStr};
 use std::sync::{Arc, LazyLock};
 
-use anyhow::{Result, bail};
+use anyhow::{Result, bail, ensure};
 use argon2::{Argon2, PasswordHash, PasswordVerifier};
 use chrono::Utc;
 use jsonwebtoken::{DecodingKey, Validation, decode};
@@ -15,11 +16,13 @@ use crate::iam::access::{authenticate_generic, authenticate_record};
 use crate::iam::issue::expiration;
 #[cfg(feature = "jwks")]
 use crate::iam::jwks;
+use crate::iam::lockout::{self, Attempts};
+use crate::iam::privileges::user_actor;
 use crate::iam::token::Claims;
-use crate::iam::{self, Actor, Auth, Level, Role};
-use crate::kvs::Datastore;
+use crate::iam::{self, Actor, Auth, Level, Role, totp};
 use crate::kvs::LockType::*;
 use crate::kvs::TransactionType::*;
+use crate::kvs::{Datastore, KVKey};
 use crate::val::Value;
 use crate::{catalog, syn};
 
@@ -102,13 +105,13 @@ pub async fn basic(
 		// DB signin
 		(Some(ns), Some(db)) => match verify_db_creds(kvs, ns, db, user, pass).await {
 			Ok(u) => {
+				// Users which have enrolled in TOTP must sign in with a code
+				ensure!(u.totp.is_none(), Error::TotpRequired);
 				debug!("Authenticated as database user '{}'", user);
 				session.exp = expiration(u.session_duration)?;
-				let au = Auth::new(Actor::from_role_names(
-					u.name.clone(),
-					&u.roles,
-					Level::Database(ns.to_owned(), db.to_owned()),
-				)?);
+				let au = Auth::new(
+					user_actor(kvs, &u, Level::Database(ns.to_owned(), db.to_owned())).await?,
+				);
 
 				session.au = Arc::new(au);
 				Ok(())
@@ -118,13 +121,11 @@ pub async fn basic(
 		// NS signin
 		(Some(ns), None) => match verify_ns_creds(kvs, ns, user, pass).await {
 			Ok(u) => {
+				// Users which have enrolled in TOTP must sign in with a code
+				ensure!(u.totp.is_none(), Error::TotpRequired);
 				debug!("Authenticated as namespace user '{}'", user);
 				session.exp = expiration(u.session_duration)?;
-				let au = Auth::new(Actor::from_role_names(
-					u.name.clone(),
-					&u.roles,
-					Level::Namespace(ns.to_owned()),
-				)?);
+				let au = Auth::new(user_actor(kvs, &u, Level::Namespace(ns.to_owned())).await?);
 
 				session.au = Arc::new(au);
 				Ok(())
@@ -134,9 +135,11 @@ pub async fn basic(
 		// Root signin
 		(None, None) => match verify_root_creds(kvs, user, pass).await {
 			Ok(u) => {
+				// Users which have enrolled in TOTP must sign in with a code
+				ensure!(u.totp.is_none(), Error::TotpRequired);
 				debug!("Authenticated as root user '{}'", user);
 				session.exp = expiration(u.session_duration)?;
-				let au = Auth::new(Actor::from_role_names(u.name.clone(), &u.roles, Level::Root)?);
+				let au = Auth::new(user_actor(kvs, &u, Level::Root).await?);
 
 				session.au = Arc::new(au);
 				Ok(())
@@ -456,14 +459,9 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			session.ns = Some(ns.to_owned());
 			session.db = Some(db.to_owned());
 			session.exp = expiration(de.session_duration)?;
-			session.au = Arc::new(Auth::new(Actor::new(
-				id.to_string(),
-				de.roles
-					.iter()
-					.map(|e| Role::from_str(e).map_err(Error::from))
-					.collect::<Result<_, _>>()?,
-				Level::Database(ns.to_string(), db.to_string()),
-			)));
+			session.au = Arc::new(Auth::new(
+				user_actor(kvs, &de, Level::Database(ns.to_string(), db.to_string())).await?,
+			));
 			Ok(())
 		}
 		// Check if this is namespace access
@@ -599,14 +597,8 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			session.tk = Some(value);
 			session.ns = Some(ns.to_owned());
 			session.exp = expiration(de.session_duration)?;
-			session.au = Arc::new(Auth::new(Actor::new(
-				id.to_string(),
-				de.roles
-					.iter()
-					.map(|e| Role::from_str(e).map_err(Error::from))
-					.collect::<Result<_, _>>()?,
-				Level::Namespace(ns.to_string()),
-			)));
+			session.au =
+				Arc::new(Auth::new(user_actor(kvs, &de, Level::Namespace(ns.to_string())).await?));
 			Ok(())
 		}
 		// Check if this is root access
@@ -711,14 +703,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			// Set the session
 			session.tk = Some(value);
 			session.exp = expiration(de.session_duration)?;
-			session.au = Arc::new(Auth::new(Actor::new(
-				id.to_string(),
-				de.roles
-					.iter()
-					.map(|e| Role::from_str(e).map_err(Error::from))
-					.collect::<Result<_, _>>()?,
-				Level::Root,
-			)));
+			session.au = Arc::new(Auth::new(user_actor(kvs, &de, Level::Root).await?));
 			Ok(())
 		}
 		// There was an auth error
@@ -742,7 +727,8 @@ pub async fn verify_root_creds(
 	// Ensure that the transaction is cancelled
 	tx.cancel().await?;
 	// Verify the specified password for the user
-	verify_pass(pass, user.hash.as_ref())?;
+	let key = crate::key::root::lk::new(&user.name);
+	verify_user_pass(ds, &key, &user, pass).await?;
 	// Clone the cached user object
 	let user = (*user).clone();
 	// Return the verified user object
@@ -780,7 +766,8 @@ pub async fn verify_ns_creds(
 	tx.cancel().await?;
 
 	// Verify the specified password for the user
-	verify_pass(pass, user.hash.as_ref())?;
+	let key = crate::key::namespace::lk::new(ns_def.namespace_id, &user.name);
+	verify_user_pass(ds, &key, &user, pass).await?;
 	// Clone the cached user object
 	let user = (*user).clone();
 	// Return the verified user object
@@ -819,13 +806,167 @@ pub async fn verify_db_creds(
 	tx.cancel().await?;
 
 	// Verify the specified password for the user
-	verify_pass(pass, user.hash.as_ref())?;
+	let key = crate::key::database::lk::new(db_def.namespace_id, db_def.database_id, &user.name);
+	verify_user_pass(ds, &key, &user, pass).await?;
 	// Clone the cached user object
 	let user = (*user).clone();
 	// Return the verified user object
 	Ok(user)
 }
 
+/// Verifies a challenge token which was issued during a two-step signin,
+/// returning the level and definition of the user it was issued to.
+pub(crate) async fn verify_totp_challenge(
+	kvs: &Datastore,
+	token: &str,
+) -> Result<(Level, catalog::UserDefinition)> {
+	// Decode the token without verifying
+	let token_data = decode::<Claims>(token, &KEY, &DUD).map_err(|e| {
+		debug!("Error decoding TOTP challenge: {e}");
+		Error::InvalidAuth
+	})?;
+	// Extract the user which the challenge was issued to
+	let Claims {
+		ns,
+		db,
+		id: Some(id),
+		..
+	} = token_data.claims
+	else {
+		bail!(Error::InvalidAuth);
+	};
+	// Create a new readonly transaction
+	let tx = kvs.transaction(Read, Optimistic).await?;
+	// Fetch the specified user from storage
+	let (level, user) = match (ns, db) {
+		(Some(ns), Some(db)) => {
+			let user = match tx.get_db_by_name(&ns, &db).await? {
+				Some(db) => tx.get_db_user(db.namespace_id, db.database_id, &id).await?,
+				None => None,
+			};
+			(Level::Database(ns, db), user)
+		}
+		(Some(ns), None) => {
+			let user = match tx.get_ns_by_name(&ns).await? {
+				Some(ns) => tx.get_ns_user(ns.namespace_id, &id).await?,
+				None => None,
+			};
+			(Level::Namespace(ns), user)
+		}
+		(None, None) => (Level::Root, tx.get_root_user(&id).await?),
+		_ => (Level::No, None),
+	};
+	// Ensure that the transaction is cancelled
+	tx.cancel().await?;
+	// Only users which have enrolled in TOTP are issued challenges
+	let Some(user) = user.filter(|u| u.totp.is_some()) else {
+		bail!(Error::InvalidAuth);
+	};
+	// Verify the token using the challenge key of the user
+	let key = totp::challenge_key(&user.code);
+	let cf = decode_key(catalog::Algorithm::Hs512, key.as_bytes())?;
+	verify_token(token, &cf.0, &cf.1)?;
+	// Clone the cached user object
+	Ok((level, (*user).clone()))
+}
+
+/// Verifies the password of a system user, enforcing the lockout and expiry
+/// of the password policy of the user, if one has been defined.
+async fn verify_user_pass<K>(
+	ds: &Datastore,
+	key: &K,
+	user: &catalog::UserDefinition,
+	pass: &str,
+) -> Result<()>
+where
+	K: KVKey<ValueType = Attempts> + Debug,
+{
+	let policy = user.policy.as_ref();
+	let limit = policy.and_then(|p| p.lockout.as_ref());
+	// Ensure that the account is not locked
+	if limit.is_some() {
+		lockout::check(ds, key).await?;
+	}
+	// Verify the password, recording the outcome
+	match (verify_pass(pass, user.hash.as_ref()), limit) {
+		// Failures are only cleared once the second factor has been verified
+		(Ok(_), Some(_)) if user.totp.is_some() => (),
+		(Ok(_), Some(_)) => lockout::success(ds, key).await?,
+		(Err(e), Some(limit)) => {
+			lockout::failure(ds, key, limit).await?;
+			return Err(e);
+		}
+		(res, None) => res?,
+	}
+	// Ensure that the password has not expired
+	if let (Some(policy), Some(changed)) = (policy, &user.changed) {
+		ensure!(!policy.expired(changed), Error::PasswordExpired);
+	}
+	Ok(())
+}
+
+/// Verifies the second factor of a system user which has enrolled in TOTP.
+///
+/// Failed codes are counted against the lockout of the password policy of
+/// the user, or against the default TOTP lockout if the policy has none, so
+/// that codes can not be found by repeatedly guessing.
+pub(crate) async fn verify_user_factor<F>(
+	ds: &Datastore,
+	level: &Level,
+	user: &catalog::UserDefinition,
+	verify: F,
+) -> Result<()>
+where
+	F: Future<Output = Result<()>>,
+{
+	match level {
+		Level::Root => {
+			let key = crate::key::root::lk::new(&user.name);
+			verify_user_attempt(ds, &key, user, verify).await
+		}
+		Level::Namespace(ns) => {
+			let tx = ds.transaction(Read, Optimistic).await?;
+			let ns = tx.expect_ns_by_name(ns).await;
+			tx.cancel().await?;
+			let key = crate::key::namespace::lk::new(ns?.namespace_id, &user.name);
+			verify_user_attempt(ds, &key, user, verify).await
+		}
+		Level::Database(ns, db) => {
+			let tx = ds.transaction(Read, Optimistic).await?;
+			let db = tx.expect_db_by_name(ns, db).await;
+			tx.cancel().await?;
+			let db = db?;
+			let key = crate::key::database::lk::new(db.namespace_id, db.database_id, &user.name);
+			verify_user_attempt(ds, &key, user, verify).await
+		}
+		_ => Err(anyhow::Error::new(Error::InvalidAuth)),
+	}
+}
+
+/// Runs the verification of a second factor, recording the outcome
+async fn verify_user_attempt<K, F>(
+	ds: &Datastore,
+	key: &K,
+	user: &catalog::UserDefinition,
+	verify: F,
+) -> Result<()>
+where
+	K: KVKey<ValueType = Attempts> + Debug,
+	F: Future<Output = Result<()>>,
+{
+	let limit = user.policy.as_ref().and_then(|p| p.lockout.as_ref()).unwrap_or(&totp::LOCKOUT);
+	// Ensure that the account is not locked
+	lockout::check(ds, key).await?;
+	// Verify the second factor, recording the outcome
+	match verify.await {
+		Ok(_) => lockout::success(ds, key).await,
+		Err(e) => {
+			lockout::failure(ds, key, limit).await?;
+			Err(e)
+		}
+	}
+}
+
 fn verify_pass(pass: &str, hash: &str) -> Result<()> {
 	// Compute the hash and verify the password
 	let hash = PasswordHash::new(hash).unwrap();
@@ -1058,6 +1199,8 @@ mod tests {
 				roles: vec!["nonexistent".to_owned()],
 				token_duration: None,
 				session_duration: None,
+				totp: None,
+				policy: None,
 				comment: None,
 			};
 
@@ -1067,8 +1210,32 @@ mod tests {
 				)))],
 			};
 
-			// Use pre-parsed definition, which bypasses the existent role check during parsing.
-			ds.process(ast, &sess, None).await.unwrap();
+			// The role is neither a built-in role nor a custom role defined with DEFINE ROLE.
+			let mut res = ds.process(ast, &sess, None).await.unwrap();
+			let e = res.remove(0).result.unwrap_err();
+			match e.downcast().expect("Unexpected error kind") {
+				Error::RoleRootNotFound {
+					..
+				}
+				| Error::RoleNsNotFound {
+					..
+				}
+				| Error::RoleDbNotFound {
+					..
+				} => {}
+				e => panic!("Unexpected error, expected a role not found error, found {e}"),
+			}
+
+			// A custom role which is removed after being assigned is invalid at signin.
+			let define_query = format!(
+				"DEFINE ROLE temporary ON {0};
+				DEFINE USER user ON {0} PASSWORD 'pass' ROLES temporary;
+				REMOVE ROLE temporary ON {0};",
+				level.level,
+			);
+			for res in ds.execute(&define_query, &sess, None).await.unwrap() {
+				res.result.unwrap();
+			}
 
 			let mut sess = Session {
 				ns: level.ns.map(String::from),
@@ -1087,6 +1254,281 @@ mod tests {
 		}
 	}
 
+	#[tokio::test]
+	async fn test_basic_custom_role() {
+		use crate::iam::{Action, Resource, ResourceKind};
+
+		let test_levels = vec![
+			TestLevel {
+				level: "ROOT",
+				ns: None,
+				db: None,
+			},
+			TestLevel {
+				level: "NS",
+				ns: Some("test"),
+				db: None,
+			},
+			TestLevel {
+				level: "DB",
+				ns: Some("test"),
+				db: Some("test"),
+			},
+		];
+
+		for level in &test_levels {
+			let ds = Datastore::new("memory").await.unwrap();
+			let sess = Session::owner().with_ns("test").with_db("test");
+
+			let define_query = format!(
+				"DEFINE ROLE auditor ON {0} GRANT SELECT, UPDATE ON TABLE person REVOKE UPDATE ON TABLE person;
+				DEFINE USER user ON {0} PASSWORD 'pass' ROLES VIEWER, auditor;",
+				level.level,
+			);
+			for res in ds.execute(&define_query, &sess, None).await.unwrap() {
+				res.result.unwrap();
+			}
+
+			let mut sess = Session {
+				ns: level.ns.map(String::from),
+				db: level.db.map(String::from),
+				..Default::default()
+			};
+
+			basic(&ds, &mut sess, "user", "pass", level.ns, level.db).await.unwrap();
+
+			// The built-in and custom roles are both assigned
+			assert!(sess.au.has_role(Role::Viewer), "Auth user expected to have Viewer role");
+			assert_eq!(sess.au.privileges().roles, vec!["auditor".to_string()]);
+
+			// Revoked privileges take precedence over granted privileges
+			let res = Resource::new(
+				"person".to_string(),
+				ResourceKind::Table,
+				Level::Database("test".to_string(), "test".to_string()),
+			);
+			assert!(sess.au.is_allowed(Action::Select, &res).is_ok());
+			assert!(sess.au.is_allowed(Action::Update, &res).is_err());
+			assert!(sess.au.is_allowed(Action::Delete, &res).is_err());
+		}
+	}
+
+	#[tokio::test]
+	async fn test_custom_role_without_builtin_role() {
+		use crate::iam::{Action, Resource, ResourceKind};
+
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+
+		let define_query = "
+			DEFINE TABLE person PERMISSIONS NONE;
+			DEFINE TABLE secret PERMISSIONS NONE;
+			CREATE person:tobie, secret:one;
+			DEFINE ROLE analyst ON DATABASE GRANT SELECT ON TABLE person;
+			DEFINE USER user ON DATABASE PASSWORD 'pass' ROLES analyst;
+		";
+		for res in ds.execute(define_query, &sess, None).await.unwrap() {
+			res.result.unwrap();
+		}
+
+		let mut sess = Session {
+			ns: Some("test".to_string()),
+			db: Some("test".to_string()),
+			..Default::default()
+		};
+
+		basic(&ds, &mut sess, "user", "pass", Some("test"), Some("test")).await.unwrap();
+
+		// Only the custom role is assigned
+		assert!(!sess.au.has_viewer_role(), "Auth user expected to have no built-in roles");
+		assert_eq!(sess.au.privileges().roles, vec!["analyst".to_string()]);
+
+		// There is no implicit view access on ungranted resources
+		let level = Level::Database("test".to_string(), "test".to_string());
+		let person = Resource::new("person".to_string(), ResourceKind::Table, level.clone());
+		let secret = Resource::new("secret".to_string(), ResourceKind::Table, level);
+		assert!(sess.au.is_allowed(Action::Select, &person).is_ok());
+		assert!(sess.au.is_allowed(Action::Select, &secret).is_err());
+		assert!(sess.au.is_allowed(Action::View, &secret).is_err());
+
+		// Table permissions still apply to ungranted tables
+		let mut res = ds.execute("SELECT VALUE id FROM person", &sess, None).await.unwrap();
+		let val = res.remove(0).result.unwrap();
+		assert_eq!(val.to_string(), "[person:tobie]");
+		let mut res = ds.execute("SELECT VALUE id FROM secret", &sess, None).await.unwrap();
+		let val = res.remove(0).result.unwrap();
+		assert_eq!(val.to_string(), "[]");
+	}
+
+	#[tokio::test]
+	async fn test_custom_role_revoke_keeps_builtin_access() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+
+		let define_query = "
+			DEFINE TABLE a PERMISSIONS NONE;
+			DEFINE TABLE b PERMISSIONS NONE;
+			CREATE a:one, b:one;
+			DEFINE ROLE restricted ON DATABASE REVOKE SELECT ON TABLE a;
+			DEFINE USER user ON DATABASE PASSWORD 'pass' ROLES OWNER, restricted;
+		";
+		for res in ds.execute(define_query, &sess, None).await.unwrap() {
+			res.result.unwrap();
+		}
+
+		let mut sess = Session {
+			ns: Some("test".to_string()),
+			db: Some("test".to_string()),
+			..Default::default()
+		};
+
+		basic(&ds, &mut sess, "user", "pass", Some("test"), Some("test")).await.unwrap();
+
+		// The revoked table can not be read
+		let mut res = ds.execute("SELECT VALUE id FROM a", &sess, None).await.unwrap();
+		let val = res.remove(0).result.unwrap();
+		assert_eq!(val.to_string(), "[]");
+		// Other tables are still covered by the built-in role
+		let mut res = ds.execute("SELECT VALUE id FROM b", &sess, None).await.unwrap();
+		let val = res.remove(0).result.unwrap();
+		assert_eq!(val.to_string(), "[b:one]");
+	}
+
+	#[tokio::test]
+	async fn test_custom_role_named_define_privileges() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+
+		let define_query = "
+			DEFINE TABLE person PERMISSIONS NONE;
+			CREATE person:tobie SET name = 'Tobie';
+			DEFINE ROLE dba ON DATABASE GRANT DEFINE ON INDEX foo;
+			DEFINE ROLE limited ON DATABASE REVOKE DEFINE ON TABLE person;
+			DEFINE USER dba ON DATABASE PASSWORD 'pass' ROLES dba;
+			DEFINE USER owner ON DATABASE PASSWORD 'pass' ROLES OWNER, limited;
+		";
+		for res in ds.execute(define_query, &sess, None).await.unwrap() {
+			res.result.unwrap();
+		}
+
+		// A named grant allows defining only the named index
+		let mut sess = Session {
+			ns: Some("test".to_string()),
+			db: Some("test".to_string()),
+			..Default::default()
+		};
+		basic(&ds, &mut sess, "dba", "pass", Some("test"), Some("test")).await.unwrap();
+		let mut res = ds
+			.execute(
+				"DEFINE INDEX foo ON person FIELDS name; DEFINE INDEX bar ON person FIELDS name; SELECT VALUE id FROM person;",
+				&sess,
+				None,
+			)
+			.await
+			.unwrap();
+		res.remove(0).result.unwrap();
+		res.remove(0).result.unwrap_err();
+		let val = res.remove(0).result.unwrap();
+		assert_eq!(val.to_string(), "[]");
+
+		// A named revoke prevents defining only the named table
+		let mut sess = Session {
+			ns: Some("test".to_string()),
+			db: Some("test".to_string()),
+			..Default::default()
+		};
+		basic(&ds, &mut sess, "owner", "pass", Some("test"), Some("test")).await.unwrap();
+		let mut res = ds
+			.execute("DEFINE TABLE OVERWRITE person; DEFINE TABLE other;", &sess, None)
+			.await
+			.unwrap();
+		res.remove(0).result.unwrap_err();
+		res.remove(0).result.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_custom_role_grants_on_resources() {
+		use crate::dbs::Options;
+		use crate::expr::Base;
+		use crate::iam::{Action, ResourceKind};
+
+		let kinds = [
+			("TABLE", ResourceKind::Table, "a", "b"),
+			("FUNCTION", ResourceKind::Function, "fn::a", "fn::b"),
+			("PARAM", ResourceKind::Parameter, "$a", "$b"),
+			("INDEX", ResourceKind::Index, "a", "b"),
+			("ACCESS", ResourceKind::Access, "a", "b"),
+		];
+
+		for (keyword, kind, a, b) in kinds {
+			let ds = Datastore::new("memory").await.unwrap();
+			let sess = Session::owner().with_ns("test").with_db("test");
+
+			let define_query = format!(
+				"DEFINE ROLE custom ON DATABASE GRANT SELECT, UPDATE ON {keyword} GRANT DELETE ON {keyword} {a} REVOKE UPDATE ON {keyword} {b};
+				DEFINE ROLE restricted ON DATABASE REVOKE DEFINE ON {keyword} {b};
+				DEFINE USER custom ON DATABASE PASSWORD 'pass' ROLES custom;
+				DEFINE USER owner ON DATABASE PASSWORD 'pass' ROLES OWNER, restricted;"
+			);
+			for res in ds.execute(&define_query, &sess, None).await.unwrap() {
+				res.result.unwrap();
+			}
+
+			// A user with only a custom role
+			let mut sess = Session {
+				ns: Some("test".to_string()),
+				db: Some("test".to_string()),
+				..Default::default()
+			};
+			basic(&ds, &mut sess, "custom", "pass", Some("test"), Some("test")).await.unwrap();
+			let opt = Options::new()
+				.with_ns(Some("test".into()))
+				.with_db(Some("test".into()))
+				.with_auth(sess.au.clone());
+			let allowed =
+				|action, name| opt.is_allowed_on(action, kind.clone(), name, &Base::Db).is_ok();
+
+			// A grant without names covers every resource of the kind
+			assert!(allowed(Action::Select, "a"), "{keyword}");
+			assert!(allowed(Action::Select, "b"), "{keyword}");
+			// A revoke takes precedence over a grant
+			assert!(allowed(Action::Update, "a"), "{keyword}");
+			assert!(!allowed(Action::Update, "b"), "{keyword}");
+			// A grant with names covers only the named resources
+			assert!(allowed(Action::Delete, "a"), "{keyword}");
+			assert!(!allowed(Action::Delete, "b"), "{keyword}");
+			// Privileges which are not granted are not allowed
+			assert!(!allowed(Action::Create, "a"), "{keyword}");
+			// There is no implicit view access on other kinds of resource
+			assert!(
+				opt.is_allowed_on(Action::View, ResourceKind::Analyzer, "a", &Base::Db).is_err(),
+				"{keyword}"
+			);
+			// Grant checks decide access, or fall back to the resource permissions
+			assert_eq!(opt.check_grants(Action::Select, kind.clone(), "b").unwrap(), Some(true));
+			assert_eq!(opt.check_grants(Action::Update, kind.clone(), "b").unwrap(), Some(false));
+			assert_eq!(opt.check_grants(Action::Create, kind.clone(), "a").unwrap(), None);
+
+			// A user with a built-in role and a custom role which revokes privileges
+			let mut sess = Session {
+				ns: Some("test".to_string()),
+				db: Some("test".to_string()),
+				..Default::default()
+			};
+			basic(&ds, &mut sess, "owner", "pass", Some("test"), Some("test")).await.unwrap();
+			let opt = opt.new_with_auth(sess.au.clone());
+			let allowed =
+				|action, name| opt.is_allowed_on(action, kind.clone(), name, &Base::Db).is_ok();
+
+			// A revoke takes precedence over the built-in role
+			assert!(allowed(Action::Define, "a"), "{keyword}");
+			assert!(!allowed(Action::Define, "b"), "{keyword}");
+			assert!(allowed(Action::Remove, "b"), "{keyword}");
+			assert_eq!(opt.check_grants(Action::Define, kind.clone(), "b").unwrap(), Some(false));
+			assert_eq!(opt.check_grants(Action::Select, kind.clone(), "b").unwrap(), Some(true));
+		}
+	}
+
 	#[tokio::test]
 	async fn test_token() {
 		#[derive(Debug)]
@@ -20,6 +20,7 @@ use crate::dbs::{Iterable, Iterator, Options, Statement};
 use crate::expr::order::Ordering;
 use crate::expr::with::With;
 use crate::expr::{Cond, Fields, Groups};
+use crate::iam::ResourceKind;
 use crate::idx::planner::executor::{InnerQueryExecutor, IteratorEntry, QueryExecutor};
 use crate::idx::planner::iterators::IteratorRef;
 use crate::idx::planner::knn::KnnBruteForceResults;
@@ -92,6 +93,13 @@ impl<'a> StatementContext<'a> {
 		if !self.is_perm {
 			return Ok(GrantedPermission::Full);
 		}
+		// Check any custom role privileges
+		if let Some(allowed) = self.opt.check_grants(self.stm.into(), ResourceKind::Table, tb)? {
+			return Ok(match allowed {
+				true => GrantedPermission::Full,
+				false => GrantedPermission::None,
+			});
+		}
 		let (ns, db) = self.ctx.get_ns_db_ids(self.opt).await?;
 		// Get the table for this planner
 		match self.ctx.tx().get_tb(ns, db, tb).await? {
@@ -68,6 +68,7 @@ impl<'a> KnnConditionRewriter<'a> {
 			| Expr::Throw(_)
 			| Expr::IfElse(_)
 			| Expr::Select(_)
+			| Expr::Match(_)
 			| Expr::Create(_)
 			| Expr::Update(_)
 			| Expr::Delete(_)
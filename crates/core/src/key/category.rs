@@ -27,6 +27,10 @@ pub enum Category {
 	Namespace,
 	/// crate::key::root::us                 /!us{us}
 	User,
+	/// crate::key::root::rl                 /!rl{rl}
+	Role,
+	/// crate::key::root::lk                 /!lk{us}
+	UserLockout,
 	/// crate::key::root::tl                 /!tl{tl}
 	TaskLease,
 	/// crate::key::root::ic                 /!ic{ns}{db}{tb}{ix}{nid}{uuid}
@@ -38,6 +42,8 @@ pub enum Category {
 	NodeRoot,
 	/// crate::key::node::lq                 /${nd}!lq{lq}{ns}{db}
 	NodeLiveQuery,
+	/// crate::key::node::nq                 /${nd}!nq{lq}{id}
+	NodeNotification,
 	///
 	/// ------------------------------
 	///
@@ -60,6 +66,10 @@ pub enum Category {
 	NamespaceAccessGrant,
 	/// crate::key::namespace::us            /*{ns}!us{us}
 	NamespaceUser,
+	/// crate::key::namespace::rl            /*{ns}!rl{rl}
+	NamespaceRole,
+	/// crate::key::namespace::lk            /*{ns}!lk{us}
+	NamespaceUserLockout,
 	///
 	/// ------------------------------
 	///
@@ -71,6 +81,8 @@ pub enum Category {
 	DatabaseAccessRoot,
 	/// crate::key::database::access::gr     /*{ns}*{db}*ac!gr{gr}
 	DatabaseAccessGrant,
+	/// crate::key::database::access::lk     /*{ns}*{db}&{ac}!lk{id}
+	DatabaseAccessLockout,
 	/// crate::key::database::ap             /*{ns}*{db}!ap{ap}
 	DatabaseApi,
 	/// crate::key::database::az             /*{ns}*{db}!az{az}
@@ -81,6 +93,8 @@ pub enum Category {
 	DatabaseFunction,
 	/// crate::key::database::ml             /*{ns}*{db}!ml{ml}{vn}
 	DatabaseModel,
+	/// crate::key::database::md             /*{ns}*{db}!md{md}
+	DatabaseModule,
 	/// crate::key::database::pa             /*{ns}*{db}!pa{pa}
 	DatabaseParameter,
 	/// crate::key::database::tb             /*{ns}*{db}!tb{tb}
@@ -89,6 +103,10 @@ pub enum Category {
 	DatabaseTimestamp,
 	/// crate::key::database::us             /*{ns}*{db}!us{us}
 	DatabaseUser,
+	/// crate::key::database::rl             /*{ns}*{db}!rl{rl}
+	DatabaseRole,
+	/// crate::key::database::lk             /*{ns}*{db}!lk{us}
+	DatabaseUserLockout,
 	/// crate::key::database::vs             /*{ns}*{db}!vs
 	DatabaseVersionstamp,
 	/// crate::key::database::cg             /*{ns}*{db}!cg{ty}
@@ -207,8 +225,11 @@ impl Display for Category {
 			Self::NamespaceIdentifier => "NamespaceIdentifier",
 			Self::Namespace => "Namespace",
 			Self::User => "User",
+			Self::Role => "Role",
+			Self::UserLockout => "UserLockout",
 			Self::NodeRoot => "NodeRoot",
 			Self::NodeLiveQuery => "NodeLiveQuery",
+			Self::NodeNotification => "NodeNotification",
 			Self::NamespaceRoot => "NamespaceRoot",
 			Self::DatabaseAlias => "DatabaseAlias",
 			Self::DatabaseIdentifier => "DatabaseIdentifier",
@@ -216,20 +237,26 @@ impl Display for Category {
 			Self::NamespaceAccessRoot => "NamespaceAccessRoot",
 			Self::NamespaceAccessGrant => "NamespaceAccessGrant",
 			Self::NamespaceUser => "NamespaceUser",
+			Self::NamespaceRole => "NamespaceRole",
+			Self::NamespaceUserLockout => "NamespaceUserLockout",
 			Self::DatabaseRoot => "DatabaseRoot",
 			Self::DatabaseAccess => "DatabaseAccess",
 			Self::DatabaseAccessRoot => "DatabaseAccessRoot",
 			Self::DatabaseAccessGrant => "DatabaseAccessGrant",
+			Self::DatabaseAccessLockout => "DatabaseAccessLockout",
 			Self::DatabaseApi => "DatabaseApi",
 			Self::DatabaseAnalyzer => "DatabaseAnalyzer",
 			Self::DatabaseBucket => "DatabaseBucket",
 			Self::DatabaseFunction => "DatabaseFunction",
 			Self::DatabaseModel => "DatabaseModel",
+			Self::DatabaseModule => "DatabaseModule",
 			Self::DatabaseParameter => "DatabaseParameter",
 			Self::DatabaseTable => "DatabaseTable",
 			Self::DatabaseTableIdentifier => "DatabaseTableIdentifier",
 			Self::DatabaseTimestamp => "DatabaseTimestamp",
 			Self::DatabaseUser => "DatabaseUser",
+			Self::DatabaseRole => "DatabaseRole",
+			Self::DatabaseUserLockout => "DatabaseUserLockout",
 			Self::DatabaseVersionstamp => "DatabaseVersionstamp",
 			Self::DatabaseSequence => "DatabaseSequence",
 			Self::DatabaseConfig => "DatabaseConfig",
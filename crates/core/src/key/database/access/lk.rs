@@ -0,0 +1,97 @@
+//! Stores the failed signin attempts of a record user of an access method
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::iam::lockout::Attempts;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct AccessLockoutKey<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub ac: Cow<'a, str>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub id: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(AccessLockoutKey<'_> => Attempts);
+
+pub fn new<'a>(ns: NamespaceId, db: DatabaseId, ac: &'a str, id: &'a str) -> AccessLockoutKey<'a> {
+	AccessLockoutKey::new(ns, db, ac, id)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId, ac: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db, ac).encode_key()?;
+	k.extend_from_slice(b"!lk\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId, ac: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db, ac).encode_key()?;
+	k.extend_from_slice(b"!lk\xff");
+	Ok(k)
+}
+
+impl Categorise for AccessLockoutKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseAccessLockout
+	}
+}
+
+impl<'a> AccessLockoutKey<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, ac: &'a str, id: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'&',
+			ac: Cow::Borrowed(ac),
+			_d: b'!',
+			_e: b'l',
+			_f: b'k',
+			id: Cow::Borrowed(id),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		#[rustfmt::skip]
+		let val = AccessLockoutKey::new(
+			NamespaceId(1),
+			DatabaseId(2),
+			"testac",
+			"testid",
+		);
+		let enc = AccessLockoutKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02&testac\0!lktestid\0");
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix(NamespaceId(1), DatabaseId(2), "testac").unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02&testac\0!lk\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix(NamespaceId(1), DatabaseId(2), "testac").unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02&testac\0!lk\xff");
+	}
+}
@@ -0,0 +1,68 @@
+//! Stores the failed signin attempts of a database user
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::iam::lockout::Attempts;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Lk<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub user: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Lk<'_> => Attempts);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, user: &str) -> Lk<'_> {
+	Lk::new(ns, db, user)
+}
+
+impl Categorise for Lk<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseUserLockout
+	}
+}
+
+impl<'a> Lk<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, user: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'l',
+			_e: b'k',
+			user: Cow::Borrowed(user),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		#[rustfmt::skip]
+		let val = Lk::new(
+			NamespaceId(1),
+			DatabaseId(2),
+			"testuser",
+		);
+		let enc = Lk::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!lktestuser\0");
+	}
+}
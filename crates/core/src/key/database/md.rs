@@ -0,0 +1,79 @@
+//! Stores a DEFINE MODULE definition
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, ModuleDefinition, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Md<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub md: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Md<'_> => ModuleDefinition);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, md: &str) -> Md<'_> {
+	Md::new(ns, db, md)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!md\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!md\xff");
+	Ok(k)
+}
+
+impl Categorise for Md<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseModule
+	}
+}
+
+impl<'a> Md<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, md: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'm',
+			_e: b'd',
+			md: Cow::Borrowed(md),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		#[rustfmt::skip]
+		let val = Md::new(
+			NamespaceId(1),
+			DatabaseId(2),
+			"testmd",
+		);
+		let enc = Md::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!mdtestmd\0");
+	}
+}
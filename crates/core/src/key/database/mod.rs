@@ -7,8 +7,11 @@ pub mod bu;
 pub mod cg;
 pub mod fc;
 pub mod ix;
+pub mod lk;
+pub mod md;
 pub mod ml;
 pub mod pa;
+pub mod rl;
 pub mod sq;
 pub mod tb;
 pub mod ti;
@@ -0,0 +1,92 @@
+//! Stores a DEFINE ROLE ON DATABASE config definition
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog;
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct RoleKey<'key> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub role: Cow<'key, str>,
+}
+
+impl_kv_key_storekey!(RoleKey<'_> => catalog::RoleDefinition);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, role: &str) -> RoleKey<'_> {
+	RoleKey::new(ns, db, role)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!rl\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!rl\xff");
+	Ok(k)
+}
+
+impl Categorise for RoleKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseRole
+	}
+}
+
+impl<'a> RoleKey<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, role: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'r',
+			_e: b'l',
+			role: Cow::Borrowed(role),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		#[rustfmt::skip]
+		let val = RoleKey::new(
+			NamespaceId(1),
+			DatabaseId(2),
+			"testrole",
+		);
+		let enc = RoleKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!rltestrole\0");
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!rl\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!rl\xff");
+	}
+}
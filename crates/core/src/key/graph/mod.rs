@@ -10,6 +10,36 @@ use crate::key::category::{Categorise, Category};
 use crate::kvs::{KVKey, impl_kv_key_storekey};
 use crate::val::{RecordId, RecordIdKey};
 
+#[derive(Clone, Debug, Eq, PartialEq, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+struct PrefixTb<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, str>,
+	_d: u8,
+}
+
+impl_kv_key_storekey!(PrefixTb<'_> => Vec<u8>);
+
+impl<'a> PrefixTb<'a> {
+	fn new(ns: NamespaceId, db: DatabaseId, tb: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'~',
+		}
+	}
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Encode, BorrowDecode)]
 #[storekey(format = "()")]
 struct Prefix<'a> {
@@ -155,6 +185,20 @@ pub fn new<'a>(
 	Graph::new(ns, db, tb, id.to_owned(), eg.to_owned(), fk)
 }
 
+/// Returns the start of the range of the edge pointers of every record in a table
+pub fn tbprefix(ns: NamespaceId, db: DatabaseId, tb: &str) -> Result<Vec<u8>> {
+	let mut k = PrefixTb::new(ns, db, tb).encode_key()?;
+	k.extend_from_slice(&[0x00]);
+	Ok(k)
+}
+
+/// Returns the end of the range of the edge pointers of every record in a table
+pub fn tbsuffix(ns: NamespaceId, db: DatabaseId, tb: &str) -> Result<Vec<u8>> {
+	let mut k = PrefixTb::new(ns, db, tb).encode_key()?;
+	k.extend_from_slice(&[0xff]);
+	Ok(k)
+}
+
 pub fn prefix(ns: NamespaceId, db: DatabaseId, tb: &str, id: &RecordIdKey) -> Result<Vec<u8>> {
 	let mut k = Prefix::new(ns, db, tb, id).encode_key()?;
 	k.extend_from_slice(&[0x00]);
@@ -22,14 +22,17 @@
 //!
 //! crate::key::root::all                /
 //! crate::key::root::ac                 /!ac{ac}
+//! crate::key::root::lk                 /!lk{us}
 //! crate::key::root::nd                 /!nd{nd}
 //! crate::key::root::ni                 /!ni
 //! crate::key::root::ns                 /!ns{ns} -> NamespaceDefinition
+//! crate::key::root::rl                 /!rl{rl}
 //! crate::key::root::us                 /!us{us}
 //! crate::key::root::tl                 /!tl{tl}
 //!
 //! crate::key::node::all                /${nd}
 //! crate::key::node::lq                 /${nd}!lq{lq}{ns}{db}
+//! crate::key::node::nq                 /${nd}!nq{lq}{id} -> Notification
 //!
 //! crate::key::root::access::all        /&{ac}
 //! crate::key::root::access::gr         /&{ac}!gr{gr}
@@ -39,6 +42,8 @@
 //! crate::key::namespace::db            /*{ns}!db{db_name} -> DatabaseDefinition
 //! crate::key::namespace::di            /+{ns}!di
 //! crate::key::namespace::lg            /*{ns}!lg{lg}
+//! crate::key::namespace::lk            /*{ns}!lk{us}
+//! crate::key::namespace::rl            /*{ns}!rl{rl}
 //! crate::key::namespace::us            /*{ns}!us{us}
 //!
 //! crate::key::namespace::access::all   /*{ns}&{ac}
@@ -49,8 +54,11 @@
 //! crate::key::database::az             /*{ns}*{db}!az{az_name}
 //! crate::key::database::bu             /*{ns}*{db}!bu{bu_name}
 //! crate::key::database::fc             /*{ns}*{db}!fn{fc_name}
+//! crate::key::database::lk             /*{ns}*{db}!lk{us_name}
+//! crate::key::database::md             /*{ns}*{db}!md{md_name}
 //! crate::key::database::ml             /*{ns}*{db}!ml{ml_name}{vn}
 //! crate::key::database::pa             /*{ns}*{db}!pa{pa_name}
+//! crate::key::database::rl             /*{ns}*{db}!rl{rl_name}
 //! crate::key::database::sq             /*{ns}*{db}!sq{sq_name}
 //! crate::key::database::tb             /*{ns}*{db}!tb{tb_name} -> TableDefinition
 //! crate::key::database::ti             /+{ns}*{db}!ti
@@ -61,6 +69,7 @@
 //!
 //! crate::key::database::access::all    /*{ns}*{db}&{ac}
 //! crate::key::database::access::gr     /*{ns}*{db}&{ac}!gr{gr}
+//! crate::key::database::access::lk     /*{ns}*{db}&{ac}!lk{id}
 //!
 //! crate::key::table::all               /*{ns}*{db}*{tb_name}
 //! crate::key::table::ev                /*{ns}*{db}*{tb_name}!ev{ev}
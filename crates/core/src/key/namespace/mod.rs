@@ -3,4 +3,6 @@ pub mod access;
 pub mod all;
 pub mod db;
 pub mod di;
+pub mod lk;
+pub mod rl;
 pub mod us;
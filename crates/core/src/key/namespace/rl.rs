@@ -0,0 +1,86 @@
+//! Stores a DEFINE ROLE ON NAMESPACE config definition
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{self, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Rl<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	_c: u8,
+	_d: u8,
+	pub role: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Rl<'_> => catalog::RoleDefinition);
+
+pub fn new(ns: NamespaceId, role: &str) -> Rl<'_> {
+	Rl::new(ns, role)
+}
+
+pub fn prefix(ns: NamespaceId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns).encode_key()?;
+	k.extend_from_slice(b"!rl\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: NamespaceId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns).encode_key()?;
+	k.extend_from_slice(b"!rl\xff");
+	Ok(k)
+}
+
+impl Categorise for Rl<'_> {
+	fn categorise(&self) -> Category {
+		Category::NamespaceRole
+	}
+}
+
+impl<'a> Rl<'a> {
+	pub fn new(ns: NamespaceId, role: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'!',
+			_c: b'r',
+			_d: b'l',
+			role: Cow::Borrowed(role),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		#[rustfmt::skip]
+		let val = Rl::new(
+			NamespaceId(1),
+			"testrole",
+		);
+		let enc = Rl::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01!rltestrole\0");
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix(NamespaceId(1)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01!rl\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix(NamespaceId(1)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01!rl\xff");
+	}
+}
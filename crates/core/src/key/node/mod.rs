@@ -1,2 +1,3 @@
 pub mod all;
 pub mod lq;
+pub mod nq;
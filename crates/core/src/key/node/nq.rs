@@ -0,0 +1,123 @@
+//! Stores a queued LIVE SELECT notification for a node on the cluster
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+use uuid::Uuid;
+
+use crate::dbs::Notification;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::vs::VersionStamp;
+
+/// The Nq key is used to deliver live query notifications to the node which
+/// owns the live query, when the notification was produced by a write on a
+/// different node in the cluster.
+///
+/// Notifications are stored grouped by live query, and then ordered by the
+/// versionstamp of the transaction which produced them, so that notifications
+/// for a single live query are read back by the owning node in the order in
+/// which their transactions were committed. Notifications which are produced
+/// by the same transaction are ordered by a time ordered unique id.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[cfg_attr(not(test), expect(dead_code))]
+pub(crate) struct Nq {
+	__: u8,
+	_a: u8,
+	pub nd: Uuid,
+	_b: u8,
+	_c: u8,
+	_d: u8,
+	pub lq: Uuid,
+	pub vs: VersionStamp,
+	pub id: Uuid,
+}
+
+impl_kv_key_storekey!(Nq => Notification);
+
+pub fn versionstamped_key_prefix(nd: Uuid, lq: Uuid) -> Result<Vec<u8>> {
+	let mut k = super::all::new(nd).encode_key()?;
+	k.extend_from_slice(b"!nq");
+	k.extend_from_slice(lq.as_bytes());
+	Ok(k)
+}
+
+pub fn versionstamped_key_suffix(id: Uuid) -> Vec<u8> {
+	id.as_bytes().to_vec()
+}
+
+pub fn prefix(nd: Uuid) -> Result<Vec<u8>> {
+	let mut k = super::all::new(nd).encode_key()?;
+	k.extend_from_slice(b"!nq\x00");
+	Ok(k)
+}
+
+pub fn suffix(nd: Uuid) -> Result<Vec<u8>> {
+	let mut k = super::all::new(nd).encode_key()?;
+	k.extend_from_slice(b"!nq");
+	k.extend_from_slice(&[0xff; 43]);
+	Ok(k)
+}
+
+impl Categorise for Nq {
+	fn categorise(&self) -> Category {
+		Category::NodeNotification
+	}
+}
+
+impl Nq {
+	#[cfg(test)]
+	pub fn new(nd: Uuid, lq: Uuid, vs: VersionStamp, id: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'$',
+			nd,
+			_b: b'!',
+			_c: b'n',
+			_d: b'q',
+			lq,
+			vs,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		#[rustfmt::skip]
+		let nd = Uuid::from_bytes([0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10]);
+		#[rustfmt::skip]
+		let lq = Uuid::from_bytes([0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f, 0x20]);
+		#[rustfmt::skip]
+		let id = Uuid::from_bytes([0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f, 0x30]);
+		let vs = VersionStamp::from_u64(0x3132333435363738);
+		let val = Nq::new(nd, lq, vs, id);
+		let enc = Nq::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/$\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10\
+			!nq\x11\x12\x13\x14\x15\x16\x17\x18\x19\x1a\x1b\x1c\x1d\x1e\x1f\x20\
+			\x31\x32\x33\x34\x35\x36\x37\x38\x00\x00\
+			\x21\x22\x23\x24\x25\x26\x27\x28\x29\x2a\x2b\x2c\x2d\x2e\x2f\x30"
+		);
+		// The versionstamped key is split around the versionstamp
+		let mut key = versionstamped_key_prefix(nd, lq).unwrap();
+		key.extend_from_slice(&vs.as_bytes());
+		key.extend_from_slice(&versionstamped_key_suffix(id));
+		assert_eq!(key, enc);
+	}
+
+	#[test]
+	fn test_prefix() {
+		#[rustfmt::skip]
+		let nd = Uuid::from_bytes([0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10]);
+		let val = super::prefix(nd).unwrap();
+		assert_eq!(
+			val,
+			b"/$\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10\
+			!nq\x00"
+		);
+	}
+}
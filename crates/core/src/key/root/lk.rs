@@ -0,0 +1,54 @@
+//! Stores the failed signin attempts of a root user
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::iam::lockout::Attempts;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Lk<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub user: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Lk<'_> => Attempts);
+
+pub fn new(user: &str) -> Lk<'_> {
+	Lk::new(user)
+}
+
+impl Categorise for Lk<'_> {
+	fn categorise(&self) -> Category {
+		Category::UserLockout
+	}
+}
+
+impl<'a> Lk<'a> {
+	pub fn new(user: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'l',
+			_c: b'k',
+			user: Cow::Borrowed(user),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = Lk::new("testuser");
+		let enc = Lk::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/!lktestuser\0");
+	}
+}
@@ -2,8 +2,10 @@ pub mod ac;
 pub mod access;
 pub mod all;
 pub mod ic;
+pub mod lk;
 pub mod nd;
 pub mod ni;
 pub mod ns;
+pub mod rl;
 pub mod tl;
 pub mod us;
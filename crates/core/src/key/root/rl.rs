@@ -0,0 +1,79 @@
+//! Stores a DEFINE ROLE ON ROOT config definition
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Rl<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub role: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Rl<'_> => catalog::RoleDefinition);
+
+pub fn new(role: &str) -> Rl<'_> {
+	Rl::new(role)
+}
+
+pub fn prefix() -> Vec<u8> {
+	let mut k = super::all::kv();
+	k.extend_from_slice(b"!rl\x00");
+	k
+}
+
+pub fn suffix() -> Vec<u8> {
+	let mut k = super::all::kv();
+	k.extend_from_slice(b"!rl\xff");
+	k
+}
+
+impl Categorise for Rl<'_> {
+	fn categorise(&self) -> Category {
+		Category::Role
+	}
+}
+
+impl<'a> Rl<'a> {
+	pub fn new(role: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'r',
+			_c: b'l',
+			role: Cow::Borrowed(role),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		#[rustfmt::skip]
+		let val = Rl::new("testrole");
+		let enc = Rl::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/!rltestrole\x00");
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix();
+		assert_eq!(val, b"/!rl\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix();
+		assert_eq!(val, b"/!rl\xff");
+	}
+}
@@ -61,6 +61,12 @@ pub trait Transaction: requirements::TransactionRequirements {
 	/// Returns if the transaction supports scanning in reverse.
 	fn supports_reverse_scan(&self) -> bool;
 
+	/// Returns if the versionstamps of changefeed entries are only assigned
+	/// by the storage engine when the transaction is committed.
+	fn assigns_versionstamps_on_commit(&self) -> bool {
+		false
+	}
+
 	/// Get the name of the transaction type.
 	fn kind(&self) -> &'static str;
 
@@ -21,6 +21,16 @@ pub(crate) enum Key {
 	Lvs(NamespaceId, DatabaseId, String, Uuid),
 	/// A cache key for live queries version (on a table)
 	Lvv(NamespaceId, DatabaseId, String),
+	/// A cache key for compiled module bytecode (on a database)
+	Mdc(NamespaceId, DatabaseId, String, [u8; 32]),
+	/// A cache key for a parsed machine learning model (on a database)
+	Mlm(NamespaceId, DatabaseId, String, String, String),
+	/// A cache key for a loaded embedding model (in a bucket)
+	#[cfg(feature = "ml")]
+	Mle(NamespaceId, DatabaseId, String, String, i64),
+	/// A cache key for a loaded embedding model (on a database)
+	#[cfg(feature = "ml")]
+	Mld(NamespaceId, DatabaseId, String, String, String),
 }
 
 impl<'a> From<Lookup<'a>> for Key {
@@ -34,6 +44,14 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Ixs(a, b, c, d) => Key::Ixs(a, b, c.to_string(), d),
 			Lookup::Lvs(a, b, c, d) => Key::Lvs(a, b, c.to_string(), d),
 			Lookup::Lvv(a, b, c) => Key::Lvv(a, b, c.to_string()),
+			Lookup::Mdc(a, b, c, d) => Key::Mdc(a, b, c.to_string(), d),
+			Lookup::Mlm(a, b, c, d, e) => {
+				Key::Mlm(a, b, c.to_string(), d.to_string(), e.to_string())
+			}
+			#[cfg(feature = "ml")]
+			Lookup::Mle(a, b, c, d, e) => Key::Mle(a, b, c.to_string(), d.to_string(), e),
+			#[cfg(feature = "ml")]
+			Lookup::Mld(a, b, c, d, e) => Key::Mld(a, b, c.to_string(), d.to_string(), e.to_string()),
 		}
 	}
 }
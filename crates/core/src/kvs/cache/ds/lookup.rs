@@ -22,6 +22,16 @@ pub(crate) enum Lookup<'a> {
 	Lvs(NamespaceId, DatabaseId, &'a str, Uuid),
 	/// A cache key for live queries version (on a table)
 	Lvv(NamespaceId, DatabaseId, &'a str),
+	/// A cache key for compiled module bytecode (on a database)
+	Mdc(NamespaceId, DatabaseId, &'a str, [u8; 32]),
+	/// A cache key for a parsed machine learning model (on a database)
+	Mlm(NamespaceId, DatabaseId, &'a str, &'a str, &'a str),
+	/// A cache key for a loaded embedding model (in a bucket)
+	#[cfg(feature = "ml")]
+	Mle(NamespaceId, DatabaseId, &'a str, &'a str, i64),
+	/// A cache key for a loaded embedding model (on a database)
+	#[cfg(feature = "ml")]
+	Mld(NamespaceId, DatabaseId, &'a str, &'a str, &'a str),
 }
 
 impl Equivalent<Key> for Lookup<'_> {
@@ -36,6 +46,12 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Ixs(la, lb, lc, ld), Key::Ixs(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Lvs(la, lb, lc, ld), Key::Lvs(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Lvv(la, lb, lc), Key::Lvv(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Mdc(la, lb, lc, ld), Key::Mdc(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
+			(Self::Mlm(la, lb, lc, ld, le), Key::Mlm(ka, kb, kc, kd, ke)) => la == ka && lb == kb && lc == kc && ld == kd && le == ke,
+			#[cfg(feature = "ml")]
+			(Self::Mle(la, lb, lc, ld, le), Key::Mle(ka, kb, kc, kd, ke)) => la == ka && lb == kb && lc == kc && ld == kd && le == ke,
+			#[cfg(feature = "ml")]
+			(Self::Mld(la, lb, lc, ld, le), Key::Mld(ka, kb, kc, kd, ke)) => la == ka && lb == kb && lc == kc && ld == kd && le == ke,
 			_ => false,
 		}
 	}
@@ -46,6 +46,28 @@ impl DatastoreCache {
 		self.cache.remove(&key);
 	}
 
+	/// Clear the compiled bytecode for a module
+	pub(crate) fn clear_md(&self, ns: NamespaceId, db: DatabaseId, md: &str, checksum: [u8; 32]) {
+		let key = Lookup::Mdc(ns, db, md, checksum);
+		self.cache.remove(&key);
+	}
+
+	/// Clear the parsed model, and the loaded embedding model, for a machine
+	/// learning model
+	pub(crate) fn clear_ml(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		ml: &str,
+		version: &str,
+		hash: &str,
+	) {
+		let key = Lookup::Mlm(ns, db, ml, version, hash);
+		self.cache.remove(&key);
+		#[cfg(feature = "ml")]
+		self.cache.remove(&Lookup::Mld(ns, db, ml, version, hash));
+	}
+
 	/// Clear all items from the datastore cache
 	pub(crate) fn clear(&self) {
 		self.cache.clear();
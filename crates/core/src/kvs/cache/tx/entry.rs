@@ -17,6 +17,8 @@ pub(crate) enum Entry {
 	Nds(Arc<[Node]>),
 	/// A slice of DefineUserStatement specified at the root.
 	Rus(Arc<[catalog::UserDefinition]>),
+	/// A slice of DefineRoleStatement specified at the root.
+	Rrs(Arc<[catalog::RoleDefinition]>),
 	/// A slice of DefineAccessStatement specified at the root.
 	Ras(Arc<[catalog::AccessDefinition]>),
 	/// A slice of AccessGrant specified at the root.
@@ -25,6 +27,8 @@ pub(crate) enum Entry {
 	Nss(Arc<[catalog::NamespaceDefinition]>),
 	/// A slice of DefineUserStatement specified on a namespace.
 	Nus(Arc<[catalog::UserDefinition]>),
+	/// A slice of DefineRoleStatement specified on a namespace.
+	Nrs(Arc<[catalog::RoleDefinition]>),
 	/// A slice of DefineAccessStatement specified on a namespace.
 	Nas(Arc<[catalog::AccessDefinition]>),
 	/// A slice of AccessGrant specified at on a namespace.
@@ -43,6 +47,8 @@ pub(crate) enum Entry {
 	Dag(Arc<[catalog::AccessGrant]>),
 	/// A slice of DefineUserStatement specified on a database.
 	Dus(Arc<[catalog::UserDefinition]>),
+	/// A slice of DefineRoleStatement specified on a database.
+	Drs(Arc<[catalog::RoleDefinition]>),
 	/// A slice of DefineFunctionStatement specified on a database.
 	Fcs(Arc<[catalog::FunctionDefinition]>),
 	/// A slice of TableDefinition specified on a database.
@@ -51,6 +57,8 @@ pub(crate) enum Entry {
 	Mls(Arc<[catalog::MlModelDefinition]>),
 	/// A slice of DefineConfigStatement specified on a database.
 	Cgs(Arc<[catalog::ConfigDefinition]>),
+	/// A slice of DefineModuleStatement specified on a database.
+	Mds(Arc<[catalog::ModuleDefinition]>),
 	/// A slice of DefineParamStatement specified on a database.
 	Pas(Arc<[catalog::ParamDefinition]>),
 	/// A slice of DefineSequenceStatement specified on a namespace.
@@ -97,6 +105,14 @@ impl Entry {
 			_ => fail!("Unable to convert type into Entry::Rus"),
 		}
 	}
+	/// Converts this cache entry into a slice of [`catalog::RoleDefinition`].
+	/// This panics if called on a cache entry that is not an [`Entry::Rrs`].
+	pub(crate) fn try_into_rrs(self) -> Result<Arc<[catalog::RoleDefinition]>> {
+		match self {
+			Entry::Rrs(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Rrs"),
+		}
+	}
 	/// Converts this cache entry into a slice of [`catalog::AccessDefinition`].
 	/// This panics if called on a cache entry that is not an [`Entry::Ras`].
 	pub(crate) fn try_into_ras(self) -> Result<Arc<[catalog::AccessDefinition]>> {
@@ -145,6 +161,14 @@ impl Entry {
 			_ => fail!("Unable to convert type into Entry::Nus"),
 		}
 	}
+	/// Converts this cache entry into a slice of [`catalog::RoleDefinition`].
+	/// This panics if called on a cache entry that is not an [`Entry::Nrs`].
+	pub(crate) fn try_into_nrs(self) -> Result<Arc<[catalog::RoleDefinition]>> {
+		match self {
+			Entry::Nrs(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Nrs"),
+		}
+	}
 	/// Converts this cache entry into a slice of [`catalog::DatabaseDefinition`].
 	/// This panics if called on a cache entry that is not an [`Entry::Dbs`].
 	pub(crate) fn try_into_dbs(self) -> Result<Arc<[catalog::DatabaseDefinition]>> {
@@ -177,6 +201,14 @@ impl Entry {
 			_ => fail!("Unable to convert type into Entry::Dus"),
 		}
 	}
+	/// Converts this cache entry into a slice of [`catalog::RoleDefinition`].
+	/// This panics if called on a cache entry that is not an [`Entry::Drs`].
+	pub(crate) fn try_into_drs(self) -> Result<Arc<[catalog::RoleDefinition]>> {
+		match self {
+			Entry::Drs(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Drs"),
+		}
+	}
 	/// Converts this cache entry into a slice of [`ApiDefinition`].
 	/// This panics if called on a cache entry that is not an [`Entry::Aps`].
 	pub(crate) fn try_into_aps(self) -> Result<Arc<[catalog::ApiDefinition]>> {
@@ -218,6 +250,14 @@ impl Entry {
 			_ => fail!("Unable to convert type into Entry::Fcs"),
 		}
 	}
+	/// Converts this cache entry into a slice of [`catalog::ModuleDefinition`].
+	/// This panics if called on a cache entry that is not an [`Entry::Mds`].
+	pub(crate) fn try_into_mds(self) -> Result<Arc<[catalog::ModuleDefinition]>> {
+		match self {
+			Entry::Mds(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Mds"),
+		}
+	}
 	/// Converts this cache entry into a slice of [`DefineParamStatement`].
 	/// This panics if called on a cache entry that is not an [`Entry::Pas`].
 	pub(crate) fn try_into_pas(self) -> Result<Arc<[catalog::ParamDefinition]>> {
@@ -10,6 +10,8 @@ pub(crate) enum Key {
 	Nds,
 	/// A cache key for root users
 	Rus,
+	/// A cache key for root roles
+	Rrs,
 	/// A cache key for root accesses
 	Ras,
 	/// A cache key for root access grants
@@ -18,6 +20,8 @@ pub(crate) enum Key {
 	Nss,
 	/// A cache key for namespace users
 	Nus(NamespaceId),
+	/// A cache key for namespace roles
+	Nrs(NamespaceId),
 	/// A cache key for namespace accesses
 	Nas(NamespaceId),
 	/// A cache key for namespace access grants
@@ -26,6 +30,8 @@ pub(crate) enum Key {
 	Dbs(NamespaceId),
 	/// A cache key for database users
 	Dus(NamespaceId, DatabaseId),
+	/// A cache key for database roles
+	Drs(NamespaceId, DatabaseId),
 	/// A cache key for database accesses
 	Das(NamespaceId, DatabaseId),
 	/// A cache key for database access grants
@@ -42,6 +48,8 @@ pub(crate) enum Key {
 	Mls(NamespaceId, DatabaseId),
 	/// A cache key for configs (on a database)
 	Cgs(NamespaceId, DatabaseId),
+	/// A cache key for modules (on a database)
+	Mds(NamespaceId, DatabaseId),
 	/// A cache key for parameters (on a database)
 	Pas(NamespaceId, DatabaseId),
 	/// A cache key for tables
@@ -62,6 +70,8 @@ pub(crate) enum Key {
 	Nd(Uuid),
 	/// A cache key for a root user
 	Ru(String),
+	/// A cache key for a root role
+	Rr(String),
 	/// A cache key for a root access
 	Ra(String),
 	/// A cache key for a root access grant
@@ -70,6 +80,8 @@ pub(crate) enum Key {
 	NsByName(String),
 	/// A cache key for a namespace user
 	Nu(NamespaceId, String),
+	/// A cache key for a namespace role
+	Nr(NamespaceId, String),
 	/// A cache key for a namespace access
 	Na(NamespaceId, String),
 	/// A cache key for a namespace access grant
@@ -78,6 +90,8 @@ pub(crate) enum Key {
 	DbByName(String, String),
 	/// A cache key for a database user
 	Du(NamespaceId, DatabaseId, String),
+	/// A cache key for a database role
+	Dr(NamespaceId, DatabaseId, String),
 	/// A cache key for a database access
 	Da(NamespaceId, DatabaseId, String),
 	/// A cache key for a database access grant
@@ -94,6 +108,8 @@ pub(crate) enum Key {
 	Ml(NamespaceId, DatabaseId, String, String),
 	/// A cache key for a config (on a database)
 	Cg(NamespaceId, DatabaseId, String),
+	/// A cache key for a module (on a database)
+	Md(NamespaceId, DatabaseId, String),
 	/// A cache key for a parameter (on a database)
 	Pa(NamespaceId, DatabaseId, String),
 	/// A cache key for a sequence (on a database)
@@ -119,14 +135,17 @@ impl<'a> From<Lookup<'a>> for Key {
 			//
 			Lookup::Nds => Key::Nds,
 			Lookup::Rus => Key::Rus,
+			Lookup::Rrs => Key::Rrs,
 			Lookup::Ras => Key::Ras,
 			Lookup::Rgs(a) => Key::Rgs(a.to_string()),
 			Lookup::Nss => Key::Nss,
 			Lookup::Nus(a) => Key::Nus(a),
+			Lookup::Nrs(a) => Key::Nrs(a),
 			Lookup::Nas(a) => Key::Nas(a),
 			Lookup::Ngs(a, b) => Key::Ngs(a, b.to_string()),
 			Lookup::Dbs(a) => Key::Dbs(a),
 			Lookup::Dus(a, b) => Key::Dus(a, b),
+			Lookup::Drs(a, b) => Key::Drs(a, b),
 			Lookup::Das(a, b) => Key::Das(a, b),
 			Lookup::Dgs(a, b, c) => Key::Dgs(a, b, c.to_string()),
 			Lookup::Aps(a, b) => Key::Aps(a, b),
@@ -135,6 +154,7 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Fcs(a, b) => Key::Fcs(a, b),
 			Lookup::Mls(a, b) => Key::Mls(a, b),
 			Lookup::Cgs(a, b) => Key::Cgs(a, b),
+			Lookup::Mds(a, b) => Key::Mds(a, b),
 			Lookup::Pas(a, b) => Key::Pas(a, b),
 			Lookup::Sqs(a, b) => Key::Sqs(a, b),
 			Lookup::Tbs(a, b) => Key::Tbs(a, b),
@@ -146,14 +166,17 @@ impl<'a> From<Lookup<'a>> for Key {
 			//
 			Lookup::Nd(a) => Key::Nd(a),
 			Lookup::Ru(a) => Key::Ru(a.to_string()),
+			Lookup::Rr(a) => Key::Rr(a.to_string()),
 			Lookup::Ra(a) => Key::Ra(a.to_string()),
 			Lookup::Rg(a, b) => Key::Rg(a.to_string(), b.to_string()),
 			Lookup::NsByName(a) => Key::NsByName(a.to_string()),
 			Lookup::Nu(a, b) => Key::Nu(a, b.to_string()),
+			Lookup::Nr(a, b) => Key::Nr(a, b.to_string()),
 			Lookup::Na(a, b) => Key::Na(a, b.to_string()),
 			Lookup::Ng(a, b, c) => Key::Ng(a, b.to_string(), c.to_string()),
 			Lookup::DbByName(a, b) => Key::DbByName(a.to_string(), b.to_string()),
 			Lookup::Du(a, b, c) => Key::Du(a, b, c.to_string()),
+			Lookup::Dr(a, b, c) => Key::Dr(a, b, c.to_string()),
 			Lookup::Da(a, b, c) => Key::Da(a, b, c.to_string()),
 			Lookup::Dg(a, b, c, d) => Key::Dg(a, b, c.to_string(), d.to_string()),
 			Lookup::Ap(a, b, c) => Key::Ap(a, b, c.to_string()),
@@ -162,6 +185,7 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Fc(a, b, c) => Key::Fc(a, b, c.to_string()),
 			Lookup::Ml(a, b, c, d) => Key::Ml(a, b, c.to_string(), d.to_string()),
 			Lookup::Cg(a, b, c) => Key::Cg(a, b, c.to_string()),
+			Lookup::Md(a, b, c) => Key::Md(a, b, c.to_string()),
 			Lookup::Pa(a, b, c) => Key::Pa(a, b, c.to_string()),
 			Lookup::Sq(a, b,c) => Key::Sq(a, b, c.to_string()),
 			Lookup::Tb(a, b, c) => Key::Tb(a, b, c.to_string()),
@@ -11,6 +11,8 @@ pub(crate) enum Lookup<'a> {
 	Nds,
 	/// A cache key for root users
 	Rus,
+	/// A cache key for root roles
+	Rrs,
 	/// A cache key for root accesses
 	Ras,
 	/// A cache key for root access grants
@@ -19,6 +21,8 @@ pub(crate) enum Lookup<'a> {
 	Nss,
 	/// A cache key for namespace users
 	Nus(NamespaceId),
+	/// A cache key for namespace roles
+	Nrs(NamespaceId),
 	/// A cache key for namespace accesses
 	Nas(NamespaceId),
 	/// A cache key for namespace access grants
@@ -27,6 +31,8 @@ pub(crate) enum Lookup<'a> {
 	Dbs(NamespaceId),
 	/// A cache key for database users
 	Dus(NamespaceId, DatabaseId),
+	/// A cache key for database roles
+	Drs(NamespaceId, DatabaseId),
 	/// A cache key for database accesses
 	Das(NamespaceId, DatabaseId),
 	/// A cache key for database access grants
@@ -43,6 +49,8 @@ pub(crate) enum Lookup<'a> {
 	Mls(NamespaceId, DatabaseId),
 	/// A cache key for configs (on a database)
 	Cgs(NamespaceId, DatabaseId),
+	/// A cache key for modules (on a database)
+	Mds(NamespaceId, DatabaseId),
 	/// A cache key for parameters (on a database)
 	Pas(NamespaceId, DatabaseId),
 	/// A cache key for sequences (on a database)
@@ -63,6 +71,8 @@ pub(crate) enum Lookup<'a> {
 	Nd(Uuid),
 	/// A cache key for a root user
 	Ru(&'a str),
+	/// A cache key for a root role
+	Rr(&'a str),
 	/// A cache key for a root access
 	Ra(&'a str),
 	/// A cache key for a root access grant
@@ -71,6 +81,8 @@ pub(crate) enum Lookup<'a> {
 	NsByName(&'a str),
 	/// A cache key for a namespace user
 	Nu(NamespaceId, &'a str),
+	/// A cache key for a namespace role
+	Nr(NamespaceId, &'a str),
 	/// A cache key for a namespace access
 	Na(NamespaceId, &'a str),
 	/// A cache key for a namespace access grant
@@ -79,6 +91,8 @@ pub(crate) enum Lookup<'a> {
 	DbByName(&'a str, &'a str),
 	/// A cache key for a database user
 	Du(NamespaceId, DatabaseId, &'a str),
+	/// A cache key for a database role
+	Dr(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for a database access
 	Da(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for a database access grant
@@ -95,6 +109,8 @@ pub(crate) enum Lookup<'a> {
 	Ml(NamespaceId, DatabaseId, &'a str, &'a str),
 	/// A cache key for a config (on a database)
 	Cg(NamespaceId, DatabaseId, &'a str),
+	/// A cache key for a module (on a database)
+	Md(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for a parameter (on a database)
 	Pa(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for a sequence (on a database)
@@ -120,14 +136,17 @@ impl Equivalent<Key> for Lookup<'_> {
 			//
 			(Self::Nds, Key::Nds) => true,
 			(Self::Rus, Key::Rus) => true,
+			(Self::Rrs, Key::Rrs) => true,
 			(Self::Ras, Key::Ras) => true,
 			(Self::Rgs(la), Key::Rgs(ka)) => la == ka,
 			(Self::Nss, Key::Nss) => true,
 			(Self::Nus(la), Key::Nus(ka)) => la == ka,
+			(Self::Nrs(la), Key::Nrs(ka)) => la == ka,
 			(Self::Nas(la), Key::Nas(ka)) => la == ka,
 			(Self::Ngs(la, lb), Key::Ngs(ka, kb)) => la == ka && lb == kb,
 			(Self::Dbs(la), Key::Dbs(ka)) => la == ka,
 			(Self::Dus(la, lb), Key::Dus(ka, kb)) => la == ka && lb == kb,
+			(Self::Drs(la, lb), Key::Drs(ka, kb)) => la == ka && lb == kb,
 			(Self::Das(la, lb), Key::Das(ka, kb)) => la == ka && lb == kb,
 			(Self::Dgs(la, lb, lc), Key::Dgs(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Aps(la, lb), Key::Aps(ka, kb)) => la == ka && lb == kb,
@@ -136,6 +155,7 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Fcs(la, lb), Key::Fcs(ka, kb)) => la == ka && lb == kb,
 			(Self::Mls(la, lb), Key::Mls(ka, kb)) => la == ka && lb == kb,
 			(Self::Cgs(la, lb), Key::Cgs(ka, kb)) => la == ka && lb == kb,
+			(Self::Mds(la, lb), Key::Mds(ka, kb)) => la == ka && lb == kb,
 			(Self::Pas(la, lb), Key::Pas(ka, kb)) => la == ka && lb == kb,
 			(Self::Sqs(la, lb), Key::Sqs(ka, kb)) => la == ka && lb == kb,
 			(Self::Tbs(la, lb), Key::Tbs(ka, kb)) => la == ka && lb == kb,
@@ -147,14 +167,17 @@ impl Equivalent<Key> for Lookup<'_> {
 			//
 			(Self::Nd(la), Key::Nd(ka)) => la == ka,
 			(Self::Ru(la), Key::Ru(ka)) => la == ka,
+			(Self::Rr(la), Key::Rr(ka)) => la == ka,
 			(Self::Ra(la), Key::Ra(ka)) => la == ka,
 			(Self::Rg(la, lb), Key::Rg(ka, kb)) => la == ka && lb == kb,
 			(Self::NsByName(la), Key::NsByName(ka)) => la == ka,
 			(Self::Nu(la, lb), Key::Nu(ka, kb)) => la == ka && lb == kb,
+			(Self::Nr(la, lb), Key::Nr(ka, kb)) => la == ka && lb == kb,
 			(Self::Na(la, lb), Key::Na(ka, kb)) => la == ka && lb == kb,
 			(Self::Ng(la, lb, lc), Key::Ng(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::DbByName(la, lb), Key::DbByName(ka, kb)) => la == ka && lb == kb,
 			(Self::Du(la, lb, lc), Key::Du(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Dr(la, lb, lc), Key::Dr(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Da(la, lb, lc), Key::Da(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Dg(la, lb, lc, ld), Key::Dg(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Ap(la, lb, lc), Key::Ap(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
@@ -163,6 +186,7 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Fc(la, lb, lc), Key::Fc(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Ml(la, lb, lc, ld), Key::Ml(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Cg(la, lb, lc), Key::Cg(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Md(la, lb, lc), Key::Md(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Pa(la, lb, lc), Key::Pa(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Sq(la, lb, lc), Key::Sq(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Tb(la, lb, lc), Key::Tb(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
@@ -186,6 +210,7 @@ mod tests {
 	#[rstest]
 	#[case(Lookup::Nds, Key::Nds, true)]
 	#[case(Lookup::Rus, Key::Rus, true)]
+	#[case(Lookup::Rrs, Key::Rrs, true)]
 	#[case(Lookup::Ras, Key::Ras, true)]
 	#[case(Lookup::Rgs("test"), Key::Rgs("test".to_string()), true)]
 	#[case(Lookup::Nss, Key::Nss, true)]
@@ -234,6 +259,11 @@ mod tests {
 		Key::Cgs(NamespaceId(1), DatabaseId(1)),
 		true
 	)]
+	#[case(
+		Lookup::Mds(NamespaceId(1), DatabaseId(1)),
+		Key::Mds(NamespaceId(1), DatabaseId(1)),
+		true
+	)]
 	#[case(
 		Lookup::Pas(NamespaceId(1), DatabaseId(1)),
 		Key::Pas(NamespaceId(1), DatabaseId(1)),
@@ -251,6 +281,7 @@ mod tests {
 	#[case(Lookup::Lvs(NamespaceId(1), DatabaseId(1), "test"), Key::Lvs(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Nd(Uuid::from_u128(1)), Key::Nd(Uuid::from_u128(1)), true)]
 	#[case(Lookup::Ru("test"), Key::Ru("test".to_string()), true)]
+	#[case(Lookup::Rr("test"), Key::Rr("test".to_string()), true)]
 	#[case(Lookup::Ra("test"), Key::Ra("test".to_string()), true)]
 	#[case(Lookup::Rg("test", "test"), Key::Rg("test".to_string(), "test".to_string()), true)]
 	#[case(Lookup::NsByName("test"), Key::NsByName("test".to_string()), true)]
@@ -267,6 +298,7 @@ mod tests {
 	#[case(Lookup::Fc(NamespaceId(1), DatabaseId(1), "test"), Key::Fc(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Ml(NamespaceId(1), DatabaseId(1), "test", "test"), Key::Ml(NamespaceId(1), DatabaseId(1), "test".to_string(), "test".to_string()), true)]
 	#[case(Lookup::Cg(NamespaceId(1), DatabaseId(1), "test"), Key::Cg(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
+	#[case(Lookup::Md(NamespaceId(1), DatabaseId(1), "test"), Key::Md(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Pa(NamespaceId(1), DatabaseId(1), "test"), Key::Pa(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Sq(NamespaceId(1), DatabaseId(1), "test"), Key::Sq(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Tb(NamespaceId(1), DatabaseId(1), "test"), Key::Tb(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
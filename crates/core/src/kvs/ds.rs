@@ -31,7 +31,8 @@ use super::version::MajorVersion;
 use super::{api, export};
 use crate::api::body::ApiBody;
 use crate::api::invocation::ApiInvocation;
-use crate::api::response::{ApiResponse, ResponseInstruction};
+use crate::api::openapi;
+use crate::api::response::{ApiResponse, ApiStream, ResponseInstruction};
 use crate::buc::BucketConnections;
 use crate::catalog::providers::{
 	ApiProvider, CatalogProvider, DatabaseProvider, NamespaceProvider, TableProvider, UserProvider,
@@ -50,7 +51,7 @@ use crate::expr::statements::DefineUserStatement;
 use crate::expr::{Base, Expr, FlowResultExt as _, LogicalPlan};
 #[cfg(feature = "jwks")]
 use crate::iam::jwks::JwksCache;
-use crate::iam::{Action, Auth, Error as IamError, Resource, Role};
+use crate::iam::{Action, Auth, Error as IamError, Resource, ResourceKind, Role};
 use crate::idx::IndexKeyBase;
 use crate::idx::ft::fulltext::FullTextIndex;
 use crate::idx::index::IndexOperation;
@@ -166,6 +167,7 @@ impl TransactionFactory {
 				inner,
 				stash: super::stash::Stash::default(),
 				cf: cf::Writer::new(),
+				vs: Default::default(),
 			},
 		))
 	}
@@ -201,6 +203,13 @@ pub trait TransactionBuilder: TransactionBuilderRequirements {
 
 	/// Perform any backend-specific shutdown/cleanup.
 	async fn shutdown(&self) -> Result<()>;
+
+	/// Write a snapshot of the datastore, if the backend supports it.
+	async fn snapshot(&self) -> Result<()> {
+		bail!(Error::Unimplemented(format!(
+			"Snapshots are not supported by the {self} storage engine"
+		)))
+	}
 }
 
 #[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
@@ -291,7 +300,7 @@ impl TransactionBuilderFactory for DatastoreFlavor {
 				#[cfg(feature = "kv-mem")]
 				{
 					// Initialise the storage engine
-					let v = super::mem::Datastore::new().await.map(Self::Mem)?;
+					let v = super::mem::Datastore::new(None).await.map(Self::Mem)?;
 					let c = clock.unwrap_or_else(|| Arc::new(SizedClock::system()));
 					info!(target: TARGET, "Started kvs store in {flavour}");
 					(v, c)
@@ -299,6 +308,20 @@ impl TransactionBuilderFactory for DatastoreFlavor {
 				#[cfg(not(feature = "kv-mem"))]
 				bail!(Error::Ds("Cannot connect to the `memory` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
 			}
+			// Initiate an in-memory datastore persisted to disk
+			(flavour @ "mem", path) => {
+				#[cfg(feature = "kv-mem")]
+				{
+					// Initialise the storage engine
+					let path = (!path.is_empty()).then_some(path.as_str());
+					let v = super::mem::Datastore::new(path).await.map(Self::Mem)?;
+					let c = clock.unwrap_or_else(|| Arc::new(SizedClock::system()));
+					info!(target: TARGET, "Started {flavour} kvs store");
+					(v, c)
+				}
+				#[cfg(not(feature = "kv-mem"))]
+				bail!(Error::Ds("Cannot connect to the `memory` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
 			// Initiate a File (RocksDB) datastore
 			(flavour @ "file", path) => {
 				#[cfg(feature = "kv-rocksdb")]
@@ -311,7 +334,8 @@ impl TransactionBuilderFactory for DatastoreFlavor {
 						"file:// is deprecated, please use surrealkv:// or surrealkv+versioned:// or rocksdb://"
 					);
 
-					let v = super::rocksdb::Datastore::new(&path).await.map(Self::RocksDB)?;
+					let v =
+						super::rocksdb::Datastore::new(&path, false).await.map(Self::RocksDB)?;
 					let c = clock.unwrap_or_else(|| Arc::new(SizedClock::system()));
 					info!(target: TARGET, "Started {flavour} kvs store");
 					(v, c)
@@ -319,6 +343,21 @@ impl TransactionBuilderFactory for DatastoreFlavor {
 				#[cfg(not(feature = "kv-rocksdb"))]
 				bail!(Error::Ds("Cannot connect to the `rocksdb` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
 			}
+			// Initiate a RocksDB versioned datastore
+			(flavour @ "rocksdb+versioned", path) => {
+				#[cfg(feature = "kv-rocksdb")]
+				{
+					// Create a new blocking threadpool
+					super::threadpool::initialise();
+					// Initialise the storage engine
+					let v = super::rocksdb::Datastore::new(&path, true).await.map(Self::RocksDB)?;
+					let c = clock.unwrap_or_else(|| Arc::new(SizedClock::system()));
+					info!(target: TARGET, "Started {flavour} kvs store with versions enabled");
+					(v, c)
+				}
+				#[cfg(not(feature = "kv-rocksdb"))]
+				bail!(Error::Ds("Cannot connect to the `rocksdb` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
 			// Initiate a RocksDB datastore
 			(flavour @ "rocksdb", path) => {
 				#[cfg(feature = "kv-rocksdb")]
@@ -327,7 +366,8 @@ impl TransactionBuilderFactory for DatastoreFlavor {
 					super::threadpool::initialise();
 					// Initialise the storage engine
 
-					let v = super::rocksdb::Datastore::new(&path).await.map(Self::RocksDB)?;
+					let v =
+						super::rocksdb::Datastore::new(&path, false).await.map(Self::RocksDB)?;
 					let c = clock.unwrap_or_else(|| Arc::new(SizedClock::system()));
 					info!(target: TARGET, "Started {flavour} kvs store");
 					(v, c)
@@ -421,8 +461,10 @@ impl TransactionBuilderFactory for DatastoreFlavor {
 	fn path_valid(v: &str) -> Result<String> {
 		match v {
 			"memory" => Ok(v.to_string()),
+			v if v.starts_with("mem:") => Ok(v.to_string()),
 			v if v.starts_with("file:") => Ok(v.to_string()),
 			v if v.starts_with("rocksdb:") => Ok(v.to_string()),
+			v if v.starts_with("rocksdb+versioned:") => Ok(v.to_string()),
 			v if v.starts_with("surrealkv:") => Ok(v.to_string()),
 			v if v.starts_with("surrealkv+versioned:") => Ok(v.to_string()),
 			v if v.starts_with("tikv:") => Ok(v.to_string()),
@@ -504,6 +546,17 @@ impl TransactionBuilder for DatastoreFlavor {
 			_ => unreachable!(),
 		}
 	}
+
+	async fn snapshot(&self) -> Result<()> {
+		match self {
+			#[cfg(feature = "kv-mem")]
+			Self::Mem(v) => v.snapshot().await,
+			#[allow(unreachable_patterns)]
+			_ => bail!(Error::Unimplemented(format!(
+				"Snapshots are not supported by the {self} storage engine"
+			))),
+		}
+	}
 }
 
 impl Display for DatastoreFlavor {
@@ -644,6 +697,36 @@ impl Datastore {
 		}
 	}
 
+	/// Create a new datastore node with the same persistent data (inner),
+	/// and the same configuration, with a flushed cache and a separate
+	/// notification channel. Simulating another node joining the cluster
+	pub fn new_node(&self, id: Uuid) -> Self {
+		Self {
+			id,
+			strict: self.strict,
+			auth_enabled: self.auth_enabled,
+			query_timeout: self.query_timeout,
+			slow_log: self.slow_log.clone(),
+			transaction_timeout: self.transaction_timeout,
+			capabilities: self.capabilities.clone(),
+			notification_channel: self
+				.notification_channel
+				.as_ref()
+				.map(|_| async_channel::bounded(LQ_CHANNEL_SIZE)),
+			index_stores: Default::default(),
+			#[cfg(not(target_family = "wasm"))]
+			index_builder: IndexBuilder::new(self.transaction_factory.clone()),
+			#[cfg(feature = "jwks")]
+			jwks_cache: Arc::new(Default::default()),
+			#[cfg(storage)]
+			temporary_directory: self.temporary_directory.clone(),
+			cache: Arc::new(DatastoreCache::new()),
+			buckets: Arc::new(DashMap::new()),
+			sequences: Sequences::new(self.transaction_factory.clone()),
+			transaction_factory: self.transaction_factory.clone(),
+		}
+	}
+
 	/// Specify whether this Datastore should run in strict mode
 	pub fn with_node_id(mut self, id: Uuid) -> Self {
 		self.id = id;
@@ -905,6 +988,17 @@ impl Datastore {
 		Ok(())
 	}
 
+	/// Run the background task to deliver notifications queued for this node
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn node_notifications_deliver(&self) -> Result<()> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Delivering queued notifications");
+		// Deliver notifications from other nodes
+		self.deliver_notifications().await?;
+		// Everything ok
+		Ok(())
+	}
+
 	/// Performs changefeed garbage collection as a background task.
 	///
 	/// This method is responsible for cleaning up old changefeed data across
@@ -1112,6 +1206,15 @@ impl Datastore {
 		self.transaction_factory.builder.shutdown().await
 	}
 
+	/// Write a snapshot of the datastore, if the storage engine supports it
+	///
+	/// This is supported by the in-memory storage engine, when it is started
+	/// with a directory for persistence, for example `mem://path/to/data`.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn snapshot(&self) -> Result<()> {
+		self.transaction_factory.builder.snapshot().await
+	}
+
 	/// Create a new transaction on this datastore
 	///
 	/// ```rust,no_run
@@ -1492,6 +1595,11 @@ impl Datastore {
 		self.notification_channel.as_ref().map(|v| v.1.clone())
 	}
 
+	/// Returns the sending side of the notification channel, if enabled
+	pub(crate) fn notification_sender(&self) -> Option<Sender<Notification>> {
+		self.notification_channel.as_ref().map(|v| v.0.clone())
+	}
+
 	/// Performs a database import from SQL
 	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
 	pub async fn import(&self, sql: &str, sess: &Session) -> Result<Vec<Response>> {
@@ -1538,11 +1646,24 @@ impl Datastore {
 		// Retrieve the provided NS and DB
 		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
 		// Create a new readonly transaction
-		let txn = self.transaction(Read, Optimistic).await?;
+		let txn = Arc::new(self.transaction(Read, Optimistic).await?);
+		// Masked fields are computed within the context of the session
+		let masks = match cfg.masks {
+			true => {
+				let mut ctx = self.setup_ctx()?;
+				ctx.set_transaction(Arc::clone(&txn));
+				ctx.attach_session(sess)?;
+				Some(export::MaskContext {
+					ctx: ctx.freeze(),
+					opt: self.setup_options(sess),
+				})
+			}
+			false => None,
+		};
 		// Return an async export job
 		Ok(async move {
 			// Process the export
-			txn.export(&ns, &db, cfg, chn).await?;
+			txn.export(&ns, &db, cfg, masks.as_ref(), chn).await?;
 			// Everything ok
 			Ok(())
 		})
@@ -1735,8 +1856,82 @@ impl Datastore {
 			tx.cancel().await?;
 		}
 
+		// Stream the records of a table, once the action has been committed
+		let mut res = res?;
+		if let Some((res, _)) = &mut res {
+			if let Some(ApiStream::Ndjson(Value::Table(tb))) = &res.stream {
+				let txn = self.transaction(Read, Optimistic).await?;
+				let rows = ApiStream::rows(txn, db.namespace_id, db.database_id, tb)?;
+				res.stream = Some(rows);
+			}
+		}
+
+		Ok(res)
+	}
+
+	/// Responds to a CORS preflight request for an API, using the CORS
+	/// policy configured for the requested method.
+	pub async fn invoke_api_preflight(
+		&self,
+		ns: &str,
+		db: &str,
+		path: &str,
+		session: &Session,
+		method: ApiMethod,
+		headers: HeaderMap,
+	) -> Result<Option<ApiResponse>> {
+		let tx = Arc::new(self.transaction(TransactionType::Read, LockType::Optimistic).await?);
+
+		let Some(def) = tx.get_db_by_name(ns, db).await? else {
+			tx.cancel().await?;
+			return Ok(None);
+		};
+
+		let apis = tx.all_db_apis(def.namespace_id, def.database_id).await?;
+		let segments: Vec<&str> = path.split('/').filter(|x| !x.is_empty()).collect();
+
+		let res = match ApiDefinition::find_definition(apis.as_ref(), segments, method) {
+			Some((api, params)) => {
+				let invocation = ApiInvocation {
+					params,
+					method,
+					headers,
+					query: BTreeMap::new(),
+				};
+
+				let opt = self.setup_options(session);
+
+				let mut ctx = self.setup_ctx()?;
+				ctx.set_transaction(Arc::clone(&tx));
+				ctx.attach_session(session)?;
+				let ctx = &ctx.freeze();
+
+				invocation.preflight(ctx, &opt, api).await
+			}
+			_ => Ok(None),
+		};
+
+		tx.cancel().await?;
+
 		res
 	}
+
+	/// Generates an OpenAPI document describing the APIs defined on a
+	/// database, for a session which is allowed to view those APIs.
+	pub async fn api_openapi_document(&self, sess: &Session, ns: &str, db: &str) -> Result<Value> {
+		// Check permissions before revealing whether the database exists
+		self.check(sess, Action::View, ResourceKind::Api.on_db(ns, db))?;
+		let tx = self.transaction(TransactionType::Read, LockType::Optimistic).await?;
+		let Some(def) = tx.get_db_by_name(ns, db).await? else {
+			tx.cancel().await?;
+			return Err(anyhow::anyhow!(Error::DbNotFound {
+				name: db.to_owned(),
+			}));
+		};
+		let apis = tx.all_db_apis(def.namespace_id, def.database_id).await;
+		tx.cancel().await?;
+		Ok(openapi::document(ns, db, apis?.as_ref()))
+	}
 }
 
 #[cfg(test)]
@@ -1791,6 +1986,120 @@ mod test {
 		)
 	}
 
+	#[tokio::test]
+	async fn test_export_masks() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = r#"
+			DEFINE TABLE user PERMISSIONS FULL;
+			DEFINE FIELD card ON user TYPE string MASK "****" + string::slice($value, 12) PERMISSIONS FOR select NONE;
+			DEFINE FIELD ssn ON user TYPE string MASK "***-**-****" PERMISSIONS FULL;
+			CREATE user:john SET name = "John", card = "4111222233334444", ssn = "123-45-6789";
+		"#;
+		for res in ds.execute(sql, &sess, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		let export = |masks: bool| {
+			let ds = &ds;
+			let sess = &sess;
+			async move {
+				let (tx, rx) = async_channel::unbounded();
+				let cfg = export::Config {
+					masks,
+					..Default::default()
+				};
+				ds.export_with_config(sess, tx, cfg).await.unwrap().await.unwrap();
+				let mut out = Vec::new();
+				while let Ok(bytes) = rx.try_recv() {
+					out.extend(bytes);
+				}
+				String::from_utf8(out).unwrap()
+			}
+		};
+		// Without masks the original values are exported
+		let out = export(false).await;
+		assert!(out.contains("'4111222233334444'"), "{out}");
+		assert!(out.contains("'123-45-6789'"), "{out}");
+		// With masks every masked field is replaced, even
+		// where the SELECT permission would be granted
+		let out = export(true).await;
+		assert!(out.contains("'****4444'"), "{out}");
+		assert!(out.contains("'***-**-****'"), "{out}");
+		assert!(!out.contains("4111222233334444"), "{out}");
+		assert!(!out.contains("123-45-6789"), "{out}");
+		assert!(out.contains("'John'"), "{out}");
+	}
+
+	#[tokio::test]
+	async fn test_mem_persistence_restores_data() {
+		let dir = temp_dir::TempDir::new().unwrap();
+		let path = format!("mem://{}", dir.path().display());
+		let sess = Session::owner().with_ns("test").with_db("test");
+		// Write some data, with a snapshot between the writes
+		let ds = Datastore::new(&path).await.unwrap();
+		for res in ds.execute("CREATE person:one SET name = 'Tobie'", &sess, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		ds.snapshot().await.unwrap();
+		let sql = "CREATE person:two SET name = 'Jaime'; UPDATE person:one SET age = 30";
+		for res in ds.execute(sql, &sess, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		drop(ds);
+		// Reopen the datastore and check the data was restored
+		let ds = Datastore::new(&path).await.unwrap();
+		let mut res = ds.execute("SELECT * FROM person", &sess, None).await.unwrap();
+		let val = res.remove(0).result.unwrap();
+		assert_eq!(
+			val.to_string(),
+			"[{ age: 30, id: person:one, name: 'Tobie' }, { id: person:two, name: 'Jaime' }]"
+		);
+	}
+
+	#[tokio::test]
+	async fn test_api_streams_table_records() {
+		use futures::TryStreamExt;
+
+		use crate::dbs::capabilities::{ExperimentalTarget, Targets};
+
+		let capabilities = Capabilities::default()
+			.with_experimental(Targets::Some([ExperimentalTarget::DefineApi].into()));
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(capabilities);
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = "
+			CREATE |person:0..1200|;
+			DEFINE API '/people' FOR get THEN {
+				RETURN { stream: 'ndjson', body: type::table('person') };
+			};
+		";
+		for res in ds.execute(sql, &sess, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		let body =
+			futures::stream::empty::<std::result::Result<Bytes, Box<dyn Display + Send + Sync>>>();
+		let (res, _) = ds
+			.invoke_api_handler(
+				"test",
+				"test",
+				"people",
+				&sess,
+				ApiMethod::Get,
+				HeaderMap::from_iter([(http::header::ACCEPT, "application/json".parse().unwrap())]),
+				BTreeMap::new(),
+				body,
+			)
+			.await
+			.unwrap()
+			.unwrap();
+		// The records are read in batches, once the action has completed
+		let Some(ApiStream::Rows(rows)) = res.stream else {
+			panic!("Expected a stream of records, found {:?}", res.stream);
+		};
+		let rows: Vec<Value> = rows.try_collect().await.unwrap();
+		assert_eq!(rows.len(), 1200);
+		assert_eq!(rows[0].to_string(), "{ id: person:0 }");
+	}
+
 	#[tokio::test]
 	pub async fn very_deep_query() -> Result<()> {
 		use reblessive::{Stack, Stk};
@@ -1939,4 +2248,26 @@ mod test {
 		//
 		Ok(())
 	}
+
+	#[tokio::test]
+	async fn test_api_openapi_document_permissions() {
+		let ds = Datastore::new("memory").await.unwrap().with_auth_enabled(true);
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute("DEFINE DATABASE test", &sess, None).await.unwrap();
+		// Anonymous sessions can not list the APIs of a database
+		let anon = Session::default().with_ns("test").with_db("test");
+		let err = ds.api_openapi_document(&anon, "test", "test").await.unwrap_err();
+		assert!(
+			matches!(err.downcast_ref(), Some(Error::IamError(IamError::NotAllowed { .. }))),
+			"{err}"
+		);
+		// Nor can they find out whether a database exists
+		let err = ds.api_openapi_document(&anon, "test", "missing").await.unwrap_err();
+		assert!(
+			matches!(err.downcast_ref(), Some(Error::IamError(IamError::NotAllowed { .. }))),
+			"{err}"
+		);
+		// Users which can view the database can list its APIs
+		ds.api_openapi_document(&sess, "test", "test").await.unwrap();
+	}
 }
@@ -1,19 +1,26 @@
 use std::fmt;
+use std::sync::Arc;
 
 use anyhow::Result;
 use async_channel::Sender;
 use chrono::TimeZone;
 use chrono::prelude::Utc;
+use reblessive::tree::TreeStack;
 
 use super::Transaction;
 use crate::catalog::providers::{
 	AuthorisationProvider, DatabaseProvider, TableProvider, UserProvider,
 };
-use crate::catalog::{DatabaseId, NamespaceId, TableDefinition};
+use crate::catalog::{DatabaseId, FieldDefinition, NamespaceId, TableDefinition};
 use crate::cnf::EXPORT_BATCH_SIZE;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::{CursorDoc, compute_mask};
 use crate::err::Error;
 use crate::expr::paths::{IN, OUT};
-use crate::expr::statements::define::{DefineAccessStatement, DefineUserStatement};
+use crate::expr::statements::define::{
+	DefineAccessStatement, DefineRoleStatement, DefineUserStatement,
+};
 use crate::expr::{Base, DefineAnalyzerStatement};
 use crate::key::record;
 use crate::kvs::KVValue;
@@ -32,6 +39,11 @@ pub struct Config {
 	pub versions: bool,
 	pub records: bool,
 	pub sequences: bool,
+	/// Whether to replace masked fields with their `MASK` clause. Unlike a
+	/// `SELECT`, the mask of every masked field is applied unconditionally,
+	/// without checking whether the `SELECT` permission of the field would
+	/// be granted to the session which performs the export.
+	pub masks: bool,
 }
 
 impl Default for Config {
@@ -46,6 +58,7 @@ impl Default for Config {
 			versions: false,
 			records: true,
 			sequences: true,
+			masks: false,
 		}
 	}
 }
@@ -80,6 +93,7 @@ impl Config {
 				bool_prop!(analyzers);
 				bool_prop!(versions);
 				bool_prop!(records);
+				bool_prop!(masks);
 
 				if let Some(v) = obj.get("tables") {
 					config.tables = v.try_into()?;
@@ -106,6 +120,7 @@ impl From<Config> for Value {
 			"versions" => config.versions.into(),
 			"records" => config.records.into(),
 			"sequences" => config.sequences.into(),
+			"masks" => config.masks.into(),
 			"tables" => match config.tables {
 				TableConfig::All => true.into(),
 				TableConfig::None => false.into(),
@@ -224,13 +239,53 @@ impl<F: fmt::Display> fmt::Display for InlineCommentDisplay<F> {
 	}
 }
 
+/// The context in which the `MASK` clauses of fields are computed when
+/// exporting with [`Config::masks`] enabled.
+pub(crate) struct MaskContext {
+	pub(crate) ctx: Context,
+	pub(crate) opt: Options,
+}
+
+impl MaskContext {
+	/// Replaces the masked fields of a record with their `MASK` clause
+	async fn apply(
+		&self,
+		k: &record::RecordKey<'_>,
+		mut record: Record,
+		fields: &[FieldDefinition],
+	) -> Result<Record> {
+		let rid = RecordId {
+			table: k.tb.to_string(),
+			key: k.id.clone(),
+		};
+		record.data.to_mut().def(&rid);
+		let doc = CursorDoc::new(Some(Arc::new(rid)), None, record.clone());
+		let mut stack = TreeStack::new();
+		for fd in fields.iter() {
+			let Some(mask) = &fd.mask else {
+				continue;
+			};
+			for idiom in doc.doc.as_ref().each(&fd.name).iter() {
+				let val = doc.doc.as_ref().pick(idiom);
+				let val = stack
+					.enter(|stk| compute_mask(stk, &self.ctx, &self.opt, mask, &doc, val))
+					.finish()
+					.await?;
+				record.data.to_mut().put(idiom, val);
+			}
+		}
+		Ok(record)
+	}
+}
+
 impl Transaction {
 	/// Writes the full database contents as binary SQL.
-	pub async fn export(
+	pub(crate) async fn export(
 		&self,
 		ns: &str,
 		db: &str,
 		cfg: Config,
+		masks: Option<&MaskContext>,
 		chn: Sender<Vec<u8>>,
 	) -> Result<()> {
 		let db = self.get_db_by_name(ns, db).await?.ok_or_else(|| {
@@ -239,10 +294,10 @@ impl Transaction {
 			})
 		})?;
 
-		// Output USERS, ACCESSES, PARAMS, FUNCTIONS, ANALYZERS
+		// Output ROLES, USERS, ACCESSES, PARAMS, FUNCTIONS, ANALYZERS
 		self.export_metadata(&cfg, &chn, db.namespace_id, db.database_id).await?;
 		// Output TABLES
-		self.export_tables(&cfg, &chn, db.namespace_id, db.database_id).await?;
+		self.export_tables(&cfg, masks, &chn, db.namespace_id, db.database_id).await?;
 		Ok(())
 	}
 
@@ -256,6 +311,17 @@ impl Transaction {
 		// Output OPTIONS
 		self.export_section("OPTION", ["OPTION IMPORT"].iter(), chn).await?;
 
+		// Output ROLES
+		if cfg.users {
+			let roles = self.all_db_roles(ns, db).await?;
+			self.export_section(
+				"ROLES",
+				roles.iter().map(|x| DefineRoleStatement::from_definition(Base::Db, x)),
+				chn,
+			)
+			.await?;
+		}
+
 		// Output USERS
 		if cfg.users {
 			let users = self.all_db_users(ns, db).await?;
@@ -286,6 +352,12 @@ impl Transaction {
 			self.export_section("PARAMS", params.iter(), chn).await?;
 		}
 
+		// Output MODULES
+		if cfg.functions {
+			let modules = self.all_db_modules(ns, db).await?;
+			self.export_section("MODULES", modules.iter(), chn).await?;
+		}
+
 		// Output FUNCTIONS
 		if cfg.functions {
 			let functions = self.all_db_functions(ns, db).await?;
@@ -341,6 +413,7 @@ impl Transaction {
 	async fn export_tables(
 		&self,
 		cfg: &Config,
+		masks: Option<&MaskContext>,
 		chn: &Sender<Vec<u8>>,
 		ns: NamespaceId,
 		db: DatabaseId,
@@ -361,7 +434,7 @@ impl Transaction {
 			self.export_table_structure(ns, db, table, chn).await?;
 			// Then export the table data if its desired
 			if cfg.records {
-				self.export_table_data(ns, db, table, cfg, chn).await?;
+				self.export_table_data(ns, db, table, cfg, masks, chn).await?;
 			}
 		}
 
@@ -409,12 +482,22 @@ impl Transaction {
 		db: DatabaseId,
 		table: &TableDefinition,
 		cfg: &Config,
+		masks: Option<&MaskContext>,
 		chn: &Sender<Vec<u8>>,
 	) -> Result<()> {
 		chn.send(bytes!("-- ------------------------------")).await?;
 		chn.send(bytes!(format!("-- TABLE DATA: {}", InlineCommentDisplay(&table.name)))).await?;
 		chn.send(bytes!("-- ------------------------------")).await?;
 		chn.send(bytes!("")).await?;
+		// Fetch the fields which are masked in the export
+		let fields = match masks {
+			Some(_) => {
+				let fields = self.all_tb_fields(ns, db, &table.name, None).await?;
+				fields.iter().filter(|fd| fd.mask.is_some()).cloned().collect()
+			}
+			None => Vec::new(),
+		};
+		let masks = masks.filter(|_| !fields.is_empty()).map(|m| (m, fields.as_slice()));
 
 		let beg = crate::key::record::prefix(ns, db, &table.name)?;
 		let end = crate::key::record::suffix(ns, db, &table.name)?;
@@ -428,7 +511,7 @@ impl Transaction {
 				if batch.result.is_empty() {
 					break;
 				}
-				self.export_versioned_data(batch.result, chn).await?;
+				self.export_versioned_data(batch.result, masks, chn).await?;
 			} else {
 				let batch = self.batch_keys_vals(rng, *EXPORT_BATCH_SIZE, None).await?;
 				next = batch.next;
@@ -436,7 +519,7 @@ impl Transaction {
 				if batch.result.is_empty() {
 					break;
 				}
-				self.export_regular_data(batch.result, chn).await?;
+				self.export_regular_data(batch.result, masks, chn).await?;
 			}
 			// Fetch more records
 			continue;
@@ -531,6 +614,7 @@ impl Transaction {
 	/// * `versioned_values` - A vector of tuples containing the versioned values to be exported.
 	///   Each tuple consists of a key, value, version, and a boolean indicating if the record is a
 	///   tombstone.
+	/// * `masks` - The context and fields used to mask the records, if masking is enabled.
 	/// * `chn` - A reference to the channel to which the SQL commands will be sent.
 	///
 	/// # Returns
@@ -540,6 +624,7 @@ impl Transaction {
 	async fn export_versioned_data(
 		&self,
 		versioned_values: Vec<(Vec<u8>, Vec<u8>, u64, bool)>,
+		masks: Option<(&MaskContext, &[FieldDefinition])>,
 		chn: &Sender<Vec<u8>>,
 	) -> Result<()> {
 		// Initialize a vector to hold graph edge records.
@@ -561,6 +646,11 @@ impl Transaction {
 			} else {
 				KVValue::kv_decode_value(v)?
 			};
+			// Replace any masked fields of the record
+			let v = match masks {
+				Some((m, fields)) if !is_tombstone => m.apply(&k, v, fields).await?,
+				_ => v,
+			};
 			// Process the value and generate the appropriate SQL command.
 			let sql = Self::process_record(
 				k,
@@ -621,6 +711,7 @@ impl Transaction {
 	///
 	/// * `regular_values` - A vector of tuples containing the regular values to be exported. Each
 	///   tuple consists of a key and a value.
+	/// * `masks` - The context and fields used to mask the records, if masking is enabled.
 	/// * `chn` - A reference to the channel to which the SQL commands will be sent.
 	///
 	/// # Returns
@@ -630,6 +721,7 @@ impl Transaction {
 	async fn export_regular_data(
 		&self,
 		regular_values: Vec<(Vec<u8>, Vec<u8>)>,
+		masks: Option<(&MaskContext, &[FieldDefinition])>,
 		chn: &Sender<Vec<u8>>,
 	) -> Result<()> {
 		// Initialize vectors to hold normal records and graph edge records.
@@ -640,6 +732,11 @@ impl Transaction {
 		for (k, v) in regular_values {
 			let k = record::RecordKey::decode_key(&k)?;
 			let v = Record::kv_decode_value(v)?;
+			// Replace any masked fields of the record
+			let v = match masks {
+				Some((m, fields)) => m.apply(&k, v, fields).await?,
+				None => v,
+			};
 			// Process the value and categorize it into records_relate or records_normal.
 			Self::process_record(k, v, &mut records_relate, &mut records_normal, None, None);
 		}
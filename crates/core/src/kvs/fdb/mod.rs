@@ -174,6 +174,10 @@ impl super::api::Transaction for Transaction {
 		false
 	}
 
+	fn assigns_versionstamps_on_commit(&self) -> bool {
+		true
+	}
+
 	/// Behaviour if unclosed
 	fn check_level(&mut self, check: Check) {
 		self.check = check;
@@ -0,0 +1,9 @@
+use std::sync::LazyLock;
+
+/// Should we sync writes to disk before acknowledgement
+pub(super) static SYNC_DATA: LazyLock<bool> = lazy_env_parse!("SURREAL_SYNC_DATA", bool, false);
+
+/// The interval in seconds between writing snapshots of a persisted in-memory
+/// datastore, where 0 disables periodic snapshots (default: 600)
+pub(super) static MEMORY_SNAPSHOT_INTERVAL: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_MEMORY_SNAPSHOT_INTERVAL", u64, 600);
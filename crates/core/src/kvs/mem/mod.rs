@@ -1,8 +1,18 @@
 #![cfg(feature = "kv-mem")]
 
+#[cfg(not(target_family = "wasm"))]
+mod cnf;
+#[cfg(not(target_family = "wasm"))]
+mod persist;
+
 use std::ops::Range;
+use std::sync::Arc;
 #[cfg(not(target_family = "wasm"))]
 use std::sync::OnceLock;
+#[cfg(not(target_family = "wasm"))]
+use std::thread;
+#[cfg(not(target_family = "wasm"))]
+use std::time::Duration;
 
 use anyhow::{Result, bail, ensure};
 use surrealkv::{Options, Store, Transaction as Tx};
@@ -27,8 +37,14 @@ pub(crate) fn commit_pool() -> &'static affinitypool::Threadpool {
 	})
 }
 
+#[cfg(not(target_family = "wasm"))]
+const TARGET: &str = "surrealdb::core::kvs::mem";
+
 pub struct Datastore {
-	db: Store,
+	db: Arc<Store>,
+	/// The log and snapshots, if persistence is enabled
+	#[cfg(not(target_family = "wasm"))]
+	persistence: Option<Arc<persist::Persistence>>,
 }
 
 pub struct Transaction {
@@ -40,6 +56,15 @@ pub struct Transaction {
 	check: Check,
 	/// The underlying datastore transaction
 	inner: Option<Tx>,
+	/// The log and snapshots, if persistence is enabled
+	#[cfg(not(target_family = "wasm"))]
+	persistence: Option<Arc<persist::Persistence>>,
+	/// The writes to append to the log when committing
+	#[cfg(not(target_family = "wasm"))]
+	changes: Vec<persist::Change>,
+	/// The number of pending writes at each save point
+	#[cfg(not(target_family = "wasm"))]
+	save_points: Vec<usize>,
 }
 
 impl Drop for Transaction {
@@ -61,8 +86,8 @@ impl Drop for Transaction {
 }
 
 impl Datastore {
-	/// Open a new database
-	pub(crate) async fn new() -> Result<Datastore> {
+	/// Open a new database, persisted to the directory if specified
+	pub(crate) async fn new(path: Option<&str>) -> Result<Datastore> {
 		// Create new configuration options
 		let mut opts = Options::new();
 		// Ensure versions are disabled
@@ -70,20 +95,79 @@ impl Datastore {
 		// Ensure persistence is disabled
 		opts.disk_persistence = false;
 		// Create a new datastore
-		match Store::new(opts) {
-			Ok(db) => Ok(Datastore {
-				db,
-			}),
-			Err(e) => Err(anyhow::Error::new(Error::Ds(e.to_string()))),
+		let db = match Store::new(opts) {
+			Ok(db) => Arc::new(db),
+			Err(e) => bail!(Error::Ds(e.to_string())),
+		};
+		// Persistence is not available in the browser
+		#[cfg(target_family = "wasm")]
+		if path.is_some() {
+			bail!(Error::Ds(
+				"The in-memory storage engine can not be persisted in WebAssembly".into()
+			));
 		}
+		// Restore the datastore from the directory
+		#[cfg(not(target_family = "wasm"))]
+		let persistence = match path {
+			Some(path) => {
+				let persistence = Arc::new(persist::Persistence::open(path, &db)?);
+				// Write snapshots periodically
+				match *cnf::MEMORY_SNAPSHOT_INTERVAL {
+					0 => info!(target: TARGET, "Periodic snapshots: disabled"),
+					interval => {
+						info!(target: TARGET, "Periodic snapshots: enabled every {interval}s");
+						let dbc = db.clone();
+						let pc = Arc::downgrade(&persistence);
+						thread::spawn(move || {
+							loop {
+								// Wait for the specified interval
+								thread::sleep(Duration::from_secs(interval));
+								// Stop once the datastore is dropped
+								let Some(persistence) = pc.upgrade() else {
+									break;
+								};
+								// Write a snapshot of the datastore
+								if let Err(err) = persistence.snapshot(&dbc) {
+									error!("Failed to write a snapshot: {err}");
+								}
+							}
+						});
+					}
+				}
+				Some(persistence)
+			}
+			None => None,
+		};
+		// Return the datastore
+		Ok(Datastore {
+			db,
+			#[cfg(not(target_family = "wasm"))]
+			persistence,
+		})
 	}
 
 	/// Shutdown the database
 	pub(crate) async fn shutdown(&self) -> Result<()> {
-		// Nothing to do here
+		// Flush the log to disk
+		#[cfg(not(target_family = "wasm"))]
+		if let Some(persistence) = &self.persistence {
+			persistence.sync()?;
+		}
+		// Nothing else to do here
 		Ok(())
 	}
 
+	/// Write a snapshot of a persisted datastore
+	pub(crate) async fn snapshot(&self) -> Result<()> {
+		#[cfg(not(target_family = "wasm"))]
+		if let Some(persistence) = &self.persistence {
+			let persistence = persistence.clone();
+			let db = self.db.clone();
+			return tokio::task::spawn_blocking(move || persistence.snapshot(&db)).await?;
+		}
+		bail!(Error::Ds("The in-memory datastore is not persisted".into()))
+	}
+
 	/// Start a new transaction
 	pub(crate) async fn transaction(
 		&self,
@@ -102,6 +186,12 @@ impl Datastore {
 				check,
 				write,
 				inner: Some(inner),
+				#[cfg(not(target_family = "wasm"))]
+				persistence: self.persistence.clone(),
+				#[cfg(not(target_family = "wasm"))]
+				changes: Vec::new(),
+				#[cfg(not(target_family = "wasm"))]
+				save_points: Vec::new(),
 			})),
 			Err(e) => Err(anyhow::Error::new(Error::Tx(e.to_string()))),
 		}
@@ -165,7 +255,14 @@ impl super::api::Transaction for Transaction {
 
 		// Commit this transaction in the pool
 		#[cfg(not(target_family = "wasm"))]
-		commit_pool().spawn(move || inner.commit()).await?;
+		match self.persistence.clone() {
+			// Append the writes to the log
+			Some(persistence) => {
+				let changes = std::mem::take(&mut self.changes);
+				commit_pool().spawn(move || persistence.commit(inner, changes)).await?
+			}
+			None => commit_pool().spawn(move || inner.commit()).await?,
+		}
 		#[cfg(target_family = "wasm")]
 		inner.commit()?;
 
@@ -229,6 +326,8 @@ impl super::api::Transaction for Transaction {
 			Some(ts) => inner.set_at_ts(&key, &val, ts)?,
 			None => inner.set(&key, &val)?,
 		}
+		// Record the write
+		self.record(key, Some(val));
 		// Return result
 		Ok(())
 	}
@@ -248,6 +347,8 @@ impl super::api::Transaction for Transaction {
 
 		// Replace the key
 		inner.insert_or_replace(&key, &val)?;
+		// Record the write
+		self.record(key, Some(val));
 
 		// Return result
 		Ok(())
@@ -274,6 +375,8 @@ impl super::api::Transaction for Transaction {
 				_ => bail!(Error::TxKeyAlreadyExists),
 			}
 		}
+		// Record the write
+		self.record(key, Some(val));
 
 		// Return result
 		Ok(())
@@ -297,6 +400,8 @@ impl super::api::Transaction for Transaction {
 			(None, None) => inner.set(&key, &val)?,
 			_ => bail!(Error::TxConditionNotMet),
 		};
+		// Record the write
+		self.record(key, Some(val));
 		// Return result
 		Ok(())
 	}
@@ -315,6 +420,8 @@ impl super::api::Transaction for Transaction {
 
 		// Remove the key
 		inner.soft_delete(&key)?;
+		// Record the deletion
+		self.record(key, None);
 		// Return result
 		Ok(())
 	}
@@ -338,6 +445,8 @@ impl super::api::Transaction for Transaction {
 			(None, None) => inner.soft_delete(&key)?,
 			_ => bail!(Error::TxConditionNotMet),
 		};
+		// Record the deletion
+		self.record(key, None);
 		// Return result
 		Ok(())
 	}
@@ -356,6 +465,8 @@ impl super::api::Transaction for Transaction {
 
 		// Remove the key
 		inner.delete(&key)?;
+		// Record the deletion
+		self.record(key, None);
 		// Return result
 		Ok(())
 	}
@@ -376,6 +487,8 @@ impl super::api::Transaction for Transaction {
 			(None, None) => inner.delete(&key)?,
 			_ => bail!(Error::TxConditionNotMet),
 		};
+		// Record the deletion
+		self.record(key, None);
 
 		// Return result
 		Ok(())
@@ -483,16 +596,35 @@ impl super::api::Transaction for Transaction {
 		if let Some(inner) = &mut self.inner {
 			let _ = inner.set_savepoint();
 		}
+		#[cfg(not(target_family = "wasm"))]
+		self.save_points.push(self.changes.len());
 	}
 
 	async fn rollback_to_save_point(&mut self) -> Result<()> {
 		if let Some(inner) = &mut self.inner {
 			inner.rollback_to_savepoint()?;
 		}
+		#[cfg(not(target_family = "wasm"))]
+		if let Some(len) = self.save_points.pop() {
+			self.changes.truncate(len);
+		}
 		Ok(())
 	}
 
 	fn release_last_save_point(&mut self) -> Result<()> {
+		#[cfg(not(target_family = "wasm"))]
+		self.save_points.pop();
 		Ok(())
 	}
 }
+
+impl Transaction {
+	/// Record a write, so that it is appended to the log when committing
+	#[cfg_attr(target_family = "wasm", expect(unused_variables))]
+	fn record(&mut self, key: Key, val: Option<Val>) {
+		#[cfg(not(target_family = "wasm"))]
+		if self.persistence.is_some() {
+			self.changes.push((key, val));
+		}
+	}
+}
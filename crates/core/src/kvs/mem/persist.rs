@@ -0,0 +1,467 @@
+//! Durable persistence for the in-memory storage engine.
+//!
+//! When the datastore is started with a directory, the writes of every
+//! transaction are appended to a log before the transaction is committed,
+//! and are removed from the log again if the commit fails. Snapshots of
+//! the whole keyspace are written periodically, or on demand, after which
+//! the logs which they cover are removed. On startup the datastore is
+//! rebuilt from the latest snapshot, followed by the logs which were
+//! written after it.
+//!
+//! The directory contains a `snapshot` file, and a sequence of numbered
+//! `.log` files. The snapshot records the number of the first log which it
+//! does not cover. Both files consist of checksummed records, each holding a
+//! batch of writes. A record which was only partially written, because the
+//! process stopped while appending to the newest log, is discarded.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{Context as _, Result, bail};
+use surrealkv::{Mode, Store, Transaction as Tx};
+
+use super::{TARGET, cnf};
+use crate::err::Error;
+use crate::kvs::{Key, Val};
+
+/// The header which identifies a snapshot file
+const MAGIC: &[u8; 8] = b"SDBMEM01";
+
+/// The name of the snapshot file
+const SNAPSHOT: &str = "snapshot";
+
+/// The name of the snapshot file while it is being written
+const SNAPSHOT_TMP: &str = "snapshot.tmp";
+
+/// The marker for a key which was set
+const SET: u8 = 1;
+
+/// The marker for a key which was deleted
+const DEL: u8 = 0;
+
+/// The number of writes in each snapshot record
+const BATCH_SIZE: usize = 10_000;
+
+/// A write to a key, where `None` is a deletion
+pub(super) type Change = (Key, Option<Val>);
+
+/// The log which is currently being appended to
+struct Log {
+	/// The number of the log
+	seq: u64,
+	/// The log file
+	file: File,
+}
+
+pub(super) struct Persistence {
+	/// The directory containing the snapshot and logs
+	dir: PathBuf,
+	/// The log which is currently being appended to
+	log: Mutex<Log>,
+	/// Ensures that only one snapshot is written at a time
+	snapshot: Mutex<()>,
+}
+
+impl Persistence {
+	/// Open the directory, and restore the snapshot and logs into the store
+	pub(super) fn open(dir: &str, db: &Store) -> Result<Self> {
+		let dir = PathBuf::from(dir);
+		fs::create_dir_all(&dir)
+			.with_context(|| format!("Unable to create the directory {}", dir.display()))?;
+		// Restore the latest snapshot
+		let first = restore_snapshot(&dir.join(SNAPSHOT), db)?;
+		// Find the logs which were written after the snapshot
+		let mut logs = list_logs(&dir)?;
+		logs.retain(|seq| *seq >= first);
+		// Replay the logs in order
+		for seq in logs.iter() {
+			replay_log(&log_path(&dir, *seq), db)?;
+		}
+		// Continue appending to the newest log
+		let seq = logs.last().copied().unwrap_or(first);
+		let file = OpenOptions::new().create(true).append(true).open(log_path(&dir, seq))?;
+		info!(target: TARGET, "Restored in-memory datastore from {}", dir.display());
+		Ok(Self {
+			dir,
+			log: Mutex::new(Log {
+				seq,
+				file,
+			}),
+			snapshot: Mutex::new(()),
+		})
+	}
+
+	/// Append the writes of a transaction to the log, and commit it
+	pub(super) fn commit(&self, mut tx: Tx, writes: Vec<Change>) -> Result<()> {
+		// The log is locked while committing, so
+		// that the order of the log matches the
+		// order in which transactions commit.
+		let mut log = self.log.lock().map_err(|_| Error::Ds("The log lock is poisoned".into()))?;
+		if writes.is_empty() {
+			tx.commit()?;
+			return Ok(());
+		}
+		// Append the writes to the log before committing,
+		// so that no other transaction can observe the
+		// writes before they have been made durable.
+		let len = log.file.metadata()?.len();
+		let res = log.file.write_all(&encode_record(&writes)).map_err(anyhow::Error::from);
+		let res = match res {
+			Ok(()) if *cnf::SYNC_DATA => log.file.sync_data().map_err(anyhow::Error::from),
+			res => res,
+		};
+		// Commit the transaction
+		let res = res.and_then(|_| tx.commit().map_err(anyhow::Error::from));
+		// Remove the writes from the log if the commit failed
+		if res.is_err() {
+			log.file.set_len(len)?;
+		}
+		res
+	}
+
+	/// Write a snapshot of the store, and remove the logs which it covers
+	pub(super) fn snapshot(&self, db: &Store) -> Result<()> {
+		let _guard =
+			self.snapshot.lock().map_err(|_| Error::Ds("The snapshot lock is poisoned".into()))?;
+		// Start a new log, and begin a transaction
+		// which sees every write in the earlier logs
+		let (seq, mut tx) = {
+			let mut log =
+				self.log.lock().map_err(|_| Error::Ds("The log lock is poisoned".into()))?;
+			let tx = db.begin_with_mode(Mode::ReadOnly)?;
+			let seq = log.seq + 1;
+			log.file.sync_data()?;
+			log.file =
+				OpenOptions::new().create(true).append(true).open(log_path(&self.dir, seq))?;
+			log.seq = seq;
+			(seq, tx)
+		};
+		// Write the snapshot to a temporary file
+		let tmp = self.dir.join(SNAPSHOT_TMP);
+		let mut file = BufWriter::new(File::create(&tmp)?);
+		file.write_all(MAGIC)?;
+		file.write_all(&seq.to_le_bytes())?;
+		let mut batch = Vec::with_capacity(BATCH_SIZE);
+		for res in tx.scan(.., None) {
+			let (k, v, _) = res?;
+			batch.push((k.to_vec(), Some(v)));
+			if batch.len() >= BATCH_SIZE {
+				file.write_all(&encode_record(&batch))?;
+				batch.clear();
+			}
+		}
+		if !batch.is_empty() {
+			file.write_all(&encode_record(&batch))?;
+		}
+		let file = file.into_inner().map_err(|e| e.into_error())?;
+		file.sync_all()?;
+		// Replace the previous snapshot
+		fs::rename(&tmp, self.dir.join(SNAPSHOT))?;
+		sync_dir(&self.dir)?;
+		// Remove the logs which the snapshot covers
+		for old in list_logs(&self.dir)? {
+			if old < seq {
+				fs::remove_file(log_path(&self.dir, old))?;
+			}
+		}
+		info!(target: TARGET, "Written snapshot of in-memory datastore to {}", self.dir.display());
+		Ok(())
+	}
+
+	/// Flush the current log to disk
+	pub(super) fn sync(&self) -> Result<()> {
+		let log = self.log.lock().map_err(|_| Error::Ds("The log lock is poisoned".into()))?;
+		log.file.sync_data()?;
+		Ok(())
+	}
+}
+
+/// The path of a numbered log file
+fn log_path(dir: &Path, seq: u64) -> PathBuf {
+	dir.join(format!("{seq:020}.log"))
+}
+
+/// The numbers of the log files in the directory, in order
+fn list_logs(dir: &Path) -> Result<Vec<u64>> {
+	let mut logs = Vec::new();
+	for entry in fs::read_dir(dir)? {
+		let name = entry?.file_name();
+		let Some(seq) = name.to_str().and_then(|n| n.strip_suffix(".log")) else {
+			continue;
+		};
+		if let Ok(seq) = seq.parse() {
+			logs.push(seq);
+		}
+	}
+	logs.sort_unstable();
+	Ok(logs)
+}
+
+/// Ensure that renames within the directory are durable
+fn sync_dir(dir: &Path) -> Result<()> {
+	#[cfg(unix)]
+	File::open(dir)?.sync_all()?;
+	#[cfg(not(unix))]
+	let _ = dir;
+	Ok(())
+}
+
+/// Restore a snapshot, returning the number of the first log it does not cover
+fn restore_snapshot(path: &Path, db: &Store) -> Result<u64> {
+	let file = match File::open(path) {
+		Ok(file) => file,
+		Err(e) if e.kind() == ErrorKind::NotFound => return Ok(0),
+		Err(e) => return Err(e.into()),
+	};
+	let mut reader = BufReader::new(file);
+	let mut header = [0; 16];
+	reader.read_exact(&mut header)?;
+	if &header[..8] != MAGIC {
+		bail!(Error::Ds(format!("The snapshot at {} is not valid", path.display())));
+	}
+	let seq = u64::from_le_bytes(header[8..].try_into()?);
+	// Snapshots are written atomically, so every record must be valid
+	while let Some(writes) = read_record(&mut reader)? {
+		apply(db, writes)?;
+	}
+	Ok(seq)
+}
+
+/// Replay the writes in a log
+fn replay_log(path: &Path, db: &Store) -> Result<()> {
+	let mut reader = BufReader::new(File::open(path)?);
+	let mut valid = 0;
+	loop {
+		match read_record(&mut reader) {
+			Ok(Some(writes)) => {
+				valid = reader.stream_position()?;
+				apply(db, writes)?;
+			}
+			Ok(None) => return Ok(()),
+			// A record which was only partially written is discarded
+			Err(e) => {
+				warn!(target: TARGET, "Discarding an incomplete record in {}: {e}", path.display());
+				OpenOptions::new().write(true).open(path)?.set_len(valid)?;
+				return Ok(());
+			}
+		}
+	}
+}
+
+/// Apply a batch of writes to the store in a single transaction
+fn apply(db: &Store, writes: Vec<Change>) -> Result<()> {
+	let mut tx = db.begin()?;
+	for (k, v) in writes {
+		match v {
+			Some(v) => tx.set(&k, &v)?,
+			None => tx.delete(&k)?,
+		}
+	}
+	tx.commit()?;
+	Ok(())
+}
+
+/// Encode a batch of writes as a checksummed record
+fn encode_record(writes: &[Change]) -> Vec<u8> {
+	let mut payload = Vec::new();
+	for (k, v) in writes {
+		match v {
+			Some(v) => {
+				payload.push(SET);
+				payload.extend((k.len() as u32).to_le_bytes());
+				payload.extend_from_slice(k);
+				payload.extend((v.len() as u32).to_le_bytes());
+				payload.extend_from_slice(v);
+			}
+			None => {
+				payload.push(DEL);
+				payload.extend((k.len() as u32).to_le_bytes());
+				payload.extend_from_slice(k);
+			}
+		}
+	}
+	let mut out = Vec::with_capacity(payload.len() + 8);
+	out.extend((payload.len() as u32).to_le_bytes());
+	out.extend(crc32fast::hash(&payload).to_le_bytes());
+	out.extend(payload);
+	out
+}
+
+/// Read the next record, returning `None` at the end of the file
+fn read_record(reader: &mut impl Read) -> Result<Option<Vec<Change>>> {
+	let mut header = [0; 8];
+	match reader.read_exact(&mut header[..1]) {
+		Ok(()) => reader.read_exact(&mut header[1..])?,
+		Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+		Err(e) => return Err(e.into()),
+	}
+	let len = u32::from_le_bytes(header[..4].try_into()?) as usize;
+	let crc = u32::from_le_bytes(header[4..].try_into()?);
+	let mut payload = vec![0; len];
+	reader.read_exact(&mut payload)?;
+	if crc32fast::hash(&payload) != crc {
+		bail!(Error::Ds("The record checksum does not match".into()));
+	}
+	decode_payload(&payload).map(Some)
+}
+
+/// Decode the writes in the payload of a record
+fn decode_payload(mut buf: &[u8]) -> Result<Vec<Change>> {
+	fn take<'a>(buf: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+		if buf.len() < len {
+			bail!(Error::Ds("The record is truncated".into()));
+		}
+		let (head, tail) = buf.split_at(len);
+		*buf = tail;
+		Ok(head)
+	}
+	fn take_len(buf: &mut &[u8]) -> Result<usize> {
+		Ok(u32::from_le_bytes(take(buf, 4)?.try_into()?) as usize)
+	}
+	let mut writes = Vec::new();
+	while let Some((&op, rest)) = buf.split_first() {
+		buf = rest;
+		let len = take_len(&mut buf)?;
+		let key = take(&mut buf, len)?.to_vec();
+		match op {
+			SET => {
+				let len = take_len(&mut buf)?;
+				writes.push((key, Some(take(&mut buf, len)?.to_vec())));
+			}
+			DEL => writes.push((key, None)),
+			_ => bail!(Error::Ds("The record contains an invalid operation".into())),
+		}
+	}
+	Ok(writes)
+}
+
+#[cfg(test)]
+mod tests {
+	use surrealkv::Options;
+	use temp_dir::TempDir;
+
+	use super::*;
+
+	fn store() -> Store {
+		let mut opts = Options::new();
+		opts.enable_versions = false;
+		opts.disk_persistence = false;
+		Store::new(opts).unwrap()
+	}
+
+	fn write(db: &Store, persistence: &Persistence, changes: Vec<Change>) {
+		let mut tx = db.begin().unwrap();
+		for (k, v) in changes.iter() {
+			match v {
+				Some(v) => tx.set(k, v).unwrap(),
+				None => tx.delete(k).unwrap(),
+			}
+		}
+		persistence.commit(tx, changes).unwrap();
+	}
+
+	fn read(db: &Store, key: &[u8]) -> Option<Val> {
+		db.begin().unwrap().get(key).unwrap()
+	}
+
+	#[test]
+	fn test_restore() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().to_str().unwrap();
+		// Write to the log, and to a snapshot
+		let db = store();
+		let persistence = Persistence::open(path, &db).unwrap();
+		write(&db, &persistence, vec![(b"a".to_vec(), Some(b"1".to_vec()))]);
+		write(&db, &persistence, vec![(b"b".to_vec(), Some(b"2".to_vec()))]);
+		persistence.snapshot(&db).unwrap();
+		write(&db, &persistence, vec![(b"a".to_vec(), None), (b"c".to_vec(), Some(b"3".to_vec()))]);
+		drop(persistence);
+		// The logs covered by the snapshot are removed
+		assert_eq!(list_logs(dir.path()).unwrap(), vec![1]);
+		// Restore the snapshot and the newer log
+		let db = store();
+		let _persistence = Persistence::open(path, &db).unwrap();
+		assert_eq!(read(&db, b"a"), None);
+		assert_eq!(read(&db, b"b"), Some(b"2".to_vec()));
+		assert_eq!(read(&db, b"c"), Some(b"3".to_vec()));
+	}
+
+	#[test]
+	fn test_restore_incomplete_log() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().to_str().unwrap();
+		let db = store();
+		let persistence = Persistence::open(path, &db).unwrap();
+		write(&db, &persistence, vec![(b"a".to_vec(), Some(b"1".to_vec()))]);
+		drop(persistence);
+		// Simulate a record which was only partially written
+		let mut file = OpenOptions::new().append(true).open(log_path(dir.path(), 0)).unwrap();
+		let record = encode_record(&[(b"b".to_vec(), Some(b"2".to_vec()))]);
+		file.write_all(&record[..record.len() / 2]).unwrap();
+		drop(file);
+		// The incomplete record is discarded
+		let db = store();
+		let persistence = Persistence::open(path, &db).unwrap();
+		assert_eq!(read(&db, b"a"), Some(b"1".to_vec()));
+		assert_eq!(read(&db, b"b"), None);
+		// New records are appended after the valid records
+		write(&db, &persistence, vec![(b"c".to_vec(), Some(b"3".to_vec()))]);
+		drop(persistence);
+		let db = store();
+		let _persistence = Persistence::open(path, &db).unwrap();
+		assert_eq!(read(&db, b"c"), Some(b"3".to_vec()));
+	}
+
+	#[test]
+	fn test_failed_commit_is_not_logged() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().to_str().unwrap();
+		let db = store();
+		let persistence = Persistence::open(path, &db).unwrap();
+		// Start a transaction which conflicts with a later commit
+		let mut tx = db.begin().unwrap();
+		tx.get(b"a").unwrap();
+		tx.set(b"a", b"2").unwrap();
+		write(&db, &persistence, vec![(b"a".to_vec(), Some(b"1".to_vec()))]);
+		let res = persistence.commit(tx, vec![(b"a".to_vec(), Some(b"2".to_vec()))]);
+		assert!(res.is_err());
+		// Later commits are still appended to the log
+		write(&db, &persistence, vec![(b"b".to_vec(), Some(b"3".to_vec()))]);
+		drop(persistence);
+		// The writes of the failed commit are not restored
+		let db = store();
+		let _persistence = Persistence::open(path, &db).unwrap();
+		assert_eq!(read(&db, b"a"), Some(b"1".to_vec()));
+		assert_eq!(read(&db, b"b"), Some(b"3".to_vec()));
+	}
+
+	#[test]
+	fn test_record_roundtrip() {
+		let writes = vec![
+			(b"a".to_vec(), Some(b"1".to_vec())),
+			(b"b".to_vec(), None),
+			(vec![], Some(vec![])),
+		];
+		let enc = encode_record(&writes);
+		let mut reader = enc.as_slice();
+		assert_eq!(read_record(&mut reader).unwrap(), Some(writes));
+		assert_eq!(read_record(&mut reader).unwrap(), None);
+	}
+
+	#[test]
+	fn test_record_truncated() {
+		let enc = encode_record(&[(b"key".to_vec(), Some(b"value".to_vec()))]);
+		let mut reader = &enc[..enc.len() - 1];
+		assert!(read_record(&mut reader).is_err());
+	}
+
+	#[test]
+	fn test_record_corrupted() {
+		let mut enc = encode_record(&[(b"key".to_vec(), Some(b"value".to_vec()))]);
+		let last = enc.len() - 1;
+		enc[last] ^= 0xff;
+		assert!(read_record(&mut enc.as_slice()).is_err());
+	}
+}
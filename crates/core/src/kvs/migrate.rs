@@ -0,0 +1,812 @@
+//! Versioned schema migrations.
+//!
+//! Migrations are SurrealQL files in a directory, named with a version and a
+//! description, such as `0001_create_users.surql`. A migration which can be
+//! reverted is split into `0001_create_users.up.surql`, and a matching
+//! `0001_create_users.down.surql`. Versions are numbers, and migrations are
+//! applied in numeric order, so `2` is applied before `10` whether or not the
+//! versions are padded to the same width.
+//!
+//! Each migration is applied inside its own transaction, and recorded in the
+//! [`MIGRATIONS_TABLE`] table along with a checksum of its source, so that a
+//! migration which is modified after it was applied can be detected. While
+//! migrations are being applied or reverted, a lock record is held in the same
+//! table, so that concurrent runs can not apply the same migrations twice.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Result, bail, ensure};
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "kv-mem")]
+use super::export;
+use super::{Datastore, LockType, TransactionType};
+use crate::catalog::providers::DatabaseProvider;
+use crate::dbs::{Response, Session, Variables};
+use crate::err::Error;
+use crate::fmt::EscapeIdent;
+use crate::sql::TopLevelExpr;
+use crate::syn;
+use crate::val::{Datetime, Object, RecordId, RecordIdKey, Value};
+
+/// The table in which applied migrations are recorded
+pub const MIGRATIONS_TABLE: &str = "_migrations";
+
+/// The key of the record which is held in [`MIGRATIONS_TABLE`] while
+/// migrations are being applied or reverted
+const LOCK: &str = "lock";
+
+/// A migration which has been loaded from a directory
+#[derive(Clone, Debug)]
+pub struct Migration {
+	/// The version of the migration, such as `0001`
+	pub version: String,
+	/// The description of the migration, such as `create_users`
+	pub name: String,
+	/// The source which applies the migration
+	pub up: String,
+	/// The source which reverts the migration, if it can be reverted
+	pub down: Option<String>,
+}
+
+impl Migration {
+	/// The checksum of the source which applies the migration
+	pub fn checksum(&self) -> String {
+		hex::encode(Sha256::digest(self.up.as_bytes()))
+	}
+}
+
+/// Orders two versions numerically, so that versions which are not padded to
+/// the same width are still ordered correctly
+fn compare_versions(a: &str, b: &str) -> Ordering {
+	let (a, b) = (a.trim_start_matches('0'), b.trim_start_matches('0'));
+	a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+/// Checks that a version is a number, such as `0001`
+fn validate_version(version: &str) -> Result<()> {
+	ensure!(
+		!version.is_empty() && version.bytes().all(|b| b.is_ascii_digit()),
+		Error::InvalidMigration {
+			version: version.to_owned(),
+			message: "expected a numeric version such as '0001'".to_owned(),
+		}
+	);
+	Ok(())
+}
+
+/// Loads the migrations in a directory, ordered by version
+pub fn load(dir: &Path) -> Result<Vec<Migration>> {
+	// Versions are keyed without padding, so that
+	// equal versions of different widths collide
+	let mut migrations = BTreeMap::<(usize, String), Migration>::new();
+	let mut entries = std::fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+	entries.sort_by_key(|x| x.file_name());
+	for entry in entries {
+		let file_name = entry.file_name();
+		let Some(stem) = file_name.to_str().and_then(|x| x.strip_suffix(".surql")) else {
+			continue;
+		};
+		let (stem, down) = match stem.strip_suffix(".down") {
+			Some(stem) => (stem, true),
+			None => (stem.strip_suffix(".up").unwrap_or(stem), false),
+		};
+		let Some((version, name)) = stem.split_once('_').filter(|(x, _)| !x.is_empty()) else {
+			bail!(Error::InvalidMigration {
+				version: stem.to_owned(),
+				message: "expected a file name such as '0001_description.surql'".to_owned(),
+			});
+		};
+		validate_version(version)?;
+		let source = std::fs::read_to_string(entry.path())?;
+		let number = version.trim_start_matches('0');
+		let key = (number.len(), number.to_owned());
+		let migration = migrations.entry(key).or_insert_with(|| Migration {
+			version: version.to_owned(),
+			name: name.to_owned(),
+			up: String::new(),
+			down: None,
+		});
+		ensure!(
+			migration.version == version && migration.name == name,
+			Error::InvalidMigration {
+				version: version.to_owned(),
+				message: format!(
+					"found both '{}_{}' and '{version}_{name}'",
+					migration.version, migration.name
+				),
+			}
+		);
+		if down {
+			migration.down = Some(source);
+		} else {
+			migration.up = source;
+		}
+	}
+	for migration in migrations.values() {
+		ensure!(
+			!migration.up.is_empty() || migration.down.is_none(),
+			Error::InvalidMigration {
+				version: migration.version.clone(),
+				message: "found a down script without an up script".to_owned(),
+			}
+		);
+	}
+	Ok(migrations.into_values().collect())
+}
+
+/// The operation performed by [`Datastore::migrate`]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum Action {
+	/// Apply the pending migrations, up to and including the target version
+	#[default]
+	Up,
+	/// Revert the applied migrations after the target version, or only the
+	/// most recent migration if there is no target version
+	Down,
+	/// Only report the state of each migration
+	Status,
+}
+
+/// The configuration for [`Datastore::migrate`]
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+	pub action: Action,
+	pub target: Option<String>,
+	/// Whether to compute the changes to the schema, without changing the
+	/// database
+	pub dry_run: bool,
+}
+
+/// The state of a migration
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum State {
+	/// The migration has been applied
+	Applied,
+	/// The migration has not been applied
+	Pending,
+	/// The migration has been applied, but has been modified since
+	Modified,
+	/// The migration has been applied, but its source no longer exists
+	Missing,
+}
+
+impl State {
+	fn as_str(&self) -> &'static str {
+		match self {
+			State::Applied => "applied",
+			State::Pending => "pending",
+			State::Modified => "modified",
+			State::Missing => "missing",
+		}
+	}
+}
+
+/// The state of a single migration
+#[derive(Clone, Debug)]
+pub struct Status {
+	pub version: String,
+	pub name: String,
+	pub state: State,
+	pub applied_at: Option<Datetime>,
+}
+
+/// The changes which a set of migrations makes to the schema of a database
+#[derive(Clone, Debug, Default)]
+pub struct Diff {
+	/// The definitions which are added
+	pub added: Vec<String>,
+	/// The definitions which are removed
+	pub removed: Vec<String>,
+	/// The new definitions of those which are changed
+	pub changed: Vec<String>,
+}
+
+/// The outcome of [`Datastore::migrate`]
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+	/// The versions which were applied, or would be applied in a dry run
+	pub applied: Vec<String>,
+	/// The versions which were reverted, or would be reverted in a dry run
+	pub reverted: Vec<String>,
+	/// The state of each migration before any changes were made
+	pub status: Vec<Status>,
+	/// The changes to the schema, when performing a dry run
+	pub diff: Option<Diff>,
+}
+
+impl From<Report> for Value {
+	fn from(report: Report) -> Self {
+		let strings =
+			|x: Vec<String>| Value::from(x.into_iter().map(Value::from).collect::<Vec<_>>());
+		let status = report
+			.status
+			.into_iter()
+			.map(|x| {
+				Value::from(map! {
+					"version".to_owned() => Value::from(x.version),
+					"name".to_owned() => Value::from(x.name),
+					"state".to_owned() => Value::from(x.state.as_str()),
+					"applied_at".to_owned() => x.applied_at.map(Value::from).unwrap_or_default(),
+				})
+			})
+			.collect::<Vec<_>>();
+		let diff = report
+			.diff
+			.map(|x| {
+				Value::from(map! {
+					"added".to_owned() => strings(x.added),
+					"removed".to_owned() => strings(x.removed),
+					"changed".to_owned() => strings(x.changed),
+				})
+			})
+			.unwrap_or_default();
+		Value::from(map! {
+			"applied".to_owned() => strings(report.applied),
+			"reverted".to_owned() => strings(report.reverted),
+			"status".to_owned() => Value::from(status),
+			"diff".to_owned() => diff,
+		})
+	}
+}
+
+/// A migration which has been recorded as applied
+struct Applied {
+	version: String,
+	name: String,
+	checksum: String,
+	applied_at: Option<Datetime>,
+}
+
+/// A single change made by a migration
+enum Step<'a> {
+	Apply(&'a Migration),
+	Revert(&'a Migration),
+}
+
+impl Datastore {
+	/// Applies or reverts migrations, or reports their state.
+	///
+	/// Migrations which were modified after they were applied must be
+	/// restored before any other migrations can be applied or reverted.
+	/// Only one run can apply or revert migrations at a time, and other runs
+	/// fail with [`Error::MigrationLocked`] until it has finished.
+	pub async fn migrate(
+		&self,
+		sess: &Session,
+		migrations: Vec<Migration>,
+		cfg: Config,
+	) -> Result<Report> {
+		if let Some(target) = cfg.target.as_deref() {
+			validate_version(target)?;
+		}
+		// Reporting the state, or the effect of
+		// changes, does not modify the database
+		if cfg.dry_run || cfg.action == Action::Status {
+			return self.run_migrations(sess, migrations, cfg).await;
+		}
+		// Hold the lock for the duration of the changes
+		self.lock_migrations(sess).await?;
+		let res = self.run_migrations(sess, migrations, cfg).await;
+		let unlock = self.unlock_migrations(sess).await;
+		let report = res?;
+		unlock?;
+		Ok(report)
+	}
+
+	/// Takes the lock which prevents concurrent runs from making changes
+	async fn lock_migrations(&self, sess: &Session) -> Result<()> {
+		let sql = format!(
+			"BEGIN; DEFINE TABLE IF NOT EXISTS {tb} SCHEMALESS; CREATE {tb}:{LOCK} SET locked_at = time::now(); COMMIT;",
+			tb = EscapeIdent(MIGRATIONS_TABLE)
+		);
+		match first_error(self.execute(&sql, sess, None).await?) {
+			// The lock is held by another run, which
+			// either committed first, or concurrently
+			Err(e)
+				if matches!(
+					e.downcast_ref::<Error>(),
+					Some(
+						Error::RecordExists { .. } | Error::TxRetryable | Error::TxKeyAlreadyExists
+					)
+				) =>
+			{
+				bail!(Error::MigrationLocked)
+			}
+			res => res,
+		}
+	}
+
+	/// Releases the lock which prevents concurrent runs from making changes
+	async fn unlock_migrations(&self, sess: &Session) -> Result<()> {
+		let sql = format!("DELETE {}:{LOCK}", EscapeIdent(MIGRATIONS_TABLE));
+		first_error(self.execute(&sql, sess, None).await?)
+	}
+
+	/// Computes the state of each migration, and then makes the changes
+	async fn run_migrations(
+		&self,
+		sess: &Session,
+		migrations: Vec<Migration>,
+		cfg: Config,
+	) -> Result<Report> {
+		// Retrieve the migrations which have already been applied
+		let applied = self.applied_migrations(sess).await?;
+		// Compute the state of each migration
+		let mut status: Vec<Status> = migrations
+			.iter()
+			.map(|x| {
+				let record = applied.iter().find(|a| a.version == x.version);
+				Status {
+					version: x.version.clone(),
+					name: x.name.clone(),
+					state: match record {
+						None => State::Pending,
+						Some(a) if a.checksum == x.checksum() => State::Applied,
+						Some(_) => State::Modified,
+					},
+					applied_at: record.and_then(|a| a.applied_at.clone()),
+				}
+			})
+			.collect();
+		for a in applied.iter() {
+			if !migrations.iter().any(|x| x.version == a.version) {
+				status.push(Status {
+					version: a.version.clone(),
+					name: a.name.clone(),
+					state: State::Missing,
+					applied_at: a.applied_at.clone(),
+				});
+			}
+		}
+		status.sort_by(|a, b| compare_versions(&a.version, &b.version));
+		// Determine the changes to make
+		let in_target = |version: &str| {
+			cfg.target.as_deref().is_none_or(|x| compare_versions(version, x).is_le())
+		};
+		let steps: Vec<Step> = match cfg.action {
+			Action::Status => Vec::new(),
+			Action::Up => migrations
+				.iter()
+				.filter(|x| in_target(&x.version))
+				.filter(|x| {
+					status.iter().any(|s| s.version == x.version && s.state == State::Pending)
+				})
+				.map(Step::Apply)
+				.collect(),
+			Action::Down => {
+				let reverted = applied.iter().rev().filter(|x| !in_target(&x.version));
+				let reverted: Vec<&Applied> = match cfg.target {
+					Some(_) => reverted.collect(),
+					None => applied.last().into_iter().collect(),
+				};
+				let mut steps = Vec::new();
+				for a in reverted {
+					let Some(migration) = migrations.iter().find(|x| x.version == a.version) else {
+						bail!(Error::InvalidMigration {
+							version: a.version.clone(),
+							message: "the source of the migration no longer exists".to_owned(),
+						});
+					};
+					ensure!(
+						migration.down.is_some(),
+						Error::InvalidMigration {
+							version: a.version.clone(),
+							message: "the migration has no down script".to_owned(),
+						}
+					);
+					steps.push(Step::Revert(migration));
+				}
+				steps
+			}
+		};
+		// Refuse to make changes when a migration has been modified
+		let modified = status.iter().find(|x| x.state == State::Modified);
+		if let (Some(modified), false) = (modified, steps.is_empty()) {
+			bail!(Error::MigrationModified {
+				version: modified.version.clone(),
+			});
+		}
+		// Perform the changes, or compute their effect on a copy of the database
+		let diff = if cfg.dry_run {
+			Some(self.migration_diff(sess, &steps).await?)
+		} else {
+			for step in steps.iter() {
+				self.migration_step(sess, step).await?;
+			}
+			None
+		};
+		let mut report = Report {
+			status,
+			diff,
+			..Default::default()
+		};
+		for step in steps {
+			match step {
+				Step::Apply(x) => report.applied.push(x.version.clone()),
+				Step::Revert(x) => report.reverted.push(x.version.clone()),
+			}
+		}
+		Ok(report)
+	}
+
+	/// Retrieves the migrations which have been applied, ordered by version
+	async fn applied_migrations(&self, sess: &Session) -> Result<Vec<Applied>> {
+		// The database does not exist until a migration is applied
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		let tx = self.transaction(TransactionType::Read, LockType::Optimistic).await?;
+		let exists = tx.get_db_by_name(&ns, &db).await?.is_some();
+		tx.cancel().await?;
+		if !exists {
+			return Ok(Vec::new());
+		}
+		let sql = format!("SELECT * FROM {}", EscapeIdent(MIGRATIONS_TABLE));
+		let value = match self.execute(&sql, sess, None).await?.remove(0).output() {
+			Ok(value) => value,
+			// The table does not exist in strict mode until a migration is applied
+			Err(e) if is_not_found(&e) => return Ok(Vec::new()),
+			Err(e) => return Err(e),
+		};
+		let Value::Array(records) = value else {
+			return Ok(Vec::new());
+		};
+		let mut applied = Vec::new();
+		for record in records {
+			let Value::Object(mut record) = record else {
+				continue;
+			};
+			let Some(Value::RecordId(RecordId {
+				key: RecordIdKey::String(version),
+				..
+			})) = record.remove("id")
+			else {
+				continue;
+			};
+			// The lock is not a migration
+			if version == LOCK {
+				continue;
+			}
+			let mut field = |name: &str| record.remove(name).unwrap_or_default();
+			applied.push(Applied {
+				version,
+				name: field("name").as_raw_string(),
+				checksum: field("checksum").as_raw_string(),
+				applied_at: match field("applied_at") {
+					Value::Datetime(x) => Some(x),
+					_ => None,
+				},
+			});
+		}
+		applied.sort_by(|a, b| compare_versions(&a.version, &b.version));
+		Ok(applied)
+	}
+
+	/// Applies or reverts a single migration inside a transaction
+	async fn migration_step(&self, sess: &Session, step: &Step<'_>) -> Result<()> {
+		let (migration, source, track) = match step {
+			Step::Apply(x) => (x, x.up.as_str(), "UPSERT $migration CONTENT $content"),
+			Step::Revert(x) => (x, x.down.as_deref().unwrap_or_default(), "DELETE $migration"),
+		};
+		let invalid = |message: String| Error::InvalidMigration {
+			version: migration.version.clone(),
+			message,
+		};
+		let mut ast = syn::parse_with_capabilities(source, self.get_capabilities())
+			.map_err(|e| invalid(e.to_string()))?;
+		ensure!(
+			!ast.expressions.iter().any(|x| matches!(
+				x,
+				TopLevelExpr::Begin | TopLevelExpr::Cancel | TopLevelExpr::Commit
+			)),
+			invalid("migrations are run inside a transaction, and can not contain transaction statements".to_owned())
+		);
+		let define =
+			format!("DEFINE TABLE IF NOT EXISTS {} SCHEMALESS", EscapeIdent(MIGRATIONS_TABLE));
+		ast.expressions.insert(0, TopLevelExpr::Begin);
+		ast.expressions.extend(syn::parse(&define)?.expressions);
+		ast.expressions.extend(syn::parse(track)?.expressions);
+		ast.expressions.push(TopLevelExpr::Commit);
+		// Prepare the record of the migration
+		let mut vars = Variables::new();
+		vars.insert(
+			"migration".to_owned(),
+			Value::RecordId(RecordId::new(MIGRATIONS_TABLE.to_owned(), migration.version.clone())),
+		);
+		vars.insert(
+			"content".to_owned(),
+			Value::Object(Object::from(map! {
+				"name".to_owned() => Value::from(migration.name.clone()),
+				"checksum".to_owned() => Value::from(migration.checksum()),
+				"applied_at".to_owned() => Value::from(Datetime::now()),
+			})),
+		);
+		// Run the migration, reporting the error which caused it to fail
+		let responses = self.process(ast, sess, Some(vars)).await?;
+		first_error(responses).map_err(|e| invalid(e.to_string()).into())
+	}
+
+	/// Computes the changes which migrations make to the schema, by applying
+	/// them to an in-memory copy of the definitions in the database.
+	#[cfg(feature = "kv-mem")]
+	async fn migration_diff(&self, sess: &Session, steps: &[Step<'_>]) -> Result<Diff> {
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Export the definitions in the database
+		let (tx, rx) = async_channel::unbounded();
+		let cfg = export::Config {
+			versions: false,
+			records: false,
+			..Default::default()
+		};
+		match self.export_with_config(sess, tx, cfg).await?.await {
+			// The database does not exist until a migration is applied
+			Err(e) if is_not_found(&e) => {}
+			res => res?,
+		}
+		let mut source = Vec::new();
+		while let Ok(bytes) = rx.try_recv() {
+			source.extend(bytes);
+		}
+		// Import the definitions into a copy of the database
+		let copy =
+			Datastore::new("memory").await?.with_capabilities(self.get_capabilities().clone());
+		let owner = Session::owner().with_ns(&ns).with_db(&db);
+		let sql = format!(
+			"DEFINE NAMESPACE IF NOT EXISTS {ns}; USE NS {ns}; DEFINE DATABASE IF NOT EXISTS {db};",
+			ns = EscapeIdent(&ns),
+			db = EscapeIdent(&db)
+		);
+		first_error(copy.execute(&sql, &owner, None).await?)?;
+		first_error(copy.import(&String::from_utf8(source)?, &owner).await?)?;
+		// Compare the definitions before and after the migrations
+		let before = copy.definitions(&owner).await?;
+		for step in steps {
+			copy.migration_step(&owner, step).await?;
+		}
+		let after = copy.definitions(&owner).await?;
+		let mut diff = Diff::default();
+		for (key, definition) in after.iter() {
+			match before.get(key) {
+				None => diff.added.push(definition.clone()),
+				Some(x) if x != definition => diff.changed.push(definition.clone()),
+				Some(_) => {}
+			}
+		}
+		for (key, definition) in before {
+			if !after.contains_key(&key) {
+				diff.removed.push(definition);
+			}
+		}
+		Ok(diff)
+	}
+
+	#[cfg(not(feature = "kv-mem"))]
+	async fn migration_diff(&self, _: &Session, _: &[Step<'_>]) -> Result<Diff> {
+		bail!(Error::Unimplemented(
+			"Dry runs of migrations require the in-memory storage engine".to_owned()
+		))
+	}
+
+	/// Collects the definitions in the database and its tables, as reported
+	/// by `INFO FOR DB` and `INFO FOR TABLE`.
+	#[cfg(feature = "kv-mem")]
+	async fn definitions(&self, sess: &Session) -> Result<BTreeMap<String, String>> {
+		let mut definitions = BTreeMap::new();
+		let info = self.execute("INFO FOR DB", sess, None).await?.remove(0).output()?;
+		let Value::Object(info) = info else {
+			return Ok(definitions);
+		};
+		for (kind, values) in info.iter() {
+			let Value::Object(values) = values else {
+				continue;
+			};
+			for (name, definition) in values.iter() {
+				if kind == "tables" {
+					if name == MIGRATIONS_TABLE {
+						continue;
+					}
+					let sql = format!("INFO FOR TABLE {}", EscapeIdent(name));
+					let table = self.execute(&sql, sess, None).await?.remove(0).output()?;
+					if let Value::Object(table) = table {
+						for (kind, values) in table.iter() {
+							let Value::Object(values) = values else {
+								continue;
+							};
+							for (field, definition) in values.iter() {
+								definitions.insert(
+									format!("tables/{name}/{kind}/{field}"),
+									definition.clone().as_raw_string(),
+								);
+							}
+						}
+					}
+				}
+				definitions.insert(format!("{kind}/{name}"), definition.clone().as_raw_string());
+			}
+		}
+		Ok(definitions)
+	}
+}
+
+/// Checks whether an error is caused by a namespace, database, or table which
+/// does not exist
+fn is_not_found(e: &anyhow::Error) -> bool {
+	matches!(
+		e.downcast_ref::<Error>(),
+		Some(Error::NsNotFound { .. } | Error::DbNotFound { .. } | Error::TbNotFound { .. })
+	)
+}
+
+/// Returns the error which caused a set of statements to fail, ignoring the
+/// errors of statements which were not executed as a result.
+fn first_error(responses: Vec<Response>) -> Result<()> {
+	let mut errors = responses.into_iter().filter_map(|x| x.result.err()).peekable();
+	let Some(first) = errors.peek().map(|e| e.to_string()) else {
+		return Ok(());
+	};
+	for e in errors {
+		if !matches!(
+			e.downcast_ref::<Error>(),
+			Some(Error::QueryNotExecuted | Error::QueryNotExecutedDetail { .. })
+		) {
+			return Err(e);
+		}
+	}
+	bail!(first)
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+	use temp_dir::TempDir;
+
+	use super::*;
+
+	fn migrations(files: &[(&str, &str)]) -> Vec<Migration> {
+		let dir = TempDir::new().unwrap();
+		for (name, source) in files {
+			std::fs::write(dir.path().join(name), source).unwrap();
+		}
+		load(dir.path()).unwrap()
+	}
+
+	async fn run(
+		ds: &Datastore,
+		migrations: &[Migration],
+		action: Action,
+		dry_run: bool,
+	) -> Result<Report> {
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let cfg = Config {
+			action,
+			target: None,
+			dry_run,
+		};
+		ds.migrate(&sess, migrations.to_vec(), cfg).await
+	}
+
+	#[test]
+	fn load_migrations() {
+		let loaded = migrations(&[
+			("0002_add_email.up.surql", "DEFINE FIELD email ON person;"),
+			("0002_add_email.down.surql", "REMOVE FIELD email ON person;"),
+			("0001_create_person.surql", "DEFINE TABLE person;"),
+			("README.md", "Ignored"),
+		]);
+		assert_eq!(loaded.len(), 2);
+		assert_eq!(
+			(loaded[0].version.as_str(), loaded[0].name.as_str()),
+			("0001", "create_person")
+		);
+		assert!(loaded[0].down.is_none());
+		assert_eq!(loaded[1].down.as_deref(), Some("REMOVE FIELD email ON person;"));
+	}
+
+	#[test]
+	fn load_numeric_versions() {
+		let loaded = migrations(&[
+			("10_tenth.surql", "DEFINE TABLE tenth;"),
+			("2_second.surql", "DEFINE TABLE second;"),
+			("0001_first.surql", "DEFINE TABLE first;"),
+		]);
+		let versions: Vec<_> = loaded.iter().map(|x| x.version.as_str()).collect();
+		assert_eq!(versions, vec!["0001", "2", "10"]);
+		// Versions must be numbers
+		let dir = TempDir::new().unwrap();
+		std::fs::write(dir.path().join("v1_first.surql"), "").unwrap();
+		assert!(load(dir.path()).is_err());
+		// Equal versions of different widths are ambiguous
+		let dir = TempDir::new().unwrap();
+		std::fs::write(dir.path().join("1_first.surql"), "").unwrap();
+		std::fs::write(dir.path().join("01_first.surql"), "").unwrap();
+		assert!(load(dir.path()).is_err());
+	}
+
+	#[tokio::test]
+	async fn migrate_locked() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let loaded = migrations(&[("0001_create_person.surql", "DEFINE TABLE person;")]);
+		// Another run holds the lock
+		ds.execute("CREATE _migrations:lock", &sess, None).await.unwrap();
+		let error = run(&ds, &loaded, Action::Up, false).await.unwrap_err();
+		assert!(matches!(error.downcast_ref(), Some(Error::MigrationLocked)), "{error}");
+		// The state can still be reported, and is not affected by the lock
+		let report = run(&ds, &loaded, Action::Status, false).await.unwrap();
+		assert_eq!(report.status.len(), 1);
+		assert_eq!(report.status[0].state, State::Pending);
+		// The lock is released after each run
+		ds.execute("DELETE _migrations:lock", &sess, None).await.unwrap();
+		let report = run(&ds, &loaded, Action::Up, false).await.unwrap();
+		assert_eq!(report.applied, vec!["0001"]);
+		let report = run(&ds, &loaded, Action::Up, false).await.unwrap();
+		assert!(report.applied.is_empty());
+		// Concurrent runs never apply the same migration twice
+		let ds = Datastore::new("memory").await.unwrap();
+		let (a, b) = tokio::join!(
+			run(&ds, &loaded, Action::Up, false),
+			run(&ds, &loaded, Action::Up, false)
+		);
+		let applied: usize =
+			[a, b].into_iter().filter_map(Result::ok).map(|x| x.applied.len()).sum();
+		assert_eq!(applied, 1);
+	}
+
+	#[tokio::test]
+	async fn migrate_up_and_down() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let loaded = migrations(&[
+			("0001_create_person.surql", "DEFINE TABLE person;"),
+			("0002_add_email.up.surql", "DEFINE FIELD email ON person;"),
+			("0002_add_email.down.surql", "REMOVE FIELD email ON person;"),
+		]);
+		// A dry run reports the changes without applying them
+		let report = run(&ds, &loaded, Action::Up, true).await.unwrap();
+		assert_eq!(report.applied, vec!["0001", "0002"]);
+		let diff = report.diff.unwrap();
+		assert_eq!(diff.added.len(), 2, "{diff:?}");
+		let report = run(&ds, &loaded, Action::Status, false).await.unwrap();
+		assert!(report.status.iter().all(|x| x.state == State::Pending));
+		// Apply the migrations
+		let report = run(&ds, &loaded, Action::Up, false).await.unwrap();
+		assert_eq!(report.applied, vec!["0001", "0002"]);
+		let report = run(&ds, &loaded, Action::Up, false).await.unwrap();
+		assert!(report.applied.is_empty());
+		let mut res = ds.execute("INFO FOR TABLE person", &sess, None).await.unwrap();
+		assert!(res.remove(0).output().unwrap().to_string().contains("email"));
+		// Revert the most recent migration
+		let report = run(&ds, &loaded, Action::Down, false).await.unwrap();
+		assert_eq!(report.reverted, vec!["0002"]);
+		let mut res = ds.execute("INFO FOR TABLE person", &sess, None).await.unwrap();
+		assert!(!res.remove(0).output().unwrap().to_string().contains("email"));
+		// The first migration can not be reverted
+		assert!(run(&ds, &loaded, Action::Down, false).await.is_err());
+	}
+
+	#[tokio::test]
+	async fn migrate_failures() {
+		let ds = Datastore::new("memory").await.unwrap();
+		// A failed migration is not applied, or recorded
+		let failing = migrations(&[("0001_failing.surql", "DEFINE TABLE person; THROW 'failed';")]);
+		let error = run(&ds, &failing, Action::Up, false).await.unwrap_err();
+		assert!(error.to_string().contains("failed"), "{error}");
+		let report = run(&ds, &failing, Action::Status, false).await.unwrap();
+		assert_eq!(report.status[0].state, State::Pending);
+		// A modified migration prevents any changes
+		let loaded = migrations(&[("0001_create.surql", "DEFINE TABLE person;")]);
+		run(&ds, &loaded, Action::Up, false).await.unwrap();
+		let modified = migrations(&[
+			("0001_create.surql", "DEFINE TABLE people;"),
+			("0002_next.surql", "DEFINE TABLE other;"),
+		]);
+		let report = run(&ds, &modified, Action::Status, false).await.unwrap();
+		assert_eq!(report.status[0].state, State::Modified);
+		assert!(run(&ds, &modified, Action::Up, false).await.is_err());
+		// Transaction statements are not allowed
+		let ds = Datastore::new("memory").await.unwrap();
+		let nested = migrations(&[("0001_nested.surql", "BEGIN; DEFINE TABLE person; COMMIT;")]);
+		let error = run(&ds, &nested, Action::Up, false).await.unwrap_err();
+		assert!(error.to_string().contains("transaction"), "{error}");
+	}
+}
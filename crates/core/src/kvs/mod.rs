@@ -17,6 +17,7 @@
 //! - `mem`: in-memory database
 
 pub mod export;
+pub mod migrate;
 
 mod api;
 mod batch;
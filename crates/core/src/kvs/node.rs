@@ -5,6 +5,7 @@ use anyhow::Result;
 use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, NodeProvider, TableProvider};
 use crate::catalog::{NodeLiveQuery, SubscriptionDefinition};
 use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::dbs::Notification;
 use crate::dbs::node::Node;
 use crate::err::Error;
 use crate::kvs::LockType::*;
@@ -191,6 +192,14 @@ impl Datastore {
 					yield_now!();
 				}
 			}
+			{
+				// Log the notification deletion
+				trace!(target: TARGET, id = %id, "Deleting queued notifications for node");
+				// Delete the notifications queued for this node
+				let beg = crate::key::node::nq::prefix(*id)?;
+				let end = crate::key::node::nq::suffix(*id)?;
+				catch!(txn, txn.delr(beg..end).await);
+			}
 			{
 				// Log the node deletion
 				trace!(target: TARGET, id = %id, "Deleting node from the cluster");
@@ -206,6 +215,72 @@ impl Datastore {
 		Ok(())
 	}
 
+	/// Delivers notifications which were queued for this node.
+	///
+	/// This function should be run periodically at an interval.
+	///
+	/// When a write on another node in the cluster triggers a live query which
+	/// belongs to this node, the notification is queued in the datastore for
+	/// this node. This function fetches the queued notifications, sends them
+	/// in order to the notification channel of this datastore, and then
+	/// removes the notifications which were sent from the queue. The queue is
+	/// checked with a read-only transaction, so that a write transaction is
+	/// only opened when there are notifications to remove. A notification
+	/// which could not be sent remains queued, and a notification may be sent
+	/// again if its removal fails to commit.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::node", skip(self))]
+	pub async fn deliver_notifications(&self) -> Result<()> {
+		// Check that notifications are enabled
+		let Some(channel) = self.notification_sender() else {
+			return Ok(());
+		};
+		// Log when this method is run
+		trace!(target: TARGET, "Delivering queued notifications for node");
+		// Fetch the queued notifications
+		let beg = crate::key::node::nq::prefix(self.id())?;
+		let end = crate::key::node::nq::suffix(self.id())?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			// Fetch the next batch of keys and values
+			let res = {
+				let txn = self.transaction(Read, Optimistic).await?;
+				let max = *NORMAL_FETCH_SIZE;
+				let res = catch!(txn, txn.batch_keys_vals(rng, max, None).await);
+				catch!(txn, txn.cancel().await);
+				res
+			};
+			next = res.next;
+			// Send the notifications in order
+			let mut sent = Vec::with_capacity(res.result.len());
+			let mut closed = false;
+			for (k, v) in res.result.into_iter() {
+				// Decode the queued notification
+				let notification: Notification = KVValue::kv_decode_value(v)?;
+				if channel.send(notification).await.is_err() {
+					closed = true;
+					break;
+				}
+				sent.push(k);
+			}
+			// Remove the sent notifications from the queue
+			if !sent.is_empty() {
+				let txn = self.transaction(Write, Optimistic).await?;
+				for k in sent.iter() {
+					catch!(txn, txn.clr(k).await);
+				}
+				catch!(txn, txn.commit().await);
+			}
+			// Stop when the channel has been closed
+			if closed {
+				break;
+			}
+			// Pause and yield execution
+			yield_now!();
+		}
+		// Everything was successful
+		Ok(())
+	}
+
 	/// Clean up all other miscellaneous data.
 	///
 	/// This function should be run periodically at an interval.
@@ -194,3 +194,13 @@ pub(super) static ROCKSDB_DELETION_FACTORY_DELETE_COUNT: LazyLock<usize> =
 /// The ratio of deletions to track in the window (default: 0.5)
 pub(super) static ROCKSDB_DELETION_FACTORY_RATIO: LazyLock<f64> =
 	lazy_env_parse!("SURREAL_ROCKSDB_DELETION_FACTORY_RATIO", f64, 0.5);
+
+/// The number of seconds for which historical versions are retained, when
+/// versions are enabled, where 0 retains all versions (default: 0)
+pub(super) static ROCKSDB_VERSIONED_RETENTION: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_ROCKSDB_VERSIONED_RETENTION", u64, 0);
+
+/// The interval in seconds between removing expired historical versions
+/// (default: 300)
+pub(super) static ROCKSDB_VERSIONED_GC_INTERVAL: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_ROCKSDB_VERSIONED_GC_INTERVAL", u64, 300);
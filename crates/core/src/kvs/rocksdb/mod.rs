@@ -1,28 +1,40 @@
 #![cfg(feature = "kv-rocksdb")]
 
 mod cnf;
+mod versions;
 
 use std::ops::Range;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::{Result, bail, ensure};
 use rocksdb::{
-	BlockBasedOptions, Cache, DBCompactionStyle, DBCompressionType, FlushOptions, LogLevel,
-	OptimisticTransactionDB, OptimisticTransactionOptions, Options, ReadOptions, WriteOptions,
+	BlockBasedOptions, Cache, ColumnFamily, ColumnFamilyDescriptor, DBCompactionStyle,
+	DBCompressionType, DBRawIteratorWithThreadMode, FlushOptions, LogLevel,
+	OptimisticTransactionDB, OptimisticTransactionOptions, Options, ReadOptions,
+	WriteBatchWithTransaction, WriteOptions,
 };
 
 use super::savepoint::SavePoints;
 use crate::err::Error;
 use crate::key::debug::Sprintable;
-use crate::kvs::{Check, Key, Val};
+use crate::kvs::{Check, Key, Val, Version};
 
 const TARGET: &str = "surrealdb::core::kvs::rocksdb";
 
+/// The current time in nanoseconds, from which the versions of writes are issued
+fn now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or_default()
+}
+
 pub struct Datastore {
 	db: Pin<Arc<OptimisticTransactionDB>>,
+	/// Are historical versions of keys retained?
+	versioned: bool,
+	/// The clock which issues the versions of writes
+	clock: Arc<versions::Clock>,
 }
 
 pub struct Transaction {
@@ -36,6 +48,14 @@ pub struct Transaction {
 	inner: Option<rocksdb::Transaction<'static, OptimisticTransactionDB>>,
 	/// The read options containing the Snapshot
 	ro: ReadOptions,
+	/// Are historical versions of keys retained?
+	versioned: bool,
+	/// The writes to record as versions when committing
+	versions: Vec<(Key, Option<Val>)>,
+	/// The clock which issues the versions of writes
+	clock: Arc<versions::Clock>,
+	/// The number of pending versions at each save point
+	save_points: Vec<usize>,
 	// The above, supposedly 'static transaction
 	// actually points here, so we need to ensure
 	// the memory is kept alive. This pointer must
@@ -63,7 +83,7 @@ impl Drop for Transaction {
 
 impl Datastore {
 	/// Open a new database
-	pub(crate) async fn new(path: &str) -> Result<Datastore> {
+	pub(crate) async fn new(path: &str, versioned: bool) -> Result<Datastore> {
 		// Configure custom options
 		let mut opts = Options::default();
 		// Ensure we use fdatasync
@@ -192,6 +212,12 @@ impl Datastore {
 				bail!(Error::Ds(format!("Invalid storage engine log level specified: {l}")));
 			}
 		});
+		// The column family for historical versions is always opened, so
+		// that a datastore can be opened both with and without versions.
+		let open = |opts: &Options| {
+			let cfs = vec![ColumnFamilyDescriptor::new(versions::VERSIONS, opts.clone())];
+			OptimisticTransactionDB::open_cf_descriptors(opts, path, cfs)
+		};
 		// Configure background WAL flush behaviour
 		let db = match *cnf::ROCKSDB_BACKGROUND_FLUSH {
 			// Beckground flush is disabled which
@@ -203,7 +229,7 @@ impl Datastore {
 				// Enable manual WAL flush
 				opts.set_manual_wal_flush(false);
 				// Create the optimistic datastore
-				Arc::pin(OptimisticTransactionDB::open(&opts, path)?)
+				Arc::pin(open(&opts)?)
 			}
 			// Background flush is enabled so we
 			// spawn a background worker thread to
@@ -214,7 +240,7 @@ impl Datastore {
 				// Enable manual WAL flush
 				opts.set_manual_wal_flush(true);
 				// Create the optimistic datastore
-				let db = Arc::pin(OptimisticTransactionDB::open(&opts, path)?);
+				let db = Arc::pin(open(&opts)?);
 				// Clone the database reference
 				let dbc = db.clone();
 				// Create a new background thread
@@ -234,12 +260,90 @@ impl Datastore {
 				db
 			}
 		};
+		// Configure the retention of historical versions
+		if versioned {
+			match *cnf::ROCKSDB_VERSIONED_RETENTION {
+				// All historical versions are retained
+				0 => {
+					info!(target: TARGET, "Versions enabled, retaining all versions");
+				}
+				// Spawn a background worker thread to
+				// remove expired versions periodically.
+				retention => {
+					info!(target: TARGET, "Versions enabled, retaining versions for {retention}s");
+					// Clone the database reference
+					let dbc = db.clone();
+					// Create a new background thread
+					thread::spawn(move || {
+						loop {
+							// Get the specified interval
+							let wait = *cnf::ROCKSDB_VERSIONED_GC_INTERVAL;
+							// Wait for the specified interval
+							thread::sleep(Duration::from_secs(wait));
+							// Remove the expired versions
+							let cutoff =
+								now().saturating_sub(retention.saturating_mul(1_000_000_000));
+							match Self::remove_versions(&dbc, cutoff) {
+								Ok(count) => {
+									debug!(target: TARGET, "Removed {count} expired versions")
+								}
+								Err(err) => error!("Failed to remove expired versions: {err}"),
+							}
+						}
+					});
+				}
+			}
+		}
 		// Return the datastore
 		Ok(Datastore {
 			db,
+			versioned,
+			clock: Arc::new(versions::Clock::default()),
 		})
 	}
 
+	/// Remove the historical versions which are older than the cutoff.
+	///
+	/// The newest version of each key which is older than the cutoff is kept,
+	/// unless it is a deletion, so that reads at any version after the cutoff
+	/// continue to return the value which was current at that time.
+	fn remove_versions(db: &OptimisticTransactionDB, cutoff: u64) -> Result<usize> {
+		// Get the column family for historical versions
+		let Some(cf) = db.cf_handle(versions::VERSIONS) else {
+			return Ok(0);
+		};
+		// Iterate over all historical versions
+		let mut iter = db.raw_iterator_cf(cf);
+		iter.seek_to_first();
+		// The key for which an older version was kept
+		let mut kept: Option<Key> = None;
+		let mut batch = WriteBatchWithTransaction::<true>::default();
+		let mut count = 0;
+		while let Some((k, v)) = iter.item() {
+			if let Some((key, version)) = versions::decode(k) {
+				if version < cutoff {
+					// Only the newest expired version of a key is kept
+					if kept.as_ref() == Some(&key) || versions::decode_value(v).is_none() {
+						batch.delete_cf(cf, k);
+						count += 1;
+					}
+					kept = Some(key);
+				}
+			}
+			// Write the removals in batches
+			if batch.len() >= 1000 {
+				db.write(std::mem::take(&mut batch))?;
+			}
+			iter.next();
+		}
+		iter.status()?;
+		// Write the remaining removals
+		if !batch.is_empty() {
+			db.write(batch)?;
+		}
+		Ok(count)
+	}
+
 	/// Shutdown the database
 	pub(crate) async fn shutdown(&self) -> Result<()> {
 		// Create new flush options
@@ -301,6 +405,10 @@ impl Datastore {
 			check,
 			inner: Some(inner),
 			ro,
+			versioned: self.versioned,
+			versions: Vec::new(),
+			clock: self.clock.clone(),
+			save_points: Vec::new(),
 			_db: self.db.clone(),
 		}))
 	}
@@ -354,6 +462,20 @@ impl super::api::Transaction for Transaction {
 		ensure!(self.write, Error::TxReadonly);
 		// Mark this transaction as done
 		self.done = true;
+		// Record the versions of the written keys
+		let pending = std::mem::take(&mut self.versions);
+		if !pending.is_empty() {
+			let cf = self.versions_cf()?;
+			let inner = self.inner.as_ref().unwrap();
+			let version = self.clock.next(now());
+			for (key, val) in pending {
+				inner.put_cf(
+					cf,
+					versions::encode(&key, version),
+					versions::encode_value(val.as_deref()),
+				)?;
+			}
+		}
 		// Commit this transaction
 		self.inner.take().unwrap().commit()?;
 		// Continue
@@ -363,12 +485,15 @@ impl super::api::Transaction for Transaction {
 	/// Check if a key exists
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
 	async fn exists(&mut self, key: Key, version: Option<u64>) -> Result<bool> {
-		// RocksDB does not support versioned queries.
-		ensure!(version.is_none(), Error::UnsupportedVersionedQueries);
+		// Check to see if versioned queries are supported
+		ensure!(version.is_none() || self.versioned, Error::UnsupportedVersionedQueries);
 		// Check to see if transaction is closed
 		ensure!(!self.done, Error::TxFinished);
 		// Get the key
-		let res = self.inner.as_ref().unwrap().get_pinned_opt(key, &self.ro)?.is_some();
+		let res = match version {
+			Some(ts) => self.get_at_version(&key, ts)?.is_some(),
+			None => self.inner.as_ref().unwrap().get_pinned_opt(key, &self.ro)?.is_some(),
+		};
 		// Return result
 		Ok(res)
 	}
@@ -376,12 +501,15 @@ impl super::api::Transaction for Transaction {
 	/// Fetch a key from the database
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
 	async fn get(&mut self, key: Key, version: Option<u64>) -> Result<Option<Val>> {
-		// RocksDB does not support versioned queries.
-		ensure!(version.is_none(), Error::UnsupportedVersionedQueries);
+		// Check to see if versioned queries are supported
+		ensure!(version.is_none() || self.versioned, Error::UnsupportedVersionedQueries);
 		// Check to see if transaction is closed
 		ensure!(!self.done, Error::TxFinished);
 		// Get the key
-		let res = self.inner.as_ref().unwrap().get_opt(key, &self.ro)?;
+		let res = match version {
+			Some(ts) => self.get_at_version(&key, ts)?,
+			None => self.inner.as_ref().unwrap().get_opt(key, &self.ro)?,
+		};
 		// Return result
 		Ok(res)
 	}
@@ -404,14 +532,20 @@ impl super::api::Transaction for Transaction {
 	/// Insert or update a key in the database
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
 	async fn set(&mut self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
-		// RocksDB does not support versioned queries.
-		ensure!(version.is_none(), Error::UnsupportedVersionedQueries);
+		// Check to see if versioned queries are supported
+		ensure!(version.is_none() || self.versioned, Error::UnsupportedVersionedQueries);
 		// Check to see if transaction is closed
 		ensure!(!self.done, Error::TxFinished);
 		// Check to see if transaction is writable
 		ensure!(self.write, Error::TxReadonly);
 		// Set the key
-		self.inner.as_ref().unwrap().put(key, val)?;
+		match version {
+			Some(ts) => self.set_at_version(key, val, ts)?,
+			None => {
+				self.inner.as_ref().unwrap().put(&key, &val)?;
+				self.record(key, Some(val));
+			}
+		}
 		// Return result
 		Ok(())
 	}
@@ -419,17 +553,23 @@ impl super::api::Transaction for Transaction {
 	/// Insert a key if it doesn't exist in the database
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
 	async fn put(&mut self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
-		// RocksDB does not support versioned queries.
-		ensure!(version.is_none(), Error::UnsupportedVersionedQueries);
+		// Check to see if versioned queries are supported
+		ensure!(version.is_none() || self.versioned, Error::UnsupportedVersionedQueries);
 		// Check to see if transaction is closed
 		ensure!(!self.done, Error::TxFinished);
 		// Check to see if transaction is writable
 		ensure!(self.write, Error::TxReadonly);
+		// Set the key at the version
+		if let Some(ts) = version {
+			return self.set_at_version(key, val, ts);
+		}
 		// Set the key if empty
 		match self.inner.as_ref().unwrap().get_pinned_opt(&key, &self.ro)? {
-			None => self.inner.as_ref().unwrap().put(key, val)?,
+			None => self.inner.as_ref().unwrap().put(&key, &val)?,
 			_ => bail!(Error::TxKeyAlreadyExists),
 		};
+		// Record the version
+		self.record(key, Some(val));
 		// Return result
 		Ok(())
 	}
@@ -443,10 +583,12 @@ impl super::api::Transaction for Transaction {
 		ensure!(self.write, Error::TxReadonly);
 		// Set the key if empty
 		match (self.inner.as_ref().unwrap().get_pinned_opt(&key, &self.ro)?, chk) {
-			(Some(v), Some(w)) if v.eq(&w) => self.inner.as_ref().unwrap().put(key, val)?,
-			(None, None) => self.inner.as_ref().unwrap().put(key, val)?,
+			(Some(v), Some(w)) if v.eq(&w) => self.inner.as_ref().unwrap().put(&key, &val)?,
+			(None, None) => self.inner.as_ref().unwrap().put(&key, &val)?,
 			_ => bail!(Error::TxConditionNotMet),
 		};
+		// Record the version
+		self.record(key, Some(val));
 		// Return result
 		Ok(())
 	}
@@ -459,7 +601,9 @@ impl super::api::Transaction for Transaction {
 		// Check to see if transaction is writable
 		ensure!(self.write, Error::TxReadonly);
 		// Remove the key
-		self.inner.as_ref().unwrap().delete(key)?;
+		self.inner.as_ref().unwrap().delete(&key)?;
+		// Record the deletion
+		self.record(key, None);
 		// Return result
 		Ok(())
 	}
@@ -473,10 +617,46 @@ impl super::api::Transaction for Transaction {
 		ensure!(self.write, Error::TxReadonly);
 		// Delete the key if valid
 		match (self.inner.as_ref().unwrap().get_pinned_opt(&key, &self.ro)?, chk) {
-			(Some(v), Some(w)) if v.eq(&w) => self.inner.as_ref().unwrap().delete(key)?,
-			(None, None) => self.inner.as_ref().unwrap().delete(key)?,
+			(Some(v), Some(w)) if v.eq(&w) => self.inner.as_ref().unwrap().delete(&key)?,
+			(None, None) => self.inner.as_ref().unwrap().delete(&key)?,
+			_ => bail!(Error::TxConditionNotMet),
+		};
+		// Record the deletion
+		self.record(key, None);
+		// Return result
+		Ok(())
+	}
+
+	/// Delete all versions of a key
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn clr(&mut self, key: Key) -> Result<()> {
+		// Check to see if transaction is closed
+		ensure!(!self.done, Error::TxFinished);
+		// Check to see if transaction is writable
+		ensure!(self.write, Error::TxReadonly);
+		// Remove the key
+		self.inner.as_ref().unwrap().delete(&key)?;
+		// Remove the historical versions
+		self.clear_versions(&key)?;
+		// Return result
+		Ok(())
+	}
+
+	/// Delete all versions of a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn clrc(&mut self, key: Key, chk: Option<Val>) -> Result<()> {
+		// Check to see if transaction is closed
+		ensure!(!self.done, Error::TxFinished);
+		// Check to see if transaction is writable
+		ensure!(self.write, Error::TxReadonly);
+		// Delete the key if valid
+		match (self.inner.as_ref().unwrap().get_pinned_opt(&key, &self.ro)?, chk) {
+			(Some(v), Some(w)) if v.eq(&w) => self.inner.as_ref().unwrap().delete(&key)?,
+			(None, None) => self.inner.as_ref().unwrap().delete(&key)?,
 			_ => bail!(Error::TxConditionNotMet),
 		};
+		// Remove the historical versions
+		self.clear_versions(&key)?;
 		// Return result
 		Ok(())
 	}
@@ -490,6 +670,11 @@ impl super::api::Transaction for Transaction {
 		version: Option<u64>,
 	) -> Result<Vec<Key>> {
 		let rng = self.prepare_scan(rng, version).await?;
+		// Scan the historical versions
+		if let Some(ts) = version {
+			let res = self.scan_at_version(rng, limit, ts, false).await?;
+			return Ok(res.into_iter().map(|(k, _)| k).collect());
+		}
 		// Execute on the blocking threadpool
 		let res = affinitypool::spawn_local(move || {
 			// Create result set
@@ -541,6 +726,11 @@ impl super::api::Transaction for Transaction {
 		version: Option<u64>,
 	) -> Result<Vec<Key>> {
 		let rng = self.prepare_scan(rng, version).await?;
+		// Scan the historical versions
+		if let Some(ts) = version {
+			let res = self.scan_at_version(rng, limit, ts, true).await?;
+			return Ok(res.into_iter().map(|(k, _)| k).collect());
+		}
 		// Get the transaction
 		let inner = self.inner.as_ref().unwrap();
 		// Create result set
@@ -586,6 +776,10 @@ impl super::api::Transaction for Transaction {
 		version: Option<u64>,
 	) -> Result<Vec<(Key, Val)>> {
 		let rng = self.prepare_scan(rng, version).await?;
+		// Scan the historical versions
+		if let Some(ts) = version {
+			return self.scan_at_version(rng, limit, ts, false).await;
+		}
 		// Execute on the blocking threadpool
 		let res = affinitypool::spawn_local(move || {
 			// Create result set
@@ -636,6 +830,10 @@ impl super::api::Transaction for Transaction {
 		version: Option<u64>,
 	) -> Result<Vec<(Key, Val)>> {
 		let rng = self.prepare_scan(rng, version).await?;
+		// Scan the historical versions
+		if let Some(ts) = version {
+			return self.scan_at_version(rng, limit, ts, true).await;
+		}
 		// Get the transaction
 		let inner = self.inner.as_ref().unwrap();
 		// Create result set
@@ -681,6 +879,8 @@ impl super::api::Transaction for Transaction {
 		let inner = self.inner.as_ref().unwrap();
 		// Set the save point
 		inner.set_savepoint();
+		// Save the pending versions
+		self.save_points.push(self.versions.len());
 	}
 
 	async fn rollback_to_save_point(&mut self) -> Result<()> {
@@ -688,22 +888,231 @@ impl super::api::Transaction for Transaction {
 		let inner = self.inner.as_ref().unwrap();
 		// Rollback
 		inner.rollback_to_savepoint()?;
+		// Rollback the pending versions
+		if let Some(len) = self.save_points.pop() {
+			self.versions.truncate(len);
+		}
 		//
 		Ok(())
 	}
 
 	fn release_last_save_point(&mut self) -> Result<()> {
+		self.save_points.pop();
 		Ok(())
 	}
+
+	/// Retrieve all the versions from a range of keys from the databases
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan_all_versions(
+		&mut self,
+		rng: Range<Key>,
+		limit: u32,
+	) -> Result<Vec<(Key, Val, Version, bool)>> {
+		// Check to see if versioned queries are supported
+		ensure!(self.versioned, Error::UnsupportedVersionedQueries);
+		// Check to see if transaction is closed
+		ensure!(!self.done, Error::TxFinished);
+		// Execute on the blocking threadpool
+		affinitypool::spawn_local(move || -> Result<_> {
+			// Create result set
+			let mut res = vec![];
+			// Create the iterator
+			let mut iter =
+				self.versions_iterator(versions::start(&rng.start), versions::start(&rng.end))?;
+			// Seek to the start key
+			iter.seek(versions::start(&rng.start));
+			// Check the scan limit
+			while let Some((k, v)) = iter.item() {
+				// Decode the key and version
+				let Some((key, version)) = versions::decode(k) else {
+					break;
+				};
+				// Only stop once all versions of a key are returned
+				if res.len() >= limit as usize && res.last().is_none_or(|(k, ..)| *k != key) {
+					break;
+				}
+				// Add the version to the result set
+				match versions::decode_value(v) {
+					Some(v) => res.push((key, v, version, false)),
+					None => res.push((key, vec![], version, true)),
+				}
+				iter.next();
+			}
+			// Check the iterator status
+			iter.status()?;
+			// Return result
+			Ok(res)
+		})
+		.await
+	}
 }
 
 impl Transaction {
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
 	async fn prepare_scan(&mut self, rng: Range<Key>, version: Option<u64>) -> Result<Range<Key>> {
-		// RocksDB does not support versioned queries.
-		ensure!(version.is_none(), Error::UnsupportedVersionedQueries);
+		// Check to see if versioned queries are supported
+		ensure!(version.is_none() || self.versioned, Error::UnsupportedVersionedQueries);
 		// Check to see if transaction is closed
 		ensure!(!self.done, Error::TxFinished);
 		Ok(rng)
 	}
+
+	/// Get the column family for historical versions
+	fn versions_cf(&self) -> Result<&ColumnFamily> {
+		self._db.cf_handle(versions::VERSIONS).ok_or_else(|| {
+			anyhow::Error::new(Error::Ds("Missing column family for versions".into()))
+		})
+	}
+
+	/// Create an iterator over the historical versions between two bounds
+	fn versions_iterator(
+		&self,
+		beg: Vec<u8>,
+		end: Vec<u8>,
+	) -> Result<
+		DBRawIteratorWithThreadMode<'_, rocksdb::Transaction<'static, OptimisticTransactionDB>>,
+	> {
+		// Get the transaction
+		let inner = self.inner.as_ref().unwrap();
+		// Set the ReadOptions with the snapshot
+		let mut ro = ReadOptions::default();
+		ro.set_snapshot(&inner.snapshot());
+		ro.set_iterate_lower_bound(beg);
+		ro.set_iterate_upper_bound(end);
+		ro.set_async_io(true);
+		ro.fill_cache(true);
+		// Create the iterator
+		Ok(inner.raw_iterator_cf_opt(self.versions_cf()?, ro))
+	}
+
+	/// Record a write, so that it is stored as a version when committing
+	fn record(&mut self, key: Key, val: Option<Val>) {
+		if self.versioned {
+			self.versions.push((key, val));
+		}
+	}
+
+	/// Write a value at a specific version of a key
+	fn set_at_version(&self, key: Key, val: Val, version: u64) -> Result<()> {
+		// Get the transaction
+		let inner = self.inner.as_ref().unwrap();
+		// Check if this is the newest version of the key
+		let newest = {
+			let mut iter = self.versions_iterator(versions::start(&key), versions::after(&key))?;
+			iter.seek(versions::start(&key));
+			iter.key().and_then(versions::decode).is_none_or(|(_, v)| v <= version)
+		};
+		// Store the historical version
+		inner.put_cf(
+			self.versions_cf()?,
+			versions::encode(&key, version),
+			versions::encode_value(Some(&val)),
+		)?;
+		// Update the current value if this is the newest version
+		if newest {
+			inner.put(key, val)?;
+		}
+		Ok(())
+	}
+
+	/// Remove all the historical versions of a key
+	fn clear_versions(&mut self, key: &Key) -> Result<()> {
+		// Nothing is stored if versions are disabled
+		if !self.versioned {
+			return Ok(());
+		}
+		// Remove any pending versions of the key
+		self.versions.retain(|(k, _)| k != key);
+		for len in self.save_points.iter_mut() {
+			*len = (*len).min(self.versions.len());
+		}
+		// Remove the stored versions of the key
+		let inner = self.inner.as_ref().unwrap();
+		let cf = self.versions_cf()?;
+		let mut iter = self.versions_iterator(versions::start(key), versions::after(key))?;
+		iter.seek(versions::start(key));
+		while let Some(k) = iter.key() {
+			if versions::decode(k).is_some_and(|(k, _)| k == *key) {
+				inner.delete_cf(cf, k)?;
+			}
+			iter.next();
+		}
+		iter.status()?;
+		Ok(())
+	}
+
+	/// Fetch the newest value of a key at or before a version
+	fn get_at_version(&self, key: &Key, version: u64) -> Result<Option<Val>> {
+		// Create the iterator
+		let mut iter = self.versions_iterator(versions::start(key), versions::after(key))?;
+		// Find the newest value at or before the version
+		let res = Self::read_version(&mut iter, key, version);
+		// Check the iterator status
+		iter.status()?;
+		// Return result
+		Ok(res)
+	}
+
+	/// Retrieve a range of keys and their values at a version
+	async fn scan_at_version(
+		&mut self,
+		rng: Range<Key>,
+		limit: u32,
+		version: u64,
+		reverse: bool,
+	) -> Result<Vec<(Key, Val)>> {
+		// Execute on the blocking threadpool
+		affinitypool::spawn_local(move || -> Result<_> {
+			// Create result set
+			let mut res = vec![];
+			// Create the iterator
+			let mut iter =
+				self.versions_iterator(versions::start(&rng.start), versions::start(&rng.end))?;
+			// Seek to the first key
+			match reverse {
+				false => iter.seek(versions::start(&rng.start)),
+				true => iter.seek_for_prev(versions::start(&rng.end)),
+			}
+			// Check the scan limit
+			while res.len() < limit as usize {
+				// Get the key of the current version
+				let Some((key, _)) = iter.key().and_then(versions::decode) else {
+					break;
+				};
+				// Find the newest value at or before the version
+				let val = Self::read_version(&mut iter, &key, version);
+				// Skip the remaining versions of this key
+				match reverse {
+					false => iter.seek(versions::after(&key)),
+					true => iter.seek_for_prev(versions::start(&key)),
+				}
+				// Keys which were deleted are not returned
+				if let Some(val) = val {
+					res.push((key, val));
+				}
+			}
+			// Check the iterator status
+			iter.status()?;
+			// Return result
+			Ok(res)
+		})
+		.await
+	}
+
+	/// Seek to the newest value of a key at or before a version
+	fn read_version(
+		iter: &mut DBRawIteratorWithThreadMode<
+			'_,
+			rocksdb::Transaction<'static, OptimisticTransactionDB>,
+		>,
+		key: &Key,
+		version: u64,
+	) -> Option<Val> {
+		iter.seek(versions::encode(key, version));
+		let (k, v) = iter.item()?;
+		match versions::decode(k) {
+			Some((k, _)) if k == *key => versions::decode_value(v),
+			_ => None,
+		}
+	}
 }
@@ -0,0 +1,156 @@
+//! The encoding of the historical versions of keys, which are stored in a
+//! separate column family when versions are enabled.
+//!
+//! Each version is stored under the escaped key, followed by the inverted
+//! version. Escaping ensures that the encoding of a key is never a prefix of
+//! the encoding of another key, and that the ordering of keys is preserved.
+//! Inverting the version ensures that the versions of a key are ordered from
+//! the newest to the oldest, so that the newest version at or before a point
+//! in time can be found with a single seek.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::kvs::{Key, Val};
+
+/// The name of the column family which stores the historical versions
+pub(super) const VERSIONS: &str = "versions";
+
+/// The marker which precedes a value which was set
+const VALUE: u8 = 1;
+
+/// The marker which signifies that a key was deleted
+const TOMBSTONE: u8 = 0;
+
+/// The lowest bound for the versions of a key
+pub(super) fn start(key: &[u8]) -> Vec<u8> {
+	let mut out = Vec::with_capacity(key.len() + 10);
+	for &b in key {
+		out.push(b);
+		if b == 0 {
+			out.push(0xff);
+		}
+	}
+	out
+}
+
+/// The upper bound for the versions of a key
+pub(super) fn after(key: &[u8]) -> Vec<u8> {
+	let mut out = start(key);
+	out.extend([0, 1]);
+	out
+}
+
+/// The encoding of a specific version of a key
+pub(super) fn encode(key: &[u8], version: u64) -> Vec<u8> {
+	let mut out = start(key);
+	out.extend([0, 0]);
+	out.extend((u64::MAX - version).to_be_bytes());
+	out
+}
+
+/// Decodes the key and version from an encoded version key
+pub(super) fn decode(enc: &[u8]) -> Option<(Key, u64)> {
+	let mut key = Vec::with_capacity(enc.len());
+	let mut i = 0;
+	while i + 1 < enc.len() {
+		match (enc[i], enc[i + 1]) {
+			(0, 0) => {
+				let version = enc.get(i + 2..)?.try_into().ok()?;
+				return Some((key, u64::MAX - u64::from_be_bytes(version)));
+			}
+			(0, 0xff) => {
+				key.push(0);
+				i += 2;
+			}
+			(0, _) => return None,
+			(b, _) => {
+				key.push(b);
+				i += 1;
+			}
+		}
+	}
+	None
+}
+
+/// Encodes a value, or a deletion if no value is specified
+pub(super) fn encode_value(val: Option<&[u8]>) -> Vec<u8> {
+	match val {
+		Some(val) => {
+			let mut out = Vec::with_capacity(val.len() + 1);
+			out.push(VALUE);
+			out.extend_from_slice(val);
+			out
+		}
+		None => vec![TOMBSTONE],
+	}
+}
+
+/// Decodes a value, returning `None` if the key was deleted
+pub(super) fn decode_value(enc: &[u8]) -> Option<Val> {
+	match enc.split_first() {
+		Some((&VALUE, val)) => Some(val.to_vec()),
+		_ => None,
+	}
+}
+
+/// Issues the versions of writes, ensuring that versions never go backwards
+/// when the system clock is adjusted, and that no two commits share a version.
+#[derive(Default)]
+pub(super) struct Clock(AtomicU64);
+
+impl Clock {
+	/// Get the next version from the current time in nanoseconds, clamped to
+	/// be later than the last version which was issued
+	pub(super) fn next(&self, now: u64) -> u64 {
+		let next = |last: u64| now.max(last.saturating_add(1));
+		match self.0.fetch_update(Ordering::AcqRel, Ordering::Acquire, |last| Some(next(last))) {
+			Ok(last) | Err(last) => next(last),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_roundtrip() {
+		for key in [&b""[..], b"abc", b"a\x00b", b"\x00\x00", b"\xff\x00"] {
+			let enc = encode(key, 42);
+			assert_eq!(decode(&enc), Some((key.to_vec(), 42)));
+			assert!(start(key) < enc);
+			assert!(enc < after(key));
+		}
+	}
+
+	#[test]
+	fn test_ordering() {
+		// Newer versions of a key sort before older versions
+		assert!(encode(b"a", 2) < encode(b"a", 1));
+		// The versions of a key sort before the versions of later keys
+		let keys = [&b"a"[..], b"a\x00", b"a\x00\x00", b"a\x01", b"ab", b"b"];
+		for pair in keys.windows(2) {
+			assert!(encode(pair[0], 0) < encode(pair[1], u64::MAX));
+			assert!(after(pair[0]) <= start(pair[1]));
+		}
+	}
+
+	#[test]
+	fn test_values() {
+		assert_eq!(decode_value(&encode_value(Some(b"test"))), Some(b"test".to_vec()));
+		assert_eq!(decode_value(&encode_value(Some(b""))), Some(vec![]));
+		assert_eq!(decode_value(&encode_value(None)), None);
+	}
+
+	#[test]
+	fn test_clock() {
+		let clock = Clock::default();
+		assert_eq!(clock.next(100), 100);
+		// The same time issues a later version
+		assert_eq!(clock.next(100), 101);
+		// A clock which goes backwards is clamped
+		assert_eq!(clock.next(50), 102);
+		// A clock which goes forwards is followed
+		assert_eq!(clock.next(200), 200);
+	}
+}
@@ -330,7 +330,7 @@ mod tests {
 	#[tokio::test(flavor = "multi_thread")]
 	async fn task_lease_concurrency_memory() {
 		// Create a new in-memory datastore
-		let flavor = crate::kvs::mem::Datastore::new().await.map(DatastoreFlavor::Mem).unwrap();
+		let flavor = crate::kvs::mem::Datastore::new(None).await.map(DatastoreFlavor::Mem).unwrap();
 		// Run the concurrency test with the in-memory datastore
 		task_lease_concurrency(flavor).await;
 	}
@@ -377,7 +377,7 @@ mod tests {
 		// Create a fake clock for deterministic testing
 		let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
 		// Create an in-memory datastore
-		let flavor = crate::kvs::mem::Datastore::new().await.map(DatastoreFlavor::Mem).unwrap();
+		let flavor = crate::kvs::mem::Datastore::new(None).await.map(DatastoreFlavor::Mem).unwrap();
 		let tf = TransactionFactory::new(clock, Box::new(flavor));
 
 		// Set lease duration to 10 minutes
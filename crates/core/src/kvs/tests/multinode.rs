@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use super::CreateDs;
+use crate::catalog::providers::DatabaseProvider;
+use crate::dbs::node::Timestamp;
+use crate::dbs::{Action, MessageBroker, Notification, Session};
+use crate::doc::DefaultBroker;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+use crate::kvs::clock::{FakeClock, SizedClock};
+use crate::val::Value;
+
+pub async fn live_query_notifications(new_ds: impl CreateDs) {
+	// Create a new datastore
+	let node_a = Uuid::parse_str("a8c5a0b4-1a2e-4b5d-9b55-5a0e7b1b7a01").unwrap();
+	let node_b = Uuid::parse_str("a8c5a0b4-1a2e-4b5d-9b55-5a0e7b1b7a02").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds.create_ds(node_a, clock).await;
+	// Create two nodes which share the same storage
+	let ds_a = ds.with_notifications();
+	let ds_b = ds_a.new_node(node_b);
+	ds_a.insert_node(node_a).await.unwrap();
+	ds_b.insert_node(node_b).await.unwrap();
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	ds_a.execute("DEFINE TABLE person", &ses, None).await.unwrap();
+	// Register a live query on the first node
+	let res = &mut ds_a.execute("LIVE SELECT * FROM person", &ses, None).await.unwrap();
+	let Value::Uuid(id) = res.remove(0).result.unwrap() else {
+		panic!("expected a live query id");
+	};
+	// Write to the table on the second node
+	let sql = "CREATE person:one; UPDATE person:one SET name = 'Tobie'; DELETE person:one;";
+	let res = ds_b.execute(sql, &ses, None).await.unwrap();
+	assert!(res.into_iter().all(|r| r.result.is_ok()));
+	// The second node does not own the live query
+	assert!(ds_b.notifications().unwrap().try_recv().is_err());
+	// The notifications are not delivered until the queue is processed
+	let channel = ds_a.notifications().unwrap();
+	assert!(channel.try_recv().is_err());
+	ds_a.node_notifications_deliver().await.unwrap();
+	// The notifications are delivered in order
+	for action in [Action::Create, Action::Update, Action::Delete] {
+		let notification = channel.try_recv().unwrap();
+		assert_eq!(notification.id, id);
+		assert_eq!(notification.action, action);
+	}
+	assert!(channel.try_recv().is_err());
+	// The queue is empty once delivered
+	let beg = crate::key::node::nq::prefix(node_a).unwrap();
+	let end = crate::key::node::nq::suffix(node_a).unwrap();
+	let tx = ds_a.transaction(Read, Optimistic).await.unwrap();
+	assert!(tx.keys(beg.clone()..end.clone(), 100, None).await.unwrap().is_empty());
+	tx.cancel().await.unwrap();
+	// Notifications which cannot be sent remain queued
+	ds_b.execute("CREATE person:two", &ses, None).await.unwrap();
+	channel.close();
+	ds_a.node_notifications_deliver().await.unwrap();
+	let tx = ds_a.transaction(Read, Optimistic).await.unwrap();
+	assert_eq!(tx.keys(beg.clone()..end.clone(), 100, None).await.unwrap().len(), 1);
+	tx.cancel().await.unwrap();
+	// Remove the first node
+	ds_b.delete_node(node_a).await.unwrap();
+	ds_b.remove_nodes().await.unwrap();
+	// The queued notifications are removed with the node
+	let tx = ds_b.transaction(Read, Optimistic).await.unwrap();
+	assert!(tx.keys(beg..end, 100, None).await.unwrap().is_empty());
+	tx.cancel().await.unwrap();
+}
+
+pub async fn live_query_notification_order(new_ds: impl CreateDs) {
+	// Create a new datastore
+	let node_a = Uuid::parse_str("a8c5a0b4-1a2e-4b5d-9b55-5a0e7b1b7a01").unwrap();
+	let node_b = Uuid::parse_str("a8c5a0b4-1a2e-4b5d-9b55-5a0e7b1b7a02").unwrap();
+	let node_c = Uuid::parse_str("a8c5a0b4-1a2e-4b5d-9b55-5a0e7b1b7a03").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds.create_ds(node_a, clock).await;
+	// Create three nodes which share the same storage
+	let ds_a = ds.with_notifications();
+	let ds_b = ds_a.new_node(node_b);
+	let ds_c = ds_a.new_node(node_c);
+	ds_a.insert_node(node_a).await.unwrap();
+	ds_b.insert_node(node_b).await.unwrap();
+	ds_c.insert_node(node_c).await.unwrap();
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	ds_a.execute("DEFINE TABLE person", &ses, None).await.unwrap();
+	// Register a live query on the first node
+	let res = &mut ds_a.execute("LIVE SELECT * FROM person", &ses, None).await.unwrap();
+	let Value::Uuid(id) = res.remove(0).result.unwrap() else {
+		panic!("expected a live query id");
+	};
+	let tx = ds_a.transaction(Read, Optimistic).await.unwrap();
+	let db = tx.expect_db_by_name("test", "test").await.unwrap();
+	let (ns, db) = (db.namespace_id, db.database_id);
+	tx.cancel().await.unwrap();
+	let broker = DefaultBroker::new(async_channel::unbounded().0);
+	let notification = |action| Notification {
+		id,
+		action,
+		record: Value::None,
+		result: Value::None,
+		versionstamp: None,
+	};
+	// Queue a notification in a transaction on each of the other nodes
+	let tx_b = ds_b.transaction(Write, Optimistic).await.unwrap();
+	broker.forward(&tx_b, ns, db, node_a, notification(Action::Create)).await.unwrap();
+	let tx_c = ds_c.transaction(Write, Optimistic).await.unwrap();
+	broker.forward(&tx_c, ns, db, node_a, notification(Action::Update)).await.unwrap();
+	// Commit the transaction which was started last first
+	tx_c.commit().await.unwrap();
+	// The other transaction either conflicts, and is retried,
+	// or is given a later versionstamp when it is committed
+	if tx_b.commit().await.is_err() {
+		let tx_b = ds_b.transaction(Write, Optimistic).await.unwrap();
+		broker.forward(&tx_b, ns, db, node_a, notification(Action::Create)).await.unwrap();
+		tx_b.commit().await.unwrap();
+	}
+	// The notifications are delivered in the order of their commits
+	let channel = ds_a.notifications().unwrap();
+	ds_a.node_notifications_deliver().await.unwrap();
+	for action in [Action::Update, Action::Create] {
+		let notification = channel.try_recv().unwrap();
+		assert_eq!(notification.id, id);
+		assert_eq!(notification.action, action);
+	}
+	assert!(channel.try_recv().is_err());
+}
+
+macro_rules! define_tests {
+	($new_ds:ident) => {
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn live_query_notifications() {
+			super::multinode::live_query_notifications($new_ds).await;
+		}
+
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn live_query_notification_order() {
+			super::multinode::live_query_notification_order($new_ds).await;
+		}
+	};
+}
+pub(crate) use define_tests;
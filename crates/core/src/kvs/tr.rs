@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
 use std::ops::Range;
@@ -60,6 +61,7 @@ pub struct Transactor {
 	pub(super) inner: Box<dyn Transaction>,
 	pub(super) stash: Stash,
 	pub(super) cf: cf::Writer,
+	pub(super) vs: HashMap<(NamespaceId, DatabaseId), VersionStamp>,
 }
 
 impl fmt::Display for Transactor {
@@ -672,13 +674,87 @@ impl Transactor {
 	// Lastly, you should set lock=true if you want the changefeed to be correctly
 	// ordered for non-FDB backends.
 	pub(crate) async fn complete_changes(&mut self, _lock: bool) -> Result<()> {
+		// Store the allocated versionstamps again, as a rollback to a save
+		// point may have reverted the versionstamp keys since allocation
+		for ((ns, db), vs) in self.vs.clone() {
+			self.set(&crate::key::database::vs::new(ns, db), &vs, None).await?;
+		}
 		let changes = self.cf.get()?;
 		for (tskey, prefix, suffix, v) in changes {
-			self.set_versionstamp(tskey, prefix, suffix, v).await?
+			self.set_changes_versionstamped(tskey.ns, tskey.db, prefix, suffix, v).await?;
 		}
 		Ok(())
 	}
 
+	/// Inserts a key which contains the versionstamp of the changefeed
+	/// entries which this transaction records in the specified database,
+	/// between the given prefix and suffix.
+	///
+	/// Keys which are written with this function are ordered by the commit
+	/// order of their transactions, as transactions which allocate the same
+	/// database versionstamp conflict with each other.
+	pub(crate) async fn set_changes_versionstamped(
+		&mut self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		prefix: Key,
+		suffix: Key,
+		val: Val,
+	) -> Result<()> {
+		match self.changes_versionstamp(ns, db).await? {
+			Some(vs) => {
+				let mut key = prefix;
+				key.extend_from_slice(&vs.as_bytes());
+				key.extend_from_slice(&suffix);
+				self.inner.set(key, val, None).await
+			}
+			None => {
+				let tskey = crate::key::database::vs::new(ns, db);
+				self.set_versionstamp(tskey, prefix, suffix, val).await
+			}
+		}
+	}
+
+	/// Returns the versionstamp of the changefeed entries which this
+	/// transaction records in the specified database.
+	///
+	/// The versionstamp is allocated once per database, and is then used for
+	/// every changefeed entry and live query notification in the transaction.
+	/// Returns `None` if the storage engine only assigns the versionstamp when
+	/// the transaction is committed.
+	pub(crate) async fn changes_versionstamp(
+		&mut self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Option<VersionStamp>> {
+		if self.inner.assigns_versionstamps_on_commit() {
+			return Ok(None);
+		}
+		if let Some(vs) = self.vs.get(&(ns, db)) {
+			return Ok(Some(*vs));
+		}
+		let vs = self.get_timestamp(crate::key::database::vs::new(ns, db)).await?;
+		self.vs.insert((ns, db), vs);
+		Ok(Some(vs))
+	}
+
+	/// Returns the latest versionstamp which has been recorded in the
+	/// changefeeds of the specified database, as seen by this transaction.
+	///
+	/// Returns `None` if the storage engine only assigns the versionstamp when
+	/// the transaction is committed.
+	pub(crate) async fn current_versionstamp(
+		&mut self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Option<VersionStamp>> {
+		if self.inner.assigns_versionstamps_on_commit() {
+			return Ok(None);
+		}
+		let key = crate::key::database::vs::new(ns, db);
+		Ok(Some(self.get(&key, None).await?.unwrap_or_else(|| VersionStamp::from_u64(0))))
+	}
+
 	// set_timestamp_for_versionstamp correlates the given timestamp with the
 	// current versionstamp. This allows get_versionstamp_from_timestamp to obtain
 	// the versionstamp from the timestamp later.
@@ -822,6 +822,28 @@ impl DatabaseProvider for Transaction {
 		}
 	}
 
+	/// Retrieve all module definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn all_db_modules(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Arc<[catalog::ModuleDefinition]>> {
+		let qey = cache::tx::Lookup::Mds(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_mds(),
+			None => {
+				let beg = crate::key::database::md::prefix(ns, db)?;
+				let end = crate::key::database::md::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Mds(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Retrieve all model definitions for a specific database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	async fn all_db_models(
@@ -974,6 +996,41 @@ impl DatabaseProvider for Transaction {
 		Ok(())
 	}
 
+	/// Retrieve a specific module definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn get_db_module(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		md: &str,
+	) -> Result<Arc<catalog::ModuleDefinition>> {
+		let qey = cache::tx::Lookup::Md(ns, db, md);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type(),
+			None => {
+				let key = crate::key::database::md::new(ns, db, md);
+				let val = self.get(&key, None).await?.ok_or_else(|| Error::MdNotFound {
+					name: md.to_owned(),
+				})?;
+				let val = Arc::new(val);
+				let entr = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entr);
+				Ok(val)
+			}
+		}
+	}
+
+	async fn put_db_module(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		md: &catalog::ModuleDefinition,
+	) -> Result<()> {
+		let key = crate::key::database::md::new(ns, db, &md.name);
+		self.set(&key, md, None).await?;
+		Ok(())
+	}
+
 	/// Retrieve a specific function definition from a database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	async fn get_db_param(
@@ -1783,6 +1840,155 @@ impl UserProvider for Transaction {
 		self.set(&key, us, None).await?;
 		Ok(())
 	}
+
+	/// Retrieve all ROOT level roles in a datastore.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn all_root_roles(&self) -> Result<Arc<[catalog::RoleDefinition]>> {
+		let qey = cache::tx::Lookup::Rrs;
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_rrs(),
+			None => {
+				let beg = crate::key::root::rl::prefix();
+				let end = crate::key::root::rl::suffix();
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Rrs(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
+	/// Retrieve all namespace role definitions for a specific namespace.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn all_ns_roles(&self, ns: NamespaceId) -> Result<Arc<[catalog::RoleDefinition]>> {
+		let qey = cache::tx::Lookup::Nrs(ns);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_nrs(),
+			None => {
+				let beg = crate::key::namespace::rl::prefix(ns)?;
+				let end = crate::key::namespace::rl::suffix(ns)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Nrs(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
+	/// Retrieve all database role definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn all_db_roles(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Arc<[catalog::RoleDefinition]>> {
+		let qey = cache::tx::Lookup::Drs(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_drs(),
+			None => {
+				let beg = crate::key::database::rl::prefix(ns, db)?;
+				let end = crate::key::database::rl::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Drs(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
+	/// Retrieve a specific root role definition.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn get_root_role(&self, rl: &str) -> Result<Option<Arc<catalog::RoleDefinition>>> {
+		let qey = cache::tx::Lookup::Rr(rl);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type().map(Some),
+			None => {
+				let key = crate::key::root::rl::new(rl);
+				let Some(val) = self.get(&key, None).await? else {
+					return Ok(None);
+				};
+				let val = Arc::new(val);
+				let entr = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entr);
+				Ok(Some(val))
+			}
+		}
+	}
+
+	async fn put_root_role(&self, rl: &catalog::RoleDefinition) -> Result<()> {
+		let key = crate::key::root::rl::new(&rl.name);
+		self.set(&key, rl, None).await?;
+		Ok(())
+	}
+
+	/// Retrieve a specific namespace role definition.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn get_ns_role(
+		&self,
+		ns: NamespaceId,
+		rl: &str,
+	) -> Result<Option<Arc<catalog::RoleDefinition>>> {
+		let qey = cache::tx::Lookup::Nr(ns, rl);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type().map(Some),
+			None => {
+				let key = crate::key::namespace::rl::new(ns, rl);
+				let Some(val) = self.get(&key, None).await? else {
+					return Ok(None);
+				};
+
+				let val = Arc::new(val);
+				let entr = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entr);
+				Ok(Some(val))
+			}
+		}
+	}
+
+	async fn put_ns_role(&self, ns: NamespaceId, rl: &catalog::RoleDefinition) -> Result<()> {
+		let key = crate::key::namespace::rl::new(ns, &rl.name);
+		self.set(&key, rl, None).await?;
+		Ok(())
+	}
+
+	/// Retrieve a specific role definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn get_db_role(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		rl: &str,
+	) -> Result<Option<Arc<catalog::RoleDefinition>>> {
+		let qey = cache::tx::Lookup::Dr(ns, db, rl);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type().map(Some),
+			None => {
+				let key = crate::key::database::rl::new(ns, db, rl);
+				let Some(val) = self.get(&key, None).await? else {
+					return Ok(None);
+				};
+
+				let val = Arc::new(val);
+				let entr = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entr);
+				Ok(Some(val))
+			}
+		}
+	}
+
+	async fn put_db_role(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		rl: &catalog::RoleDefinition,
+	) -> Result<()> {
+		let key = crate::key::database::rl::new(ns, db, &rl.name);
+		self.set(&key, rl, None).await?;
+		Ok(())
+	}
 }
 
 #[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
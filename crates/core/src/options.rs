@@ -12,6 +12,8 @@ pub struct EngineOptions {
 	pub node_membership_check_interval: Duration,
 	/// Interval for cleaning up inactive nodes from the cluster
 	pub node_membership_cleanup_interval: Duration,
+	/// Interval for delivering notifications queued by other nodes
+	pub node_notifications_interval: Duration,
 	/// Interval for garbage collecting expired changefeed data
 	pub changefeed_gc_interval: Duration,
 	/// Interval for running the index compaction process
@@ -31,6 +33,7 @@ impl Default for EngineOptions {
 			node_membership_refresh_interval: Duration::from_secs(3),
 			node_membership_check_interval: Duration::from_secs(15),
 			node_membership_cleanup_interval: Duration::from_secs(300),
+			node_notifications_interval: Duration::from_millis(100),
 			changefeed_gc_interval: Duration::from_secs(10),
 			index_compaction_interval: Duration::from_secs(5),
 		}
@@ -50,6 +53,10 @@ impl EngineOptions {
 		self.node_membership_cleanup_interval = interval;
 		self
 	}
+	pub fn with_node_notifications_interval(mut self, interval: Duration) -> Self {
+		self.node_notifications_interval = interval;
+		self
+	}
 	pub fn with_changefeed_gc_interval(mut self, interval: Duration) -> Self {
 		self.changefeed_gc_interval = interval;
 		self
@@ -421,6 +421,8 @@ pub trait RpcProtocolV1: RpcContext {
 			what,
 			cond: None,
 			fetch: None,
+			snapshot: false,
+			since: None,
 		};
 		let ast = Ast {
 			expressions: vec![TopLevelExpr::Live(Box::new(sql))],
@@ -398,6 +398,8 @@ pub trait RpcProtocolV2: RpcContext {
 			what: value_to_table(what),
 			cond: None,
 			fetch: None,
+			snapshot: false,
+			since: None,
 		};
 		let ast = Ast {
 			expressions: vec![TopLevelExpr::Live(Box::new(sql))],
@@ -24,13 +24,18 @@ impl Data {
 	pub fn into_value(self) -> Value {
 		match self {
 			Data::Query(v) => v.into_iter().map(|x| x.into_value()).collect(),
-			Data::Live(v) => Value::from(Object(map! {
-				"id".to_owned() => v.id.into(),
-				"action".to_owned() => v.action.to_string().into(),
-				"record".to_owned() => v.record,
-				"result".to_owned() => v.result,
-
-			})),
+			Data::Live(v) => {
+				let mut obj = Object(map! {
+					"id".to_owned() => v.id.into(),
+					"action".to_owned() => v.action.to_string().into(),
+					"record".to_owned() => v.record,
+					"result".to_owned() => v.result,
+				});
+				if let Some(vs) = v.versionstamp {
+					obj.insert("versionstamp".to_owned(), vs.into());
+				}
+				Value::from(obj)
+			}
 			Data::Other(v) => v,
 		}
 	}
@@ -8,7 +8,8 @@ use rand::distributions::Alphanumeric;
 
 use super::Expr;
 use crate::err::Error;
-use crate::sql::{Algorithm, Literal};
+use crate::sql::user::PasswordPolicy;
+use crate::sql::{Algorithm, Literal, Param};
 
 pub(crate) fn random_key() -> String {
 	rand::thread_rng().sample_iter(&Alphanumeric).take(128).map(char::from).collect::<String>()
@@ -67,6 +68,15 @@ impl Display for AccessType {
 				if let Some(ref v) = ac.signin {
 					write!(f, " SIGNIN {v}")?
 				}
+				if let Some(ref v) = ac.policy {
+					write!(f, " {v}")?
+				}
+				if let Some(ref v) = ac.password {
+					write!(f, " PASSWORD {v}")?
+				}
+				if let Some(ref v) = ac.identity {
+					write!(f, " IDENTITY {v}")?
+				}
 				if ac.bearer.is_some() {
 					write!(f, " WITH REFRESH")?
 				}
@@ -289,6 +299,9 @@ pub struct RecordAccess {
 	pub signin: Option<Expr>,
 	pub jwt: JwtAccess,
 	pub bearer: Option<BearerAccess>,
+	pub policy: Option<PasswordPolicy>,
+	pub identity: Option<Expr>,
+	pub password: Option<Param>,
 }
 
 impl From<RecordAccess> for crate::expr::RecordAccess {
@@ -298,6 +311,9 @@ impl From<RecordAccess> for crate::expr::RecordAccess {
 			signin: v.signin.map(Into::into),
 			jwt: v.jwt.into(),
 			bearer: v.bearer.map(Into::into),
+			policy: v.policy.map(Into::into),
+			identity: v.identity.map(Into::into),
+			password: v.password.map(Into::into),
 		}
 	}
 }
@@ -309,6 +325,9 @@ impl From<crate::expr::RecordAccess> for RecordAccess {
 			signin: v.signin.map(Into::into),
 			jwt: v.jwt.into(),
 			bearer: v.bearer.map(Into::into),
+			policy: v.policy.map(Into::into),
+			identity: v.identity.map(Into::into),
+			password: v.password.map(Into::into),
 		}
 	}
 }
@@ -5,9 +5,9 @@ use crate::sql::literal::ObjectEntry;
 use crate::sql::operator::BindingPower;
 use crate::sql::statements::{
 	AlterStatement, CreateStatement, DefineStatement, DeleteStatement, ForeachStatement,
-	IfelseStatement, InfoStatement, InsertStatement, OutputStatement, RebuildStatement,
-	RelateStatement, RemoveStatement, SelectStatement, SetStatement, SleepStatement,
-	UpdateStatement, UpsertStatement,
+	IfelseStatement, InfoStatement, InsertStatement, MatchStatement, OutputStatement,
+	RebuildStatement, RelateStatement, RemoveStatement, SelectStatement, SetStatement,
+	SleepStatement, UpdateStatement, UpsertStatement,
 };
 use crate::sql::{
 	BinaryOperator, Block, Closure, Constant, FunctionCall, Idiom, Literal, Mock, Param,
@@ -51,6 +51,7 @@ pub enum Expr {
 	Return(Box<OutputStatement>),
 	If(Box<IfelseStatement>),
 	Select(Box<SelectStatement>),
+	Match(Box<MatchStatement>),
 	Create(Box<CreateStatement>),
 	Update(Box<UpdateStatement>),
 	Delete(Box<DeleteStatement>),
@@ -207,6 +208,7 @@ impl fmt::Display for Expr {
 			Expr::Throw(expr) => write!(f, "THROW {expr}"),
 			Expr::If(s) => write!(f, "{s}"),
 			Expr::Select(s) => write!(f, "{s}"),
+			Expr::Match(s) => write!(f, "{s}"),
 			Expr::Create(s) => write!(f, "{s}"),
 			Expr::Update(s) => write!(f, "{s}"),
 			Expr::Delete(s) => write!(f, "{s}"),
@@ -267,6 +269,7 @@ impl From<Expr> for crate::expr::Expr {
 			Expr::Throw(e) => crate::expr::Expr::Throw(Box::new((*e).into())),
 			Expr::If(s) => crate::expr::Expr::IfElse(Box::new((*s).into())),
 			Expr::Select(s) => crate::expr::Expr::Select(Box::new((*s).into())),
+			Expr::Match(s) => crate::expr::Expr::Match(Box::new((*s).into())),
 			Expr::Create(s) => crate::expr::Expr::Create(Box::new((*s).into())),
 			Expr::Update(s) => crate::expr::Expr::Update(Box::new((*s).into())),
 			Expr::Delete(s) => crate::expr::Expr::Delete(Box::new((*s).into())),
@@ -327,6 +330,7 @@ impl From<crate::expr::Expr> for Expr {
 			crate::expr::Expr::Throw(e) => Expr::Throw(Box::new((*e).into())),
 			crate::expr::Expr::IfElse(s) => Expr::If(Box::new((*s).into())),
 			crate::expr::Expr::Select(s) => Expr::Select(Box::new((*s).into())),
+			crate::expr::Expr::Match(s) => Expr::Match(Box::new((*s).into())),
 			crate::expr::Expr::Create(s) => Expr::Create(Box::new((*s).into())),
 			crate::expr::Expr::Update(s) => Expr::Update(Box::new((*s).into())),
 			crate::expr::Expr::Delete(s) => Expr::Delete(Box::new((*s).into())),
@@ -107,7 +107,7 @@ pub use self::statements::{
 	RemoveStatement, RemoveTableStatement, RemoveUserStatement, SelectStatement, SetStatement,
 	ShowStatement, SleepStatement, UpdateStatement, UpsertStatement, UseStatement,
 };
-pub use self::table_type::{Relation, TableType};
+pub use self::table_type::{Relation, TableConstraint, TableType};
 pub use self::timeout::Timeout;
 pub use self::tokenizer::Tokenizer;
 pub use self::view::View;
@@ -1,12 +1,16 @@
+mod access;
 pub mod field;
 mod sequence;
 mod table;
+mod user;
 
 use std::fmt::{self, Display};
 
+pub use access::AlterAccessStatement;
 pub use field::AlterFieldStatement;
 pub use sequence::AlterSequenceStatement;
 pub use table::AlterTableStatement;
+pub use user::AlterUserStatement;
 
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -49,6 +53,8 @@ pub enum AlterStatement {
 	Table(AlterTableStatement),
 	Sequence(AlterSequenceStatement),
 	Field(AlterFieldStatement),
+	User(AlterUserStatement),
+	Access(AlterAccessStatement),
 }
 
 impl Display for AlterStatement {
@@ -57,6 +63,8 @@ impl Display for AlterStatement {
 			Self::Table(v) => Display::fmt(v, f),
 			Self::Sequence(v) => Display::fmt(v, f),
 			Self::Field(v) => Display::fmt(v, f),
+			Self::User(v) => Display::fmt(v, f),
+			Self::Access(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -67,6 +75,8 @@ impl From<AlterStatement> for crate::expr::statements::AlterStatement {
 			AlterStatement::Table(v) => Self::Table(v.into()),
 			AlterStatement::Sequence(v) => Self::Sequence(v.into()),
 			AlterStatement::Field(v) => Self::Field(v.into()),
+			AlterStatement::User(v) => Self::User(v.into()),
+			AlterStatement::Access(v) => Self::Access(v.into()),
 		}
 	}
 }
@@ -77,6 +87,8 @@ impl From<crate::expr::statements::AlterStatement> for AlterStatement {
 			crate::expr::statements::AlterStatement::Table(v) => Self::Table(v.into()),
 			crate::expr::statements::AlterStatement::Sequence(v) => Self::Sequence(v.into()),
 			crate::expr::statements::AlterStatement::Field(v) => Self::Field(v.into()),
+			crate::expr::statements::AlterStatement::User(v) => Self::User(v.into()),
+			crate::expr::statements::AlterStatement::Access(v) => Self::Access(v.into()),
 		}
 	}
 }
@@ -2,7 +2,7 @@ use std::fmt::{self, Display, Write};
 
 use super::AlterKind;
 use crate::fmt::{EscapeIdent, is_pretty, pretty_indent};
-use crate::sql::{ChangeFeed, Kind, Permissions, TableType};
+use crate::sql::{ChangeFeed, Kind, Permissions, TableConstraint, TableType};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -14,6 +14,9 @@ pub struct AlterTableStatement {
 	pub changefeed: AlterKind<ChangeFeed>,
 	pub comment: AlterKind<String>,
 	pub kind: Option<TableType>,
+	pub constraints: Vec<TableConstraint>,
+	pub drop_constraints: Vec<String>,
+	pub validate: bool,
 }
 
 impl Display for AlterTableStatement {
@@ -74,6 +77,18 @@ impl Display for AlterTableStatement {
 			AlterKind::None => {}
 		}
 
+		for name in self.drop_constraints.iter() {
+			write!(f, " DROP CONSTRAINT {}", EscapeIdent(name))?;
+		}
+
+		for constraint in self.constraints.iter() {
+			write!(f, " {constraint}")?;
+		}
+
+		if self.validate {
+			write!(f, " VALIDATE")?;
+		}
+
 		let _indent = if is_pretty() {
 			Some(pretty_indent())
 		} else {
@@ -97,6 +112,9 @@ impl From<AlterTableStatement> for crate::expr::statements::alter::AlterTableSta
 			changefeed: v.changefeed.into(),
 			comment: v.comment.into(),
 			kind: v.kind.map(Into::into),
+			constraints: v.constraints.into_iter().map(Into::into).collect(),
+			drop_constraints: v.drop_constraints,
+			validate: v.validate,
 		}
 	}
 }
@@ -111,6 +129,9 @@ impl From<crate::expr::statements::alter::AlterTableStatement> for AlterTableSta
 			changefeed: v.changefeed.into(),
 			comment: v.comment.into(),
 			kind: v.kind.map(Into::into),
+			constraints: v.constraints.into_iter().map(Into::into).collect(),
+			drop_constraints: v.drop_constraints,
+			validate: v.validate,
 		}
 	}
 }
@@ -0,0 +1,48 @@
+use std::fmt::{self, Display};
+
+use crate::fmt::EscapeIdent;
+use crate::sql::Base;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AlterUserStatement {
+	pub name: String,
+	pub base: Base,
+	pub if_exists: bool,
+	pub unlock: bool,
+}
+
+impl Display for AlterUserStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ALTER USER")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", EscapeIdent(&self.name), self.base)?;
+		if self.unlock {
+			write!(f, " UNLOCK")?;
+		}
+		Ok(())
+	}
+}
+
+impl From<AlterUserStatement> for crate::expr::statements::alter::AlterUserStatement {
+	fn from(v: AlterUserStatement) -> Self {
+		crate::expr::statements::alter::AlterUserStatement {
+			name: v.name,
+			base: v.base.into(),
+			if_exists: v.if_exists,
+			unlock: v.unlock,
+		}
+	}
+}
+impl From<crate::expr::statements::alter::AlterUserStatement> for AlterUserStatement {
+	fn from(v: crate::expr::statements::alter::AlterUserStatement) -> Self {
+		AlterUserStatement {
+			name: v.name,
+			base: v.base.into(),
+			if_exists: v.if_exists,
+			unlock: v.unlock,
+		}
+	}
+}
@@ -4,7 +4,7 @@ use super::DefineKind;
 use super::config::api::ApiConfig;
 use crate::catalog::ApiMethod;
 use crate::fmt::{Fmt, pretty_indent};
-use crate::sql::{Expr, Literal};
+use crate::sql::{Expr, Kind, Literal};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -99,6 +99,8 @@ pub struct ApiAction {
 	pub methods: Vec<ApiMethod>,
 	pub action: Expr,
 	pub config: ApiConfig,
+	pub request: Option<Kind>,
+	pub response: Option<Kind>,
 }
 
 impl Display for ApiAction {
@@ -106,6 +108,12 @@ impl Display for ApiAction {
 		write!(f, "FOR {}", Fmt::comma_separated(self.methods.iter()))?;
 		let _indent = pretty_indent();
 		write!(f, "{}", self.config)?;
+		if let Some(ref v) = self.request {
+			write!(f, " REQUEST {v}")?;
+		}
+		if let Some(ref v) = self.response {
+			write!(f, " RESPONSE {v}")?;
+		}
 		write!(f, " THEN {}", self.action)?;
 		Ok(())
 	}
@@ -117,6 +125,8 @@ impl From<ApiAction> for crate::expr::statements::define::ApiAction {
 			methods: v.methods,
 			action: v.action.into(),
 			config: v.config.into(),
+			request: v.request.map(Into::into),
+			response: v.response.map(Into::into),
 		}
 	}
 }
@@ -127,6 +137,8 @@ impl From<crate::expr::statements::define::ApiAction> for ApiAction {
 			methods: v.methods,
 			action: v.action.into(),
 			config: v.config.into(),
+			request: v.request.map(Into::into),
+			response: v.response.map(Into::into),
 		}
 	}
 }
@@ -58,6 +58,9 @@ pub struct DefineFieldStatement {
 	pub assert: Option<Expr>,
 	pub computed: Option<Expr>,
 	pub default: DefineDefault,
+	/// The value returned in place of the field when the `SELECT` permission
+	/// is not granted.
+	pub mask: Option<Expr>,
 	pub permissions: Permissions,
 	pub comment: Option<Expr>,
 	pub reference: Option<Reference>,
@@ -76,6 +79,7 @@ impl Default for DefineFieldStatement {
 			assert: None,
 			computed: None,
 			default: DefineDefault::None,
+			mask: None,
 			permissions: Permissions::default(),
 			comment: None,
 			reference: None,
@@ -124,6 +128,9 @@ impl Display for DefineFieldStatement {
 		if let Some(ref v) = self.reference {
 			write!(f, " REFERENCE {v}")?
 		}
+		if let Some(ref v) = self.mask {
+			write!(f, " MASK {v}")?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -155,6 +162,7 @@ impl From<DefineFieldStatement> for crate::expr::statements::DefineFieldStatemen
 			assert: v.assert.map(Into::into),
 			computed: v.computed.map(Into::into),
 			default: v.default.into(),
+			mask: v.mask.map(Into::into),
 			permissions: v.permissions.into(),
 			comment: v.comment.map(|x| x.into()),
 			reference: v.reference.map(Into::into),
@@ -176,6 +184,7 @@ impl From<crate::expr::statements::DefineFieldStatement> for DefineFieldStatemen
 			assert: v.assert.map(Into::into),
 			computed: v.computed.map(Into::into),
 			default: v.default.into(),
+			mask: v.mask.map(Into::into),
 			permissions: v.permissions.into(),
 			comment: v.comment.map(|x| x.into()),
 			reference: v.reference.map(Into::into),
@@ -1,7 +1,7 @@
 use std::fmt::{self, Display, Write};
 
 use super::DefineKind;
-use crate::fmt::{EscapeIdent, is_pretty, pretty_indent};
+use crate::fmt::{EscapeIdent, QuoteStr, is_pretty, pretty_indent};
 use crate::sql::{Block, Expr, Kind, Permission};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -14,6 +14,39 @@ pub struct DefineFunctionStatement {
 	pub comment: Option<Expr>,
 	pub permissions: Permission,
 	pub returns: Option<Kind>,
+	pub wasm: Option<FunctionWasm>,
+}
+
+/// A function which is implemented by an export of a WebAssembly module
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct FunctionWasm {
+	pub module: Expr,
+	pub export: String,
+}
+
+impl Display for FunctionWasm {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "LANGUAGE WASM MODULE {} EXPORT {}", self.module, QuoteStr(&self.export))
+	}
+}
+
+impl From<FunctionWasm> for crate::expr::statements::define::FunctionWasm {
+	fn from(v: FunctionWasm) -> Self {
+		Self {
+			module: v.module.into(),
+			export: v.export,
+		}
+	}
+}
+
+impl From<crate::expr::statements::define::FunctionWasm> for FunctionWasm {
+	fn from(v: crate::expr::statements::define::FunctionWasm) -> Self {
+		Self {
+			module: v.module.into(),
+			export: v.export,
+		}
+	}
 }
 
 impl fmt::Display for DefineFunctionStatement {
@@ -35,7 +68,10 @@ impl fmt::Display for DefineFunctionStatement {
 		if let Some(ref v) = self.returns {
 			write!(f, "-> {v} ")?;
 		}
-		Display::fmt(&self.block, f)?;
+		match self.wasm {
+			Some(ref v) => Display::fmt(v, f)?,
+			None => Display::fmt(&self.block, f)?,
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {}", v)?
 		}
@@ -60,6 +96,7 @@ impl From<DefineFunctionStatement> for crate::expr::statements::DefineFunctionSt
 			comment: v.comment.map(|x| x.into()),
 			permissions: v.permissions.into(),
 			returns: v.returns.map(Into::into),
+			wasm: v.wasm.map(Into::into),
 		}
 	}
 }
@@ -74,6 +111,7 @@ impl From<crate::expr::statements::DefineFunctionStatement> for DefineFunctionSt
 			comment: v.comment.map(|x| x.into()),
 			permissions: v.permissions.into(),
 			returns: v.returns.map(Into::into),
+			wasm: v.wasm.map(Into::into),
 		}
 	}
 }
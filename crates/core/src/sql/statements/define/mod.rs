@@ -9,8 +9,10 @@ mod field;
 mod function;
 mod index;
 mod model;
+mod module;
 mod namespace;
 mod param;
+mod role;
 mod sequence;
 mod table;
 pub mod user;
@@ -25,11 +27,13 @@ pub use config::DefineConfigStatement;
 pub use database::DefineDatabaseStatement;
 pub use event::DefineEventStatement;
 pub use field::{DefineDefault, DefineFieldStatement};
-pub use function::DefineFunctionStatement;
+pub use function::{DefineFunctionStatement, FunctionWasm};
 pub use index::DefineIndexStatement;
 pub use model::DefineModelStatement;
+pub use module::DefineModuleStatement;
 pub use namespace::DefineNamespaceStatement;
 pub use param::DefineParamStatement;
+pub use role::DefineRoleStatement;
 pub use sequence::DefineSequenceStatement;
 pub use table::DefineTableStatement;
 pub use user::DefineUserStatement;
@@ -76,6 +80,8 @@ pub enum DefineStatement {
 	Field(DefineFieldStatement),
 	Index(DefineIndexStatement),
 	User(DefineUserStatement),
+	Role(DefineRoleStatement),
+	Module(DefineModuleStatement),
 	Model(DefineModelStatement),
 	Access(DefineAccessStatement),
 	Config(DefineConfigStatement),
@@ -91,6 +97,8 @@ impl Display for DefineStatement {
 			Self::Database(v) => Display::fmt(v, f),
 			Self::Function(v) => Display::fmt(v, f),
 			Self::User(v) => Display::fmt(v, f),
+			Self::Role(v) => Display::fmt(v, f),
+			Self::Module(v) => Display::fmt(v, f),
 			Self::Param(v) => Display::fmt(v, f),
 			Self::Table(v) => Display::fmt(v, f),
 			Self::Event(v) => Display::fmt(v, f),
@@ -120,6 +128,8 @@ impl From<DefineStatement> for crate::expr::statements::DefineStatement {
 			DefineStatement::Field(v) => Self::Field(v.into()),
 			DefineStatement::Index(v) => Self::Index(v.into()),
 			DefineStatement::User(v) => Self::User(v.into()),
+			DefineStatement::Role(v) => Self::Role(v.into()),
+			DefineStatement::Module(v) => Self::Module(v.into()),
 			DefineStatement::Model(v) => Self::Model(v.into()),
 			DefineStatement::Access(v) => Self::Access(v.into()),
 			DefineStatement::Config(v) => Self::Config(v.into()),
@@ -143,6 +153,8 @@ impl From<crate::expr::statements::DefineStatement> for DefineStatement {
 			crate::expr::statements::DefineStatement::Field(v) => Self::Field(v.into()),
 			crate::expr::statements::DefineStatement::Index(v) => Self::Index(v.into()),
 			crate::expr::statements::DefineStatement::User(v) => Self::User(v.into()),
+			crate::expr::statements::DefineStatement::Role(v) => Self::Role(v.into()),
+			crate::expr::statements::DefineStatement::Module(v) => Self::Module(v.into()),
 			crate::expr::statements::DefineStatement::Model(v) => Self::Model(v.into()),
 			crate::expr::statements::DefineStatement::Access(v) => Self::Access(v.into()),
 			crate::expr::statements::DefineStatement::Config(v) => Self::Config(v.into()),
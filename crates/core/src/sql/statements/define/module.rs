@@ -0,0 +1,51 @@
+use std::fmt::{self, Display};
+
+use super::DefineKind;
+use crate::sql::Expr;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct DefineModuleStatement {
+	pub kind: DefineKind,
+	pub name: String,
+	pub source: Expr,
+	pub comment: Option<Expr>,
+}
+
+impl Display for DefineModuleStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE MODULE")?;
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => write!(f, " OVERWRITE")?,
+			DefineKind::IfNotExists => write!(f, " IF NOT EXISTS")?,
+		}
+		write!(f, " mod::{} AS {}", self.name, self.source)?;
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {}", v)?
+		}
+		Ok(())
+	}
+}
+
+impl From<DefineModuleStatement> for crate::expr::statements::DefineModuleStatement {
+	fn from(v: DefineModuleStatement) -> Self {
+		Self {
+			kind: v.kind.into(),
+			name: v.name,
+			source: v.source.into(),
+			comment: v.comment.map(|x| x.into()),
+		}
+	}
+}
+
+impl From<crate::expr::statements::DefineModuleStatement> for DefineModuleStatement {
+	fn from(v: crate::expr::statements::DefineModuleStatement) -> Self {
+		DefineModuleStatement {
+			kind: v.kind.into(),
+			name: v.name,
+			source: v.source.into(),
+			comment: v.comment.map(|x| x.into()),
+		}
+	}
+}
@@ -0,0 +1,77 @@
+use std::fmt::{self, Display};
+
+use super::DefineKind;
+use crate::iam::RoleGrant;
+use crate::sql::{Base, Expr, Literal};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct DefineRoleStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub base: Base,
+	pub grants: Vec<RoleGrant>,
+	pub revokes: Vec<RoleGrant>,
+	pub comment: Option<Expr>,
+}
+
+impl Default for DefineRoleStatement {
+	fn default() -> Self {
+		Self {
+			kind: DefineKind::Default,
+			name: Expr::Literal(Literal::None),
+			base: Base::Root,
+			grants: vec![],
+			revokes: vec![],
+			comment: None,
+		}
+	}
+}
+
+impl Display for DefineRoleStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE ROLE")?;
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => write!(f, " OVERWRITE")?,
+			DefineKind::IfNotExists => write!(f, " IF NOT EXISTS")?,
+		}
+		write!(f, " {} ON {}", self.name, self.base)?;
+		for grant in self.grants.iter() {
+			write!(f, " GRANT {grant}")?;
+		}
+		for revoke in self.revokes.iter() {
+			write!(f, " REVOKE {revoke}")?;
+		}
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {}", v)?
+		}
+		Ok(())
+	}
+}
+
+impl From<DefineRoleStatement> for crate::expr::statements::DefineRoleStatement {
+	fn from(v: DefineRoleStatement) -> Self {
+		Self {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			base: v.base.into(),
+			grants: v.grants,
+			revokes: v.revokes,
+			comment: v.comment.map(|x| x.into()),
+		}
+	}
+}
+
+impl From<crate::expr::statements::DefineRoleStatement> for DefineRoleStatement {
+	fn from(v: crate::expr::statements::DefineRoleStatement) -> Self {
+		Self {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			base: v.base.into(),
+			grants: v.grants,
+			revokes: v.revokes,
+			comment: v.comment.map(|x| x.into()),
+		}
+	}
+}
@@ -3,7 +3,7 @@ use std::fmt::{self, Display, Write};
 use super::DefineKind;
 use crate::fmt::{is_pretty, pretty_indent};
 use crate::sql::changefeed::ChangeFeed;
-use crate::sql::{Expr, Kind, Literal, Permissions, TableType, View};
+use crate::sql::{Expr, Kind, Literal, Permissions, TableConstraint, TableType, View};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -18,6 +18,7 @@ pub struct DefineTableStatement {
 	pub changefeed: Option<ChangeFeed>,
 	pub comment: Option<Expr>,
 	pub table_type: TableType,
+	pub constraints: Vec<TableConstraint>,
 }
 
 impl Default for DefineTableStatement {
@@ -33,6 +34,7 @@ impl Default for DefineTableStatement {
 			changefeed: None,
 			comment: None,
 			table_type: TableType::default(),
+			constraints: Vec::new(),
 		}
 	}
 }
@@ -97,6 +99,9 @@ impl Display for DefineTableStatement {
 		if let Some(ref v) = self.changefeed {
 			write!(f, " {v}")?;
 		}
+		for constraint in self.constraints.iter() {
+			write!(f, " {constraint}")?;
+		}
 		let _indent = if is_pretty() {
 			Some(pretty_indent())
 		} else {
@@ -121,6 +126,7 @@ impl From<DefineTableStatement> for crate::expr::statements::DefineTableStatemen
 			changefeed: v.changefeed.map(Into::into),
 			comment: v.comment.map(|x| x.into()),
 			table_type: v.table_type.into(),
+			constraints: v.constraints.into_iter().map(Into::into).collect(),
 		}
 	}
 }
@@ -139,6 +145,7 @@ impl From<crate::expr::statements::DefineTableStatement> for DefineTableStatemen
 			changefeed: v.changefeed.map(Into::into),
 			comment: v.comment.map(|x| x.into()),
 			table_type: v.table_type.into(),
+			constraints: v.constraints.into_iter().map(Into::into).collect(),
 		}
 	}
 }
@@ -8,6 +8,7 @@ use rand::rngs::OsRng;
 
 use super::DefineKind;
 use crate::fmt::{EscapeIdent, Fmt, QuoteStr};
+use crate::sql::user::{PasswordPolicy, UserTotp};
 use crate::sql::{Base, Expr, Literal};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -29,6 +30,8 @@ pub struct DefineUserStatement {
 	pub roles: Vec<String>,
 	pub token_duration: Option<Expr>,
 	pub session_duration: Option<Expr>,
+	pub totp: Option<UserTotp>,
+	pub policy: Option<PasswordPolicy>,
 
 	pub comment: Option<Expr>,
 }
@@ -43,6 +46,8 @@ impl Default for DefineUserStatement {
 			roles: vec![],
 			token_duration: None,
 			session_duration: None,
+			totp: None,
+			policy: None,
 			comment: None,
 		}
 	}
@@ -92,6 +97,12 @@ impl Display for DefineUserStatement {
 				None => "NONE".to_string(),
 			}
 		)?;
+		if let Some(ref v) = self.totp {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.policy {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {}", v)?
 		}
@@ -102,14 +113,17 @@ impl Display for DefineUserStatement {
 #[allow(clippy::fallible_impl_from)]
 impl From<DefineUserStatement> for crate::expr::statements::DefineUserStatement {
 	fn from(v: DefineUserStatement) -> Self {
-		let hash = match v.pass_type {
-			PassType::Unset => String::new(),
-			PassType::Hash(x) => x,
+		let (hash, pass) = match v.pass_type {
+			PassType::Unset => (String::new(), None),
+			PassType::Hash(x) => (x, None),
 			// TODO: Move out of AST.
-			PassType::Password(p) => Argon2::default()
-				.hash_password(p.as_bytes(), &SaltString::generate(&mut OsRng))
-				.unwrap()
-				.to_string(),
+			PassType::Password(p) => (
+				Argon2::default()
+					.hash_password(p.as_bytes(), &SaltString::generate(&mut OsRng))
+					.unwrap()
+					.to_string(),
+				Some(p),
+			),
 		};
 
 		let code = rand::thread_rng()
@@ -123,12 +137,15 @@ impl From<DefineUserStatement> for crate::expr::statements::DefineUserStatement
 			name: v.name.into(),
 			base: v.base.into(),
 			hash,
+			pass,
 			code,
 			roles: v.roles,
 			duration: crate::expr::user::UserDuration {
 				token: v.token_duration.map(Into::into),
 				session: v.session_duration.map(Into::into),
 			},
+			totp: v.totp.map(Into::into),
+			policy: v.policy.map(Into::into),
 			comment: v.comment.map(|x| x.into()),
 		}
 	}
@@ -144,6 +161,8 @@ impl From<crate::expr::statements::DefineUserStatement> for DefineUserStatement
 			roles: v.roles,
 			token_duration: v.duration.token.map(Into::into),
 			session_duration: v.duration.session.map(Into::into),
+			totp: v.totp.map(Into::into),
+			policy: v.policy.map(Into::into),
 			comment: v.comment.map(|x| x.into()),
 		}
 	}
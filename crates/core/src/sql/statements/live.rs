@@ -2,6 +2,7 @@ use std::fmt;
 
 use uuid::Uuid;
 
+use crate::sql::statements::show::ShowSince;
 use crate::sql::{Cond, Expr, Fetchs, Fields};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -11,6 +12,8 @@ pub struct LiveStatement {
 	pub what: Expr,
 	pub cond: Option<Cond>,
 	pub fetch: Option<Fetchs>,
+	pub snapshot: bool,
+	pub since: Option<ShowSince>,
 }
 
 impl fmt::Display for LiveStatement {
@@ -22,6 +25,14 @@ impl fmt::Display for LiveStatement {
 		if let Some(ref v) = self.fetch {
 			write!(f, " {v}")?
 		}
+		if self.snapshot {
+			write!(f, " WITH SNAPSHOT")?
+		}
+		match self.since {
+			Some(ShowSince::Timestamp(ref v)) => write!(f, " SINCE {v}")?,
+			Some(ShowSince::Versionstamp(ref v)) => write!(f, " SINCE {v}")?,
+			None => {}
+		}
 		Ok(())
 	}
 }
@@ -35,6 +46,8 @@ impl From<LiveStatement> for crate::expr::statements::LiveStatement {
 			what: v.what.into(),
 			cond: v.cond.map(Into::into),
 			fetch: v.fetch.map(Into::into),
+			snapshot: v.snapshot,
+			since: v.since.map(Into::into),
 		}
 	}
 }
@@ -45,6 +58,8 @@ impl From<crate::expr::statements::LiveStatement> for LiveStatement {
 			what: v.what.into(),
 			cond: v.cond.map(Into::into),
 			fetch: v.fetch.map(Into::into),
+			snapshot: v.snapshot,
+			since: v.since.map(Into::into),
 		}
 	}
 }
@@ -0,0 +1,194 @@
+use std::fmt::{self, Display, Formatter, Write};
+
+use crate::fmt::{EscapeIdent, Fmt};
+use crate::sql::order::Ordering;
+use crate::sql::{Cond, Dir, Fields, Limit, Start};
+
+/// A node in a MATCH pattern, `(alias:table|table)`
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct MatchNode {
+	pub alias: Option<String>,
+	pub what: Vec<String>,
+}
+
+/// An edge in a MATCH pattern, `-[alias:table|table]->`
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct MatchEdge {
+	pub alias: Option<String>,
+	pub what: Vec<String>,
+	pub dir: Dir,
+}
+
+/// A path of nodes joined by edges in a MATCH statement
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct MatchPattern {
+	pub start: MatchNode,
+	pub steps: Vec<(MatchEdge, MatchNode)>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct MatchStatement {
+	pub patterns: Vec<MatchPattern>,
+	pub cond: Option<Cond>,
+	/// The foo,bar part in MATCH ... RETURN foo,bar.
+	pub expr: Fields,
+	pub order: Option<Ordering>,
+	pub limit: Option<Limit>,
+	pub start: Option<Start>,
+}
+
+fn fmt_binding(f: &mut Formatter, alias: &Option<String>, what: &[String]) -> fmt::Result {
+	if let Some(alias) = alias {
+		EscapeIdent(alias).fmt(f)?;
+	}
+	for (i, tb) in what.iter().enumerate() {
+		f.write_char(if i == 0 {
+			':'
+		} else {
+			'|'
+		})?;
+		EscapeIdent(tb).fmt(f)?;
+	}
+	Ok(())
+}
+
+impl Display for MatchNode {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_char('(')?;
+		fmt_binding(f, &self.alias, &self.what)?;
+		f.write_char(')')
+	}
+}
+
+impl Display for MatchEdge {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if self.alias.is_none() && self.what.is_empty() {
+			return Display::fmt(&self.dir, f);
+		}
+		f.write_str(match self.dir {
+			Dir::In => "<-[",
+			_ => "-[",
+		})?;
+		fmt_binding(f, &self.alias, &self.what)?;
+		f.write_str(match self.dir {
+			Dir::Out => "]->",
+			_ => "]-",
+		})
+	}
+}
+
+impl Display for MatchPattern {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		self.start.fmt(f)?;
+		for (edge, node) in self.steps.iter() {
+			write!(f, "{edge}{node}")?;
+		}
+		Ok(())
+	}
+}
+
+impl Display for MatchStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "MATCH {}", Fmt::comma_separated(self.patterns.iter()))?;
+		if let Some(ref v) = self.cond {
+			write!(f, " {v}")?
+		}
+		write!(f, " RETURN {}", self.expr)?;
+		if let Some(ref v) = self.order {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.limit {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.start {
+			write!(f, " {v}")?
+		}
+		Ok(())
+	}
+}
+
+impl From<MatchNode> for crate::expr::statements::r#match::MatchNode {
+	fn from(v: MatchNode) -> Self {
+		Self {
+			alias: v.alias,
+			what: v.what,
+		}
+	}
+}
+
+impl From<crate::expr::statements::r#match::MatchNode> for MatchNode {
+	fn from(v: crate::expr::statements::r#match::MatchNode) -> Self {
+		Self {
+			alias: v.alias,
+			what: v.what,
+		}
+	}
+}
+
+impl From<MatchEdge> for crate::expr::statements::r#match::MatchEdge {
+	fn from(v: MatchEdge) -> Self {
+		Self {
+			alias: v.alias,
+			what: v.what,
+			dir: v.dir.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::r#match::MatchEdge> for MatchEdge {
+	fn from(v: crate::expr::statements::r#match::MatchEdge) -> Self {
+		Self {
+			alias: v.alias,
+			what: v.what,
+			dir: v.dir.into(),
+		}
+	}
+}
+
+impl From<MatchPattern> for crate::expr::statements::r#match::MatchPattern {
+	fn from(v: MatchPattern) -> Self {
+		Self {
+			start: v.start.into(),
+			steps: v.steps.into_iter().map(|(e, n)| (e.into(), n.into())).collect(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::r#match::MatchPattern> for MatchPattern {
+	fn from(v: crate::expr::statements::r#match::MatchPattern) -> Self {
+		Self {
+			start: v.start.into(),
+			steps: v.steps.into_iter().map(|(e, n)| (e.into(), n.into())).collect(),
+		}
+	}
+}
+
+impl From<MatchStatement> for crate::expr::statements::MatchStatement {
+	fn from(v: MatchStatement) -> Self {
+		Self {
+			patterns: v.patterns.into_iter().map(Into::into).collect(),
+			cond: v.cond.map(Into::into),
+			expr: v.expr.into(),
+			order: v.order.map(Into::into),
+			limit: v.limit.map(Into::into),
+			start: v.start.map(Into::into),
+		}
+	}
+}
+
+impl From<crate::expr::statements::MatchStatement> for MatchStatement {
+	fn from(v: crate::expr::statements::MatchStatement) -> Self {
+		Self {
+			patterns: v.patterns.into_iter().map(Into::into).collect(),
+			cond: v.cond.map(Into::into),
+			expr: v.expr.into(),
+			order: v.order.map(Into::into),
+			limit: v.limit.map(Into::into),
+			start: v.start.map(Into::into),
+		}
+	}
+}
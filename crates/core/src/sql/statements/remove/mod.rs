@@ -8,8 +8,10 @@ mod field;
 mod function;
 mod index;
 mod model;
+mod module;
 mod namespace;
 mod param;
+mod role;
 mod sequence;
 mod table;
 mod user;
@@ -26,8 +28,10 @@ pub use field::RemoveFieldStatement;
 pub use function::RemoveFunctionStatement;
 pub use index::RemoveIndexStatement;
 pub use model::RemoveModelStatement;
+pub use module::RemoveModuleStatement;
 pub use namespace::RemoveNamespaceStatement;
 pub use param::RemoveParamStatement;
+pub use role::RemoveRoleStatement;
 pub use sequence::RemoveSequenceStatement;
 pub use table::RemoveTableStatement;
 pub use user::RemoveUserStatement;
@@ -46,6 +50,8 @@ pub enum RemoveStatement {
 	Field(RemoveFieldStatement),
 	Index(RemoveIndexStatement),
 	User(RemoveUserStatement),
+	Role(RemoveRoleStatement),
+	Module(RemoveModuleStatement),
 	Model(RemoveModelStatement),
 	Api(RemoveApiStatement),
 	Bucket(RemoveBucketStatement),
@@ -66,6 +72,8 @@ impl Display for RemoveStatement {
 			Self::Index(v) => Display::fmt(v, f),
 			Self::Analyzer(v) => Display::fmt(v, f),
 			Self::User(v) => Display::fmt(v, f),
+			Self::Role(v) => Display::fmt(v, f),
+			Self::Module(v) => Display::fmt(v, f),
 			Self::Model(v) => Display::fmt(v, f),
 			Self::Api(v) => Display::fmt(v, f),
 			Self::Bucket(v) => Display::fmt(v, f),
@@ -88,6 +96,8 @@ impl From<RemoveStatement> for crate::expr::statements::RemoveStatement {
 			RemoveStatement::Field(v) => Self::Field(v.into()),
 			RemoveStatement::Index(v) => Self::Index(v.into()),
 			RemoveStatement::User(v) => Self::User(v.into()),
+			RemoveStatement::Role(v) => Self::Role(v.into()),
+			RemoveStatement::Module(v) => Self::Module(v.into()),
 			RemoveStatement::Model(v) => Self::Model(v.into()),
 			RemoveStatement::Api(v) => Self::Api(v.into()),
 			RemoveStatement::Bucket(v) => Self::Bucket(v.into()),
@@ -110,6 +120,8 @@ impl From<crate::expr::statements::RemoveStatement> for RemoveStatement {
 			crate::expr::statements::RemoveStatement::Field(v) => Self::Field(v.into()),
 			crate::expr::statements::RemoveStatement::Index(v) => Self::Index(v.into()),
 			crate::expr::statements::RemoveStatement::User(v) => Self::User(v.into()),
+			crate::expr::statements::RemoveStatement::Role(v) => Self::Role(v.into()),
+			crate::expr::statements::RemoveStatement::Module(v) => Self::Module(v.into()),
 			crate::expr::statements::RemoveStatement::Model(v) => Self::Model(v.into()),
 			crate::expr::statements::RemoveStatement::Api(v) => Self::Api(v.into()),
 			crate::expr::statements::RemoveStatement::Bucket(v) => Self::Bucket(v.into()),
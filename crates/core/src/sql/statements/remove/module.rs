@@ -0,0 +1,37 @@
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RemoveModuleStatement {
+	pub name: String,
+	pub if_exists: bool,
+}
+
+impl Display for RemoveModuleStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "REMOVE MODULE")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " mod::{}", self.name)?;
+		Ok(())
+	}
+}
+
+impl From<RemoveModuleStatement> for crate::expr::statements::RemoveModuleStatement {
+	fn from(v: RemoveModuleStatement) -> Self {
+		crate::expr::statements::RemoveModuleStatement {
+			name: v.name,
+			if_exists: v.if_exists,
+		}
+	}
+}
+
+impl From<crate::expr::statements::RemoveModuleStatement> for RemoveModuleStatement {
+	fn from(v: crate::expr::statements::RemoveModuleStatement) -> Self {
+		RemoveModuleStatement {
+			name: v.name,
+			if_exists: v.if_exists,
+		}
+	}
+}
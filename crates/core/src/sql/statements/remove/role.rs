@@ -0,0 +1,52 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::sql::{Base, Expr, Literal};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RemoveRoleStatement {
+	pub name: Expr,
+	pub base: Base,
+	pub if_exists: bool,
+}
+
+impl Default for RemoveRoleStatement {
+	fn default() -> Self {
+		Self {
+			name: Expr::Literal(Literal::None),
+			base: Base::default(),
+			if_exists: false,
+		}
+	}
+}
+
+impl Display for RemoveRoleStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "REMOVE ROLE")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", self.name, self.base)?;
+		Ok(())
+	}
+}
+
+impl From<RemoveRoleStatement> for crate::expr::statements::RemoveRoleStatement {
+	fn from(v: RemoveRoleStatement) -> Self {
+		crate::expr::statements::RemoveRoleStatement {
+			name: v.name.into(),
+			if_exists: v.if_exists,
+			base: v.base.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::RemoveRoleStatement> for RemoveRoleStatement {
+	fn from(v: crate::expr::statements::RemoveRoleStatement) -> Self {
+		RemoveRoleStatement {
+			name: v.name.into(),
+			if_exists: v.if_exists,
+			base: v.base.into(),
+		}
+	}
+}
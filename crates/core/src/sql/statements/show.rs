@@ -1,5 +1,6 @@
 use std::fmt;
 
+use crate::fmt::EscapeKwFreeIdent;
 use crate::val::Datetime;
 use crate::vs::VersionStamp;
 
@@ -54,7 +55,7 @@ impl fmt::Display for ShowStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "SHOW CHANGES FOR")?;
 		match self.table {
-			Some(ref v) => write!(f, " TABLE {}", v)?,
+			Some(ref v) => write!(f, " TABLE {}", EscapeKwFreeIdent(v))?,
 			None => write!(f, " DATABASE")?,
 		}
 		match self.since {
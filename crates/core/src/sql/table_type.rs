@@ -1,7 +1,8 @@
 use std::fmt;
 use std::fmt::Display;
 
-use crate::sql::Kind;
+use crate::fmt::EscapeIdent;
+use crate::sql::{Expr, Kind};
 
 /// The type of records stored by a table
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -86,3 +87,35 @@ impl From<crate::catalog::Relation> for Relation {
 		}
 	}
 }
+
+/// A named condition which every record in a table must satisfy
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct TableConstraint {
+	pub name: String,
+	pub check: Expr,
+}
+
+impl Display for TableConstraint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "CONSTRAINT {} ASSERT {}", EscapeIdent(&self.name), self.check)
+	}
+}
+
+impl From<TableConstraint> for crate::catalog::TableConstraint {
+	fn from(v: TableConstraint) -> Self {
+		Self {
+			name: v.name,
+			check: v.check.into(),
+		}
+	}
+}
+
+impl From<crate::catalog::TableConstraint> for TableConstraint {
+	fn from(v: crate::catalog::TableConstraint) -> Self {
+		Self {
+			name: v.name,
+			check: v.check.into(),
+		}
+	}
+}
@@ -1,4 +1,9 @@
+use std::fmt::{self, Display, Formatter};
+use std::time;
+
+use crate::fmt::{Fmt, QuoteStr};
 use crate::sql::Expr;
+use crate::val::Duration;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -41,3 +46,187 @@ impl From<crate::expr::user::UserDuration> for UserDuration {
 		}
 	}
 }
+
+/// The TOTP second factor of a user
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum UserTotp {
+	/// Enrol the user with a newly generated secret and recovery codes
+	Generate,
+	/// Enrol the user with an existing secret and hashed recovery codes
+	Secret {
+		secret: String,
+		recovery: Vec<String>,
+	},
+}
+
+impl Display for UserTotp {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "TOTP")?;
+		if let UserTotp::Secret {
+			secret,
+			recovery,
+		} = self
+		{
+			write!(f, " SECRET {}", QuoteStr(secret))?;
+			if !recovery.is_empty() {
+				write!(
+					f,
+					" RECOVERY {}",
+					Fmt::comma_separated(recovery.iter().map(|x| QuoteStr(x)))
+				)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl From<UserTotp> for crate::expr::user::UserTotp {
+	fn from(v: UserTotp) -> Self {
+		match v {
+			UserTotp::Generate => crate::expr::user::UserTotp::Generate,
+			UserTotp::Secret {
+				secret,
+				recovery,
+			} => crate::expr::user::UserTotp::Secret {
+				secret,
+				recovery,
+			},
+		}
+	}
+}
+
+impl From<crate::expr::user::UserTotp> for UserTotp {
+	fn from(v: crate::expr::user::UserTotp) -> Self {
+		match v {
+			crate::expr::user::UserTotp::Generate => UserTotp::Generate,
+			crate::expr::user::UserTotp::Secret {
+				secret,
+				recovery,
+			} => UserTotp::Secret {
+				secret,
+				recovery,
+			},
+		}
+	}
+}
+
+/// A class of characters which a password can be required to contain
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum PasswordClass {
+	Uppercase,
+	Lowercase,
+	Numeric,
+	Punct,
+}
+
+impl Display for PasswordClass {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Uppercase => f.write_str("UPPERCASE"),
+			Self::Lowercase => f.write_str("LOWERCASE"),
+			Self::Numeric => f.write_str("NUMERIC"),
+			Self::Punct => f.write_str("PUNCT"),
+		}
+	}
+}
+
+impl From<PasswordClass> for crate::expr::user::PasswordClass {
+	fn from(v: PasswordClass) -> Self {
+		match v {
+			PasswordClass::Uppercase => Self::Uppercase,
+			PasswordClass::Lowercase => Self::Lowercase,
+			PasswordClass::Numeric => Self::Numeric,
+			PasswordClass::Punct => Self::Punct,
+		}
+	}
+}
+
+impl From<crate::expr::user::PasswordClass> for PasswordClass {
+	fn from(v: crate::expr::user::PasswordClass) -> Self {
+		match v {
+			crate::expr::user::PasswordClass::Uppercase => Self::Uppercase,
+			crate::expr::user::PasswordClass::Lowercase => Self::Lowercase,
+			crate::expr::user::PasswordClass::Numeric => Self::Numeric,
+			crate::expr::user::PasswordClass::Punct => Self::Punct,
+		}
+	}
+}
+
+/// The number of failed signins after which an account is locked, and the
+/// duration for which it then remains locked
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct PasswordLockout {
+	pub attempts: u64,
+	pub duration: time::Duration,
+}
+
+/// The password policy of a user or record access method
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct PasswordPolicy {
+	// The minimum number of characters in a password
+	pub length: Option<u64>,
+	// The classes of characters which a password must contain
+	pub require: Vec<PasswordClass>,
+	// Duration after which a password expires, and must be changed
+	pub expiry: Option<time::Duration>,
+	// The number of previous passwords which can not be reused
+	pub history: Option<u64>,
+	// The lockout which is applied after repeated failed signins
+	pub lockout: Option<PasswordLockout>,
+}
+
+impl Display for PasswordPolicy {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "POLICY")?;
+		if let Some(v) = self.length {
+			write!(f, " LENGTH {v}")?;
+		}
+		if !self.require.is_empty() {
+			write!(f, " REQUIRE {}", Fmt::comma_separated(self.require.iter()))?;
+		}
+		if let Some(v) = self.expiry {
+			write!(f, " EXPIRY {}", Duration(v))?;
+		}
+		if let Some(v) = self.history {
+			write!(f, " HISTORY {v}")?;
+		}
+		if let Some(ref v) = self.lockout {
+			write!(f, " LOCKOUT {} FOR {}", v.attempts, Duration(v.duration))?;
+		}
+		Ok(())
+	}
+}
+
+impl From<PasswordPolicy> for crate::expr::user::PasswordPolicy {
+	fn from(v: PasswordPolicy) -> Self {
+		crate::expr::user::PasswordPolicy {
+			length: v.length,
+			require: v.require.into_iter().map(Into::into).collect(),
+			expiry: v.expiry,
+			history: v.history,
+			lockout: v.lockout.map(|x| crate::expr::user::PasswordLockout {
+				attempts: x.attempts,
+				duration: x.duration,
+			}),
+		}
+	}
+}
+
+impl From<crate::expr::user::PasswordPolicy> for PasswordPolicy {
+	fn from(v: crate::expr::user::PasswordPolicy) -> Self {
+		PasswordPolicy {
+			length: v.length,
+			require: v.require.into_iter().map(Into::into).collect(),
+			expiry: v.expiry,
+			history: v.history,
+			lockout: v.lockout.map(|x| PasswordLockout {
+				attempts: x.attempts,
+				duration: x.duration,
+			}),
+		}
+	}
+}
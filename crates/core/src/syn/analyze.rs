@@ -0,0 +1,676 @@
+//! Static analysis of SurrealQL source against a database schema.
+//!
+//! The analysis checks statements without running them, using the tables,
+//! fields, functions, and params which are defined in a schema, along with
+//! any definitions in the analysed source itself. Each problem which is
+//! found is reported as a [`Diagnostic`], with the location of the problem
+//! in the source.
+//!
+//! The analysis is conservative: only problems which would definitely cause
+//! a statement to fail, or to be rejected by the schema, are reported.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::error::Location;
+use super::token::Span;
+use crate::catalog::TableType;
+use crate::err::Error;
+use crate::expr::expression::VisitExpression;
+use crate::expr::{
+	AssignOperator, Data, DefineStatement, Expr, Field, Function, Idiom, Kind, Literal, Part,
+	TopLevelExpr,
+};
+use crate::fnc;
+use crate::fnc::args::Arity;
+use crate::val::{Array, Number, Object, Value};
+
+/// The params which are always available to a query
+pub(super) const BUILTIN_PARAMS: &[&str] = &[
+	"access",
+	"action",
+	"after",
+	"auth",
+	"before",
+	"event",
+	"file",
+	"input",
+	"parent",
+	"reference",
+	"request",
+	"self",
+	"session",
+	"target",
+	"this",
+	"token",
+	"value",
+];
+
+/// The tables, fields, functions, and params defined in a database.
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+	tables: BTreeMap<String, TableSchema>,
+	functions: BTreeMap<String, Vec<(String, Kind)>>,
+	params: BTreeSet<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct TableSchema {
+	full: bool,
+	relation: bool,
+	fields: Vec<FieldSchema>,
+}
+
+#[derive(Clone, Debug)]
+struct FieldSchema {
+	name: Idiom,
+	kind: Option<Kind>,
+	/// Whether values are stored as they are assigned, without a `VALUE` or
+	/// `COMPUTED` clause changing them first.
+	checked: bool,
+}
+
+impl Schema {
+	/// Collects the definitions from SurrealQL source, such as the output of
+	/// an export or a file of `DEFINE` statements.
+	pub fn parse(source: &str) -> Result<Self> {
+		let mut schema = Schema::default();
+		schema.extend(source)?;
+		Ok(schema)
+	}
+
+	/// Adds the definitions from more SurrealQL source to the schema.
+	pub fn extend(&mut self, source: &str) -> Result<()> {
+		for (stmt, _) in super::parse_statements(source)? {
+			self.define(&stmt.into());
+		}
+		Ok(())
+	}
+
+	fn define(&mut self, stmt: &TopLevelExpr) {
+		let TopLevelExpr::Expr(Expr::Define(define)) = stmt else {
+			return;
+		};
+		match define.as_ref() {
+			DefineStatement::Table(table) => {
+				let Some(name) = name_of(&table.name) else {
+					return;
+				};
+				let entry = self.tables.entry(name).or_default();
+				entry.full = table.full;
+				entry.relation = matches!(table.table_type, TableType::Relation(_));
+			}
+			DefineStatement::Field(field) => {
+				let (Some(table), Some(name)) = (name_of(&field.what), idiom_of(&field.name))
+				else {
+					return;
+				};
+				let fields = &mut self.tables.entry(table).or_default().fields;
+				fields.retain(|x| x.name != name);
+				fields.push(FieldSchema {
+					name,
+					kind: field.field_kind.clone(),
+					checked: field.value.is_none() && field.computed.is_none(),
+				});
+			}
+			DefineStatement::Function(function) => {
+				self.functions.insert(function.name.clone(), function.args.clone());
+			}
+			DefineStatement::Param(param) => {
+				self.params.insert(param.name.clone());
+			}
+			_ => {}
+		}
+	}
+}
+
+/// A problem found when analysing SurrealQL source.
+#[derive(Clone, Debug, Serialize)]
+pub struct Diagnostic {
+	/// A short identifier for the kind of problem, such as `unknown-table`
+	pub code: &'static str,
+	/// A description of the problem
+	pub message: String,
+	/// The start of the source which caused the problem
+	pub start: Location,
+	/// The end of the source which caused the problem
+	pub end: Location,
+}
+
+/// Analyses SurrealQL source against a schema.
+///
+/// Definitions within the source are added to the schema before the
+/// statements are checked. A source which does not parse results in a
+/// single `parse-error` diagnostic.
+pub fn analyze(source: &str, schema: &Schema) -> Result<Vec<Diagnostic>> {
+	let statements = match super::parse_statements(source) {
+		Ok(x) => x,
+		Err(e) => {
+			let Some(Error::InvalidQuery(error)) = e.downcast_ref::<Error>() else {
+				return Err(e);
+			};
+			let location = error.snippets.first().map(|x| x.location()).unwrap_or(Location {
+				line: 1,
+				column: 1,
+			});
+			return Ok(vec![Diagnostic {
+				code: "parse-error",
+				message: error.errors.join("\n"),
+				start: location,
+				end: location,
+			}]);
+		}
+	};
+	let statements: Vec<(TopLevelExpr, Span)> =
+		statements.into_iter().map(|(stmt, span)| (stmt.into(), span)).collect();
+	// Add the definitions and variables of the source itself
+	let mut schema = schema.clone();
+	let mut locals = BTreeSet::new();
+	for (stmt, _) in statements.iter() {
+		schema.define(stmt);
+		if let TopLevelExpr::Expr(expr) = stmt {
+			expr.visit(&mut |expr: &Expr| collect_locals(expr, &mut locals));
+		}
+	}
+	let mut analyzer = Analyzer {
+		source,
+		schema: &schema,
+		locals: &locals,
+		span: Span::empty(),
+		diagnostics: Vec::new(),
+	};
+	for (stmt, span) in statements.iter() {
+		let TopLevelExpr::Expr(expr) = stmt else {
+			continue;
+		};
+		analyzer.span = *span;
+		expr.visit(&mut |expr: &Expr| analyzer.check(expr));
+	}
+	Ok(analyzer.diagnostics)
+}
+
+/// Collects the names of params which are introduced within an expression
+fn collect_locals(expr: &Expr, locals: &mut BTreeSet<String>) {
+	match expr {
+		Expr::Let(set) => {
+			locals.insert(set.name.clone());
+		}
+		Expr::Foreach(foreach) => {
+			locals.insert(foreach.param.as_str().to_owned());
+		}
+		Expr::Closure(closure) => {
+			locals.extend(closure.args.iter().map(|(x, _)| x.as_str().to_owned()));
+		}
+		Expr::Literal(Literal::Closure(closure)) => {
+			locals.extend(closure.args.iter().map(|(x, _)| x.as_str().to_owned()));
+		}
+		Expr::Define(define) => {
+			if let DefineStatement::Function(function) = define.as_ref() {
+				locals.extend(function.args.iter().map(|(x, _)| x.clone()));
+			}
+		}
+		_ => {}
+	}
+}
+
+struct Analyzer<'a> {
+	source: &'a str,
+	schema: &'a Schema,
+	locals: &'a BTreeSet<String>,
+	/// The span of the statement which is being checked
+	span: Span,
+	diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Analyzer<'a> {
+	/// Returns the definition of a table which is known to exist
+	fn schema_of(&self, table: &str) -> &'a TableSchema {
+		&self.schema.tables[table]
+	}
+
+	fn check(&mut self, expr: &Expr) {
+		match expr {
+			Expr::Param(param) => {
+				let name = param.as_str();
+				if !BUILTIN_PARAMS.contains(&name)
+					&& !self.locals.contains(name)
+					&& !self.schema.params.contains(name)
+				{
+					self.report(
+						"unknown-param",
+						&format!("${name}"),
+						format!("The param '${name}' is not defined"),
+					);
+				}
+			}
+			Expr::FunctionCall(call) => match &call.receiver {
+				Function::Normal(name) => {
+					let Some(arity) = fnc::arity(name) else {
+						return;
+					};
+					if !arity.accepts(call.arguments.len()) {
+						self.report(
+							"invalid-arguments",
+							name,
+							format!(
+								"Incorrect arguments for function {name}(). {}",
+								arity.expected()
+							),
+						);
+					}
+				}
+				Function::Custom(name) => {
+					let needle = format!("fn::{name}");
+					let schema = self.schema;
+					let Some(args) = schema.functions.get(name) else {
+						self.report(
+							"unknown-function",
+							&needle,
+							format!("The function 'fn::{name}' is not defined"),
+						);
+						return;
+					};
+					let arity = Arity {
+						lower: args
+							.iter()
+							.rposition(|(_, kind)| !kind.can_be_none())
+							.map_or(0, |x| x + 1),
+						upper: Some(args.len()),
+					};
+					if !arity.accepts(call.arguments.len()) {
+						self.report(
+							"invalid-arguments",
+							&needle,
+							format!(
+								"Incorrect arguments for function fn::{name}(). {}",
+								arity.expected()
+							),
+						);
+					}
+				}
+				_ => {}
+			},
+			Expr::Select(select) => {
+				let table = self.check_targets(&select.what);
+				if let Some(table) = table {
+					let mut fields = Vec::new();
+					for field in select.expr.iter_fields() {
+						if let Field::Single {
+							expr,
+							..
+						} = field
+						{
+							fields_in(expr, &mut fields);
+						}
+					}
+					if let Some(cond) = &select.cond {
+						fields_in(&cond.0, &mut fields);
+					}
+					self.check_fields(table, fields);
+				}
+			}
+			Expr::Create(create) => {
+				if let Some(table) = self.check_targets(&create.what) {
+					self.check_data(table, create.data.as_ref());
+				}
+			}
+			Expr::Update(update) => {
+				if let Some(table) = self.check_targets(&update.what) {
+					self.check_data(table, update.data.as_ref());
+					self.check_cond(table, update.cond.as_ref().map(|x| &x.0));
+				}
+			}
+			Expr::Upsert(upsert) => {
+				if let Some(table) = self.check_targets(&upsert.what) {
+					self.check_data(table, upsert.data.as_ref());
+					self.check_cond(table, upsert.cond.as_ref().map(|x| &x.0));
+				}
+			}
+			Expr::Delete(delete) => {
+				if let Some(table) = self.check_targets(&delete.what) {
+					self.check_cond(table, delete.cond.as_ref().map(|x| &x.0));
+				}
+			}
+			Expr::Insert(insert) => {
+				let Some(into) = &insert.into else {
+					return;
+				};
+				if let Some(table) = self.check_targets(std::slice::from_ref(into)) {
+					self.check_data(table, Some(&insert.data));
+				}
+			}
+			Expr::Relate(relate) => {
+				if let Some(table) = self.check_targets(std::slice::from_ref(&relate.through)) {
+					self.check_data(table, relate.data.as_ref());
+				}
+			}
+			_ => {}
+		}
+	}
+
+	/// Checks that the targets of a statement are defined tables, returning
+	/// the table if the statement targets a single schemafull table.
+	fn check_targets<'e>(&mut self, what: &'e [Expr]) -> Option<&'e str> {
+		let mut tables = Vec::new();
+		for expr in what {
+			let name = match expr {
+				Expr::Table(name) => name.as_str(),
+				Expr::Literal(Literal::RecordId(record)) => record.table.as_str(),
+				_ => return None,
+			};
+			if !self.schema.tables.contains_key(name) {
+				self.report("unknown-table", name, format!("The table '{name}' does not exist"));
+				return None;
+			}
+			tables.push(name);
+		}
+		match tables.as_slice() {
+			[table] if self.schema.tables[*table].full => Some(table),
+			_ => None,
+		}
+	}
+
+	fn check_data(&mut self, table: &str, data: Option<&Data>) {
+		let Some(data) = data else {
+			return;
+		};
+		match data {
+			Data::SetExpression(assignments) | Data::UpdateExpression(assignments) => {
+				for assignment in assignments {
+					self.check_field(table, &assignment.place);
+					if assignment.operator == AssignOperator::Assign {
+						self.check_kind(table, &assignment.place, &assignment.value);
+					}
+				}
+			}
+			Data::UnsetExpression(idioms) => {
+				for idiom in idioms {
+					self.check_field(table, idiom);
+				}
+			}
+			Data::ValuesExpression(rows) => {
+				for (idiom, value) in rows.iter().flatten() {
+					self.check_field(table, idiom);
+					self.check_kind(table, idiom, value);
+				}
+			}
+			Data::ContentExpression(expr)
+			| Data::MergeExpression(expr)
+			| Data::ReplaceExpression(expr)
+			| Data::SingleExpression(expr) => {
+				let objects = match expr {
+					Expr::Literal(Literal::Array(exprs)) => exprs.iter().collect(),
+					expr => vec![expr],
+				};
+				for object in objects {
+					let Expr::Literal(Literal::Object(entries)) = object else {
+						continue;
+					};
+					for entry in entries {
+						let idiom = Idiom::field(entry.key.clone());
+						self.check_field(table, &idiom);
+						self.check_kind(table, &idiom, &entry.value);
+					}
+				}
+			}
+			_ => {}
+		}
+	}
+
+	fn check_cond(&mut self, table: &str, cond: Option<&Expr>) {
+		let mut fields = Vec::new();
+		if let Some(cond) = cond {
+			fields_in(cond, &mut fields);
+		}
+		self.check_fields(table, fields);
+	}
+
+	fn check_fields(&mut self, table: &str, fields: Vec<&Idiom>) {
+		for idiom in fields {
+			self.check_field(table, idiom);
+		}
+	}
+
+	/// Checks that the top-level field of an idiom is defined on a table
+	fn check_field(&mut self, table: &str, idiom: &Idiom) {
+		let Some(Part::Field(name)) = idiom.first() else {
+			return;
+		};
+		let schema = self.schema_of(table);
+		let defined = name == "id"
+			|| (schema.relation && (name == "in" || name == "out"))
+			|| schema.fields.iter().any(|x| x.name.first() == idiom.first());
+		if !defined {
+			self.report(
+				"unknown-field",
+				name,
+				format!("Found field '{name}', but no such field exists for table '{table}'"),
+			);
+		}
+	}
+
+	/// Checks that a value which is assigned to a field matches its kind
+	fn check_kind(&mut self, table: &str, idiom: &Idiom, value: &Expr) {
+		let Some(field) = self.schema_of(table).fields.iter().find(|x| &x.name == idiom) else {
+			return;
+		};
+		let (Some(kind), true) = (&field.kind, field.checked) else {
+			return;
+		};
+		let Some(static_value) = static_value(value) else {
+			return;
+		};
+		if static_value.coerce_to_kind(kind).is_err() {
+			let needle = value.to_string();
+			self.report(
+				"kind-mismatch",
+				&needle,
+				format!(
+					"Found {needle} for field `{idiom}`, on table '{table}', but expected a {kind}"
+				),
+			);
+		}
+	}
+
+	/// Adds a diagnostic, located at the first occurrence of the needle in the
+	/// current statement, or at the whole statement if it does not occur.
+	fn report(&mut self, code: &'static str, needle: &str, message: String) {
+		let span = find(self.source, self.span, needle).unwrap_or(self.span);
+		let Range {
+			start,
+			end,
+		} = Location::range_of_span(self.source, span);
+		self.diagnostics.push(Diagnostic {
+			code,
+			message,
+			start,
+			end,
+		});
+	}
+}
+
+/// Collects the idioms which refer to fields of the current record, without
+/// descending into subqueries.
+fn fields_in<'a>(expr: &'a Expr, fields: &mut Vec<&'a Idiom>) {
+	match expr {
+		Expr::Idiom(idiom) => fields.push(idiom),
+		Expr::Prefix {
+			expr,
+			..
+		}
+		| Expr::Postfix {
+			expr,
+			..
+		} => fields_in(expr, fields),
+		Expr::Binary {
+			left,
+			right,
+			..
+		} => {
+			fields_in(left, fields);
+			fields_in(right, fields);
+		}
+		Expr::FunctionCall(call) => call.arguments.iter().for_each(|x| fields_in(x, fields)),
+		Expr::Literal(Literal::Array(exprs)) => exprs.iter().for_each(|x| fields_in(x, fields)),
+		Expr::Literal(Literal::Object(entries)) => {
+			entries.iter().for_each(|x| fields_in(&x.value, fields))
+		}
+		_ => {}
+	}
+}
+
+/// Returns the value of an expression which does not need to be computed
+fn static_value(expr: &Expr) -> Option<Value> {
+	let Expr::Literal(literal) = expr else {
+		return None;
+	};
+	let value = match literal {
+		Literal::None => Value::None,
+		Literal::Null => Value::Null,
+		Literal::Bool(x) => Value::Bool(*x),
+		Literal::Integer(x) => Value::Number(Number::Int(*x)),
+		Literal::Float(x) => Value::Number(Number::Float(*x)),
+		Literal::Decimal(x) => Value::Number(Number::Decimal(*x)),
+		Literal::String(x) => Value::String(x.clone()),
+		Literal::Duration(x) => Value::Duration(*x),
+		Literal::Datetime(x) => Value::Datetime(x.clone()),
+		Literal::Uuid(x) => Value::Uuid(*x),
+		Literal::Array(exprs) => {
+			Value::Array(Array(exprs.iter().map(static_value).collect::<Option<_>>()?))
+		}
+		Literal::Object(entries) => Value::Object(Object(
+			entries
+				.iter()
+				.map(|x| static_value(&x.value).map(|v| (x.key.clone(), v)))
+				.collect::<Option<_>>()?,
+		)),
+		_ => return None,
+	};
+	Some(value)
+}
+
+/// Returns the name of a table, field, or other definition
+fn name_of(expr: &Expr) -> Option<String> {
+	match expr {
+		Expr::Table(name) => Some(name.clone()),
+		Expr::Literal(Literal::String(name)) => Some(name.clone()),
+		Expr::Idiom(idiom) => match idiom.0.as_slice() {
+			[Part::Field(name)] => Some(name.clone()),
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+/// Returns the idiom which a field definition is defined on
+fn idiom_of(expr: &Expr) -> Option<Idiom> {
+	match expr {
+		Expr::Idiom(idiom) => Some(idiom.clone()),
+		expr => name_of(expr).map(Idiom::field),
+	}
+}
+
+/// Finds the first occurrence of a needle within a span of the source, which
+/// is not part of a longer identifier.
+pub(super) fn find(source: &str, span: Span, needle: &str) -> Option<Span> {
+	let start = span.offset as usize;
+	let text = source.get(start..start + span.len as usize)?;
+	let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+	text.match_indices(needle).map(|(i, _)| i).find_map(|i| {
+		let before = text[..i].chars().next_back();
+		let after = text[i + needle.len()..].chars().next();
+		if before.is_some_and(is_ident) || after.is_some_and(is_ident) {
+			return None;
+		}
+		Some(Span {
+			offset: (start + i) as u32,
+			len: needle.len() as u32,
+		})
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn codes(source: &str) -> Vec<&'static str> {
+		let schema = Schema::parse(
+			r#"
+			DEFINE TABLE person SCHEMAFULL;
+			DEFINE FIELD name ON person TYPE string;
+			DEFINE FIELD age ON person TYPE option<int>;
+			DEFINE FIELD created ON person VALUE time::now();
+			DEFINE TABLE likes TYPE RELATION SCHEMAFULL;
+			DEFINE TABLE log SCHEMALESS;
+			DEFINE FUNCTION fn::greet($name: string, $title: option<string>) { RETURN $name };
+			DEFINE PARAM $max VALUE 10;
+			"#,
+		)
+		.unwrap();
+		analyze(source, &schema).unwrap().into_iter().map(|x| x.code).collect()
+	}
+
+	#[test]
+	fn analyze_valid() {
+		let source = r#"
+			CREATE person SET name = 'Tobie', age = 33;
+			SELECT name, age FROM person WHERE age > $max;
+			RELATE person:a->likes->person:b SET in = person:a;
+			CREATE log SET anything = true;
+			LET $x = fn::greet('Tobie');
+			RETURN string::len($x);
+			FOR $p IN [1, 2] { UPDATE person SET age = $p };
+		"#;
+		assert_eq!(codes(source), Vec::<&str>::new());
+	}
+
+	#[test]
+	fn analyze_tables_and_fields() {
+		assert_eq!(codes("SELECT * FROM animal;"), vec!["unknown-table"]);
+		assert_eq!(codes("UPDATE person SET email = 'a@b.c';"), vec!["unknown-field"]);
+		assert_eq!(codes("SELECT email FROM person;"), vec!["unknown-field"]);
+		assert_eq!(codes("DELETE person WHERE email = '';"), vec!["unknown-field"]);
+		assert_eq!(codes("CREATE person CONTENT { nickname: 'T' };"), vec!["unknown-field"]);
+		// Subqueries are checked against their own tables
+		assert_eq!(
+			codes("SELECT * FROM person WHERE name IN (SELECT VALUE x FROM log);"),
+			Vec::<&str>::new()
+		);
+	}
+
+	#[test]
+	fn analyze_kinds() {
+		assert_eq!(codes("CREATE person SET name = 42;"), vec!["kind-mismatch"]);
+		assert_eq!(codes("CREATE person CONTENT { age: 'old' };"), vec!["kind-mismatch"]);
+		// Fields with a VALUE clause are not checked
+		assert_eq!(codes("CREATE person SET created = 42;"), Vec::<&str>::new());
+	}
+
+	#[test]
+	fn analyze_functions_and_params() {
+		assert_eq!(codes("RETURN fn::missing();"), vec!["unknown-function"]);
+		assert_eq!(codes("RETURN fn::greet();"), vec!["invalid-arguments"]);
+		assert_eq!(codes("RETURN fn::greet('a', 'b', 'c');"), vec!["invalid-arguments"]);
+		assert_eq!(codes("RETURN string::len('a', 'b');"), vec!["invalid-arguments"]);
+		assert_eq!(codes("RETURN $missing;"), vec!["unknown-param"]);
+	}
+
+	#[test]
+	fn analyze_locations() {
+		let schema = Schema::default();
+		let diagnostics = analyze("SELECT * FROM a;\nRETURN 1 + $missing;", &schema).unwrap();
+		let param = diagnostics.iter().find(|x| x.code == "unknown-param").unwrap();
+		assert_eq!((param.start.line, param.start.column), (2, 12));
+		assert_eq!((param.end.line, param.end.column), (2, 20));
+	}
+
+	#[test]
+	fn analyze_parse_error() {
+		let diagnostics = analyze("SELECT * FROM", &Schema::default()).unwrap();
+		assert_eq!(diagnostics.len(), 1);
+		assert_eq!(diagnostics[0].code, "parse-error");
+	}
+}
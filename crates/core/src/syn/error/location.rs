@@ -1,12 +1,14 @@
 use std::ops::Range;
 
+use serde::Serialize;
+
 use crate::syn::token::Span;
 
 /// A human readable location inside a string.
 ///
 /// Locations are 1 indexed, the first character on the first line being on line
 /// 1 column 1.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize)]
 pub struct Location {
 	pub line: usize,
 	/// In chars.
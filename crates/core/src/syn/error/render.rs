@@ -89,6 +89,11 @@ impl Snippet {
 	/// it gets truncated.
 	const MAX_ERROR_LINE_OFFSET: usize = 50;
 
+	/// The location of the snippet in the original source code.
+	pub fn location(&self) -> Location {
+		self.location
+	}
+
 	pub fn from_source_location(
 		source: &str,
 		location: Location,
@@ -0,0 +1,283 @@
+//! Carries the comments within the source of a statement over to its printed
+//! form.
+//!
+//! The syntax tree does not retain comments, so each comment is attached to
+//! the span of a token next to it in the source: the token before it, if the
+//! comment follows that token on the same line, or otherwise the token after
+//! it. The significant tokens of the source and of the printed statement are
+//! then matched up, and each comment is written next to the printed token
+//! which matches its own.
+
+use crate::syn::lexer::Lexer;
+use crate::syn::token::{Span, TokenKind};
+
+/// The largest number of token pairs which are compared when matching up the
+/// tokens of the source and the printed statement
+const MAX_COMPARISONS: usize = 1 << 22;
+
+/// A comment within the source of a statement
+struct Comment<'a> {
+	/// The text of the comment, without any trailing newline
+	text: &'a str,
+	/// The index of the significant token which the comment is attached to
+	token: usize,
+	/// Whether the comment follows the token on the same line, rather than
+	/// preceding it
+	trailing: bool,
+}
+
+/// Checks whether the source of a statement contains any comments
+pub(super) fn has_comments(text: &str) -> bool {
+	let mut lexer = Lexer::new(text.as_bytes());
+	loop {
+		let token = lexer.next_token();
+		match token.kind {
+			TokenKind::Eof => return false,
+			TokenKind::WhiteSpace => {
+				if !lexer.span_str(token.span).trim().is_empty() {
+					return true;
+				}
+			}
+			_ => {}
+		}
+	}
+}
+
+/// A comment, and where it is written in a printed statement
+pub(super) struct Placement<'a> {
+	/// The text of the comment
+	text: &'a str,
+	/// The offset in the printed text at which the comment is written
+	pub(super) offset: usize,
+	/// Whether the comment follows the token which ends at the offset, rather
+	/// than preceding the token which starts there
+	trailing: bool,
+}
+
+impl Placement<'_> {
+	/// Whether the comment continues until the end of the line
+	fn is_line(&self) -> bool {
+		!self.text.starts_with("/*")
+	}
+
+	/// Checks whether writing the comment breaks a line of the printed text
+	pub(super) fn breaks_line(&self, printed: &str) -> bool {
+		if self.trailing {
+			self.is_line() && !rest_of_line(printed, self.offset).trim().is_empty()
+		} else {
+			!line_before(printed, self.offset).trim().is_empty()
+		}
+	}
+}
+
+/// Finds where the comments in the source of a statement are written in its
+/// printed form, returning `None` if the tokens of the two could not be
+/// matched up.
+pub(super) fn place<'a>(source: &'a str, printed: &str) -> Option<Vec<Placement<'a>>> {
+	let (source_tokens, comments) = lex(source);
+	let (printed_tokens, _) = lex(printed);
+	if source_tokens.len().saturating_mul(printed_tokens.len()) > MAX_COMPARISONS {
+		return None;
+	}
+	let matches = match_tokens(&source_tokens, &printed_tokens);
+	let mut placements = Vec::with_capacity(comments.len());
+	for comment in comments {
+		let (offset, trailing) = if comment.trailing {
+			// Attach to the nearest matched token at or before this one
+			match (0..=comment.token).rev().find_map(|i| matches[i]) {
+				Some(j) => (end(printed_tokens[j].1), true),
+				None => (printed_tokens.first()?.1.offset as usize, false),
+			}
+		} else {
+			// Attach to the nearest matched token at or after this one
+			match (comment.token..source_tokens.len()).find_map(|i| matches[i]) {
+				Some(j) => (printed_tokens[j].1.offset as usize, false),
+				None => (end(printed_tokens.last()?.1), true),
+			}
+		};
+		placements.push(Placement {
+			text: comment.text,
+			offset,
+			trailing,
+		});
+	}
+	// Comments at the same offset keep their order in the source
+	placements.sort_by_key(|placement| placement.offset);
+	Some(placements)
+}
+
+/// Writes the printed text of a statement with the comments in it
+pub(super) fn insert(printed: &str, placements: &[Placement], unit: &str) -> String {
+	let mut out = String::with_capacity(printed.len());
+	let mut offset = 0;
+	let mut skip_spaces = false;
+	let mut open_line = false;
+	for placement in placements {
+		let position = placement.offset;
+		push_printed(&mut out, &printed[offset..position], &mut skip_spaces, &mut open_line);
+		offset = position;
+		let continuation = continuation_indent(printed, position, unit);
+		if placement.trailing {
+			out.push(' ');
+			out.push_str(placement.text);
+			if placement.is_line() {
+				open_line = true;
+			}
+		} else if line_before(&out, out.len()).trim().is_empty() {
+			// The comment goes on its own line, above the token
+			let indent = line_before(&out, out.len()).to_owned();
+			if printed[position..].starts_with(['}', ']', ')']) {
+				// A comment at the end of a block belongs inside it
+				out.push_str(unit);
+			}
+			out.push_str(placement.text);
+			out.push('\n');
+			out.push_str(&indent);
+		} else {
+			// The token is moved on to a new line, below the comment
+			let len = out.trim_end_matches([' ', '\t']).len();
+			out.truncate(len);
+			out.push('\n');
+			out.push_str(&continuation);
+			out.push_str(placement.text);
+			out.push('\n');
+			out.push_str(&continuation);
+			open_line = false;
+			skip_spaces = true;
+		}
+		if open_line && !rest_of_line(printed, position).trim().is_empty() {
+			// The printed text continues on a new line after a line comment
+			out.push('\n');
+			out.push_str(&continuation);
+			open_line = false;
+			skip_spaces = true;
+		}
+	}
+	push_printed(&mut out, &printed[offset..], &mut skip_spaces, &mut open_line);
+	if open_line {
+		// Nothing may follow a line comment on the same line
+		out.push('\n');
+	}
+	out
+}
+
+/// Appends printed text, leaving out any leading spaces if required
+fn push_printed(out: &mut String, text: &str, skip_spaces: &mut bool, open_line: &mut bool) {
+	let text = if *skip_spaces {
+		text.trim_start_matches([' ', '\t'])
+	} else {
+		text
+	};
+	if !text.is_empty() {
+		*skip_spaces = false;
+		if text.contains('\n') {
+			*open_line = false;
+		}
+	}
+	out.push_str(text);
+}
+
+/// Splits the source of a statement into its significant tokens, and the
+/// comments attached to them
+fn lex(text: &str) -> (Vec<(TokenKind, Span)>, Vec<Comment<'_>>) {
+	let mut lexer = Lexer::new(text.as_bytes());
+	let mut tokens = Vec::new();
+	let mut comments = Vec::new();
+	// Whether there is a newline between the last token and this one
+	let mut newline = false;
+	loop {
+		let token = lexer.next_token();
+		match token.kind {
+			TokenKind::Eof => break,
+			TokenKind::WhiteSpace => {
+				let span = lexer.span_str(token.span);
+				let comment = span.trim();
+				if comment.is_empty() {
+					newline |= span.contains('\n');
+					continue;
+				}
+				let trailing = !newline && !tokens.is_empty();
+				comments.push(Comment {
+					text: comment,
+					token: if trailing {
+						tokens.len() - 1
+					} else {
+						tokens.len()
+					},
+					trailing,
+				});
+				// A line comment includes the newline which ends it
+				newline |= span.contains('\n') || !comment.starts_with("/*");
+			}
+			kind => {
+				tokens.push((kind, token.span));
+				newline = false;
+			}
+		}
+	}
+	// Comments after the last token are attached to it
+	for comment in comments.iter_mut() {
+		if comment.token == tokens.len() && !tokens.is_empty() {
+			comment.token -= 1;
+			comment.trailing = true;
+		}
+	}
+	(tokens, comments)
+}
+
+/// Matches up the tokens of the source with those of the printed text,
+/// returning the index of the printed token which matches each source token.
+///
+/// Tokens are matched by their longest common subsequence of kinds.
+fn match_tokens(source: &[(TokenKind, Span)], printed: &[(TokenKind, Span)]) -> Vec<Option<usize>> {
+	let (n, m) = (source.len(), printed.len());
+	// The length of the longest common subsequence of the suffixes
+	let mut lengths = vec![0u32; (n + 1) * (m + 1)];
+	let at = |i: usize, j: usize| i * (m + 1) + j;
+	for i in (0..n).rev() {
+		for j in (0..m).rev() {
+			lengths[at(i, j)] = if source[i].0 == printed[j].0 {
+				lengths[at(i + 1, j + 1)] + 1
+			} else {
+				lengths[at(i + 1, j)].max(lengths[at(i, j + 1)])
+			};
+		}
+	}
+	let mut matches = vec![None; n];
+	let (mut i, mut j) = (0, 0);
+	while i < n && j < m {
+		if source[i].0 == printed[j].0 {
+			matches[i] = Some(j);
+			i += 1;
+			j += 1;
+		} else if lengths[at(i + 1, j)] >= lengths[at(i, j + 1)] {
+			i += 1;
+		} else {
+			j += 1;
+		}
+	}
+	matches
+}
+
+/// The offset of the end of a span
+fn end(span: Span) -> usize {
+	(span.offset + span.len) as usize
+}
+
+/// The start of the line of text before an offset
+fn line_before(text: &str, offset: usize) -> &str {
+	&text[text[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0)..offset]
+}
+
+/// The remainder of the printed line from an offset
+fn rest_of_line(printed: &str, offset: usize) -> &str {
+	let rest = &printed[offset..];
+	&rest[..rest.find('\n').unwrap_or(rest.len())]
+}
+
+/// The indentation for a line which continues the printed line at an offset
+fn continuation_indent(printed: &str, offset: usize, unit: &str) -> String {
+	let line = line_before(printed, offset);
+	let indent = &line[..line.len() - line.trim_start_matches([' ', '\t']).len()];
+	format!("{indent}{unit}")
+}
@@ -0,0 +1,296 @@
+//! A formatter for SurrealQL source files.
+//!
+//! Statements are parsed, and then laid out by a printer over their syntax
+//! tree, with the configured indentation and keyword casing. Comments within
+//! a statement are attached to the tokens next to them and written back into
+//! the printed statement. Comments between statements are preserved, and
+//! blank lines between statements are kept, with runs of blank lines
+//! collapsed into one.
+//!
+//! Formatting never changes the meaning of a query. Every formatted
+//! statement is parsed again, and if it does not result in the same
+//! statement, the statement is kept as it was written instead.
+
+mod comments;
+mod printer;
+
+use anyhow::Result;
+
+use self::printer::Printer;
+use super::lexer::Lexer;
+use super::token::{Span, TokenKind};
+use crate::sql::TopLevelExpr;
+
+/// The indentation which is used for nested syntax
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Indent {
+	/// Indent with one tab per level
+	#[default]
+	Tabs,
+	/// Indent with the specified number of spaces per level
+	Spaces(u8),
+}
+
+/// The casing which is used for keywords
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum KeywordCase {
+	/// Write keywords in uppercase, for example `SELECT`
+	#[default]
+	Upper,
+	/// Write keywords in lowercase, for example `select`
+	Lower,
+}
+
+/// The options for formatting SurrealQL source
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FormatOptions {
+	/// The indentation which is used for nested syntax
+	pub indent: Indent,
+	/// The casing which is used for keywords
+	pub keyword_case: KeywordCase,
+}
+
+/// Formats SurrealQL source, returning an error if it could not be parsed.
+///
+/// The output always ends with a newline, unless the source contains no
+/// statements or comments at all.
+pub fn format(source: &str, options: &FormatOptions) -> Result<String> {
+	let statements = super::parse_statements(source)?;
+	// Output each statement, preceded by the comments before it
+	let mut out = String::new();
+	let mut offset = 0;
+	for (stmt, span) in statements.iter() {
+		let start = span.offset as usize;
+		write_gap(&mut out, &source[offset..start], true);
+		let text = &source[start..start + span.len as usize];
+		out.push_str(&format_statement(stmt, text, options).unwrap_or_else(|| text.to_owned()));
+		out.push(';');
+		offset = start + span.len as usize;
+	}
+	write_gap(&mut out, &source[offset..], false);
+	// Ensure the output ends with a single newline
+	let len = out.trim_end().len();
+	out.truncate(len);
+	if !out.is_empty() {
+		out.push('\n');
+	}
+	Ok(out)
+}
+
+/// Prints a statement, along with the comments in its source, returning
+/// `None` if the result did not parse as the same statement.
+fn format_statement(stmt: &TopLevelExpr, source: &str, options: &FormatOptions) -> Option<String> {
+	let mut printer = Printer::new(options);
+	let mut text = printer.print(stmt);
+	let mut placements = Vec::new();
+	if comments::has_comments(source) {
+		// Break any nodes on a single line which a comment would split
+		loop {
+			placements = comments::place(source, &text)?;
+			let offsets = placements.iter().filter(|p| p.breaks_line(&text)).map(|p| p.offset);
+			if !printer.break_around(offsets) {
+				break;
+			}
+			text = printer.print(stmt);
+		}
+	}
+	if !reparses_as(&text, stmt) {
+		return None;
+	}
+	if options.keyword_case == KeywordCase::Lower {
+		// Identifiers might have the same name as a
+		// keyword, so only use the lowercase keywords
+		// if the statement has not changed.
+		let lower = lowercase_keywords(&text);
+		if reparses_as(&lower, stmt) {
+			text = lower;
+		}
+	}
+	if !placements.is_empty() {
+		let unit = printer::indent_unit(options.indent);
+		text = comments::insert(&text, &placements, &unit);
+		if !reparses_as(&text, stmt) {
+			return None;
+		}
+	}
+	Some(text)
+}
+
+/// Checks whether the text parses as exactly the specified statement
+fn reparses_as(text: &str, stmt: &TopLevelExpr) -> bool {
+	match super::parse(text) {
+		Ok(ast) => ast.expressions.len() == 1 && &ast.expressions[0] == stmt,
+		Err(_) => false,
+	}
+}
+
+/// Converts the uppercase keywords in printed SurrealQL to lowercase
+fn lowercase_keywords(text: &str) -> String {
+	let mut lexer = Lexer::new(text.as_bytes());
+	let mut out = String::with_capacity(text.len());
+	let mut offset = 0;
+	loop {
+		let token = lexer.next_token();
+		if token.kind == TokenKind::Eof {
+			break;
+		}
+		let Span {
+			offset: start,
+			len,
+		} = token.span;
+		let end = (start + len) as usize;
+		let word = &text[start as usize..end];
+		let keyword = matches!(
+			token.kind,
+			TokenKind::Keyword(_)
+				| TokenKind::Algorithm(_)
+				| TokenKind::Language(_)
+				| TokenKind::Distance(_)
+				| TokenKind::VectorType(_)
+				| TokenKind::Operator(_)
+		);
+		if keyword && word.bytes().all(|b| b.is_ascii_uppercase() || b == b'_') {
+			out.push_str(&text[offset..start as usize]);
+			out.push_str(&word.to_ascii_lowercase());
+			offset = end;
+		}
+	}
+	out.push_str(&text[offset..]);
+	out
+}
+
+/// Writes the comments in the source between two statements.
+///
+/// A comment on the same line as the end of the previous statement stays on
+/// that line. Other comments are each written on their own line, and a blank
+/// line is kept wherever the source contains one or more blank lines.
+fn write_gap(out: &mut String, gap: &str, before_statement: bool) {
+	let mut newlines = 0;
+	let mut chars = gap.char_indices().peekable();
+	while let Some((i, c)) = chars.next() {
+		let len = match c {
+			'\n' => {
+				newlines += 1;
+				continue;
+			}
+			'#' => line_comment_len(&gap[i..]),
+			'-' | '/' if gap[i + 1..].starts_with(c) => line_comment_len(&gap[i..]),
+			'/' if gap[i + 1..].starts_with('*') => {
+				gap[i + 2..].find("*/").map(|end| end + 4).unwrap_or(gap.len() - i)
+			}
+			// Whitespace and empty statements are skipped
+			_ => continue,
+		};
+		let comment = gap[i..i + len].trim_end();
+		write_separator(out, newlines);
+		out.push_str(comment);
+		newlines = 0;
+		// Skip the remainder of the comment
+		while chars.next_if(|(j, _)| *j < i + len).is_some() {}
+	}
+	if before_statement {
+		// Statements always start on a new line
+		write_separator(out, newlines.max(1));
+	}
+}
+
+/// Writes the whitespace which precedes a comment or statement
+fn write_separator(out: &mut String, newlines: usize) {
+	if out.is_empty() {
+		return;
+	}
+	match newlines {
+		0 => out.push(' '),
+		1 => out.push('\n'),
+		_ => out.push_str("\n\n"),
+	}
+}
+
+/// The length of a comment which continues until the end of the line
+fn line_comment_len(text: &str) -> usize {
+	text.find('\n').unwrap_or(text.len())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn format_statements() {
+		let source = "select * from person where age>18;;create person:tobie set name='Tobie'";
+		assert_eq!(
+			format(source, &FormatOptions::default()).unwrap(),
+			"SELECT * FROM person WHERE age > 18;\nCREATE person:tobie SET name = 'Tobie';\n"
+		);
+	}
+
+	#[test]
+	fn format_comments() {
+		let source = "-- The first statement\nSELECT * FROM a; // After the first\n\n\n\n/* Before the second */\nSELECT * FROM b;\n# The end\n";
+		assert_eq!(
+			format(source, &FormatOptions::default()).unwrap(),
+			"-- The first statement\nSELECT * FROM a; // After the first\n\n/* Before the second */\nSELECT * FROM b;\n# The end\n"
+		);
+	}
+
+	#[test]
+	fn format_comments_within_statements() {
+		let source =
+			"SELECT *   -- every field\nFROM a;\nselect * from b /* all of b */ where x=1;";
+		assert_eq!(
+			format(source, &FormatOptions::default()).unwrap(),
+			"SELECT * -- every field\n\tFROM a;\nSELECT * FROM b /* all of b */ WHERE x = 1;\n"
+		);
+		let source = "DEFINE FUNCTION fn::add($a: int, $b: int) {\n-- Add them up\nlet $sum = $a+$b; return $sum; // The result\n};";
+		assert_eq!(
+			format(source, &FormatOptions::default()).unwrap(),
+			"DEFINE FUNCTION fn::add($a: int, $b: int) {\n\t-- Add them up\n\tLET $sum = $a + $b;\n\tRETURN $sum; // The result\n} PERMISSIONS FULL;\n"
+		);
+		let source = "IF $a {\nCREATE b;\n/* At the end */\n};";
+		let out = format(source, &FormatOptions::default()).unwrap();
+		assert_eq!(out, "IF $a {\n\tCREATE b;\n\t/* At the end */\n};\n");
+		// The formatted output is stable
+		assert_eq!(format(&out, &FormatOptions::default()).unwrap(), out);
+		let source = "CREATE person CONTENT {\n\t# The name\n\tname: 'Tobie'\n};";
+		assert_eq!(
+			format(source, &FormatOptions::default()).unwrap(),
+			"CREATE person CONTENT {\n\t# The name\n\tname: 'Tobie'\n};\n"
+		);
+	}
+
+	#[test]
+	fn format_indent() {
+		let source = format!("SELECT * FROM {{foo: [1, 2, 3], bar: '{}'}};", "x".repeat(80));
+		let options = FormatOptions {
+			indent: Indent::Spaces(2),
+			..Default::default()
+		};
+		assert_eq!(
+			format(&source, &options).unwrap(),
+			format!("SELECT * FROM {{\n  foo: [1, 2, 3],\n  bar: '{}'\n}};\n", "x".repeat(80))
+		);
+	}
+
+	#[test]
+	fn format_keyword_case() {
+		let source = "SELECT * FROM person WHERE name CONTAINS 'SELECT' AND `WHERE` = NONE;";
+		let options = FormatOptions {
+			keyword_case: KeywordCase::Lower,
+			..Default::default()
+		};
+		let out = format(source, &options).unwrap();
+		assert!(out.starts_with("select * from person where name contains 'SELECT' and "), "{out}");
+		// The formatted output is stable
+		assert_eq!(format(&out, &options).unwrap(), out);
+	}
+
+	#[test]
+	fn format_invalid() {
+		assert!(format("SELECT * FROM", &FormatOptions::default()).is_err());
+	}
+
+	#[test]
+	fn format_empty() {
+		assert_eq!(format(" ;\n", &FormatOptions::default()).unwrap(), "");
+	}
+}
@@ -0,0 +1,705 @@
+//! A printer which lays out the syntax tree of a statement.
+//!
+//! The printer walks the expressions and clauses of a statement, and breaks
+//! blocks, objects and arrays over several indented lines. Nodes which have
+//! no nested layout of their own, like idioms, kinds and most definitions,
+//! are written with their compact `Display` form.
+
+use std::collections::HashSet;
+use std::fmt::{Display, Write};
+use std::ops::Range;
+
+use super::{FormatOptions, Indent};
+use crate::fmt::{EscapeIdent, EscapeKey};
+use crate::sql::literal::ObjectEntry;
+use crate::sql::operator::BindingPower;
+use crate::sql::statements::define::DefineKind;
+use crate::sql::statements::{
+	CreateStatement, DefineEventStatement, DefineFunctionStatement, DefineParamStatement,
+	DefineStatement, DeleteStatement, ForeachStatement, IfelseStatement, InsertStatement,
+	OutputStatement, RelateStatement, SelectStatement, SetStatement, UpdateStatement,
+	UpsertStatement,
+};
+use crate::sql::{
+	BinaryOperator, Block, Closure, Cond, Data, Expr, Field, Fields, Function, FunctionCall, Kind,
+	Literal, TopLevelExpr,
+};
+
+/// The column after which blocks, objects and arrays are broken over
+/// several lines
+const MAX_WIDTH: usize = 100;
+
+/// The number of columns which a tab is counted as
+const TAB_WIDTH: usize = 4;
+
+pub(super) struct Printer {
+	/// The printed text
+	out: String,
+	/// The text of one level of indentation
+	unit: String,
+	/// The current level of indentation
+	level: usize,
+	/// The nodes which are broken over several lines, even if they fit on one
+	breaks: HashSet<*const ()>,
+	/// The nodes which were written on a single line, and where they were
+	/// written
+	flat: Vec<(*const (), Range<usize>)>,
+}
+
+impl Printer {
+	pub(super) fn new(options: &FormatOptions) -> Self {
+		Printer {
+			out: String::new(),
+			unit: indent_unit(options.indent),
+			level: 0,
+			breaks: HashSet::new(),
+			flat: Vec::new(),
+		}
+	}
+
+	/// Prints a top-level statement
+	pub(super) fn print(&mut self, stmt: &TopLevelExpr) -> String {
+		self.flat.clear();
+		match stmt {
+			TopLevelExpr::Expr(expr) => self.expr(expr),
+			stmt => self.display(stmt),
+		}
+		std::mem::take(&mut self.out)
+	}
+
+	/// Breaks the nodes which were last written on a single line around any of
+	/// the offsets, returning whether there were any such nodes.
+	pub(super) fn break_around(&mut self, offsets: impl IntoIterator<Item = usize>) -> bool {
+		let mut changed = false;
+		for offset in offsets {
+			for (node, range) in self.flat.iter() {
+				if range.start < offset && offset < range.end {
+					changed |= self.breaks.insert(*node);
+				}
+			}
+		}
+		changed
+	}
+
+	fn expr(&mut self, expr: &Expr) {
+		match expr {
+			Expr::Literal(Literal::Array(items)) => self.array(expr, items),
+			Expr::Literal(Literal::Object(entries)) => self.object(expr, entries),
+			Expr::Literal(Literal::Closure(closure)) | Expr::Closure(closure) => {
+				self.closure(closure)
+			}
+			Expr::Block(block) => self.block(block),
+			Expr::Prefix {
+				op,
+				expr,
+			} => {
+				let expr_bp = BindingPower::for_expr(expr);
+				let op_bp = BindingPower::for_prefix_operator(op);
+				self.display(op);
+				self.operand(
+					expr,
+					expr_bp < op_bp || expr_bp == op_bp && matches!(expr_bp, BindingPower::Range),
+				);
+			}
+			Expr::Postfix {
+				expr,
+				op,
+			} => {
+				let expr_bp = BindingPower::for_expr(expr);
+				let op_bp = BindingPower::for_postfix_operator(op);
+				self.operand(
+					expr,
+					expr_bp < op_bp || expr_bp == op_bp && matches!(expr_bp, BindingPower::Range),
+				);
+				self.display(op);
+			}
+			Expr::Binary {
+				left,
+				op,
+				right,
+			} => {
+				let op_bp = BindingPower::for_binary_operator(op);
+				for (i, expr) in [left, right].into_iter().enumerate() {
+					if i > 0 {
+						if matches!(
+							op,
+							BinaryOperator::Range
+								| BinaryOperator::RangeSkip
+								| BinaryOperator::RangeInclusive
+								| BinaryOperator::RangeSkipInclusive
+						) {
+							self.display(op);
+						} else {
+							write!(self.out, " {op} ").ok();
+						}
+					}
+					let expr_bp = BindingPower::for_expr(expr);
+					self.operand(
+						expr,
+						expr_bp < op_bp
+							|| expr_bp == op_bp
+								&& matches!(expr_bp, BindingPower::Range | BindingPower::Relation),
+					);
+				}
+			}
+			Expr::FunctionCall(call) => self.function_call(call),
+			Expr::Throw(expr) => {
+				self.write("THROW ");
+				self.expr(expr);
+			}
+			Expr::Return(stmt) => self.output(stmt),
+			Expr::If(stmt) => self.if_else(stmt),
+			Expr::Select(stmt) => self.select(stmt),
+			Expr::Create(stmt) => self.create(stmt),
+			Expr::Update(stmt) => self.update(stmt),
+			Expr::Upsert(stmt) => self.upsert(stmt),
+			Expr::Delete(stmt) => self.delete(stmt),
+			Expr::Relate(stmt) => self.relate(stmt),
+			Expr::Insert(stmt) => self.insert(stmt),
+			Expr::Define(stmt) => self.define(stmt),
+			Expr::Foreach(stmt) => self.foreach(stmt),
+			Expr::Let(stmt) => self.set(stmt),
+			expr => self.display(expr),
+		}
+	}
+
+	/// Prints the operand of an operator, in parentheses if required
+	fn operand(&mut self, expr: &Expr, parenthesized: bool) {
+		if parenthesized {
+			self.write("(");
+			self.expr(expr);
+			self.write(")");
+		} else {
+			self.expr(expr);
+		}
+	}
+
+	/// Prints a block, with each statement on its own line
+	fn block(&mut self, block: &Block) {
+		if block.0.is_empty() {
+			return self.display(block);
+		}
+		if block.0.len() == 1 && self.flat(block) {
+			return;
+		}
+		self.write("{");
+		self.level += 1;
+		for expr in block.0.iter() {
+			self.newline();
+			self.expr(expr);
+			self.write(";");
+		}
+		self.level -= 1;
+		self.newline();
+		self.write("}");
+	}
+
+	/// Prints an array, with each item on its own line if it does not fit
+	fn array(&mut self, expr: &Expr, items: &[Expr]) {
+		if items.is_empty() {
+			return self.display(expr);
+		}
+		if self.flat(expr) {
+			return;
+		}
+		self.write("[");
+		self.level += 1;
+		for (i, item) in items.iter().enumerate() {
+			if i > 0 {
+				self.write(",");
+			}
+			self.newline();
+			self.expr(item);
+		}
+		self.level -= 1;
+		self.newline();
+		self.write("]");
+	}
+
+	/// Prints an object, with each entry on its own line if it does not fit
+	fn object(&mut self, expr: &Expr, entries: &[ObjectEntry]) {
+		if entries.is_empty() {
+			return self.display(expr);
+		}
+		if self.flat(expr) {
+			return;
+		}
+		self.write("{");
+		self.level += 1;
+		for (i, entry) in entries.iter().enumerate() {
+			if i > 0 {
+				self.write(",");
+			}
+			self.newline();
+			write!(self.out, "{}: ", EscapeKey(&entry.key)).ok();
+			self.expr(&entry.value);
+		}
+		self.level -= 1;
+		self.newline();
+		self.write("}");
+	}
+
+	fn closure(&mut self, closure: &Closure) {
+		self.write("|");
+		for (i, (name, kind)) in closure.args.iter().enumerate() {
+			if i > 0 {
+				self.write(", ");
+			}
+			match kind {
+				k @ Kind::Either(_) => write!(self.out, "${name}: <{k}>").ok(),
+				k => write!(self.out, "${name}: {k}").ok(),
+			};
+		}
+		self.write("|");
+		if let Some(returns) = &closure.returns {
+			write!(self.out, " -> {returns}").ok();
+		}
+		self.write(" ");
+		self.expr(&closure.body);
+	}
+
+	fn function_call(&mut self, call: &FunctionCall) {
+		match call.receiver {
+			Function::Normal(ref s) => self.write(s),
+			Function::Custom(ref s) => {
+				write!(self.out, "fn::{s}").ok();
+			}
+			Function::Model(ref m) => self.display(m),
+			Function::Script(_) => return self.display(call),
+		}
+		self.write("(");
+		self.comma_separated(&call.arguments);
+		self.write(")");
+	}
+
+	fn output(&mut self, stmt: &OutputStatement) {
+		self.write("RETURN ");
+		self.expr(&stmt.what);
+		if let Some(ref v) = stmt.fetch {
+			write!(self.out, " {v}").ok();
+		}
+	}
+
+	fn if_else(&mut self, stmt: &IfelseStatement) {
+		let bracketed = stmt.bracketed();
+		for (i, (cond, then)) in stmt.exprs.iter().enumerate() {
+			if i > 0 {
+				self.write(" ELSE ");
+			}
+			self.write("IF ");
+			self.expr(cond);
+			self.write(if bracketed {
+				" "
+			} else {
+				" THEN "
+			});
+			self.expr(then);
+		}
+		if let Some(ref v) = stmt.close {
+			self.write(" ELSE ");
+			self.expr(v);
+		}
+		if !bracketed {
+			self.write(" END");
+		}
+	}
+
+	fn foreach(&mut self, stmt: &ForeachStatement) {
+		write!(self.out, "FOR {} IN ", stmt.param).ok();
+		self.expr(&stmt.range);
+		self.write(" ");
+		self.block(&stmt.block);
+	}
+
+	fn set(&mut self, stmt: &SetStatement) {
+		write!(self.out, "LET ${}", EscapeIdent(&stmt.name)).ok();
+		if let Some(ref kind) = stmt.kind {
+			write!(self.out, ": {kind}").ok();
+		}
+		self.write(" = ");
+		self.expr(&stmt.what);
+	}
+
+	fn select(&mut self, stmt: &SelectStatement) {
+		self.write("SELECT ");
+		match stmt.expr {
+			Fields::Value(ref field) => {
+				self.write("VALUE ");
+				self.field(field);
+			}
+			Fields::Select(ref fields) => {
+				for (i, field) in fields.iter().enumerate() {
+					if i > 0 {
+						self.write(", ");
+					}
+					self.field(field);
+				}
+			}
+		}
+		if !stmt.omit.is_empty() {
+			self.write(" OMIT ");
+			self.comma_separated(&stmt.omit);
+		}
+		self.write(" FROM");
+		if stmt.only {
+			self.write(" ONLY");
+		}
+		self.write(" ");
+		self.comma_separated(&stmt.what);
+		self.clause(stmt.with.as_ref());
+		self.cond(stmt.cond.as_ref());
+		self.clause(stmt.split.as_ref());
+		self.clause(stmt.group.as_ref());
+		if let Some(ref v) = stmt.order {
+			// The ordering is written with a trailing newline
+			write!(self.out, " {}", v.to_string().trim_end()).ok();
+		}
+		self.clause(stmt.limit.as_ref());
+		self.clause(stmt.start.as_ref());
+		self.clause(stmt.fetch.as_ref());
+		self.version(stmt.version.as_ref());
+		self.clause(stmt.timeout.as_ref());
+		self.parallel(stmt.parallel);
+		self.clause(stmt.explain.as_ref());
+	}
+
+	fn create(&mut self, stmt: &CreateStatement) {
+		self.write("CREATE");
+		self.what(stmt.only, &stmt.what);
+		self.data(stmt.data.as_ref());
+		self.clause(stmt.output.as_ref());
+		self.version(stmt.version.as_ref());
+		self.clause(stmt.timeout.as_ref());
+		self.parallel(stmt.parallel);
+	}
+
+	fn update(&mut self, stmt: &UpdateStatement) {
+		self.write("UPDATE");
+		self.what(stmt.only, &stmt.what);
+		self.clause(stmt.with.as_ref());
+		self.data(stmt.data.as_ref());
+		self.cond(stmt.cond.as_ref());
+		self.clause(stmt.output.as_ref());
+		self.clause(stmt.timeout.as_ref());
+		self.parallel(stmt.parallel);
+		self.clause(stmt.explain.as_ref());
+	}
+
+	fn upsert(&mut self, stmt: &UpsertStatement) {
+		self.write("UPSERT");
+		self.what(stmt.only, &stmt.what);
+		self.clause(stmt.with.as_ref());
+		self.data(stmt.data.as_ref());
+		self.cond(stmt.cond.as_ref());
+		self.clause(stmt.output.as_ref());
+		self.clause(stmt.timeout.as_ref());
+		self.parallel(stmt.parallel);
+		self.clause(stmt.explain.as_ref());
+	}
+
+	fn delete(&mut self, stmt: &DeleteStatement) {
+		self.write("DELETE");
+		self.what(stmt.only, &stmt.what);
+		self.clause(stmt.with.as_ref());
+		self.cond(stmt.cond.as_ref());
+		self.clause(stmt.output.as_ref());
+		self.clause(stmt.timeout.as_ref());
+		self.parallel(stmt.parallel);
+		self.clause(stmt.explain.as_ref());
+	}
+
+	fn relate(&mut self, stmt: &RelateStatement) {
+		self.write("RELATE");
+		if stmt.only {
+			self.write(" ONLY");
+		}
+		self.write(" ");
+		self.expr(&stmt.from);
+		self.write(" -> ");
+		self.expr(&stmt.through);
+		self.write(" -> ");
+		self.expr(&stmt.to);
+		if stmt.uniq {
+			self.write(" UNIQUE");
+		}
+		self.data(stmt.data.as_ref());
+		self.clause(stmt.output.as_ref());
+		self.clause(stmt.timeout.as_ref());
+		self.parallel(stmt.parallel);
+	}
+
+	fn insert(&mut self, stmt: &InsertStatement) {
+		self.write("INSERT");
+		if stmt.relation {
+			self.write(" RELATION");
+		}
+		if stmt.ignore {
+			self.write(" IGNORE");
+		}
+		if let Some(ref into) = stmt.into {
+			self.write(" INTO ");
+			self.expr(into);
+		}
+		self.data(Some(&stmt.data));
+		self.data(stmt.update.as_ref());
+		self.clause(stmt.output.as_ref());
+		self.version(stmt.version.as_ref());
+		self.clause(stmt.timeout.as_ref());
+		self.parallel(stmt.parallel);
+	}
+
+	fn define(&mut self, stmt: &DefineStatement) {
+		match stmt {
+			DefineStatement::Function(stmt) if stmt.wasm.is_none() => self.define_function(stmt),
+			DefineStatement::Event(stmt) => self.define_event(stmt),
+			DefineStatement::Param(stmt) => self.define_param(stmt),
+			stmt => self.display(stmt),
+		}
+	}
+
+	fn define_function(&mut self, stmt: &DefineFunctionStatement) {
+		self.write("DEFINE FUNCTION");
+		self.define_kind(&stmt.kind);
+		write!(self.out, " fn::{}(", stmt.name).ok();
+		for (i, (name, kind)) in stmt.args.iter().enumerate() {
+			if i > 0 {
+				self.write(", ");
+			}
+			write!(self.out, "${}: {kind}", EscapeIdent(name)).ok();
+		}
+		self.write(") ");
+		if let Some(ref v) = stmt.returns {
+			write!(self.out, "-> {v} ").ok();
+		}
+		self.block(&stmt.block);
+		self.comment(stmt.comment.as_ref());
+		write!(self.out, " PERMISSIONS {}", stmt.permissions).ok();
+	}
+
+	fn define_event(&mut self, stmt: &DefineEventStatement) {
+		self.write("DEFINE EVENT");
+		self.define_kind(&stmt.kind);
+		self.write(" ");
+		self.expr(&stmt.name);
+		self.write(" ON ");
+		self.expr(&stmt.target_table);
+		self.write(" WHEN ");
+		self.expr(&stmt.when);
+		self.write(" THEN ");
+		self.comma_separated(&stmt.then);
+		self.comment(stmt.comment.as_ref());
+	}
+
+	fn define_param(&mut self, stmt: &DefineParamStatement) {
+		self.write("DEFINE PARAM");
+		self.define_kind(&stmt.kind);
+		write!(self.out, " ${} VALUE ", EscapeIdent(&stmt.name)).ok();
+		self.expr(&stmt.value);
+		self.comment(stmt.comment.as_ref());
+		write!(self.out, " PERMISSIONS {}", stmt.permissions).ok();
+	}
+
+	fn define_kind(&mut self, kind: &DefineKind) {
+		match kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => self.write(" OVERWRITE"),
+			DefineKind::IfNotExists => self.write(" IF NOT EXISTS"),
+		}
+	}
+
+	fn comment(&mut self, comment: Option<&Expr>) {
+		if let Some(v) = comment {
+			self.write(" COMMENT ");
+			self.expr(v);
+		}
+	}
+
+	fn field(&mut self, field: &Field) {
+		match field {
+			Field::All => self.write("*"),
+			Field::Single {
+				expr,
+				alias,
+			} => {
+				self.expr(expr);
+				if let Some(alias) = alias {
+					write!(self.out, " AS {alias}").ok();
+				}
+			}
+		}
+	}
+
+	/// Prints the targets of a statement, following its keyword
+	fn what(&mut self, only: bool, what: &[Expr]) {
+		if only {
+			self.write(" ONLY");
+		}
+		self.write(" ");
+		self.comma_separated(what);
+	}
+
+	fn data(&mut self, data: Option<&Data>) {
+		let Some(data) = data else {
+			return;
+		};
+		self.write(" ");
+		match data {
+			Data::SetExpression(v) | Data::UpdateExpression(v) => {
+				self.write(match data {
+					Data::SetExpression(_) => "SET ",
+					_ => "ON DUPLICATE KEY UPDATE ",
+				});
+				for (i, arg) in v.iter().enumerate() {
+					if i > 0 {
+						self.write(", ");
+					}
+					write!(self.out, "{} {} ", arg.place, arg.operator).ok();
+					self.expr(&arg.value);
+				}
+			}
+			Data::PatchExpression(v) => {
+				self.write("PATCH ");
+				self.expr(v);
+			}
+			Data::MergeExpression(v) => {
+				self.write("MERGE ");
+				self.expr(v);
+			}
+			Data::ReplaceExpression(v) => {
+				self.write("REPLACE ");
+				self.expr(v);
+			}
+			Data::ContentExpression(v) => {
+				self.write("CONTENT ");
+				self.expr(v);
+			}
+			Data::SingleExpression(v) => self.expr(v),
+			data => self.display(data),
+		}
+	}
+
+	fn cond(&mut self, cond: Option<&Cond>) {
+		if let Some(cond) = cond {
+			self.write(" WHERE ");
+			self.expr(&cond.0);
+		}
+	}
+
+	fn version(&mut self, version: Option<&Expr>) {
+		if let Some(version) = version {
+			self.write(" VERSION ");
+			self.expr(version);
+		}
+	}
+
+	fn parallel(&mut self, parallel: bool) {
+		if parallel {
+			self.write(" PARALLEL");
+		}
+	}
+
+	/// Prints a clause which has no nested layout, following a space
+	fn clause(&mut self, clause: Option<&impl Display>) {
+		if let Some(clause) = clause {
+			write!(self.out, " {clause}").ok();
+		}
+	}
+
+	fn comma_separated(&mut self, exprs: &[Expr]) {
+		for (i, expr) in exprs.iter().enumerate() {
+			if i > 0 {
+				self.write(", ");
+			}
+			self.expr(expr);
+		}
+	}
+
+	/// Writes a node on a single line, returning `false` without writing
+	/// anything if it does not fit, or if it is to be broken
+	fn flat<T: Display>(&mut self, node: &T) -> bool {
+		let key = node as *const T as *const ();
+		if self.breaks.contains(&key) {
+			return false;
+		}
+		let text = node.to_string();
+		if text.contains('\n') || self.column() + text.chars().count() > MAX_WIDTH {
+			return false;
+		}
+		let start = self.out.len();
+		self.out.push_str(&text);
+		self.flat.push((key, start..self.out.len()));
+		true
+	}
+
+	/// The column at which the next text is written
+	fn column(&self) -> usize {
+		let line = &self.out[self.out.rfind('\n').map(|i| i + 1).unwrap_or(0)..];
+		line.chars()
+			.map(|c| {
+				if c == '\t' {
+					TAB_WIDTH
+				} else {
+					1
+				}
+			})
+			.sum()
+	}
+
+	fn newline(&mut self) {
+		self.out.push('\n');
+		for _ in 0..self.level {
+			self.out.push_str(&self.unit);
+		}
+	}
+
+	fn write(&mut self, text: &str) {
+		self.out.push_str(text);
+	}
+
+	/// Writes the compact form of a node
+	fn display(&mut self, node: &impl Display) {
+		write!(self.out, "{node}").ok();
+	}
+}
+
+/// The text of one level of indentation
+pub(super) fn indent_unit(indent: Indent) -> String {
+	match indent {
+		Indent::Tabs => "\t".to_owned(),
+		Indent::Spaces(width) => " ".repeat(width as usize),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn print(source: &str) -> String {
+		let ast = crate::syn::parse(source).unwrap();
+		Printer::new(&FormatOptions::default()).print(&ast.expressions[0])
+	}
+
+	#[test]
+	fn print_blocks() {
+		assert_eq!(print("IF $a { 1 } ELSE { 2 }"), "IF $a { 1 } ELSE { 2 }");
+		assert_eq!(
+			print("FOR $x IN [1, 2] { LET $y = $x * 2; CREATE foo CONTENT { y: $y }; }"),
+			"FOR $x IN [1, 2] {\n\tLET $y = $x * 2;\n\tCREATE foo CONTENT { y: $y };\n}"
+		);
+	}
+
+	#[test]
+	fn print_long_objects() {
+		let source = format!("CREATE foo CONTENT {{ a: '{}', b: [1, 2] }}", "x".repeat(90));
+		assert_eq!(
+			print(&source),
+			format!("CREATE foo CONTENT {{\n\ta: '{}',\n\tb: [1, 2]\n}}", "x".repeat(90))
+		);
+	}
+
+	#[test]
+	fn print_operator_precedence() {
+		assert_eq!(print("RETURN (1 + 2) * 3"), "RETURN (1 + 2) * 3");
+		assert_eq!(print("RETURN -(1 + 2)"), "RETURN -(1 + 2)");
+	}
+}
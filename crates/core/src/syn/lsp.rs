@@ -0,0 +1,196 @@
+//! Language queries for editor integrations.
+//!
+//! These functions back the `surreal lsp` language server. They work on the
+//! text of a single document, using the same parser as the database, so
+//! that editors report exactly the errors which a query would fail with.
+//! Positions use the 1 indexed [`Location`] of the parser, counted in chars.
+
+use std::ops::Range;
+
+use anyhow::Result;
+
+use super::analyze::{self, BUILTIN_PARAMS, Diagnostic, Schema};
+use super::error::Location;
+use super::token::Keyword;
+use crate::expr::{DefineStatement, Expr, TopLevelExpr};
+use crate::fnc;
+
+/// The problems reported to editors, leaving out those which depend on the
+/// schema of a database the editor cannot see.
+const DIAGNOSTICS: &[&str] = &["parse-error", "invalid-arguments"];
+
+/// The kind of a definition found within a document
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SymbolKind {
+	/// A `DEFINE FUNCTION` statement
+	Function,
+	/// A `DEFINE PARAM` statement
+	Param,
+}
+
+/// A function or param defined within a document
+#[derive(Clone, Debug)]
+pub struct Symbol {
+	pub kind: SymbolKind,
+	/// The name as it is referenced, such as `fn::greet` or `$name`
+	pub name: String,
+	/// The location of the name within the definition
+	pub range: Range<Location>,
+	/// The source of the whole definition
+	pub source: String,
+}
+
+/// Words which are only keywords within a specific clause, and which are
+/// otherwise parsed as identifiers.
+const CONTEXTUAL_KEYWORDS: &[&str] = &[
+	"EXPIRY", "EXPORT", "HISTORY", "IDENTITY", "LANGUAGE", "LENGTH", "LOCKOUT", "RECOVERY",
+	"REQUEST", "REQUIRE", "RESPONSE", "SECRET", "UNLOCK", "VALIDATE", "WASM",
+];
+
+/// Returns the keywords of the language.
+pub fn keywords() -> impl Iterator<Item = &'static str> {
+	Keyword::ALL.iter().map(Keyword::as_str).chain(CONTEXTUAL_KEYWORDS.iter().copied())
+}
+
+/// Returns the names of the builtin functions, such as `string::len`.
+pub fn builtin_functions() -> impl Iterator<Item = &'static str> {
+	fnc::names()
+}
+
+/// Returns the names of the params which are always available, without the
+/// leading `$`.
+pub fn builtin_params() -> impl Iterator<Item = &'static str> {
+	BUILTIN_PARAMS.iter().copied()
+}
+
+/// Returns markdown documentation for a builtin function.
+pub fn builtin_docs(name: &str) -> Option<String> {
+	if !fnc::names().any(|x| x == name) {
+		return None;
+	}
+	let mut docs = format!("```surql\n{name}()\n```\nBuiltin function.");
+	if let Some(arity) = fnc::arity(name) {
+		docs.push_str(&format!(" {}.", arity.expected()));
+	}
+	let module = name.split("::").next().unwrap_or(name);
+	docs.push_str(&format!(
+		"\n\n[Documentation](https://surrealdb.com/docs/surrealql/functions/database/{module})"
+	));
+	Some(docs)
+}
+
+/// Checks a document for problems, using the definitions in a schema.
+pub fn diagnostics(source: &str, schema: &Schema) -> Result<Vec<Diagnostic>> {
+	let mut diagnostics = analyze::analyze(source, schema)?;
+	diagnostics.retain(|x| DIAGNOSTICS.contains(&x.code));
+	Ok(diagnostics)
+}
+
+/// Returns the functions and params defined in a document.
+///
+/// A document which does not parse has no definitions.
+pub fn symbols(source: &str) -> Vec<Symbol> {
+	let Ok(statements) = super::parse_statements(source) else {
+		return Vec::new();
+	};
+	let mut symbols = Vec::new();
+	for (stmt, span) in statements {
+		let stmt: TopLevelExpr = stmt.into();
+		let TopLevelExpr::Expr(Expr::Define(define)) = stmt else {
+			continue;
+		};
+		let (kind, name) = match *define {
+			DefineStatement::Function(function) => {
+				(SymbolKind::Function, format!("fn::{}", function.name))
+			}
+			DefineStatement::Param(param) => (SymbolKind::Param, format!("${}", param.name)),
+			_ => continue,
+		};
+		let found = analyze::find(source, span, &name).unwrap_or(span);
+		let start = span.offset as usize;
+		symbols.push(Symbol {
+			kind,
+			name,
+			range: Location::range_of_span(source, found),
+			source: source[start..start + span.len as usize].to_owned(),
+		});
+	}
+	symbols
+}
+
+/// Returns the name of the function or param at a location, such as
+/// `string::len`, `fn::greet` or `$name`.
+pub fn word_at(source: &str, location: Location) -> Option<String> {
+	let line = source.lines().nth(location.line.checked_sub(1)?)?;
+	let chars: Vec<char> = line.chars().collect();
+	let is_word = |c: &char| c.is_alphanumeric() || matches!(c, '_' | ':' | '$');
+	let at = location.column.checked_sub(1)?.min(chars.len());
+	let start = chars[..at].iter().rposition(|c| !is_word(c)).map_or(0, |x| x + 1);
+	let end = chars[at..].iter().position(|c| !is_word(c)).map_or(chars.len(), |x| at + x);
+	let word: String = chars[start..end].iter().collect();
+	let word = word.trim_matches(':');
+	(!word.is_empty()).then(|| word.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn finds_symbols() {
+		let source = "DEFINE PARAM $greeting VALUE 'Hello';\n\
+			DEFINE FUNCTION fn::greet($name: string) { RETURN $greeting + $name; };";
+		let found = symbols(source);
+		assert_eq!(found.len(), 2);
+		assert_eq!(found[0].kind, SymbolKind::Param);
+		assert_eq!(found[0].name, "$greeting");
+		assert_eq!(found[0].range.start.line, 1);
+		assert_eq!(found[0].range.start.column, 14);
+		assert_eq!(found[1].kind, SymbolKind::Function);
+		assert_eq!(found[1].name, "fn::greet");
+		assert_eq!(found[1].range.start.line, 2);
+		assert_eq!(found[1].range.start.column, 17);
+		assert!(found.iter().all(|x| x.source.starts_with("DEFINE")));
+		assert!(symbols("DEFINE FUNCTION").is_empty());
+	}
+
+	#[test]
+	fn finds_words() {
+		let source = "RETURN string::len($name);\nRETURN fn::greet();";
+		let at = |line, column| {
+			word_at(
+				source,
+				Location {
+					line,
+					column,
+				},
+			)
+		};
+		assert_eq!(at(1, 10).as_deref(), Some("string::len"));
+		assert_eq!(at(1, 19).as_deref(), Some("string::len"));
+		assert_eq!(at(1, 21).as_deref(), Some("$name"));
+		assert_eq!(at(2, 17).as_deref(), Some("fn::greet"));
+		assert_eq!(at(2, 18), None);
+		assert_eq!(at(3, 1), None);
+	}
+
+	#[test]
+	fn reports_builtins() {
+		assert!(keywords().any(|x| x == "SELECT"));
+		assert!(builtin_functions().any(|x| x == "string::len"));
+		assert!(builtin_params().any(|x| x == "auth"));
+		assert!(builtin_docs("string::len").unwrap().contains("Expected 1 argument"));
+		assert!(builtin_docs("string::unknown").is_none());
+	}
+
+	#[test]
+	fn reports_diagnostics() {
+		let schema = Schema::default();
+		let codes = |source| {
+			diagnostics(source, &schema).unwrap().into_iter().map(|x| x.code).collect::<Vec<_>>()
+		};
+		assert!(codes("SELECT * FROM person; RETURN $x;").is_empty());
+		assert_eq!(codes("RETURN string::len(1, 2);"), ["invalid-arguments"]);
+		assert_eq!(codes("SELECT * FROM;"), ["parse-error"]);
+	}
+}
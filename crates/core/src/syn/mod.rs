@@ -5,11 +5,16 @@ use crate::cnf::{MAX_OBJECT_PARSING_DEPTH, MAX_QUERY_PARSING_DEPTH};
 use crate::dbs::Capabilities;
 use crate::dbs::capabilities::ExperimentalTarget;
 use crate::err::Error;
-use crate::sql::{Ast, Block, Expr, Fetchs, Fields, Idiom, Kind, Output, RecordIdLit};
+use crate::sql::{
+	Ast, Block, Expr, Fetchs, Fields, Idiom, Kind, Output, RecordIdLit, TopLevelExpr,
+};
 use crate::val::{Datetime, Duration, RecordId, Value};
 
+pub mod analyze;
 pub mod error;
+pub mod format;
 pub mod lexer;
+pub mod lsp;
 pub mod parser;
 pub mod token;
 
@@ -25,7 +30,7 @@ use anyhow::{Result, bail, ensure};
 use lexer::Lexer;
 use parser::{ParseResult, Parser, ParserSettings};
 use reblessive::{Stack, Stk};
-use token::t;
+use token::{Span, t};
 
 const TARGET: &str = "surrealdb::core::syn";
 
@@ -108,6 +113,35 @@ pub fn parse_with_capabilities(input: &str, capabilities: &Capabilities) -> Resu
 	)
 }
 
+/// Parses the top-level statements of a SurrealQL query, along with the
+/// span of source text which each statement was parsed from.
+pub(crate) fn parse_statements(input: &str) -> Result<Vec<(TopLevelExpr, Span)>> {
+	// Ensure the whole source parses, so that errors are reported as usual
+	parse(input)?;
+	// Parse each statement along with its source span
+	parse_with_settings(
+		input.as_bytes(),
+		settings_from_capabilities(&Capabilities::all()),
+		async |parser, stk| {
+			let mut res = Vec::new();
+			loop {
+				match parser.peek_kind() {
+					t!(";") => {
+						parser.pop_peek();
+					}
+					t!("eof") => break,
+					_ => {
+						let start = parser.peek().span;
+						let stmt = parser.parse_statement(stk).await?;
+						res.push((stmt, start.covers(parser.last_span())));
+					}
+				}
+			}
+			Ok(res)
+		},
+	)
+}
+
 /// Parses a SurrealQL [`Value`].
 #[instrument(level = "trace", target = "surrealdb::core::syn", fields(length = input.len()))]
 pub fn expr(input: &str) -> Result<Expr> {
@@ -329,6 +329,34 @@ impl Parser<'_> {
 		}
 	}
 
+	/// Returns whether the next token is an identifier which matches the
+	/// given word, for words which are only keywords in a specific clause.
+	pub(crate) fn peek_ident_word(&mut self, word: &str) -> bool {
+		let token = self.peek();
+		token.kind == TokenKind::Identifier
+			&& self.lexer.span_str(token.span).eq_ignore_ascii_case(word)
+	}
+
+	/// Eats the next token if it is an identifier which matches the given word.
+	pub(crate) fn eat_ident_word(&mut self, word: &str) -> bool {
+		if self.peek_ident_word(word) {
+			self.pop_peek();
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Expects the next token to be an identifier which matches the given
+	/// word.
+	pub(crate) fn expect_ident_word(&mut self, word: &'static str) -> ParseResult<()> {
+		if self.eat_ident_word(word) {
+			return Ok(());
+		}
+		let token = self.next();
+		unexpected!(self, token, word)
+	}
+
 	pub(crate) fn parse_flexible_ident(&mut self) -> ParseResult<String> {
 		let token = self.next();
 		match token.kind {
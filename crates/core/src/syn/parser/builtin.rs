@@ -99,6 +99,10 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("crypto::pbkdf2::generate") => PathKind::Function,
 		UniCase::ascii("crypto::scrypt::compare") => PathKind::Function,
 		UniCase::ascii("crypto::scrypt::generate") => PathKind::Function,
+		UniCase::ascii("crypto::totp::generate") => PathKind::Function,
+		UniCase::ascii("crypto::totp::secret") => PathKind::Function,
+		UniCase::ascii("crypto::totp::uri") => PathKind::Function,
+		UniCase::ascii("crypto::totp::verify") => PathKind::Function,
 		//
 		UniCase::ascii("duration::days") => PathKind::Function,
 		UniCase::ascii("duration::hours") => PathKind::Function,
@@ -145,6 +149,14 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("geo::hash::encode") => PathKind::Function,
 		UniCase::ascii("geo::is_valid") => PathKind::Function,
 		//
+		UniCase::ascii("graph::components") => PathKind::Function,
+		UniCase::ascii("graph::degree_centrality") => PathKind::Function,
+		UniCase::ascii("graph::k_shortest_paths") => PathKind::Function,
+		UniCase::ascii("graph::label_propagation") => PathKind::Function,
+		UniCase::ascii("graph::pagerank") => PathKind::Function,
+		UniCase::ascii("graph::shortest_path") => PathKind::Function,
+		UniCase::ascii("graph::triangles") => PathKind::Function,
+		//
 		UniCase::ascii("http::head") => PathKind::Function,
 		UniCase::ascii("http::get") => PathKind::Function,
 		UniCase::ascii("http::put") => PathKind::Function,
@@ -58,6 +58,14 @@ impl Parser<'_> {
 			name.push_str("::");
 			name.push_str(&self.parse_ident()?)
 		}
+		// Builtin machine learning functions are called without a version
+		if name == "embed" && self.eat(t!("(")) {
+			let args = self.parse_function_args(stk).await?;
+			return Ok(FunctionCall {
+				receiver: Function::Normal(format!("ml::{name}")),
+				arguments: args,
+			});
+		}
 		let start = expected!(self, t!("<")).span;
 
 		let token = self.next();
@@ -124,6 +132,7 @@ impl Parser<'_> {
 #[cfg(test)]
 mod test {
 	use super::*;
+	use crate::dbs::capabilities::{Capabilities, ExperimentalTarget, Targets};
 	use crate::{sql, syn};
 
 	#[test]
@@ -259,6 +268,25 @@ mod test {
 		assert_eq!("ml::insurance::prediction<1.0.0>(1, 2, 3, 4)", out.to_string());
 	}
 
+	#[test]
+	fn ml_embed() {
+		let capabilities = Capabilities::all()
+			.with_experimental(Targets::Some([ExperimentalTarget::Files].into()));
+		let sql = "ml::embed(f\"models:/minilm\", $text)";
+		let out = syn::expr_with_capabilities(sql, &capabilities).unwrap();
+		assert_eq!("ml::embed(f\"models:/minilm\", $text)", out.to_string());
+		let Expr::FunctionCall(f) = out else {
+			panic!()
+		};
+		assert_eq!(f.receiver, Function::Normal(String::from("ml::embed")));
+		// A model imported into the database is referenced by name
+		let out = syn::expr("ml::embed('minilm<1.0.0>', $text)").unwrap();
+		assert_eq!("ml::embed('minilm<1.0.0>', $text)", out.to_string());
+		// A model with the same name is still called with its version
+		let out = syn::expr("ml::embed<1.0.0>(1)").unwrap();
+		assert_eq!("ml::embed<1.0.0>(1)", out.to_string());
+	}
+
 	#[test]
 	fn script_basic() {
 		let sql = "function(){return true;}";
@@ -279,6 +279,11 @@ impl Parser<'_> {
 				let stmt = self.parse_let_stmt(stk).await?;
 				Expr::Let(Box::new(stmt))
 			}
+			t!("MATCH") if self.peek1().kind == t!("(") => {
+				self.pop_peek();
+				let stmt = self.parse_match_stmt(stk).await?;
+				Expr::Match(Box::new(stmt))
+			}
 			t!("SLEEP") if self.peek1().kind != t!("(") => {
 				self.pop_peek();
 				let stmt = self.parse_sleep_stmt()?;
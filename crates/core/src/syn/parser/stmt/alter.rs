@@ -2,12 +2,15 @@ use reblessive::Stk;
 
 use crate::sql::TableType;
 use crate::sql::statements::alter::field::AlterDefault;
-use crate::sql::statements::alter::{AlterFieldStatement, AlterKind, AlterSequenceStatement};
+use crate::sql::statements::alter::{
+	AlterAccessStatement, AlterFieldStatement, AlterKind, AlterSequenceStatement,
+	AlterUserStatement,
+};
 use crate::sql::statements::{AlterStatement, AlterTableStatement};
 use crate::syn::error::bail;
 use crate::syn::parser::mac::{expected, unexpected};
 use crate::syn::parser::{ParseResult, Parser};
-use crate::syn::token::t;
+use crate::syn::token::{TokenKind, t};
 
 impl Parser<'_> {
 	pub(crate) async fn parse_alter_stmt(&mut self, stk: &mut Stk) -> ParseResult<AlterStatement> {
@@ -16,6 +19,8 @@ impl Parser<'_> {
 			t!("TABLE") => self.parse_alter_table(stk).await.map(AlterStatement::Table),
 			t!("FIELD") => self.parse_alter_field(stk).await.map(AlterStatement::Field),
 			t!("SEQUENCE") => self.parse_alter_sequence(stk).await.map(AlterStatement::Sequence),
+			t!("USER") => self.parse_alter_user().map(AlterStatement::User),
+			t!("ACCESS") => self.parse_alter_access().map(AlterStatement::Access),
 			_ => unexpected!(self, next, "a alter statement keyword"),
 		}
 	}
@@ -51,8 +56,12 @@ impl Parser<'_> {
 							self.pop_peek();
 							res.changefeed = AlterKind::Drop;
 						}
+						t!("CONSTRAINT") => {
+							self.pop_peek();
+							res.drop_constraints.push(self.parse_ident()?);
+						}
 						_ => {
-							unexpected!(self, peek, "`COMMENT` or `CHANGEFEED`")
+							unexpected!(self, peek, "`COMMENT`, `CHANGEFEED`, or `CONSTRAINT`")
 						}
 					}
 				}
@@ -95,6 +104,14 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = AlterKind::Set(self.parse_changefeed()?)
 				}
+				t!("CONSTRAINT") => {
+					self.pop_peek();
+					res.constraints.push(self.parse_table_constraint(stk).await?);
+				}
+				TokenKind::Identifier if self.peek_ident_word("VALIDATE") => {
+					self.pop_peek();
+					res.validate = true;
+				}
 				_ => break,
 			}
 		}
@@ -256,4 +273,42 @@ impl Parser<'_> {
 
 		Ok(res)
 	}
+
+	pub(crate) fn parse_alter_user(&mut self) -> ParseResult<AlterUserStatement> {
+		let if_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.parse_ident()?;
+		expected!(self, t!("ON"));
+		let base = self.parse_base()?;
+		let unlock = self.eat_ident_word("UNLOCK");
+		Ok(AlterUserStatement {
+			name,
+			base,
+			if_exists,
+			unlock,
+		})
+	}
+
+	pub(crate) fn parse_alter_access(&mut self) -> ParseResult<AlterAccessStatement> {
+		let if_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.parse_ident()?;
+		expected!(self, t!("ON"));
+		let base = self.parse_base()?;
+		let unlock = self.eat_ident_word("UNLOCK");
+		Ok(AlterAccessStatement {
+			name,
+			base,
+			if_exists,
+			unlock,
+		})
+	}
 }
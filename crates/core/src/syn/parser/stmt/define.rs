@@ -1,6 +1,7 @@
 use reblessive::Stk;
 
 use crate::catalog::ApiMethod;
+use crate::iam::{Privilege, ResourceKind, RoleGrant};
 use crate::sql::access::AccessDuration;
 use crate::sql::access_type::JwtAccessVerify;
 use crate::sql::base::Base;
@@ -12,18 +13,19 @@ use crate::sql::statements::define::config::{ConfigInner, graphql};
 use crate::sql::statements::define::user::PassType;
 use crate::sql::statements::define::{
 	ApiAction, DefineBucketStatement, DefineConfigStatement, DefineDefault, DefineKind,
-	DefineSequenceStatement,
+	DefineModuleStatement, DefineSequenceStatement, FunctionWasm,
 };
 use crate::sql::statements::{
 	DefineAccessStatement, DefineAnalyzerStatement, DefineApiStatement, DefineDatabaseStatement,
 	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
-	DefineNamespaceStatement, DefineParamStatement, DefineStatement, DefineTableStatement,
-	DefineUserStatement,
+	DefineNamespaceStatement, DefineParamStatement, DefineRoleStatement, DefineStatement,
+	DefineTableStatement, DefineUserStatement,
 };
 use crate::sql::tokenizer::Tokenizer;
+use crate::sql::user::UserTotp;
 use crate::sql::{
-	AccessType, Expr, Index, Kind, Literal, Param, Permission, Permissions, Scoring, TableType,
-	access_type, table_type,
+	AccessType, Block, Expr, Index, Kind, Literal, Param, Permission, Permissions, Scoring,
+	TableConstraint, TableType, access_type, table_type,
 };
 use crate::syn::error::bail;
 use crate::syn::parser::mac::{expected, unexpected};
@@ -44,6 +46,8 @@ impl Parser<'_> {
 			t!("DATABASE") => self.parse_define_database(stk).await.map(DefineStatement::Database),
 			t!("FUNCTION") => self.parse_define_function(stk).await.map(DefineStatement::Function),
 			t!("USER") => self.parse_define_user(stk).await.map(DefineStatement::User),
+			t!("ROLE") => self.parse_define_role(stk).await.map(DefineStatement::Role),
+			t!("MODULE") => self.parse_define_module(stk).await.map(DefineStatement::Module),
 			t!("PARAM") => self.parse_define_param(stk).await.map(DefineStatement::Param),
 			t!("TABLE") => self.parse_define_table(stk).await.map(DefineStatement::Table),
 			t!("API") => self.parse_define_api(stk).await.map(DefineStatement::Api),
@@ -168,8 +172,23 @@ impl Parser<'_> {
 			None
 		};
 
-		let next = expected!(self, t!("{")).span;
-		let block = self.parse_block(stk, next).await?;
+		let (block, wasm) = if self.eat_ident_word("LANGUAGE") {
+			self.expect_ident_word("WASM")?;
+			expected!(self, t!("MODULE"));
+			let module = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+			self.expect_ident_word("EXPORT")?;
+			let export = self.parse_string_lit()?;
+			(
+				Block(Vec::new()),
+				Some(FunctionWasm {
+					module,
+					export,
+				}),
+			)
+		} else {
+			let next = expected!(self, t!("{")).span;
+			(self.parse_block(stk, next).await?, None)
+		};
 
 		let mut res = DefineFunctionStatement {
 			name,
@@ -179,6 +198,7 @@ impl Parser<'_> {
 			returns,
 			comment: None,
 			permissions: Permission::default(),
+			wasm,
 		};
 
 		loop {
@@ -245,20 +265,36 @@ impl Parser<'_> {
 					}
 					res.pass_type = PassType::Hash(self.parse_string_lit()?);
 				}
+				t!("TOTP") => {
+					self.pop_peek();
+					res.totp = Some(if self.eat_ident_word("SECRET") {
+						let secret = self.parse_string_lit()?;
+						let mut recovery = Vec::new();
+						if self.eat_ident_word("RECOVERY") {
+							loop {
+								recovery.push(self.parse_string_lit()?);
+								if !self.eat(t!(",")) {
+									break;
+								}
+							}
+						}
+						UserTotp::Secret {
+							secret,
+							recovery,
+						}
+					} else {
+						UserTotp::Generate
+					});
+				}
+				t!("POLICY") => {
+					self.pop_peek();
+					res.policy = Some(self.parse_password_policy(true)?);
+				}
 				t!("ROLES") => {
 					self.pop_peek();
 					let mut roles = Vec::new();
 					loop {
-						let token = self.peek();
 						let role = self.parse_ident()?;
-						// NOTE(gguillemas): This hardcoded list is a temporary fix in order
-						// to avoid making breaking changes to the DefineUserStatement structure
-						// while still providing parsing feedback to users referencing unexistent
-						// roles. This list should be removed once arbitrary roles can be
-						// defined by users.
-						if !matches!(role.to_lowercase().as_str(), "viewer" | "editor" | "owner") {
-							unexpected!(self, token, "an existent role");
-						}
 						roles.push(role);
 
 						if !self.eat(t!(",")) {
@@ -380,9 +416,37 @@ impl Parser<'_> {
 										ac.signin =
 											Some(stk.run(|stk| self.parse_expr_field(stk)).await?);
 									}
+									t!("POLICY") => {
+										self.pop_peek();
+										ac.policy = Some(self.parse_password_policy(false)?);
+									}
+									TokenKind::Identifier if self.peek_ident_word("IDENTITY") => {
+										self.pop_peek();
+										ac.identity =
+											Some(stk.run(|stk| self.parse_expr_field(stk)).await?);
+									}
+									t!("PASSWORD") => {
+										self.pop_peek();
+										ac.password = Some(self.next_token_value::<Param>()?);
+									}
 									_ => break,
 								}
 							}
+							// Failed signins are tracked against an explicit identity
+							if ac.policy.as_ref().is_some_and(|p| p.lockout.is_some())
+								&& ac.identity.is_none()
+							{
+								bail!("A record access lockout requires an IDENTITY clause", @self.last_span => "Specify the identity against which failed signins are tracked");
+							}
+							// Passwords are checked against an explicit signup variable
+							if ac
+								.policy
+								.as_ref()
+								.is_some_and(|p| p.length.is_some() || !p.require.is_empty())
+								&& ac.password.is_none()
+							{
+								bail!("A record access password policy requires a PASSWORD clause", @self.last_span => "Specify the signup variable which contains the password");
+							}
 
 							while self.eat(t!("WITH")) {
 								let token = self.peek();
@@ -537,6 +601,148 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	pub async fn parse_define_role(&mut self, stk: &mut Stk) -> ParseResult<DefineRoleStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
+		} else {
+			DefineKind::Default
+		};
+		let name = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		expected!(self, t!("ON"));
+		let base = self.parse_base()?;
+
+		let mut res = DefineRoleStatement {
+			kind,
+			name,
+			base,
+			..DefineRoleStatement::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(stk.run(|ctx| self.parse_expr_field(ctx)).await?);
+				}
+				t!("GRANT") => {
+					self.pop_peek();
+					res.grants.push(self.parse_role_grant()?);
+				}
+				t!("REVOKE") => {
+					self.pop_peek();
+					res.revokes.push(self.parse_role_grant()?);
+				}
+				_ => break,
+			}
+		}
+
+		Ok(res)
+	}
+
+	/// Parses the body of a `GRANT` or `REVOKE` clause on a `DEFINE ROLE`
+	/// statement, e.g. `SELECT, CREATE ON TABLE person, post`.
+	fn parse_role_grant(&mut self) -> ParseResult<RoleGrant> {
+		let privileges = if self.eat(t!("ALL")) {
+			Privilege::ALL.to_vec()
+		} else {
+			let mut privileges = Vec::new();
+			loop {
+				let next = self.next();
+				let privilege = match next.kind {
+					t!("SELECT") => Privilege::Select,
+					t!("CREATE") => Privilege::Create,
+					t!("UPDATE") => Privilege::Update,
+					t!("DELETE") => Privilege::Delete,
+					t!("DEFINE") => Privilege::Define,
+					t!("REMOVE") => Privilege::Remove,
+					_ => unexpected!(self, next, "a privilege"),
+				};
+				if !privileges.contains(&privilege) {
+					privileges.push(privilege);
+				}
+				if !self.eat(t!(",")) {
+					break;
+				}
+			}
+			privileges
+		};
+		expected!(self, t!("ON"));
+		let next = self.next();
+		let kind = match next.kind {
+			t!("TABLE") => ResourceKind::Table,
+			t!("FUNCTION") => ResourceKind::Function,
+			t!("PARAM") => ResourceKind::Parameter,
+			t!("INDEX") => ResourceKind::Index,
+			t!("ACCESS") => ResourceKind::Access,
+			_ => unexpected!(self, next, "`TABLE`, `FUNCTION`, `PARAM`, `INDEX`, or `ACCESS`"),
+		};
+		// Without any names the grant covers every resource of this kind
+		let mut names = Vec::new();
+		let has_names = match kind {
+			ResourceKind::Function => self.peek_kind() == t!("fn"),
+			ResourceKind::Parameter => self.peek_kind() == TokenKind::Parameter,
+			_ => !matches!(
+				self.peek_kind(),
+				t!("GRANT")
+					| t!("REVOKE") | t!("COMMENT")
+					| t!(";") | t!("eof")
+					| t!(")") | t!("}")
+			),
+		};
+		if has_names {
+			loop {
+				let name = match kind {
+					ResourceKind::Function => self.parse_custom_function_name()?,
+					ResourceKind::Parameter => self.next_token_value::<Param>()?.into_string(),
+					_ => self.parse_ident()?,
+				};
+				names.push(name);
+				if !self.eat(t!(",")) {
+					break;
+				}
+			}
+		}
+		Ok(RoleGrant {
+			privileges,
+			kind,
+			names,
+		})
+	}
+
+	pub async fn parse_define_module(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<DefineModuleStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
+		} else {
+			DefineKind::Default
+		};
+		let name = self.parse_module_name()?;
+		expected!(self, t!("AS"));
+		let source = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+
+		let mut res = DefineModuleStatement {
+			kind,
+			name,
+			source,
+			comment: None,
+		};
+
+		while self.eat(t!("COMMENT")) {
+			res.comment = Some(stk.run(|ctx| self.parse_expr_field(ctx)).await?);
+		}
+		Ok(res)
+	}
+
 	pub async fn parse_define_param(&mut self, stk: &mut Stk) -> ParseResult<DefineParamStatement> {
 		let kind = if self.eat(t!("IF")) {
 			expected!(self, t!("NOT"));
@@ -646,6 +852,10 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = Some(self.parse_changefeed()?);
 				}
+				t!("CONSTRAINT") => {
+					self.pop_peek();
+					res.constraints.push(self.parse_table_constraint(stk).await?);
+				}
 				t!("AS") => {
 					self.pop_peek();
 					let peek = self.peek();
@@ -738,12 +948,26 @@ impl Parser<'_> {
 
 					let config = self.parse_api_config(stk).await?;
 
+					let request = if self.eat_ident_word("REQUEST") {
+						Some(stk.run(|ctx| self.parse_inner_kind(ctx)).await?)
+					} else {
+						None
+					};
+
+					let response = if self.eat_ident_word("RESPONSE") {
+						Some(stk.run(|ctx| self.parse_inner_kind(ctx)).await?)
+					} else {
+						None
+					};
+
 					expected!(self, t!("THEN"));
 					let action = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
 					res.actions.push(ApiAction {
 						methods,
 						action,
 						config,
+						request,
+						response,
 					});
 				}
 				found => {
@@ -887,6 +1111,10 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.computed = Some(stk.run(|stk| self.parse_expr_field(stk)).await?);
 				}
+				t!("MASK") => {
+					self.pop_peek();
+					res.mask = Some(stk.run(|stk| self.parse_expr_field(stk)).await?);
+				}
 				_ => break,
 			}
 		}
@@ -1505,6 +1733,18 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses the name and condition of a `CONSTRAINT name ASSERT condition`
+	/// clause, after the `CONSTRAINT` keyword.
+	pub async fn parse_table_constraint(&mut self, stk: &mut Stk) -> ParseResult<TableConstraint> {
+		let name = self.parse_ident()?;
+		expected!(self, t!("ASSERT"));
+		let check = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		Ok(TableConstraint {
+			name,
+			check,
+		})
+	}
+
 	pub fn parse_tables(&mut self) -> ParseResult<Kind> {
 		let mut names = vec![self.parse_ident()?];
 		while self.eat(t!("|")) {
@@ -0,0 +1,135 @@
+use reblessive::Stk;
+
+use crate::sql::Dir;
+use crate::sql::statements::MatchStatement;
+use crate::sql::statements::r#match::{MatchEdge, MatchNode, MatchPattern};
+use crate::syn::parser::mac::{expected, expected_whitespace, unexpected};
+use crate::syn::parser::{ParseResult, Parser};
+use crate::syn::token::t;
+
+impl Parser<'_> {
+	/// expects `match` to be eaten.
+	pub(crate) async fn parse_match_stmt(&mut self, stk: &mut Stk) -> ParseResult<MatchStatement> {
+		let mut patterns = vec![self.parse_match_pattern()?];
+		while self.eat(t!(",")) {
+			patterns.push(self.parse_match_pattern()?);
+		}
+		let cond = self.try_parse_condition(stk).await?;
+		expected!(self, t!("RETURN"));
+		let before = self.peek().span;
+		let expr = self.parse_fields(stk).await?;
+		let fields_span = before.covers(self.last_span());
+		let order = self.try_parse_orders(stk, &expr, fields_span).await?;
+		let (limit, start) = if let t!("START") = self.peek_kind() {
+			let start = self.try_parse_start(stk).await?;
+			let limit = self.try_parse_limit(stk).await?;
+			(limit, start)
+		} else {
+			let limit = self.try_parse_limit(stk).await?;
+			let start = self.try_parse_start(stk).await?;
+			(limit, start)
+		};
+		Ok(MatchStatement {
+			patterns,
+			cond,
+			expr,
+			order,
+			limit,
+			start,
+		})
+	}
+
+	/// Parses a pattern like `(a:person)-[:knows]->(b)<-(c)`.
+	fn parse_match_pattern(&mut self) -> ParseResult<MatchPattern> {
+		let start = self.parse_match_node()?;
+		let mut steps = Vec::new();
+		loop {
+			let edge = match self.peek_kind() {
+				t!("->") => {
+					self.pop_peek();
+					MatchEdge {
+						dir: Dir::Out,
+						..Default::default()
+					}
+				}
+				t!("<") => {
+					let token = self.pop_peek();
+					if self.eat_whitespace(t!("->")) {
+						MatchEdge {
+							dir: Dir::Both,
+							..Default::default()
+						}
+					} else if self.eat_whitespace(t!("-")) {
+						let (alias, what) = if self.eat_whitespace(t!("[")) {
+							let open = self.last_span();
+							let binding = self.parse_match_binding()?;
+							self.expect_closing_delimiter(t!("]"), open)?;
+							expected_whitespace!(self, t!("-"));
+							binding
+						} else {
+							(None, Vec::new())
+						};
+						MatchEdge {
+							alias,
+							what,
+							dir: Dir::In,
+						}
+					} else {
+						unexpected!(self, token, "either `<-` or `<->`")
+					}
+				}
+				t!("-") => {
+					self.pop_peek();
+					let open = expected_whitespace!(self, t!("[")).span;
+					let (alias, what) = self.parse_match_binding()?;
+					self.expect_closing_delimiter(t!("]"), open)?;
+					let dir = if self.eat_whitespace(t!("->")) {
+						Dir::Out
+					} else {
+						expected_whitespace!(self, t!("-"));
+						Dir::Both
+					};
+					MatchEdge {
+						alias,
+						what,
+						dir,
+					}
+				}
+				_ => break,
+			};
+			let node = self.parse_match_node()?;
+			steps.push((edge, node));
+		}
+		Ok(MatchPattern {
+			start,
+			steps,
+		})
+	}
+
+	fn parse_match_node(&mut self) -> ParseResult<MatchNode> {
+		let open = expected!(self, t!("(")).span;
+		let (alias, what) = self.parse_match_binding()?;
+		self.expect_closing_delimiter(t!(")"), open)?;
+		Ok(MatchNode {
+			alias,
+			what,
+		})
+	}
+
+	/// Parses the optional `alias:table|table` part of a node or an edge.
+	fn parse_match_binding(&mut self) -> ParseResult<(Option<String>, Vec<String>)> {
+		let alias = if Self::kind_is_identifier(self.peek_kind()) {
+			Some(self.parse_ident()?)
+		} else {
+			None
+		};
+		let mut what = Vec::new();
+		if self.eat(t!(":")) {
+			what.push(self.parse_ident()?);
+			while self.eat(t!("|")) {
+				what.push(self.parse_ident()?);
+			}
+		}
+		Ok((alias, what))
+	}
+}
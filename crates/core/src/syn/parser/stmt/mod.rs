@@ -25,6 +25,7 @@ mod define;
 mod delete;
 mod r#if;
 mod insert;
+mod r#match;
 mod parts;
 mod relate;
 mod remove;
@@ -424,12 +425,25 @@ impl Parser<'_> {
 		};
 		let cond = self.try_parse_condition(stk).await?;
 		let fetch = self.try_parse_fetch(stk).await?;
+		let snapshot = if self.eat(t!("WITH")) {
+			expected!(self, t!("SNAPSHOT"));
+			true
+		} else {
+			false
+		};
+		let since = if self.eat(t!("SINCE")) {
+			Some(self.parse_since()?)
+		} else {
+			None
+		};
 
 		Ok(LiveStatement {
 			fields: expr,
 			what,
 			cond,
 			fetch,
+			snapshot,
+			since,
 		})
 	}
 
@@ -543,6 +557,22 @@ impl Parser<'_> {
 
 		expected!(self, t!("SINCE"));
 
+		let since = self.parse_since()?;
+
+		let limit = self.eat(t!("LIMIT")).then(|| self.next_token_value()).transpose()?;
+
+		Ok(ShowStatement {
+			table,
+			since,
+			limit,
+		})
+	}
+
+	/// Parses the versionstamp or datetime after a `SINCE` clause.
+	///
+	/// # Parser State
+	/// Expects `SINCE` to already be consumed.
+	fn parse_since(&mut self) -> ParseResult<ShowSince> {
 		let next = self.peek();
 		let since = match next.kind {
 			TokenKind::Digits => {
@@ -560,14 +590,7 @@ impl Parser<'_> {
 			}
 			_ => unexpected!(self, next, "a version stamp or a date-time"),
 		};
-
-		let limit = self.eat(t!("LIMIT")).then(|| self.next_token_value()).transpose()?;
-
-		Ok(ShowStatement {
-			table,
-			since,
-			limit,
-		})
+		Ok(since)
 	}
 
 	/// Parsers a SLEEP statement
@@ -5,6 +5,7 @@ use reblessive::Stk;
 use crate::sql::changefeed::ChangeFeed;
 use crate::sql::index::{Distance, VectorType};
 use crate::sql::reference::{Reference, ReferenceDeleteStrategy};
+use crate::sql::user::{PasswordClass, PasswordLockout, PasswordPolicy};
 use crate::sql::{
 	Base, Cond, Data, Explain, Expr, Fetch, Fetchs, Field, Fields, Group, Groups, Idiom, Output,
 	Permission, Permissions, Timeout, View, With,
@@ -464,6 +465,57 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parses a password policy production
+	///
+	/// Password expiry and history can only be enforced for system users, as
+	/// the passwords of record users are not stored by the database itself.
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `POLICY` keyword
+	pub fn parse_password_policy(&mut self, system_user: bool) -> ParseResult<PasswordPolicy> {
+		let mut res = PasswordPolicy::default();
+		loop {
+			if self.eat_ident_word("LENGTH") {
+				res.length = Some(self.next_token_value::<u64>()?);
+			} else if self.eat_ident_word("REQUIRE") {
+				loop {
+					let next = self.next();
+					res.require.push(match next.kind {
+						t!("UPPERCASE") => PasswordClass::Uppercase,
+						t!("LOWERCASE") => PasswordClass::Lowercase,
+						t!("NUMERIC") => PasswordClass::Numeric,
+						t!("PUNCT") => PasswordClass::Punct,
+						_ => unexpected!(self, next, "UPPERCASE, LOWERCASE, NUMERIC, or PUNCT"),
+					});
+					if !self.eat(t!(",")) {
+						break;
+					}
+				}
+			} else if self.peek_ident_word("EXPIRY") || self.peek_ident_word("HISTORY") {
+				let token = self.next();
+				if !system_user {
+					unexpected!(self, token, "a password policy clause for record users");
+				}
+				if self.lexer.span_str(token.span).eq_ignore_ascii_case("EXPIRY") {
+					res.expiry = Some(self.next_token_value::<Duration>()?.0);
+				} else {
+					res.history = Some(self.next_token_value::<u64>()?);
+				}
+			} else if self.eat_ident_word("LOCKOUT") {
+				let attempts = self.next_token_value::<u64>()?;
+				expected!(self, t!("FOR"));
+				let duration = self.next_token_value::<Duration>()?.0;
+				res.lockout = Some(PasswordLockout {
+					attempts,
+					duration,
+				});
+			} else {
+				break;
+			}
+		}
+		Ok(res)
+	}
+
 	/// Parses a reference
 	///
 	/// # Parser State
@@ -570,6 +622,20 @@ impl Parser<'_> {
 		// Safety: Parser guarentees no null bytes.
 		Ok(name)
 	}
+	pub fn parse_module_name(&mut self) -> ParseResult<String> {
+		let token = self.peek();
+		if !self.parse_ident()?.eq_ignore_ascii_case("mod") {
+			unexpected!(self, token, "`mod`");
+		}
+		expected!(self, t!("::"));
+		let mut name = self.parse_ident()?;
+		while self.eat(t!("::")) {
+			let part = self.parse_ident()?;
+			name.push_str("::");
+			name.push_str(part.as_str());
+		}
+		Ok(name)
+	}
 	pub(super) fn try_parse_explain(&mut self) -> ParseResult<Option<Explain>> {
 		Ok(self.eat(t!("EXPLAIN")).then(|| Explain(self.eat(t!("FULL")))))
 	}
@@ -2,12 +2,13 @@ use reblessive::Stk;
 
 use crate::sql::Param;
 use crate::sql::statements::remove::{
-	RemoveAnalyzerStatement, RemoveApiStatement, RemoveBucketStatement, RemoveSequenceStatement,
+	RemoveAnalyzerStatement, RemoveApiStatement, RemoveBucketStatement, RemoveModuleStatement,
+	RemoveSequenceStatement,
 };
 use crate::sql::statements::{
 	RemoveAccessStatement, RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement,
 	RemoveFunctionStatement, RemoveIndexStatement, RemoveNamespaceStatement, RemoveParamStatement,
-	RemoveStatement, RemoveUserStatement,
+	RemoveRoleStatement, RemoveStatement, RemoveUserStatement,
 };
 use crate::syn::parser::mac::{expected, unexpected};
 use crate::syn::parser::{ParseResult, Parser};
@@ -98,6 +99,20 @@ impl Parser<'_> {
 					if_exists,
 				})
 			}
+			t!("MODULE") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.parse_module_name()?;
+
+				RemoveStatement::Module(RemoveModuleStatement {
+					name,
+					if_exists,
+				})
+			}
 			t!("PARAM") => {
 				let if_exists = if self.eat(t!("IF")) {
 					expected!(self, t!("EXISTS"));
@@ -233,6 +248,23 @@ impl Parser<'_> {
 					if_exists,
 				})
 			}
+			t!("ROLE") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = stk.run(|stk| self.parse_expr_field(stk)).await?;
+				expected!(self, t!("ON"));
+				let base = self.parse_base()?;
+
+				RemoveStatement::Role(RemoveRoleStatement {
+					name,
+					base,
+					if_exists,
+				})
+			}
 			t!("API") => {
 				let if_exists = if self.eat(t!("IF")) {
 					expected!(self, t!("EXISTS"));
@@ -1,6 +1,7 @@
 use chrono::offset::TimeZone;
 use chrono::{NaiveDate, Offset, Utc};
 
+use crate::iam::{Privilege, ResourceKind, RoleGrant};
 use crate::sql::access::AccessDuration;
 use crate::sql::access_type::{
 	AccessType, BearerAccess, BearerAccessSubject, BearerAccessType, JwtAccess, JwtAccessIssue,
@@ -17,27 +18,31 @@ use crate::sql::order::{OrderList, Ordering};
 use crate::sql::statements::access::{
 	self, AccessStatementGrant, AccessStatementPurge, AccessStatementRevoke, AccessStatementShow,
 };
+use crate::sql::statements::alter::{AlterStatement, AlterUserStatement};
 use crate::sql::statements::define::user::PassType;
-use crate::sql::statements::define::{DefineDefault, DefineKind};
+use crate::sql::statements::define::{DefineDefault, DefineKind, FunctionWasm};
+use crate::sql::statements::r#match::{MatchEdge, MatchNode, MatchPattern};
 use crate::sql::statements::show::{ShowSince, ShowStatement};
 use crate::sql::statements::sleep::SleepStatement;
 use crate::sql::statements::{
 	AccessStatement, CreateStatement, DefineAccessStatement, DefineAnalyzerStatement,
 	DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement, DefineFunctionStatement,
-	DefineIndexStatement, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
-	DefineTableStatement, DeleteStatement, ForeachStatement, IfelseStatement, InfoStatement,
-	InsertStatement, KillStatement, OptionStatement, OutputStatement, RelateStatement,
-	RemoveAccessStatement, RemoveAnalyzerStatement, RemoveDatabaseStatement, RemoveEventStatement,
-	RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement, RemoveNamespaceStatement,
-	RemoveParamStatement, RemoveStatement, RemoveTableStatement, RemoveUserStatement,
-	SelectStatement, UpdateStatement, UpsertStatement, UseStatement,
+	DefineIndexStatement, DefineModuleStatement, DefineNamespaceStatement, DefineParamStatement,
+	DefineStatement, DefineTableStatement, DeleteStatement, ForeachStatement, IfelseStatement,
+	InfoStatement, InsertStatement, KillStatement, MatchStatement, OptionStatement,
+	OutputStatement, RelateStatement, RemoveAccessStatement, RemoveAnalyzerStatement,
+	RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement,
+	RemoveIndexStatement, RemoveModuleStatement, RemoveNamespaceStatement, RemoveParamStatement,
+	RemoveStatement, RemoveTableStatement, RemoveUserStatement, SelectStatement, UpdateStatement,
+	UpsertStatement, UseStatement,
 };
 use crate::sql::tokenizer::Tokenizer;
+use crate::sql::user::{PasswordClass, PasswordLockout, PasswordPolicy, UserTotp};
 use crate::sql::{
 	Algorithm, AssignOperator, Base, BinaryOperator, Block, Cond, Data, Dir, Explain, Expr, Fetch,
 	Fetchs, Field, Fields, Group, Groups, Idiom, Index, Kind, Limit, Literal, Lookup, Mock, Order,
 	Output, Param, Part, Permission, Permissions, RecordIdKeyLit, RecordIdLit, Scoring, Split,
-	Splits, Start, TableType, Timeout, TopLevelExpr, With,
+	Splits, Start, TableConstraint, TableType, Timeout, TopLevelExpr, With,
 };
 use crate::syn;
 use crate::syn::parser::ParserSettings;
@@ -248,6 +253,34 @@ fn parse_define_function() {
 			comment: Some(Expr::Literal(Literal::String("test".to_string()))),
 			permissions: Permission::Full,
 			returns: None,
+			wasm: None,
+		})))
+	)
+}
+
+#[test]
+fn parse_define_function_wasm() {
+	let res = syn::parse_with(
+		r#"DEFINE FUNCTION fn::add($a: int, $b: int) -> int LANGUAGE WASM MODULE $module EXPORT 'add' PERMISSIONS FULL"#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Function(DefineFunctionStatement {
+			kind: DefineKind::Default,
+			name: "add".to_owned(),
+			args: vec![("a".to_owned(), Kind::Int), ("b".to_owned(), Kind::Int)],
+			block: Block(Vec::new()),
+			comment: None,
+			permissions: Permission::Full,
+			returns: Some(Kind::Int),
+			wasm: Some(FunctionWasm {
+				module: Expr::Param(Param::new("module".to_owned())),
+				export: "add".to_owned(),
+			}),
 		})))
 	)
 }
@@ -400,25 +433,179 @@ fn parse_define_user() {
 		)
 		.unwrap_err();
 	}
-	// With nonexistent role.
+	// With custom role.
 	{
-		syn::parse_with(
+		let res = syn::parse_with(
 			r#"DEFINE USER user ON ROOT COMMENT 'test' PASSHASH 'hunter2' ROLES foo"#.as_bytes(),
 			async |parser, stk| parser.parse_expr_inherit(stk).await,
 		)
-		.unwrap_err();
+		.unwrap();
+
+		let Expr::Define(res) = res else {
+			panic!()
+		};
+		let DefineStatement::User(stmt) = *res else {
+			panic!()
+		};
+
+		assert_eq!(stmt.roles, vec!["foo".to_string()]);
 	}
-	// With existent and nonexistent roles.
+	// With built-in and custom roles.
 	{
-		syn::parse_with(
+		let res = syn::parse_with(
 			r#"DEFINE USER user ON ROOT COMMENT 'test' PASSHASH 'hunter2' ROLES Viewer, foo"#
 				.as_bytes(),
 			async |parser, stk| parser.parse_expr_inherit(stk).await,
 		)
-		.unwrap_err();
+		.unwrap();
+
+		let Expr::Define(res) = res else {
+			panic!()
+		};
+		let DefineStatement::User(stmt) = *res else {
+			panic!()
+		};
+
+		assert_eq!(stmt.roles, vec!["Viewer".to_string(), "foo".to_string()]);
+	}
+	// With generated TOTP secret.
+	{
+		let res = syn::parse_with(
+			r#"DEFINE USER user ON ROOT PASSHASH 'hunter2' TOTP"#.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.unwrap();
+
+		let Expr::Define(res) = res else {
+			panic!()
+		};
+		let DefineStatement::User(stmt) = *res else {
+			panic!()
+		};
+
+		assert_eq!(stmt.totp, Some(UserTotp::Generate));
+	}
+	// With existing TOTP secret and recovery codes.
+	{
+		let res = syn::parse_with(
+			r#"DEFINE USER user ON ROOT PASSHASH 'hunter2' TOTP SECRET 'GEZDGNBVGY3TQOJQ' RECOVERY 'a', 'b' COMMENT 'test'"#
+				.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.unwrap();
+
+		let Expr::Define(res) = res else {
+			panic!()
+		};
+		let DefineStatement::User(stmt) = *res else {
+			panic!()
+		};
+
+		assert_eq!(
+			stmt.totp,
+			Some(UserTotp::Secret {
+				secret: "GEZDGNBVGY3TQOJQ".to_string(),
+				recovery: vec!["a".to_string(), "b".to_string()],
+			})
+		);
+		assert_eq!(stmt.comment, Some(Expr::Literal(Literal::String("test".to_string()))));
+	}
+	// With password policy.
+	{
+		let res = syn::parse_with(
+			r#"DEFINE USER user ON ROOT PASSWORD 'hunter2' POLICY LENGTH 12 REQUIRE UPPERCASE, NUMERIC EXPIRY 90d HISTORY 5 LOCKOUT 5 FOR 15m"#
+				.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.unwrap();
+
+		let Expr::Define(res) = res else {
+			panic!()
+		};
+		let DefineStatement::User(stmt) = *res else {
+			panic!()
+		};
+
+		assert_eq!(
+			stmt.policy,
+			Some(PasswordPolicy {
+				length: Some(12),
+				require: vec![PasswordClass::Uppercase, PasswordClass::Numeric],
+				expiry: Some(std::time::Duration::from_secs(90 * 86400)),
+				history: Some(5),
+				lockout: Some(PasswordLockout {
+					attempts: 5,
+					duration: std::time::Duration::from_secs(15 * 60),
+				}),
+			})
+		);
 	}
 }
 
+#[test]
+fn parse_alter_user() {
+	let res = syn::parse_with(
+		r#"ALTER USER IF EXISTS user ON DATABASE UNLOCK"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Alter(Box::new(AlterStatement::User(AlterUserStatement {
+			name: "user".to_string(),
+			base: Base::Db,
+			if_exists: true,
+			unlock: true,
+		})))
+	);
+	assert_eq!(res.to_string(), "ALTER USER IF EXISTS user ON DATABASE UNLOCK");
+}
+
+#[test]
+fn parse_define_role() {
+	let res = syn::parse_with(
+		r#"DEFINE ROLE auditor ON DATABASE GRANT SELECT ON TABLE person, post GRANT ALL ON FUNCTION fn::report REVOKE DELETE, UPDATE ON TABLE COMMENT "read only""#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	let Expr::Define(res) = res else {
+		panic!()
+	};
+	let DefineStatement::Role(stmt) = *res else {
+		panic!()
+	};
+
+	assert_eq!(stmt.name, Expr::Idiom(Idiom::field("auditor".to_string())));
+	assert_eq!(stmt.base, Base::Db);
+	assert_eq!(
+		stmt.grants,
+		vec![
+			RoleGrant {
+				privileges: vec![Privilege::Select],
+				kind: ResourceKind::Table,
+				names: vec!["person".to_string(), "post".to_string()],
+			},
+			RoleGrant {
+				privileges: Privilege::ALL.to_vec(),
+				kind: ResourceKind::Function,
+				names: vec!["report".to_string()],
+			},
+		]
+	);
+	assert_eq!(
+		stmt.revokes,
+		vec![RoleGrant {
+			privileges: vec![Privilege::Delete, Privilege::Update],
+			kind: ResourceKind::Table,
+			names: vec![],
+		}]
+	);
+	assert_eq!(stmt.comment, Some(Expr::Literal(Literal::String("read only".to_string()))));
+}
+
 #[test]
 fn parse_define_access_jwt_key() {
 	// With comment. Asymmetric verify only.
@@ -1050,6 +1237,9 @@ fn parse_define_access_record() {
 						}),
 					},
 					bearer: None,
+					policy: None,
+					identity: None,
+					password: None,
 				}),
 				authenticate: None,
 				duration: AccessDuration {
@@ -1087,6 +1277,9 @@ fn parse_define_access_record() {
 						}),
 					},
 					bearer: None,
+					policy: None,
+					identity: None,
+					password: None,
 				}),
 				authenticate: None,
 				duration: AccessDuration {
@@ -1145,6 +1338,9 @@ fn parse_define_access_record() {
 							}),
 						},
 					}),
+					policy: None,
+					identity: None,
+					password: None,
 				}),
 				authenticate: None,
 				duration: AccessDuration {
@@ -1202,6 +1398,9 @@ fn parse_define_access_record() {
 							}),
 						},
 					}),
+					policy: None,
+					identity: None,
+					password: None,
 				}),
 				authenticate: None,
 				duration: AccessDuration {
@@ -1239,6 +1438,9 @@ fn parse_define_access_record() {
 						}),
 					},
 					bearer: None,
+					policy: None,
+					identity: None,
+					password: None,
 				}),
 				authenticate: None,
 				duration: AccessDuration {
@@ -1558,6 +1760,26 @@ fn parse_define_access_bearer() {
 	}
 }
 
+#[test]
+fn parse_define_module() {
+	let res = syn::parse_with(
+		r#"DEFINE MODULE IF NOT EXISTS mod::utils::strings AS 'export const a = 1;' COMMENT 'test'"#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Module(DefineModuleStatement {
+			kind: DefineKind::IfNotExists,
+			name: "utils::strings".to_owned(),
+			source: Expr::Literal(Literal::String("export const a = 1;".to_owned())),
+			comment: Some(Expr::Literal(Literal::String("test".to_owned()))),
+		})))
+	);
+}
+
 #[test]
 fn parse_define_param() {
 	let res = syn::parse_with(
@@ -1626,6 +1848,40 @@ fn parse_define_table() {
 			comment: None,
 
 			table_type: TableType::Normal,
+			constraints: Vec::new(),
+		})))
+	);
+}
+
+#[test]
+fn parse_define_table_constraint() {
+	let res = syn::parse_with(
+		r#"DEFINE TABLE booking SCHEMAFULL CONSTRAINT ordered ASSERT finish > start"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Table(DefineTableStatement {
+			kind: DefineKind::Default,
+			id: None,
+			name: Expr::Idiom(Idiom::field("booking".to_string())),
+			drop: false,
+			full: true,
+			view: None,
+			permissions: Permissions::none(),
+			changefeed: None,
+			comment: None,
+			table_type: TableType::Normal,
+			constraints: vec![TableConstraint {
+				name: "ordered".to_string(),
+				check: Expr::Binary {
+					left: Box::new(ident_field("finish")),
+					op: BinaryOperator::MoreThan,
+					right: Box::new(ident_field("start")),
+				},
+			}],
 		})))
 	);
 }
@@ -1687,6 +1943,41 @@ fn parse_define_field() {
 				comment: None,
 				reference: None,
 				computed: None,
+				mask: None,
+			})))
+		)
+	}
+
+	// Mask
+	{
+		let res = syn::parse_with(
+			r#"DEFINE FIELD card ON TABLE bar TYPE string MASK "****" PERMISSIONS FOR SELECT NONE"#
+				.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.unwrap();
+		assert_eq!(
+			res,
+			Expr::Define(Box::new(DefineStatement::Field(DefineFieldStatement {
+				kind: DefineKind::Default,
+				name: Expr::Idiom(Idiom(vec![Part::Field("card".to_string())])),
+				what: Expr::Idiom(Idiom::field("bar".to_string())),
+				flex: false,
+				field_kind: Some(Kind::String),
+				readonly: false,
+				value: None,
+				assert: None,
+				default: DefineDefault::None,
+				permissions: Permissions {
+					delete: Permission::Full,
+					update: Permission::Full,
+					create: Permission::Full,
+					select: Permission::None,
+				},
+				comment: None,
+				reference: None,
+				computed: None,
+				mask: Some(Expr::Literal(Literal::String("****".to_string()))),
 			})))
 		)
 	}
@@ -1722,6 +2013,7 @@ fn parse_define_field() {
 				comment: None,
 				reference: None,
 				computed: None,
+				mask: None,
 			})))
 		)
 	}
@@ -2141,6 +2433,93 @@ fn parse_select() {
 	);
 }
 
+#[test]
+fn parse_match() {
+	let res = syn::parse_with(
+		r#"MATCH (a:person)-[:knows]->(b)<-[e]-(c:person|robot), (b) <-> () WHERE a.age > 18 RETURN a, c LIMIT 5"#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Match(Box::new(MatchStatement {
+			patterns: vec![
+				MatchPattern {
+					start: MatchNode {
+						alias: Some("a".to_owned()),
+						what: vec!["person".to_owned()],
+					},
+					steps: vec![
+						(
+							MatchEdge {
+								alias: None,
+								what: vec!["knows".to_owned()],
+								dir: Dir::Out,
+							},
+							MatchNode {
+								alias: Some("b".to_owned()),
+								what: vec![],
+							},
+						),
+						(
+							MatchEdge {
+								alias: Some("e".to_owned()),
+								what: vec![],
+								dir: Dir::In,
+							},
+							MatchNode {
+								alias: Some("c".to_owned()),
+								what: vec!["person".to_owned(), "robot".to_owned()],
+							},
+						),
+					],
+				},
+				MatchPattern {
+					start: MatchNode {
+						alias: Some("b".to_owned()),
+						what: vec![],
+					},
+					steps: vec![(
+						MatchEdge {
+							alias: None,
+							what: vec![],
+							dir: Dir::Both,
+						},
+						MatchNode::default(),
+					)],
+				},
+			],
+			cond: Some(Cond(Expr::Binary {
+				left: Box::new(Expr::Idiom(Idiom(vec![
+					Part::Field("a".to_owned()),
+					Part::Field("age".to_owned()),
+				]))),
+				op: BinaryOperator::MoreThan,
+				right: Box::new(Expr::Literal(Literal::Integer(18))),
+			})),
+			expr: Fields::Select(vec![
+				Field::Single {
+					expr: ident_field("a"),
+					alias: None,
+				},
+				Field::Single {
+					expr: ident_field("c"),
+					alias: None,
+				},
+			]),
+			order: None,
+			limit: Some(Limit(Expr::Literal(Literal::Integer(5)))),
+			start: None,
+		}))
+	);
+	assert_eq!(
+		res.to_string(),
+		"MATCH (a:person)-[:knows]->(b)<-[e]-(c:person|robot), (b)<->() WHERE a.age > 18 RETURN a, c LIMIT 5"
+	);
+}
+
 #[test]
 fn parse_show() {
 	let res = syn::parse_with(
@@ -2470,7 +2849,20 @@ fn parse_live() {
 			]))),
 			Fetch(Expr::Idiom(Idiom(vec![Part::Field("b".to_owned())]))),
 		])),
+	);
+	assert!(!stmt.snapshot);
+	assert_eq!(stmt.since, None);
+
+	let res = syn::parse_with(
+		r#"LIVE SELECT * FROM table WITH SNAPSHOT SINCE 10"#.as_bytes(),
+		async |parser, stk| parser.parse_top_level_expr(stk).await,
 	)
+	.unwrap();
+	let TopLevelExpr::Live(stmt) = res else {
+		panic!()
+	};
+	assert!(stmt.snapshot);
+	assert_eq!(stmt.since, Some(ShowSince::Versionstamp(10)));
 }
 
 #[test]
@@ -2596,6 +2988,19 @@ fn parse_remove() {
 		})))
 	);
 
+	let res =
+		syn::parse_with(r#"REMOVE MODULE IF EXISTS mod::utils"#.as_bytes(), async |parser, stk| {
+			parser.parse_expr_inherit(stk).await
+		})
+		.unwrap();
+	assert_eq!(
+		res,
+		Expr::Remove(Box::new(RemoveStatement::Module(RemoveModuleStatement {
+			name: "utils".to_owned(),
+			if_exists: true,
+		})))
+	);
+
 	let res =
 		syn::parse_with(r#"REMOVE ACCESS foo ON DATABASE"#.as_bytes(), async |parser, stk| {
 			parser.parse_expr_inherit(stk).await
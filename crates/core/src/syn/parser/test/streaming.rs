@@ -205,6 +205,7 @@ fn statements() -> Vec<TopLevelExpr> {
 				comment: Some(Expr::Literal(Literal::String("test".to_string()))),
 				permissions: Permission::Full,
 				returns: None,
+				wasm: None,
 			},
 		)))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Access(
@@ -223,6 +224,9 @@ fn statements() -> Vec<TopLevelExpr> {
 						issue: None,
 					},
 					bearer: None,
+					policy: None,
+					identity: None,
+					password: None,
 				}),
 				authenticate: None,
 				// Default durations.
@@ -282,6 +286,7 @@ fn statements() -> Vec<TopLevelExpr> {
 			comment: None,
 
 			table_type: TableType::Normal,
+			constraints: Vec::new(),
 		})))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Event(DefineEventStatement {
 			kind: DefineKind::Default,
@@ -319,6 +324,7 @@ fn statements() -> Vec<TopLevelExpr> {
 			comment: None,
 			reference: None,
 			computed: None,
+			mask: None,
 		})))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
 			kind: DefineKind::Default,
@@ -8,6 +8,9 @@ macro_rules! keyword {
 		}
 
 		impl Keyword{
+			/// All of the keywords.
+			pub const ALL: &[Keyword] = &[$(Keyword::$name,)*];
+
 			pub fn as_str(&self) -> &'static str{
 				match self{
 					$(Keyword::$name => $value,)*
@@ -61,6 +64,7 @@ keyword! {
 	Commit => "COMMIT",
 	Concurrently => "CONCURRENTLY",
 	Config => "CONFIG",
+	Constraint => "CONSTRAINT",
 	Content => "CONTENT",
 	Continue => "CONTINUE",
 	Computed => "COMPUTED",
@@ -128,8 +132,11 @@ keyword! {
 	M => "M",
 	M0 => "M0",
 	Mapper => "MAPPER",
+	Mask => "MASK",
+	Match => "MATCH",
 	Middleware => "MIDDLEWARE",
 	Merge => "MERGE",
+	Module => "MODULE",
 	Model => "MODEL",
 	MTree => "MTREE",
 	MTreeCache => "MTREE_CACHE",
@@ -152,6 +159,7 @@ keyword! {
 	Passhash => "PASSHASH",
 	Password => "PASSWORD",
 	Patch => "PATCH",
+	Policy => "POLICY",
 	Permissions => "PERMISSIONS",
 	PostingsCache => "POSTINGS_CACHE",
 	PostingsOrder => "POSTINGS_ORDER",
@@ -170,6 +178,7 @@ keyword! {
 	Return => "RETURN",
 	Revoke => "REVOKE",
 	Revoked => "REVOKED",
+	Role => "ROLE",
 	Roles => "ROLES",
 	Root => "ROOT",
 	Schemafull => "SCHEMAFULL",
@@ -184,6 +193,7 @@ keyword! {
 	Signup => "SIGNUP",
 	Since => "SINCE",
 	Sleep => "SLEEP",
+	Snapshot => "SNAPSHOT",
 	Snowball => "SNOWBALL",
 	Split => "SPLIT",
 	Start => "START",
@@ -199,6 +209,7 @@ keyword! {
 	Tokenizers => "TOKENIZERS",
 	Token => "TOKEN",
 	To => "TO",
+	Totp => "TOTP",
 	Transaction => "TRANSACTION",
 	True => "true",
 	Type => "TYPE",
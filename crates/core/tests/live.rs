@@ -6,7 +6,7 @@ use helpers::{new_ds, skip_ok};
 use surrealdb_core::dbs::{Action, Session, Variables};
 use surrealdb_core::expr::Kind;
 use surrealdb_core::syn;
-use surrealdb_core::val::RecordId;
+use surrealdb_core::val::{RecordId, Value};
 
 #[tokio::test]
 async fn live_permissions() -> Result<()> {
@@ -254,3 +254,197 @@ async fn live_document_reduction() -> Result<()> {
 	// Test passed!
 	Ok(())
 }
+
+#[tokio::test]
+async fn live_with_snapshot() -> Result<()> {
+	let dbs = new_ds().await?.with_notifications();
+	let Some(channel) = dbs.notifications() else {
+		unreachable!("No notification channel");
+	};
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	// Create the existing records
+	let sql = "
+		DEFINE TABLE test CHANGEFEED 1h INCLUDE ORIGINAL;
+		CREATE test:1 SET value = 1;
+		CREATE test:2 SET value = 2;
+		CREATE test:3 SET value = 3;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	skip_ok(res, 4)?;
+	// Start a live query with a snapshot of the matching records
+	let sql = "LIVE SELECT * FROM test WHERE value >= 2 WITH SNAPSHOT;";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let lqid = res.remove(0).result?;
+	// Receive the snapshot
+	let mut snapshot = None;
+	for expected in ["{ id: test:2, value: 2 }", "{ id: test:3, value: 3 }"] {
+		let tmp = channel.recv().await?;
+		assert_eq!(Value::from(tmp.id), lqid);
+		assert_eq!(tmp.action, Action::Create);
+		assert_eq!(tmp.result, syn::value(expected).unwrap());
+		assert!(tmp.versionstamp.is_some());
+		snapshot = tmp.versionstamp;
+	}
+	// Receive the changes after the snapshot
+	let res = &mut dbs.execute("CREATE test:4 SET value = 4;", &ses, None).await?;
+	skip_ok(res, 1)?;
+	let tmp = channel.recv().await?;
+	assert_eq!(tmp.action, Action::Create);
+	assert_eq!(tmp.result, syn::value("{ id: test:4, value: 4 }").unwrap());
+	assert!(channel.try_recv().is_err());
+	// Resuming from the snapshot replays only the later changes
+	let Some(vs) = snapshot else {
+		panic!("expected a versionstamp");
+	};
+	let sql = format!("LIVE SELECT * FROM test SINCE {vs};");
+	let res = &mut dbs.execute(&sql, &ses, None).await?;
+	skip_ok(res, 1)?;
+	let tmp = channel.recv().await?;
+	assert_eq!(tmp.action, Action::Create);
+	assert_eq!(tmp.result, syn::value("{ id: test:4, value: 4 }").unwrap());
+	assert!(channel.try_recv().is_err());
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn live_resume_since_versionstamp() -> Result<()> {
+	let dbs = new_ds().await?.with_notifications();
+	let Some(channel) = dbs.notifications() else {
+		unreachable!("No notification channel");
+	};
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let sql = "DEFINE TABLE test CHANGEFEED 1h INCLUDE ORIGINAL;";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	skip_ok(res, 1)?;
+	// Start a live query
+	let res = &mut dbs.execute("LIVE SELECT * FROM test;", &ses, None).await?;
+	skip_ok(res, 1)?;
+	// Notifications for a table with a changefeed have a versionstamp
+	let res = &mut dbs.execute("CREATE test:1 SET value = 1;", &ses, None).await?;
+	skip_ok(res, 1)?;
+	let tmp = channel.recv().await?;
+	let Some(vs) = tmp.versionstamp else {
+		panic!("expected a versionstamp");
+	};
+	// Make changes while the client is disconnected
+	let sql = "
+		UPDATE test:1 SET value = 2;
+		CREATE test:2 SET value = 3;
+		DELETE test:1;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	skip_ok(res, 3)?;
+	let mut missed = Vec::new();
+	for _ in 0..3 {
+		missed.push(channel.recv().await?);
+	}
+	// Resume from the last received versionstamp
+	let sql = format!("LIVE SELECT * FROM test SINCE {vs};");
+	let res = &mut dbs.execute(&sql, &ses, None).await?;
+	let lqid = res.remove(0).result?;
+	// Only the changes after the versionstamp are replayed, in order
+	for (action, expected) in [
+		(Action::Update, "{ id: test:1, value: 2 }"),
+		(Action::Create, "{ id: test:2, value: 3 }"),
+		(Action::Delete, "{ id: test:1, value: 2 }"),
+	] {
+		let tmp = channel.recv().await?;
+		assert_eq!(Value::from(tmp.id), lqid);
+		assert_eq!(tmp.action, action);
+		assert_eq!(tmp.result, syn::value(expected).unwrap());
+		// The replayed change has the versionstamp of the original notification
+		let original = missed.remove(0);
+		assert_eq!(original.action, action);
+		assert_eq!(tmp.versionstamp, original.versionstamp);
+		assert!(tmp.versionstamp.is_some_and(|v| v > vs));
+	}
+	assert!(channel.try_recv().is_err());
+	// Resuming requires a changefeed which includes the original records
+	let sql = "
+		DEFINE TABLE other CHANGEFEED 1h;
+		LIVE SELECT * FROM other SINCE 0;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	skip_ok(res, 1)?;
+	assert!(res.remove(0).result.is_err());
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn live_resume_since_past_other_tables() -> Result<()> {
+	let dbs = new_ds().await?.with_notifications();
+	let Some(channel) = dbs.notifications() else {
+		unreachable!("No notification channel");
+	};
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let sql = "
+		DEFINE TABLE test CHANGEFEED 1h INCLUDE ORIGINAL;
+		DEFINE TABLE other CHANGEFEED 1h INCLUDE ORIGINAL;
+		CREATE test:1 SET value = 1;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	skip_ok(res, 3)?;
+	// Make more changes to another table than are scanned at a time
+	let sql = (0..250).map(|i| format!("CREATE other:{i};")).collect::<String>();
+	let res = &mut dbs.execute(&sql, &ses, None).await?;
+	skip_ok(res, 250)?;
+	let res = &mut dbs.execute("CREATE test:2 SET value = 2;", &ses, None).await?;
+	skip_ok(res, 1)?;
+	// The changes to the table after the other changes are replayed
+	let res = &mut dbs.execute("LIVE SELECT * FROM test SINCE 0;", &ses, None).await?;
+	skip_ok(res, 1)?;
+	for expected in ["{ id: test:1, value: 1 }", "{ id: test:2, value: 2 }"] {
+		let tmp = channel.recv().await?;
+		assert_eq!(tmp.action, Action::Create);
+		assert_eq!(tmp.result, syn::value(expected).unwrap());
+	}
+	assert!(channel.try_recv().is_err());
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn live_masked_fields() -> Result<()> {
+	let dbs = new_ds().await?.with_auth_enabled(true).with_notifications();
+	let Some(channel) = dbs.notifications() else {
+		unreachable!("No notification channel");
+	};
+	let ses_owner = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let ses_record = Session::for_record(
+		"test",
+		"test",
+		"test",
+		RecordId::new("user".to_owned(), "test".to_owned()).into(),
+	)
+	.with_rt(true);
+	let sql = r#"
+		DEFINE TABLE test PERMISSIONS FULL;
+		DEFINE FIELD card ON test TYPE string MASK "****" + string::slice($value, 12) PERMISSIONS FOR select NONE;
+		DEFINE FIELD ssn ON test TYPE string MASK "***-**-****" PERMISSIONS FOR select WHERE $auth = user:other;
+	"#;
+	let res = &mut dbs.execute(sql, &ses_owner, None).await?;
+	skip_ok(res, 3)?;
+	// The record user receives the masked values
+	let res = &mut dbs.execute("LIVE SELECT * FROM test;", &ses_record, None).await?;
+	skip_ok(res, 1)?;
+	let sql = r#"CREATE test:1 SET name = "John", card = "4111222233334444", ssn = "123-45-6789";"#;
+	let res = &mut dbs.execute(sql, &ses_owner, None).await?;
+	skip_ok(res, 1)?;
+	let tmp = channel.recv().await?;
+	assert_eq!(tmp.action, Action::Create);
+	let val = syn::value("{ id: test:1, name: 'John', card: '****4444', ssn: '***-**-****' }")?;
+	assert_eq!(tmp.result, val);
+	// The masks are applied to updates as well
+	let res = &mut dbs
+		.execute(r#"UPDATE test:1 SET card = "4111222233335555";"#, &ses_owner, None)
+		.await?;
+	skip_ok(res, 1)?;
+	let tmp = channel.recv().await?;
+	assert_eq!(tmp.action, Action::Update);
+	let val = syn::value("{ id: test:1, name: 'John', card: '****5555', ssn: '***-**-****' }")?;
+	assert_eq!(tmp.result, val);
+	//
+	Ok(())
+}
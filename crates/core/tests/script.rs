@@ -207,6 +207,69 @@ async fn script_function_module_os() -> Result<()> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn script_function_module_defined() -> Result<()> {
+	let sql = "
+		DEFINE MODULE mod::math AS 'export function add(a, b) { return a + b; }';
+		DEFINE MODULE mod::utils::sum AS '
+			import { add } from \"mod::math\";
+			export default function sum(...args) { return args.reduce(add, 0); }
+		';
+		RETURN function() {
+			const { default: sum } = await import('mod::utils::sum');
+			return sum(1, 2, 3);
+		};
+		DEFINE MODULE OVERWRITE mod::math AS 'export function add(a, b) { return a + b * 10; }';
+		RETURN function() {
+			const { default: sum } = await import('mod::utils::sum');
+			return sum(1, 2, 3);
+		};
+		REMOVE MODULE mod::math;
+		RETURN function() {
+			const { default: sum } = await import('mod::utils::sum');
+			return sum(1, 2, 3);
+		};
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	res.remove(0).result?;
+	res.remove(0).result?;
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from(6.0));
+	//
+	res.remove(0).result?;
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from(60.0));
+	//
+	res.remove(0).result?;
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_err());
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn script_function_module_invalid() -> Result<()> {
+	let sql = "
+		DEFINE MODULE mod::broken AS 'export function (';
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_err());
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn script_query_from_script_select() -> Result<()> {
 	let sql = r#"
@@ -14,6 +14,7 @@ use crate::Result;
 use crate::core::dbs::Notification;
 use crate::core::expr::LogicalPlan;
 use crate::core::kvs::export::Config as DbExportConfig;
+use crate::core::kvs::migrate::Config as MigrateConfig;
 #[cfg(any(feature = "protocol-ws", feature = "protocol-http"))]
 use crate::core::val::Table as CoreTable;
 #[allow(unused_imports)]
@@ -85,11 +86,13 @@ pub(crate) enum Command {
 	},
 	Query {
 		txn: Option<Uuid>,
+		read_only: bool,
 		query: LogicalPlan,
 		variables: CoreObject,
 	},
 	RawQuery {
 		txn: Option<Uuid>,
+		read_only: bool,
 		query: Cow<'static, str>,
 		variables: CoreObject,
 	},
@@ -115,6 +118,10 @@ pub(crate) enum Command {
 	ImportMl {
 		path: PathBuf,
 	},
+	Migrate {
+		path: PathBuf,
+		config: MigrateConfig,
+	},
 	Health,
 	Version,
 	Set {
@@ -400,6 +407,7 @@ impl Command {
 				txn,
 				query,
 				variables,
+				..
 			} => {
 				let params: Vec<CoreValue> = vec![query.to_string().into(), variables.into()];
 				RouterRequest {
@@ -413,6 +421,7 @@ impl Command {
 				txn,
 				query,
 				variables,
+				..
 			} => {
 				let params: Vec<CoreValue> = vec![query.into_owned().into(), variables.into()];
 				RouterRequest {
@@ -439,6 +448,9 @@ impl Command {
 			}
 			| Command::ImportMl {
 				..
+			}
+			| Command::Migrate {
+				..
 			} => return None,
 			Command::Health => RouterRequest {
 				id,
@@ -78,7 +78,7 @@ impl conn::Sealed for Any {
 					);
 				}
 
-				EndpointKind::RocksDb => {
+				EndpointKind::RocksDb | EndpointKind::RocksDbVersioned => {
 					#[cfg(feature = "kv-rocksdb")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
@@ -264,6 +264,19 @@ type LiveQueryMap = HashMap<Uuid, Sender<Result<Notification>>>;
 /// # Ok(())
 /// # }
 /// ```
+///
+/// Instantiating an in-memory instance which is persisted to a directory
+///
+/// ```no_run
+/// use surrealdb::Surreal;
+/// use surrealdb::engine::local::Mem;
+///
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// let db = Surreal::new::<Mem>("path/to/database-folder").await?;
+/// # Ok(())
+/// # }
+/// ```
 #[cfg(feature = "kv-mem")]
 #[cfg_attr(docsrs, doc(cfg(feature = "kv-mem")))]
 #[derive(Debug)]
@@ -910,9 +923,9 @@ async fn router(
 			Ok(DbResponse::Other(value))
 		}
 		Command::Query {
-			txn: _,
 			query,
 			variables,
+			..
 		} => {
 			let mut vars = vars.read().await.clone();
 			vars.merge(variables);
@@ -922,9 +935,9 @@ async fn router(
 			Ok(DbResponse::Query(response))
 		}
 		Command::RawQuery {
-			txn: _,
 			query,
 			variables,
+			..
 		} => {
 			let mut vars = vars.read().await.clone();
 			vars.merge(variables);
@@ -944,6 +957,11 @@ async fn router(
 			..
 		} => Err(crate::api::Error::BackupsNotSupported.into()),
 
+		#[cfg(target_family = "wasm")]
+		Command::Migrate {
+			..
+		} => Err(crate::api::Error::MigrationsNotSupported.into()),
+
 		#[cfg(any(target_family = "wasm", not(feature = "ml")))]
 		Command::ExportMl {
 			..
@@ -1157,6 +1175,15 @@ async fn router(
 
 			Ok(DbResponse::Other(val::Value::None))
 		}
+		#[cfg(not(target_family = "wasm"))]
+		Command::Migrate {
+			path,
+			config,
+		} => {
+			let migrations = surrealdb_core::kvs::migrate::load(&path)?;
+			let report = kvs.migrate(&*session.read().await, migrations, config).await?;
+			Ok(DbResponse::Other(report.into()))
+		}
 		#[cfg(all(not(target_family = "wasm"), feature = "ml"))]
 		Command::ImportMl {
 			path,
@@ -298,12 +298,14 @@ async fn router(
 	match req.command {
 		Command::Query {
 			txn,
+			read_only,
 			query,
 			mut variables,
 		} => {
 			variables.extend(vars.clone());
 			let req = Command::Query {
 				txn,
+				read_only,
 				query,
 				variables,
 			}
@@ -520,6 +522,9 @@ async fn router(
 		Command::SubscribeLive {
 			..
 		} => Err(Error::LiveQueriesNotSupported.into()),
+		Command::Migrate {
+			..
+		} => Err(Error::MigrationsNotSupported.into()),
 		cmd => {
 			let needs_flatten = cmd.needs_flatten();
 			let req = cmd.into_router_request(None).unwrap();
@@ -10,6 +10,7 @@ use url::Url;
 
 use super::Client;
 use crate::api::conn::{Route, Router};
+use crate::api::engine::remote::pool;
 use crate::api::method::BoxFuture;
 use crate::api::opt::Endpoint;
 #[cfg(any(feature = "native-tls", feature = "rustls"))]
@@ -22,52 +23,59 @@ impl crate::api::Connection for Client {}
 impl conn::Sealed for Client {
 	fn connect(address: Endpoint, capacity: usize) -> BoxFuture<'static, Result<Surreal<Self>>> {
 		Box::pin(async move {
-			let headers = super::default_headers();
 			let config = address.config.clone();
+			let router = pool::connect(&config, capacity, move || {
+				Box::pin(connect_router(address.clone(), capacity))
+			})
+			.await?;
+			let waiter = watch::channel(Some(WaitFor::Connection));
+			Ok((router, waiter).into())
+		})
+	}
+}
 
-			#[cfg_attr(not(any(feature = "native-tls", feature = "rustls")), expect(unused_mut))]
-			let mut builder = ClientBuilder::new().default_headers(headers);
+async fn connect_router(address: Endpoint, capacity: usize) -> Result<Router> {
+	let headers = super::default_headers();
+	let config = address.config.clone();
 
-			#[cfg(any(feature = "native-tls", feature = "rustls"))]
-			if let Some(tls) = address.config.tls_config {
-				builder = match tls {
-					#[cfg(feature = "native-tls")]
-					Tls::Native(config) => builder.use_preconfigured_tls(config),
-					#[cfg(feature = "rustls")]
-					Tls::Rust(config) => builder.use_preconfigured_tls(config),
-				};
-			}
+	#[cfg_attr(not(any(feature = "native-tls", feature = "rustls")), expect(unused_mut))]
+	let mut builder = ClientBuilder::new().default_headers(headers);
 
-			let client = builder.build()?;
+	#[cfg(any(feature = "native-tls", feature = "rustls"))]
+	if let Some(tls) = address.config.tls_config {
+		builder = match tls {
+			#[cfg(feature = "native-tls")]
+			Tls::Native(config) => builder.use_preconfigured_tls(config),
+			#[cfg(feature = "rustls")]
+			Tls::Rust(config) => builder.use_preconfigured_tls(config),
+		};
+	}
 
-			let base_url = address.url;
+	let client = builder.build()?;
 
-			let req = client
-				.get(base_url.join("health")?)
-				.header(reqwest::header::USER_AGENT, &*SURREALDB_USER_AGENT);
-			super::health(req).await?;
+	let base_url = address.url;
 
-			let (route_tx, route_rx) = match capacity {
-				0 => async_channel::unbounded(),
-				capacity => async_channel::bounded(capacity),
-			};
+	let req = client
+		.get(base_url.join("health")?)
+		.header(reqwest::header::USER_AGENT, &*SURREALDB_USER_AGENT);
+	super::health(req).await?;
 
-			tokio::spawn(run_router(base_url, client, route_rx));
+	let (route_tx, route_rx) = match capacity {
+		0 => async_channel::unbounded(),
+		capacity => async_channel::bounded(capacity),
+	};
 
-			let mut features = HashSet::new();
-			features.insert(ExtraFeatures::Backup);
+	tokio::spawn(run_router(base_url, client, route_rx));
 
-			let waiter = watch::channel(Some(WaitFor::Connection));
-			let router = Router {
-				features,
-				config,
-				sender: route_tx,
-				last_id: AtomicI64::new(0),
-			};
+	let mut features = HashSet::new();
+	features.insert(ExtraFeatures::Backup);
 
-			Ok((router, waiter).into())
-		})
-	}
+	Ok(Router {
+		features,
+		config,
+		sender: route_tx,
+		last_id: AtomicI64::new(0),
+	})
 }
 
 pub(crate) async fn run_router(base_url: Url, client: reqwest::Client, route_rx: Receiver<Route>) {
@@ -102,6 +102,9 @@ pub mod http;
 #[cfg_attr(docsrs, doc(cfg(feature = "protocol-ws")))]
 pub mod ws;
 
+#[cfg(not(target_family = "wasm"))]
+mod pool;
+
 use std::time::Duration;
 
 use indexmap::IndexMap;
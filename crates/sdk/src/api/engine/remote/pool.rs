@@ -0,0 +1,541 @@
+//! A pool of connections to a remote server
+
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicUsize, Ordering};
+
+use async_channel::{Receiver, Sender};
+use indexmap::IndexMap;
+use tokio::sync::Mutex;
+use tokio::time::{self, MissedTickBehavior};
+
+use crate::api::Result;
+use crate::api::conn::{Command, DbResponse, RequestData, Route, Router};
+use crate::api::err::Error;
+use crate::api::method::BoxFuture;
+use crate::api::opt::{Config, PoolConfig, RetryPolicy};
+use crate::core::err::Error as CoreError;
+use crate::core::expr::TopLevelExpr;
+use crate::core::val::Value as CoreValue;
+
+/// The session state which is applied to every connection in the pool
+#[derive(Debug, Default)]
+struct Session {
+	/// The namespace selected by the `use` method
+	namespace: Option<String>,
+	/// The database selected by the `use` method
+	database: Option<String>,
+	/// The last `authenticate` or `invalidate` request, with a `signin` or
+	/// `signup` request being recorded as the token which it returned
+	auth: Option<Command>,
+	/// Vars currently set by the `set` method
+	vars: IndexMap<String, CoreValue>,
+}
+
+impl Session {
+	/// Records the effect of a request on the session
+	fn update(&mut self, command: &Command) {
+		match command {
+			Command::Use {
+				namespace,
+				database,
+			} => {
+				if namespace.is_some() {
+					self.namespace.clone_from(namespace);
+				}
+				if database.is_some() {
+					self.database.clone_from(database);
+				}
+			}
+			Command::Authenticate {
+				..
+			}
+			| Command::Invalidate => {
+				self.auth = Some(command.clone());
+			}
+			Command::Set {
+				key,
+				value,
+			} => {
+				self.vars.insert(key.clone(), value.clone());
+			}
+			Command::Unset {
+				key,
+			} => {
+				self.vars.shift_remove(key);
+			}
+			_ => {}
+		}
+	}
+
+	/// Returns the requests which restore the session on a connection
+	fn replay(&self) -> Vec<Command> {
+		let mut commands = Vec::new();
+		if self.namespace.is_some() || self.database.is_some() {
+			commands.push(Command::Use {
+				namespace: self.namespace.clone(),
+				database: self.database.clone(),
+			});
+		}
+		commands.extend(self.auth.clone());
+		for (key, value) in self.vars.iter() {
+			commands.push(Command::Set {
+				key: key.clone(),
+				value: value.clone(),
+			});
+		}
+		commands
+	}
+}
+
+/// A single connection in the pool
+#[derive(Debug)]
+struct Member {
+	router: Router,
+	/// Whether the last health check of this connection succeeded
+	healthy: AtomicBool,
+	/// Whether the session of this connection may be out of date
+	stale: AtomicBool,
+}
+
+impl Member {
+	fn new(router: Router) -> Self {
+		Self {
+			router,
+			healthy: AtomicBool::new(true),
+			stale: AtomicBool::new(false),
+		}
+	}
+
+	/// Whether read requests can be sent over this connection
+	fn is_ready(&self) -> bool {
+		self.healthy.load(Ordering::Acquire) && !self.stale.load(Ordering::Acquire)
+	}
+
+	/// Sends a request over this connection, with the response being sent
+	/// directly to the caller
+	async fn forward(&self, command: Command, response: Sender<Result<DbResponse>>) {
+		let route = Route {
+			request: RequestData {
+				id: self.router.next_id(),
+				command,
+			},
+			response,
+		};
+		if let Err(error) = self.router.sender.send(route).await {
+			let reset = io::Error::from(io::ErrorKind::ConnectionReset);
+			error.0.response.send(Err(reset.into())).await.ok();
+		}
+	}
+
+	/// Sends a request over this connection, and waits for the response
+	async fn execute(&self, command: Command) -> Result<DbResponse> {
+		let receiver = self.router.send(command).await?;
+		receiver.recv().await?
+	}
+}
+
+#[derive(Debug)]
+struct Pool {
+	/// The connections in the pool, the first of which is the primary
+	members: Vec<Member>,
+	/// The session which is replayed on stale connections
+	session: Mutex<Session>,
+	/// The position of the next connection to use for a read request
+	next: AtomicUsize,
+	/// Whether a health check is currently running
+	checking: AtomicBool,
+	config: PoolConfig,
+	retry: Option<RetryPolicy>,
+}
+
+/// Connects to the server, using a pool of connections if a pool or a retry
+/// policy has been configured
+pub(crate) async fn connect<F>(config: &Config, capacity: usize, connect: F) -> Result<Router>
+where
+	F: Fn() -> BoxFuture<'static, Result<Router>>,
+{
+	if config.pool.is_none() && config.retry.is_none() {
+		return connect().await;
+	}
+	let pool = config.pool.clone().unwrap_or_else(|| PoolConfig::new().size(1));
+	let mut members = Vec::with_capacity(pool.size);
+	for _ in 0..pool.size {
+		members.push(Member::new(connect().await?));
+	}
+	let features = members[0].router.features.clone();
+	let (route_tx, route_rx) = match capacity {
+		0 => async_channel::unbounded(),
+		capacity => async_channel::bounded(capacity),
+	};
+	let pool = Arc::new(Pool {
+		members,
+		session: Mutex::new(Session::default()),
+		next: AtomicUsize::new(0),
+		checking: AtomicBool::new(false),
+		config: pool,
+		retry: config.retry.clone(),
+	});
+	tokio::spawn(run_pool(pool, route_rx));
+	Ok(Router {
+		features,
+		config: config.clone(),
+		sender: route_tx,
+		last_id: AtomicI64::new(0),
+	})
+}
+
+async fn run_pool(pool: Arc<Pool>, route_rx: Receiver<Route>) {
+	let mut interval = time::interval(pool.config.health_check_interval);
+	interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+	// The first tick completes immediately
+	interval.tick().await;
+	loop {
+		tokio::select! {
+			route = route_rx.recv() => {
+				let Ok(route) = route else {
+					// The client has been dropped
+					break;
+				};
+				pool.dispatch(route).await;
+			}
+			_ = interval.tick() => {
+				if !pool.checking.swap(true, Ordering::AcqRel) {
+					tokio::spawn(pool.clone().check_health());
+				}
+			}
+		}
+	}
+}
+
+impl Pool {
+	fn primary(&self) -> &Member {
+		&self.members[0]
+	}
+
+	async fn dispatch(self: &Arc<Self>, route: Route) {
+		let Route {
+			request,
+			response,
+		} = route;
+		let command = request.command;
+		if is_credentials(&command) {
+			// Credentials are only sent to the primary, as a signup must not
+			// be repeated, and the other connections are then authenticated
+			// with the token which the server returned
+			let result = self.primary().execute(command).await;
+			if let Ok(DbResponse::Other(value)) = &result {
+				let auth = match token(value) {
+					Some(token) => Command::Authenticate {
+						token,
+					},
+					None => Command::Invalidate,
+				};
+				self.broadcast(auth).await;
+			}
+			response.send(result).await.ok();
+		} else if changes_session(&command) {
+			self.broadcast(command.clone()).await;
+			self.primary().forward(command, response).await;
+		} else if is_retryable(&command) {
+			let pool = self.clone();
+			tokio::spawn(async move {
+				let result = pool.execute(command).await;
+				response.send(result).await.ok();
+			});
+		} else {
+			self.primary().forward(command, response).await;
+		}
+	}
+
+	/// Records a change to the session, and applies it to every connection
+	/// other than the primary, in the order it was made
+	async fn broadcast(self: &Arc<Self>, command: Command) {
+		self.session.lock().await.update(&command);
+		for (index, member) in self.members.iter().enumerate().skip(1) {
+			let receiver = match member.router.send(command.clone()).await {
+				Ok(receiver) => receiver,
+				Err(_) => {
+					member.stale.store(true, Ordering::Release);
+					continue;
+				}
+			};
+			let pool = self.clone();
+			tokio::spawn(async move {
+				if !matches!(receiver.recv().await, Ok(Ok(_))) {
+					pool.members[index].stale.store(true, Ordering::Release);
+				}
+			});
+		}
+	}
+
+	/// Returns the next connection which is ready for a read request, or the
+	/// primary connection if no connection is ready
+	fn pick(&self) -> usize {
+		let len = self.members.len();
+		let start = self.next.fetch_add(1, Ordering::Relaxed);
+		(0..len).map(|i| (start + i) % len).find(|&i| self.members[i].is_ready()).unwrap_or(0)
+	}
+
+	/// Executes a read request, retrying it on a transient error
+	async fn execute(&self, command: Command) -> Result<DbResponse> {
+		let mut attempt = 0;
+		loop {
+			let index = self.pick();
+			let result = self.members[index].execute(command.clone()).await;
+			let retry = match &result {
+				Ok(DbResponse::Query(response)) => {
+					response.results.values().any(|(_, x)| x.as_ref().is_err_and(is_conflict))
+				}
+				Ok(DbResponse::Other(_)) => false,
+				Err(error) if is_disconnected(error) => {
+					self.members[index].healthy.store(false, Ordering::Release);
+					true
+				}
+				Err(error) => is_conflict(error),
+			};
+			let Some(policy) = self.retry.as_ref().filter(|x| retry && attempt < x.max_retries)
+			else {
+				return result;
+			};
+			time::sleep(policy.backoff(attempt)).await;
+			attempt += 1;
+		}
+	}
+
+	/// Checks the health of every connection, restoring the session of
+	/// connections which may be out of date
+	async fn check_health(self: Arc<Self>) {
+		let timeout = self.config.health_check_timeout;
+		for member in self.members.iter() {
+			let health = member.execute(Command::Health);
+			let healthy = matches!(time::timeout(timeout, health).await, Ok(Ok(_)));
+			if healthy && member.stale.load(Ordering::Acquire) {
+				let commands = self.session.lock().await.replay();
+				let mut restored = true;
+				for command in commands {
+					restored &= member.execute(command).await.is_ok();
+				}
+				member.stale.store(!restored, Ordering::Release);
+			}
+			member.healthy.store(healthy, Ordering::Release);
+		}
+		self.checking.store(false, Ordering::Release);
+	}
+}
+
+/// Whether a request authenticates the session using credentials
+fn is_credentials(command: &Command) -> bool {
+	matches!(command, Command::Signup { .. } | Command::Signin { .. })
+}
+
+/// Returns the token from the response to a `signin` or `signup` request
+fn token(value: &CoreValue) -> Option<String> {
+	match value {
+		CoreValue::String(token) => Some(token.clone()),
+		CoreValue::Object(object) => match object.get("token") {
+			Some(CoreValue::String(token)) => Some(token.clone()),
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+/// Whether a request changes the session of the connection it is sent over
+fn changes_session(command: &Command) -> bool {
+	matches!(
+		command,
+		Command::Use { .. }
+			| Command::Authenticate { .. }
+			| Command::Invalidate
+			| Command::Set { .. }
+			| Command::Unset { .. }
+	)
+}
+
+/// Whether a request can be sent over any connection, and repeated safely
+fn is_retryable(command: &Command) -> bool {
+	match command {
+		Command::Select {
+			txn: None,
+			..
+		} => true,
+		Command::RawQuery {
+			txn: None,
+			read_only,
+			..
+		} => *read_only,
+		// Live queries must be registered on the connection they were started on
+		Command::Query {
+			txn: None,
+			read_only,
+			query,
+			..
+		} => *read_only && !query.expressions.iter().any(|x| matches!(x, TopLevelExpr::Live(_))),
+		_ => false,
+	}
+}
+
+/// Whether an error was caused by the connection to the server
+fn is_disconnected(error: &anyhow::Error) -> bool {
+	if let Some(error) = error.downcast_ref::<io::Error>() {
+		return matches!(
+			error.kind(),
+			io::ErrorKind::ConnectionReset
+				| io::ErrorKind::ConnectionAborted
+				| io::ErrorKind::ConnectionRefused
+				| io::ErrorKind::NotConnected
+				| io::ErrorKind::BrokenPipe
+				| io::ErrorKind::TimedOut
+				| io::ErrorKind::UnexpectedEof
+		);
+	}
+	#[cfg(feature = "protocol-http")]
+	if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+		return error.is_connect() || error.is_timeout();
+	}
+	matches!(error.downcast_ref::<Error>(), Some(Error::Ws(_)))
+		|| error.downcast_ref::<async_channel::RecvError>().is_some()
+}
+
+/// Whether an error was caused by a transaction conflict which can be retried
+fn is_conflict(error: &anyhow::Error) -> bool {
+	match error.downcast_ref::<Error>() {
+		// Errors from the server only include the message of the error
+		Some(Error::Query(message) | Error::InternalError(message)) => {
+			*message == CoreError::TxRetryable.to_string()
+		}
+		_ => matches!(error.downcast_ref::<CoreError>(), Some(CoreError::TxRetryable)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn session_replay() {
+		let mut session = Session::default();
+		for command in [
+			Command::Use {
+				namespace: Some("test".to_owned()),
+				database: None,
+			},
+			Command::Use {
+				namespace: None,
+				database: Some("test".to_owned()),
+			},
+			Command::Authenticate {
+				token: "old".to_owned(),
+			},
+			Command::Invalidate,
+			Command::Set {
+				key: "a".to_owned(),
+				value: CoreValue::from(1),
+			},
+			Command::Set {
+				key: "b".to_owned(),
+				value: CoreValue::from(2),
+			},
+			Command::Unset {
+				key: "a".to_owned(),
+			},
+		] {
+			session.update(&command);
+		}
+		let replay = session.replay();
+		assert_eq!(replay.len(), 3);
+		assert!(matches!(
+			&replay[0],
+			Command::Use { namespace: Some(ns), database: Some(db) } if ns == "test" && db == "test"
+		));
+		assert!(matches!(replay[1], Command::Invalidate));
+		assert!(matches!(&replay[2], Command::Set { key, .. } if key == "b"));
+	}
+
+	#[tokio::test]
+	async fn signup_is_only_sent_to_primary() {
+		let (primary_tx, primary_rx) = async_channel::unbounded();
+		let (secondary_tx, secondary_rx) = async_channel::unbounded();
+		let router = |sender| Router {
+			sender,
+			config: Config::default(),
+			last_id: AtomicI64::new(0),
+			features: Default::default(),
+		};
+		let pool = Arc::new(Pool {
+			members: vec![Member::new(router(primary_tx)), Member::new(router(secondary_tx))],
+			session: Mutex::new(Session::default()),
+			next: AtomicUsize::new(0),
+			checking: AtomicBool::new(false),
+			config: PoolConfig::new(),
+			retry: None,
+		});
+		// The primary returns a token for the signup
+		tokio::spawn(async move {
+			let route: Route = primary_rx.recv().await.unwrap();
+			assert!(matches!(route.request.command, Command::Signup { .. }));
+			let token = CoreValue::String("token".to_owned());
+			route.response.send(Ok(DbResponse::Other(token))).await.unwrap();
+		});
+		let (response_tx, response_rx) = async_channel::unbounded();
+		let route = Route {
+			request: RequestData {
+				id: 0,
+				command: Command::Signup {
+					credentials: Default::default(),
+				},
+			},
+			response: response_tx,
+		};
+		pool.dispatch(route).await;
+		assert!(matches!(response_rx.recv().await, Ok(Ok(DbResponse::Other(_)))));
+		// The other connections are authenticated with the token instead
+		let route = secondary_rx.recv().await.unwrap();
+		assert!(
+			matches!(&route.request.command, Command::Authenticate { token } if token == "token")
+		);
+		assert!(secondary_rx.is_empty());
+		let replay = pool.session.lock().await.replay();
+		assert!(matches!(&replay[..], [Command::Authenticate { token }] if token == "token"));
+	}
+
+	#[test]
+	fn retryable_commands() {
+		let select = Command::Select {
+			txn: None,
+			what: crate::opt::Resource::from("person"),
+		};
+		assert!(is_retryable(&select));
+		let query = |read_only| Command::RawQuery {
+			txn: None,
+			read_only,
+			query: "SELECT * FROM person".into(),
+			variables: Default::default(),
+		};
+		assert!(is_retryable(&query(true)));
+		assert!(!is_retryable(&query(false)));
+		assert!(!is_retryable(&Command::Invalidate));
+	}
+
+	#[test]
+	fn transient_errors() {
+		let conflict = CoreError::TxRetryable.to_string();
+		assert!(is_conflict(&Error::Query(conflict).into()));
+		assert!(is_conflict(&CoreError::TxRetryable.into()));
+		assert!(!is_conflict(&Error::Query("Parse error".to_owned()).into()));
+		assert!(is_disconnected(&io::Error::from(io::ErrorKind::ConnectionReset).into()));
+		assert!(!is_disconnected(&Error::Query("Parse error".to_owned()).into()));
+	}
+
+	#[test]
+	fn retry_backoff() {
+		let policy = RetryPolicy::new()
+			.initial_backoff(std::time::Duration::from_millis(100))
+			.max_backoff(std::time::Duration::from_millis(300));
+		assert_eq!(policy.backoff(0).as_millis(), 100);
+		assert_eq!(policy.backoff(1).as_millis(), 200);
+		assert_eq!(policy.backoff(2).as_millis(), 300);
+		assert_eq!(policy.backoff(40).as_millis(), 300);
+	}
+}
@@ -22,8 +22,8 @@ use trice::Instant;
 
 use super::{HandleResult, PATH, PendingRequest, ReplayMethod, RequestEffect};
 use crate::api::conn::{self, Command, DbResponse, RequestData, Route, Router};
-use crate::api::engine::remote::Response;
 use crate::api::engine::remote::ws::{Client, PING_INTERVAL};
+use crate::api::engine::remote::{Response, pool};
 use crate::api::err::Error;
 use crate::api::method::BoxFuture;
 use crate::api::opt::Endpoint;
@@ -88,51 +88,51 @@ impl conn::Sealed for Client {
 	) -> BoxFuture<'static, Result<Surreal<Self>>> {
 		Box::pin(async move {
 			address.url = address.url.join(PATH)?;
-			#[cfg(any(feature = "native-tls", feature = "rustls"))]
-			let maybe_connector = address.config.tls_config.clone().map(Connector::from);
-			#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
-			let maybe_connector = None;
-
-			let ws_config = WebSocketConfig::default()
-				.read_buffer_size(address.config.websocket.read_buffer_size)
-				.max_message_size(address.config.websocket.max_message_size)
-				.max_frame_size(address.config.websocket.max_frame_size)
-				.max_write_buffer_size(address.config.websocket.max_write_buffer_size)
-				.write_buffer_size(address.config.websocket.write_buffer_size);
-
-			let socket = connect(&address, Some(ws_config), maybe_connector.clone()).await?;
-
-			let (route_tx, route_rx) = match capacity {
-				0 => async_channel::unbounded(),
-				capacity => async_channel::bounded(capacity),
-			};
 			let config = address.config.clone();
-
-			tokio::spawn(run_router(
-				address,
-				maybe_connector,
-				capacity,
-				ws_config,
-				socket,
-				route_rx,
-			));
-
-			let mut features = HashSet::new();
-			features.insert(ExtraFeatures::LiveQueries);
-
+			let router = pool::connect(&config, capacity, move || {
+				Box::pin(connect_router(address.clone(), capacity))
+			})
+			.await?;
 			let waiter = watch::channel(Some(WaitFor::Connection));
-			let router = Router {
-				features,
-				config,
-				sender: route_tx,
-				last_id: AtomicI64::new(0),
-			};
-
 			Ok((router, waiter).into())
 		})
 	}
 }
 
+async fn connect_router(address: Endpoint, capacity: usize) -> Result<Router> {
+	#[cfg(any(feature = "native-tls", feature = "rustls"))]
+	let maybe_connector = address.config.tls_config.clone().map(Connector::from);
+	#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+	let maybe_connector = None;
+
+	let ws_config = WebSocketConfig::default()
+		.read_buffer_size(address.config.websocket.read_buffer_size)
+		.max_message_size(address.config.websocket.max_message_size)
+		.max_frame_size(address.config.websocket.max_frame_size)
+		.max_write_buffer_size(address.config.websocket.max_write_buffer_size)
+		.write_buffer_size(address.config.websocket.write_buffer_size);
+
+	let socket = connect(&address, Some(ws_config), maybe_connector.clone()).await?;
+
+	let (route_tx, route_rx) = match capacity {
+		0 => async_channel::unbounded(),
+		capacity => async_channel::bounded(capacity),
+	};
+	let config = address.config.clone();
+
+	tokio::spawn(run_router(address, maybe_connector, capacity, ws_config, socket, route_rx));
+
+	let mut features = HashSet::new();
+	features.insert(ExtraFeatures::LiveQueries);
+
+	Ok(Router {
+		features,
+		config,
+		sender: route_tx,
+		last_id: AtomicI64::new(0),
+	})
+}
+
 async fn router_handle_route(
 	Route {
 		request,
@@ -223,8 +223,14 @@ async fn router_handle_route(
 	}
 
 	let message = {
+		let error = match command {
+			Command::Migrate {
+				..
+			} => Error::MigrationsNotSupported,
+			_ => Error::BackupsNotSupported,
+		};
 		let Some(request) = command.into_router_request(Some(id)) else {
-			let _ = response.send(Err(Error::BackupsNotSupported.into())).await;
+			let _ = response.send(Err(error.into())).await;
 			return HandleResult::Ok;
 		};
 		trace!("Request {:?}", request);
@@ -162,8 +162,14 @@ async fn router_handle_request(
 	}
 
 	let message = {
+		let error = match command {
+			Command::Migrate {
+				..
+			} => Error::MigrationsNotSupported,
+			_ => Error::BackupsNotSupported,
+		};
 		let Some(req) = command.into_router_request(Some(id)) else {
-			let _ = response.send(Err(Error::BackupsNotSupported.into())).await;
+			let _ = response.send(Err(error.into())).await;
 			return HandleResult::Ok;
 		};
 		trace!("Request {:?}", req);
@@ -51,7 +51,8 @@ pub fn init(dbs: Arc<Datastore>, canceller: CancellationToken, opts: &EngineOpti
 	let task3 = spawn_task_node_membership_cleanup(dbs.clone(), canceller.clone(), opts);
 	let task4 = spawn_task_changefeed_cleanup(dbs.clone(), canceller.clone(), opts);
 	let task5 = spawn_task_index_compaction(dbs.clone(), canceller.clone(), opts);
-	Tasks(vec![task1, task2, task3, task4, task5])
+	let task6 = spawn_task_node_notifications_delivery(dbs.clone(), canceller.clone(), opts);
+	Tasks(vec![task1, task2, task3, task4, task5, task6])
 }
 
 fn spawn_task_node_membership_refresh(
@@ -147,6 +148,37 @@ fn spawn_task_node_membership_cleanup(
 	}))
 }
 
+fn spawn_task_node_notifications_delivery(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let delay = opts.node_notifications_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Delivering notifications queued by other nodes every {delay:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(delay).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.node_notifications_deliver().await {
+						error!("Error delivering notifications queued by other nodes: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Delivering notifications queued by other nodes");
+	}))
+}
+
 fn spawn_task_changefeed_cleanup(
 	dbs: Arc<Datastore>,
 	canceller: CancellationToken,
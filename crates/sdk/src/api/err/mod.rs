@@ -187,6 +187,11 @@ pub enum Error {
 	#[error("The protocol or storage engine does not support live queries on this architecture")]
 	LiveQueriesNotSupported,
 
+	/// The protocol or storage engine being used does not support running
+	/// migrations
+	#[error("The protocol or storage engine does not support running migrations")]
+	MigrationsNotSupported,
+
 	/// Tried to use a range query on an object
 	#[error("Live queries on objects not supported")]
 	LiveOnObject,
@@ -281,6 +286,14 @@ pub enum Error {
 	/// The write buffer size is too small
 	#[error("The write buffer size is too small")]
 	MaxWriteBufferSizeTooSmall,
+
+	/// Tried to write to a table which is not replicated
+	#[error("The table `{0}` is not replicated")]
+	TableNotReplicated(String),
+
+	/// Tried to write a replicated record which is not an object
+	#[error("The content of a replicated record should be an object")]
+	ReplicaContentNotObject,
 }
 
 impl serde::ser::Error for Error {
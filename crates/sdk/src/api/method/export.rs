@@ -143,6 +143,17 @@ where
 		}
 		self
 	}
+
+	/// Whether to replace fields defined with a `MASK` clause with their mask
+	///
+	/// Masks are applied to every record, regardless of the `SELECT`
+	/// permissions of the fields.
+	pub fn masks(mut self, masks: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.masks = masks;
+		}
+		self
+	}
 }
 
 impl<C, R, T> Export<'_, C, R, T>
@@ -0,0 +1,139 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::api::conn::Command;
+use crate::api::method::BoxFuture;
+use crate::api::{Connection, Result};
+use crate::core::kvs::migrate::{Action, Config};
+use crate::method::OnceLockExt;
+use crate::{Datetime, Surreal};
+
+/// A schema migration future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Migrate<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) dir: PathBuf,
+	pub(super) config: Config,
+}
+
+impl<C> Migrate<'_, C>
+where
+	C: Connection,
+{
+	/// Revert the applied migrations instead of applying the pending ones
+	///
+	/// Without a target version, only the most recently applied migration is
+	/// reverted.
+	pub fn down(mut self) -> Self {
+		self.config.action = Action::Down;
+		self
+	}
+
+	/// Only report the state of each migration, without changing the
+	/// database
+	pub fn status(mut self) -> Self {
+		self.config.action = Action::Status;
+		self
+	}
+
+	/// Migrate up to, or down to, the given version
+	pub fn to(mut self, version: impl Into<String>) -> Self {
+		self.config.target = Some(version.into());
+		self
+	}
+
+	/// Report the changes the migrations would make to the schema, without
+	/// changing the database
+	pub fn dry_run(mut self) -> Self {
+		self.config.dry_run = true;
+		self
+	}
+
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> Migrate<'static, C> {
+		Migrate {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Migrate<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<MigrationReport>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			router
+				.execute(Command::Migrate {
+					path: self.dir,
+					config: self.config,
+				})
+				.await
+		})
+	}
+}
+
+/// The outcome of running the migrations in a directory
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct MigrationReport {
+	/// The versions which were applied, or would be applied in a dry run
+	pub applied: Vec<String>,
+	/// The versions which were reverted, or would be reverted in a dry run
+	pub reverted: Vec<String>,
+	/// The state of each known migration, before any changes were made
+	pub status: Vec<MigrationStatus>,
+	/// The changes to the schema, if this was a dry run
+	pub diff: Option<SchemaDiff>,
+}
+
+/// The state of a single migration
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct MigrationStatus {
+	/// The version of the migration, such as `0001`
+	pub version: String,
+	/// The description of the migration, such as `create_users`
+	pub name: String,
+	/// Whether the migration is applied, pending, modified or missing
+	pub state: MigrationState,
+	/// When the migration was applied
+	pub applied_at: Option<Datetime>,
+}
+
+/// Whether a migration has been applied to the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum MigrationState {
+	/// The migration has been applied
+	Applied,
+	/// The migration has not been applied
+	Pending,
+	/// The migration has been applied, but has been modified since
+	Modified,
+	/// The migration has been applied, but its source no longer exists
+	Missing,
+}
+
+/// The definitions a dry run would add, remove or change
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct SchemaDiff {
+	/// The definitions which would be added
+	pub added: Vec<String>,
+	/// The definitions which would be removed
+	pub removed: Vec<String>,
+	/// The definitions which would be changed
+	pub changed: Vec<String>,
+}
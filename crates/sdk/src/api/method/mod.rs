@@ -31,6 +31,7 @@ mod insert;
 mod insert_relation;
 mod invalidate;
 mod merge;
+mod migrate;
 mod patch;
 mod run;
 mod select;
@@ -63,6 +64,7 @@ pub use insert::Insert;
 pub use invalidate::Invalidate;
 pub use live::Stream;
 pub use merge::Merge;
+pub use migrate::{Migrate, MigrationReport, MigrationState, MigrationStatus, SchemaDiff};
 pub use patch::Patch;
 pub use query::{Query, QueryStream};
 pub use run::{IntoFn, Run};
@@ -636,6 +638,7 @@ where
 		let result = query.into_query(self).0;
 		Query {
 			txn: None,
+			read_only: false,
 			inner: result,
 			client: Cow::Borrowed(self),
 		}
@@ -1399,6 +1402,50 @@ where
 			import_type: PhantomData,
 		}
 	}
+
+	/// Applies the versioned schema migrations in a directory
+	///
+	/// Migrations are `.surql` files named after their version and
+	/// description, such as `0001_create_users.surql`. A migration can be
+	/// split into `0001_create_users.up.surql` and
+	/// `0001_create_users.down.surql` so that it can be reverted. Each
+	/// migration runs in its own transaction, and is recorded with a
+	/// checksum in the `_migrations` table.
+	///
+	/// # Support
+	///
+	/// Currently only supported by the local engines. *Not* supported on
+	/// WebAssembly.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Apply all of the pending migrations
+	/// let report = db.migrate("migrations").await?;
+	/// println!("Applied {:?}", report.applied);
+	///
+	/// // Show the schema changes of reverting to a version
+	/// let report = db.migrate("migrations").down().to("0002").dry_run().await?;
+	/// println!("{:?}", report.diff);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn migrate<P>(&'_ self, dir: P) -> Migrate<'_, C>
+	where
+		P: AsRef<Path>,
+	{
+		Migrate {
+			client: Cow::Borrowed(self),
+			dir: dir.as_ref().to_owned(),
+			config: Default::default(),
+		}
+	}
 }
 
 fn validate_data(data: &val::Value, error_message: &str) -> crate::Result<()> {
@@ -30,6 +30,7 @@ use crate::{Surreal, Value};
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 pub struct Query<'r, C: Connection> {
 	pub(crate) txn: Option<Uuid>,
+	pub(crate) read_only: bool,
 	pub(crate) client: Cow<'r, Surreal<C>>,
 	pub(crate) inner: Result<ValidQuery>,
 }
@@ -69,6 +70,7 @@ where
 	) -> Self {
 		Query {
 			txn: None,
+			read_only: false,
 			client,
 			inner: Ok(ValidQuery::Normal {
 				query,
@@ -85,11 +87,13 @@ where
 		match self.inner {
 			Ok(x) => Query {
 				txn: self.txn,
+				read_only: self.read_only,
 				client: self.client,
 				inner: f(x),
 			},
 			x => Query {
 				txn: self.txn,
+				read_only: self.read_only,
 				client: self.client,
 				inner: x,
 			},
@@ -101,6 +105,7 @@ where
 	pub fn into_owned(self) -> Query<'static, C> {
 		Query {
 			txn: self.txn,
+			read_only: self.read_only,
 			client: Cow::Owned(self.client.into_owned()),
 			inner: self.inner,
 		}
@@ -128,6 +133,7 @@ where
 						.execute_query(Command::RawQuery {
 							query,
 							txn: self.txn,
+							read_only: self.read_only,
 							variables: bindings,
 						})
 						.await
@@ -171,6 +177,7 @@ where
 					let mut response = router
 						.execute_query(Command::Query {
 							txn: self.txn,
+							read_only: self.read_only,
 							query,
 							variables: bindings,
 						})
@@ -260,6 +267,31 @@ where
 		})
 	}
 
+	/// Marks the query as read-only
+	///
+	/// Read-only queries do not modify any data, so they can be sent over any
+	/// connection in a connection pool, and are retried when they fail with a
+	/// transient error, if a retry policy has been configured. The database
+	/// does not check that the query is actually read-only.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let response = db.query("SELECT * FROM person WHERE age > $age")
+	///     .bind(("age", 18))
+	///     .read_only()
+	///     .await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub const fn read_only(mut self) -> Self {
+		self.read_only = true;
+		self
+	}
+
 	/// Return query statistics along with its results
 	pub const fn with_stats(self) -> WithStats<Self> {
 		WithStats(self)
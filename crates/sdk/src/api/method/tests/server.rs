@@ -135,6 +135,9 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				}
 				| Command::ImportFile {
 					..
+				}
+				| Command::Migrate {
+					..
 				} => Ok(DbResponse::Other(val::Value::None)),
 			};
 
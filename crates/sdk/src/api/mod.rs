@@ -122,6 +122,7 @@ pub mod err;
 pub mod headers;
 pub mod method;
 pub mod opt;
+pub mod replica;
 pub mod value;
 
 mod conn;
@@ -35,6 +35,7 @@ impl From<&ExperimentalFeature> for ExperimentalTarget {
 ///
 /// Capabilities are split into categories:
 /// - Scripting: Whether or not users can execute scripts
+/// - WebAssembly: Whether or not users can execute WebAssembly functions
 /// - Guest access: Whether or not unauthenticated users can execute queries
 /// - Functions: Whether or not users can execute certain functions
 /// - Network: Whether or not users can connect to certain network addresses
@@ -42,6 +43,7 @@ impl From<&ExperimentalFeature> for ExperimentalTarget {
 /// Capabilities are configured globally. By default, capabilities are
 /// configured as:
 /// - Scripting: false
+/// - WebAssembly: false
 /// - Guest access: false
 /// - Functions: All functions are allowed
 /// - Network: No network address is allowed, all are impliticly denied
@@ -173,6 +175,13 @@ impl Capabilities {
 		}
 	}
 
+	/// Set whether to enable the WebAssembly function runtime.
+	pub fn with_wasm(self, enabled: bool) -> Self {
+		Self {
+			cap: self.cap.with_wasm(enabled),
+		}
+	}
+
 	/// Set whether to allow non-authenticated users to execute queries when
 	/// authentication is enabled.
 	pub fn with_guest_access(self, enabled: bool) -> Self {
@@ -5,6 +5,7 @@ use std::time::Duration;
 use crate::core::dbs::Capabilities as CoreCapabilities;
 use crate::core::iam::Level;
 use crate::opt::capabilities::Capabilities;
+use crate::opt::pool::{PoolConfig, RetryPolicy};
 use crate::opt::websocket::WebsocketConfig;
 
 /// Configuration for server connection, including: strictness, notifications,
@@ -24,6 +25,8 @@ pub struct Config {
 	pub(crate) password: String,
 	pub(crate) capabilities: CoreCapabilities,
 	pub(crate) websocket: WebsocketConfig,
+	pub(crate) pool: Option<PoolConfig>,
+	pub(crate) retry: Option<RetryPolicy>,
 	#[cfg(storage)]
 	pub(crate) temporary_directory: Option<PathBuf>,
 	pub(crate) node_membership_refresh_interval: Option<Duration>,
@@ -121,6 +124,24 @@ impl Config {
 		Ok(self)
 	}
 
+	/// Use a pool of connections to the server
+	///
+	/// Only used by the remote engines. See [`PoolConfig`] for how requests
+	/// are spread across the connections in the pool.
+	pub fn pool(mut self, pool: PoolConfig) -> Self {
+		self.pool = Some(pool);
+		self
+	}
+
+	/// Retry requests which fail with a transient error
+	///
+	/// Only used by the remote engines. See [`RetryPolicy`] for which requests
+	/// are retried.
+	pub fn retry(mut self, retry: RetryPolicy) -> Self {
+		self.retry = Some(retry);
+		self
+	}
+
 	#[cfg(storage)]
 	pub fn temporary_directory(mut self, path: Option<PathBuf>) -> Self {
 		self.temporary_directory = path;
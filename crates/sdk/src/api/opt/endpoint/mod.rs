@@ -15,6 +15,8 @@ mod rocksdb;
 mod surrealkv;
 #[cfg(feature = "kv-tikv")]
 mod tikv;
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+mod versioned;
 
 use url::Url;
 
@@ -126,6 +128,7 @@ pub enum EndpointKind {
 	IndxDb,
 	Memory,
 	RocksDb,
+	RocksDbVersioned,
 	TiKv,
 	Unsupported(String),
 	SurrealKv,
@@ -144,6 +147,7 @@ impl From<&str> for EndpointKind {
 			"indxdb" => Self::IndxDb,
 			"mem" => Self::Memory,
 			"rocksdb" => Self::RocksDb,
+			"rocksdb+versioned" => Self::RocksDbVersioned,
 			"tikv" => Self::TiKv,
 			"surrealkv" => Self::SurrealKv,
 			"surrealkv+versioned" => Self::SurrealKvVersioned,
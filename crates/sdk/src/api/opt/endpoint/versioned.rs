@@ -0,0 +1,72 @@
+use crate::Connect;
+use crate::api::Result;
+use crate::api::engine::local::Db;
+use crate::api::err::Error;
+use crate::api::opt::Endpoint;
+
+impl<R> Connect<Db, R> {
+	/// Local database with versions enabled
+	///
+	/// Versions are supported by the SurrealKV and RocksDB engines.
+	///
+	/// # Examples
+	///
+	/// Instantiating a SurrealKV-backed instance with versions
+	///
+	/// ```no_run
+	/// # #[cfg(feature = "kv-surrealkv")]
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// use surrealdb::Surreal;
+	/// use surrealdb::engine::local::SurrealKv;
+	///
+	/// let db = Surreal::new::<SurrealKv>("path/to/database-folder").versioned().await?;
+	/// # Ok(())
+	/// # }
+	/// # #[cfg(not(feature = "kv-surrealkv"))]
+	/// # fn main() {}
+	/// ```
+	///
+	/// Instantiating a RocksDB-backed strict instance with versions
+	///
+	/// ```no_run
+	/// # #[cfg(feature = "kv-rocksdb")]
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// use surrealdb::opt::Config;
+	/// use surrealdb::Surreal;
+	/// use surrealdb::engine::local::RocksDb;
+	///
+	/// let config = Config::default().strict();
+	/// let db = Surreal::new::<RocksDb>(("path/to/database-folder", config)).versioned().await?;
+	/// # Ok(())
+	/// # }
+	/// # #[cfg(not(feature = "kv-rocksdb"))]
+	/// # fn main() {}
+	/// ```
+	pub fn versioned(mut self) -> Self {
+		let replace_scheme = |mut endpoint: Endpoint| -> Result<Endpoint> {
+			let versioned = match endpoint.url.scheme() {
+				// If the engine is unversioned, we want to switch it to a versioned one
+				"surrealkv" => "surrealkv+versioned",
+				"rocksdb" => "rocksdb+versioned",
+				// The engine is already versioned, nothing to do here
+				"surrealkv+versioned" | "rocksdb+versioned" => return Ok(endpoint),
+				// This engine doesn't support versions
+				scheme => return Err(Error::VersionsNotSupported(scheme.to_owned()).into()),
+			};
+			// Replace the scheme in the URL
+			endpoint
+				.url
+				.set_scheme(versioned)
+				.unwrap_or_else(|_| unreachable!("`{versioned}` should be static and valid"));
+			// and in the path
+			if let Some((_, rest)) = endpoint.path.split_once(':') {
+				endpoint.path = format!("{versioned}:{rest}");
+			}
+			Ok(endpoint)
+		};
+		self.address = self.address.and_then(replace_scheme);
+		self
+	}
+}
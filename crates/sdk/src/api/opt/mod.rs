@@ -10,6 +10,7 @@ pub mod capabilities;
 mod config;
 pub(crate) mod endpoint;
 mod export;
+mod pool;
 pub(crate) mod query;
 mod resource;
 mod tls;
@@ -18,6 +19,7 @@ mod websocket;
 pub use config::*;
 pub use endpoint::*;
 pub use export::*;
+pub use pool::*;
 pub use query::*;
 pub use resource::*;
 use serde_content::{Serializer, Value as Content};
@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+/// Configuration options for a pool of remote connections.
+///
+/// By default, a client uses a single connection to the server. With a pool,
+/// the client opens several connections to the same endpoint, and spreads
+/// read requests which are safe to retry, such as `select` and queries marked
+/// with [`read_only`](crate::method::Query::read_only), across the connections
+/// which are currently healthy. All other requests, including transactions and
+/// live queries, are sent over the first connection in the pool, so that they
+/// are processed in order.
+///
+/// Changes to the session, such as `use_ns`, `use_db`, `signin`,
+/// `authenticate`, and `set`, are applied to every connection in the pool,
+/// and are replayed on a connection which failed a health check before it is
+/// used again.
+///
+/// Connection pools are only supported by the remote engines on native
+/// targets.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// use std::time::Duration;
+/// use surrealdb::Surreal;
+/// use surrealdb::engine::remote::ws::Ws;
+/// use surrealdb::opt::{Config, PoolConfig};
+///
+/// let pool = PoolConfig::new()
+///     .size(4)
+///     .health_check_interval(Duration::from_secs(5));
+/// let config = Config::new().pool(pool);
+/// let db = Surreal::new::<Ws>(("localhost:8000", config)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+	/// The number of connections in the pool (default: 4)
+	pub(crate) size: usize,
+	/// How often each connection is checked (default: 10 seconds)
+	pub(crate) health_check_interval: Duration,
+	/// How long a health check can take before it fails (default: 5 seconds)
+	pub(crate) health_check_timeout: Duration,
+}
+
+impl Default for PoolConfig {
+	fn default() -> Self {
+		Self {
+			size: 4,
+			health_check_interval: Duration::from_secs(10),
+			health_check_timeout: Duration::from_secs(5),
+		}
+	}
+}
+
+impl PoolConfig {
+	/// Creates a new `PoolConfig` with default values.
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Sets the number of connections in the pool.
+	///
+	/// A size of `0` is treated as `1`.
+	pub fn size(mut self, size: usize) -> Self {
+		self.size = size.max(1);
+		self
+	}
+
+	/// Sets how often the health of each connection is checked.
+	pub fn health_check_interval(mut self, interval: Duration) -> Self {
+		self.health_check_interval = interval;
+		self
+	}
+
+	/// Sets how long a health check can take before the connection is
+	/// considered to be unhealthy.
+	pub fn health_check_timeout(mut self, timeout: Duration) -> Self {
+		self.health_check_timeout = timeout;
+		self
+	}
+}
+
+/// A policy for retrying requests which failed with a transient error.
+///
+/// Only requests which are safe to repeat are retried: `select` outside of a
+/// transaction, and queries marked with
+/// [`read_only`](crate::method::Query::read_only). A request is retried when
+/// the connection to the server failed, or when the database reported a
+/// transaction conflict which can be retried. The delay between attempts
+/// doubles after each attempt, up to the maximum backoff.
+///
+/// Retries are only supported by the remote engines on native targets.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// use std::time::Duration;
+/// use surrealdb::Surreal;
+/// use surrealdb::engine::remote::http::Http;
+/// use surrealdb::opt::{Config, RetryPolicy};
+///
+/// let retry = RetryPolicy::new()
+///     .max_retries(5)
+///     .initial_backoff(Duration::from_millis(100));
+/// let config = Config::new().retry(retry);
+/// let db = Surreal::new::<Http>(("localhost:8000", config)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+	/// The number of times a request is retried (default: 3)
+	pub(crate) max_retries: u32,
+	/// The delay before the first retry (default: 50 milliseconds)
+	pub(crate) initial_backoff: Duration,
+	/// The maximum delay between retries (default: 2 seconds)
+	pub(crate) max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		Self {
+			max_retries: 3,
+			initial_backoff: Duration::from_millis(50),
+			max_backoff: Duration::from_secs(2),
+		}
+	}
+}
+
+impl RetryPolicy {
+	/// Creates a new `RetryPolicy` with default values.
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Sets the number of times a request is retried.
+	pub fn max_retries(mut self, max_retries: u32) -> Self {
+		self.max_retries = max_retries;
+		self
+	}
+
+	/// Sets the delay before the first retry.
+	pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+		self.initial_backoff = backoff;
+		self
+	}
+
+	/// Sets the maximum delay between retries.
+	pub fn max_backoff(mut self, backoff: Duration) -> Self {
+		self.max_backoff = backoff;
+		self
+	}
+
+	/// Returns the delay before the given retry, starting from `0`.
+	pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+		let factor = 2u32.saturating_pow(attempt);
+		self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+	}
+}
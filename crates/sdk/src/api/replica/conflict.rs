@@ -0,0 +1,168 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::core::val::{RecordId as CoreRecordId, Value as CoreValue};
+use crate::{RecordId, Value};
+
+/// A record which was changed on the remote server while a local write to it
+/// was waiting to be uploaded
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Conflict {
+	/// The record which was changed on both sides
+	pub id: RecordId,
+	/// The local version of the record, or `None` if it was deleted locally
+	pub local: Option<Value>,
+	/// The remote version of the record, or `None` if it was deleted on the
+	/// server
+	pub remote: Option<Value>,
+}
+
+type MergeFn = dyn Fn(Conflict) -> Option<Value> + Send + Sync;
+
+/// How a conflict between a local write and a remote change is resolved
+#[derive(Clone, Default)]
+#[non_exhaustive]
+pub enum Resolution {
+	/// Keep the version which was written last
+	///
+	/// The time of a write is read from the timestamp field of the record,
+	/// which the replica sets on every local write. A remote version without a
+	/// timestamp, including a remote delete, is considered to be older than
+	/// the local write.
+	#[default]
+	LastWriterWins,
+	/// Resolve the conflict with a custom function
+	///
+	/// The function returns the version of the record to keep on both sides,
+	/// or `None` to delete the record.
+	Merge(Arc<MergeFn>),
+}
+
+impl Resolution {
+	/// Resolves conflicts with a custom merge function
+	///
+	/// # Examples
+	///
+	/// Keep the remote version of every record:
+	///
+	/// ```
+	/// use surrealdb::replica::Resolution;
+	///
+	/// let resolution = Resolution::merge(|conflict| conflict.remote);
+	/// ```
+	pub fn merge<F>(merge: F) -> Self
+	where
+		F: Fn(Conflict) -> Option<Value> + Send + Sync + 'static,
+	{
+		Self::Merge(Arc::new(merge))
+	}
+
+	/// Returns the version of a record to keep on both sides
+	pub(super) fn resolve(
+		&self,
+		field: &str,
+		id: &CoreRecordId,
+		local: Option<CoreValue>,
+		written_at: &CoreValue,
+		remote: Option<CoreValue>,
+	) -> Option<CoreValue> {
+		match self {
+			Self::LastWriterWins => {
+				let remote_time = match &remote {
+					Some(CoreValue::Object(remote)) => remote.get(field),
+					_ => None,
+				};
+				let local_wins = match (written_at, remote_time) {
+					(CoreValue::Datetime(local), Some(CoreValue::Datetime(remote))) => {
+						local >= remote
+					}
+					_ => true,
+				};
+				if local_wins {
+					local
+				} else {
+					remote
+				}
+			}
+			Self::Merge(merge) => merge(Conflict {
+				id: RecordId::from_inner(id.clone()),
+				local: local.map(Value::from_inner),
+				remote: remote.map(Value::from_inner),
+			})
+			.map(Value::into_inner),
+		}
+	}
+}
+
+impl fmt::Debug for Resolution {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::LastWriterWins => f.write_str("LastWriterWins"),
+			Self::Merge(_) => f.write_str("Merge"),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use chrono::{TimeZone, Utc};
+
+	use super::*;
+	use crate::core::val::{Datetime, Object};
+
+	fn record(time: Option<i64>) -> CoreValue {
+		let mut object = Object::default();
+		object.insert("name".to_owned(), CoreValue::from("tobie"));
+		if let Some(time) = time {
+			object.insert("updated_at".to_owned(), datetime(time));
+		}
+		CoreValue::Object(object)
+	}
+
+	fn datetime(secs: i64) -> CoreValue {
+		CoreValue::Datetime(Datetime(Utc.timestamp_opt(secs, 0).unwrap()))
+	}
+
+	fn resolve(
+		resolution: &Resolution,
+		local: Option<CoreValue>,
+		written_at: i64,
+		remote: Option<CoreValue>,
+	) -> Option<CoreValue> {
+		let id = CoreRecordId::new("person".to_owned(), "tobie".to_owned());
+		resolution.resolve("updated_at", &id, local, &datetime(written_at), remote)
+	}
+
+	#[test]
+	fn last_writer_wins() {
+		let resolution = Resolution::LastWriterWins;
+		// The remote version was written after the local one
+		let resolved = resolve(&resolution, Some(record(Some(1))), 1, Some(record(Some(2))));
+		assert_eq!(resolved, Some(record(Some(2))));
+		// The local version was written after the remote one
+		let resolved = resolve(&resolution, Some(record(Some(3))), 3, Some(record(Some(2))));
+		assert_eq!(resolved, Some(record(Some(3))));
+		// A local delete written after the remote version
+		let resolved = resolve(&resolution, None, 3, Some(record(Some(2))));
+		assert_eq!(resolved, None);
+		// The remote version has no timestamp
+		let resolved = resolve(&resolution, Some(record(Some(1))), 1, Some(record(None)));
+		assert_eq!(resolved, Some(record(Some(1))));
+		// The record was deleted on the server
+		let resolved = resolve(&resolution, Some(record(Some(1))), 1, None);
+		assert_eq!(resolved, Some(record(Some(1))));
+	}
+
+	#[test]
+	fn merge() {
+		let resolution = Resolution::merge(|conflict| {
+			assert_eq!(conflict.id.table(), "person");
+			conflict.remote
+		});
+		let resolved = resolve(&resolution, Some(record(Some(3))), 3, Some(record(Some(2))));
+		assert_eq!(resolved, Some(record(Some(2))));
+		let resolved = resolve(&resolution, Some(record(Some(3))), 3, None);
+		assert_eq!(resolved, None);
+	}
+}
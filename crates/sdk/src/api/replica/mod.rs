@@ -0,0 +1,638 @@
+//! Offline-first replication between an embedded and a remote database
+//!
+//! A [`Replica`] keeps a set of tables in an embedded database in sync with a
+//! remote server. Changes made on the server are read from the changefeed of
+//! the remote database, and applied to the embedded database. Writes made
+//! through the replica are applied to the embedded database straight away, and
+//! queued until they can be uploaded to the server. The queue, and the position
+//! in the remote changefeed, are stored in the embedded database, so an app
+//! can keep working offline, and across restarts.
+//!
+//! The replicated tables must have a changefeed on the server. The first sync
+//! of a table copies all of its records from the server, and the changefeed is
+//! followed from there on. If the replica has not synced for longer than the
+//! changefeed is kept for, the tables are copied from the server again. When a
+//! record was changed on the server while a local write to it was queued, the
+//! conflict is resolved with the configured [`Resolution`].
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # #[tokio::main]
+//! # async fn main() -> surrealdb::Result<()> {
+//! use serde_json::json;
+//! use surrealdb::engine::local::SurrealKv;
+//! use surrealdb::engine::remote::ws::Ws;
+//! use surrealdb::replica::Replica;
+//! use surrealdb::{RecordId, Surreal};
+//!
+//! let local = Surreal::new::<SurrealKv>("app.db").await?;
+//! local.use_ns("app").use_db("app").await?;
+//!
+//! // The tables need a changefeed on the server, for example
+//! // `DEFINE TABLE task CHANGEFEED 7d`
+//! let remote = Surreal::new::<Ws>("localhost:8000").await?;
+//! remote.use_ns("app").use_db("app").await?;
+//!
+//! let replica = Replica::new(local, remote).table("task");
+//!
+//! // Writes are applied locally, even while offline
+//! let id = RecordId::from_table_key("task", "groceries");
+//! replica.upsert(id, json!({ "title": "Buy groceries", "done": false })).await?;
+//!
+//! // Download the remote changes, and upload the local writes
+//! let report = replica.sync().await?;
+//! println!("pulled {}, pushed {}", report.pulled, report.pushed);
+//! # Ok(())
+//! # }
+//! ```
+
+mod conflict;
+
+use std::collections::HashMap;
+
+pub use conflict::{Conflict, Resolution};
+use serde::Serialize;
+
+use crate::api::err::Error;
+use crate::api::{self, Connection, Result};
+use crate::core::sql::statements::{ShowSince, ShowStatement};
+use crate::core::sql::{Ast, TopLevelExpr};
+use crate::core::val::{Datetime, Number, Object, RecordId as CoreRecordId, Value as CoreValue};
+use crate::core::vs::VersionStamp;
+use crate::{RecordId, Surreal, Value};
+
+/// Applies a write to the embedded database, and queues it for upload
+const WRITE_QUERY: &str = "
+	BEGIN;
+	IF $value = NONE { DELETE $id } ELSE { UPSERT $id CONTENT $value };
+	DELETE _replica_queue WHERE record = $id;
+	CREATE _replica_queue CONTENT { record: $id, value: $value, written_at: $written_at };
+	COMMIT;
+";
+
+/// Applies a remote change to the embedded database, discarding any queued
+/// write to the same record
+const APPLY_QUERY: &str = "
+	BEGIN;
+	IF $value = NONE { DELETE $id } ELSE { UPSERT $id CONTENT $value };
+	DELETE _replica_queue WHERE record = $id;
+	COMMIT;
+";
+
+/// Uploads a queued write to the server
+const UPLOAD_QUERY: &str = "IF $value = NONE { DELETE $id } ELSE { UPSERT $id CONTENT $value }";
+
+/// Removes an uploaded write from the queue, unless the record has been
+/// written again since
+const DEQUEUE_QUERY: &str = "DELETE _replica_queue WHERE record = $id AND written_at = $written_at";
+
+/// Returns the queued writes, oldest first
+const QUEUE_QUERY: &str =
+	"SELECT record, value, written_at FROM _replica_queue ORDER BY written_at";
+
+/// Returns all the records of a table
+const SNAPSHOT_QUERY: &str = "SELECT * FROM type::table($table)";
+
+/// Returns the versionstamp of the last remote change which was applied, and
+/// the tables which have been copied from the server
+const CURSOR_QUERY: &str = "RETURN _replica_cursor:changefeed.{ versionstamp, tables }";
+
+/// Stores the versionstamp of the last remote change which was applied, and
+/// the tables which have been copied from the server
+const SAVE_CURSOR_QUERY: &str =
+	"UPSERT _replica_cursor:changefeed SET versionstamp = $versionstamp, tables = $tables";
+
+/// The smallest number of changefeed entries read from the server in a
+/// single request
+const MIN_BATCH_SIZE: u32 = 10;
+
+/// The largest number of changefeed entries the server returns in a single
+/// request
+const MAX_BATCH_SIZE: u32 = 1000;
+
+/// The outcome of a single [`Replica::sync`]
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct SyncReport {
+	/// The number of remote changes which were applied to the embedded
+	/// database
+	pub pulled: usize,
+	/// The number of local writes which were uploaded to the server
+	pub pushed: usize,
+	/// The number of remote changes which conflicted with a queued local write
+	pub conflicts: usize,
+}
+
+/// A changed record, and its new version or `None` if it was deleted
+type Change = (CoreRecordId, Option<CoreValue>);
+
+/// The position of the replica in the remote changefeed
+#[derive(Debug, Default)]
+struct Cursor {
+	/// The versionstamp of the last remote change which was applied
+	versionstamp: Option<VersionStamp>,
+	/// The tables which have been copied from the server
+	tables: Vec<String>,
+}
+
+/// A local write which is waiting to be uploaded
+#[derive(Debug)]
+struct Pending {
+	record: CoreRecordId,
+	/// The new version of the record, or `None` if it was deleted
+	value: Option<CoreValue>,
+	written_at: CoreValue,
+}
+
+/// An embedded database which is kept in sync with a remote server
+///
+/// See the [module documentation](self) for more details.
+#[derive(Debug)]
+pub struct Replica<L: Connection, R: Connection> {
+	local: Surreal<L>,
+	remote: Surreal<R>,
+	tables: Vec<String>,
+	resolution: Resolution,
+	timestamp_field: String,
+	batch_size: u32,
+}
+
+impl<L, R> Replica<L, R>
+where
+	L: Connection,
+	R: Connection,
+{
+	/// Creates a replica of a remote database
+	///
+	/// Both clients must already be connected, and have a namespace and a
+	/// database selected.
+	pub fn new(local: Surreal<L>, remote: Surreal<R>) -> Self {
+		Self {
+			local,
+			remote,
+			tables: Vec::new(),
+			resolution: Resolution::default(),
+			timestamp_field: "updated_at".to_owned(),
+			batch_size: 100,
+		}
+	}
+
+	/// Adds a table to replicate
+	pub fn table(mut self, table: impl Into<String>) -> Self {
+		let table = table.into();
+		if !self.tables.contains(&table) {
+			self.tables.push(table);
+		}
+		self
+	}
+
+	/// Sets how conflicts between local writes and remote changes are resolved
+	///
+	/// Defaults to [`Resolution::LastWriterWins`].
+	pub fn resolution(mut self, resolution: Resolution) -> Self {
+		self.resolution = resolution;
+		self
+	}
+
+	/// Sets the field which holds the time a record was last written
+	///
+	/// Defaults to `updated_at`.
+	pub fn timestamp_field(mut self, field: impl Into<String>) -> Self {
+		self.timestamp_field = field.into();
+		self
+	}
+
+	/// Sets the maximum number of changefeed entries read from the server in a
+	/// single request
+	///
+	/// A transaction has one changefeed entry for each table it changed.
+	/// Defaults to `100`, must be at least `10`, and is limited to `1000` by
+	/// the server.
+	pub fn batch_size(mut self, size: u32) -> Self {
+		self.batch_size = size.clamp(MIN_BATCH_SIZE, MAX_BATCH_SIZE);
+		self
+	}
+
+	/// Returns the client for the embedded database, for reading the
+	/// replicated data
+	pub fn local(&self) -> &Surreal<L> {
+		&self.local
+	}
+
+	/// Returns the client for the remote server
+	pub fn remote(&self) -> &Surreal<R> {
+		&self.remote
+	}
+
+	/// Creates or replaces a record, and queues the write for upload
+	///
+	/// The content must be an object. The timestamp field of the record is set
+	/// to the current time.
+	pub async fn upsert(&self, id: RecordId, content: impl Serialize + 'static) -> Result<()> {
+		let id = id.into_inner();
+		self.check_table(&id)?;
+		let CoreValue::Object(mut content) = api::value::to_core_value(content)? else {
+			return Err(Error::ReplicaContentNotObject.into());
+		};
+		let now = CoreValue::Datetime(Datetime::now());
+		content.insert(self.timestamp_field.clone(), now.clone());
+		self.write(&id, Some(CoreValue::Object(content)), now).await
+	}
+
+	/// Deletes a record, and queues the delete for upload
+	pub async fn delete(&self, id: RecordId) -> Result<()> {
+		let id = id.into_inner();
+		self.check_table(&id)?;
+		let now = CoreValue::Datetime(Datetime::now());
+		self.write(&id, None, now).await
+	}
+
+	/// Returns the number of local writes which are waiting to be uploaded
+	pub async fn pending(&self) -> Result<usize> {
+		Ok(self.queue().await?.len())
+	}
+
+	/// Applies the remote changes to the embedded database, and then uploads
+	/// the queued local writes
+	///
+	/// If the server can't be reached, the error is returned, and the queued
+	/// writes are kept for the next sync.
+	pub async fn sync(&self) -> Result<SyncReport> {
+		let mut report = SyncReport::default();
+		self.pull(&mut report).await?;
+		self.push(&mut report).await?;
+		Ok(report)
+	}
+
+	fn check_table(&self, id: &CoreRecordId) -> Result<()> {
+		if !self.tables.contains(&id.table) {
+			return Err(Error::TableNotReplicated(id.table.clone()).into());
+		}
+		Ok(())
+	}
+
+	async fn write(
+		&self,
+		id: &CoreRecordId,
+		value: Option<CoreValue>,
+		written_at: CoreValue,
+	) -> Result<()> {
+		let vars = bindings([
+			("id", id.clone().into()),
+			("value", value.unwrap_or_default()),
+			("written_at", written_at),
+		]);
+		self.local.query(WRITE_QUERY).bind(vars).await?.check()?;
+		Ok(())
+	}
+
+	async fn apply(&self, id: &CoreRecordId, value: Option<CoreValue>) -> Result<()> {
+		let vars = bindings([("id", id.clone().into()), ("value", value.unwrap_or_default())]);
+		self.local.query(APPLY_QUERY).bind(vars).await?.check()?;
+		Ok(())
+	}
+
+	async fn queue(&self) -> Result<Vec<Pending>> {
+		let mut response = self.local.query(QUEUE_QUERY).await?;
+		let CoreValue::Array(entries) = response.take::<Value>(0)?.into_inner() else {
+			return Err(Error::InvalidResponse("expected the queued writes".to_owned()).into());
+		};
+		entries.into_iter().map(Pending::from_value).collect()
+	}
+
+	async fn cursor(&self) -> Result<Cursor> {
+		let mut response = self.local.query(CURSOR_QUERY).await?;
+		let CoreValue::Object(mut cursor) = response.take::<Value>(0)?.into_inner() else {
+			return Ok(Cursor::default());
+		};
+		let versionstamp = match cursor.remove("versionstamp") {
+			Some(CoreValue::Number(Number::Int(versionstamp))) => {
+				Some(versionstamp_from_int(versionstamp)?)
+			}
+			_ => None,
+		};
+		let tables = match cursor.remove("tables") {
+			Some(CoreValue::Array(tables)) => tables
+				.into_iter()
+				.filter_map(|table| match table {
+					CoreValue::String(table) => Some(table),
+					_ => None,
+				})
+				.collect(),
+			_ => Vec::new(),
+		};
+		Ok(Cursor {
+			versionstamp,
+			tables,
+		})
+	}
+
+	async fn save_cursor(&self, cursor: &Cursor) -> Result<()> {
+		let tables = cursor.tables.iter().map(|table| CoreValue::from(table.as_str())).collect();
+		let vars = bindings([
+			(
+				"versionstamp",
+				cursor.versionstamp.map(|x| CoreValue::from(x.into_u128())).unwrap_or_default(),
+			),
+			("tables", CoreValue::Array(tables)),
+		]);
+		self.local.query(SAVE_CURSOR_QUERY).bind(vars).await?.check()?;
+		Ok(())
+	}
+
+	/// Reads up to `limit` changefeed entries from the remote changefeed,
+	/// starting with the change at the versionstamp
+	async fn changes(
+		&self,
+		since: Option<VersionStamp>,
+		limit: u32,
+	) -> Result<Vec<(VersionStamp, Vec<Change>)>> {
+		let show = ShowStatement {
+			table: None,
+			since: ShowSince::Versionstamp(since.map_or(0, VersionStamp::into_u64_lossy)),
+			limit: Some(limit),
+		};
+		let mut response = self
+			.remote
+			.query(Ast {
+				expressions: vec![TopLevelExpr::Show(show)],
+			})
+			.await?;
+		let CoreValue::Array(changesets) = response.take::<Value>(0)?.into_inner() else {
+			return Err(Error::InvalidResponse("expected a list of changes".to_owned()).into());
+		};
+		changesets.into_iter().map(parse_changeset).collect()
+	}
+
+	/// Reads a batch of complete changes from the remote changefeed, starting
+	/// with the change at the versionstamp
+	///
+	/// The server limits the number of changefeed entries in a batch, and a
+	/// change has one entry for each table the transaction changed, so the
+	/// last change of a full batch might be missing some of its tables. A
+	/// change is only returned once it is known to be complete: when a later
+	/// change follows it, or when a larger batch contains nothing more.
+	async fn batch(&self, since: Option<VersionStamp>) -> Result<Vec<(VersionStamp, Vec<Change>)>> {
+		let mut limit = self.batch_size;
+		let mut changesets = self.changes(since, limit).await?;
+		loop {
+			let new = changesets.iter().filter(|(x, _)| since.is_none_or(|since| *x > since));
+			if new.count() > 1 {
+				// The last change is read again by the next batch
+				changesets.pop();
+				return Ok(changesets);
+			}
+			if limit >= MAX_BATCH_SIZE {
+				return Ok(changesets);
+			}
+			limit = limit.saturating_mul(2).min(MAX_BATCH_SIZE);
+			let larger = self.changes(since, limit).await?;
+			if larger == changesets {
+				return Ok(changesets);
+			}
+			changesets = larger;
+		}
+	}
+
+	/// Returns the versionstamp of the newest change in the remote changefeed
+	async fn latest(&self) -> Result<Option<VersionStamp>> {
+		let mut latest = None;
+		loop {
+			let changesets = self.batch(latest).await?;
+			match changesets.last() {
+				Some((versionstamp, _)) if Some(*versionstamp) != latest => {
+					latest = Some(*versionstamp);
+				}
+				_ => return Ok(latest),
+			}
+		}
+	}
+
+	/// Copies the current records of the tables from the server, for records
+	/// which are older than the remote changefeed
+	#[expect(clippy::mutable_key_type)]
+	async fn snapshot(
+		&self,
+		tables: &[String],
+		queue: &mut HashMap<CoreRecordId, Pending>,
+		report: &mut SyncReport,
+	) -> Result<()> {
+		for table in tables {
+			let vars = bindings([("table", CoreValue::from(table.as_str()))]);
+			let mut local: HashMap<_, _> =
+				records(self.local.query(SNAPSHOT_QUERY).bind(vars.clone()).await?.take(0)?)?
+					.collect();
+			let remote = records(self.remote.query(SNAPSHOT_QUERY).bind(vars).await?.take(0)?)?;
+			for (id, record) in remote {
+				if local.remove(&id).as_ref() == Some(&record) {
+					continue;
+				}
+				self.merge(queue, report, id, Some(record)).await?;
+			}
+			// Records which only exist locally were deleted on the server,
+			// unless they were written locally
+			for id in local.into_keys() {
+				if !queue.contains_key(&id) {
+					self.merge(queue, report, id, None).await?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	async fn pull(&self, report: &mut SyncReport) -> Result<()> {
+		#[expect(clippy::mutable_key_type)]
+		let mut queue: HashMap<_, _> =
+			self.queue().await?.into_iter().map(|x| (x.record.clone(), x)).collect();
+		let mut cursor = self.cursor().await?;
+		// Copy the tables which have not been replicated before, as the
+		// records may be older than the remote changefeed. The changes from
+		// the newest change onwards are applied on top of the copy.
+		let missing: Vec<_> =
+			self.tables.iter().filter(|x| !cursor.tables.contains(x)).cloned().collect();
+		if !missing.is_empty() {
+			if cursor.versionstamp.is_none() {
+				cursor.versionstamp = self.latest().await?;
+			}
+			self.snapshot(&missing, &mut queue, report).await?;
+			cursor.tables.extend(missing);
+			self.save_cursor(&cursor).await?;
+		}
+		loop {
+			let changesets = self.batch(cursor.versionstamp).await?;
+			// The changes since the cursor include the change at the cursor,
+			// unless it has been removed from the changefeed. The changes
+			// which were removed along with it can't be read anymore, so the
+			// tables are copied from the server again.
+			if let Some(versionstamp) = cursor.versionstamp {
+				if changesets.first().map(|x| x.0) != Some(versionstamp) {
+					cursor.versionstamp = self.latest().await?;
+					self.snapshot(&self.tables, &mut queue, report).await?;
+					self.save_cursor(&cursor).await?;
+					continue;
+				}
+			}
+			let mut latest = cursor.versionstamp;
+			for (versionstamp, changes) in changesets {
+				if latest.is_some_and(|x| versionstamp <= x) {
+					continue;
+				}
+				for (id, remote) in changes {
+					if self.tables.contains(&id.table) {
+						self.merge(&mut queue, report, id, remote).await?;
+					}
+				}
+				latest = Some(versionstamp);
+			}
+			// Stop once there are no more changes after the cursor
+			if latest == cursor.versionstamp {
+				return Ok(());
+			}
+			cursor.versionstamp = latest;
+			self.save_cursor(&cursor).await?;
+		}
+	}
+
+	/// Applies a remote change to the embedded database, resolving any
+	/// conflict with a queued local write to the same record
+	#[expect(clippy::mutable_key_type)]
+	async fn merge(
+		&self,
+		queue: &mut HashMap<CoreRecordId, Pending>,
+		report: &mut SyncReport,
+		id: CoreRecordId,
+		remote: Option<CoreValue>,
+	) -> Result<()> {
+		report.pulled += 1;
+		let Some(pending) = queue.remove(&id) else {
+			return self.apply(&id, remote).await;
+		};
+		report.conflicts += 1;
+		let resolved = self.resolution.resolve(
+			&self.timestamp_field,
+			&id,
+			pending.value,
+			&pending.written_at,
+			remote.clone(),
+		);
+		if resolved == remote {
+			self.apply(&id, remote).await
+		} else {
+			self.write(&id, resolved.clone(), pending.written_at.clone()).await?;
+			queue.insert(
+				id,
+				Pending {
+					value: resolved,
+					..pending
+				},
+			);
+			Ok(())
+		}
+	}
+
+	async fn push(&self, report: &mut SyncReport) -> Result<()> {
+		for pending in self.queue().await? {
+			let vars = bindings([
+				("id", pending.record.clone().into()),
+				("value", pending.value.unwrap_or_default()),
+			]);
+			self.remote.query(UPLOAD_QUERY).bind(vars).await?.check()?;
+			let vars =
+				bindings([("id", pending.record.into()), ("written_at", pending.written_at)]);
+			self.local.query(DEQUEUE_QUERY).bind(vars).await?.check()?;
+			report.pushed += 1;
+		}
+		Ok(())
+	}
+}
+
+impl Pending {
+	fn from_value(value: CoreValue) -> Result<Self> {
+		let CoreValue::Object(mut entry) = value else {
+			return Err(Error::InvalidResponse("expected a queued write".to_owned()).into());
+		};
+		let record = record_id(entry.get("record"))?;
+		let value = match entry.remove("value") {
+			None | Some(CoreValue::None) => None,
+			value => value,
+		};
+		let written_at = entry.remove("written_at").unwrap_or_default();
+		Ok(Self {
+			record,
+			value,
+			written_at,
+		})
+	}
+}
+
+/// Parses a change set from the changefeed into its versionstamp, and the
+/// records which were changed
+fn parse_changeset(value: CoreValue) -> Result<(VersionStamp, Vec<Change>)> {
+	let invalid = || Error::InvalidResponse("expected a changefeed entry".to_owned());
+	let CoreValue::Object(mut changeset) = value else {
+		return Err(invalid().into());
+	};
+	let Some(CoreValue::Number(Number::Int(versionstamp))) = changeset.remove("versionstamp")
+	else {
+		return Err(invalid().into());
+	};
+	let Some(CoreValue::Array(changes)) = changeset.remove("changes") else {
+		return Err(invalid().into());
+	};
+	let mut records = Vec::new();
+	for change in changes {
+		let CoreValue::Object(mut change) = change else {
+			return Err(invalid().into());
+		};
+		// Changes stored with their diff hold the whole record in `current`
+		if let Some(record) = change.remove("current").or_else(|| change.remove("update")) {
+			let CoreValue::Object(object) = &record else {
+				return Err(invalid().into());
+			};
+			records.push((record_id(object.get("id"))?, Some(record)));
+		} else if let Some(CoreValue::Object(deleted)) = change.remove("delete") {
+			records.push((record_id(deleted.get("id"))?, None));
+		}
+		// Table definitions are not replicated
+	}
+	Ok((versionstamp_from_int(versionstamp)?, records))
+}
+
+/// Parses the records of a table, keyed by their id
+fn records(value: Value) -> Result<impl Iterator<Item = (CoreRecordId, CoreValue)>> {
+	let CoreValue::Array(records) = value.into_inner() else {
+		return Err(Error::InvalidResponse("expected a list of records".to_owned()).into());
+	};
+	records
+		.into_iter()
+		.map(|record| {
+			let CoreValue::Object(object) = &record else {
+				return Err(Error::InvalidResponse("expected a record".to_owned()).into());
+			};
+			Ok((record_id(object.get("id"))?, record))
+		})
+		.collect::<Result<Vec<_>>>()
+		.map(Vec::into_iter)
+}
+
+/// Parses a versionstamp as returned by the changefeed
+fn versionstamp_from_int(versionstamp: i64) -> Result<VersionStamp> {
+	u128::try_from(versionstamp)
+		.ok()
+		.and_then(|x| VersionStamp::try_from_u128(x).ok())
+		.ok_or_else(|| Error::InvalidResponse("expected a versionstamp".to_owned()).into())
+}
+
+fn record_id(value: Option<&CoreValue>) -> Result<CoreRecordId> {
+	match value {
+		Some(CoreValue::RecordId(id)) => Ok(id.clone()),
+		_ => Err(Error::InvalidResponse("expected a record id".to_owned()).into()),
+	}
+}
+
+fn bindings<const N: usize>(vars: [(&str, CoreValue); N]) -> CoreValue {
+	CoreValue::Object(Object(
+		vars.into_iter().map(|(key, value)| (key.to_owned(), value)).collect(),
+	))
+}
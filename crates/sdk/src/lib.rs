@@ -41,7 +41,7 @@ pub mod error {
 pub use crate::api::headers;
 #[doc(inline)]
 pub use crate::api::{
-	Connect, Connection, Response, Surreal, engine, method, opt,
+	Connect, Connection, Response, Surreal, engine, method, opt, replica,
 	value::{
 		self, Action, Bytes, Datetime, Notification, Number, Object, RecordId, RecordIdKey, Value,
 	},
@@ -1,4 +1,4 @@
-#![cfg(feature = "kv-surrealkv")]
+#![cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
 
 use serde::{Deserialize, Serialize};
 use surrealdb_core::cnf::EXPORT_BATCH_SIZE;
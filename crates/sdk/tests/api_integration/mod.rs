@@ -301,12 +301,15 @@ mod http {
 
 #[cfg(feature = "kv-mem")]
 mod mem {
+	use serde::Deserialize;
+	use serde_json::json;
 	use surrealdb::engine::local::{Db, Mem};
 	use surrealdb::error::Db as DbError;
 	use surrealdb::opt::auth::Root;
 	use surrealdb::opt::capabilities::{Capabilities, ExperimentalFeature};
 	use surrealdb::opt::{Config, Resource};
-	use surrealdb::{RecordIdKey, Surreal};
+	use surrealdb::replica::Replica;
+	use surrealdb::{RecordId, RecordIdKey, Surreal};
 	use surrealdb_core::iam;
 	use tokio::sync::{Semaphore, SemaphorePermit};
 
@@ -413,6 +416,134 @@ mod mem {
 		db.query(surql).await.unwrap().check().unwrap();
 	}
 
+	#[test_log::test(tokio::test)]
+	async fn replica_sync() {
+		#[derive(Debug, Deserialize)]
+		struct Task {
+			title: String,
+		}
+
+		let local = Surreal::new::<Mem>(()).await.unwrap();
+		local.use_ns("test").use_db("test").await.unwrap();
+		let remote = Surreal::new::<Mem>(()).await.unwrap();
+		remote.use_ns("test").use_db("test").await.unwrap();
+		let sql = "
+			DEFINE TABLE task CHANGEFEED 1h;
+			CREATE task:remote SET title = 'remote';
+		";
+		remote.query(sql).await.unwrap().check().unwrap();
+		let replica = Replica::new(local, remote).table("task");
+		// Writes are applied locally before they are uploaded
+		let id = RecordId::from_table_key("task", "local");
+		replica.upsert(id.clone(), json!({ "title": "local" })).await.unwrap();
+		assert_eq!(replica.pending().await.unwrap(), 1);
+		let task: Option<Task> = replica.remote().select(&id).await.unwrap();
+		assert!(task.is_none());
+		// Remote changes are pulled, and local writes are pushed
+		let report = replica.sync().await.unwrap();
+		assert_eq!((report.pulled, report.pushed, report.conflicts), (1, 1, 0));
+		assert_eq!(replica.pending().await.unwrap(), 0);
+		let task: Option<Task> = replica.local().select(("task", "remote")).await.unwrap();
+		assert_eq!(task.unwrap().title, "remote");
+		let task: Option<Task> = replica.remote().select(&id).await.unwrap();
+		assert_eq!(task.unwrap().title, "local");
+		// The last writer wins a conflict
+		let remote_id = RecordId::from_table_key("task", "remote");
+		replica.upsert(remote_id.clone(), json!({ "title": "local edit" })).await.unwrap();
+		let sql = "UPDATE task:remote SET title = 'remote edit', updated_at = time::now()";
+		replica.remote().query(sql).await.unwrap().check().unwrap();
+		let report = replica.sync().await.unwrap();
+		assert_eq!(report.conflicts, 1);
+		assert_eq!(replica.pending().await.unwrap(), 0);
+		let task: Option<Task> = replica.local().select(&remote_id).await.unwrap();
+		assert_eq!(task.unwrap().title, "remote edit");
+		// Deletes are replicated
+		replica.delete(id.clone()).await.unwrap();
+		replica.sync().await.unwrap();
+		let task: Option<Task> = replica.remote().select(&id).await.unwrap();
+		assert!(task.is_none());
+		// Only replicated tables can be written to
+		let id = RecordId::from_table_key("person", "tobie");
+		replica.upsert(id, json!({ "name": "Tobie" })).await.unwrap_err();
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn replica_snapshot() {
+		#[derive(Debug, Deserialize)]
+		struct Task {
+			title: String,
+		}
+
+		let local = Surreal::new::<Mem>(()).await.unwrap();
+		local.use_ns("test").use_db("test").await.unwrap();
+		let remote = Surreal::new::<Mem>(()).await.unwrap();
+		remote.use_ns("test").use_db("test").await.unwrap();
+		let sql = "
+			DEFINE TABLE task;
+			CREATE task:old SET title = 'old';
+			DEFINE TABLE OVERWRITE task CHANGEFEED 1h;
+			CREATE task:new SET title = 'new';
+		";
+		remote.query(sql).await.unwrap().check().unwrap();
+		let replica = Replica::new(local, remote).table("task");
+		// Records from before the changefeed are copied from the server
+		let report = replica.sync().await.unwrap();
+		assert_eq!(report.pulled, 2);
+		let task: Option<Task> = replica.local().select(("task", "old")).await.unwrap();
+		assert_eq!(task.unwrap().title, "old");
+		let task: Option<Task> = replica.local().select(("task", "new")).await.unwrap();
+		assert_eq!(task.unwrap().title, "new");
+		// Nothing is pulled again once the replica is up to date
+		let report = replica.sync().await.unwrap();
+		assert_eq!(report.pulled, 0);
+		// A cursor which is no longer in the changefeed copies the tables again
+		let sql = "UPSERT _replica_cursor:changefeed SET versionstamp = 1; CREATE task:stale;";
+		replica.local().query(sql).await.unwrap().check().unwrap();
+		let sql = "UPDATE task:old SET title = 'old edit'; DELETE task:new;";
+		replica.remote().query(sql).await.unwrap().check().unwrap();
+		let report = replica.sync().await.unwrap();
+		assert_eq!(report.pulled, 3);
+		let task: Option<Task> = replica.local().select(("task", "old")).await.unwrap();
+		assert_eq!(task.unwrap().title, "old edit");
+		let task: Option<Task> = replica.local().select(("task", "new")).await.unwrap();
+		assert!(task.is_none());
+		let task: Option<Task> = replica.local().select(("task", "stale")).await.unwrap();
+		assert!(task.is_none());
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn replica_batches() {
+		let local = Surreal::new::<Mem>(()).await.unwrap();
+		local.use_ns("test").use_db("test").await.unwrap();
+		let remote = Surreal::new::<Mem>(()).await.unwrap();
+		remote.use_ns("test").use_db("test").await.unwrap();
+		let sql = "
+			DEFINE TABLE task CHANGEFEED 1h;
+			DEFINE TABLE note CHANGEFEED 1h;
+		";
+		remote.query(sql).await.unwrap().check().unwrap();
+		// The smallest batch size is raised so that the replica makes progress
+		let replica = Replica::new(local, remote).table("task").table("note").batch_size(1);
+		replica.sync().await.unwrap();
+		// Transactions which change both tables have two changefeed entries,
+		// so some of them are split across the boundary between batches
+		for i in 0..20 {
+			let sql = format!(
+				"CREATE task:single{i}; BEGIN; CREATE task:both{i}; CREATE note:both{i}; COMMIT;"
+			);
+			replica.remote().query(sql).await.unwrap().check().unwrap();
+		}
+		let report = replica.sync().await.unwrap();
+		assert_eq!(report.pulled, 60);
+		let tasks: Vec<ApiRecordId> = replica.local().select("task").await.unwrap();
+		assert_eq!(tasks.len(), 40);
+		let notes: Vec<ApiRecordId> = replica.local().select("note").await.unwrap();
+		assert_eq!(notes.len(), 20);
+		// Nothing is pulled again once the replica is up to date
+		let report = replica.sync().await.unwrap();
+		assert_eq!(report.pulled, 0);
+	}
+
 	include_tests!(new_db => basic, serialisation, live, backup);
 }
 
@@ -463,6 +594,55 @@ mod rocksdb {
 	include_tests!(new_db => basic, serialisation, live, backup);
 }
 
+#[cfg(feature = "kv-rocksdb")]
+mod rocksdb_versioned {
+	use surrealdb::Surreal;
+	use surrealdb::engine::local::{Db, RocksDb};
+	use surrealdb::opt::Config;
+	use surrealdb::opt::auth::Root;
+	use surrealdb::opt::capabilities::Capabilities;
+	use tokio::sync::{Semaphore, SemaphorePermit};
+	use ulid::Ulid;
+
+	use super::{ROOT_PASS, ROOT_USER, TEMP_DIR};
+
+	static PERMITS: Semaphore = Semaphore::const_new(1);
+
+	async fn new_db() -> (SemaphorePermit<'static>, Surreal<Db>) {
+		let permit = PERMITS.acquire().await.unwrap();
+		let path = TEMP_DIR.join(Ulid::new().to_string());
+		let root = Root {
+			username: ROOT_USER,
+			password: ROOT_PASS,
+		};
+		let config = Config::new().user(root).capabilities(Capabilities::all());
+		let db = Surreal::new::<RocksDb>((path, config)).versioned().await.unwrap();
+		db.signin(root).await.unwrap();
+		(permit, db)
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn any_engine_can_connect() {
+		let db_dir = Ulid::new().to_string();
+		// Create a database directory using an absolute path
+		surrealdb::engine::any::connect(format!(
+			"rocksdb+versioned://{}",
+			TEMP_DIR.join("absolute").join(&db_dir).display()
+		))
+		.await
+		.unwrap();
+		// Switch to the temporary directory, if possible, to test relative paths
+		if std::env::set_current_dir(&*TEMP_DIR).is_ok() {
+			// Create a database directory using a relative path
+			surrealdb::engine::any::connect(format!("rocksdb+versioned://relative/{db_dir}"))
+				.await
+				.unwrap();
+		}
+	}
+
+	include_tests!(new_db => basic, serialisation, version, live, backup, backup_version);
+}
+
 #[cfg(feature = "kv-tikv")]
 mod tikv {
 	use surrealdb::Surreal;
@@ -1,4 +1,4 @@
-#![cfg(feature = "kv-surrealkv")]
+#![cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
 
 use surrealdb::Value;
 use ulid::Ulid;
@@ -0,0 +1,244 @@
+use proc_macro2::Span;
+use syn::meta::ParseNestedMeta;
+use syn::{Attribute, Error, ExprPath, LitStr, Path, Result};
+
+/// How the variants of an enum are represented as values.
+pub(crate) enum Tagging {
+	/// `{ "Variant": value }`, or `"Variant"` for unit variants.
+	External,
+	/// `{ tag: "Variant", ...fields }`, for unit and struct variants only.
+	Internal(String),
+	/// `{ tag: "Variant", content: value }`.
+	Adjacent(String, String),
+	/// The value of the variant itself, without any tag.
+	Untagged,
+}
+
+/// The attributes which can be set on a struct or enum.
+#[derive(Default)]
+pub(crate) struct ContainerAttrs {
+	/// Renames all fields, or all enum variants, with a casing rule.
+	pub rename_all: Option<RenameRule>,
+	/// The field which holds the name of the variant.
+	pub tag: Option<String>,
+	/// The field which holds the value of the variant.
+	pub content: Option<String>,
+	/// Whether the variants are represented without a tag.
+	pub untagged: bool,
+	/// The table in which the records are stored.
+	pub table: Option<String>,
+	/// Whether the table allows fields which have not been defined.
+	pub schemaless: bool,
+}
+
+impl ContainerAttrs {
+	pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+		let mut out = Self::default();
+		for attr in attrs.iter().filter(|x| x.path().is_ident("surreal")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("rename_all") {
+					let lit: LitStr = meta.value()?.parse()?;
+					out.rename_all = Some(RenameRule::parse(&lit)?);
+				} else if meta.path.is_ident("tag") {
+					out.tag = Some(string(&meta)?);
+				} else if meta.path.is_ident("content") {
+					out.content = Some(string(&meta)?);
+				} else if meta.path.is_ident("untagged") {
+					out.untagged = true;
+				} else if meta.path.is_ident("table") {
+					out.table = Some(string(&meta)?);
+				} else if meta.path.is_ident("schemaless") {
+					out.schemaless = true;
+				} else {
+					return Err(meta.error("unsupported container attribute"));
+				}
+				Ok(())
+			})?;
+		}
+		Ok(out)
+	}
+
+	/// Returns the representation of the variants of an enum.
+	pub fn tagging(&self, span: Span) -> Result<Tagging> {
+		match (&self.tag, &self.content, self.untagged) {
+			(None, None, false) => Ok(Tagging::External),
+			(Some(tag), None, false) => Ok(Tagging::Internal(tag.clone())),
+			(Some(tag), Some(content), false) => {
+				Ok(Tagging::Adjacent(tag.clone(), content.clone()))
+			}
+			(None, None, true) => Ok(Tagging::Untagged),
+			(None, Some(_), false) => Err(Error::new(span, "`content` requires a `tag`")),
+			_ => Err(Error::new(span, "`untagged` can not be combined with `tag` or `content`")),
+		}
+	}
+}
+
+/// The value which is used for a field which is missing or skipped.
+pub(crate) enum DefaultAttr {
+	/// Uses `Default::default()`.
+	Trait,
+	/// Calls the specified function.
+	Path(ExprPath),
+}
+
+/// The attributes which can be set on a field.
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+	/// The name of the field in the object.
+	pub rename: Option<String>,
+	/// Whether the field is left out of the object.
+	pub skip: bool,
+	/// The value which is used when the field is missing.
+	pub default: Option<DefaultAttr>,
+	/// Whether the fields of this field are merged into the parent object.
+	pub flatten: bool,
+	/// A module which converts the field instead of `SurrealValue`.
+	pub with: Option<Path>,
+}
+
+impl FieldAttrs {
+	pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+		let mut out = Self::default();
+		for attr in attrs.iter().filter(|x| x.path().is_ident("surreal")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("rename") {
+					out.rename = Some(string(&meta)?);
+				} else if meta.path.is_ident("skip") {
+					out.skip = true;
+				} else if meta.path.is_ident("default") {
+					out.default = Some(if meta.input.peek(syn::Token![=]) {
+						let lit: LitStr = meta.value()?.parse()?;
+						DefaultAttr::Path(lit.parse()?)
+					} else {
+						DefaultAttr::Trait
+					});
+				} else if meta.path.is_ident("flatten") {
+					out.flatten = true;
+				} else if meta.path.is_ident("with") {
+					let lit: LitStr = meta.value()?.parse()?;
+					out.with = Some(lit.parse()?);
+				} else {
+					return Err(meta.error("unsupported field attribute"));
+				}
+				Ok(())
+			})?;
+		}
+		Ok(out)
+	}
+}
+
+/// The attributes which can be set on an enum variant.
+#[derive(Default)]
+pub(crate) struct VariantAttrs {
+	/// The name of the variant in the value.
+	pub rename: Option<String>,
+}
+
+impl VariantAttrs {
+	pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+		let mut out = Self::default();
+		for attr in attrs.iter().filter(|x| x.path().is_ident("surreal")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("rename") {
+					out.rename = Some(string(&meta)?);
+				} else {
+					return Err(meta.error("unsupported variant attribute"));
+				}
+				Ok(())
+			})?;
+		}
+		Ok(out)
+	}
+}
+
+/// A casing rule which is applied to the names of fields or variants.
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+	Lower,
+	Upper,
+	Pascal,
+	Camel,
+	Snake,
+	ScreamingSnake,
+	Kebab,
+	ScreamingKebab,
+}
+
+impl RenameRule {
+	fn parse(lit: &LitStr) -> Result<Self> {
+		match lit.value().as_str() {
+			"lowercase" => Ok(Self::Lower),
+			"UPPERCASE" => Ok(Self::Upper),
+			"PascalCase" => Ok(Self::Pascal),
+			"camelCase" => Ok(Self::Camel),
+			"snake_case" => Ok(Self::Snake),
+			"SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnake),
+			"kebab-case" => Ok(Self::Kebab),
+			"SCREAMING-KEBAB-CASE" => Ok(Self::ScreamingKebab),
+			_ => Err(Error::new(lit.span(), "unknown casing rule")),
+		}
+	}
+
+	/// Applies the rule to a field name in `snake_case`, or a variant name in
+	/// `PascalCase`.
+	pub fn apply(self, name: &str) -> String {
+		let words = words(name);
+		match self {
+			Self::Lower => name.to_lowercase(),
+			Self::Upper => name.to_uppercase(),
+			Self::Pascal => words.iter().map(|x| capitalize(x)).collect(),
+			Self::Camel => {
+				let mut out = String::new();
+				for (i, word) in words.iter().enumerate() {
+					match i {
+						0 => out.push_str(word),
+						_ => out.push_str(&capitalize(word)),
+					}
+				}
+				out
+			}
+			Self::Snake => words.join("_"),
+			Self::ScreamingSnake => words.join("_").to_uppercase(),
+			Self::Kebab => words.join("-"),
+			Self::ScreamingKebab => words.join("-").to_uppercase(),
+		}
+	}
+}
+
+/// Splits a name into lowercase words, at underscores and at the start of
+/// each capitalised word.
+fn words(name: &str) -> Vec<String> {
+	let mut words = Vec::new();
+	let mut word = String::new();
+	let mut upper = false;
+	for c in name.chars() {
+		if c == '_' || c == '-' {
+			if !word.is_empty() {
+				words.push(std::mem::take(&mut word));
+			}
+			continue;
+		}
+		if c.is_uppercase() && !upper && !word.is_empty() {
+			words.push(std::mem::take(&mut word));
+		}
+		upper = c.is_uppercase();
+		word.extend(c.to_lowercase());
+	}
+	if !word.is_empty() {
+		words.push(word);
+	}
+	words
+}
+
+fn capitalize(word: &str) -> String {
+	let mut chars = word.chars();
+	match chars.next() {
+		Some(c) => c.to_uppercase().chain(chars).collect(),
+		None => String::new(),
+	}
+}
+
+fn string(meta: &ParseNestedMeta) -> Result<String> {
+	let lit: LitStr = meta.value()?.parse()?;
+	Ok(lit.value())
+}
@@ -1,12 +1,15 @@
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{Data, DeriveInput, Fields, Ident, Type, parse_macro_input};
+use syn::{DeriveInput, parse_macro_input};
 
-/// Derive macro for implementing the `SurrealValue` trait on structs.
+mod attr;
+mod table;
+mod value;
+
+/// Derive macro for implementing the `SurrealValue` trait on structs and enums.
 ///
-/// This macro automatically implements the `SurrealValue` trait for structs, providing
-/// seamless conversion between Rust structs and SurrealDB `Value` types. The implementation
-/// supports named fields, tuple structs, and unit structs.
+/// This macro automatically implements the `SurrealValue` trait for structs and enums, providing
+/// seamless conversion between Rust types and SurrealDB `Value` types. The implementation
+/// supports named fields, tuple structs, unit structs, and enums.
 ///
 /// # Examples
 ///
@@ -124,6 +127,75 @@ use syn::{Data, DeriveInput, Fields, Ident, Type, parse_macro_input};
 /// let restored_container = Container::<Vec<i64>>::from_value(value).unwrap();
 /// ```
 ///
+/// ## Field Attributes
+///
+/// ```compile_fail
+/// use surrealdb_types::{SurrealValue, Value};
+///
+/// #[derive(SurrealValue)]
+/// #[surreal(rename_all = "camelCase", table = "person")]
+/// struct Person {
+///     // Stored as `person:tobie`, and read back from any `person` record id
+///     id: String,
+///     // Stored as `firstName`
+///     first_name: String,
+///     // Stored as `mail` instead of `email`
+///     #[surreal(rename = "mail")]
+///     email: String,
+///     // Never stored, and set to `Default::default()` when read
+///     #[surreal(skip)]
+///     cached: Option<String>,
+///     // Set to `default_tags()` when missing, or `Default::default()` without a function
+///     #[surreal(default = "default_tags")]
+///     tags: Vec<String>,
+///     // The fields of `Audit` are stored in this object
+///     #[surreal(flatten)]
+///     audit: Audit,
+///     // Converted with `as_text::{kind_of, is_value, into_value, from_value}`
+///     #[surreal(with = "as_text")]
+///     version: u32,
+/// }
+/// ```
+///
+/// Fields of type `Option<T>` may be missing from the object, in which case they are `None`.
+///
+/// ## Enums
+///
+/// ```compile_fail
+/// use surrealdb_types::{SurrealValue, Value};
+///
+/// // Stored as `'Active'`, or `{ Banned: { reason: '...' } }`
+/// #[derive(SurrealValue)]
+/// enum Status {
+///     Active,
+///     Banned { reason: String },
+/// }
+///
+/// // Stored as `{ type: 'circle', radius: 1.5 }`
+/// #[derive(SurrealValue)]
+/// #[surreal(tag = "type", rename_all = "lowercase")]
+/// enum Shape {
+///     Circle { radius: f64 },
+///     Square { side: f64 },
+/// }
+///
+/// // Stored as `{ t: 'Text', c: '...' }`
+/// #[derive(SurrealValue)]
+/// #[surreal(tag = "t", content = "c")]
+/// enum Message {
+///     Text(String),
+///     Ping,
+/// }
+///
+/// // Stored as the value of the variant, trying each variant in order when read
+/// #[derive(SurrealValue)]
+/// #[surreal(untagged)]
+/// enum Id {
+///     Number(i64),
+///     Text(String),
+/// }
+/// ```
+///
 /// # Generated Methods
 ///
 /// The derive macro generates implementations for all four methods of the `SurrealValue` trait:
@@ -135,234 +207,41 @@ use syn::{Data, DeriveInput, Fields, Ident, Type, parse_macro_input};
 ///
 /// # Limitations
 ///
-/// - Only works with structs (not enums)
-/// - All field types must implement `SurrealValue`
+/// - All field types must implement `SurrealValue`, unless converted `with` a module
 /// - Named fields are converted to/from SurrealDB objects
 /// - Tuple structs are converted to/from SurrealDB arrays
 /// - Unit structs are converted to/from empty SurrealDB objects
-#[proc_macro_derive(SurrealValue)]
+/// - Internally tagged enums only support unit and struct variants
+#[proc_macro_derive(SurrealValue, attributes(surreal))]
 pub fn surreal_value(input: TokenStream) -> TokenStream {
 	let input = parse_macro_input!(input as DeriveInput);
-	let name = &input.ident;
-	let generics = &input.generics;
-	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-
-	// Extract fields from the struct
-	let fields = match &input.data {
-		Data::Struct(data) => &data.fields,
-		_ => panic!("SurrealValue can only be derived for structs"),
-	};
-
-	match fields {
-		Fields::Named(named_fields) => {
-			let field_names: Vec<&Ident> =
-				named_fields.named.iter().filter_map(|f| f.ident.as_ref()).collect();
-			let field_types: Vec<&Type> = named_fields.named.iter().map(|f| &f.ty).collect();
-
-			let is_value_checks: Vec<_> = field_names.iter().zip(field_types.iter()).map(|(field_name, field_type)| {
-				let field_name_str = field_name.to_string();
-				quote! {
-					obj.get(#field_name_str).map_or(false, |v| <#field_type as surrealdb_types::SurrealValue>::is_value(v))
-				}
-			}).collect();
-
-			let into_value_fields: Vec<_> = field_names
-				.iter()
-				.zip(field_types.iter())
-				.map(|(field_name, _field_type)| {
-					let field_name_str = field_name.to_string();
-					quote! {
-						(#field_name_str.to_string(), self.#field_name.into_value())
-					}
-				})
-				.collect();
-
-			let from_value_fields: Vec<_> = field_names
-				.iter()
-				.zip(field_types.iter())
-				.map(|(field_name, field_type)| {
-					let field_name_str = field_name.to_string();
-					quote! {
-						let #field_name = <#field_type as surrealdb_types::SurrealValue>::from_value(
-							obj.get(#field_name_str)
-								.ok_or_else(|| surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Missing field '{}'", Self::kind_of(), #field_name_str))?
-								.clone()
-						).map_err(|e| surrealdb_types::anyhow::anyhow!("Failed to convert to {}: {}", Self::kind_of(), e))?;
-					}
-				})
-				.collect();
-
-			let field_kinds: Vec<_> = field_names
-				.iter()
-				.zip(field_types.iter())
-				.map(|(field_name, field_type)| {
-					let field_name_str = field_name.to_string();
-					quote! {
-						(#field_name_str.to_string(), <#field_type as surrealdb_types::SurrealValue>::kind_of())
-					}
-				})
-				.collect();
-
-			let output = quote! {
-				impl #impl_generics surrealdb_types::SurrealValue for #name #ty_generics #where_clause {
-					fn kind_of() -> surrealdb_types::Kind {
-						surrealdb_types::Kind::Literal(surrealdb_types::KindLiteral::Object(std::collections::BTreeMap::from([
-							#(#field_kinds),*
-						])))
-					}
-
-					fn is_value(value: &surrealdb_types::Value) -> bool {
-						if let surrealdb_types::Value::Object(obj) = value {
-							#(#is_value_checks)&&*
-						} else {
-							false
-						}
-					}
-
-					fn into_value(self) -> surrealdb_types::Value {
-						surrealdb_types::Value::Object(surrealdb_types::Object::from(std::collections::BTreeMap::from([
-							#(#into_value_fields),*
-						])))
-					}
-
-					fn from_value(value: surrealdb_types::Value) -> surrealdb_types::anyhow::Result<Self> {
-						let surrealdb_types::Value::Object(obj) = value else {
-							return Err(surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Expected Object, got {:?}", Self::kind_of(), value.value_kind()));
-						};
-
-						#(#from_value_fields)*
-
-						Ok(Self {
-							#(#field_names),*
-						})
-					}
-				}
-			};
-
-			output.into()
-		}
-		Fields::Unnamed(unnamed_fields) => {
-			let field_types: Vec<&Type> = unnamed_fields.unnamed.iter().map(|f| &f.ty).collect();
-			let field_count = field_types.len();
-
-			let is_value_checks: Vec<_> = (0..field_count)
-				.map(|i| {
-					let field_type = &field_types[i];
-					quote! {
-						values.get(#i).map_or(false, |v| <#field_type as surrealdb_types::SurrealValue>::is_value(v))
-					}
-				})
-				.collect();
-
-			let into_value_fields: Vec<_> = (0..field_count)
-				.map(|i| {
-					let i_lit = syn::Index::from(i);
-					quote! {
-						self.#i_lit.into_value()
-					}
-				})
-				.collect();
-
-			let from_value_fields: Vec<_> = (0..field_count)
-				.map(|i| {
-					let _i_lit = syn::Index::from(i);
-					let field_type = &field_types[i];
-					let field_ident =
-						syn::Ident::new(&format!("field_{}", i), proc_macro2::Span::call_site());
-					quote! {
-						let #field_ident = <#field_type as surrealdb_types::SurrealValue>::from_value(
-							values.get(#i)
-								.ok_or_else(|| surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Missing field at index {}", Self::kind_of(), #i))?
-								.clone()
-						).map_err(|e| surrealdb_types::anyhow::anyhow!("Failed to convert to {}: {}", Self::kind_of(), e))?;
-					}
-				})
-				.collect();
-
-			let field_assignments: Vec<_> = (0..field_count)
-				.map(|i| {
-					let field_ident =
-						syn::Ident::new(&format!("field_{}", i), proc_macro2::Span::call_site());
-					quote! { #field_ident }
-				})
-				.collect();
-
-			let field_kinds: Vec<_> = field_types
-				.iter()
-				.map(|field_type| {
-					quote! {
-						<#field_type as surrealdb_types::SurrealValue>::kind_of()
-					}
-				})
-				.collect();
-
-			let output = quote! {
-				impl #impl_generics surrealdb_types::SurrealValue for #name #ty_generics #where_clause {
-					fn kind_of() -> surrealdb_types::Kind {
-						surrealdb_types::Kind::Literal(surrealdb_types::KindLiteral::Array(vec![
-							#(#field_kinds),*
-						]))
-					}
-
-					fn is_value(value: &surrealdb_types::Value) -> bool {
-						if let surrealdb_types::Value::Array(values) = value {
-							values.len() == #field_count && #(#is_value_checks)&&*
-						} else {
-							false
-						}
-					}
-
-					fn into_value(self) -> surrealdb_types::Value {
-						surrealdb_types::Value::Array(surrealdb_types::Array::from(vec![
-							#(#into_value_fields),*
-						]))
-					}
-
-					fn from_value(value: surrealdb_types::Value) -> surrealdb_types::anyhow::Result<Self> {
-						let surrealdb_types::Value::Array(values) = value else {
-							return Err(surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Expected Array, got {:?}", Self::kind_of(), value.value_kind()));
-						};
-
-						if values.len() != #field_count {
-							return Err(surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Expected Array of length {}, got {}", Self::kind_of(), #field_count, values.len()));
-						}
-
-						#(#from_value_fields)*
-
-						Ok(Self(
-							#(#field_assignments),*
-						))
-					}
-				}
-			};
-
-			output.into()
-		}
-		Fields::Unit => {
-			let output = quote! {
-				impl #impl_generics surrealdb_types::SurrealValue for #name #ty_generics #where_clause {
-					fn kind_of() -> surrealdb_types::Kind {
-						surrealdb_types::Kind::Literal(surrealdb_types::KindLiteral::Object(std::collections::BTreeMap::new()))
-					}
-
-					fn is_value(value: &surrealdb_types::Value) -> bool {
-						matches!(value, surrealdb_types::Value::Object(obj) if obj.is_empty())
-					}
-
-					fn into_value(self) -> surrealdb_types::Value {
-						surrealdb_types::Value::Object(surrealdb_types::Object::new())
-					}
-
-					fn from_value(value: surrealdb_types::Value) -> surrealdb_types::anyhow::Result<Self> {
-						match value {
-							surrealdb_types::Value::Object(obj) if obj.is_empty() => Ok(Self),
-							_ => Err(surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Expected empty Object, got {:?}", Self::kind_of(), value.value_kind())),
-						}
-					}
-				}
-			};
+	value::derive(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
 
-			output.into()
-		}
-	}
+/// Derive macro for implementing the `SurrealTable` trait on structs.
+///
+/// The table schema is generated from the `Kind` of the struct, so this is usually derived
+/// together with `SurrealValue`. The table name defaults to the name of the struct in
+/// `snake_case`, and the table is `SCHEMAFULL` unless marked as `schemaless`.
+///
+/// ```compile_fail
+/// use surrealdb_types::{SurrealTable, SurrealValue};
+///
+/// #[derive(SurrealValue, SurrealTable)]
+/// #[surreal(table = "person")]
+/// struct Person {
+///     id: String,
+///     name: String,
+///     age: Option<i64>,
+/// }
+///
+/// // DEFINE TABLE OVERWRITE person SCHEMAFULL;
+/// // DEFINE FIELD OVERWRITE age ON person TYPE none | int;
+/// // DEFINE FIELD OVERWRITE name ON person TYPE string;
+/// let schema = Person::schema();
+/// ```
+#[proc_macro_derive(SurrealTable, attributes(surreal))]
+pub fn surreal_table(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	table::derive(input).unwrap_or_else(syn::Error::into_compile_error).into()
 }
@@ -0,0 +1,28 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Result};
+
+use crate::attr::{ContainerAttrs, RenameRule};
+
+pub(crate) fn derive(input: DeriveInput) -> Result<TokenStream> {
+	let attrs = ContainerAttrs::parse(&input.attrs)?;
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+	if !matches!(&input.data, Data::Struct(data) if matches!(data.fields, Fields::Named(_))) {
+		return Err(Error::new_spanned(
+			name,
+			"SurrealTable can only be derived for structs with named fields",
+		));
+	}
+
+	let table = attrs.table.unwrap_or_else(|| RenameRule::Snake.apply(&name.to_string()));
+	let schemafull = !attrs.schemaless;
+
+	Ok(quote! {
+		impl #impl_generics surrealdb_types::SurrealTable for #name #ty_generics #where_clause {
+			const TABLE: &'static str = #table;
+			const SCHEMAFULL: bool = #schemafull;
+		}
+	})
+}
@@ -0,0 +1,752 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Data, DataEnum, DeriveInput, Error, Fields, Ident, Member, Result, Type};
+
+use crate::attr::{ContainerAttrs, DefaultAttr, FieldAttrs, RenameRule, Tagging, VariantAttrs};
+
+pub(crate) fn derive(input: DeriveInput) -> Result<TokenStream> {
+	let attrs = ContainerAttrs::parse(&input.attrs)?;
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+	let [kind_of, is_value, into_value, from_value] = match &input.data {
+		Data::Struct(data) => {
+			if attrs.tag.is_some() || attrs.content.is_some() || attrs.untagged {
+				return Err(Error::new_spanned(name, "enum tagging can only be used on enums"));
+			}
+			derive_struct(&data.fields, &attrs)?
+		}
+		Data::Enum(data) => {
+			if attrs.table.is_some() {
+				return Err(Error::new_spanned(name, "`table` can only be used on structs"));
+			}
+			derive_enum(data, &attrs, attrs.tagging(name.span())?)?
+		}
+		Data::Union(_) => {
+			return Err(Error::new_spanned(name, "SurrealValue can not be derived for unions"));
+		}
+	};
+
+	Ok(quote! {
+		impl #impl_generics surrealdb_types::SurrealValue for #name #ty_generics #where_clause {
+			fn kind_of() -> surrealdb_types::Kind {
+				#kind_of
+			}
+
+			fn is_value(value: &surrealdb_types::Value) -> bool {
+				#is_value
+			}
+
+			fn into_value(self) -> surrealdb_types::Value {
+				#into_value
+			}
+
+			fn from_value(value: surrealdb_types::Value) -> surrealdb_types::anyhow::Result<Self> {
+				#from_value
+			}
+		}
+	})
+}
+
+/// Generates the bodies of the `SurrealValue` methods for a struct.
+fn derive_struct(fields: &Fields, attrs: &ContainerAttrs) -> Result<[TokenStream; 4]> {
+	let shape = Shape::new(fields, attrs.rename_all, false, Some(attrs.table.as_deref()))?;
+	let pattern = shape.pattern(quote!(Self));
+	let into = shape.to_value();
+	Ok([
+		shape.kind(),
+		shape.is(quote!(value)),
+		quote! {
+			let #pattern = self;
+			#into
+		},
+		shape.parse(quote!(Self)),
+	])
+}
+
+/// An enum variant, with the name which it is represented by.
+struct Variant<'a> {
+	ident: &'a Ident,
+	name: String,
+	shape: Shape<'a>,
+}
+
+/// Generates the bodies of the `SurrealValue` methods for an enum.
+fn derive_enum(
+	data: &DataEnum,
+	attrs: &ContainerAttrs,
+	tagging: Tagging,
+) -> Result<[TokenStream; 4]> {
+	let variants = data
+		.variants
+		.iter()
+		.map(|v| {
+			let variant_attrs = VariantAttrs::parse(&v.attrs)?;
+			let ident = v.ident.unraw().to_string();
+			let name = match (variant_attrs.rename, attrs.rename_all) {
+				(Some(name), _) => name,
+				(None, Some(rule)) => rule.apply(&ident),
+				(None, None) => ident,
+			};
+			let shape = Shape::new(&v.fields, None, true, None)?;
+			if matches!(tagging, Tagging::Internal(_))
+				&& matches!(shape.style, Style::Tuple | Style::Newtype)
+			{
+				return Err(Error::new_spanned(
+					v,
+					"internally tagged enums only support unit and struct variants",
+				));
+			}
+			Ok(Variant {
+				ident: &v.ident,
+				name,
+				shape,
+			})
+		})
+		.collect::<Result<Vec<_>>>()?;
+
+	let kinds = variants.iter().map(|v| {
+		let name = &v.name;
+		let inner = v.shape.kind();
+		let unit = matches!(v.shape.style, Style::Unit);
+		match &tagging {
+			Tagging::External if unit => quote! {
+				surrealdb_types::Kind::Literal(surrealdb_types::KindLiteral::String(#name.to_string()))
+			},
+			Tagging::External => quote! {
+				surrealdb_types::Kind::Literal(surrealdb_types::KindLiteral::Object(
+					::std::collections::BTreeMap::from([(#name.to_string(), #inner)])
+				))
+			},
+			Tagging::Internal(tag) if unit => quote! {
+				surrealdb_types::Kind::Literal(surrealdb_types::KindLiteral::Object(
+					::std::collections::BTreeMap::from([(
+						#tag.to_string(),
+						surrealdb_types::Kind::Literal(surrealdb_types::KindLiteral::String(#name.to_string())),
+					)])
+				))
+			},
+			Tagging::Internal(tag) => quote! {
+				surrealdb_types::private::tagged_kind(#inner, #tag, #name)
+			},
+			Tagging::Adjacent(tag, _) if unit => quote! {
+				surrealdb_types::Kind::Literal(surrealdb_types::KindLiteral::Object(
+					::std::collections::BTreeMap::from([(
+						#tag.to_string(),
+						surrealdb_types::Kind::Literal(surrealdb_types::KindLiteral::String(#name.to_string())),
+					)])
+				))
+			},
+			Tagging::Adjacent(tag, content) => quote! {
+				surrealdb_types::Kind::Literal(surrealdb_types::KindLiteral::Object(
+					::std::collections::BTreeMap::from([
+						(
+							#tag.to_string(),
+							surrealdb_types::Kind::Literal(surrealdb_types::KindLiteral::String(#name.to_string())),
+						),
+						(#content.to_string(), #inner),
+					])
+				))
+			},
+			Tagging::Untagged if unit => quote!(surrealdb_types::Kind::Null),
+			Tagging::Untagged => inner,
+		}
+	});
+	let kind_of = quote! {
+		surrealdb_types::Kind::either(vec![#(#kinds),*])
+	};
+
+	let into_arms = variants.iter().map(|v| {
+		let ident = v.ident;
+		let name = &v.name;
+		let pattern = v.shape.pattern(quote!(Self::#ident));
+		let inner = v.shape.to_value();
+		let unit = matches!(v.shape.style, Style::Unit);
+		let value = match &tagging {
+			Tagging::External if unit => quote! {
+				surrealdb_types::Value::String(#name.to_string())
+			},
+			Tagging::External => quote! {{
+				let mut obj = surrealdb_types::Object::new();
+				obj.insert(#name.to_string(), #inner);
+				surrealdb_types::Value::Object(obj)
+			}},
+			Tagging::Internal(tag) | Tagging::Adjacent(tag, _) if unit => quote! {{
+				let mut obj = surrealdb_types::Object::new();
+				obj.insert(#tag.to_string(), surrealdb_types::Value::String(#name.to_string()));
+				surrealdb_types::Value::Object(obj)
+			}},
+			Tagging::Internal(tag) => quote! {
+				surrealdb_types::private::tagged(#inner, #tag, #name)
+			},
+			Tagging::Adjacent(tag, content) => quote! {{
+				let mut obj = surrealdb_types::Object::new();
+				obj.insert(#tag.to_string(), surrealdb_types::Value::String(#name.to_string()));
+				obj.insert(#content.to_string(), #inner);
+				surrealdb_types::Value::Object(obj)
+			}},
+			Tagging::Untagged if unit => quote!(surrealdb_types::Value::Null),
+			Tagging::Untagged => inner,
+		};
+		quote!(#pattern => #value,)
+	});
+	let into_value = quote! {
+		match self {
+			#(#into_arms)*
+		}
+	};
+
+	let (units, others): (Vec<_>, Vec<_>) =
+		variants.iter().partition(|v| matches!(v.shape.style, Style::Unit));
+	let unknown = quote! {
+		Err(surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Unknown variant '{}'", Self::kind_of(), tag))
+	};
+	let expected = |what: &str| {
+		quote! {
+			Err(surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Expected {}, got {:?}", Self::kind_of(), #what, value.value_kind()))
+		}
+	};
+
+	let (is_value, from_value) = match &tagging {
+		Tagging::External => {
+			let unit_names = units.iter().map(|v| &v.name).collect::<Vec<_>>();
+			let unit_idents = units.iter().map(|v| v.ident);
+			let other_names = others.iter().map(|v| &v.name).collect::<Vec<_>>();
+			let other_is = others.iter().map(|v| v.shape.is(quote!(value)));
+			let other_from = others.iter().map(|v| {
+				let ident = v.ident;
+				v.shape.parse(quote!(Self::#ident))
+			});
+			let expected = expected("String or Object");
+			let is_unit = match unit_names.is_empty() {
+				true => quote!(surrealdb_types::Value::String(_) => false),
+				false => quote! {
+					surrealdb_types::Value::String(tag) => matches!(tag.as_str(), #(#unit_names)|*)
+				},
+			};
+			(
+				quote! {
+					match value {
+						#is_unit,
+						surrealdb_types::Value::Object(obj) => match surrealdb_types::private::single(obj) {
+							#(Some((#other_names, value)) => #other_is,)*
+							_ => false,
+						},
+						_ => false,
+					}
+				},
+				quote! {
+					match value {
+						surrealdb_types::Value::String(tag) => match tag.as_str() {
+							#(#unit_names => Ok(Self::#unit_idents),)*
+							_ => #unknown,
+						},
+						surrealdb_types::Value::Object(obj) => {
+							let Some((tag, value)) = surrealdb_types::private::into_single(obj) else {
+								return Err(surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Expected an Object with a single field", Self::kind_of()));
+							};
+							match tag.as_str() {
+								#(#other_names => { #other_from })*
+								_ => #unknown,
+							}
+						}
+						value => #expected,
+					}
+				},
+			)
+		}
+		Tagging::Internal(tag_field) | Tagging::Adjacent(tag_field, _) => {
+			let content = match &tagging {
+				Tagging::Adjacent(_, content) => Some(content),
+				_ => None,
+			};
+			let names = variants.iter().map(|v| &v.name).collect::<Vec<_>>();
+			let is_arms = variants.iter().map(|v| match (&v.shape.style, content) {
+				(Style::Unit, _) => quote!(true),
+				(_, None) => v.shape.is(quote!(value)),
+				(_, Some(content)) => {
+					let is = v.shape.is(quote!(value));
+					quote! {{
+						let none = surrealdb_types::Value::None;
+						let value = obj.get(#content).unwrap_or(&none);
+						#is
+					}}
+				}
+			});
+			let from_arms = variants.iter().map(|v| {
+				let ident = v.ident;
+				let from = v.shape.parse(quote!(Self::#ident));
+				match (&v.shape.style, content) {
+					(Style::Unit, _) => quote!(Ok(Self::#ident)),
+					(_, None) => quote! {{
+						let value = surrealdb_types::Value::Object(obj);
+						#from
+					}},
+					(_, Some(content)) => quote! {{
+						let value = obj.remove(#content).unwrap_or(surrealdb_types::Value::None);
+						#from
+					}},
+				}
+			});
+			let expected = expected("Object");
+			(
+				quote! {
+					match value {
+						surrealdb_types::Value::Object(obj) => match surrealdb_types::private::tag(obj, #tag_field).as_deref() {
+							#(Some(#names) => #is_arms,)*
+							_ => false,
+						},
+						_ => false,
+					}
+				},
+				quote! {
+					#[allow(unused_mut)]
+					let surrealdb_types::Value::Object(mut obj) = value else {
+						return #expected;
+					};
+					let Some(tag) = surrealdb_types::private::tag(&obj, #tag_field) else {
+						return Err(surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Missing tag '{}'", Self::kind_of(), #tag_field));
+					};
+					match tag.as_str() {
+						#(#names => #from_arms,)*
+						_ => #unknown,
+					}
+				},
+			)
+		}
+		Tagging::Untagged => {
+			let is_checks = variants.iter().map(|v| match v.shape.style {
+				Style::Unit => quote!(matches!(value, surrealdb_types::Value::Null)),
+				_ => v.shape.is(quote!(value)),
+			});
+			let from_attempts = variants.iter().map(|v| {
+				let ident = v.ident;
+				match v.shape.style {
+					Style::Unit => quote! {
+						if matches!(value, surrealdb_types::Value::Null) {
+							return Ok(Self::#ident);
+						}
+					},
+					_ => {
+						let from = v.shape.parse(quote!(Self::#ident));
+						quote! {
+							let attempt = || -> surrealdb_types::anyhow::Result<Self> {
+								let value = value.clone();
+								#from
+							};
+							if let Ok(v) = attempt() {
+								return Ok(v);
+							}
+						}
+					}
+				}
+			});
+			(
+				quote!(false #(|| #is_checks)*),
+				quote! {
+					#(#from_attempts)*
+					Err(surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Value did not match any variant", Self::kind_of()))
+				},
+			)
+		}
+	};
+
+	Ok([kind_of, is_value, into_value, from_value])
+}
+
+/// How the fields of a struct or enum variant are represented.
+enum Style {
+	/// Fields are stored in an object.
+	Named,
+	/// Fields are stored in an array.
+	Tuple,
+	/// The only field of an enum variant is stored as the value itself.
+	Newtype,
+	/// There are no fields.
+	Unit,
+}
+
+/// A field of a struct or enum variant.
+struct Field<'a> {
+	member: Member,
+	binding: Ident,
+	ty: &'a Type,
+	key: String,
+	attrs: FieldAttrs,
+}
+
+impl Field<'_> {
+	fn kind(&self) -> TokenStream {
+		let ty = self.ty;
+		match &self.attrs.with {
+			Some(with) => quote!(#with::kind_of()),
+			None => quote!(<#ty as surrealdb_types::SurrealValue>::kind_of()),
+		}
+	}
+
+	fn is_fn(&self) -> TokenStream {
+		let ty = self.ty;
+		match &self.attrs.with {
+			Some(with) => quote!(#with::is_value),
+			None => quote!(<#ty as surrealdb_types::SurrealValue>::is_value),
+		}
+	}
+
+	fn convert_fn(&self) -> TokenStream {
+		let ty = self.ty;
+		match &self.attrs.with {
+			Some(with) => quote!(#with::into_value),
+			None => quote!(<#ty as surrealdb_types::SurrealValue>::into_value),
+		}
+	}
+
+	fn parse_fn(&self) -> TokenStream {
+		let ty = self.ty;
+		match &self.attrs.with {
+			Some(with) => quote!(#with::from_value),
+			None => quote!(<#ty as surrealdb_types::SurrealValue>::from_value),
+		}
+	}
+
+	fn default(&self) -> TokenStream {
+		match &self.attrs.default {
+			Some(DefaultAttr::Path(path)) => quote!(#path()),
+			_ => quote!(::core::default::Default::default()),
+		}
+	}
+}
+
+/// The fields of a struct or enum variant.
+struct Shape<'a> {
+	style: Style,
+	fields: Vec<Field<'a>>,
+	/// The table of the `id` field, when the fields belong to a record.
+	record: Option<Option<&'a str>>,
+}
+
+impl<'a> Shape<'a> {
+	fn new(
+		fields: &'a Fields,
+		rename_all: Option<RenameRule>,
+		variant: bool,
+		record: Option<Option<&'a str>>,
+	) -> Result<Self> {
+		let style = match fields {
+			Fields::Named(_) => Style::Named,
+			Fields::Unnamed(x) if variant && x.unnamed.len() == 1 => Style::Newtype,
+			Fields::Unnamed(_) => Style::Tuple,
+			Fields::Unit => Style::Unit,
+		};
+		let fields = fields
+			.iter()
+			.enumerate()
+			.map(|(i, field)| {
+				let attrs = FieldAttrs::parse(&field.attrs)?;
+				let (member, key) = match &field.ident {
+					Some(ident) => {
+						let name = ident.unraw().to_string();
+						let key = match (&attrs.rename, rename_all) {
+							(Some(key), _) => key.clone(),
+							(None, Some(rule)) => rule.apply(&name),
+							(None, None) => name,
+						};
+						(Member::Named(ident.clone()), key)
+					}
+					None => {
+						let named = attrs.rename.is_some()
+							|| attrs.skip || attrs.default.is_some()
+							|| attrs.flatten;
+						if named {
+							return Err(Error::new_spanned(
+								field,
+								"`rename`, `skip`, `default` and `flatten` are only supported on named fields",
+							));
+						}
+						(Member::Unnamed(i.into()), i.to_string())
+					}
+				};
+				Ok(Field {
+					member,
+					binding: format_ident!("__field{}", i),
+					ty: &field.ty,
+					key,
+					attrs,
+				})
+			})
+			.collect::<Result<Vec<_>>>()?;
+		Ok(Self {
+			style,
+			fields,
+			record,
+		})
+	}
+
+	/// The fields which are stored in the value.
+	fn stored(&self) -> impl Iterator<Item = &Field<'a>> {
+		self.fields.iter().filter(|x| !x.attrs.skip)
+	}
+
+	/// Returns the table of the record, if known, when this is the `id` field
+	/// of a record.
+	fn record_id(&self, field: &Field) -> Option<Option<&'a str>> {
+		self.record.filter(|_| field.key == "id")
+	}
+
+	/// A pattern which binds each field to a variable.
+	fn pattern(&self, path: TokenStream) -> TokenStream {
+		let bindings = self.fields.iter().map(|x| match x.attrs.skip {
+			true => quote!(_),
+			false => {
+				let binding = &x.binding;
+				quote!(#binding)
+			}
+		});
+		match self.style {
+			Style::Named => {
+				let members = self.fields.iter().map(|x| &x.member);
+				quote!(#path { #(#members: #bindings),* })
+			}
+			Style::Tuple | Style::Newtype => quote!(#path(#(#bindings),*)),
+			Style::Unit => path,
+		}
+	}
+
+	/// An expression which returns the kind of the value.
+	fn kind(&self) -> TokenStream {
+		match self.style {
+			Style::Named => {
+				let entries = self.stored().map(|x| {
+					let mut kind = x.kind();
+					if x.attrs.flatten {
+						return quote!(surrealdb_types::private::flatten_kind(&mut map, #kind););
+					}
+					if let Some(table) = self.record_id(x) {
+						let table = match table {
+							Some(table) => quote!(Some(#table)),
+							None => quote!(None),
+						};
+						kind = quote!(surrealdb_types::private::record_kind(#kind, #table));
+					}
+					if x.attrs.default.is_some() {
+						kind = quote!(surrealdb_types::Kind::option(#kind));
+					}
+					let key = &x.key;
+					quote!(map.insert(#key.to_string(), #kind);)
+				});
+				quote! {{
+					#[allow(unused_mut)]
+					let mut map = ::std::collections::BTreeMap::new();
+					#(#entries)*
+					surrealdb_types::Kind::Literal(surrealdb_types::KindLiteral::Object(map))
+				}}
+			}
+			Style::Tuple => {
+				let kinds = self.fields.iter().map(Field::kind);
+				quote! {
+					surrealdb_types::Kind::Literal(surrealdb_types::KindLiteral::Array(vec![#(#kinds),*]))
+				}
+			}
+			Style::Newtype => self.fields[0].kind(),
+			Style::Unit => quote! {
+				surrealdb_types::Kind::Literal(surrealdb_types::KindLiteral::Object(::std::collections::BTreeMap::new()))
+			},
+		}
+	}
+
+	/// An expression which checks whether the `&Value` in `value` matches.
+	fn is(&self, value: TokenStream) -> TokenStream {
+		match self.style {
+			Style::Named => {
+				let checks = self
+					.stored()
+					.map(|x| {
+						let is = x.is_fn();
+						if x.attrs.flatten {
+							return quote!(#is(#value));
+						}
+						let present = match self.record_id(x) {
+							Some(_) => quote!(surrealdb_types::private::is_record_key(v, #is)),
+							None => quote!(#is(v)),
+						};
+						let missing = match x.attrs.default {
+							Some(_) => quote!(true),
+							None => quote!(#is(&surrealdb_types::Value::None)),
+						};
+						let key = &x.key;
+						quote!(obj.get(#key).map_or_else(|| #missing, |v| #present))
+					})
+					.collect::<Vec<_>>();
+				if checks.is_empty() {
+					return quote!(matches!(#value, surrealdb_types::Value::Object(_)));
+				}
+				quote! {
+					match #value {
+						surrealdb_types::Value::Object(obj) => #(#checks)&&*,
+						_ => false,
+					}
+				}
+			}
+			Style::Tuple => {
+				let count = self.fields.len();
+				let checks = self.fields.iter().enumerate().map(|(i, x)| {
+					let is = x.is_fn();
+					quote!(&& values.get(#i).is_some_and(#is))
+				});
+				quote! {
+					match #value {
+						surrealdb_types::Value::Array(values) => values.len() == #count #(#checks)*,
+						_ => false,
+					}
+				}
+			}
+			Style::Newtype => {
+				let is = self.fields[0].is_fn();
+				quote!(#is(#value))
+			}
+			Style::Unit => quote! {
+				matches!(#value, surrealdb_types::Value::Object(obj) if obj.is_empty())
+			},
+		}
+	}
+
+	/// An expression which converts the bound fields into a `Value`.
+	fn to_value(&self) -> TokenStream {
+		match self.style {
+			Style::Named => {
+				let inserts = self.stored().map(|x| {
+					let into = x.convert_fn();
+					let binding = &x.binding;
+					if x.attrs.flatten {
+						return quote!(surrealdb_types::private::flatten_value(&mut obj, #into(#binding)););
+					}
+					let value = match self.record_id(x) {
+						Some(Some(table)) => {
+							quote!(surrealdb_types::private::record_id(#into(#binding), #table))
+						}
+						_ => quote!(#into(#binding)),
+					};
+					let key = &x.key;
+					quote!(obj.insert(#key.to_string(), #value);)
+				});
+				quote! {{
+					#[allow(unused_mut)]
+					let mut obj = surrealdb_types::Object::new();
+					#(#inserts)*
+					surrealdb_types::Value::Object(obj)
+				}}
+			}
+			Style::Tuple => {
+				let values = self.fields.iter().map(|x| {
+					let into = x.convert_fn();
+					let binding = &x.binding;
+					quote!(#into(#binding))
+				});
+				quote! {
+					surrealdb_types::Value::Array(surrealdb_types::Array::from(
+						::std::vec::Vec::<surrealdb_types::Value>::from([#(#values),*])
+					))
+				}
+			}
+			Style::Newtype => {
+				let into = self.fields[0].convert_fn();
+				let binding = &self.fields[0].binding;
+				quote!(#into(#binding))
+			}
+			Style::Unit => quote!(surrealdb_types::Value::Object(surrealdb_types::Object::new())),
+		}
+	}
+
+	/// Statements which convert the owned `Value` in `value` into the type at
+	/// `path`, ending in an expression which returns the result.
+	fn parse(&self, path: TokenStream) -> TokenStream {
+		let convert_err = quote! {
+			|e| surrealdb_types::anyhow::anyhow!("Failed to convert to {}: {}", Self::kind_of(), e)
+		};
+		let members = self.fields.iter().map(|x| &x.member);
+		let bindings = self.fields.iter().map(|x| &x.binding);
+		match self.style {
+			Style::Named => {
+				let fields = self.fields.iter().map(|x| {
+					let binding = &x.binding;
+					let from = x.parse_fn();
+					let default = x.default();
+					let key = &x.key;
+					if x.attrs.skip {
+						return quote!(let #binding = #default;);
+					}
+					if x.attrs.flatten {
+						return quote! {
+							let #binding = #from(surrealdb_types::Value::Object(obj.clone())).map_err(#convert_err)?;
+						};
+					}
+					let from = match self.record_id(x) {
+						Some(_) => {
+							let is = x.is_fn();
+							quote!(|v| #from(surrealdb_types::private::record_key(v, #is)))
+						}
+						None => from,
+					};
+					match x.attrs.default {
+						Some(_) => quote! {
+							let #binding = match obj.get(#key) {
+								Some(v) => (#from)(v.clone()).map_err(#convert_err)?,
+								None => #default,
+							};
+						},
+						None => quote! {
+							let #binding = surrealdb_types::private::field(Self::kind_of, &obj, #key, #from)?;
+						},
+					}
+				});
+				let obj = match self.stored().next() {
+					Some(_) => quote!(obj),
+					None => quote!(_),
+				};
+				quote! {
+					let surrealdb_types::Value::Object(#obj) = value else {
+						return Err(surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Expected Object, got {:?}", Self::kind_of(), value.value_kind()));
+					};
+					#(#fields)*
+					Ok(#path { #(#members: #bindings),* })
+				}
+			}
+			Style::Tuple => {
+				let count = self.fields.len();
+				let fields = self.fields.iter().enumerate().map(|(i, x)| {
+					let binding = &x.binding;
+					let from = x.parse_fn();
+					quote! {
+						let #binding = #from(
+							values.get(#i)
+								.ok_or_else(|| surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Missing field at index {}", Self::kind_of(), #i))?
+								.clone()
+						).map_err(#convert_err)?;
+					}
+				});
+				quote! {
+					let surrealdb_types::Value::Array(values) = value else {
+						return Err(surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Expected Array, got {:?}", Self::kind_of(), value.value_kind()));
+					};
+
+					if values.len() != #count {
+						return Err(surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Expected Array of length {}, got {}", Self::kind_of(), #count, values.len()));
+					}
+
+					#(#fields)*
+					Ok(#path(#(#bindings),*))
+				}
+			}
+			Style::Newtype => {
+				let from = self.fields[0].parse_fn();
+				quote!(Ok(#path(#from(value).map_err(#convert_err)?)))
+			}
+			Style::Unit => quote! {
+				match value {
+					surrealdb_types::Value::Object(obj) if obj.is_empty() => Ok(#path),
+					_ => Err(surrealdb_types::anyhow::anyhow!("Failed to convert to {}: Expected empty Object, got {:?}", Self::kind_of(), value.value_kind())),
+				}
+			},
+		}
+	}
+}
@@ -3,6 +3,8 @@
 
 mod flatbuffers;
 mod kind;
+#[doc(hidden)]
+pub mod private;
 mod traits;
 pub(crate) mod utils;
 mod value;
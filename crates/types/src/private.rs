@@ -0,0 +1,128 @@
+//! Runtime helpers which are used by the code generated by the derive macros.
+//! These are not part of the public API, and may change at any time.
+
+use std::collections::BTreeMap;
+
+use crate::{Kind, KindLiteral, Object, RecordId, RecordIdKey, Value};
+
+/// Extracts a field from an object, converting the value with the given
+/// function. Missing fields are converted from `NONE`, so that optional
+/// fields can be omitted.
+pub fn field<T>(
+	kind: fn() -> Kind,
+	obj: &Object,
+	name: &str,
+	from: fn(Value) -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+	match obj.get(name) {
+		Some(v) => {
+			from(v.clone()).map_err(|e| anyhow::anyhow!("Failed to convert to {}: {e}", kind()))
+		}
+		None => from(Value::None).map_err(|_| {
+			anyhow::anyhow!("Failed to convert to {}: Missing field '{name}'", kind())
+		}),
+	}
+}
+
+/// Returns the key of a record id when the value is a record id which is not
+/// accepted by the `id` field itself.
+pub fn record_key(value: Value, accepts: fn(&Value) -> bool) -> Value {
+	if accepts(&value) {
+		return value;
+	}
+	match value {
+		Value::RecordId(id) => id.key.into_value(),
+		value => value,
+	}
+}
+
+/// Checks whether a value is accepted by an `id` field, either directly or as
+/// the key of a record id.
+pub fn is_record_key(value: &Value, accepts: fn(&Value) -> bool) -> bool {
+	match value {
+		Value::RecordId(id) => accepts(value) || accepts(&id.key.clone().into_value()),
+		value => accepts(value),
+	}
+}
+
+/// Converts the value of an `id` field into a record id on the given table,
+/// unless the value is a record id already.
+pub fn record_id(value: Value, table: &str) -> Value {
+	match value {
+		Value::RecordId(id) => Value::RecordId(id),
+		value => match RecordIdKey::from_value(value.clone()) {
+			Some(key) => Value::RecordId(RecordId::new(table, key)),
+			None => value,
+		},
+	}
+}
+
+/// Returns the kind of an `id` field, which is always stored as a record id.
+pub fn record_kind(kind: Kind, table: Option<&str>) -> Kind {
+	match (kind, table) {
+		(_, Some(table)) => Kind::Record(vec![table.to_owned()]),
+		(kind @ Kind::Record(_), None) => kind,
+		(kind, None) => Kind::either(vec![Kind::Record(vec![]), kind]),
+	}
+}
+
+/// Merges the fields of a flattened object kind into the fields of its parent.
+pub fn flatten_kind(fields: &mut BTreeMap<String, Kind>, kind: Kind) {
+	if let Kind::Literal(KindLiteral::Object(inner)) = kind {
+		fields.extend(inner);
+	}
+}
+
+/// Merges the fields of a flattened object into its parent.
+pub fn flatten_value(obj: &mut Object, value: Value) {
+	if let Value::Object(inner) = value {
+		obj.extend(inner);
+	}
+}
+
+/// Returns the tag of an internally or adjacently tagged enum value.
+pub fn tag(obj: &Object, tag: &str) -> Option<String> {
+	match obj.get(tag) {
+		Some(Value::String(v)) => Some(v.clone()),
+		_ => None,
+	}
+}
+
+/// Adds the tag of an internally tagged enum variant to its fields.
+pub fn tagged(value: Value, tag: &str, name: &str) -> Value {
+	match value {
+		Value::Object(mut obj) => {
+			obj.insert(tag.to_owned(), Value::String(name.to_owned()));
+			Value::Object(obj)
+		}
+		value => value,
+	}
+}
+
+/// Adds the tag of an internally tagged enum variant to the kind of its
+/// fields.
+pub fn tagged_kind(kind: Kind, tag: &str, name: &str) -> Kind {
+	match kind {
+		Kind::Literal(KindLiteral::Object(mut fields)) => {
+			fields.insert(tag.to_owned(), Kind::Literal(KindLiteral::String(name.to_owned())));
+			Kind::Literal(KindLiteral::Object(fields))
+		}
+		kind => kind,
+	}
+}
+
+/// Returns the only entry of an externally tagged enum value.
+pub fn single(obj: &Object) -> Option<(&str, &Value)> {
+	match obj.len() {
+		1 => obj.0.iter().next().map(|(k, v)| (k.as_str(), v)),
+		_ => None,
+	}
+}
+
+/// Takes the only entry of an externally tagged enum value.
+pub fn into_single(obj: Object) -> Option<(String, Value)> {
+	match obj.len() {
+		1 => obj.into_iter().next(),
+		_ => None,
+	}
+}
@@ -1,2 +1,4 @@
+mod surreal_table;
 mod surreal_value;
+pub use surreal_table::*;
 pub use surreal_value::*;
@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+use crate::utils::escape::EscapeIdent;
+use crate::{Kind, KindLiteral, SurrealValue};
+
+/// Trait for Rust types which are stored as records in a SurrealDB table
+///
+/// The schema of the table is generated from the `Kind` of the type, so that
+/// the table definition always matches the shape of the Rust type. This trait
+/// is usually implemented with `#[derive(SurrealTable)]`.
+pub trait SurrealTable: SurrealValue {
+	/// The name of the table in which the records are stored
+	const TABLE: &'static str;
+	/// Whether the table only allows the fields which have been defined
+	const SCHEMAFULL: bool = true;
+
+	/// Returns the `DEFINE TABLE` and `DEFINE FIELD` statements for this type
+	fn definitions() -> Vec<String> {
+		let table = EscapeIdent(Self::TABLE).to_string();
+		let mode = if Self::SCHEMAFULL {
+			"SCHEMAFULL"
+		} else {
+			"SCHEMALESS"
+		};
+		let mut out = vec![format!("DEFINE TABLE OVERWRITE {table} {mode}")];
+		if let Kind::Literal(KindLiteral::Object(fields)) = Self::kind_of() {
+			define_fields(&table, "", &fields, &mut out);
+		}
+		out
+	}
+
+	/// Returns the schema for this type, as a single SurrealQL query
+	fn schema() -> String {
+		Self::definitions().into_iter().map(|x| x + ";\n").collect()
+	}
+}
+
+/// Appends the `DEFINE FIELD` statements for the fields of an object.
+fn define_fields(table: &str, path: &str, fields: &BTreeMap<String, Kind>, out: &mut Vec<String>) {
+	for (name, kind) in fields {
+		// The id of a record is defined by the table itself
+		if path.is_empty() && name == "id" {
+			continue;
+		}
+		let path = if path.is_empty() {
+			EscapeIdent(name).to_string()
+		} else {
+			format!("{path}.{}", EscapeIdent(name))
+		};
+		match nested_object(kind) {
+			Some((kind, suffix, nested)) => {
+				out.push(format!("DEFINE FIELD OVERWRITE {path} ON {table} TYPE {kind}"));
+				define_fields(table, &format!("{path}{suffix}"), nested, out);
+			}
+			None => out.push(format!("DEFINE FIELD OVERWRITE {path} ON {table} TYPE {kind}")),
+		}
+	}
+}
+
+/// Finds a nested object in a kind, whose fields are defined separately.
+/// Returns the kind with the object replaced by `object`, the suffix which
+/// addresses the object from the field, and the fields of the object.
+fn nested_object(kind: &Kind) -> Option<(Kind, &'static str, &BTreeMap<String, Kind>)> {
+	match kind {
+		Kind::Literal(KindLiteral::Object(fields)) => Some((Kind::Object, "", fields)),
+		Kind::Array(inner, max) => match nested_object(inner)? {
+			(inner, "", fields) => Some((Kind::Array(Box::new(inner), *max), "[*]", fields)),
+			_ => None,
+		},
+		Kind::Set(inner, max) => match nested_object(inner)? {
+			(inner, "", fields) => Some((Kind::Set(Box::new(inner), *max), "[*]", fields)),
+			_ => None,
+		},
+		// Only a single object can be defined for a field
+		Kind::Either(kinds) => {
+			let mut found = None;
+			let mut either = Vec::with_capacity(kinds.len());
+			for kind in kinds {
+				match nested_object(kind) {
+					Some(_) if found.is_some() => return None,
+					Some((kind, suffix, fields)) => {
+						found = Some((suffix, fields));
+						either.push(kind);
+					}
+					None => either.push(kind.clone()),
+				}
+			}
+			found.map(|(suffix, fields)| (Kind::Either(either), suffix, fields))
+		}
+		_ => None,
+	}
+}
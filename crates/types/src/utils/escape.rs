@@ -58,3 +58,17 @@ impl fmt::Display for QuoteStr<'_> {
 		f.write_fmt(format_args!("{}{}{}", quote, Escape::escape_str(s, quote), quote))
 	}
 }
+
+pub struct EscapeIdent<'a>(pub &'a str);
+impl fmt::Display for EscapeIdent<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let s = self.0;
+		let plain = s.starts_with(|x: char| x.is_ascii_alphabetic() || x == '_')
+			&& s.chars().all(|x| x.is_ascii_alphanumeric() || x == '_');
+		if plain {
+			f.write_str(s)
+		} else {
+			f.write_fmt(format_args!("`{}`", Escape::escape_str(s, '`')))
+		}
+	}
+}
@@ -0,0 +1,299 @@
+use surrealdb_types::{
+	Kind, KindLiteral, Object, RecordId, SurrealTable, SurrealValue, Value, array, object,
+};
+
+#[derive(SurrealValue, Debug, PartialEq)]
+struct Audit {
+	created_by: String,
+}
+
+#[derive(SurrealValue, SurrealTable, Debug, PartialEq)]
+#[surreal(table = "person", rename_all = "camelCase")]
+struct Person {
+	id: String,
+	first_name: String,
+	#[surreal(rename = "mail")]
+	email: String,
+	#[surreal(skip)]
+	cached: Option<String>,
+	#[surreal(default = "default_tags")]
+	tags: Vec<String>,
+	nickname: Option<String>,
+	#[surreal(flatten)]
+	audit: Audit,
+	#[surreal(with = "as_text")]
+	version: i64,
+}
+
+fn default_tags() -> Vec<String> {
+	vec!["new".to_string()]
+}
+
+mod as_text {
+	use surrealdb_types::{Kind, Value};
+
+	pub fn kind_of() -> Kind {
+		Kind::String
+	}
+
+	pub fn is_value(value: &Value) -> bool {
+		matches!(value, Value::String(v) if v.parse::<i64>().is_ok())
+	}
+
+	pub fn into_value(value: i64) -> Value {
+		Value::String(value.to_string())
+	}
+
+	pub fn from_value(value: Value) -> surrealdb_types::anyhow::Result<i64> {
+		match value {
+			Value::String(v) => Ok(v.parse()?),
+			v => Err(surrealdb_types::anyhow::anyhow!("Expected a string, got {v:?}")),
+		}
+	}
+}
+
+fn person() -> Person {
+	Person {
+		id: "tobie".to_string(),
+		first_name: "Tobie".to_string(),
+		email: "tobie@surrealdb.com".to_string(),
+		cached: None,
+		tags: vec!["admin".to_string()],
+		nickname: None,
+		audit: Audit {
+			created_by: "jaime".to_string(),
+		},
+		version: 3,
+	}
+}
+
+#[test]
+fn struct_attributes_into_value() {
+	let Value::Object(obj) = person().into_value() else {
+		panic!("Expected an object");
+	};
+	assert_eq!(obj.get("id"), Some(&Value::RecordId(RecordId::new("person", "tobie"))));
+	assert_eq!(obj.get("firstName"), Some(&Value::String("Tobie".to_string())));
+	assert_eq!(obj.get("mail"), Some(&Value::String("tobie@surrealdb.com".to_string())));
+	assert_eq!(obj.get("createdBy"), None);
+	assert_eq!(obj.get("created_by"), Some(&Value::String("jaime".to_string())));
+	assert_eq!(obj.get("version"), Some(&Value::String("3".to_string())));
+	assert_eq!(obj.get("cached"), None);
+	assert_eq!(obj.get("audit"), None);
+}
+
+#[test]
+fn struct_attributes_from_value() {
+	let value = Value::Object(object! {
+		id: RecordId::new("person", "tobie"),
+		firstName: "Tobie".to_string(),
+		mail: "tobie@surrealdb.com".to_string(),
+		cached: "ignored".to_string(),
+		created_by: "jaime".to_string(),
+		version: "3".to_string(),
+	});
+	assert!(Person::is_value(&value));
+	let person = Person::from_value(value).unwrap();
+	assert_eq!(person.id, "tobie");
+	assert_eq!(person.cached, None);
+	assert_eq!(person.tags, vec!["new".to_string()]);
+	assert_eq!(person.nickname, None);
+	assert_eq!(person.audit.created_by, "jaime");
+	assert_eq!(person.version, 3);
+}
+
+#[test]
+fn struct_attributes_round_trip() {
+	let value = person().into_value();
+	assert!(Person::is_value(&value));
+	assert_eq!(Person::from_value(value).unwrap(), person());
+}
+
+#[test]
+fn struct_missing_field() {
+	let value = Value::Object(object! {
+		id: "tobie".to_string(),
+		mail: "tobie@surrealdb.com".to_string(),
+		created_by: "jaime".to_string(),
+		version: "3".to_string(),
+	});
+	assert!(!Person::is_value(&value));
+	let err = Person::from_value(value).unwrap_err();
+	assert!(err.to_string().contains("Missing field 'firstName'"), "{err}");
+}
+
+#[test]
+fn struct_attributes_kind() {
+	let Kind::Literal(KindLiteral::Object(fields)) = Person::kind_of() else {
+		panic!("Expected a literal object kind");
+	};
+	assert_eq!(fields.get("id"), Some(&Kind::Record(vec!["person".to_string()])));
+	assert_eq!(fields.get("mail"), Some(&Kind::String));
+	assert_eq!(fields.get("created_by"), Some(&Kind::String));
+	assert_eq!(fields.get("version"), Some(&Kind::String));
+	assert_eq!(fields.get("tags"), Some(&Kind::option(Vec::<String>::kind_of())));
+	assert_eq!(fields.get("cached"), None);
+}
+
+#[test]
+fn table_definitions() {
+	assert_eq!(Person::TABLE, "person");
+	assert_eq!(
+		Person::definitions(),
+		vec![
+			"DEFINE TABLE OVERWRITE person SCHEMAFULL",
+			"DEFINE FIELD OVERWRITE created_by ON person TYPE string",
+			"DEFINE FIELD OVERWRITE firstName ON person TYPE string",
+			"DEFINE FIELD OVERWRITE mail ON person TYPE string",
+			"DEFINE FIELD OVERWRITE nickname ON person TYPE none | string",
+			"DEFINE FIELD OVERWRITE tags ON person TYPE none | array<string>",
+			"DEFINE FIELD OVERWRITE version ON person TYPE string",
+		]
+	);
+}
+
+#[derive(SurrealValue, SurrealTable, Debug, PartialEq)]
+#[surreal(schemaless)]
+struct BlogPost {
+	title: String,
+	author: Audit,
+	#[surreal(rename = "related posts")]
+	related: Vec<Audit>,
+}
+
+#[test]
+fn table_nested_definitions() {
+	assert_eq!(BlogPost::TABLE, "blog_post");
+	assert_eq!(
+		BlogPost::schema(),
+		"DEFINE TABLE OVERWRITE blog_post SCHEMALESS;\n\
+		DEFINE FIELD OVERWRITE author ON blog_post TYPE object;\n\
+		DEFINE FIELD OVERWRITE author.created_by ON blog_post TYPE string;\n\
+		DEFINE FIELD OVERWRITE `related posts` ON blog_post TYPE array<object>;\n\
+		DEFINE FIELD OVERWRITE `related posts`[*].created_by ON blog_post TYPE string;\n\
+		DEFINE FIELD OVERWRITE title ON blog_post TYPE string;\n"
+	);
+}
+
+#[derive(SurrealValue, Debug, PartialEq)]
+enum Status {
+	Active,
+	Banned {
+		reason: String,
+	},
+	Renamed(String),
+	Moved(i64, i64),
+}
+
+#[test]
+fn enum_externally_tagged() {
+	assert_eq!(Status::Active.into_value(), Value::String("Active".to_string()));
+	let value = Status::Banned {
+		reason: "spam".to_string(),
+	}
+	.into_value();
+	assert_eq!(
+		value,
+		Value::Object(object! {
+			Banned: object! { reason: "spam".to_string() },
+		})
+	);
+	let value = Status::Moved(1, 2).into_value();
+	assert_eq!(value, Value::Object(object! { Moved: array![1, 2] }));
+	for status in [
+		Status::Active,
+		Status::Banned {
+			reason: "spam".to_string(),
+		},
+		Status::Renamed("tobie".to_string()),
+		Status::Moved(1, 2),
+	] {
+		let value = status.into_value();
+		assert!(Status::is_value(&value));
+		assert_eq!(Status::from_value(value.clone()).unwrap().into_value(), value);
+	}
+	assert!(!Status::is_value(&Value::String("Deleted".to_string())));
+	let err = Status::from_value(Value::String("Deleted".to_string())).unwrap_err();
+	assert!(err.to_string().contains("Unknown variant 'Deleted'"), "{err}");
+}
+
+#[derive(SurrealValue, Debug, PartialEq)]
+#[surreal(tag = "type", rename_all = "lowercase")]
+enum Shape {
+	Circle {
+		radius: i64,
+	},
+	Square {
+		side: i64,
+	},
+	#[surreal(rename = "none")]
+	Empty,
+}
+
+#[test]
+fn enum_internally_tagged() {
+	let value = Shape::Circle {
+		radius: 2,
+	}
+	.into_value();
+	assert_eq!(value, Value::Object(object! { type: "circle".to_string(), radius: 2 }));
+	assert!(Shape::is_value(&value));
+	assert_eq!(
+		Shape::from_value(value).unwrap(),
+		Shape::Circle {
+			radius: 2
+		}
+	);
+	let value = Shape::Empty.into_value();
+	assert_eq!(value, Value::Object(object! { type: "none".to_string() }));
+	assert_eq!(Shape::from_value(value).unwrap(), Shape::Empty);
+	assert!(!Shape::is_value(&Value::Object(object! { type: "square".to_string(), radius: 2 })));
+	let err = Shape::from_value(Value::Object(object! { radius: 2 })).unwrap_err();
+	assert!(err.to_string().contains("Missing tag 'type'"), "{err}");
+	let Kind::Either(kinds) = Shape::kind_of() else {
+		panic!("Expected an either kind");
+	};
+	assert_eq!(kinds.len(), 3);
+}
+
+#[derive(SurrealValue, Debug, PartialEq)]
+#[surreal(tag = "t", content = "c")]
+enum Message {
+	Text(String),
+	Ping,
+}
+
+#[test]
+fn enum_adjacently_tagged() {
+	let value = Message::Text("hello".to_string()).into_value();
+	assert_eq!(value, Value::Object(object! { t: "Text".to_string(), c: "hello".to_string() }));
+	assert!(Message::is_value(&value));
+	assert_eq!(Message::from_value(value).unwrap(), Message::Text("hello".to_string()));
+	let value = Message::Ping.into_value();
+	assert_eq!(value, Value::Object(object! { t: "Ping".to_string() }));
+	assert_eq!(Message::from_value(value).unwrap(), Message::Ping);
+}
+
+#[derive(SurrealValue, Debug, PartialEq)]
+#[surreal(untagged)]
+enum Key {
+	Number(i64),
+	Text(String),
+	Nothing,
+}
+
+#[test]
+fn enum_untagged() {
+	assert_eq!(Key::Number(1).into_value(), Value::from(1));
+	assert_eq!(Key::from_value(Value::from(1)).unwrap(), Key::Number(1));
+	assert_eq!(Key::from_value(Value::from("a".to_string())).unwrap(), Key::Text("a".to_string()));
+	assert_eq!(Key::from_value(Value::Null).unwrap(), Key::Nothing);
+	assert!(Key::is_value(&Value::from("a".to_string())));
+	assert!(!Key::is_value(&Value::Bool(true)));
+	assert!(Key::from_value(Value::Bool(true)).is_err());
+}
+
+#[test]
+fn enum_empty_object() {
+	assert!(!Status::is_value(&Value::Object(Object::new())));
+}
@@ -48,6 +48,10 @@ struct ExportConfigArguments {
 	/// Whether records should be exported
 	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
 	records: Option<bool>,
+	/// Whether masked fields should be replaced with their mask, regardless
+	/// of their select permissions
+	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
+	masks: Option<bool>,
 }
 
 #[derive(Args, Debug)]
@@ -191,5 +195,9 @@ fn apply_config<C: Connection, R>(
 		export = export.records(value);
 	}
 
+	if let Some(value) = config.masks {
+		export = export.masks(value);
+	}
+
 	export
 }
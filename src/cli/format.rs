@@ -0,0 +1,106 @@
+use anyhow::{Result, bail};
+use clap::{Args, ValueEnum};
+use glob::glob;
+
+use crate::core::syn::format::{self, FormatOptions, Indent, KeywordCase};
+
+#[derive(Args, Debug)]
+pub struct FormatCommandArguments {
+	#[arg(help = "Glob pattern for the files to format")]
+	#[arg(default_value = "**/*.surql")]
+	patterns: Vec<String>,
+	#[arg(help = "Check whether the files are formatted, without modifying them")]
+	#[arg(long)]
+	check: bool,
+	#[arg(help = "The indentation to use, either 'tabs' or a number of spaces")]
+	#[arg(long, default_value = "tabs", value_parser = parse_indent)]
+	indent: Indent,
+	#[arg(help = "The casing to use for keywords")]
+	#[arg(long, value_enum, default_value_t = Case::Upper)]
+	keyword_case: Case,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Case {
+	Upper,
+	Lower,
+}
+
+fn parse_indent(value: &str) -> Result<Indent, String> {
+	if value.eq_ignore_ascii_case("tabs") {
+		return Ok(Indent::Tabs);
+	}
+	match value.parse() {
+		Ok(width) if width > 0 => Ok(Indent::Spaces(width)),
+		_ => Err("expected 'tabs' or a number of spaces between 1 and 255".to_string()),
+	}
+}
+
+pub async fn init(args: FormatCommandArguments) -> Result<()> {
+	let FormatCommandArguments {
+		patterns,
+		check,
+		indent,
+		keyword_case,
+	} = args;
+
+	let options = FormatOptions {
+		indent,
+		keyword_case: match keyword_case {
+			Case::Upper => KeywordCase::Upper,
+			Case::Lower => KeywordCase::Lower,
+		},
+	};
+
+	let mut entries = vec![];
+
+	for pattern in patterns {
+		let pattern_entries = match glob(&pattern) {
+			Ok(entries) => entries,
+			Err(error) => {
+				eprintln!("Error parsing glob pattern {pattern}: {error}");
+
+				return Err(anyhow::Error::new(error)
+					.context(format!("Error parsing glob pattern '{pattern}'")));
+			}
+		};
+
+		entries.extend(pattern_entries.flatten());
+	}
+
+	if entries.is_empty() {
+		eprintln!("No files found");
+		bail!("No files found");
+	}
+
+	let mut unformatted = 0;
+
+	for entry in entries {
+		let file_content = tokio::fs::read_to_string(&entry).await?;
+		let formatted = match format::format(&file_content, &options) {
+			Ok(formatted) => formatted,
+			Err(error) => {
+				println!("{}: KO", entry.display());
+				eprintln!("{error}");
+
+				bail!(error)
+			}
+		};
+
+		if formatted == file_content {
+			println!("{}: OK", entry.display());
+		} else if check {
+			println!("{}: needs formatting", entry.display());
+			unformatted += 1;
+		} else {
+			tokio::fs::write(&entry, formatted).await?;
+			println!("{}: formatted", entry.display());
+		}
+	}
+
+	if unformatted > 0 {
+		bail!("{unformatted} file(s) need formatting");
+	}
+
+	Ok(())
+}
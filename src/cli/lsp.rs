@@ -0,0 +1,426 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use glob::glob;
+use serde_json::{Value, json};
+use tokio::io::{
+	self, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader,
+};
+use url::Url;
+
+use crate::core::syn::analyze::Schema;
+use crate::core::syn::error::Location;
+use crate::core::syn::lsp::{self, Symbol, SymbolKind};
+
+/// The JSON-RPC error code for a method which is not supported
+const METHOD_NOT_FOUND: i64 = -32601;
+/// The JSON-RPC error code for a message which is not valid JSON
+const PARSE_ERROR: i64 = -32700;
+
+#[derive(Args, Debug)]
+pub struct LspCommandArguments {
+	// Editors commonly pass this flag, and stdio is the only transport
+	#[arg(long, hide = true)]
+	stdio: bool,
+}
+
+pub async fn init(_: LspCommandArguments) -> Result<()> {
+	let mut reader = BufReader::new(io::stdin());
+	let mut writer = io::stdout();
+	let mut server = Server::default();
+	// Handle each message until the editor exits
+	while let Some(body) = read_message(&mut reader).await? {
+		let replies = match serde_json::from_slice::<Value>(&body) {
+			Ok(message) => server.handle(message),
+			Err(e) => vec![error(Value::Null, PARSE_ERROR, e.to_string())],
+		};
+		for reply in replies {
+			write_message(&mut writer, &reply).await?;
+		}
+		if server.exit {
+			break;
+		}
+	}
+	// Everything OK
+	Ok(())
+}
+
+/// Reads the body of the next message, or `None` at the end of the input
+async fn read_message<R: AsyncRead + Unpin>(reader: &mut BufReader<R>) -> Result<Option<Vec<u8>>> {
+	let mut length = None;
+	loop {
+		let mut header = String::new();
+		if reader.read_line(&mut header).await? == 0 {
+			return Ok(None);
+		}
+		let header = header.trim_end();
+		if header.is_empty() {
+			break;
+		}
+		match header.split_once(':') {
+			Some((name, value)) if name.eq_ignore_ascii_case("Content-Length") => {
+				length = Some(value.trim().parse::<usize>().context("Invalid Content-Length")?);
+			}
+			_ => {}
+		}
+	}
+	let Some(length) = length else {
+		bail!("Missing Content-Length header");
+	};
+	let mut body = vec![0; length];
+	reader.read_exact(&mut body).await?;
+	Ok(Some(body))
+}
+
+async fn write_message<W: AsyncWrite + Unpin>(writer: &mut W, message: &Value) -> Result<()> {
+	let body = serde_json::to_vec(message)?;
+	writer.write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes()).await?;
+	writer.write_all(&body).await?;
+	writer.flush().await?;
+	Ok(())
+}
+
+fn response(id: Value, result: Value) -> Value {
+	json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn error(id: Value, code: i64, message: String) -> Value {
+	json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+	json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+fn request(id: &str, method: &str, params: Value) -> Value {
+	json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+/// Converts a parser location into an LSP position.
+///
+/// Columns are counted in chars, which matches the UTF-16 positions of the
+/// protocol for all characters in the basic multilingual plane.
+fn position(location: Location) -> Value {
+	json!({
+		"line": location.line.saturating_sub(1),
+		"character": location.column.saturating_sub(1),
+	})
+}
+
+fn range(range: Range<Location>) -> Value {
+	json!({ "start": position(range.start), "end": position(range.end) })
+}
+
+/// The text of a SurrealQL document, with the definitions which it contains
+struct Document {
+	text: String,
+	symbols: Vec<Symbol>,
+}
+
+impl Document {
+	fn new(text: String) -> Self {
+		let symbols = lsp::symbols(&text);
+		Self {
+			text,
+			symbols,
+		}
+	}
+}
+
+#[derive(Default)]
+struct Server {
+	/// The directories of the workspace, which are searched for definitions
+	roots: Vec<PathBuf>,
+	/// The SurrealQL files within the workspace, as they are on disk
+	files: BTreeMap<Url, Document>,
+	/// The documents which are open in the editor
+	documents: BTreeMap<Url, Document>,
+	/// The definitions of the files which are not open, built when needed
+	schema: Option<Schema>,
+	/// Whether the editor can notify the server of changes to files
+	watch: bool,
+	/// Whether the editor has asked the server to exit
+	exit: bool,
+}
+
+impl Server {
+	/// Handles a message, returning the messages to send back to the editor
+	fn handle(&mut self, message: Value) -> Vec<Value> {
+		let Some(method) = message["method"].as_str() else {
+			// Responses to the requests of the server need no handling
+			return Vec::new();
+		};
+		let params = &message["params"];
+		let Some(id) = message.get("id").cloned() else {
+			return self.notify(method, params);
+		};
+		let result = match method {
+			"initialize" => self.initialize(params),
+			"shutdown" => Value::Null,
+			"textDocument/completion" => self.completion(),
+			"textDocument/hover" => self.hover(params),
+			"textDocument/definition" => self.definition(params),
+			_ => {
+				return vec![error(id, METHOD_NOT_FOUND, format!("Unsupported method '{method}'"))];
+			}
+		};
+		vec![response(id, result)]
+	}
+
+	/// Handles a notification, which does not receive a response
+	fn notify(&mut self, method: &str, params: &Value) -> Vec<Value> {
+		match method {
+			"initialized" => return self.register(),
+			"workspace/didChangeWatchedFiles" => return self.watched(params),
+			_ => {}
+		}
+		let Some(uri) = params["textDocument"]["uri"].as_str().and_then(|x| Url::parse(x).ok())
+		else {
+			self.exit = method == "exit";
+			return Vec::new();
+		};
+		match method {
+			"textDocument/didOpen" => {
+				let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+				self.documents.insert(uri.clone(), Document::new(text.to_owned()));
+				self.schema = None;
+			}
+			"textDocument/didChange" => {
+				// The server asks for the full text of the document on each change
+				let changes = params["contentChanges"].as_array();
+				let Some(text) = changes.and_then(|x| x.last()).and_then(|x| x["text"].as_str())
+				else {
+					return Vec::new();
+				};
+				self.documents.insert(uri.clone(), Document::new(text.to_owned()));
+			}
+			"textDocument/didClose" => {
+				self.documents.remove(&uri);
+				// The document may have been saved without the file being watched
+				self.load(&uri);
+				return vec![notification(
+					"textDocument/publishDiagnostics",
+					json!({ "uri": uri.as_str(), "diagnostics": [] }),
+				)];
+			}
+			_ => return Vec::new(),
+		}
+		vec![self.diagnostics(&uri)]
+	}
+
+	fn initialize(&mut self, params: &Value) -> Value {
+		let folders = params["workspaceFolders"].as_array().into_iter().flatten();
+		let uris = folders.map(|x| &x["uri"]).chain([&params["rootUri"]]);
+		for uri in uris.filter_map(Value::as_str) {
+			let Some(path) = Url::parse(uri).ok().and_then(|x| x.to_file_path().ok()) else {
+				continue;
+			};
+			if !self.roots.contains(&path) {
+				self.roots.push(path);
+			}
+		}
+		let watch = &params["capabilities"]["workspace"]["didChangeWatchedFiles"];
+		self.watch = watch["dynamicRegistration"].as_bool().unwrap_or_default();
+		self.scan();
+		json!({
+			"capabilities": {
+				"textDocumentSync": 1,
+				"completionProvider": { "triggerCharacters": [":", "$"] },
+				"hoverProvider": true,
+				"definitionProvider": true,
+			},
+			"serverInfo": { "name": "surreal", "version": env!("CARGO_PKG_VERSION") },
+		})
+	}
+
+	/// Asks the editor to notify the server when SurrealQL files change
+	fn register(&self) -> Vec<Value> {
+		if !self.watch {
+			return Vec::new();
+		}
+		vec![request(
+			"watch",
+			"client/registerCapability",
+			json!({ "registrations": [{
+				"id": "surql-files",
+				"method": "workspace/didChangeWatchedFiles",
+				"registerOptions": { "watchers": [{ "globPattern": "**/*.surql" }] },
+			}] }),
+		)]
+	}
+
+	/// Reads every SurrealQL file in the workspace
+	fn scan(&mut self) {
+		for root in self.roots.iter() {
+			let pattern = root.join("**").join("*.surql");
+			let Ok(paths) = glob(&pattern.to_string_lossy()) else {
+				continue;
+			};
+			for path in paths.flatten() {
+				let (Ok(uri), Ok(text)) =
+					(Url::from_file_path(&path), std::fs::read_to_string(&path))
+				else {
+					continue;
+				};
+				self.files.insert(uri, Document::new(text));
+			}
+		}
+		self.schema = None;
+	}
+
+	/// Reads a file of the workspace again, forgetting it if it was removed
+	fn load(&mut self, uri: &Url) {
+		self.schema = None;
+		let Ok(path) = uri.to_file_path() else {
+			return;
+		};
+		let inside = self.roots.iter().any(|x| path.starts_with(x));
+		let surql = path.extension().is_some_and(|x| x == "surql");
+		match std::fs::read_to_string(&path) {
+			Ok(text) if inside && surql => {
+				self.files.insert(uri.clone(), Document::new(text));
+			}
+			_ => {
+				self.files.remove(uri);
+			}
+		}
+	}
+
+	/// Updates the files which the editor reports have changed on disk, and
+	/// reports the problems of the open documents against the new definitions
+	fn watched(&mut self, params: &Value) -> Vec<Value> {
+		let changes = params["changes"].as_array().into_iter().flatten();
+		for uri in changes.filter_map(|x| Url::parse(x["uri"].as_str()?).ok()) {
+			self.load(&uri);
+		}
+		let uris = self.documents.keys().cloned().collect::<Vec<_>>();
+		uris.iter().map(|uri| self.diagnostics(uri)).collect()
+	}
+
+	/// Returns every SurrealQL document in the workspace, preferring the
+	/// text of open documents to the files on disk
+	fn workspace(&self) -> impl Iterator<Item = (&Url, &Document)> {
+		let files = self.files.iter().filter(|(uri, _)| !self.documents.contains_key(*uri));
+		files.chain(self.documents.iter())
+	}
+
+	/// Returns the functions and params defined in the workspace
+	fn symbols(&self) -> impl Iterator<Item = (&Url, &Symbol)> {
+		self.workspace().flat_map(|(uri, doc)| doc.symbols.iter().map(move |x| (uri, x)))
+	}
+
+	/// Returns the definitions of the workspace. Only the open documents are
+	/// parsed again, as the files on disk change far less often.
+	fn schema(&mut self) -> Schema {
+		let mut schema = match self.schema.take() {
+			Some(schema) => schema,
+			None => {
+				let mut schema = Schema::default();
+				for (uri, doc) in self.files.iter() {
+					if !self.documents.contains_key(uri) {
+						// Documents which do not parse report their own errors
+						schema.extend(&doc.text).ok();
+					}
+				}
+				schema
+			}
+		};
+		self.schema = Some(schema.clone());
+		for doc in self.documents.values() {
+			schema.extend(&doc.text).ok();
+		}
+		schema
+	}
+
+	fn diagnostics(&mut self, uri: &Url) -> Value {
+		let schema = self.schema();
+		let text = self.documents.get(uri).map(|x| x.text.as_str()).unwrap_or_default();
+		let diagnostics = lsp::diagnostics(text, &schema).unwrap_or_default();
+		let diagnostics = diagnostics
+			.into_iter()
+			.map(|x| {
+				// Extend empty ranges so that editors highlight something
+				let mut end = x.end;
+				if (end.line, end.column) == (x.start.line, x.start.column) {
+					end.column += 1;
+				}
+				json!({
+					"range": range(x.start..end),
+					"severity": 1,
+					"code": x.code,
+					"source": "surreal",
+					"message": x.message,
+				})
+			})
+			.collect::<Vec<_>>();
+		notification(
+			"textDocument/publishDiagnostics",
+			json!({ "uri": uri.as_str(), "diagnostics": diagnostics }),
+		)
+	}
+
+	fn completion(&self) -> Value {
+		// The kinds of completion items in the protocol
+		const FUNCTION: u8 = 3;
+		const VARIABLE: u8 = 6;
+		const KEYWORD: u8 = 14;
+		let mut items = Vec::new();
+		for keyword in lsp::keywords() {
+			items.push(json!({ "label": keyword, "kind": KEYWORD }));
+		}
+		for function in lsp::builtin_functions() {
+			items.push(json!({ "label": function, "kind": FUNCTION }));
+		}
+		for param in lsp::builtin_params() {
+			items.push(json!({ "label": format!("${param}"), "kind": VARIABLE }));
+		}
+		for (_, symbol) in self.symbols() {
+			let kind = match symbol.kind {
+				SymbolKind::Function => FUNCTION,
+				SymbolKind::Param => VARIABLE,
+			};
+			items.push(json!({ "label": symbol.name, "kind": kind, "detail": symbol.source }));
+		}
+		Value::from(items)
+	}
+
+	/// Returns the name of the function or param at the requested position
+	fn word(&self, params: &Value) -> Option<String> {
+		let uri = Url::parse(params["textDocument"]["uri"].as_str()?).ok()?;
+		let text = &self.documents.get(&uri)?.text;
+		let location = Location {
+			line: params["position"]["line"].as_u64()? as usize + 1,
+			column: params["position"]["character"].as_u64()? as usize + 1,
+		};
+		lsp::word_at(text, location)
+	}
+
+	fn hover(&self, params: &Value) -> Value {
+		let Some(word) = self.word(params) else {
+			return Value::Null;
+		};
+		let docs = lsp::builtin_docs(&word).or_else(|| {
+			let (_, symbol) = self.symbols().find(|(_, x)| x.name == word)?;
+			Some(format!("```surql\n{}\n```", symbol.source))
+		});
+		match docs {
+			Some(docs) => json!({ "contents": { "kind": "markdown", "value": docs } }),
+			None => Value::Null,
+		}
+	}
+
+	fn definition(&self, params: &Value) -> Value {
+		let Some(word) = self.word(params) else {
+			return Value::Null;
+		};
+		let locations = self
+			.symbols()
+			.filter(|(_, x)| x.name == word)
+			.map(|(uri, x)| json!({ "uri": uri.as_str(), "range": range(x.range.clone()) }))
+			.collect::<Vec<_>>();
+		Value::from(locations)
+	}
+}
@@ -0,0 +1,129 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use surrealdb::engine::any::connect;
+use surrealdb::method::{MigrationReport, MigrationState};
+
+use crate::cli::abstraction::DatabaseSelectionArguments;
+
+#[derive(Debug, Subcommand)]
+pub enum MigrateCommand {
+	#[command(about = "Apply the pending migrations")]
+	Up(ChangeArguments),
+	#[command(about = "Revert the most recent migration, or every migration after a version")]
+	Down(ChangeArguments),
+	#[command(about = "Show the state of each migration")]
+	Status(SourceArguments),
+}
+
+#[derive(Args, Debug)]
+pub struct SourceArguments {
+	#[arg(help = "Path to the directory containing the migration files")]
+	#[arg(short = 'd', long = "dir", default_value = "migrations")]
+	dir: String,
+	#[arg(help = "Embedded database endpoint to migrate, such as surrealkv://data")]
+	#[arg(short = 'e', long = "endpoint", visible_aliases = ["conn"])]
+	#[arg(value_parser = super::validator::endpoint_valid)]
+	endpoint: String,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+#[derive(Args, Debug)]
+pub struct ChangeArguments {
+	#[command(flatten)]
+	source: SourceArguments,
+	#[arg(help = "The version to migrate up to, or down to")]
+	#[arg(long)]
+	to: Option<String>,
+	#[arg(help = "Print the changes to the schema, without changing the database")]
+	#[arg(long)]
+	dry_run: bool,
+}
+
+pub async fn init(command: MigrateCommand) -> Result<()> {
+	let (source, change, down) = match command {
+		MigrateCommand::Up(args) => (args.source, Some((args.to, args.dry_run)), false),
+		MigrateCommand::Down(args) => (args.source, Some((args.to, args.dry_run)), true),
+		MigrateCommand::Status(args) => (args, None, false),
+	};
+	let SourceArguments {
+		dir,
+		endpoint,
+		sel: DatabaseSelectionArguments {
+			namespace,
+			database,
+		},
+	} = source;
+	// Migrations run directly against an embedded datastore
+	debug!("Connecting to the database engine without authentication");
+	let client = connect(endpoint).await?;
+	// Use the specified namespace / database
+	client.use_ns(namespace).use_db(database).await?;
+	// Run the migrations
+	let mut migrate = client.migrate(dir);
+	if down {
+		migrate = migrate.down();
+	}
+	let Some((to, dry_run)) = change else {
+		let report = migrate.status().await?;
+		for status in report.status {
+			let state = match status.state {
+				MigrationState::Applied => "applied",
+				MigrationState::Pending => "pending",
+				MigrationState::Modified => "modified",
+				MigrationState::Missing => "missing",
+				_ => "unknown",
+			};
+			match status.applied_at {
+				Some(at) => println!("{}_{} {state} {at}", status.version, status.name),
+				None => println!("{}_{} {state}", status.version, status.name),
+			}
+		}
+		return Ok(());
+	};
+	if let Some(to) = to {
+		migrate = migrate.to(to);
+	}
+	if dry_run {
+		migrate = migrate.dry_run();
+	}
+	let report = migrate.await?;
+	print_report(&report, dry_run);
+	// Everything OK
+	Ok(())
+}
+
+/// Prints the migrations which were run, and any changes to the schema
+fn print_report(report: &MigrationReport, dry_run: bool) {
+	let name = |version: &String| {
+		let status = report.status.iter().find(|x| &x.version == version);
+		match status {
+			Some(status) => format!("{}_{}", status.version, status.name),
+			None => version.clone(),
+		}
+	};
+	let (apply, revert) = match dry_run {
+		true => ("Would apply", "Would revert"),
+		false => ("Applied", "Reverted"),
+	};
+	if report.applied.is_empty() && report.reverted.is_empty() {
+		println!("No migrations to run");
+	}
+	for version in &report.applied {
+		println!("{apply} {}", name(version));
+	}
+	for version in &report.reverted {
+		println!("{revert} {}", name(version));
+	}
+	if let Some(diff) = &report.diff {
+		for added in &diff.added {
+			println!("+ {added}");
+		}
+		for removed in &diff.removed {
+			println!("- {removed}");
+		}
+		for changed in &diff.changed {
+			println!("~ {changed}");
+		}
+	}
+}
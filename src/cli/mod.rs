@@ -4,8 +4,11 @@ pub(crate) mod abstraction;
 mod config;
 mod export;
 mod fix;
+mod format;
 mod import;
 mod isready;
+mod lsp;
+mod migrate;
 mod ml;
 mod sql;
 mod start;
@@ -25,8 +28,11 @@ use clap::{Parser, Subcommand, ValueEnum};
 pub use config::CF;
 use export::ExportCommandArguments;
 use fix::FixCommandArguments;
+use format::FormatCommandArguments;
 use import::ImportCommandArguments;
 use isready::IsReadyCommandArguments;
+use lsp::LspCommandArguments;
+use migrate::MigrateCommand;
 use ml::MlCommand;
 use semver::Version;
 use sql::SqlCommandArguments;
@@ -173,6 +179,12 @@ enum Commands {
 	IsReady(IsReadyCommandArguments),
 	#[command(about = "Validate SurrealQL query files")]
 	Validate(ValidateCommandArguments),
+	#[command(about = "Format SurrealQL query files")]
+	Fmt(FormatCommandArguments),
+	#[command(about = "Start a SurrealQL language server for editors, over stdio")]
+	Lsp(LspCommandArguments),
+	#[command(subcommand, about = "Apply versioned schema migrations to an embedded database")]
+	Migrate(MigrateCommand),
 	#[command(about = "Fix database storage issues")]
 	Fix(FixCommandArguments),
 }
@@ -258,6 +270,9 @@ pub async fn init<F: TransactionBuilderFactory>() -> ExitCode {
 		Commands::Ml(args) => ml::init(args).await,
 		Commands::IsReady(args) => isready::init(args).await,
 		Commands::Validate(args) => validate::init(args).await,
+		Commands::Fmt(args) => format::init(args).await,
+		Commands::Lsp(args) => lsp::init(args).await,
+		Commands::Migrate(args) => migrate::init(args).await,
 		Commands::Fix(args) => fix::init::<F>(args).await,
 	};
 	// Save the flamegraph and profile
@@ -58,6 +58,13 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_NODE_MEMBERSHIP_CLEANUP_INTERVAL", long = "node-membership-cleanup-interval", value_parser = super::validator::duration)]
 	#[arg(default_value = "300s")]
 	node_membership_cleanup_interval: Duration,
+	#[arg(
+		help = "The interval at which to deliver live query notifications queued by other nodes",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_NODE_NOTIFICATIONS_INTERVAL", long = "node-notifications-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "100ms")]
+	node_notifications_interval: Duration,
 	#[arg(
 		help = "The interval at which to perform changefeed garbage collection",
 		help_heading = "Database"
@@ -160,6 +167,7 @@ pub async fn init<F: TransactionBuilderFactory>(
 		node_membership_refresh_interval,
 		node_membership_check_interval,
 		node_membership_cleanup_interval,
+		node_notifications_interval,
 		changefeed_gc_interval,
 		index_compaction_interval,
 		no_banner,
@@ -191,6 +199,7 @@ pub async fn init<F: TransactionBuilderFactory>(
 		.with_node_membership_refresh_interval(node_membership_refresh_interval)
 		.with_node_membership_check_interval(node_membership_check_interval)
 		.with_node_membership_cleanup_interval(node_membership_cleanup_interval)
+		.with_node_notifications_interval(node_notifications_interval)
 		.with_changefeed_gc_interval(changefeed_gc_interval)
 		.with_index_compaction_interval(index_compaction_interval);
 	// Configure the config
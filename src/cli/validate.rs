@@ -1,19 +1,46 @@
-use anyhow::{Result, bail};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
 use clap::Args;
+use futures_util::StreamExt;
 use glob::glob;
+use serde::Serialize;
+use surrealdb::engine::any::{self, connect};
 
+use crate::cli::abstraction::auth::{CredentialsBuilder, CredentialsLevel};
+use crate::cli::abstraction::{AuthArguments, LevelSelectionArguments};
 use crate::core::syn;
+use crate::core::syn::analyze::{self, Diagnostic, Schema};
 
 #[derive(Args, Debug)]
 pub struct ValidateCommandArguments {
 	#[arg(help = "Glob pattern for the files to validate")]
 	#[arg(default_value = "**/*.surql")]
 	patterns: Vec<String>,
+	#[arg(help = "Check the files against the schema in a SurrealQL file, or in a database \
+	              endpoint, and output the problems found as JSON")]
+	#[arg(long)]
+	schema: Option<String>,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	level: LevelSelectionArguments,
+}
+
+/// A problem found in a validated file
+#[derive(Serialize)]
+struct FileDiagnostic {
+	file: PathBuf,
+	#[serde(flatten)]
+	diagnostic: Diagnostic,
 }
 
 pub async fn init(args: ValidateCommandArguments) -> Result<()> {
 	let ValidateCommandArguments {
 		patterns,
+		schema,
+		auth,
+		level,
 	} = args;
 
 	let mut entries = vec![];
@@ -32,6 +59,11 @@ pub async fn init(args: ValidateCommandArguments) -> Result<()> {
 		entries.extend(pattern_entries.flatten());
 	}
 
+	if let Some(schema) = schema {
+		let schema = load_schema(&schema, auth, level).await?;
+		return analyze_entries(entries, &schema).await;
+	}
+
 	let mut has_entries = false;
 
 	for entry in entries {
@@ -60,3 +92,98 @@ pub async fn init(args: ValidateCommandArguments) -> Result<()> {
 
 	Ok(())
 }
+
+/// Checks each file against the schema, printing the problems found as JSON
+async fn analyze_entries(entries: Vec<PathBuf>, schema: &Schema) -> Result<()> {
+	if entries.is_empty() {
+		eprintln!("No files found");
+		bail!("No files found");
+	}
+
+	let mut diagnostics = vec![];
+
+	for entry in entries {
+		let file_content = tokio::fs::read_to_string(&entry).await?;
+		for diagnostic in analyze::analyze(&file_content, schema)? {
+			diagnostics.push(FileDiagnostic {
+				file: entry.clone(),
+				diagnostic,
+			});
+		}
+	}
+
+	println!("{}", serde_json::to_string_pretty(&diagnostics)?);
+
+	if !diagnostics.is_empty() {
+		bail!("Found {} problem(s)", diagnostics.len());
+	}
+
+	Ok(())
+}
+
+/// Loads the schema from a SurrealQL file, or exports it from a database
+async fn load_schema(
+	schema: &str,
+	AuthArguments {
+		username,
+		password,
+		token,
+		auth_level,
+	}: AuthArguments,
+	LevelSelectionArguments {
+		namespace,
+		database,
+	}: LevelSelectionArguments,
+) -> Result<Schema> {
+	if !schema.contains("://") {
+		let source = tokio::fs::read_to_string(schema)
+			.await
+			.with_context(|| format!("Unable to read the schema file '{schema}'"))?;
+		return Schema::parse(&source);
+	}
+
+	let (Some(namespace), Some(database)) = (namespace, database) else {
+		bail!("A namespace and database must be selected to load the schema from an endpoint");
+	};
+
+	let endpoint = schema.to_owned();
+	let is_local = any::__into_endpoint(&endpoint)?.parse_kind()?.is_local();
+	let client = if username.is_some() && password.is_some() && !is_local {
+		debug!("Connecting to the database engine with authentication");
+		let creds = CredentialsBuilder::default()
+			.with_username(username.as_deref())
+			.with_password(password.as_deref())
+			.with_namespace(namespace.as_str())
+			.with_database(database.as_str());
+
+		let client = connect(endpoint).await?;
+
+		debug!("Signing in to the database engine at '{:?}' level", auth_level);
+		match auth_level {
+			CredentialsLevel::Root => client.signin(creds.root()?).await?,
+			CredentialsLevel::Namespace => client.signin(creds.namespace()?).await?,
+			CredentialsLevel::Database => client.signin(creds.database()?).await?,
+		};
+
+		client
+	} else if token.is_some() && !is_local {
+		let client = connect(endpoint).await?;
+		client.authenticate(token.unwrap()).await?;
+
+		client
+	} else {
+		debug!("Connecting to the database engine without authentication");
+		connect(endpoint).await?
+	};
+
+	client.use_ns(namespace).use_db(database).await?;
+
+	// Export the definitions, without any records
+	let mut export = client.export(()).with_config().records(false).versions(false).await?;
+	let mut source = vec![];
+	while let Some(bytes) = export.next().await {
+		source.extend_from_slice(&bytes?);
+	}
+
+	Schema::parse(&String::from_utf8(source)?)
+}
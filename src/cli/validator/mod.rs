@@ -56,6 +56,7 @@ pub(crate) fn endpoint_valid(v: &str) -> Result<String, String> {
 		| "fdb"
 		| "mem"
 		| "rocksdb"
+		| "rocksdb+versioned"
 		| "surrealkv"
 		| "surrealkv+versioned"
 		| "file"
@@ -80,6 +80,11 @@ pub struct DbsCapabilities {
 	#[arg(env = "SURREAL_CAPS_ALLOW_SCRIPT", long, conflicts_with_all = ["allow_all", "deny_scripting"])]
 	allow_scripting: bool,
 
+	#[cfg(feature = "wasm-functions")]
+	#[arg(help = "Allow execution of WebAssembly functions")]
+	#[arg(env = "SURREAL_CAPS_ALLOW_WASM", long, conflicts_with_all = ["allow_all", "deny_wasm"])]
+	allow_wasm: bool,
+
 	#[arg(help = "Allow guest users to execute queries")]
 	#[arg(env = "SURREAL_CAPS_ALLOW_GUESTS", long, conflicts_with_all = ["allow_all", "deny_guests"])]
 	allow_guests: bool,
@@ -172,6 +177,11 @@ Targets must be in the form of <host>[:<port>], <ipv4|ipv6>[/<mask>]. For exampl
 	#[arg(env = "SURREAL_CAPS_DENY_SCRIPT", long, conflicts_with_all = ["deny_all", "allow_scripting"])]
 	deny_scripting: bool,
 
+	#[cfg(feature = "wasm-functions")]
+	#[arg(help = "Deny execution of WebAssembly functions")]
+	#[arg(env = "SURREAL_CAPS_DENY_WASM", long, conflicts_with_all = ["deny_all", "allow_wasm"])]
+	deny_wasm: bool,
+
 	#[arg(help = "Deny guest users to execute queries")]
 	#[arg(env = "SURREAL_CAPS_DENY_GUESTS", long, conflicts_with_all = ["deny_all", "allow_guests"])]
 	deny_guests: bool,
@@ -266,6 +276,19 @@ impl DbsCapabilities {
 		false
 	}
 
+	#[cfg(feature = "wasm-functions")]
+	fn get_wasm(&self) -> bool {
+		// Even if there was a global deny, we allow if there is a specific allow for
+		// WebAssembly functions Even if there is a global allow, we deny if there is
+		// a specific deny for WebAssembly functions
+		self.allow_wasm || (self.allow_all && !self.deny_wasm)
+	}
+
+	#[cfg(not(feature = "wasm-functions"))]
+	fn get_wasm(&self) -> bool {
+		false
+	}
+
 	fn get_allow_guests(&self) -> bool {
 		// Even if there was a global deny, we allow if there is a specific allow for
 		// guests Even if there is a global allow, we deny if there is a specific deny
@@ -540,6 +563,7 @@ impl DbsCapabilities {
 fn merge_capabilities(initial: Capabilities, caps: DbsCapabilities) -> Capabilities {
 	initial
 		.with_scripting(caps.get_scripting())
+		.with_wasm(caps.get_wasm())
 		.with_guest_access(caps.get_allow_guests())
 		.with_functions(caps.get_allow_funcs())
 		.without_functions(caps.get_deny_funcs())
@@ -1060,6 +1084,8 @@ mod tests {
 		let caps = DbsCapabilities {
 			allow_all: false,
 			allow_scripting: false,
+			#[cfg(feature = "wasm-functions")]
+			allow_wasm: false,
 			allow_guests: false,
 			allow_funcs: None,
 			allow_experimental: Some(Targets::All),
@@ -1069,6 +1095,8 @@ mod tests {
 			allow_http: None,
 			deny_all: false,
 			deny_scripting: false,
+			#[cfg(feature = "wasm-functions")]
+			deny_wasm: false,
 			deny_guests: false,
 			deny_funcs: None,
 			deny_experimental: None,
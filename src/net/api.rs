@@ -1,13 +1,23 @@
+use std::convert::Infallible;
 use std::fmt::Display;
+use std::sync::Arc;
 
 use axum::body::Body;
-use axum::extract::{DefaultBodyLimit, Path, Query};
+use axum::extract::{DefaultBodyLimit, Path, Query, State};
 use axum::http::{HeaderMap, Method};
-use axum::response::IntoResponse;
+use axum::middleware::map_response;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
 use axum::routing::any;
 use axum::{Extension, Router};
-use futures::StreamExt;
-use http::header::CONTENT_TYPE;
+use futures::{Stream, StreamExt};
+use http::header::{
+	ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_REQUEST_METHOD, CONTENT_ENCODING, CONTENT_TYPE,
+};
+use http::{HeaderValue, StatusCode};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tower_http::compression::{CompressionLayer, Predicate};
 use tower_http::limit::RequestBodyLimitLayer;
 
 use super::AppState;
@@ -15,28 +25,78 @@ use super::error::ResponseError;
 use super::params::Params;
 use crate::cnf::HTTP_MAX_API_BODY_SIZE;
 use crate::core::api::err::ApiError;
-use crate::core::api::response::ResponseInstruction;
+use crate::core::api::response::{ApiStream, ResponseInstruction};
 use crate::core::catalog::ApiMethod;
-use crate::core::dbs::Session;
 use crate::core::dbs::capabilities::{ExperimentalTarget, RouteTarget};
-use crate::core::rpc::RpcError;
+use crate::core::dbs::{Notification, Session, Variables};
+use crate::core::kvs::Datastore;
 use crate::core::rpc::format::{Format, cbor, json, revision};
+use crate::core::rpc::{Data, RpcError};
 use crate::core::val::Value;
 use crate::net::error::Error as NetError;
+use crate::rpc::RpcState;
 
-pub(super) fn router<S>() -> Router<S>
-where
-	S: Clone + Send + Sync + 'static,
-{
+/// The number of notifications which are buffered for each API event stream.
+const EVENT_STREAM_BUFFER: usize = 64;
+
+pub(super) fn router() -> Router<Arc<RpcState>> {
 	Router::new()
 		.route("/api/{ns}/{db}/{*path}", any(handler))
 		.route_layer(DefaultBodyLimit::disable())
 		.layer(RequestBodyLimitLayer::new(*HTTP_MAX_API_BODY_SIZE))
+		.layer(CompressionLayer::new().compress_when(Compressible))
+		.layer(map_response(default_cors))
+}
+
+/// Marks whether an API response should be compressed, as configured by
+/// the `api::res::compress` middleware.
+#[derive(Clone, Copy, Debug)]
+struct Compress(bool);
+
+/// Compresses API responses which were marked for compression.
+#[derive(Clone, Copy, Debug)]
+struct Compressible;
+
+impl Predicate for Compressible {
+	fn should_compress<B>(&self, response: &http::Response<B>) -> bool
+	where
+		B: http_body::Body,
+	{
+		matches!(response.extensions().get::<Compress>(), Some(Compress(true)))
+	}
+}
+
+/// API routes apply their own CORS policies. Errors which occur before an
+/// endpoint's policy is known allow any origin, like all other routes.
+async fn default_cors(mut res: Response) -> Response {
+	if res.status().is_client_error() || res.status().is_server_error() {
+		res.headers_mut()
+			.entry(ACCESS_CONTROL_ALLOW_ORIGIN)
+			.or_insert(HeaderValue::from_static("*"));
+	}
+	res
+}
+
+/// Describes the APIs defined on a database, for sessions which are allowed
+/// to view them. This is served at `openapi.json` unless an API has been
+/// defined at that path.
+async fn openapi(
+	ds: &Datastore,
+	session: &Session,
+	ns: &str,
+	db: &str,
+) -> Result<Response, ResponseError> {
+	// Generate the document for the defined APIs
+	let doc = ds.api_openapi_document(session, ns, db).await.map_err(ResponseError)?;
+	let body = json::encode(doc).map_err(|_| RpcError::ParseError)?;
+
+	Ok(([(CONTENT_TYPE, "application/json")], body).into_response())
 }
 
 async fn handler(
 	Extension(state): Extension<AppState>,
 	Extension(session): Extension<Session>,
+	State(rpc_state): State<Arc<RpcState>>,
 	Path((ns, db, path)): Path<(String, String, String)>,
 	headers: HeaderMap,
 	Query(query): Query<Params>,
@@ -60,6 +120,28 @@ async fn handler(
 		return Err(NetError::ForbiddenRoute(RouteTarget::Api.to_string()).into());
 	}
 
+	// Respond to CORS preflight requests for the requested method
+	if method == Method::OPTIONS {
+		let Some(method) = headers
+			.get(ACCESS_CONTROL_REQUEST_METHOD)
+			.and_then(|v| v.to_str().ok())
+			.and_then(|v| ApiMethod::try_from(&Value::from(v)).ok())
+		else {
+			return Err(NetError::NotFound(url).into());
+		};
+
+		let res = ds
+			.invoke_api_preflight(&ns, &db, &path, &session, method, headers)
+			.await
+			.map_err(ResponseError)?;
+
+		let Some(res) = res else {
+			return Err(NetError::NotFound(url).into());
+		};
+
+		return Ok((res.status, res.headers).into_response());
+	}
+
 	let method = match method {
 		Method::DELETE => ApiMethod::Delete,
 		Method::GET => ApiMethod::Get,
@@ -90,9 +172,78 @@ async fn handler(
 		.map_err(ResponseError)?;
 
 	let Some((mut res, res_instruction)) = res else {
+		// Describe the defined APIs, unless an API exists at this path
+		if method == ApiMethod::Get && path == "openapi.json" {
+			return openapi(ds, &session, &ns, &db).await;
+		}
 		return Err(NetError::NotFound(url).into());
 	};
 
+	// Stream the response body, when requested by the action
+	if let Some(stream) = res.stream.take() {
+		let response = match stream {
+			ApiStream::Ndjson(values) => {
+				let values = match values {
+					Value::Array(values) => values.0,
+					value => vec![value],
+				};
+				let rows = futures::stream::iter(values).map(Ok);
+				ndjson(res.status, res.headers, rows)
+			}
+			ApiStream::Rows(rows) => ndjson(res.status, res.headers, rows),
+			ApiStream::Bytes(chunks) => {
+				res.headers
+					.entry(CONTENT_TYPE)
+					.or_insert(HeaderValue::from_static("application/octet-stream"));
+				let chunks = chunks.map(|x| x.map_err(std::io::Error::other));
+				(res.status, res.headers, Body::from_stream(chunks)).into_response()
+			}
+			ApiStream::Events(table) => {
+				// Start a live query on the table, with the permissions of the
+				// client, which only runs while the client is connected
+				let vars = Variables::from(map! {
+					String::from("table") => Value::Table(table),
+				});
+				let mut res_live = ds
+					.execute("LIVE SELECT * FROM $table", &session.with_rt(true), Some(vars))
+					.await
+					.map_err(ResponseError)?;
+				let id: uuid::Uuid = match res_live.pop().map(|x| x.result) {
+					Some(Ok(Value::Uuid(id))) => id.into(),
+					Some(Err(err)) => return Err(ResponseError(err)),
+					_ => {
+						return Err(ApiError::Unreachable(
+							"Expected the id of the live query".into(),
+						)
+						.into());
+					}
+				};
+				let (sender, receiver) = mpsc::channel(EVENT_STREAM_BUFFER);
+				rpc_state.event_streams.write().await.insert(id, sender);
+				// Remove the live query when the client disconnects
+				let guard = EventStream {
+					ds: state.datastore.clone(),
+					state: rpc_state,
+					id,
+				};
+				let events = ReceiverStream::new(receiver).map(move |notification| {
+					let _ = &guard;
+					Ok::<_, Infallible>(event(notification))
+				});
+				let mut response =
+					Sse::new(events).keep_alive(KeepAlive::default()).into_response();
+				response.headers_mut().extend(res.headers);
+				response
+			}
+			ApiStream::File(_) => {
+				return Err(
+					ApiError::Unreachable("Expected the file to have been opened".into()).into()
+				);
+			}
+		};
+		return Ok(compress(response, res.compress));
+	}
+
 	let res_body: Vec<u8> = if let Some(body) = res.body {
 		match res_instruction {
 			ResponseInstruction::Raw => {
@@ -161,5 +312,66 @@ async fn handler(
 		Vec::new()
 	};
 
-	Ok((res.status, res.headers, res_body))
+	let response = (res.status, res.headers, res_body).into_response();
+
+	Ok(compress(response, res.compress))
+}
+
+/// Marks a response for compression, as configured by the endpoint.
+fn compress(mut response: Response, compress: Option<bool>) -> Response {
+	match compress {
+		Some(true) => {
+			response.extensions_mut().insert(Compress(true));
+		}
+		// Prevent the server-wide compression layer from compressing the body
+		Some(false) => {
+			response.headers_mut().insert(CONTENT_ENCODING, HeaderValue::from_static("identity"));
+		}
+		None => (),
+	}
+	response
+}
+
+/// Sends each value as a separate line of JSON.
+fn ndjson<S>(status: StatusCode, mut headers: HeaderMap, rows: S) -> Response
+where
+	S: Stream<Item = anyhow::Result<Value>> + Send + 'static,
+{
+	headers.entry(CONTENT_TYPE).or_insert(HeaderValue::from_static("application/x-ndjson"));
+	let lines = rows.map(|v| {
+		let mut line =
+			json::encode(v.map_err(RpcError::InternalError)?).map_err(|_| RpcError::ParseError)?;
+		line.push(b'\n');
+		Ok::<_, RpcError>(line)
+	});
+	(status, headers, Body::from_stream(lines)).into_response()
+}
+
+/// Converts a live query notification into a server-sent event.
+fn event(notification: Notification) -> Event {
+	let name = notification.action.to_string();
+	let data = json::encode(Data::Live(notification).into_value()).unwrap_or_default();
+	Event::default().event(name).data(String::from_utf8_lossy(&data))
+}
+
+/// Feeds an API event stream with the notifications of a live query, and
+/// kills the live query once the client disconnects.
+struct EventStream {
+	ds: Arc<Datastore>,
+	state: Arc<RpcState>,
+	id: uuid::Uuid,
+}
+
+impl Drop for EventStream {
+	fn drop(&mut self) {
+		let ds = self.ds.clone();
+		let state = self.state.clone();
+		let id = self.id;
+		tokio::spawn(async move {
+			state.event_streams.write().await.remove(&id);
+			if let Err(err) = ds.delete_queries(vec![id]).await {
+				error!("Error removing the live query of an API event stream: {err}");
+			}
+		});
+	}
 }
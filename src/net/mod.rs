@@ -16,6 +16,7 @@ mod rpc;
 mod signals;
 mod signin;
 mod signup;
+mod snapshot;
 mod sql;
 mod sync;
 mod tracer;
@@ -150,23 +151,22 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<()> {
 		.layer(SetSensitiveResponseHeadersLayer::from_shared(headers))
 		.layer(AsyncRequireAuthorizationLayer::new(auth::SurrealAuth))
 		.layer(headers::add_server_header(!opt.no_identification_headers))
-		.layer(headers::add_version_header(!opt.no_identification_headers))
-		// Apply CORS headers to relevant responses
-		.layer(
-			CorsLayer::new()
-				.allow_methods([
-					http::Method::GET,
-					http::Method::PUT,
-					http::Method::POST,
-					http::Method::PATCH,
-					http::Method::DELETE,
-					http::Method::OPTIONS,
-				])
-				.allow_headers(allow_header)
-				// allow requests from any origin
-				.allow_origin(Any)
-				.max_age(Duration::from_secs(86400)),
-		);
+		.layer(headers::add_version_header(!opt.no_identification_headers));
+
+	// Apply CORS headers to relevant responses
+	let cors = CorsLayer::new()
+		.allow_methods([
+			http::Method::GET,
+			http::Method::PUT,
+			http::Method::POST,
+			http::Method::PATCH,
+			http::Method::DELETE,
+			http::Method::OPTIONS,
+		])
+		.allow_headers(allow_header)
+		// allow requests from any origin
+		.allow_origin(Any)
+		.max_age(Duration::from_secs(86400));
 
 	let axum_app = Router::<Arc<RpcState>>::new()
 		// Redirect until we provide a UI
@@ -181,8 +181,11 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<()> {
 		.merge(sql::router())
 		.merge(signin::router())
 		.merge(signup::router())
+		.merge(snapshot::router())
 		.merge(key::router())
 		.merge(ml::router())
+		.layer(cors)
+		// Custom API routes apply their own CORS policies
 		.merge(api::router());
 	//.merge(gql::router(ds.clone()));
 
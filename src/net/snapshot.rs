@@ -0,0 +1,39 @@
+use axum::response::IntoResponse;
+use axum::routing::post;
+use axum::{Extension, Router};
+
+use super::AppState;
+use super::error::ResponseError;
+use crate::core::dbs::Session;
+use crate::core::dbs::capabilities::RouteTarget;
+use crate::core::iam::Action::Edit;
+use crate::core::iam::Level;
+use crate::core::iam::ResourceKind::Any;
+use crate::net::error::Error as NetError;
+
+pub(super) fn router<S>() -> Router<S>
+where
+	S: Clone + Send + Sync + 'static,
+{
+	Router::new().route("/snapshot", post(handler))
+}
+
+async fn handler(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+) -> Result<impl IntoResponse, ResponseError> {
+	// Get the datastore reference
+	let db = &state.datastore;
+	// Check if capabilities allow querying the requested HTTP route
+	if !db.allows_http_route(&RouteTarget::Snapshot) {
+		warn!(
+			"Capabilities denied HTTP route request attempt, target: '{}'",
+			&RouteTarget::Snapshot
+		);
+		return Err(NetError::ForbiddenRoute(RouteTarget::Snapshot.to_string()).into());
+	}
+	// Snapshots can only be written by root users
+	db.check(&session, Edit, Any.on_level(Level::Root)).map_err(ResponseError)?;
+	// Write a snapshot of the datastore
+	db.snapshot().await.map_err(ResponseError)
+}
@@ -10,11 +10,12 @@ use std::time::Duration;
 
 use futures::stream::FuturesUnordered;
 use opentelemetry::Context as TelemetryContext;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, mpsc};
 use tokio_stream::StreamExt;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+use crate::core::dbs::Notification;
 use crate::core::kvs::Datastore;
 use crate::core::rpc::Data;
 use crate::rpc::websocket::Websocket;
@@ -27,12 +28,16 @@ type WebSocket = Arc<Websocket>;
 type WebSockets = RwLock<HashMap<Uuid, WebSocket>>;
 /// Mapping of LIVE Query ID to WebSocket ID
 type LiveQueries = RwLock<HashMap<Uuid, Uuid>>;
+/// Mapping of LIVE Query ID to API event stream
+type EventStreams = RwLock<HashMap<Uuid, mpsc::Sender<Notification>>>;
 
 pub struct RpcState {
 	/// Stores the currently connected WebSockets
 	pub web_sockets: WebSockets,
 	/// Stores the currently initiated LIVE queries
 	pub live_queries: LiveQueries,
+	/// Stores the LIVE queries which feed API event streams
+	pub event_streams: EventStreams,
 }
 
 impl RpcState {
@@ -40,6 +45,7 @@ impl RpcState {
 		RpcState {
 			web_sockets: WebSockets::default(),
 			live_queries: LiveQueries::default(),
+			event_streams: EventStreams::default(),
 		}
 	}
 }
@@ -95,6 +101,15 @@ pub(crate) async fn notifications(
 							// Pus the future to the pipeline
 							futures.push(future);
 						}
+					} else {
+						// Get the API event stream for this notification
+						let sender = {
+							state.event_streams.read().await.get(id).cloned()
+						};
+						// Slow clients miss notifications instead of blocking delivery
+						if sender.is_some_and(|x| x.try_send(notification).is_err()) {
+							trace!("Dropped a notification for an API event stream");
+						}
 					}
 				},
 			}
@@ -0,0 +1,59 @@
+use std::sync::LazyLock;
+use std::sync::atomic::Ordering;
+
+use opentelemetry::global;
+use opentelemetry::metrics::{Meter, ObservableCounter};
+use surrealdb_core::expr::INFERENCE;
+
+static METER: LazyLock<Meter> = LazyLock::new(|| global::meter("surrealdb.ml"));
+
+pub static ML_INFERENCE_COUNT: LazyLock<ObservableCounter<u64>> = LazyLock::new(|| {
+	METER
+		.u64_observable_counter("ml.inference.count")
+		.with_description("The total number of machine learning model computations.")
+		.with_callback(|o| o.observe(INFERENCE.calls.load(Ordering::Relaxed), &[]))
+		.init()
+});
+
+pub static ML_INFERENCE_ROWS: LazyLock<ObservableCounter<u64>> = LazyLock::new(|| {
+	METER
+		.u64_observable_counter("ml.inference.rows")
+		.with_description("The total number of input rows passed to machine learning models.")
+		.with_callback(|o| o.observe(INFERENCE.rows.load(Ordering::Relaxed), &[]))
+		.init()
+});
+
+pub static ML_INFERENCE_TIME: LazyLock<ObservableCounter<u64>> = LazyLock::new(|| {
+	METER
+		.u64_observable_counter("ml.inference.time")
+		.with_description("The total time spent computing machine learning models.")
+		.with_unit("us")
+		.with_callback(|o| o.observe(INFERENCE.micros.load(Ordering::Relaxed), &[]))
+		.init()
+});
+
+pub static ML_MODEL_CACHE_HITS: LazyLock<ObservableCounter<u64>> = LazyLock::new(|| {
+	METER
+		.u64_observable_counter("ml.model.cache.hits")
+		.with_description("The number of model computations which used a cached parsed model.")
+		.with_callback(|o| o.observe(INFERENCE.cache_hits.load(Ordering::Relaxed), &[]))
+		.init()
+});
+
+pub static ML_MODEL_CACHE_MISSES: LazyLock<ObservableCounter<u64>> = LazyLock::new(|| {
+	METER
+		.u64_observable_counter("ml.model.cache.misses")
+		.with_description("The number of model computations which had to parse the model file.")
+		.with_callback(|o| o.observe(INFERENCE.cache_misses.load(Ordering::Relaxed), &[]))
+		.init()
+});
+
+/// Registers the observable machine learning inference metrics, which
+/// are read from the datastore each time that the metrics are exported.
+pub fn register() {
+	LazyLock::force(&ML_INFERENCE_COUNT);
+	LazyLock::force(&ML_INFERENCE_ROWS);
+	LazyLock::force(&ML_INFERENCE_TIME);
+	LazyLock::force(&ML_MODEL_CACHE_HITS);
+	LazyLock::force(&ML_MODEL_CACHE_MISSES);
+}
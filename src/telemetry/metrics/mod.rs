@@ -1,4 +1,5 @@
 pub mod http;
+pub mod ml;
 pub mod ws;
 
 use opentelemetry::metrics::MetricsError;
@@ -193,6 +193,8 @@ impl Builder {
 		// Setup the metrics layer
 		if let Some(provider) = metrics::init()? {
 			global::set_meter_provider(provider);
+			// Register the observable metrics
+			metrics::ml::register();
 		}
 		// Create a non-blocking stdout log destination
 		let (stdout, stdout_guard) = NonBlockingBuilder::default()
@@ -1147,6 +1147,164 @@ mod cli_integration {
 		assert!(common::run_in_dir("validate", &temp_dir).output().is_err());
 	}
 
+	#[test]
+	fn validate_against_schema() {
+		let temp_dir = assert_fs::TempDir::new().unwrap();
+
+		let schema_file = temp_dir.child("schema.txt");
+		schema_file
+			.write_str("DEFINE TABLE person SCHEMAFULL; DEFINE FIELD name ON person TYPE string;")
+			.unwrap();
+
+		let statement_file = temp_dir.child("statement.surql");
+		statement_file.write_str("CREATE person SET name = 'Tobie';").unwrap();
+
+		let output =
+			common::run_in_dir("validate --schema schema.txt", &temp_dir).output().unwrap();
+		assert!(output.contains("[]"), "{output}");
+
+		statement_file.write_str("CREATE person SET name = 'Tobie', email = '';").unwrap();
+
+		assert!(common::run_in_dir("validate --schema schema.txt", &temp_dir).output().is_err());
+	}
+
+	#[test]
+	fn migrate_up_status_and_down() {
+		let temp_dir = assert_fs::TempDir::new().unwrap();
+
+		let migrations = temp_dir.child("migrations");
+		migrations
+			.child("0001_create_person.up.surql")
+			.write_str("DEFINE TABLE person SCHEMAFULL;")
+			.unwrap();
+		migrations
+			.child("0001_create_person.down.surql")
+			.write_str("REMOVE TABLE person;")
+			.unwrap();
+		migrations
+			.child("0002_person_name.surql")
+			.write_str("DEFINE FIELD name ON person TYPE string;")
+			.unwrap();
+
+		let conn = "--endpoint surrealkv://data --ns N --db D";
+
+		let output = common::run_in_dir(&format!("migrate up --dry-run {conn}"), &temp_dir)
+			.output()
+			.unwrap();
+		assert!(output.contains("Would apply 0001_create_person"), "{output}");
+		assert!(output.contains("+ tables/person"), "{output}");
+
+		let output = common::run_in_dir(&format!("migrate up --to 0001 {conn}"), &temp_dir)
+			.output()
+			.unwrap();
+		assert!(output.contains("Applied 0001_create_person"), "{output}");
+		assert!(!output.contains("0002_person_name"), "{output}");
+
+		let output =
+			common::run_in_dir(&format!("migrate status {conn}"), &temp_dir).output().unwrap();
+		assert!(output.contains("0001_create_person applied"), "{output}");
+		assert!(output.contains("0002_person_name pending"), "{output}");
+
+		common::run_in_dir(&format!("migrate up {conn}"), &temp_dir).output().unwrap();
+
+		// The second migration has no down script
+		assert!(common::run_in_dir(&format!("migrate down {conn}"), &temp_dir).output().is_err());
+
+		migrations.child("0002_person_name.surql").write_str("").unwrap();
+		// The second migration has been modified since it was applied
+		assert!(common::run_in_dir(&format!("migrate down {conn}"), &temp_dir).output().is_err());
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn lsp_serves_a_workspace() {
+		let temp_dir = assert_fs::TempDir::new().unwrap();
+
+		temp_dir
+			.child("functions.surql")
+			.write_str("DEFINE FUNCTION fn::greet() { RETURN 'Hello'; };")
+			.unwrap();
+
+		let root = format!("file://{}", temp_dir.path().display());
+		let uri = format!("{root}/query.surql");
+		let messages = [
+			json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {
+				"rootUri": root,
+				"capabilities": { "workspace": { "didChangeWatchedFiles": { "dynamicRegistration": true } } }
+			} }),
+			json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+			json!({ "jsonrpc": "2.0", "id": "watch", "result": null }),
+			json!({ "jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+				"textDocument": { "uri": uri, "languageId": "surrealql", "version": 1, "text": "RETURN fn::greet();" }
+			} }),
+			json!({ "jsonrpc": "2.0", "id": 2, "method": "textDocument/definition", "params": {
+				"textDocument": { "uri": uri }, "position": { "line": 0, "character": 10 }
+			} }),
+			json!({ "jsonrpc": "2.0", "id": 3, "method": "textDocument/hover", "params": {
+				"textDocument": { "uri": uri }, "position": { "line": 0, "character": 10 }
+			} }),
+			json!({ "jsonrpc": "2.0", "method": "textDocument/didChange", "params": {
+				"textDocument": { "uri": uri, "version": 2 },
+				"contentChanges": [{ "text": "RETURN string::len(1, 2);" }]
+			} }),
+			json!({ "jsonrpc": "2.0", "method": "workspace/didChangeWatchedFiles", "params": {
+				"changes": [{ "uri": format!("{root}/functions.surql"), "type": 2 }]
+			} }),
+			json!({ "jsonrpc": "2.0", "id": 4, "method": "shutdown" }),
+			json!({ "jsonrpc": "2.0", "method": "exit" }),
+		];
+		let input = messages
+			.iter()
+			.map(|x| {
+				let body = x.to_string();
+				format!("Content-Length: {}\r\n\r\n{body}", body.len())
+			})
+			.collect::<String>();
+
+		let output = common::run_in_dir("lsp --stdio", &temp_dir).input(&input).output().unwrap();
+		assert!(output.contains("\"definitionProvider\":true"), "{output}");
+		assert!(output.contains("functions.surql"), "{output}");
+		assert!(output.contains("RETURN 'Hello'"), "{output}");
+		assert!(output.contains("\"code\":\"invalid-arguments\""), "{output}");
+		// The server watches the files of the workspace, rather than reading them on each request
+		assert!(output.contains("client/registerCapability"), "{output}");
+		assert!(!output.contains("\"error\""), "{output}");
+	}
+
+	#[test]
+	fn fmt_rewrites_surql_files() {
+		let temp_dir = assert_fs::TempDir::new().unwrap();
+
+		let statement_file = temp_dir.child("statement.surql");
+
+		statement_file.touch().unwrap();
+		statement_file.write_str("-- Create a thing\ncreate thing:success").unwrap();
+
+		assert!(common::run_in_dir("fmt --check", &temp_dir).output().is_err());
+		common::run_in_dir("fmt", &temp_dir).output().unwrap();
+		assert_eq!(
+			std::fs::read_to_string(statement_file.path()).unwrap(),
+			"-- Create a thing\nCREATE thing:success;\n"
+		);
+		common::run_in_dir("fmt --check", &temp_dir).output().unwrap();
+	}
+
+	#[test]
+	fn fmt_failed_due_to_invalid_surql_files_syntax() {
+		let temp_dir = assert_fs::TempDir::new().unwrap();
+
+		let statement_file = temp_dir.child("statement.surql");
+
+		statement_file.touch().unwrap();
+		statement_file.write_str("CREATE $thing WHERE value = '';").unwrap();
+
+		assert!(common::run_in_dir("fmt", &temp_dir).output().is_err());
+		assert_eq!(
+			std::fs::read_to_string(statement_file.path()).unwrap(),
+			"CREATE $thing WHERE value = '';"
+		);
+	}
+
 	#[cfg(unix)]
 	#[test(tokio::test)]
 	async fn test_server_graceful_shutdown() {
@@ -305,6 +305,95 @@ mod graphql_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn masks() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_gql().await.unwrap();
+		let gql_url = &format!("http://{addr}/graphql");
+		let sql_url = &format!("http://{addr}/sql");
+		let signup_url = &format!("http://{addr}/signup");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// add schema and data
+		{
+			let res = client
+				.post(sql_url)
+				.basic_auth(USER, Some(PASS))
+				.body(
+					r#"
+					DEFINE CONFIG GRAPHQL AUTO;
+					DEFINE ACCESS user ON DATABASE TYPE RECORD
+					SIGNUP ( CREATE user SET email = $email, pass = crypto::argon2::generate($pass) )
+					SIGNIN ( SELECT * FROM user WHERE email = $email AND crypto::argon2::compare(pass, $pass) )
+					DURATION FOR SESSION 60s, FOR TOKEN 1d;
+
+                    DEFINE TABLE foo SCHEMAFUL PERMISSIONS FULL;
+                    DEFINE FIELD card ON foo TYPE string MASK "****" + string::slice($value, 12) PERMISSIONS FOR select NONE;
+                    CREATE foo:1 set card = "4111222233334444";
+                "#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// check root sees the original value
+		{
+			let res = client
+				.post(gql_url)
+				.basic_auth(USER, Some(PASS))
+				.body(json!({"query": r#"query{foo{id, card}}"#}).to_string())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let expected = json!({"data":{"foo":[{"id":"foo:1","card":"4111222233334444"}]}});
+			assert_eq!(expected, body);
+		}
+
+		// check record users see the masked value
+		{
+			let req_body = serde_json::to_string(
+				json!({
+					"ns": ns,
+					"db": db,
+					"ac": "user",
+					"email": "user@email.com",
+					"pass": "pass",
+				})
+				.as_object()
+				.unwrap(),
+			)
+			.unwrap();
+
+			let res = client.post(signup_url).body(req_body).send().await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			let token = body["token"].as_str().unwrap();
+
+			let res = client
+				.post(gql_url)
+				.bearer_auth(token)
+				.body(json!({"query": r#"query{foo{id, card}}"#}).to_string())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let expected = json!({"data":{"foo":[{"id":"foo:1","card":"****4444"}]}});
+			assert_eq!(expected, body);
+		}
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn config() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_gql_without_auth().await.unwrap();
@@ -33,6 +33,13 @@ mod ml_integration {
 		time: String,
 	}
 
+	#[derive(Serialize, Deserialize, Debug)]
+	struct BatchData {
+		result: Vec<Vec<f64>>,
+		status: String,
+		time: String,
+	}
+
 	struct LockHandle;
 
 	impl LockHandle {
@@ -289,4 +296,50 @@ mod ml_integration {
 		}
 		Ok(())
 	}
+
+	#[test(tokio::test)]
+	async fn batched_compute() -> Result<(), Box<dyn std::error::Error>> {
+		let _lock = LockHandle::acquire_lock();
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+
+		upload_file(&addr, &ns, &db).await?;
+
+		// Prepare HTTP client
+		let mut headers = reqwest::header::HeaderMap::new();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// perform a batched buffered and raw computation with the cached model
+		{
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(common::USER, Some(common::PASS))
+				.body(
+					r#"
+					ml::Prediction<0.0.1>([{squarefoot: 500.0, num_floors: 1.0}, {squarefoot: 500.0, num_floors: 1.0}]);
+					ml::Prediction<0.0.1>([[1.0, 1.0], [1.0, 1.0]]);
+					"#,
+				)
+				.send()
+				.await?;
+			assert!(res.status().is_success(), "body: {}", res.text().await?);
+			let body = res.text().await?;
+			let deserialized_data: Vec<BatchData> = serde_json::from_str(&body)?;
+			assert_eq!(deserialized_data[0].result.len(), 2);
+			assert_eq!(deserialized_data[0].result[0][0], 177206.21875);
+			assert_eq!(deserialized_data[0].result[1][0], 177206.21875);
+			assert_eq!(deserialized_data[1].result.len(), 2);
+			assert_eq!(deserialized_data[1].result[0][0], 0.9998061656951904);
+			assert_eq!(deserialized_data[1].result[1][0], 0.9998061656951904);
+		}
+		Ok(())
+	}
 }